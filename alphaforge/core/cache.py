@@ -4,9 +4,10 @@ Python fallback implementation for cache system when Rust components are not ava
 This provides the same API but with reduced performance compared to the Rust implementation.
 """
 
-from typing import Dict, Any, Optional, List, TypeVar, Generic
+from typing import Dict, Any, Optional, List, Tuple, TypeVar, Generic
 from dataclasses import dataclass
 from threading import RLock
+import sys
 import time
 import json
 from collections import OrderedDict
@@ -106,9 +107,12 @@ class Cache(Generic[T]):
     
     def put(self, key: str, value: T) -> bool:
         """Put value into cache."""
+        return self.put_with_ttl(key, value, self.config.ttl_seconds)
+    
+    def put_with_ttl(self, key: str, value: T, ttl_seconds: Optional[int] = None) -> bool:
+        """Put value into cache, expiring after ttl_seconds rather than the configured TTL, or never when None."""
         with self._lock:
-            ttl = self.config.ttl_seconds
-            entry = CacheEntry(value, ttl)
+            entry = CacheEntry(value, ttl_seconds)
             
             # Check if key already exists
             existed = key in self._data
@@ -181,6 +185,15 @@ class Cache(Generic[T]):
             
             return list(self._data.keys())
     
+    def scan(self, prefix: str) -> List[Tuple[str, T]]:
+        """Get (key, value) pairs whose keys start with prefix, sorted by key."""
+        with self._lock:
+            return sorted(
+                (key, entry.value)
+                for key, entry in self._data.items()
+                if key.startswith(prefix) and not entry.is_expired()
+            )
+    
     def values(self) -> List[T]:
         """Get all values in cache."""
         with self._lock:
@@ -207,7 +220,10 @@ class Cache(Generic[T]):
         with self._lock:
             # Update memory usage estimate
             if self._stats:
-                self._stats.memory_usage = len(self._data) * 64  # Rough estimate
+                self._stats.memory_usage = sum(
+                    sys.getsizeof(key) + sys.getsizeof(entry) + sys.getsizeof(entry.value)
+                    for key, entry in self._data.items()
+                )
             
             return self._stats
     
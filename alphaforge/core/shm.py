@@ -0,0 +1,90 @@
+# AlphaForge Shared-Memory Bus Reader
+"""
+Reader for the shared-memory rings the Rust message bus exports topics to
+(see ``alphaforge_core::shm_bus``), for analytics processes on the same host.
+
+Records are envelopes as MessagePack maps with ``message_type``, ``sender``,
+``timestamp`` and ``payload`` keys. The reader never blocks the writer: if it
+falls more than a ring behind, it skips ahead and counts the lost bytes.
+"""
+
+import mmap
+import struct
+from typing import Any, Dict, Iterator, Optional
+
+MAGIC = b"AFSHMBUS"
+LAYOUT_VERSION = 1
+HEADER_LEN = 64
+WRAP_MARKER = 0xFFFFFFFF
+
+
+class ShmReader:
+    """Follows a shared-memory ring from where the writer was when opened."""
+
+    def __init__(self, path: str):
+        with open(path, "rb") as f:
+            self._map = mmap.mmap(f.fileno(), 0, access=mmap.ACCESS_READ)
+        magic, version = struct.unpack_from("<8sI", self._map, 0)
+        (self.capacity,) = struct.unpack_from("<Q", self._map, 16)
+        if magic != MAGIC or version != LAYOUT_VERSION or HEADER_LEN + self.capacity > len(self._map):
+            raise ValueError(f"{path} is not a version {LAYOUT_VERSION} shared-memory ring")
+        self.position = self._written()
+        self.lost_bytes = 0
+
+    def _written(self) -> int:
+        return struct.unpack_from("<Q", self._map, 24)[0]
+
+    def _reserved(self) -> int:
+        return struct.unpack_from("<Q", self._map, 32)[0]
+
+    def _overwritten(self) -> bool:
+        if self._reserved() - self.position <= self.capacity:
+            return False
+        self._skip_to(self._written())
+        return True
+
+    def _skip_to(self, position: int) -> None:
+        self.lost_bytes += position - self.position
+        self.position = position
+
+    def try_next(self) -> Optional[bytes]:
+        """Take the next record if one is waiting."""
+        while True:
+            written = self._written()
+            if self.position >= written:
+                return None
+            if written - self.position > self.capacity:
+                self._skip_to(written)
+                continue
+            offset = self.position % self.capacity
+            start = HEADER_LEN + offset
+            (length,) = struct.unpack_from("<I", self._map, start)
+            if length == WRAP_MARKER:
+                if not self._overwritten():
+                    self.position += self.capacity - offset
+                continue
+            size = (4 + length + 7) & ~7
+            if offset + size > self.capacity:
+                if not self._overwritten():
+                    self._skip_to(written)
+                continue
+            record = self._map[start + 4:start + 4 + length]
+            if self._overwritten():
+                continue
+            self.position += size
+            return record
+
+    def try_next_envelope(self) -> Optional[Dict[str, Any]]:
+        """Take the next envelope if one is waiting, as a dict."""
+        import msgpack
+
+        record = self.try_next()
+        return None if record is None else msgpack.unpackb(record, raw=False)
+
+    def __iter__(self) -> Iterator[bytes]:
+        """Records waiting now."""
+        while (record := self.try_next()) is not None:
+            yield record
+
+    def close(self) -> None:
+        self._map.close()
@@ -14,14 +14,44 @@ try:
     CacheStatistics = _rust_ext.cache.CacheStatistics
     unix_nanos_now = _rust_ext.core.unix_nanos_now_py
     uuid4_new = _rust_ext.core.uuid4_new_py
+    uuid7_new = _rust_ext.core.uuid7_new_py
+    configure_logging = _rust_ext.core.configure_logging_py
+    set_log_level = _rust_ext.core.set_log_level_py
+    
+    # Exceptions raised for AlphaForge errors, with their codes as `code`
+    AlphaForgeError = _rust_ext.AlphaForgeError
+    ConfigurationError = _rust_ext.ConfigurationError
+    NetworkError = _rust_ext.NetworkError
+    SerializationError = _rust_ext.SerializationError
+    TimeError = _rust_ext.TimeError
+    ValidationError = _rust_ext.ValidationError
+    ComponentError = _rust_ext.ComponentError
+    MessageBusError = _rust_ext.MessageBusError
+    EngineRuntimeError = _rust_ext.EngineRuntimeError
+    ExecutionError = _rust_ext.ExecutionError
+    CacheError = _rust_ext.CacheError
     
     # Re-export main components for convenience
     __all__ = [
         'unix_nanos_now',
         'uuid4_new', 
+        'uuid7_new',
+        'configure_logging',
+        'set_log_level',
         'Cache',
         'CacheConfig',
         'CacheStatistics',
+        'AlphaForgeError',
+        'ConfigurationError',
+        'NetworkError',
+        'SerializationError',
+        'TimeError',
+        'ValidationError',
+        'ComponentError',
+        'MessageBusError',
+        'EngineRuntimeError',
+        'ExecutionError',
+        'CacheError',
     ]
     
 except ImportError as e:
@@ -51,6 +81,112 @@ except ImportError as e:
         import uuid
         return str(uuid.uuid4())
 
+    def uuid7_new() -> str:
+        """
+        Generate a new time-ordered UUID7 string.
+        
+        This is a fallback Python implementation.
+        """
+        import os
+        import uuid
+        value = (time.time_ns() // 1_000_000) << 80 | int.from_bytes(os.urandom(10), 'big')
+        value = value & ~(0xf << 76) | (0x7 << 76)  # Version 7
+        value = value & ~(0x3 << 62) | (0x2 << 62)  # Variant bits
+        return str(uuid.UUID(int=value))
+
+    def configure_logging(
+        level: str = "info",
+        json: bool = False,
+        components: Optional[Dict[str, str]] = None,
+        directory: Optional[str] = None,
+        max_file_bytes: int = 64 * 1024 * 1024,
+        max_files: int = 10,
+        stdout: bool = True,
+        stdout_max_per_sec: Optional[int] = None,
+    ) -> None:
+        """
+        Configure logging through the standard `logging` module.
+        
+        This is a fallback Python implementation; JSON output and the
+        standard output rate limit are not supported.
+        """
+        import logging
+        import logging.handlers
+        import os
+        root = logging.getLogger("alphaforge")
+        for handler in list(root.handlers):
+            root.removeHandler(handler)
+        formatter = logging.Formatter("%(asctime)s %(levelname)5s %(name)s: %(message)s")
+        handlers = []
+        if stdout:
+            handlers.append(logging.StreamHandler())
+        if directory is not None:
+            os.makedirs(directory, exist_ok=True)
+            handlers.append(logging.handlers.RotatingFileHandler(
+                os.path.join(directory, "alphaforge.log"),
+                maxBytes=max_file_bytes,
+                backupCount=max(max_files - 1, 0),
+            ))
+        for handler in handlers:
+            handler.setFormatter(formatter)
+            root.addHandler(handler)
+        set_log_level(level)
+        for component, component_level in (components or {}).items():
+            set_log_level(component_level, component)
+
+    def set_log_level(level: str, component: Optional[str] = None) -> None:
+        """
+        Set the level of all logging, or of one component.
+        
+        This is a fallback Python implementation.
+        """
+        import logging
+        name = "alphaforge" if component is None else f"alphaforge.{component}"
+        levels = {"trace": logging.DEBUG, "debug": logging.DEBUG, "info": logging.INFO,
+                  "warn": logging.WARNING, "warning": logging.WARNING, "error": logging.ERROR}
+        logging.getLogger(name).setLevel(levels[level.lower()])
+
+    class AlphaForgeError(RuntimeError):
+        """
+        Base of the exceptions raised for AlphaForge errors.
+        
+        This is a fallback Python implementation.
+        """
+        
+        code = 0
+
+    class ConfigurationError(AlphaForgeError):
+        code = 100
+
+    class NetworkError(AlphaForgeError):
+        code = 200
+
+    class SerializationError(AlphaForgeError):
+        code = 300
+
+    class TimeError(AlphaForgeError):
+        code = 400
+
+    class ValidationError(AlphaForgeError):
+        code = 500
+
+    class ComponentError(AlphaForgeError):
+        code = 600
+
+    class MessageBusError(AlphaForgeError):
+        code = 700
+
+    class EngineRuntimeError(AlphaForgeError):
+        code = 800
+
+    # Execution and cache errors carry the code of the specific error in
+    # the compiled module; these are the codes of the most general ones
+    class ExecutionError(AlphaForgeError):
+        code = 905
+
+    class CacheError(AlphaForgeError):
+        code = 1004
+
     class CacheStatistics:
         """
         Cache performance statistics.
@@ -146,7 +282,21 @@ except ImportError as e:
     __all__ = [
         'unix_nanos_now',
         'uuid4_new', 
+        'uuid7_new',
+        'configure_logging',
+        'set_log_level',
         'Cache',
         'CacheConfig',
         'CacheStatistics',
+        'AlphaForgeError',
+        'ConfigurationError',
+        'NetworkError',
+        'SerializationError',
+        'TimeError',
+        'ValidationError',
+        'ComponentError',
+        'MessageBusError',
+        'EngineRuntimeError',
+        'ExecutionError',
+        'CacheError',
     ]
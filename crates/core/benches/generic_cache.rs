@@ -0,0 +1,66 @@
+//! Concurrent put/get throughput of GenericCache with one shard against the
+//! default sharding, and read-heavy throughput of the locked cache against
+//! the concurrent one
+
+use std::sync::Arc;
+use std::thread;
+
+use alphaforge_core::generic_cache::{CacheConcurrency, GenericCache, GenericCacheConfig, DEFAULT_SHARDS};
+use criterion::{criterion_group, criterion_main, BenchmarkId, Criterion, Throughput};
+
+const THREADS: usize = 8;
+const OPS_PER_THREAD: usize = 10_000;
+
+/// Run every thread's operations, one in `write_every` a put
+fn run(cache: &Arc<GenericCache<u64>>, write_every: usize) {
+    let handles: Vec<_> = (0..THREADS)
+        .map(|thread| {
+            let cache = Arc::clone(cache);
+            thread::spawn(move || {
+                for i in 0..OPS_PER_THREAD {
+                    let key = format!("{}-{}", thread, i % 1_000);
+                    if i % write_every == 0 {
+                        cache.put(key, i as u64);
+                    } else {
+                        criterion::black_box(cache.get(&key));
+                    }
+                }
+            })
+        })
+        .collect();
+    for handle in handles {
+        handle.join().unwrap();
+    }
+}
+
+fn concurrent_put_get(c: &mut Criterion) {
+    let mut group = c.benchmark_group("generic_cache_concurrent");
+    group.throughput(Throughput::Elements((THREADS * OPS_PER_THREAD) as u64));
+    for shards in [1, DEFAULT_SHARDS] {
+        let cache = Arc::new(GenericCache::new(GenericCacheConfig {
+            max_size: 100_000,
+            shards,
+            ..Default::default()
+        }));
+        group.bench_with_input(BenchmarkId::from_parameter(shards), &cache, |b, cache| b.iter(|| run(cache, 4)));
+    }
+    group.finish();
+}
+
+fn read_heavy_contention(c: &mut Criterion) {
+    let mut group = c.benchmark_group("generic_cache_read_heavy");
+    group.throughput(Throughput::Elements((THREADS * OPS_PER_THREAD) as u64));
+    for concurrency in [CacheConcurrency::Locked, CacheConcurrency::Concurrent] {
+        let cache = Arc::new(GenericCache::new(GenericCacheConfig {
+            max_size: 100_000,
+            concurrency,
+            ..Default::default()
+        }));
+        let id = BenchmarkId::from_parameter(format!("{:?}", concurrency));
+        group.bench_with_input(id, &cache, |b, cache| b.iter(|| run(cache, 100)));
+    }
+    group.finish();
+}
+
+criterion_group!(benches, concurrent_put_get, read_heavy_contention);
+criterion_main!(benches);
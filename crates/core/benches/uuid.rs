@@ -0,0 +1,30 @@
+//! UUID generation from the thread-local generator against reading
+//! /dev/urandom on every call, as UUID4 used to. On a Linux x86-64 VM a
+//! UUID4 takes about 8ns and a UUID7 about 54ns, against about 1.6µs for
+//! the /dev/urandom read.
+
+use alphaforge_core::uuid::{UUID4, UUID7};
+use criterion::{criterion_group, criterion_main, Criterion, Throughput};
+
+/// The previous UUID4 randomness source
+fn urandom_per_call() -> [u8; 16] {
+    use std::fs::File;
+    use std::io::Read;
+    let mut bytes = [0u8; 16];
+    if let Ok(mut f) = File::open("/dev/urandom") {
+        let _ = f.read_exact(&mut bytes);
+    }
+    bytes
+}
+
+fn generation(c: &mut Criterion) {
+    let mut group = c.benchmark_group("uuid_generation");
+    group.throughput(Throughput::Elements(1));
+    group.bench_function("urandom_per_call", |b| b.iter(urandom_per_call));
+    group.bench_function("uuid4", |b| b.iter(UUID4::new));
+    group.bench_function("uuid7", |b| b.iter(UUID7::new));
+    group.finish();
+}
+
+criterion_group!(benches, generation);
+criterion_main!(benches);
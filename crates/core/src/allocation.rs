@@ -0,0 +1,160 @@
+//! AlphaForge Capital Allocation
+//!
+//! Splits a fund's capital between the strategies running in one engine.
+//! Each strategy gets a weight of total capital, optionally capped at a
+//! fixed notional. The `Portfolio` holds the allocation and the execution
+//! engine checks every order against it, so a strategy cannot build gross
+//! exposure beyond its share. Strategies without an allocation are not
+//! limited.
+
+use std::collections::HashMap;
+
+use serde::{Deserialize, Serialize};
+
+use crate::identifiers::StrategyId;
+
+/// Capital assigned to one strategy
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct Allocation {
+    /// Fraction of total capital
+    pub weight: f64,
+    /// Cap on the allocated capital, whatever the weight
+    pub max_notional: Option<f64>,
+}
+
+/// Capital weights and limits across strategies
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct CapitalAllocation {
+    total_capital: f64,
+    allocations: HashMap<StrategyId, Allocation>,
+}
+
+impl CapitalAllocation {
+    pub fn new(total_capital: f64) -> Result<Self, String> {
+        let mut allocation = Self::default();
+        allocation.set_total_capital(total_capital)?;
+        Ok(allocation)
+    }
+
+    pub fn total_capital(&self) -> f64 {
+        self.total_capital
+    }
+
+    /// Change the capital being allocated (e.g. after deposits); weights
+    /// are kept
+    pub fn set_total_capital(&mut self, total_capital: f64) -> Result<(), String> {
+        if !total_capital.is_finite() || total_capital < 0.0 {
+            return Err(format!("Invalid total capital: {}", total_capital));
+        }
+        self.total_capital = total_capital;
+        Ok(())
+    }
+
+    /// Assign a strategy a weight of total capital, replacing its previous
+    /// allocation; weights across strategies may not exceed 1
+    pub fn allocate(&mut self, strategy_id: StrategyId, weight: f64, max_notional: Option<f64>) -> Result<(), String> {
+        Self::validate_weight(strategy_id, weight)?;
+        if let Some(limit) = max_notional.filter(|limit| !limit.is_finite() || *limit < 0.0) {
+            return Err(format!("Invalid notional limit for strategy {}: {}", strategy_id, limit));
+        }
+
+        let others: f64 = self.allocations
+            .iter()
+            .filter(|(id, _)| **id != strategy_id)
+            .map(|(_, allocation)| allocation.weight)
+            .sum();
+        Self::validate_total(others + weight)?;
+        self.allocations.insert(strategy_id, Allocation { weight, max_notional });
+        Ok(())
+    }
+
+    /// Remove a strategy's allocation, leaving it unlimited
+    pub fn deallocate(&mut self, strategy_id: StrategyId) -> Option<Allocation> {
+        self.allocations.remove(&strategy_id)
+    }
+
+    /// Set new weights for several strategies at once, keeping their
+    /// notional limits. Nothing changes unless the resulting weights are
+    /// valid. Returns the capital each listed strategy now has.
+    pub fn rebalance(&mut self, weights: &[(StrategyId, f64)]) -> Result<HashMap<StrategyId, f64>, String> {
+        let mut allocations = self.allocations.clone();
+        for (strategy_id, weight) in weights {
+            Self::validate_weight(*strategy_id, *weight)?;
+            allocations
+                .entry(*strategy_id)
+                .and_modify(|allocation| allocation.weight = *weight)
+                .or_insert(Allocation { weight: *weight, max_notional: None });
+        }
+        Self::validate_total(allocations.values().map(|allocation| allocation.weight).sum())?;
+
+        self.allocations = allocations;
+        Ok(weights
+            .iter()
+            .filter_map(|(strategy_id, _)| Some((*strategy_id, self.capital(*strategy_id)?)))
+            .collect())
+    }
+
+    pub fn allocation(&self, strategy_id: StrategyId) -> Option<&Allocation> {
+        self.allocations.get(&strategy_id)
+    }
+
+    /// Capital a strategy may deploy (`None` when it has no allocation)
+    pub fn capital(&self, strategy_id: StrategyId) -> Option<f64> {
+        self.allocations.get(&strategy_id).map(|allocation| {
+            let capital = self.total_capital * allocation.weight;
+            allocation.max_notional.map_or(capital, |limit| capital.min(limit))
+        })
+    }
+
+    /// Total weight allocated
+    pub fn allocated_weight(&self) -> f64 {
+        self.allocations.values().map(|allocation| allocation.weight).sum()
+    }
+
+    fn validate_weight(strategy_id: StrategyId, weight: f64) -> Result<(), String> {
+        if !(0.0..=1.0).contains(&weight) {
+            return Err(format!("Invalid weight for strategy {}: {}", strategy_id, weight));
+        }
+        Ok(())
+    }
+
+    fn validate_total(total: f64) -> Result<(), String> {
+        if total > 1.0 + 1e-9 {
+            return Err(format!("Allocation weights sum to {}, above 1", total));
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_allocate_and_rebalance() {
+        let (trend, carry, arb) = (StrategyId::new(1), StrategyId::new(2), StrategyId::new(3));
+        let mut allocation = CapitalAllocation::new(1_000_000.0).unwrap();
+        allocation.allocate(trend, 0.5, None).unwrap();
+        allocation.allocate(carry, 0.3, Some(200_000.0)).unwrap();
+
+        assert_eq!(allocation.capital(trend), Some(500_000.0));
+        assert_eq!(allocation.capital(carry), Some(200_000.0));
+        assert_eq!(allocation.capital(arb), None);
+        assert!(allocation.allocate(arb, 0.3, None).is_err());
+        assert!(allocation.allocate(arb, -0.1, None).is_err());
+
+        // Shifting weight from trend to a new strategy in one step
+        let capital = allocation.rebalance(&[(trend, 0.2), (arb, 0.5)]).unwrap();
+        assert_eq!(capital[&trend], 200_000.0);
+        assert_eq!(capital[&arb], 500_000.0);
+        assert_eq!(allocation.allocation(carry).unwrap().max_notional, Some(200_000.0));
+
+        // An invalid rebalance changes nothing
+        assert!(allocation.rebalance(&[(trend, 0.5)]).is_err());
+        assert_eq!(allocation.capital(trend), Some(200_000.0));
+
+        allocation.set_total_capital(2_000_000.0).unwrap();
+        assert_eq!(allocation.capital(trend), Some(400_000.0));
+        assert!((allocation.allocated_weight() - 1.0).abs() < 1e-12);
+    }
+}
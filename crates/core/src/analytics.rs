@@ -0,0 +1,527 @@
+//! AlphaForge Market Data Analytics
+//!
+//! Incremental per-instrument calculators maintained by the Data Engine
+//! as ticks arrive.
+
+use std::collections::VecDeque;
+use rust_decimal::Decimal;
+use rust_decimal::prelude::ToPrimitive;
+use serde::{Deserialize, Serialize};
+
+use crate::data::{AggressorSide, Bar, TradeTick};
+use crate::time::UnixNanos;
+
+/// Running session VWAP and TWAP for a single instrument
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct SessionPrices {
+    notional: Decimal,
+    volume: Decimal,
+    time_weighted: Decimal,
+    last_price: Option<Decimal>,
+    ts_first: Option<UnixNanos>,
+    ts_last: UnixNanos,
+}
+
+impl SessionPrices {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Update with a trade tick; ticks with a non-finite price or size are
+    /// ignored
+    pub fn update(&mut self, tick: &TradeTick) {
+        let (Some(price), Some(size)) = (tick.price_decimal(), tick.size_decimal()) else {
+            return;
+        };
+        let ts = tick.ts_event;
+
+        self.notional += price * size;
+        self.volume += size;
+
+        // Each price is weighted by how long it stood before the next trade
+        match (self.last_price, self.ts_first) {
+            (Some(last_price), Some(_)) if ts > self.ts_last => {
+                self.time_weighted += last_price * Decimal::from(ts - self.ts_last);
+                self.ts_last = ts;
+            }
+            (_, None) => {
+                self.ts_first = Some(ts);
+                self.ts_last = ts;
+            }
+            _ => {}
+        }
+
+        self.last_price = Some(price);
+    }
+
+    /// Volume-weighted average price for the session
+    pub fn vwap(&self) -> Option<f64> {
+        if self.volume.is_zero() {
+            return None;
+        }
+        (self.notional / self.volume).to_f64()
+    }
+
+    /// Time-weighted average price for the session
+    pub fn twap(&self) -> Option<f64> {
+        let ts_first = self.ts_first?;
+        let elapsed = self.ts_last - ts_first;
+        if elapsed == 0 {
+            return self.last_price.and_then(|price| price.to_f64());
+        }
+        (self.time_weighted / Decimal::from(elapsed)).to_f64()
+    }
+
+    /// Total traded volume for the session
+    pub fn volume(&self) -> f64 {
+        self.volume.to_f64().unwrap_or_default()
+    }
+
+    /// Start a new session
+    pub fn reset(&mut self) {
+        *self = Self::default();
+    }
+}
+
+/// Rolling-window bar statistics (realized volatility, ATR, high/low)
+/// maintained in O(1) amortized time per bar
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RollingStatistics {
+    window: usize,
+    seq: u64,
+    prev_close: Option<f64>,
+    returns: VecDeque<f64>,
+    returns_sum: f64,
+    returns_sum_sq: f64,
+    true_ranges: VecDeque<f64>,
+    true_range_sum: f64,
+    // Monotonic deques of (sequence, value) for rolling extremes
+    highs: VecDeque<(u64, f64)>,
+    lows: VecDeque<(u64, f64)>,
+}
+
+impl RollingStatistics {
+    pub fn new(window: usize) -> Self {
+        let window = window.max(1);
+        Self {
+            window,
+            seq: 0,
+            prev_close: None,
+            returns: VecDeque::with_capacity(window),
+            returns_sum: 0.0,
+            returns_sum_sq: 0.0,
+            true_ranges: VecDeque::with_capacity(window),
+            true_range_sum: 0.0,
+            highs: VecDeque::new(),
+            lows: VecDeque::new(),
+        }
+    }
+
+    /// Update with a completed bar
+    pub fn update(&mut self, bar: &Bar) {
+        let true_range = match self.prev_close {
+            Some(prev_close) => (bar.high - bar.low)
+                .max((bar.high - prev_close).abs())
+                .max((bar.low - prev_close).abs()),
+            None => bar.high - bar.low,
+        };
+        self.true_ranges.push_back(true_range);
+        self.true_range_sum += true_range;
+        if self.true_ranges.len() > self.window {
+            if let Some(old) = self.true_ranges.pop_front() {
+                self.true_range_sum -= old;
+            }
+        }
+
+        if let Some(prev_close) = self.prev_close {
+            if prev_close > 0.0 && bar.close > 0.0 {
+                let log_return = (bar.close / prev_close).ln();
+                self.returns.push_back(log_return);
+                self.returns_sum += log_return;
+                self.returns_sum_sq += log_return * log_return;
+                if self.returns.len() > self.window {
+                    if let Some(old) = self.returns.pop_front() {
+                        self.returns_sum -= old;
+                        self.returns_sum_sq -= old * old;
+                    }
+                }
+            }
+        }
+
+        let seq = self.seq;
+        self.seq += 1;
+        let expired = seq.saturating_sub(self.window as u64 - 1);
+
+        while self.highs.back().is_some_and(|&(_, high)| high <= bar.high) {
+            self.highs.pop_back();
+        }
+        self.highs.push_back((seq, bar.high));
+        while self.highs.front().is_some_and(|&(s, _)| s < expired) {
+            self.highs.pop_front();
+        }
+
+        while self.lows.back().is_some_and(|&(_, low)| low >= bar.low) {
+            self.lows.pop_back();
+        }
+        self.lows.push_back((seq, bar.low));
+        while self.lows.front().is_some_and(|&(s, _)| s < expired) {
+            self.lows.pop_front();
+        }
+
+        self.prev_close = Some(bar.close);
+    }
+
+    /// Sample standard deviation of close-to-close log returns (per bar, not annualized)
+    pub fn realized_volatility(&self) -> Option<f64> {
+        let n = self.returns.len();
+        if n < 2 {
+            return None;
+        }
+        let n = n as f64;
+        let mean = self.returns_sum / n;
+        let variance = (self.returns_sum_sq - n * mean * mean) / (n - 1.0);
+        Some(variance.max(0.0).sqrt())
+    }
+
+    /// Average true range over the window
+    pub fn atr(&self) -> Option<f64> {
+        if self.true_ranges.is_empty() {
+            None
+        } else {
+            Some(self.true_range_sum / self.true_ranges.len() as f64)
+        }
+    }
+
+    /// Highest high over the window
+    pub fn rolling_high(&self) -> Option<f64> {
+        self.highs.front().map(|&(_, high)| high)
+    }
+
+    /// Lowest low over the window
+    pub fn rolling_low(&self) -> Option<f64> {
+        self.lows.front().map(|&(_, low)| low)
+    }
+
+    /// Check if a full window of bars has been observed
+    pub fn is_warm(&self) -> bool {
+        self.true_ranges.len() >= self.window
+    }
+}
+
+/// Aggressor volume totals for one window
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub struct OrderFlowSnapshot {
+    /// Volume traded by aggressive buyers (taker buys)
+    pub buy_volume: f64,
+    /// Volume traded by aggressive sellers (taker sells)
+    pub sell_volume: f64,
+    /// Buy volume minus sell volume
+    pub delta: f64,
+}
+
+/// Sliding time window of aggressor volume
+#[derive(Debug, Clone, Serialize, Deserialize)]
+struct OrderFlowWindow {
+    window_ns: u64,
+    trades: VecDeque<(UnixNanos, Decimal, Decimal)>,
+    buy_volume: Decimal,
+    sell_volume: Decimal,
+}
+
+impl OrderFlowWindow {
+    fn new(window_ns: u64) -> Self {
+        Self {
+            window_ns,
+            trades: VecDeque::new(),
+            buy_volume: Decimal::ZERO,
+            sell_volume: Decimal::ZERO,
+        }
+    }
+
+    fn update(&mut self, ts: UnixNanos, buy: Decimal, sell: Decimal) {
+        self.trades.push_back((ts, buy, sell));
+        self.buy_volume += buy;
+        self.sell_volume += sell;
+
+        // Window covers (ts - window_ns, ts]
+        let Some(cutoff) = ts.checked_sub(self.window_ns) else {
+            return;
+        };
+        while self.trades.front().is_some_and(|&(t, _, _)| t <= cutoff) {
+            if let Some((_, old_buy, old_sell)) = self.trades.pop_front() {
+                self.buy_volume -= old_buy;
+                self.sell_volume -= old_sell;
+            }
+        }
+    }
+
+    /// Totals over `(now - window_ns, now]`, leaving out trades that aged
+    /// out since the last update
+    fn snapshot(&self, now: UnixNanos) -> OrderFlowSnapshot {
+        let (mut buy_volume, mut sell_volume) = (self.buy_volume, self.sell_volume);
+        if let Some(cutoff) = now.checked_sub(self.window_ns) {
+            for &(_, old_buy, old_sell) in self.trades.iter().take_while(|&&(t, _, _)| t <= cutoff) {
+                buy_volume -= old_buy;
+                sell_volume -= old_sell;
+            }
+        }
+        OrderFlowSnapshot {
+            buy_volume: buy_volume.to_f64().unwrap_or_default(),
+            sell_volume: sell_volume.to_f64().unwrap_or_default(),
+            delta: (buy_volume - sell_volume).to_f64().unwrap_or_default(),
+        }
+    }
+}
+
+/// Maker/taker volume aggregates over one or more time windows
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct OrderFlow {
+    windows: Vec<OrderFlowWindow>,
+}
+
+impl OrderFlow {
+    /// Create aggregates for the given window lengths (nanoseconds)
+    pub fn new(windows_ns: &[u64]) -> Self {
+        Self {
+            windows: windows_ns.iter().map(|&w| OrderFlowWindow::new(w)).collect(),
+        }
+    }
+
+    /// Update with a trade tick; trades without an aggressor or with a
+    /// non-finite size are ignored
+    pub fn update(&mut self, tick: &TradeTick) {
+        let Some(size) = tick.size_decimal() else {
+            return;
+        };
+        let (buy, sell) = match tick.aggressor_side {
+            AggressorSide::Buyer => (size, Decimal::ZERO),
+            AggressorSide::Seller => (Decimal::ZERO, size),
+            AggressorSide::NoAggressor => return,
+        };
+
+        for window in self.windows.iter_mut() {
+            window.update(tick.ts_event, buy, sell);
+        }
+    }
+
+    /// Aggregates for a configured window length ending at `now`
+    pub fn snapshot(&self, window_ns: u64, now: UnixNanos) -> Option<OrderFlowSnapshot> {
+        self.windows
+            .iter()
+            .find(|w| w.window_ns == window_ns)
+            .map(|w| w.snapshot(now))
+    }
+}
+
+/// Summary of feed latency (`ts_init - ts_event`) for one instrument.
+///
+/// Negative values mean the venue clock runs ahead of ours.
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub struct FeedLatencySnapshot {
+    pub count: u64,
+    pub mean_ns: f64,
+    pub min_ns: i64,
+    pub max_ns: i64,
+    /// Median over the recent sample window
+    pub p50_ns: i64,
+    /// 99th percentile over the recent sample window
+    pub p99_ns: i64,
+    pub last_ns: i64,
+}
+
+/// Feed latency distribution: lifetime min/max/mean plus percentiles over
+/// the most recent samples
+#[derive(Debug, Clone)]
+pub struct FeedLatency {
+    sample_window: usize,
+    samples: VecDeque<i64>,
+    count: u64,
+    sum: i128,
+    min: i64,
+    max: i64,
+    last: i64,
+}
+
+impl FeedLatency {
+    pub fn new(sample_window: usize) -> Self {
+        Self {
+            sample_window: sample_window.max(1),
+            samples: VecDeque::new(),
+            count: 0,
+            sum: 0,
+            min: i64::MAX,
+            max: i64::MIN,
+            last: 0,
+        }
+    }
+
+    /// Record a data item's timestamps, returning the latency in nanoseconds
+    pub fn update(&mut self, ts_event: UnixNanos, ts_init: UnixNanos) -> i64 {
+        let latency = (ts_init as i128 - ts_event as i128)
+            .clamp(i64::MIN as i128, i64::MAX as i128) as i64;
+
+        self.count += 1;
+        self.sum += latency as i128;
+        self.min = self.min.min(latency);
+        self.max = self.max.max(latency);
+        self.last = latency;
+
+        if self.samples.len() == self.sample_window {
+            self.samples.pop_front();
+        }
+        self.samples.push_back(latency);
+
+        latency
+    }
+
+    /// Current distribution summary
+    pub fn snapshot(&self) -> Option<FeedLatencySnapshot> {
+        if self.count == 0 {
+            return None;
+        }
+
+        let mut sorted: Vec<i64> = self.samples.iter().copied().collect();
+        sorted.sort_unstable();
+        let percentile = |p: f64| sorted[((sorted.len() - 1) as f64 * p).round() as usize];
+
+        Some(FeedLatencySnapshot {
+            count: self.count,
+            mean_ns: self.sum as f64 / self.count as f64,
+            min_ns: self.min,
+            max_ns: self.max,
+            p50_ns: percentile(0.5),
+            p99_ns: percentile(0.99),
+            last_ns: self.last,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::identifiers::InstrumentId;
+
+    fn trade(price: f64, size: f64, ts: UnixNanos) -> TradeTick {
+        aggressor_trade(price, size, ts, AggressorSide::NoAggressor)
+    }
+
+    fn aggressor_trade(price: f64, size: f64, ts: UnixNanos, side: AggressorSide) -> TradeTick {
+        TradeTick {
+            instrument_id: InstrumentId::new(1),
+            price,
+            size,
+            aggressor_side: side,
+            trade_id: ts.to_string(),
+            ts_event: ts,
+            ts_init: ts,
+        }
+    }
+
+    #[test]
+    fn test_session_vwap_and_twap() {
+        let mut prices = SessionPrices::new();
+        assert_eq!(prices.vwap(), None);
+        assert_eq!(prices.twap(), None);
+
+        prices.update(&trade(100.0, 1.0, 0));
+        prices.update(&trade(102.0, 3.0, 10));
+        prices.update(&trade(104.0, 1.0, 40));
+
+        // (100 + 306 + 104) / 5
+        assert_eq!(prices.vwap(), Some(102.0));
+        // 100 held for 10ns, 102 held for 30ns
+        assert_eq!(prices.twap(), Some(101.5));
+
+        prices.reset();
+        assert_eq!(prices.vwap(), None);
+        assert_eq!(prices.volume(), 0.0);
+    }
+
+    #[test]
+    fn test_rolling_statistics() {
+        use crate::data::{BarAggregation, BarSpecification, BarType};
+
+        let bar_type = BarType {
+            instrument_id: InstrumentId::new(1),
+            bar_spec: BarSpecification { step: 1, aggregation: BarAggregation::Tick(1) },
+        };
+        let bar = |high: f64, low: f64, close: f64| Bar {
+            bar_type: bar_type.clone(),
+            open: close,
+            high,
+            low,
+            close,
+            volume: 1.0,
+            ts_event: 0,
+            ts_init: 0,
+        };
+
+        let mut stats = RollingStatistics::new(2);
+        stats.update(&bar(105.0, 95.0, 100.0));
+        assert_eq!(stats.atr(), Some(10.0));
+        assert_eq!(stats.realized_volatility(), None);
+
+        stats.update(&bar(112.0, 104.0, 110.0));
+        stats.update(&bar(111.0, 99.0, 100.0));
+
+        // True ranges in window: 12 (112 - 100), 12 (111 - 99)
+        assert_eq!(stats.atr(), Some(12.0));
+        assert_eq!(stats.rolling_high(), Some(112.0));
+        assert_eq!(stats.rolling_low(), Some(99.0));
+        assert!(stats.is_warm());
+
+        let up = (110.0_f64 / 100.0).ln();
+        let down = (100.0_f64 / 110.0).ln();
+        let mean = (up + down) / 2.0;
+        let expected = ((up - mean).powi(2) + (down - mean).powi(2)).sqrt();
+        assert!((stats.realized_volatility().unwrap() - expected).abs() < 1e-12);
+    }
+
+    #[test]
+    fn test_order_flow_windows() {
+        let mut flow = OrderFlow::new(&[10, 100]);
+
+        flow.update(&aggressor_trade(100.0, 2.0, 0, AggressorSide::Buyer));
+        flow.update(&aggressor_trade(100.0, 0.5, 5, AggressorSide::Seller));
+        flow.update(&aggressor_trade(100.0, 9.0, 6, AggressorSide::NoAggressor));
+        flow.update(&aggressor_trade(100.0, 1.0, 20, AggressorSide::Seller));
+
+        let short = flow.snapshot(10, 20).unwrap();
+        assert_eq!(short.buy_volume, 0.0);
+        assert_eq!(short.sell_volume, 1.0);
+        assert_eq!(short.delta, -1.0);
+
+        let long = flow.snapshot(100, 20).unwrap();
+        assert_eq!(long.buy_volume, 2.0);
+        assert_eq!(long.sell_volume, 1.5);
+        assert_eq!(long.delta, 0.5);
+
+        // Trades age out without new ticks arriving
+        assert_eq!(flow.snapshot(10, 30).unwrap(), OrderFlowSnapshot::default());
+        assert_eq!(flow.snapshot(100, 99).unwrap().buy_volume, 2.0);
+        assert_eq!(flow.snapshot(100, 100).unwrap().buy_volume, 0.0);
+        assert_eq!(flow.snapshot(100, 105).unwrap().sell_volume, 1.0);
+
+        assert!(flow.snapshot(50, 20).is_none());
+    }
+
+    #[test]
+    fn test_feed_latency_distribution() {
+        let mut latency = FeedLatency::new(4);
+        assert!(latency.snapshot().is_none());
+
+        for (ts_event, ts_init) in [(100, 110), (200, 230), (300, 295), (400, 420), (500, 540)] {
+            latency.update(ts_event, ts_init);
+        }
+
+        let snapshot = latency.snapshot().unwrap();
+        assert_eq!(snapshot.count, 5);
+        assert_eq!(snapshot.min_ns, -5);
+        assert_eq!(snapshot.max_ns, 40);
+        assert_eq!(snapshot.mean_ns, 19.0);
+        // Window holds the last four samples: 30, -5, 20, 40
+        assert_eq!(snapshot.p50_ns, 30);
+        assert_eq!(snapshot.p99_ns, 40);
+        assert_eq!(snapshot.last_ns, 40);
+    }
+}
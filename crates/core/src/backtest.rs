@@ -0,0 +1,79 @@
+//! AlphaForge Backtesting
+//!
+//! Runs a single strategy over recorded market data on a `TestClock`, so
+//! timers fire at data time, and records how its equity evolved. There is
+//! no simulated venue: strategies account for their trades with
+//! `StrategyContext::record_trade`.
+
+use std::sync::{Arc, Mutex};
+
+use crate::clock::{Clock, TestClock};
+use crate::data::MarketData;
+use crate::data_engine::{DataEngine, DataEngineConfig};
+use crate::strategy_engine::{Strategy, StrategyConfig, StrategyEngine, StrategyMetrics, StrategyState};
+use crate::time::UnixNanos;
+
+/// Outcome of a backtest run
+#[derive(Debug, Clone)]
+pub struct BacktestResult {
+    /// Strategy metrics after the last record
+    pub metrics: StrategyMetrics,
+    /// (data time, equity) whenever equity changed, starting from the
+    /// configured starting equity
+    pub equity_curve: Vec<(UnixNanos, f64)>,
+    /// Strategy state at the end (`RiskBreached` or `Error` if it stopped early)
+    pub final_state: StrategyState,
+    /// Records replayed
+    pub records: u64,
+}
+
+/// Replay `data` (ordered by `ts_init`) through `strategy`
+pub fn run_backtest(
+    strategy: Box<dyn Strategy>,
+    config: StrategyConfig,
+    data: &[MarketData],
+) -> Result<BacktestResult, String> {
+    let strategy_id = config.strategy_id;
+    let starting_equity = config.starting_equity;
+    let start_ts = data.first().map_or(0, MarketData::ts_init);
+
+    let clock = Arc::new(TestClock::new(start_ts));
+    let data_engine = Arc::new(Mutex::new(DataEngine::new(DataEngineConfig::default())));
+    let mut engine = StrategyEngine::new(data_engine);
+    engine.set_clock(clock.clone())?;
+    engine.add_strategy(strategy, config)?;
+    engine.start()?;
+
+    let total_pnl = |engine: &StrategyEngine| {
+        engine.get_strategy_metrics(&strategy_id).map_or(0.0, |metrics| metrics.total_pnl)
+    };
+    let mut equity_curve = vec![(start_ts, starting_equity)];
+    let mut last_pnl = 0.0;
+    let mut records = 0;
+
+    for record in data {
+        let ts = record.ts_init().max(clock.timestamp_ns());
+        engine.advance_clock_to(&clock, ts)?;
+        engine.process_data(record)?;
+        records += 1;
+
+        let pnl = total_pnl(&engine);
+        if pnl != last_pnl {
+            equity_curve.push((ts, starting_equity + pnl));
+            last_pnl = pnl;
+        }
+    }
+
+    let final_state = engine.strategy_state(&strategy_id).unwrap_or(StrategyState::Error);
+    engine.stop()?;
+    let metrics = engine
+        .get_strategy_metrics(&strategy_id)
+        .ok_or_else(|| "Strategy metrics unavailable".to_string())?;
+
+    Ok(BacktestResult {
+        metrics,
+        equity_curve,
+        final_state,
+        records,
+    })
+}
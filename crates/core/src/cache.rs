@@ -1,26 +1,39 @@
 //! AlphaForge Cache Subsystem
 //! 
 //! High-performance in-memory cache with optional persistence for market and execution data.
-//! Implements O(1) lookups with AHashMap and LRU eviction for memory management.
+//! Implements O(1) lookups with AHashMap and LRU, FIFO or LFU eviction for memory management.
 
-use std::collections::VecDeque;
-use ahash::AHashMap;
+use std::borrow::Borrow;
+use std::collections::{BTreeMap, VecDeque};
+use std::hash::Hash;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::mpsc::{self, RecvTimeoutError};
+use std::sync::Arc;
+use std::time::Duration;
+use ahash::{AHashMap, AHashSet};
+use serde::de::DeserializeOwned;
 use serde::{Serialize, Deserialize};
 use parking_lot::RwLock;
-use tracing::{debug, info};
+use tracing::{debug, info, warn};
 
-use crate::time::UnixNanos;
+use crate::execution_engine::OrderStatus;
+use crate::message_bus::MessageBus;
+use crate::time::{unix_nanos_now, UnixNanos};
 use crate::identifiers::*;
 use crate::data::*;
 
 /// High-performance cache configuration
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
 pub struct CacheConfig {
-    /// Maximum number of items to cache per data type
+    /// Maximum number of items to cache per data type. Quote, trade and bar
+    /// histories count as one item per instrument or bar type, and each
+    /// keeps up to this many of its latest ticks or bars. Instruments,
+    /// accounts, orders and positions are never evicted.
     pub max_items_per_type: usize,
-    /// Enable persistent backing store
+    /// Write updates through to the database given `Cache::with_database`
     pub enable_persistence: bool,
-    /// Cache eviction policy
+    /// Which item of a full data type makes room for a new one
     pub eviction_policy: EvictionPolicy,
     /// Flush interval for persistence (milliseconds)
     pub flush_interval_ms: u64,
@@ -38,7 +51,7 @@ impl Default for CacheConfig {
 }
 
 /// Cache eviction policies
-#[derive(Debug, Clone, Copy)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
 pub enum EvictionPolicy {
     /// Least Recently Used
     LRU,
@@ -57,15 +70,172 @@ pub struct CacheIndex {
     pub instruments_by_venue: AHashMap<String, Vec<InstrumentId>>,
     /// Currency pairs index
     pub currency_pairs: AHashMap<(String, String), Vec<InstrumentId>>,
+    /// Instrument to order IDs mapping
+    pub orders_by_instrument: AHashMap<InstrumentId, AHashSet<String>>,
+    /// Strategy to order IDs mapping
+    pub orders_by_strategy: AHashMap<String, AHashSet<String>>,
+    /// Instrument to position IDs mapping
+    pub positions_by_instrument: AHashMap<InstrumentId, AHashSet<String>>,
+    /// Strategy to position IDs mapping
+    pub positions_by_strategy: AHashMap<String, AHashSet<String>>,
+    /// IDs of positions with a nonzero quantity
+    pub positions_open: AHashSet<String>,
+}
+
+impl CacheIndex {
+    /// Index an order, replacing the entries of the version it updates
+    fn index_order(&mut self, order: &Order, previous: Option<&Order>) {
+        if let Some(previous) = previous {
+            unindex(&mut self.orders_by_instrument, &previous.instrument_id, &previous.id);
+            if let Some(strategy_id) = &previous.strategy_id {
+                unindex(&mut self.orders_by_strategy, strategy_id, &previous.id);
+            }
+        }
+        self.orders_by_instrument.entry(order.instrument_id).or_default().insert(order.id.clone());
+        if let Some(strategy_id) = &order.strategy_id {
+            self.orders_by_strategy.entry(strategy_id.clone()).or_default().insert(order.id.clone());
+        }
+    }
+    
+    /// Index a position, replacing the entries of the version it updates
+    fn index_position(&mut self, position: &Position, previous: Option<&Position>) {
+        if let Some(previous) = previous {
+            unindex(&mut self.positions_by_instrument, &previous.instrument_id, &previous.id);
+            if let Some(strategy_id) = &previous.strategy_id {
+                unindex(&mut self.positions_by_strategy, strategy_id, &previous.id);
+            }
+        }
+        self.positions_by_instrument.entry(position.instrument_id).or_default().insert(position.id.clone());
+        if let Some(strategy_id) = &position.strategy_id {
+            self.positions_by_strategy.entry(strategy_id.clone()).or_default().insert(position.id.clone());
+        }
+        if position.is_open() {
+            self.positions_open.insert(position.id.clone());
+        } else {
+            self.positions_open.remove(&position.id);
+        }
+    }
+
+}
+
+// Remove `id` from the IDs indexed under `key`, dropping emptied sets
+fn unindex<K: std::hash::Hash + Eq>(index: &mut AHashMap<K, AHashSet<String>>, key: &K, id: &str) {
+    if let Some(ids) = index.get_mut(key) {
+        ids.remove(id);
+        if ids.is_empty() {
+            index.remove(key);
+        }
+    }
+}
+
+// The items with the given IDs
+fn indexed<V: Clone>(ids: Option<&AHashSet<String>>, items: &AHashMap<String, V>) -> Vec<V> {
+    ids.map(|ids| ids.iter().filter_map(|id| items.get(id).cloned()).collect()).unwrap_or_default()
 }
 
 /// Database adapter trait for persistence
 pub trait CacheDatabaseAdapter: Send + Sync {
     fn write_batch(&self, data: &[CacheEntry]) -> Result<(), CacheError>;
     fn read_by_key(&self, key: &str) -> Result<Option<CacheEntry>, CacheError>;
+    /// Every stored entry, latest write per key. Adapters that cannot list
+    /// their entries fail, and caches using them cannot be loaded at startup.
+    fn read_all(&self) -> Result<Vec<CacheEntry>, CacheError> {
+        Err(CacheError::Database("Listing entries is not supported".to_string()))
+    }
     fn flush(&self) -> Result<(), CacheError>;
 }
 
+// Flush `database` every `interval` until the returned sender is dropped,
+// then once more
+fn start_flusher(database: Arc<dyn CacheDatabaseAdapter>, interval: Duration) -> Option<mpsc::Sender<()>> {
+    let (stop, stopped) = mpsc::channel::<()>();
+    let spawned = std::thread::Builder::new().name("cache-flusher".to_string()).spawn(move || loop {
+        let stopping = !matches!(stopped.recv_timeout(interval), Err(RecvTimeoutError::Timeout));
+        if let Err(e) = database.flush() {
+            warn!("Failed to flush cache database: {}", e);
+        }
+        if stopping {
+            break;
+        }
+    });
+    match spawned {
+        Ok(_) => Some(stop),
+        Err(e) => {
+            warn!("Failed to start cache flusher: {}", e);
+            None
+        }
+    }
+}
+
+/// `CacheEntry::data_type` of each persisted data type
+const CURRENCY_DATA_TYPE: &str = "currency";
+const INSTRUMENT_DATA_TYPE: &str = "instrument";
+const ACCOUNT_DATA_TYPE: &str = "account";
+const ORDER_DATA_TYPE: &str = "order";
+const POSITION_DATA_TYPE: &str = "position";
+
+// Decode the entries of one data type
+fn decode_entries<T: DeserializeOwned>(entries: &[CacheEntry], data_type: &str) -> Result<Vec<T>, CacheError> {
+    entries
+        .iter()
+        .filter(|entry| entry.data_type == data_type)
+        .map(|entry| Ok(bincode::deserialize(&entry.data)?))
+        .collect()
+}
+
+/// Data to preload into a cache at startup, before engines begin
+/// processing: the persistence backend, or a venue reconciliation
+pub trait CacheWarmUpSource {
+    fn currencies(&self) -> Result<Vec<Currency>, CacheError> {
+        Ok(Vec::new())
+    }
+    fn instruments(&self) -> Result<Vec<InstrumentAny>, CacheError> {
+        Ok(Vec::new())
+    }
+    fn open_orders(&self) -> Result<Vec<Order>, CacheError> {
+        Ok(Vec::new())
+    }
+    fn open_positions(&self) -> Result<Vec<Position>, CacheError> {
+        Ok(Vec::new())
+    }
+}
+
+/// Every entry of a cache database, read once to warm a cache up from
+#[derive(Debug, Clone, Default)]
+pub struct StoredEntries(pub Vec<CacheEntry>);
+
+impl StoredEntries {
+    pub fn read(database: &dyn CacheDatabaseAdapter) -> Result<Self, CacheError> {
+        Ok(Self(database.read_all()?))
+    }
+}
+
+impl CacheWarmUpSource for StoredEntries {
+    fn currencies(&self) -> Result<Vec<Currency>, CacheError> {
+        decode_entries(&self.0, CURRENCY_DATA_TYPE)
+    }
+    fn instruments(&self) -> Result<Vec<InstrumentAny>, CacheError> {
+        decode_entries(&self.0, INSTRUMENT_DATA_TYPE)
+    }
+    fn open_orders(&self) -> Result<Vec<Order>, CacheError> {
+        let orders: Vec<Order> = decode_entries(&self.0, ORDER_DATA_TYPE)?;
+        Ok(orders.into_iter().filter(Order::is_open).collect())
+    }
+    fn open_positions(&self) -> Result<Vec<Position>, CacheError> {
+        let positions: Vec<Position> = decode_entries(&self.0, POSITION_DATA_TYPE)?;
+        Ok(positions.into_iter().filter(Position::is_open).collect())
+    }
+}
+
+/// What `Cache::warm_up` preloaded
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct WarmUpReport {
+    pub currencies: usize,
+    pub instruments: usize,
+    pub orders: usize,
+    pub positions: usize,
+}
+
 /// Cache entry for serialization
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct CacheEntry {
@@ -76,6 +246,237 @@ pub struct CacheEntry {
     pub access_count: u64,
 }
 
+/// Rank of a key in eviction order; the smallest is evicted first. The
+/// last component is the key's insertion sequence, keeping ranks unique.
+type Rank = (u64, u64, u64);
+
+#[derive(Debug)]
+struct Ranked<V> {
+    value: V,
+    seq: u64,
+    /// Access clock at the last use, updated by readers
+    last_used: AtomicU64,
+    uses: AtomicU64,
+}
+
+impl<V> Ranked<V> {
+    fn current_rank(&self, policy: EvictionPolicy) -> Rank {
+        let last_used = self.last_used.load(Ordering::Relaxed);
+        match policy {
+            EvictionPolicy::LRU => (0, last_used, self.seq),
+            EvictionPolicy::LFU => (self.uses.load(Ordering::Relaxed), last_used, self.seq),
+            EvictionPolicy::FIFO => (0, 0, self.seq),
+        }
+    }
+}
+
+/// Items of one data type, evicted in policy order once `capacity` keys
+/// are held. Lookups take `&self`, so they run under a read lock, and
+/// record their use in atomics; eviction refiles keys used since they were
+/// filed before choosing one.
+#[derive(Debug)]
+struct PolicyMap<K, V> {
+    items: AHashMap<K, Ranked<V>>,
+    /// Keys by rank when last filed, which trails their uses
+    order: BTreeMap<Rank, K>,
+    policy: EvictionPolicy,
+    capacity: usize,
+    /// Advanced on every insert and access
+    clock: AtomicU64,
+    /// Keys inserted so far
+    inserted: u64,
+}
+
+impl<K: Hash + Eq + Clone, V> PolicyMap<K, V> {
+    /// An empty map sized for `expected` keys
+    fn new(config: &CacheConfig, expected: usize) -> Self {
+        Self {
+            items: AHashMap::with_capacity(expected.min(config.max_items_per_type)),
+            order: BTreeMap::new(),
+            policy: config.eviction_policy,
+            capacity: config.max_items_per_type,
+            clock: AtomicU64::new(0),
+            inserted: 0,
+        }
+    }
+
+    fn len(&self) -> usize {
+        self.items.len()
+    }
+
+    fn values(&self) -> impl Iterator<Item = &V> {
+        self.items.values().map(|item| &item.value)
+    }
+
+    // Record a use of `item` (FIFO ignores uses)
+    fn touch(&self, item: &Ranked<V>) {
+        if self.policy != EvictionPolicy::FIFO {
+            item.last_used.store(self.clock.fetch_add(1, Ordering::Relaxed) + 1, Ordering::Relaxed);
+            item.uses.fetch_add(1, Ordering::Relaxed);
+        }
+    }
+
+    /// The value of `key`, counting a use of it
+    fn get<Q>(&self, key: &Q) -> Option<&V>
+    where
+        K: Borrow<Q>,
+        Q: Hash + Eq + ?Sized,
+    {
+        let item = self.items.get(key)?;
+        self.touch(item);
+        Some(&item.value)
+    }
+
+    /// The value of `key`, inserting `default()` if it has none, with the
+    /// entries evicted to make room
+    fn get_or_insert_with(&mut self, key: K, default: impl FnOnce() -> V) -> (&mut V, Vec<(K, V)>) {
+        let mut evicted = Vec::new();
+        if let Some(item) = self.items.get(&key) {
+            self.touch(item);
+        } else {
+            while !self.items.is_empty() && self.items.len() >= self.capacity {
+                evicted.extend(self.evict());
+            }
+            let item = Ranked {
+                value: default(),
+                seq: self.inserted,
+                last_used: AtomicU64::new(0),
+                uses: AtomicU64::new(0),
+            };
+            self.inserted += 1;
+            self.touch(&item);
+            self.order.insert(item.current_rank(self.policy), key.clone());
+            self.items.insert(key.clone(), item);
+        }
+        let item = self.items.get_mut(&key).expect("key is present");
+        (&mut item.value, evicted)
+    }
+
+    /// Set the value of `key`, returning the entries evicted to make room
+    fn insert(&mut self, key: K, value: V) -> Vec<(K, V)> {
+        let mut value = Some(value);
+        let (slot, evicted) = self.get_or_insert_with(key, || value.take().expect("called once"));
+        if let Some(value) = value {
+            *slot = value;
+        }
+        evicted
+    }
+
+    fn evict(&mut self) -> Option<(K, V)> {
+        loop {
+            let (rank, key) = self.order.pop_first()?;
+            let current = self.items.get(&key)?.current_rank(self.policy);
+            if current == rank {
+                let item = self.items.remove(&key)?;
+                return Some((key, item.value));
+            }
+            // Used since it was filed
+            self.order.insert(current, key);
+        }
+    }
+
+    fn clear(&mut self) {
+        self.items.clear();
+        self.order.clear();
+    }
+}
+
+/// Prefix of the topics cache events are published on,
+/// `cache.{data_type}.{kind}`
+pub const CACHE_EVENTS_TOPIC_PREFIX: &str = "cache.";
+
+/// Cached data types, as named in cache event topics
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum CacheDataType {
+    Currencies,
+    Instruments,
+    Books,
+    Quotes,
+    Trades,
+    Bars,
+    Accounts,
+    Orders,
+    Positions,
+}
+
+impl CacheDataType {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            CacheDataType::Currencies => "currencies",
+            CacheDataType::Instruments => "instruments",
+            CacheDataType::Books => "books",
+            CacheDataType::Quotes => "quotes",
+            CacheDataType::Trades => "trades",
+            CacheDataType::Bars => "bars",
+            CacheDataType::Accounts => "accounts",
+            CacheDataType::Orders => "orders",
+            CacheDataType::Positions => "positions",
+        }
+    }
+}
+
+/// How cached data changed
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum CacheEventKind {
+    /// Added or updated
+    Inserted,
+    /// Removed to make room: a whole entry per the eviction policy, or the
+    /// oldest items of a quote, trade or bar history at its capacity
+    Evicted,
+}
+
+impl CacheEventKind {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            CacheEventKind::Inserted => "inserted",
+            CacheEventKind::Evicted => "evicted",
+        }
+    }
+}
+
+/// Topic events of one data type and kind are published on, e.g.
+/// `cache.quotes.evicted`
+pub fn cache_event_topic(data_type: CacheDataType, kind: CacheEventKind) -> String {
+    format!("{}{}.{}", CACHE_EVENTS_TOPIC_PREFIX, data_type.as_str(), kind.as_str())
+}
+
+/// A change to the items cached under one key. Consecutive changes of the
+/// same kind to a key in one batch are reported as one event.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct CacheEvent {
+    pub data_type: CacheDataType,
+    pub kind: CacheEventKind,
+    pub key: String,
+    /// Items changed: ticks or bars for histories, else one
+    pub count: usize,
+    pub ts_event: UnixNanos,
+}
+
+/// Events gathered under a data type's lock, published once it is released
+/// so subscribers may read the cache. Gathers nothing without a message bus.
+struct CacheChanges {
+    events: Option<Vec<CacheEvent>>,
+    ts_event: UnixNanos,
+}
+
+impl CacheChanges {
+    fn note(&mut self, data_type: CacheDataType, kind: CacheEventKind, key: impl FnOnce() -> String, count: usize) {
+        let Some(events) = &mut self.events else {
+            return;
+        };
+        if count == 0 {
+            return;
+        }
+        let key = key();
+        match events.last_mut() {
+            Some(last) if last.data_type == data_type && last.kind == kind && last.key == key => last.count += count,
+            _ => events.push(CacheEvent { data_type, kind, key, count, ts_event: self.ts_event }),
+        }
+    }
+}
+
 /// Cache errors
 #[derive(Debug, thiserror::Error)]
 pub enum CacheError {
@@ -93,15 +494,21 @@ pub enum CacheError {
 pub struct Cache {
     config: CacheConfig,
     index: RwLock<CacheIndex>,
-    database: Option<Box<dyn CacheDatabaseAdapter>>,
+    /// Account, order and position updates are written through to it
+    database: Option<Arc<dyn CacheDatabaseAdapter>>,
+    /// Stops the thread flushing `database` when dropped
+    flusher: Option<mpsc::Sender<()>>,
+    /// Cache events are published on it
+    message_bus: Option<Arc<MessageBus>>,
     
     // Core market data - O(1) lookups with AHashMap
-    currencies: RwLock<AHashMap<String, Currency>>,
+    currencies: RwLock<PolicyMap<String, Currency>>,
+    /// Never evicted, as orders and positions refer to them
     instruments: RwLock<AHashMap<InstrumentId, InstrumentAny>>,
-    books: RwLock<AHashMap<InstrumentId, OrderBook>>,
-    quotes: RwLock<AHashMap<InstrumentId, VecDeque<QuoteTick>>>,
-    trades: RwLock<AHashMap<InstrumentId, VecDeque<TradeTick>>>,
-    bars: RwLock<AHashMap<BarType, VecDeque<Bar>>>,
+    books: RwLock<PolicyMap<InstrumentId, OrderBook>>,
+    quotes: RwLock<PolicyMap<InstrumentId, VecDeque<QuoteTick>>>,
+    trades: RwLock<PolicyMap<InstrumentId, VecDeque<TradeTick>>>,
+    bars: RwLock<PolicyMap<BarType, VecDeque<Bar>>>,
     
     // Execution data
     accounts: RwLock<AHashMap<String, Account>>,
@@ -112,6 +519,17 @@ pub struct Cache {
     stats: CacheStats,
 }
 
+impl std::fmt::Debug for Cache {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("Cache")
+            .field("config", &self.config)
+            .field("has_database", &self.database.is_some())
+            .field("has_message_bus", &self.message_bus.is_some())
+            .field("stats", &self.stats)
+            .finish_non_exhaustive()
+    }
+}
+
 /// Cache performance statistics
 #[derive(Debug, Default)]
 pub struct CacheStats {
@@ -119,6 +537,40 @@ pub struct CacheStats {
     pub misses: std::sync::atomic::AtomicU64,
     pub evictions: std::sync::atomic::AtomicU64,
     pub writes: std::sync::atomic::AtomicU64,
+    /// Evictions of each data type, adding up to `evictions`
+    pub evictions_by_type: EvictionCounters,
+}
+
+/// Eviction counters of each cached data type
+#[derive(Debug, Default)]
+pub struct EvictionCounters {
+    pub currencies: AtomicU64,
+    pub books: AtomicU64,
+    pub quotes: AtomicU64,
+    pub trades: AtomicU64,
+    pub bars: AtomicU64,
+}
+
+impl EvictionCounters {
+    pub fn snapshot(&self) -> EvictionCounts {
+        EvictionCounts {
+            currencies: self.currencies.load(Ordering::Relaxed),
+            books: self.books.load(Ordering::Relaxed),
+            quotes: self.quotes.load(Ordering::Relaxed),
+            trades: self.trades.load(Ordering::Relaxed),
+            bars: self.bars.load(Ordering::Relaxed),
+        }
+    }
+}
+
+/// Evictions of each cached data type
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct EvictionCounts {
+    pub currencies: u64,
+    pub books: u64,
+    pub quotes: u64,
+    pub trades: u64,
+    pub bars: u64,
 }
 
 impl CacheStats {
@@ -141,15 +593,17 @@ impl Cache {
         info!("Initializing AlphaForge cache with config: {:?}", config);
         
         Self {
-            config,
             index: RwLock::new(CacheIndex::default()),
             database: None,
-            currencies: RwLock::new(AHashMap::with_capacity(200)), // ~200 currencies
+            flusher: None,
+            message_bus: None,
+            currencies: RwLock::new(PolicyMap::new(&config, 200)), // ~200 currencies
             instruments: RwLock::new(AHashMap::with_capacity(10_000)), // 10k instruments
-            books: RwLock::new(AHashMap::with_capacity(1_000)), // 1k order books
-            quotes: RwLock::new(AHashMap::with_capacity(1_000)),
-            trades: RwLock::new(AHashMap::with_capacity(1_000)),
-            bars: RwLock::new(AHashMap::with_capacity(1_000)),
+            books: RwLock::new(PolicyMap::new(&config, 1_000)), // 1k order books
+            quotes: RwLock::new(PolicyMap::new(&config, 1_000)),
+            trades: RwLock::new(PolicyMap::new(&config, 1_000)),
+            bars: RwLock::new(PolicyMap::new(&config, 1_000)),
+            config,
             accounts: RwLock::new(AHashMap::with_capacity(100)),
             orders: RwLock::new(AHashMap::with_capacity(100_000)),
             positions: RwLock::new(AHashMap::with_capacity(10_000)),
@@ -160,65 +614,156 @@ impl Cache {
     /// Add currency to cache - O(1) operation
     pub fn add_currency(&self, currency: Currency) -> Result<(), CacheError> {
         let code = currency.code.clone(); // Clone before moving
-        let mut currencies = self.currencies.write();
-        currencies.insert(currency.code.clone(), currency);
-        self.stats.writes.fetch_add(1, std::sync::atomic::Ordering::Relaxed);
+        self.add_currencies(vec![currency])?;
         debug!("Cached currency: {}", code);
         Ok(())
     }
     
+    /// Add several currencies under one lock, written through to the database
+    pub fn add_currencies(&self, batch: Vec<Currency>) -> Result<(), CacheError> {
+        self.write_through(CURRENCY_DATA_TYPE, &batch, |currency| currency.code.clone())?;
+        self.cache_currencies(batch);
+        Ok(())
+    }
+    
+    fn cache_currencies(&self, batch: Vec<Currency>) {
+        let count = batch.len() as u64;
+        let mut changes = self.changes();
+        let mut currencies = self.currencies.write();
+        for currency in batch {
+            let code = currency.code.clone();
+            let evicted = currencies.insert(currency.code.clone(), currency);
+            self.record_evictions(&self.stats.evictions_by_type.currencies, evicted.len());
+            for (code, _) in evicted {
+                changes.note(CacheDataType::Currencies, CacheEventKind::Evicted, || code, 1);
+            }
+            changes.note(CacheDataType::Currencies, CacheEventKind::Inserted, || code, 1);
+        }
+        drop(currencies);
+        self.publish_changes(changes);
+        self.stats.writes.fetch_add(count, std::sync::atomic::Ordering::Relaxed);
+        debug!("Cached {} currencies", count);
+    }
+    
     /// Get currency from cache - O(1) lookup
     pub fn get_currency(&self, code: &str) -> Option<Currency> {
         let currencies = self.currencies.read();
-        if let Some(currency) = currencies.get(code) {
-            self.stats.hits.fetch_add(1, std::sync::atomic::Ordering::Relaxed);
-            Some(currency.clone())
-        } else {
-            self.stats.misses.fetch_add(1, std::sync::atomic::Ordering::Relaxed);
-            None
+        self.record_lookup(currencies.get(code).cloned())
+    }
+    
+    /// Get several currencies under one lock, in order
+    pub fn get_currencies(&self, codes: &[&str]) -> Vec<Option<Currency>> {
+        let currencies = self.currencies.read();
+        codes.iter().map(|code| self.record_lookup(currencies.get(*code).cloned())).collect()
+    }
+    
+    // Count a lookup as a hit or a miss
+    fn record_lookup<T>(&self, found: Option<T>) -> Option<T> {
+        let counter = if found.is_some() { &self.stats.hits } else { &self.stats.misses };
+        counter.fetch_add(1, std::sync::atomic::Ordering::Relaxed);
+        found
+    }
+    
+    /// Publish cache events on `message_bus`
+    pub fn with_message_bus(mut self, message_bus: Arc<MessageBus>) -> Self {
+        self.message_bus = Some(message_bus);
+        self
+    }
+    
+    fn changes(&self) -> CacheChanges {
+        CacheChanges {
+            events: self.message_bus.as_ref().map(|_| Vec::new()),
+            ts_event: unix_nanos_now(),
+        }
+    }
+    
+    fn publish_changes(&self, changes: CacheChanges) {
+        let (Some(bus), Some(events)) = (&self.message_bus, changes.events) else {
+            return;
+        };
+        for event in events {
+            bus.publish_from("cache", &cache_event_topic(event.data_type, event.kind), &event);
+        }
+    }
+    
+    // Count evictions in the total and in the counter of their data type
+    fn record_evictions(&self, by_type: &AtomicU64, count: usize) {
+        if count > 0 {
+            self.stats.evictions.fetch_add(count as u64, Ordering::Relaxed);
+            by_type.fetch_add(count as u64, Ordering::Relaxed);
         }
     }
     
     /// Add instrument to cache with automatic indexing
     pub fn add_instrument(&self, instrument: InstrumentAny) -> Result<(), CacheError> {
         let instrument_id = instrument.id();
-        let symbol = instrument.symbol().to_string();
-        let venue = instrument.venue().to_string();
-        
-        // Update main cache
+        self.add_instruments(vec![instrument])?;
+        debug!("Cached instrument: {}", instrument_id);
+        Ok(())
+    }
+    
+    /// Add several instruments, taking the instrument and index locks once,
+    /// written through to the database
+    pub fn add_instruments(&self, batch: Vec<InstrumentAny>) -> Result<(), CacheError> {
+        self.write_through(INSTRUMENT_DATA_TYPE, &batch, |instrument| instrument.id().to_string())?;
+        self.cache_instruments(batch);
+        Ok(())
+    }
+    
+    fn cache_instruments(&self, batch: Vec<InstrumentAny>) {
+        let count = batch.len() as u64;
+        let mut changes = self.changes();
         let mut instruments = self.instruments.write();
-        instruments.insert(instrument_id, instrument);
-        
-        // Update index
         let mut index = self.index.write();
-        index.instruments_by_symbol.insert(symbol, instrument_id);
-        index.instruments_by_venue
-            .entry(venue)
-            .or_insert_with(Vec::new)
-            .push(instrument_id);
+        for instrument in batch {
+            let instrument_id = instrument.id();
+            index.instruments_by_symbol.insert(instrument.symbol().to_string(), instrument_id);
+            let venue_instruments = index.instruments_by_venue.entry(instrument.venue().to_string()).or_default();
+            if !venue_instruments.contains(&instrument_id) {
+                venue_instruments.push(instrument_id);
+            }
+            instruments.insert(instrument_id, instrument);
+            changes.note(CacheDataType::Instruments, CacheEventKind::Inserted, || instrument_id.to_string(), 1);
+        }
+        drop((instruments, index));
+        self.publish_changes(changes);
         
-        self.stats.writes.fetch_add(1, std::sync::atomic::Ordering::Relaxed);
-        debug!("Cached instrument: {}", instrument_id);
-        Ok(())
+        self.stats.writes.fetch_add(count, std::sync::atomic::Ordering::Relaxed);
     }
     
     /// Get instrument from cache - O(1) lookup
     pub fn get_instrument(&self, instrument_id: &InstrumentId) -> Option<InstrumentAny> {
         let instruments = self.instruments.read();
-        if let Some(instrument) = instruments.get(instrument_id) {
-            self.stats.hits.fetch_add(1, std::sync::atomic::Ordering::Relaxed);
-            Some(instrument.clone())
-        } else {
-            self.stats.misses.fetch_add(1, std::sync::atomic::Ordering::Relaxed);
-            None
-        }
+        self.record_lookup(instruments.get(instrument_id).cloned())
+    }
+    
+    /// Get several instruments under one lock, in order
+    pub fn get_instruments(&self, instrument_ids: &[InstrumentId]) -> Vec<Option<InstrumentAny>> {
+        let instruments = self.instruments.read();
+        instrument_ids.iter().map(|id| self.record_lookup(instruments.get(id).cloned())).collect()
+    }
+    
+    /// Get every cached listing of a symbol across venues
+    pub fn get_instruments_by_symbol(&self, symbol: &str) -> Vec<InstrumentAny> {
+        self.instruments
+            .read()
+            .values()
+            .filter(|instrument| instrument.symbol() == symbol)
+            .cloned()
+            .collect()
     }
     
     /// Add order book to cache
     pub fn add_order_book(&self, book: OrderBook) -> Result<(), CacheError> {
         let instrument_id = book.instrument_id;
-        let mut books = self.books.write();
-        books.insert(instrument_id, book);
+        let mut changes = self.changes();
+        let evicted = self.books.write().insert(instrument_id, book);
+        self.record_evictions(&self.stats.evictions_by_type.books, evicted.len());
+        for (evicted_id, _) in evicted {
+            changes.note(CacheDataType::Books, CacheEventKind::Evicted, || evicted_id.to_string(), 1);
+        }
+        changes.note(CacheDataType::Books, CacheEventKind::Inserted, || instrument_id.to_string(), 1);
+        self.publish_changes(changes);
         self.stats.writes.fetch_add(1, std::sync::atomic::Ordering::Relaxed);
         debug!("Cached order book: {}", instrument_id);
         Ok(())
@@ -238,22 +783,52 @@ impl Cache {
     
     /// Add quote tick with automatic deque management
     pub fn add_quote_tick(&self, tick: QuoteTick) -> Result<(), CacheError> {
-        let instrument_id = tick.instrument_id;
+        self.add_quote_ticks(vec![tick])
+    }
+    
+    /// Add several quote ticks under one lock, in order
+    pub fn add_quote_ticks(&self, ticks: Vec<QuoteTick>) -> Result<(), CacheError> {
+        let mut changes = self.changes();
         let mut quotes = self.quotes.write();
-        
-        let quote_deque = quotes.entry(instrument_id).or_insert_with(VecDeque::new);
-        quote_deque.push_back(tick);
-        
-        // Implement LRU eviction if queue is too long
-        if quote_deque.len() > self.config.max_items_per_type {
-            quote_deque.pop_front();
-            self.stats.evictions.fetch_add(1, std::sync::atomic::Ordering::Relaxed);
+        for tick in ticks {
+            let instrument_id = tick.instrument_id;
+            let (quote_deque, evicted) = quotes.get_or_insert_with(instrument_id, VecDeque::new);
+            quote_deque.push_back(tick);
+            self.trim_history(CacheDataType::Quotes, &instrument_id, quote_deque, evicted, &mut changes);
         }
-        
-        self.stats.writes.fetch_add(1, std::sync::atomic::Ordering::Relaxed);
+        drop(quotes);
+        self.publish_changes(changes);
         Ok(())
     }
     
+    // Count a write to the history of `key`, dropping its oldest item once it
+    // is over capacity, and the histories evicted to make room for it
+    fn trim_history<K: std::fmt::Display, T>(
+        &self,
+        data_type: CacheDataType,
+        key: &K,
+        history: &mut VecDeque<T>,
+        evicted: Vec<(K, VecDeque<T>)>,
+        changes: &mut CacheChanges,
+    ) {
+        let evictions = match data_type {
+            CacheDataType::Quotes => &self.stats.evictions_by_type.quotes,
+            CacheDataType::Trades => &self.stats.evictions_by_type.trades,
+            _ => &self.stats.evictions_by_type.bars,
+        };
+        for (evicted_key, evicted) in evicted {
+            self.record_evictions(evictions, evicted.len());
+            changes.note(data_type, CacheEventKind::Evicted, || evicted_key.to_string(), evicted.len());
+        }
+        changes.note(data_type, CacheEventKind::Inserted, || key.to_string(), 1);
+        if history.len() > self.config.max_items_per_type {
+            history.pop_front();
+            self.record_evictions(evictions, 1);
+            changes.note(data_type, CacheEventKind::Evicted, || key.to_string(), 1);
+        }
+        self.stats.writes.fetch_add(1, std::sync::atomic::Ordering::Relaxed);
+    }
+    
     /// Get recent quotes for instrument
     pub fn get_quotes(&self, instrument_id: &InstrumentId, limit: Option<usize>) -> Vec<QuoteTick> {
         let quotes = self.quotes.read();
@@ -272,21 +847,30 @@ impl Cache {
         }
     }
     
+    /// Get quotes with `from_ts <= ts_event <= to_ts` in ascending time
+    /// order, found by binary search as quotes are added in time order
+    pub fn get_quotes_range(&self, instrument_id: &InstrumentId, from_ts: UnixNanos, to_ts: UnixNanos) -> Vec<QuoteTick> {
+        let quotes = self.quotes.read();
+        self.history_range(quotes.get(instrument_id), |q| q.ts_event, Some(from_ts), Some(to_ts), None)
+    }
+    
     /// Add trade tick with automatic deque management  
     pub fn add_trade_tick(&self, tick: TradeTick) -> Result<(), CacheError> {
-        let instrument_id = tick.instrument_id;
+        self.add_trade_ticks(vec![tick])
+    }
+    
+    /// Add several trade ticks under one lock, in order
+    pub fn add_trade_ticks(&self, ticks: Vec<TradeTick>) -> Result<(), CacheError> {
+        let mut changes = self.changes();
         let mut trades = self.trades.write();
-        
-        let trade_deque = trades.entry(instrument_id).or_insert_with(VecDeque::new);
-        trade_deque.push_back(tick);
-        
-        // Implement LRU eviction if queue is too long
-        if trade_deque.len() > self.config.max_items_per_type {
-            trade_deque.pop_front();
-            self.stats.evictions.fetch_add(1, std::sync::atomic::Ordering::Relaxed);
+        for tick in ticks {
+            let instrument_id = tick.instrument_id;
+            let (trade_deque, evicted) = trades.get_or_insert_with(instrument_id, VecDeque::new);
+            trade_deque.push_back(tick);
+            self.trim_history(CacheDataType::Trades, &instrument_id, trade_deque, evicted, &mut changes);
         }
-        
-        self.stats.writes.fetch_add(1, std::sync::atomic::Ordering::Relaxed);
+        drop(trades);
+        self.publish_changes(changes);
         Ok(())
     }
     
@@ -308,6 +892,297 @@ impl Cache {
         }
     }
     
+    /// Get trades with `from_ts <= ts_event <= to_ts` in ascending time
+    /// order, found by binary search as trades are added in time order
+    pub fn get_trades_range(&self, instrument_id: &InstrumentId, from_ts: UnixNanos, to_ts: UnixNanos) -> Vec<TradeTick> {
+        let trades = self.trades.read();
+        self.history_range(trades.get(instrument_id), |t| t.ts_event, Some(from_ts), Some(to_ts), None)
+    }
+    
+    /// Add a bar, keeping each bar type's history ordered by event time.
+    ///
+    /// A bar with the same event time as a stored bar replaces it, so
+    /// history can be reloaded over live data without duplicates.
+    pub fn add_bar(&self, bar: Bar) -> Result<(), CacheError> {
+        self.add_bars(vec![bar])
+    }
+    
+    /// Add several bars under one lock, as `add_bar` would one by one
+    pub fn add_bars(&self, batch: Vec<Bar>) -> Result<(), CacheError> {
+        let mut changes = self.changes();
+        let mut bars = self.bars.write();
+        for bar in batch {
+            let bar_type = bar.bar_type.clone();
+            let (bar_deque, evicted) = bars.get_or_insert_with(bar.bar_type.clone(), VecDeque::new);
+            
+            let idx = bar_deque.partition_point(|b| b.ts_event < bar.ts_event);
+            match bar_deque.get_mut(idx) {
+                Some(existing) if existing.ts_event == bar.ts_event => *existing = bar,
+                _ => bar_deque.insert(idx, bar),
+            }
+            self.trim_history(CacheDataType::Bars, &bar_type, bar_deque, evicted, &mut changes);
+        }
+        drop(bars);
+        self.publish_changes(changes);
+        Ok(())
+    }
+    
+    /// Get bars with `from_ts <= ts_event <= to_ts` in ascending time order.
+    ///
+    /// With a `limit`, the most recent bars in the range are returned.
+    pub fn get_bars(
+        &self,
+        bar_type: &BarType,
+        from_ts: Option<UnixNanos>,
+        to_ts: Option<UnixNanos>,
+        limit: Option<usize>,
+    ) -> Vec<Bar> {
+        let bars = self.bars.read();
+        self.history_range(bars.get(bar_type), |b| b.ts_event, from_ts, to_ts, limit)
+    }
+    
+    // Items of a time-ordered history within the bounds, the most recent
+    // `limit` of them when given, counting a hit or a miss
+    fn history_range<T: Clone>(
+        &self,
+        history: Option<&VecDeque<T>>,
+        ts_event: fn(&T) -> UnixNanos,
+        from_ts: Option<UnixNanos>,
+        to_ts: Option<UnixNanos>,
+        limit: Option<usize>,
+    ) -> Vec<T> {
+        let Some(history) = history else {
+            self.stats.misses.fetch_add(1, std::sync::atomic::Ordering::Relaxed);
+            return Vec::new();
+        };
+        self.stats.hits.fetch_add(1, std::sync::atomic::Ordering::Relaxed);
+        
+        let start = from_ts.map_or(0, |ts| history.partition_point(|item| ts_event(item) < ts));
+        let end = to_ts.map_or(history.len(), |ts| history.partition_point(|item| ts_event(item) <= ts));
+        if start >= end {
+            return Vec::new();
+        }
+        
+        let start = limit.map_or(start, |limit| start.max(end.saturating_sub(limit)));
+        history.range(start..end).cloned().collect()
+    }
+    
+    /// Load from `database` with `load_all` or `warm_up_from_database`.
+    /// With `enable_persistence` set, currencies, instruments and account,
+    /// order and position updates are also written through to it before
+    /// being cached, and it is flushed every `flush_interval_ms` and when
+    /// the cache is dropped.
+    pub fn with_database(mut self, database: Box<dyn CacheDatabaseAdapter>) -> Self {
+        let database: Arc<dyn CacheDatabaseAdapter> = Arc::from(database);
+        self.flusher = None;
+        if self.config.enable_persistence && self.config.flush_interval_ms > 0 {
+            self.flusher = start_flusher(Arc::clone(&database), Duration::from_millis(self.config.flush_interval_ms));
+        }
+        self.database = Some(database);
+        self
+    }
+    
+    /// Add or update an account, written through to the database
+    pub fn add_account(&self, account: Account) -> Result<(), CacheError> {
+        let batch = vec![account];
+        self.write_through(ACCOUNT_DATA_TYPE, &batch, |account| account.id.clone())?;
+        self.cache_accounts(batch);
+        Ok(())
+    }
+    
+    fn cache_accounts(&self, batch: Vec<Account>) {
+        let count = batch.len() as u64;
+        let mut changes = self.changes();
+        for account in &batch {
+            changes.note(CacheDataType::Accounts, CacheEventKind::Inserted, || account.id.clone(), 1);
+        }
+        let mut accounts = self.accounts.write();
+        for account in batch {
+            accounts.insert(account.id.clone(), account);
+        }
+        drop(accounts);
+        self.publish_changes(changes);
+        self.stats.writes.fetch_add(count, Ordering::Relaxed);
+    }
+    
+    /// Add or update an order, written through to the database
+    pub fn add_order(&self, order: Order) -> Result<(), CacheError> {
+        let batch = vec![order];
+        self.write_through(ORDER_DATA_TYPE, &batch, |order| order.id.clone())?;
+        self.cache_orders(batch);
+        Ok(())
+    }
+    
+    fn cache_orders(&self, batch: Vec<Order>) {
+        let count = batch.len() as u64;
+        let mut changes = self.changes();
+        for order in &batch {
+            changes.note(CacheDataType::Orders, CacheEventKind::Inserted, || order.id.clone(), 1);
+        }
+        let mut orders = self.orders.write();
+        let mut index = self.index.write();
+        for order in batch {
+            index.index_order(&order, orders.get(&order.id));
+            orders.insert(order.id.clone(), order);
+        }
+        drop((orders, index));
+        self.publish_changes(changes);
+        self.stats.writes.fetch_add(count, Ordering::Relaxed);
+    }
+    
+    /// Add or update a position, written through to the database
+    pub fn add_position(&self, position: Position) -> Result<(), CacheError> {
+        let batch = vec![position];
+        self.write_through(POSITION_DATA_TYPE, &batch, |position| position.id.clone())?;
+        self.cache_positions(batch);
+        Ok(())
+    }
+    
+    fn cache_positions(&self, batch: Vec<Position>) {
+        let count = batch.len() as u64;
+        let mut changes = self.changes();
+        for position in &batch {
+            changes.note(CacheDataType::Positions, CacheEventKind::Inserted, || position.id.clone(), 1);
+        }
+        let mut positions = self.positions.write();
+        let mut index = self.index.write();
+        for position in batch {
+            index.index_position(&position, positions.get(&position.id));
+            positions.insert(position.id.clone(), position);
+        }
+        drop((positions, index));
+        self.publish_changes(changes);
+        self.stats.writes.fetch_add(count, Ordering::Relaxed);
+    }
+    
+    /// Get account from cache - O(1) lookup
+    pub fn get_account(&self, account_id: &str) -> Option<Account> {
+        self.record_lookup(self.accounts.read().get(account_id).cloned())
+    }
+    
+    /// Get order from cache - O(1) lookup
+    pub fn get_order(&self, order_id: &str) -> Option<Order> {
+        self.record_lookup(self.orders.read().get(order_id).cloned())
+    }
+    
+    /// Get every cached order for an instrument
+    pub fn orders_for_instrument(&self, instrument_id: &InstrumentId) -> Vec<Order> {
+        let orders = self.orders.read();
+        indexed(self.index.read().orders_by_instrument.get(instrument_id), &orders)
+    }
+    
+    /// Get every cached order of a strategy
+    pub fn orders_for_strategy(&self, strategy_id: &str) -> Vec<Order> {
+        let orders = self.orders.read();
+        indexed(self.index.read().orders_by_strategy.get(strategy_id), &orders)
+    }
+    
+    /// Get position from cache - O(1) lookup
+    pub fn get_position(&self, position_id: &str) -> Option<Position> {
+        self.record_lookup(self.positions.read().get(position_id).cloned())
+    }
+    
+    /// Get every cached position of a strategy, open or closed
+    pub fn positions_for_strategy(&self, strategy_id: &str) -> Vec<Position> {
+        let positions = self.positions.read();
+        indexed(self.index.read().positions_by_strategy.get(strategy_id), &positions)
+    }
+    
+    /// Get open positions, of one instrument when given
+    pub fn positions_open(&self, instrument_id: Option<&InstrumentId>) -> Vec<Position> {
+        let positions = self.positions.read();
+        let index = self.index.read();
+        let open = |id: &String| index.positions_open.contains(id).then(|| positions.get(id).cloned()).flatten();
+        match instrument_id {
+            Some(instrument_id) => index
+                .positions_by_instrument
+                .get(instrument_id)
+                .map(|ids| ids.iter().filter_map(open).collect())
+                .unwrap_or_default(),
+            None => index.positions_open.iter().filter_map(|id| positions.get(id).cloned()).collect(),
+        }
+    }
+    
+    // Store `batch` in the database, if persisting to one, so updates the
+    // database rejects are not cached either
+    fn write_through<T: Serialize>(&self, data_type: &str, batch: &[T], key: impl Fn(&T) -> String) -> Result<(), CacheError> {
+        let Some(database) = self.database.as_ref().filter(|_| self.config.enable_persistence) else {
+            return Ok(());
+        };
+        let timestamp = unix_nanos_now();
+        let entries = batch
+            .iter()
+            .map(|value| {
+                Ok(CacheEntry {
+                    key: format!("{}:{}", data_type, key(value)),
+                    data_type: data_type.to_string(),
+                    data: bincode::serialize(value)?,
+                    timestamp,
+                    access_count: 0,
+                })
+            })
+            .collect::<Result<Vec<_>, CacheError>>()?;
+        database.write_batch(&entries)
+    }
+    
+    /// Load everything stored in the database, returning how many entries
+    /// were loaded
+    pub fn load_all(&self) -> Result<usize, CacheError> {
+        let Some(database) = &self.database else {
+            return Ok(0);
+        };
+        let entries = database.read_all()?;
+        let known = [CURRENCY_DATA_TYPE, INSTRUMENT_DATA_TYPE, ACCOUNT_DATA_TYPE, ORDER_DATA_TYPE, POSITION_DATA_TYPE];
+        for entry in entries.iter().filter(|entry| !known.contains(&entry.data_type.as_str())) {
+            warn!("Skipping cache entry {} of unknown type {}", entry.key, entry.data_type);
+        }
+        let currencies = decode_entries(&entries, CURRENCY_DATA_TYPE)?;
+        let instruments = decode_entries(&entries, INSTRUMENT_DATA_TYPE)?;
+        let accounts = decode_entries(&entries, ACCOUNT_DATA_TYPE)?;
+        let orders = decode_entries(&entries, ORDER_DATA_TYPE)?;
+        let positions = decode_entries(&entries, POSITION_DATA_TYPE)?;
+        let loaded = currencies.len() + instruments.len() + accounts.len() + orders.len() + positions.len();
+        self.cache_currencies(currencies);
+        self.cache_instruments(instruments);
+        self.cache_accounts(accounts);
+        self.cache_orders(orders);
+        self.cache_positions(positions);
+        info!("Loaded {} cache entries from the database", loaded);
+        Ok(loaded)
+    }
+    
+    /// Preload currencies, instruments, open orders and open positions from
+    /// `source` at startup. Nothing is written back to the database.
+    pub fn warm_up<S: CacheWarmUpSource + ?Sized>(&self, source: &S) -> Result<WarmUpReport, CacheError> {
+        let (currencies, instruments) = (source.currencies()?, source.instruments()?);
+        let (orders, positions) = (source.open_orders()?, source.open_positions()?);
+        let report = WarmUpReport {
+            currencies: currencies.len(),
+            instruments: instruments.len(),
+            orders: orders.len(),
+            positions: positions.len(),
+        };
+        self.cache_currencies(currencies);
+        self.cache_instruments(instruments);
+        self.cache_orders(orders);
+        self.cache_positions(positions);
+        info!("Warmed up cache: {:?}", report);
+        Ok(report)
+    }
+    
+    /// Preload from the cache's own database, if it has one
+    pub fn warm_up_from_database(&self) -> Result<WarmUpReport, CacheError> {
+        match &self.database {
+            Some(database) => self.warm_up(&StoredEntries::read(database.as_ref())?),
+            None => Ok(WarmUpReport::default()),
+        }
+    }
+    
+    /// Flush the database's buffered writes
+    pub fn flush(&self) -> Result<(), CacheError> {
+        self.database.as_ref().map_or(Ok(()), |database| database.flush())
+    }
+    
     /// Get cache statistics for monitoring
     pub fn get_stats(&self) -> CacheStatistics {
         CacheStatistics {
@@ -316,11 +1191,16 @@ impl Cache {
             total_misses: self.stats.misses.load(std::sync::atomic::Ordering::Relaxed),
             total_writes: self.stats.writes.load(std::sync::atomic::Ordering::Relaxed),
             total_evictions: self.stats.evictions.load(std::sync::atomic::Ordering::Relaxed),
+            evictions_by_type: self.stats.evictions_by_type.snapshot(),
             currencies_count: self.currencies.read().len(),
             instruments_count: self.instruments.read().len(),
             books_count: self.books.read().len(),
             quotes_count: self.quotes.read().values().map(|q| q.len()).sum(),
             trades_count: self.trades.read().values().map(|t| t.len()).sum(),
+            bars_count: self.bars.read().values().map(|b| b.len()).sum(),
+            accounts_count: self.accounts.read().len(),
+            orders_count: self.orders.read().len(),
+            positions_count: self.positions.read().len(),
         }
     }
     
@@ -348,15 +1228,20 @@ pub struct CacheStatistics {
     pub total_misses: u64,
     pub total_writes: u64,
     pub total_evictions: u64,
+    pub evictions_by_type: EvictionCounts,
     pub currencies_count: usize,
     pub instruments_count: usize,
     pub books_count: usize,
     pub quotes_count: usize,
     pub trades_count: usize,
+    pub bars_count: usize,
+    pub accounts_count: usize,
+    pub orders_count: usize,
+    pub positions_count: usize,
 }
 
 // Placeholder types - these would be implemented in their respective modules
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct Currency {
     pub code: String,
     pub precision: u8,
@@ -364,52 +1249,123 @@ pub struct Currency {
     pub name: String,
 }
 
-#[derive(Debug, Clone)]
+/// Instrument metadata used when interpreting market data
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct InstrumentAny {
-    // Placeholder - actual implementation would be an enum of different instrument types
+    pub symbol: String,
+    pub venue: String,
+    /// Decimal places of quoted prices
+    pub price_precision: u8,
+    /// Decimal places of traded sizes
+    pub size_precision: u8,
+    /// Minimum price increment
+    pub tick_size: f64,
+    /// Minimum size increment
+    pub lot_size: f64,
+    /// Contract multiplier (1.0 for spot)
+    pub multiplier: f64,
 }
 
 impl InstrumentAny {
+    pub fn new(
+        symbol: &str,
+        venue: &str,
+        price_precision: u8,
+        size_precision: u8,
+        tick_size: f64,
+        lot_size: f64,
+        multiplier: f64,
+    ) -> Self {
+        Self {
+            symbol: symbol.to_string(),
+            venue: venue.to_string(),
+            price_precision,
+            size_precision,
+            tick_size,
+            lot_size,
+            multiplier,
+        }
+    }
+
     pub fn id(&self) -> InstrumentId {
-        // For now, return a placeholder ID
-        // In a real implementation, this would extract the ID from the enum variant
-        InstrumentId::new(1)
+        InstrumentId::from_symbol_venue(&self.symbol, &self.venue)
     }
     
     pub fn symbol(&self) -> &str {
-        // Placeholder implementation
-        "PLACEHOLDER"
+        &self.symbol
     }
     
     pub fn venue(&self) -> &str {
-        // Placeholder implementation  
-        "PLACEHOLDER"
+        &self.venue
+    }
+
+    /// Round a price to the nearest tick, then to the price precision
+    pub fn round_price(&self, price: f64) -> f64 {
+        let price = if self.tick_size > 0.0 {
+            (price / self.tick_size).round() * self.tick_size
+        } else {
+            price
+        };
+        round_to(price, self.price_precision)
+    }
+
+    /// Round a size down to a whole number of lots, then to the size precision
+    pub fn round_size(&self, size: f64) -> f64 {
+        let size = if self.lot_size > 0.0 {
+            // Tolerate representation error so 0.3 / 0.1 is three lots
+            (size / self.lot_size + 1e-9).floor() * self.lot_size
+        } else {
+            size
+        };
+        round_to(size, self.size_precision)
     }
 }
 
-#[derive(Debug, Clone)]
+fn round_to(value: f64, precision: u8) -> f64 {
+    let scale = 10f64.powi(precision as i32);
+    (value * scale).round() / scale
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct Account {
     pub id: String,
     pub balance: f64,
 }
 
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct Order {
     pub id: String,
     pub instrument_id: InstrumentId,
+    pub strategy_id: Option<String>,
     pub side: String,
     pub quantity: f64,
     pub price: Option<f64>,
+    pub status: OrderStatus,
 }
 
-#[derive(Debug, Clone)]
+impl Order {
+    /// Whether the order can still be filled
+    pub fn is_open(&self) -> bool {
+        !matches!(self.status, OrderStatus::Filled | OrderStatus::Cancelled | OrderStatus::Rejected | OrderStatus::Expired)
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct Position {
     pub id: String,
     pub instrument_id: InstrumentId,
+    pub strategy_id: Option<String>,
     pub quantity: f64,
     pub avg_price: f64,
 }
 
+impl Position {
+    /// Whether the position holds a nonzero quantity
+    pub fn is_open(&self) -> bool {
+        self.quantity != 0.0
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -462,4 +1418,479 @@ mod tests {
         assert_eq!(stats.total_misses, 1);
         assert_eq!(stats.hit_ratio, 0.0);
     }
+    
+    #[test]
+    fn test_bar_range_query() {
+        let cache = Cache::new(CacheConfig::default());
+        let bar_type = BarType {
+            instrument_id: InstrumentId::new(1),
+            bar_spec: BarSpecification {
+                step: 1,
+                aggregation: BarAggregation::Time(60_000_000_000),
+            },
+        };
+        
+        // Insert out of order, with one bar reloaded
+        for ts in [3, 1, 4, 2, 5, 3] {
+            cache.add_bar(Bar {
+                bar_type: bar_type.clone(),
+                open: ts as f64,
+                high: ts as f64,
+                low: ts as f64,
+                close: ts as f64,
+                volume: 1.0,
+                ts_event: ts,
+                ts_init: ts,
+            }).unwrap();
+        }
+        
+        let all: Vec<_> = cache.get_bars(&bar_type, None, None, None).iter().map(|b| b.ts_event).collect();
+        assert_eq!(all, vec![1, 2, 3, 4, 5]);
+        
+        let ranged: Vec<_> = cache.get_bars(&bar_type, Some(2), Some(4), None).iter().map(|b| b.ts_event).collect();
+        assert_eq!(ranged, vec![2, 3, 4]);
+        
+        let limited: Vec<_> = cache.get_bars(&bar_type, Some(2), None, Some(2)).iter().map(|b| b.ts_event).collect();
+        assert_eq!(limited, vec![4, 5]);
+        
+        assert!(cache.get_bars(&bar_type, Some(6), None, None).is_empty());
+        assert_eq!(cache.get_stats().bars_count, 5);
+    }
+    
+    #[test]
+    fn test_batch_operations() {
+        let cache = Cache::new(CacheConfig { max_items_per_type: 2, ..CacheConfig::default() });
+        let currency = |code: &str| Currency { code: code.to_string(), precision: 2, iso4217: 0, name: code.to_string() };
+        cache.add_currencies(vec![currency("USD"), currency("EUR")]).unwrap();
+        let found: Vec<Option<String>> = cache.get_currencies(&["EUR", "JPY", "USD"]).into_iter().map(|c| c.map(|c| c.code)).collect();
+        assert_eq!(found, vec![Some("EUR".to_string()), None, Some("USD".to_string())]);
+        
+        let btc = InstrumentAny::new("BTCUSDT", "BINANCE", 2, 6, 0.01, 0.000001, 1.0);
+        let eth = InstrumentAny::new("ETHUSDT", "BINANCE", 2, 5, 0.01, 0.00001, 1.0);
+        let (btc_id, eth_id) = (btc.id(), eth.id());
+        cache.add_instruments(vec![btc, eth]).unwrap();
+        let found = cache.get_instruments(&[eth_id, btc_id]);
+        assert_eq!(found[0].as_ref().map(|i| i.symbol()), Some("ETHUSDT"));
+        assert_eq!(cache.index.read().instruments_by_venue["BINANCE"], vec![btc_id, eth_id]);
+        
+        // Histories are trimmed as they would be by single adds
+        let trade = |instrument_id: InstrumentId, ts: UnixNanos| TradeTick {
+            instrument_id,
+            price: 100.0,
+            size: 1.0,
+            aggressor_side: AggressorSide::Buyer,
+            trade_id: ts.to_string(),
+            ts_event: ts,
+            ts_init: ts,
+        };
+        cache.add_trade_ticks((1..=3).map(|ts| trade(btc_id, ts)).chain([trade(eth_id, 9)]).collect()).unwrap();
+        let recent: Vec<UnixNanos> = cache.get_trades(&btc_id, None).iter().map(|t| t.ts_event).collect();
+        assert_eq!(recent, vec![3, 2]);
+        
+        let stats = cache.get_stats();
+        assert_eq!((stats.total_hits, stats.total_misses, stats.total_evictions), (5, 1, 1));
+        assert_eq!((stats.total_writes, stats.trades_count), (8, 3));
+    }
+    
+    #[test]
+    fn test_eviction_policies() {
+        let currency = |code: &str| Currency { code: code.to_string(), precision: 2, iso4217: 0, name: code.to_string() };
+        let cached = |cache: &Cache| {
+            let mut codes: Vec<String> = cache.currencies.read().values().map(|c| c.code.clone()).collect();
+            codes.sort();
+            codes
+        };
+        for (policy, kept) in [
+            (EvictionPolicy::LRU, ["JPY", "USD"]),
+            (EvictionPolicy::FIFO, ["EUR", "USD"]),
+            (EvictionPolicy::LFU, ["EUR", "USD"]),
+        ] {
+            let cache = Cache::new(CacheConfig { max_items_per_type: 2, eviction_policy: policy, ..CacheConfig::default() });
+            cache.add_currencies(vec![currency("JPY"), currency("EUR")]).unwrap();
+            cache.get_currency("EUR");
+            cache.get_currency("EUR");
+            cache.get_currency("JPY");
+            cache.add_currency(currency("USD")).unwrap();
+            assert_eq!(cached(&cache), kept, "{:?}", policy);
+            assert_eq!(cache.get_stats().evictions_by_type.currencies, 1);
+        }
+        
+        // Instruments are never evicted, and evicted histories count each tick
+        let cache = Cache::new(CacheConfig { max_items_per_type: 1, ..CacheConfig::default() });
+        let btc = InstrumentAny::new("BTCUSDT", "BINANCE", 2, 6, 0.01, 0.000001, 1.0);
+        let eth = InstrumentAny::new("ETHUSDT", "BINANCE", 2, 5, 0.01, 0.00001, 1.0);
+        let (btc_id, eth_id) = (btc.id(), eth.id());
+        cache.add_instruments(vec![btc, eth]).unwrap();
+        assert!(cache.get_instrument(&btc_id).is_some());
+        assert_eq!(cache.index.read().instruments_by_venue["BINANCE"], vec![btc_id, eth_id]);
+        
+        let quote = |instrument_id: InstrumentId, ts: UnixNanos| QuoteTick {
+            instrument_id,
+            bid_price: 1.0,
+            ask_price: 1.0,
+            bid_size: 1.0,
+            ask_size: 1.0,
+            ts_event: ts,
+            ts_init: ts,
+        };
+        cache.add_quote_ticks(vec![quote(btc_id, 1), quote(btc_id, 2), quote(eth_id, 3)]).unwrap();
+        assert!(cache.get_quotes(&btc_id, None).is_empty());
+        assert_eq!(cache.get_stats().evictions_by_type.quotes, 2);
+        assert_eq!(cache.get_stats().total_evictions, 2);
+    }
+    
+    #[test]
+    fn test_lookups_share_read_lock() {
+        let currency = |code: &str| Currency { code: code.to_string(), precision: 2, iso4217: 0, name: code.to_string() };
+        let cache = Cache::new(CacheConfig { max_items_per_type: 2, ..CacheConfig::default() });
+        cache.add_currencies(vec![currency("JPY"), currency("EUR")]).unwrap();
+        {
+            // Would deadlock if a lookup took the write lock
+            let _reader = cache.currencies.read();
+            assert!(cache.get_currency("JPY").is_some());
+        }
+        // The use under the read lock still counts for LRU
+        cache.add_currency(currency("USD")).unwrap();
+        assert!(cache.get_currency("JPY").is_some());
+        assert!(cache.get_currency("EUR").is_none());
+    }
+    
+    /// Keeps the latest entry of each key, failing writes once `fail` is set
+    #[derive(Default)]
+    struct MemoryDatabase {
+        entries: std::sync::Mutex<indexmap::IndexMap<String, CacheEntry>>,
+        fail: std::sync::atomic::AtomicBool,
+        flushes: AtomicU64,
+        reads: AtomicU64,
+    }
+    
+    fn persisting() -> CacheConfig {
+        CacheConfig { enable_persistence: true, ..CacheConfig::default() }
+    }
+    
+    impl CacheDatabaseAdapter for std::sync::Arc<MemoryDatabase> {
+        fn write_batch(&self, data: &[CacheEntry]) -> Result<(), CacheError> {
+            if self.fail.load(Ordering::Relaxed) {
+                return Err(CacheError::Database("unavailable".to_string()));
+            }
+            let mut entries = self.entries.lock().unwrap();
+            for entry in data {
+                entries.insert(entry.key.clone(), entry.clone());
+            }
+            Ok(())
+        }
+        
+        fn read_by_key(&self, key: &str) -> Result<Option<CacheEntry>, CacheError> {
+            Ok(self.entries.lock().unwrap().get(key).cloned())
+        }
+        
+        fn read_all(&self) -> Result<Vec<CacheEntry>, CacheError> {
+            self.reads.fetch_add(1, Ordering::Relaxed);
+            Ok(self.entries.lock().unwrap().values().cloned().collect())
+        }
+        
+        fn flush(&self) -> Result<(), CacheError> {
+            self.flushes.fetch_add(1, Ordering::Relaxed);
+            Ok(())
+        }
+    }
+    
+    /// Stores nothing and cannot list its entries
+    struct KeyValueStore;
+    
+    impl CacheDatabaseAdapter for KeyValueStore {
+        fn write_batch(&self, _data: &[CacheEntry]) -> Result<(), CacheError> {
+            Ok(())
+        }
+        
+        fn read_by_key(&self, _key: &str) -> Result<Option<CacheEntry>, CacheError> {
+            Ok(None)
+        }
+        
+        fn flush(&self) -> Result<(), CacheError> {
+            Ok(())
+        }
+    }
+    
+    #[test]
+    fn test_persistence_follows_config() {
+        let database = std::sync::Arc::new(MemoryDatabase::default());
+        let account = Account { id: "A-1".to_string(), balance: 1.0 };
+        
+        // Without persistence the database is only read
+        let reading = Cache::new(CacheConfig::default()).with_database(Box::new(database.clone()));
+        reading.add_account(account.clone()).unwrap();
+        assert!(database.entries.lock().unwrap().is_empty());
+        drop(reading);
+        
+        let writing = Cache::new(CacheConfig { flush_interval_ms: 5, ..persisting() }).with_database(Box::new(database.clone()));
+        writing.add_account(account).unwrap();
+        assert_eq!(database.entries.lock().unwrap().len(), 1);
+        let deadline = std::time::Instant::now() + Duration::from_secs(5);
+        while database.flushes.load(Ordering::Relaxed) < 2 {
+            assert!(std::time::Instant::now() < deadline, "database was not flushed");
+            std::thread::sleep(Duration::from_millis(1));
+        }
+        
+        drop(writing);
+        
+        // Flushed once more on drop, however long the interval
+        let idle_database = std::sync::Arc::new(MemoryDatabase::default());
+        let idle = Cache::new(CacheConfig { flush_interval_ms: 3_600_000, ..persisting() }).with_database(Box::new(idle_database.clone()));
+        drop(idle);
+        while idle_database.flushes.load(Ordering::Relaxed) < 1 {
+            assert!(std::time::Instant::now() < deadline, "database was not flushed on drop");
+            std::thread::sleep(Duration::from_millis(1));
+        }
+        
+        // Adapters that cannot list their entries cannot be loaded from
+        let unlisted = Cache::new(persisting()).with_database(Box::new(KeyValueStore));
+        assert!(matches!(unlisted.load_all(), Err(CacheError::Database(_))));
+    }
+    
+    #[test]
+    fn test_write_through_and_load_all() {
+        let database = std::sync::Arc::new(MemoryDatabase::default());
+        let cache = Cache::new(persisting()).with_database(Box::new(database.clone()));
+        let order = |quantity: f64| Order {
+            id: "O-1".to_string(),
+            instrument_id: InstrumentId::new(1),
+            strategy_id: None,
+            side: "BUY".to_string(),
+            quantity,
+            price: Some(100.0),
+            status: OrderStatus::Accepted,
+        };
+        cache.add_account(Account { id: "A-1".to_string(), balance: 1_000.0 }).unwrap();
+        cache.add_order(order(1.0)).unwrap();
+        cache.add_order(order(2.0)).unwrap();
+        cache.add_position(Position { id: "P-1".to_string(), instrument_id: InstrumentId::new(1), strategy_id: None, quantity: 2.0, avg_price: 100.0 }).unwrap();
+        assert!(database.read_by_key("order:O-1").unwrap().is_some());
+        
+        // A rejected write leaves the cache as it was
+        database.fail.store(true, Ordering::Relaxed);
+        assert!(cache.add_order(order(3.0)).is_err());
+        assert_eq!(cache.orders.read()["O-1"].quantity, 2.0);
+        
+        let restarted = Cache::new(persisting()).with_database(Box::new(database.clone()));
+        assert_eq!(restarted.load_all().unwrap(), 3);
+        assert_eq!(restarted.orders.read()["O-1"].quantity, 2.0);
+        assert_eq!(restarted.accounts.read()["A-1"].balance, 1_000.0);
+        assert_eq!(restarted.positions.read()["P-1"].avg_price, 100.0);
+        assert_eq!(restarted.positions_open(Some(&InstrumentId::new(1))).len(), 1);
+    }
+    
+    /// Open orders and positions as a venue reports them
+    struct VenueReconciliation;
+    
+    impl CacheWarmUpSource for VenueReconciliation {
+        fn open_orders(&self) -> Result<Vec<Order>, CacheError> {
+            Ok(vec![Order { id: "V-1".to_string(), instrument_id: InstrumentId::new(2), strategy_id: None, side: "SELL".to_string(), quantity: 1.0, price: None, status: OrderStatus::Accepted }])
+        }
+    }
+    
+    #[test]
+    fn test_warm_up() {
+        let database = std::sync::Arc::new(MemoryDatabase::default());
+        let cache = Cache::new(persisting()).with_database(Box::new(database.clone()));
+        let btc = InstrumentAny::new("BTCUSDT", "BINANCE", 2, 6, 0.01, 0.000001, 1.0);
+        let btc_id = btc.id();
+        cache.add_currency(Currency { code: "USDT".to_string(), precision: 2, iso4217: 0, name: "Tether".to_string() }).unwrap();
+        cache.add_instrument(btc).unwrap();
+        cache.add_account(Account { id: "A-1".to_string(), balance: 1.0 }).unwrap();
+        let position = |id: &str, quantity: f64| Position { id: id.to_string(), instrument_id: btc_id, strategy_id: None, quantity, avg_price: 1.0 };
+        cache.add_position(position("P-1", 1.0)).unwrap();
+        cache.add_position(position("P-2", 0.0)).unwrap();
+        let order = |id: &str, status: OrderStatus| Order { id: id.to_string(), instrument_id: btc_id, strategy_id: None, side: "BUY".to_string(), quantity: 1.0, price: None, status };
+        cache.add_order(order("O-1", OrderStatus::PartiallyFilled)).unwrap();
+        cache.add_order(order("O-2", OrderStatus::Filled)).unwrap();
+        
+        // Closed orders and positions are left out, all read at once
+        let restarted = Cache::new(persisting()).with_database(Box::new(database.clone()));
+        let report = restarted.warm_up_from_database().unwrap();
+        assert_eq!(report, WarmUpReport { currencies: 1, instruments: 1, orders: 1, positions: 1 });
+        assert_eq!(database.reads.load(Ordering::Relaxed), 1);
+        assert!(restarted.get_order("O-2").is_none());
+        assert_eq!(restarted.get_instruments_by_symbol("BTCUSDT").len(), 1);
+        assert!(restarted.get_account("A-1").is_none());
+        
+        let written = database.entries.lock().unwrap().len();
+        assert_eq!(restarted.warm_up(&VenueReconciliation).unwrap().orders, 1);
+        assert_eq!(restarted.orders_for_instrument(&InstrumentId::new(2)).len(), 1);
+        assert_eq!(database.entries.lock().unwrap().len(), written);
+    }
+    
+    #[test]
+    fn test_execution_data_lookups() {
+        let cache = Cache::new(CacheConfig::default());
+        let (btc, eth) = (InstrumentId::new(1), InstrumentId::new(2));
+        let order = |id: &str, instrument_id: InstrumentId| Order {
+            id: id.to_string(),
+            instrument_id,
+            strategy_id: None,
+            side: "BUY".to_string(),
+            quantity: 1.0,
+            price: None,
+            status: OrderStatus::Accepted,
+        };
+        let position = |id: &str, instrument_id: InstrumentId, quantity: f64| Position {
+            id: id.to_string(),
+            instrument_id,
+            strategy_id: None,
+            quantity,
+            avg_price: 100.0,
+        };
+        cache.add_account(Account { id: "A-1".to_string(), balance: 50.0 }).unwrap();
+        cache.add_order(order("O-1", btc)).unwrap();
+        cache.add_order(order("O-2", btc)).unwrap();
+        cache.add_order(order("O-3", eth)).unwrap();
+        cache.add_position(position("P-1", btc, 1.0)).unwrap();
+        cache.add_position(position("P-2", eth, -2.0)).unwrap();
+        cache.add_position(position("P-3", btc, 0.0)).unwrap();
+        
+        assert_eq!(cache.get_account("A-1").map(|a| a.balance), Some(50.0));
+        assert_eq!(cache.get_order("O-3").map(|o| o.instrument_id), Some(eth));
+        assert!(cache.get_order("O-4").is_none());
+        let mut btc_orders: Vec<String> = cache.orders_for_instrument(&btc).into_iter().map(|o| o.id).collect();
+        btc_orders.sort();
+        assert_eq!(btc_orders, vec!["O-1", "O-2"]);
+        
+        let mut open: Vec<String> = cache.positions_open(None).into_iter().map(|p| p.id).collect();
+        open.sort();
+        assert_eq!(open, vec!["P-1", "P-2"]);
+        
+        // Closing a position drops it from the open ones
+        cache.add_position(position("P-1", btc, 0.0)).unwrap();
+        assert!(cache.positions_open(Some(&btc)).is_empty());
+        assert_eq!(cache.get_position("P-1").map(|p| p.quantity), Some(0.0));
+        
+        let stats = cache.get_stats();
+        assert_eq!((stats.total_hits, stats.total_misses, stats.orders_count, stats.positions_count), (3, 1, 3, 3));
+    }
+    
+    #[test]
+    fn test_change_events_published() {
+        let bus = Arc::new(MessageBus::new());
+        let mut events = bus.subscribe("cache.*");
+        let evicted = bus.subscribe("cache.trades.evicted");
+        let cache = Cache::new(CacheConfig { max_items_per_type: 1, ..CacheConfig::default() }).with_message_bus(Arc::clone(&bus));
+        let (btc, eth) = (InstrumentId::new(1), InstrumentId::new(2));
+        let trade = |instrument_id: InstrumentId, ts: UnixNanos| TradeTick {
+            instrument_id,
+            price: 100.0,
+            size: 1.0,
+            aggressor_side: AggressorSide::Buyer,
+            trade_id: ts.to_string(),
+            ts_event: ts,
+            ts_init: ts,
+        };
+        cache.add_trade_ticks(vec![trade(btc, 1), trade(btc, 2)]).unwrap();
+        cache.add_trade_ticks(vec![trade(eth, 3)]).unwrap();
+        cache.add_order(Order { id: "O-1".to_string(), instrument_id: eth, strategy_id: None, side: "BUY".to_string(), quantity: 1.0, price: None, status: OrderStatus::Accepted }).unwrap();
+        
+        let mut received = Vec::new();
+        while let Ok(envelope) = events.try_recv() {
+            let event: CacheEvent = envelope.decode().unwrap();
+            assert_eq!(envelope.message_type, cache_event_topic(event.data_type, event.kind));
+            received.push((event.data_type, event.kind, event.key, event.count));
+        }
+        let (btc, eth) = (btc.to_string(), eth.to_string());
+        assert_eq!(received, vec![
+            (CacheDataType::Trades, CacheEventKind::Inserted, btc.clone(), 2),
+            (CacheDataType::Trades, CacheEventKind::Evicted, btc.clone(), 1),
+            (CacheDataType::Trades, CacheEventKind::Evicted, btc, 1),
+            (CacheDataType::Trades, CacheEventKind::Inserted, eth, 1),
+            (CacheDataType::Orders, CacheEventKind::Inserted, "O-1".to_string(), 1),
+        ]);
+        assert_eq!(evicted.len(), 2);
+    }
+    
+    #[test]
+    fn test_strategy_indexes_follow_updates() {
+        let cache = Cache::new(CacheConfig::default());
+        let (btc, eth) = (InstrumentId::new(1), InstrumentId::new(2));
+        let order = |id: &str, instrument_id: InstrumentId, strategy_id: &str| Order {
+            id: id.to_string(),
+            instrument_id,
+            strategy_id: Some(strategy_id.to_string()),
+            side: "BUY".to_string(),
+            quantity: 1.0,
+            price: None,
+            status: OrderStatus::Accepted,
+        };
+        let position = |id: &str, strategy_id: Option<&str>| Position {
+            id: id.to_string(),
+            instrument_id: btc,
+            strategy_id: strategy_id.map(str::to_string),
+            quantity: 1.0,
+            avg_price: 100.0,
+        };
+        cache.add_order(order("O-1", btc, "momentum")).unwrap();
+        cache.add_order(order("O-2", eth, "momentum")).unwrap();
+        cache.add_order(order("O-3", btc, "carry")).unwrap();
+        cache.add_position(position("P-1", Some("carry"))).unwrap();
+        cache.add_position(position("P-2", None)).unwrap();
+        
+        let ids = |orders: Vec<Order>| {
+            let mut ids: Vec<String> = orders.into_iter().map(|o| o.id).collect();
+            ids.sort();
+            ids
+        };
+        assert_eq!(ids(cache.orders_for_strategy("momentum")), vec!["O-1", "O-2"]);
+        assert_eq!(ids(cache.orders_for_strategy("carry")), vec!["O-3"]);
+        assert!(cache.orders_for_strategy("arbitrage").is_empty());
+        assert_eq!(cache.positions_for_strategy("carry").len(), 1);
+        
+        // An update moves the order out of its previous instrument and strategy
+        cache.add_order(order("O-2", btc, "carry")).unwrap();
+        assert_eq!(ids(cache.orders_for_strategy("momentum")), vec!["O-1"]);
+        assert_eq!(ids(cache.orders_for_strategy("carry")), vec!["O-2", "O-3"]);
+        assert!(cache.orders_for_instrument(&eth).is_empty());
+        assert!(!cache.index.read().orders_by_instrument.contains_key(&eth));
+        cache.add_position(position("P-2", Some("carry"))).unwrap();
+        cache.add_position(position("P-1", None)).unwrap();
+        assert_eq!(cache.positions_for_strategy("carry").into_iter().map(|p| p.id).collect::<Vec<_>>(), vec!["P-2"]);
+    }
+    
+    #[test]
+    fn test_tick_range_queries() {
+        let cache = Cache::new(CacheConfig::default());
+        let instrument_id = InstrumentId::new(1);
+        for ts in [10, 20, 20, 30, 40] {
+            cache.add_quote_tick(QuoteTick {
+                instrument_id,
+                bid_price: 99.0,
+                ask_price: 101.0,
+                bid_size: 1.0,
+                ask_size: 1.0,
+                ts_event: ts,
+                ts_init: ts,
+            }).unwrap();
+            cache.add_trade_tick(TradeTick {
+                instrument_id,
+                price: 100.0,
+                size: 1.0,
+                aggressor_side: AggressorSide::Buyer,
+                trade_id: ts.to_string(),
+                ts_event: ts,
+                ts_init: ts,
+            }).unwrap();
+        }
+        
+        let quotes: Vec<UnixNanos> = cache.get_quotes_range(&instrument_id, 20, 30).iter().map(|q| q.ts_event).collect();
+        assert_eq!(quotes, vec![20, 20, 30]);
+        let trades: Vec<UnixNanos> = cache.get_trades_range(&instrument_id, 15, 45).iter().map(|t| t.ts_event).collect();
+        assert_eq!(trades, vec![20, 20, 30, 40]);
+        assert!(cache.get_trades_range(&instrument_id, 41, 50).is_empty());
+        assert!(cache.get_quotes_range(&instrument_id, 30, 20).is_empty());
+        assert!(cache.get_quotes_range(&InstrumentId::new(2), 0, 50).is_empty());
+        
+        let stats = cache.get_stats();
+        assert_eq!((stats.total_hits, stats.total_misses), (4, 1));
+
+        // Range queries only read, so they run while other readers hold the histories
+        let (_quotes, _trades) = (cache.quotes.read(), cache.trades.read());
+        assert_eq!(cache.get_quotes_range(&instrument_id, 20, 30).len(), 3);
+        assert_eq!(cache.get_trades_range(&instrument_id, 15, 45).len(), 4);
+    }
 }
@@ -0,0 +1,181 @@
+//! AlphaForge Trading Calendar
+//!
+//! When a venue is open, in its own time zone: weekly session windows plus
+//! holidays. A strategy configured with a `SessionConfig` only receives
+//! market data while its calendar is open; the strategy engine can flatten
+//! its positions at the close.
+
+use chrono::{DateTime, Datelike, Duration, NaiveDate, NaiveDateTime, NaiveTime, TimeZone, Weekday};
+use chrono_tz::Tz;
+use serde::{Deserialize, Serialize};
+
+use crate::time::UnixNanos;
+
+/// A trading window on some weekdays, in exchange time. A window closing
+/// at or before it opens runs overnight into the next day.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct SessionWindow {
+    /// Days the window opens on
+    pub days: Vec<Weekday>,
+    pub open: NaiveTime,
+    pub close: NaiveTime,
+}
+
+impl SessionWindow {
+    pub fn new(days: Vec<Weekday>, open: NaiveTime, close: NaiveTime) -> Self {
+        Self { days, open, close }
+    }
+
+    /// Monday to Friday between `open` and `close`
+    pub fn weekdays(open: NaiveTime, close: NaiveTime) -> Self {
+        Self::new(vec![Weekday::Mon, Weekday::Tue, Weekday::Wed, Weekday::Thu, Weekday::Fri], open, close)
+    }
+
+    fn contains(&self, weekday: Weekday, time: NaiveTime) -> bool {
+        if self.open < self.close {
+            return self.days.contains(&weekday) && time >= self.open && time < self.close;
+        }
+        // Overnight: the evening of an opening day or the morning after it
+        (self.days.contains(&weekday) && time >= self.open) || (self.days.contains(&weekday.pred()) && time < self.close)
+    }
+
+    // Day the session containing `date` at `time` opened on
+    fn opening_date(&self, date: NaiveDate, time: NaiveTime) -> NaiveDate {
+        if self.open >= self.close && time < self.close {
+            date - Duration::days(1)
+        } else {
+            date
+        }
+    }
+}
+
+/// Sessions and holidays of a venue
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct TradingCalendar {
+    /// Exchange time zone, e.g. `America/New_York`
+    pub timezone: Tz,
+    pub sessions: Vec<SessionWindow>,
+    /// Dates (in exchange time) on which no session opens
+    #[serde(default)]
+    pub holidays: Vec<NaiveDate>,
+}
+
+impl TradingCalendar {
+    pub fn new(timezone: Tz, sessions: Vec<SessionWindow>) -> Self {
+        Self {
+            timezone,
+            sessions,
+            holidays: Vec::new(),
+        }
+    }
+
+    pub fn with_holidays(mut self, holidays: Vec<NaiveDate>) -> Self {
+        self.holidays = holidays;
+        self
+    }
+
+    /// Whether a session is open at `ts`
+    pub fn is_open(&self, ts: UnixNanos) -> bool {
+        let local = DateTime::from_timestamp_nanos(ts as i64).with_timezone(&self.timezone);
+        let (date, time) = (local.date_naive(), local.time());
+        self.sessions.iter().any(|session| {
+            session.contains(local.weekday(), time) && !self.holidays.contains(&session.opening_date(date, time))
+        })
+    }
+
+    /// First session open strictly after `ts`, within a year
+    pub fn next_open(&self, ts: UnixNanos) -> Option<UnixNanos> {
+        self.next_boundary(ts, |session, date| date.and_time(session.open))
+    }
+
+    /// First session close strictly after `ts`, within a year
+    pub fn next_close(&self, ts: UnixNanos) -> Option<UnixNanos> {
+        self.next_boundary(ts, |session, date| {
+            let close_date = if session.open >= session.close { date + Duration::days(1) } else { date };
+            close_date.and_time(session.close)
+        })
+    }
+
+    // Earliest `boundary` after `ts` of a session opening on a trading day
+    fn next_boundary(&self, ts: UnixNanos, boundary: impl Fn(&SessionWindow, NaiveDate) -> NaiveDateTime) -> Option<UnixNanos> {
+        let local = DateTime::from_timestamp_nanos(ts as i64).with_timezone(&self.timezone);
+        // From the day before, whose overnight session may still close
+        let first = local.date_naive() - Duration::days(1);
+        let mut next: Option<UnixNanos> = None;
+        for date in first.iter_days().take(368) {
+            // Boundaries fall at most a day after their session's opening day
+            if next.is_some_and(|next| self.local_date(next) < date - Duration::days(1)) {
+                break;
+            }
+            if self.holidays.contains(&date) {
+                continue;
+            }
+            for session in self.sessions.iter().filter(|session| session.days.contains(&date.weekday())) {
+                let at = self.timezone
+                    .from_local_datetime(&boundary(session, date))
+                    .earliest()
+                    .and_then(|at| at.timestamp_nanos_opt())
+                    .map(|at| at as UnixNanos);
+                if let Some(at) = at.filter(|at| *at > ts) {
+                    next = Some(next.map_or(at, |next| next.min(at)));
+                }
+            }
+        }
+        next
+    }
+
+    fn local_date(&self, ts: UnixNanos) -> NaiveDate {
+        DateTime::from_timestamp_nanos(ts as i64).with_timezone(&self.timezone).date_naive()
+    }
+}
+
+/// Trading hours of a strategy
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct SessionConfig {
+    pub calendar: TradingCalendar,
+    /// Close the strategy's open positions when its session ends
+    #[serde(default)]
+    pub flatten_at_close: bool,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::TimeZone;
+
+    fn ts(calendar: &TradingCalendar, date: (i32, u32, u32), time: (u32, u32)) -> UnixNanos {
+        calendar.timezone
+            .with_ymd_and_hms(date.0, date.1, date.2, time.0, time.1, 0)
+            .unwrap()
+            .timestamp_nanos_opt()
+            .unwrap() as UnixNanos
+    }
+
+    #[test]
+    fn test_sessions_holidays_and_overnight_windows() {
+        let hours = |h, m| NaiveTime::from_hms_opt(h, m, 0).unwrap();
+        let equities = TradingCalendar::new(chrono_tz::America::New_York, vec![SessionWindow::weekdays(hours(9, 30), hours(16, 0))])
+            .with_holidays(vec![NaiveDate::from_ymd_opt(2024, 7, 4).unwrap()]);
+
+        // Wednesday 3 July 2024, in summer time
+        assert!(!equities.is_open(ts(&equities, (2024, 7, 3), (9, 29))));
+        assert!(equities.is_open(ts(&equities, (2024, 7, 3), (9, 30))));
+        assert!(!equities.is_open(ts(&equities, (2024, 7, 3), (16, 0))));
+        assert!(!equities.is_open(ts(&equities, (2024, 7, 4), (12, 0)))); // Holiday
+        assert!(!equities.is_open(ts(&equities, (2024, 7, 6), (12, 0)))); // Saturday
+        // Same exchange time in winter is a different UTC time
+        assert!(equities.is_open(ts(&equities, (2024, 1, 3), (9, 30))));
+
+        // Sunday to Thursday evenings through to the next afternoon
+        let futures = TradingCalendar::new(chrono_tz::America::Chicago, vec![SessionWindow::new(
+            vec![Weekday::Sun, Weekday::Mon, Weekday::Tue, Weekday::Wed, Weekday::Thu],
+            hours(17, 0),
+            hours(16, 0),
+        )]);
+        assert!(futures.is_open(ts(&futures, (2024, 7, 7), (18, 0)))); // Sunday evening
+        assert!(futures.is_open(ts(&futures, (2024, 7, 8), (3, 0))));
+        assert!(!futures.is_open(ts(&futures, (2024, 7, 8), (16, 30)))); // Daily break
+        assert!(futures.is_open(ts(&futures, (2024, 7, 12), (15, 0)))); // Friday, from Thursday
+        assert!(!futures.is_open(ts(&futures, (2024, 7, 12), (18, 0)))); // Friday evening
+    }
+}
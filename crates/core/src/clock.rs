@@ -1,96 +1,333 @@
 //! High-performance clock abstractions for AlphaForge
 
+use std::fmt;
 use std::sync::Arc;
-use async_trait::async_trait;
-use tokio::sync::{Mutex, mpsc};
+use parking_lot::Mutex;
+use tokio::sync::mpsc;
 use std::collections::HashMap;
-use tracing::{debug, warn};
+use serde::{Deserialize, Serialize};
+use tracing::debug;
 
-use crate::time::{UnixNanos, unix_nanos_now};
+use crate::time::{UnixNanos, monotonic_nanos_now};
 use crate::error::{AlphaForgeError, Result};
+use crate::message_bus::MessageBus;
 
-/// Timer callback function type
-pub type TimerCallback = Box<dyn Fn() + Send + Sync>;
+/// A timer firing
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct TimeEvent {
+    /// Name the timer was set under
+    pub name: String,
+    /// Time the timer was due
+    pub ts_event: UnixNanos,
+    /// Time the clock fired it, later than `ts_event` on a live clock
+    pub ts_init: UnixNanos,
+}
+
+/// Where a timer's events go, so they queue alongside the owner's other
+/// events and can be recorded and replayed
+#[derive(Clone)]
+pub enum TimeEventSender {
+    /// Sent on the owner's channel
+    Channel(mpsc::UnboundedSender<TimeEvent>),
+    /// Published on the owner's topic of a message bus
+    Bus { bus: Arc<MessageBus>, topic: String },
+    /// Passed to a handler, e.g. one queueing it for the owner's thread
+    Handler(Arc<dyn Fn(TimeEvent) + Send + Sync>),
+}
+
+impl TimeEventSender {
+    pub fn send(&self, event: TimeEvent) {
+        match self {
+            TimeEventSender::Channel(sender) => {
+                if sender.send(event).is_err() {
+                    debug!("Time event receiver is gone");
+                }
+            }
+            TimeEventSender::Bus { bus, topic } => bus.publish(topic, &event),
+            TimeEventSender::Handler(handler) => handler(event),
+        }
+    }
+}
+
+impl fmt::Debug for TimeEventSender {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            TimeEventSender::Channel(_) => f.write_str("Channel"),
+            TimeEventSender::Bus { topic, .. } => f.debug_struct("Bus").field("topic", topic).finish_non_exhaustive(),
+            TimeEventSender::Handler(_) => f.write_str("Handler"),
+        }
+    }
+}
+
+/// Irregular fire times of a timer, e.g. a cron expression or session opens
+pub trait TimerSchedule: Send + Sync {
+    /// First fire time strictly after `ts`, or None when the schedule ends
+    fn next_after(&self, ts: UnixNanos) -> Option<UnixNanos>;
+}
 
 /// Timer information
-#[derive(Debug, Clone)]
+#[derive(Clone)]
 pub struct Timer {
     pub name: String,
     pub interval_ns: u64,
     pub next_time_ns: u64,
     pub stop_time_ns: Option<u64>,
-    pub callback: Arc<dyn Fn() + Send + Sync>,
+    pub sender: TimeEventSender,
+    /// Fire times after the first, instead of every `interval_ns`
+    pub schedule: Option<Arc<dyn TimerSchedule>>,
+}
+
+impl Timer {
+    /// Time the timer fires next after firing at `fired_ns`, or None when
+    /// it is done
+    pub fn following(&self, fired_ns: UnixNanos) -> Option<UnixNanos> {
+        let next = match &self.schedule {
+            Some(schedule) => schedule.next_after(fired_ns)?,
+            None if self.interval_ns == 0 => return None,
+            None => fired_ns + self.interval_ns,
+        };
+        match self.stop_time_ns {
+            Some(stop) if next > stop => None,
+            _ => Some(next),
+        }
+    }
+
+    fn event(&self, ts_init: UnixNanos) -> TimeEvent {
+        TimeEvent {
+            name: self.name.clone(),
+            ts_event: self.next_time_ns,
+            ts_init,
+        }
+    }
+}
+
+impl fmt::Debug for Timer {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Timer")
+            .field("name", &self.name)
+            .field("interval_ns", &self.interval_ns)
+            .field("next_time_ns", &self.next_time_ns)
+            .field("stop_time_ns", &self.stop_time_ns)
+            .field("sender", &self.sender)
+            .field("scheduled", &self.schedule.is_some())
+            .finish()
+    }
 }
 
-/// Clock abstraction for unified time handling
-#[async_trait]
+/// Clock abstraction for unified time handling.
+///
+/// Timer registration takes `&self` so a shared `Arc<dyn Clock>` can be
+/// used from synchronous strategy callbacks.
 pub trait Clock: Send + Sync {
     /// Get current timestamp in nanoseconds
     fn timestamp_ns(&self) -> UnixNanos;
     
-    /// Set a timer with callback
-    async fn set_timer(
-        &mut self,
+    /// Set a timer sending a `TimeEvent` to `sender` every `interval_ns` from
+    /// `start_time_ns`, replacing any timer with the same name
+    fn set_timer(
+        &self,
         name: String,
         interval_ns: u64,
         start_time_ns: u64,
         stop_time_ns: Option<u64>,
-        callback: TimerCallback,
+        sender: TimeEventSender,
+    ) -> Result<()>;
+    
+    /// Set a timer sending a `TimeEvent` to `sender` at each time of
+    /// `schedule` until `stop_time_ns`, replacing any timer with the same name
+    fn set_scheduled_timer(
+        &self,
+        name: String,
+        schedule: Arc<dyn TimerSchedule>,
+        stop_time_ns: Option<u64>,
+        sender: TimeEventSender,
     ) -> Result<()>;
     
     /// Cancel a timer
-    async fn cancel_timer(&mut self, name: String) -> Result<()>;
+    fn cancel_timer(&self, name: String) -> Result<()>;
     
     /// Get next scheduled timer time
     fn next_timer_ns(&self) -> Option<UnixNanos>;
 }
 
-/// Live clock implementation using system time
+impl fmt::Debug for dyn Clock {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Clock").field("timestamp_ns", &self.timestamp_ns()).finish()
+    }
+}
+
+// A timer first firing at `schedule`'s next time after `now`
+fn scheduled_timer(
+    name: String,
+    schedule: Arc<dyn TimerSchedule>,
+    now: UnixNanos,
+    stop_time_ns: Option<u64>,
+    sender: TimeEventSender,
+) -> Result<Timer> {
+    let next_time_ns = schedule
+        .next_after(now)
+        .filter(|next| stop_time_ns.is_none_or(|stop| *next <= stop))
+        .ok_or_else(|| AlphaForgeError::validation(format!("Timer {} would never fire", name)))?;
+    Ok(Timer {
+        name,
+        interval_ns: 0,
+        next_time_ns,
+        stop_time_ns,
+        sender,
+        schedule: Some(schedule),
+    })
+}
+
+// Send the events of timers due at `now`, dropping finished timers. A
+// timer behind by more than one firing fires once and skips the rest,
+// staying on its interval.
+fn fire_due_timers(timers: &mut HashMap<String, Timer>, now: UnixNanos) {
+    timers.retain(|name, timer| {
+        if now < timer.next_time_ns {
+            return true;
+        }
+        timer.sender.send(timer.event(now));
+        let next = match timer.following(timer.next_time_ns) {
+            Some(_) if timer.schedule.is_some() => timer.following(now),
+            Some(next) if next <= now => timer.following(next + (now - next) / timer.interval_ns * timer.interval_ns),
+            next => next,
+        };
+        match next {
+            Some(next_time_ns) => {
+                timer.next_time_ns = next_time_ns;
+                true
+            }
+            None => {
+                debug!("Timer expired and removed: {}", name);
+                false
+            }
+        }
+    });
+}
+
+/// High-resolution mode for short `LiveClock` timers. They run on a
+/// dedicated thread that sleeps until just before each firing and spins
+/// the rest of the way, instead of on the 1ms polling loop.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct HighResolutionConfig {
+    /// Timers repeating more often than this run in high-resolution mode
+    pub interval_threshold_ns: u64,
+    /// How long before a firing to stop sleeping and spin
+    pub spin_ns: u64,
+    /// Most time spent spinning in any second; past it timers fire from
+    /// sleep alone for the rest of the second
+    pub max_spin_per_sec_ns: u64,
+}
+
+impl Default for HighResolutionConfig {
+    fn default() -> Self {
+        Self {
+            interval_threshold_ns: 10_000_000,
+            spin_ns: 100_000,
+            max_spin_per_sec_ns: 100_000_000,
+        }
+    }
+}
+
+// Timer loop of the high-resolution thread
+fn run_high_resolution_timers(config: HighResolutionConfig, commands: std::sync::mpsc::Receiver<TimerCommand>) {
+    use std::sync::mpsc::RecvTimeoutError;
+    use std::time::Duration;
+
+    let mut timers: HashMap<String, Timer> = HashMap::new();
+    let mut second_start = monotonic_nanos_now();
+    let mut spun_ns = 0;
+    loop {
+        let now = monotonic_nanos_now();
+        if now >= second_start + 1_000_000_000 {
+            second_start = now;
+            spun_ns = 0;
+        }
+        let command = match timers.values().map(|timer| timer.next_time_ns).min() {
+            None => match commands.recv() {
+                Ok(command) => Some(command),
+                Err(_) => break,
+            },
+            Some(due) => {
+                let spin_ns = if spun_ns < config.max_spin_per_sec_ns { config.spin_ns } else { 0 };
+                if due > now + spin_ns {
+                    // Sleep until the spin starts, waking for commands
+                    match commands.recv_timeout(Duration::from_nanos(due - now - spin_ns)) {
+                        Ok(command) => Some(command),
+                        Err(RecvTimeoutError::Timeout) => None,
+                        Err(RecvTimeoutError::Disconnected) => break,
+                    }
+                } else {
+                    while monotonic_nanos_now() < due {
+                        std::hint::spin_loop();
+                    }
+                    spun_ns += monotonic_nanos_now().saturating_sub(now);
+                    None
+                }
+            }
+        };
+        match command {
+            Some(TimerCommand::Set(timer)) => {
+                debug!("High-resolution timer set: {}", timer.name);
+                timers.insert(timer.name.clone(), timer);
+            }
+            Some(TimerCommand::Cancel { name }) => {
+                timers.remove(&name);
+            }
+            None => fire_due_timers(&mut timers, monotonic_nanos_now()),
+        }
+    }
+}
+
+/// Live clock implementation using system time, read through a
+/// `MonotonicClock` so timestamps never go backwards
 pub struct LiveClock {
-    timers: Arc<Mutex<HashMap<String, Timer>>>,
-    timer_tx: mpsc::UnboundedSender<TimerCommand>,
+    /// Commands to the timer task, started with the first timer
+    timer_tx: std::sync::OnceLock<mpsc::UnboundedSender<TimerCommand>>,
+    high_resolution: Option<(HighResolutionConfig, std::sync::mpsc::Sender<TimerCommand>)>,
 }
 
-#[derive(Debug)]
 enum TimerCommand {
-    Set {
-        name: String,
-        interval_ns: u64,
-        start_time_ns: u64,
-        stop_time_ns: Option<u64>,
-        callback: Arc<dyn Fn() + Send + Sync>,
-    },
+    Set(Timer),
     Cancel {
         name: String,
     },
 }
 
 impl LiveClock {
-    /// Create a new live clock
+    /// Create a new live clock. Its timer task starts when the first timer
+    /// is set, which needs a Tokio runtime; without one the clock still
+    /// tells time, and setting timers fails until called within one.
     pub fn new() -> Self {
+        Self {
+            timer_tx: std::sync::OnceLock::new(),
+            high_resolution: None,
+        }
+    }
+
+    // Commands to the timer task, starting it on the current Tokio runtime
+    // if no timer was set before. Nothing is kept without a runtime, so a
+    // later call within one still starts the task.
+    fn timers(&self) -> Result<&mpsc::UnboundedSender<TimerCommand>> {
+        if let Some(timer_tx) = self.timer_tx.get() {
+            return Ok(timer_tx);
+        }
+        let runtime = tokio::runtime::Handle::try_current().map_err(|_| AlphaForgeError::Component {
+            msg: "No Tokio runtime to run live clock timers".to_string()
+        })?;
         let (timer_tx, mut timer_rx) = mpsc::unbounded_channel();
-        let timers = Arc::new(Mutex::new(HashMap::new()));
-        
-        // Spawn timer management task
-        let timers_clone = Arc::clone(&timers);
-        tokio::spawn(async move {
+        // Timer management task
+        let timers = async move {
             let mut active_timers: HashMap<String, Timer> = HashMap::new();
-            
+
             loop {
                 tokio::select! {
                     // Handle timer commands
                     cmd = timer_rx.recv() => {
                         match cmd {
-                            Some(TimerCommand::Set { name, interval_ns, start_time_ns, stop_time_ns, callback }) => {
-                                let timer = Timer {
-                                    name: name.clone(),
-                                    interval_ns,
-                                    next_time_ns: start_time_ns,
-                                    stop_time_ns,
-                                    callback,
-                                };
-                                active_timers.insert(name, timer);
+                            Some(TimerCommand::Set(timer)) => {
                                 debug!("Timer set: {}", timer.name);
+                                active_timers.insert(timer.name.clone(), timer);
                             }
                             Some(TimerCommand::Cancel { name }) => {
                                 active_timers.remove(&name);
@@ -99,91 +336,111 @@ impl LiveClock {
                             None => break, // Channel closed
                         }
                     }
-                    
+
                     // Check for timer expiration
                     _ = tokio::time::sleep(std::time::Duration::from_millis(1)) => {
-                        let now = unix_nanos_now();
-                        let mut expired_timers = Vec::new();
-                        
-                        for (name, timer) in &mut active_timers {
-                            if now >= timer.next_time_ns {
-                                // Timer expired, execute callback
-                                (timer.callback)();
-                                
-                                // Check if timer should continue
-                                if let Some(stop_time) = timer.stop_time_ns {
-                                    if now >= stop_time {
-                                        expired_timers.push(name.clone());
-                                        continue;
-                                    }
-                                }
-                                
-                                // Schedule next execution
-                                timer.next_time_ns = now + timer.interval_ns;
-                            }
-                        }
-                        
-                        // Remove expired timers
-                        for name in expired_timers {
-                            active_timers.remove(&name);
-                            debug!("Timer expired and removed: {}", name);
-                        }
+                        fire_due_timers(&mut active_timers, monotonic_nanos_now());
                     }
                 }
             }
-        });
-        
-        Self {
-            timers,
-            timer_tx,
+        };
+
+        // Unless a concurrent first call started the task first
+        if self.timer_tx.set(timer_tx).is_ok() {
+            runtime.spawn(timers);
+        }
+        Ok(self.timer_tx.get().expect("timer task started"))
+    }
+
+    /// Run timers repeating more often than `config.interval_threshold_ns`
+    /// in high-resolution mode, on a thread of their own
+    pub fn with_high_resolution(mut self, config: HighResolutionConfig) -> Result<Self> {
+        let (sender, commands) = std::sync::mpsc::channel();
+        std::thread::Builder::new()
+            .name("alphaforge-hires-timers".to_string())
+            .spawn(move || run_high_resolution_timers(config, commands))
+            .map_err(|e| AlphaForgeError::runtime(format!("Failed to spawn high-resolution timer thread: {}", e)))?;
+        self.high_resolution = Some((config, sender));
+        Ok(self)
+    }
+
+    // Hand `timer` to the loop that runs it, cancelling any timer of the
+    // same name in the other
+    fn set(&self, timer: Timer) -> Result<()> {
+        let unavailable = || AlphaForgeError::Component {
+            msg: "Timer system unavailable".to_string()
+        };
+        let Some((config, high_resolution)) = &self.high_resolution else {
+            return self.timers()?.send(TimerCommand::Set(timer)).map_err(|_| unavailable());
+        };
+        let cancel = TimerCommand::Cancel { name: timer.name.clone() };
+        if timer.schedule.is_none() && timer.interval_ns > 0 && timer.interval_ns < config.interval_threshold_ns {
+            if let Some(timer_tx) = self.timer_tx.get() {
+                let _ = timer_tx.send(cancel);
+            }
+            high_resolution.send(TimerCommand::Set(timer)).map_err(|_| unavailable())
+        } else {
+            let _ = high_resolution.send(cancel);
+            self.timers()?.send(TimerCommand::Set(timer)).map_err(|_| unavailable())
         }
     }
 }
 
-#[async_trait]
 impl Clock for LiveClock {
     fn timestamp_ns(&self) -> UnixNanos {
-        unix_nanos_now()
+        monotonic_nanos_now()
     }
     
-    async fn set_timer(
-        &mut self,
+    fn set_timer(
+        &self,
         name: String,
         interval_ns: u64,
         start_time_ns: u64,
         stop_time_ns: Option<u64>,
-        callback: TimerCallback,
+        sender: TimeEventSender,
     ) -> Result<()> {
-        let cmd = TimerCommand::Set {
+        self.set(Timer {
             name,
             interval_ns,
-            start_time_ns,
+            next_time_ns: start_time_ns,
             stop_time_ns,
-            callback: Arc::from(callback),
-        };
-        
-        self.timer_tx.send(cmd)
-            .map_err(|_| AlphaForgeError::Component { 
-                msg: "Timer system unavailable".to_string()
-            })?;
-            
-        Ok(())
+            sender,
+            schedule: None,
+        })
+    }
+    
+    fn set_scheduled_timer(
+        &self,
+        name: String,
+        schedule: Arc<dyn TimerSchedule>,
+        stop_time_ns: Option<u64>,
+        sender: TimeEventSender,
+    ) -> Result<()> {
+        let timer = scheduled_timer(name, schedule, self.timestamp_ns(), stop_time_ns, sender)?;
+        self.set(timer)
     }
     
-    async fn cancel_timer(&mut self, name: String) -> Result<()> {
+    fn cancel_timer(&self, name: String) -> Result<()> {
+        let high_resolution = self
+            .high_resolution
+            .as_ref()
+            .is_some_and(|(_, commands)| commands.send(TimerCommand::Cancel { name: name.clone() }).is_ok());
         let cmd = TimerCommand::Cancel { name };
+        // Without a timer task there is no timer to cancel
+        let cancelled = self.timer_tx.get().is_none_or(|timer_tx| timer_tx.send(cmd).is_ok());
         
-        self.timer_tx.send(cmd)
-            .map_err(|_| AlphaForgeError::Component { 
+        if !cancelled && !high_resolution {
+            return Err(AlphaForgeError::Component { 
                 msg: "Timer system unavailable".to_string()
-            })?;
+            });
+        }
             
         Ok(())
     }
     
     fn next_timer_ns(&self) -> Option<UnixNanos> {
         // For live clock, always return current time + small buffer
-        Some(unix_nanos_now() + 1_000_000) // 1ms buffer
+        Some(monotonic_nanos_now() + 1_000_000) // 1ms buffer
     }
 }
 
@@ -209,18 +466,56 @@ impl TestClock {
     }
     
     /// Advance time by specified duration
-    pub async fn advance_time(&self, duration_ns: u64) {
+    pub fn advance_time(&self, duration_ns: u64) -> usize {
         let current = self.current_time.load(std::sync::atomic::Ordering::Relaxed);
-        let new_time = current + duration_ns;
-        self.current_time.store(new_time, std::sync::atomic::Ordering::Relaxed);
+        self.advance_to(current + duration_ns)
+    }
+    
+    /// Advance time to `target_ns`, firing due timers in time order.
+    ///
+    /// The clock reads each timer's scheduled time as its event is sent, and
+    /// events carry that time as both `ts_event` and `ts_init`, so replays
+    /// see the same events on every run. Returns the number of events sent.
+    pub fn advance_to(&self, target_ns: UnixNanos) -> usize {
+        let mut fired = 0;
         
-        // Process expired timers
-        let timers = self.timers.lock().await;
-        for timer in timers.values() {
-            if new_time >= timer.next_time_ns {
-                (timer.callback)();
-            }
+        loop {
+            let (sender, event) = {
+                let mut timers = self.timers.lock();
+                let due = timers
+                    .values()
+                    .filter(|timer| timer.next_time_ns <= target_ns)
+                    .min_by(|a, b| a.next_time_ns.cmp(&b.next_time_ns).then_with(|| a.name.cmp(&b.name)))
+                    .map(|timer| timer.name.clone());
+                let Some(name) = due else {
+                    break;
+                };
+                
+                let timer = timers.get_mut(&name).expect("due timer exists");
+                let fire_time = timer.next_time_ns;
+                if fire_time > self.timestamp_ns() {
+                    self.set_time(fire_time);
+                }
+                
+                let fired_timer = (timer.sender.clone(), timer.event(fire_time));
+                match timer.following(fire_time) {
+                    Some(next_time_ns) => timer.next_time_ns = next_time_ns,
+                    None => {
+                        timers.remove(&name);
+                        debug!("Timer expired and removed: {}", name);
+                    }
+                }
+                fired_timer
+            };
+            
+            sender.send(event);
+            fired += 1;
+        }
+        
+        if target_ns > self.timestamp_ns() {
+            self.set_time(target_ns);
         }
+        fired
     }
     
     /// Set time to specific timestamp
@@ -229,47 +524,58 @@ impl TestClock {
     }
 }
 
-#[async_trait]
 impl Clock for TestClock {
     fn timestamp_ns(&self) -> UnixNanos {
         self.current_time.load(std::sync::atomic::Ordering::Relaxed)
     }
     
-    async fn set_timer(
-        &mut self,
+    fn set_timer(
+        &self,
         name: String,
         interval_ns: u64,
         start_time_ns: u64,
         stop_time_ns: Option<u64>,
-        callback: TimerCallback,
+        sender: TimeEventSender,
     ) -> Result<()> {
         let timer = Timer {
             name: name.clone(),
             interval_ns,
             next_time_ns: start_time_ns,
             stop_time_ns,
-            callback: Arc::from(callback),
+            sender,
+            schedule: None,
         };
         
-        self.timers.lock().await.insert(name, timer);
+        self.timers.lock().insert(name, timer);
         Ok(())
     }
     
-    async fn cancel_timer(&mut self, name: String) -> Result<()> {
-        self.timers.lock().await.remove(&name);
+    fn set_scheduled_timer(
+        &self,
+        name: String,
+        schedule: Arc<dyn TimerSchedule>,
+        stop_time_ns: Option<u64>,
+        sender: TimeEventSender,
+    ) -> Result<()> {
+        let timer = scheduled_timer(name, schedule, self.timestamp_ns(), stop_time_ns, sender)?;
+        self.timers.lock().insert(timer.name.clone(), timer);
+        Ok(())
+    }
+    
+    fn cancel_timer(&self, name: String) -> Result<()> {
+        self.timers.lock().remove(&name);
         Ok(())
     }
     
     fn next_timer_ns(&self) -> Option<UnixNanos> {
         // For test clock, return earliest timer
-        self.current_time.load(std::sync::atomic::Ordering::Relaxed).into()
+        self.timers.lock().values().map(|timer| timer.next_time_ns).min()
     }
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
-    use std::sync::atomic::{AtomicBool, Ordering};
     use tokio::time::{sleep, Duration};
     
     #[tokio::test]
@@ -285,9 +591,11 @@ mod tests {
     
     #[tokio::test]
     async fn test_live_clock_timer() {
-        let mut clock = LiveClock::new();
-        let called = Arc::new(AtomicBool::new(false));
-        let called_clone = Arc::clone(&called);
+        let clock = LiveClock::new();
+        // The timer task starts with the first timer
+        assert!(clock.timer_tx.get().is_none());
+        assert!(clock.cancel_timer("none".to_string()).is_ok());
+        let (sender, mut events) = mpsc::unbounded_channel();
         
         let start_time = clock.timestamp_ns() + 10_000_000; // 10ms from now
         
@@ -296,29 +604,73 @@ mod tests {
             1_000_000, // 1ms interval
             start_time,
             None,
-            Box::new(move || {
-                called_clone.store(true, Ordering::Relaxed);
-            }),
-        ).await.unwrap();
+            TimeEventSender::Channel(sender),
+        ).unwrap();
         
         // Wait for timer to fire
         sleep(Duration::from_millis(20)).await;
         
-        assert!(called.load(Ordering::Relaxed));
+        let event = events.try_recv().unwrap();
+        assert_eq!((event.name.as_str(), event.ts_event), ("test_timer", start_time));
+        assert!(event.ts_init >= start_time);
+        assert!(clock.timer_tx.get().is_some());
     }
     
     #[test]
-    fn test_test_clock() {
-        let runtime = tokio::runtime::Runtime::new().unwrap();
-        
+    fn test_live_clock_without_runtime() {
+        let clock = LiveClock::new();
+        assert!(clock.timestamp_ns() > 0);
+        let (sender, mut events) = mpsc::unbounded_channel();
+        assert!(clock.set_timer("t".to_string(), 1_000_000, clock.timestamp_ns(), None, TimeEventSender::Channel(sender.clone())).is_err());
+
+        // The same clock runs timers once used within a runtime
+        let runtime = tokio::runtime::Builder::new_current_thread().enable_time().build().unwrap();
         runtime.block_on(async {
-            let start_time = 1000000000000000000; // Some fixed time
-            let clock = TestClock::new(start_time);
-            
-            assert_eq!(clock.timestamp_ns(), start_time);
-            
-            clock.advance_time(1000000000).await; // 1 second
-            assert_eq!(clock.timestamp_ns(), start_time + 1000000000);
+            let start_time = clock.timestamp_ns() + 1_000_000;
+            clock.set_timer("t".to_string(), 1_000_000, start_time, Some(start_time), TimeEventSender::Channel(sender)).unwrap();
+            let event = tokio::time::timeout(std::time::Duration::from_secs(5), events.recv()).await.unwrap().unwrap();
+            assert_eq!((event.name.as_str(), event.ts_event), ("t", start_time));
         });
     }
+    
+    #[test]
+    fn test_live_clock_high_resolution_timer() {
+        let clock = LiveClock::new().with_high_resolution(HighResolutionConfig::default()).unwrap();
+        let (sender, mut events) = mpsc::unbounded_channel();
+        
+        // Every 200us for 2ms, without a Tokio runtime
+        let start_time = clock.timestamp_ns() + 1_000_000;
+        let stop_time = start_time + 2_000_000;
+        clock.set_timer("fast".to_string(), 200_000, start_time, Some(stop_time), TimeEventSender::Channel(sender)).unwrap();
+        
+        // The channel closes once the expired timer is dropped, however late
+        // a loaded machine runs it
+        let fired: Vec<TimeEvent> = std::iter::from_fn(|| events.blocking_recv()).collect();
+        assert_eq!(fired[0].ts_event, start_time);
+        // Firings stay on the 200us grid; late ones skip missed firings
+        assert!(fired.iter().all(|event| (event.ts_event - start_time).is_multiple_of(200_000)));
+        assert!(fired.iter().all(|event| event.ts_event <= stop_time && event.ts_init >= event.ts_event));
+        assert!(fired.windows(2).all(|pair| pair[0].ts_event < pair[1].ts_event));
+        assert!(clock.cancel_timer("fast".to_string()).is_ok());
+    }
+    
+    #[test]
+    fn test_test_clock() {
+        let start_time = 1000000000000000000; // Some fixed time
+        let clock = TestClock::new(start_time);
+        
+        assert_eq!(clock.timestamp_ns(), start_time);
+        
+        clock.advance_time(1000000000); // 1 second
+        assert_eq!(clock.timestamp_ns(), start_time + 1000000000);
+        
+        let bus = Arc::new(MessageBus::new());
+        let mut timers = bus.subscribe("timers.backtest");
+        let sender = TimeEventSender::Bus { bus, topic: "timers.backtest".to_string() };
+        clock.set_timer("t".to_string(), 10, start_time + 1000000010, Some(start_time + 1000000020), sender).unwrap();
+        assert_eq!(clock.advance_time(100), 2);
+        let event: TimeEvent = timers.try_recv().unwrap().decode().unwrap();
+        assert_eq!(event, TimeEvent { name: "t".to_string(), ts_event: start_time + 1000000010, ts_init: start_time + 1000000010 });
+        assert_eq!(timers.len(), 1);
+    }
 }
@@ -0,0 +1,457 @@
+//! AlphaForge Configuration Files
+//!
+//! Loads a node's data, strategy, risk, execution, cache and logging
+//! settings from a TOML or YAML file, `alphaforge.toml` by default, so
+//! deployments keep configuration out of code.
+//!
+//! Any value can be overridden from the environment: `ALPHAFORGE__`
+//! followed by the path to the field, segments joined by `__` (array
+//! elements by index), e.g. `ALPHAFORGE__DATA_ENGINE__MAX_TICK_BUFFER_SIZE=5000`
+//! or `ALPHAFORGE__STRATEGIES__0__MAX_DAILY_LOSS=250`. Segments match keys
+//! case-insensitively, keeping the case of the key matched; a key not set
+//! anywhere is taken in lowercase. Override values are read as the type of
+//! the value they replace (a string stays a string, a number must parse as
+//! one); values of unset optional fields are read as JSON when they parse
+//! as JSON and as strings otherwise.
+//!
+//! Strategy and instrument IDs may be written as numbers or strings
+//! (`"BTCUSDT.BINANCE"`).
+
+use std::collections::{HashMap, HashSet};
+use std::path::Path;
+use std::str::FromStr;
+
+use serde::{Deserialize, Deserializer, Serialize};
+use serde_json::Value;
+
+use crate::cache::CacheConfig;
+use crate::data_engine::DataEngineConfig;
+use crate::error::{AlphaForgeError, Result};
+use crate::execution_engine::ExecutionEngine;
+use crate::identifiers::{InstrumentId, StrategyId};
+use crate::logging::{LogLevel, LoggingConfig};
+use crate::portfolio::Portfolio;
+use crate::strategy_engine::StrategyConfig;
+
+/// Prefix of environment variables overriding file settings
+pub const ENV_PREFIX: &str = "ALPHAFORGE__";
+
+/// File `NodeConfig::load` reads from the working directory
+pub const DEFAULT_CONFIG_FILE: &str = "alphaforge.toml";
+
+/// Environment variable naming the file `NodeConfig::load` reads instead
+pub const CONFIG_PATH_ENV: &str = "ALPHAFORGE_CONFIG";
+
+/// Syntax of a configuration file
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ConfigFormat {
+    Toml,
+    Yaml,
+}
+
+impl ConfigFormat {
+    /// Format implied by a file's extension
+    pub fn from_path(path: &Path) -> Result<Self> {
+        match path.extension().and_then(|extension| extension.to_str()) {
+            Some("toml") => Ok(ConfigFormat::Toml),
+            Some("yaml" | "yml") => Ok(ConfigFormat::Yaml),
+            _ => Err(AlphaForgeError::config(format!(
+                "Unsupported config file {}: expected .toml, .yaml or .yml",
+                path.display()
+            ))),
+        }
+    }
+}
+
+/// Capital allocated to one strategy
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct AllocationConfig {
+    #[serde(deserialize_with = "deserialize_strategy_id")]
+    pub strategy_id: StrategyId,
+    pub weight: f64,
+    #[serde(default)]
+    pub max_notional: Option<f64>,
+}
+
+/// Combined exposure cap on a group of instruments
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ExposureLimitConfig {
+    pub group: String,
+    #[serde(deserialize_with = "deserialize_instrument_ids")]
+    pub instruments: Vec<InstrumentId>,
+    pub max_notional: f64,
+}
+
+/// Portfolio risk settings
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct RiskConfig {
+    /// Capital shared out by strategy weights
+    pub total_capital: Option<f64>,
+    pub allocations: Vec<AllocationConfig>,
+    pub exposure_limits: Vec<ExposureLimitConfig>,
+}
+
+impl RiskConfig {
+    /// Install the allocations and exposure limits on `portfolio`
+    pub fn apply(&self, portfolio: &mut Portfolio) -> std::result::Result<(), String> {
+        if let Some(total_capital) = self.total_capital {
+            portfolio.allocation_mut().set_total_capital(total_capital)?;
+        }
+        for allocation in &self.allocations {
+            portfolio
+                .allocation_mut()
+                .allocate(allocation.strategy_id, allocation.weight, allocation.max_notional)?;
+        }
+        for limit in &self.exposure_limits {
+            let instruments: HashSet<_> = limit.instruments.iter().copied().collect();
+            portfolio.set_exposure_limit(&limit.group, instruments, limit.max_notional)?;
+        }
+        Ok(())
+    }
+}
+
+/// Order routing settings
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct ExecutionConfig {
+    /// Exchange adapter name by instrument ID
+    pub routing: HashMap<String, String>,
+}
+
+impl ExecutionConfig {
+    /// Configure `engine` to route each instrument to its exchange
+    pub fn apply(&self, engine: &ExecutionEngine) -> std::result::Result<(), String> {
+        for (instrument, exchange) in &self.routing {
+            engine.configure_routing(InstrumentId::from_str(instrument)?, exchange.clone());
+        }
+        Ok(())
+    }
+}
+
+/// Everything a node is configured with
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(default)]
+pub struct NodeConfig {
+    pub data_engine: DataEngineConfig,
+    pub strategies: Vec<StrategyConfig>,
+    pub risk: RiskConfig,
+    pub execution: ExecutionConfig,
+    pub cache: CacheConfig,
+    pub logging: LoggingConfig,
+}
+
+impl NodeConfig {
+    /// Load the file named by `ALPHAFORGE_CONFIG`, or `alphaforge.toml` in
+    /// the working directory. Without either file the defaults are used,
+    /// still with environment overrides.
+    pub fn load() -> Result<Self> {
+        match std::env::var(CONFIG_PATH_ENV) {
+            Ok(path) => Self::from_file(path),
+            Err(_) if Path::new(DEFAULT_CONFIG_FILE).exists() => Self::from_file(DEFAULT_CONFIG_FILE),
+            Err(_) => Self::parse("", ConfigFormat::Toml, std::env::vars()),
+        }
+    }
+
+    /// Load a TOML or YAML file (by extension), applying `ALPHAFORGE__`
+    /// environment overrides
+    pub fn from_file(path: impl AsRef<Path>) -> Result<Self> {
+        let path = path.as_ref();
+        let format = ConfigFormat::from_path(path)?;
+        let text = std::fs::read_to_string(path)
+            .map_err(|e| AlphaForgeError::from(e).with_context(format!("Reading {}", path.display())))?;
+        Self::parse(&text, format, std::env::vars())
+            .map_err(|e| e.with_context(format!("Loading {}", path.display())))
+    }
+
+    /// Parse configuration text, applying overrides given as
+    /// (`ALPHAFORGE__...` variable, value) pairs; other variables are ignored
+    pub fn parse(
+        text: &str,
+        format: ConfigFormat,
+        overrides: impl IntoIterator<Item = (String, String)>,
+    ) -> Result<Self> {
+        let mut value: Value = match format {
+            ConfigFormat::Toml => toml::from_str(text).map_err(|e| AlphaForgeError::config(e.to_string()))?,
+            ConfigFormat::Yaml => serde_yaml::from_str(text).map_err(|e| AlphaForgeError::config(e.to_string()))?,
+        };
+        if value.is_null() {
+            value = Value::Object(Default::default());
+        }
+
+        let mut overrides = overrides.into_iter().filter(|(name, _)| name.starts_with(ENV_PREFIX)).peekable();
+        if overrides.peek().is_some() {
+            // Defaults filled in, so overrides of fields left out of the
+            // file are read as the type declared for them
+            if let Ok(declared) = serde_json::from_value::<Self>(value.clone()).and_then(serde_json::to_value) {
+                value = declared;
+            }
+        }
+        for (name, raw) in overrides {
+            apply_override(&mut value, &name[ENV_PREFIX.len()..], &raw)?;
+        }
+
+        let config: Self = serde_json::from_value(value).map_err(|e| AlphaForgeError::config(e.to_string()))?;
+        config.validate()?;
+        Ok(config)
+    }
+
+    /// Check values no engine could run with and settings that span
+    /// sections
+    pub fn validate(&self) -> Result<()> {
+        for (field, value) in [
+            ("data_engine.max_bars_per_instrument", self.data_engine.max_bars_per_instrument),
+            ("data_engine.max_tick_buffer_size", self.data_engine.max_tick_buffer_size),
+            ("cache.max_items_per_type", self.cache.max_items_per_type),
+        ] {
+            if value == 0 {
+                return Err(AlphaForgeError::config(format!("{} must be positive", field)));
+            }
+        }
+        if self.cache.enable_persistence && self.cache.flush_interval_ms == 0 {
+            return Err(AlphaForgeError::config("cache.flush_interval_ms must be positive with persistence enabled"));
+        }
+
+        let mut strategy_ids = HashSet::new();
+        for strategy in &self.strategies {
+            if !strategy_ids.insert(strategy.strategy_id) {
+                return Err(AlphaForgeError::config(format!(
+                    "Strategy ID {} is configured more than once",
+                    strategy.strategy_id
+                )));
+            }
+        }
+        for allocation in &self.risk.allocations {
+            if !strategy_ids.contains(&allocation.strategy_id) {
+                return Err(AlphaForgeError::config(format!(
+                    "Allocation for unknown strategy {}",
+                    allocation.strategy_id
+                )));
+            }
+        }
+        Ok(())
+    }
+}
+
+// Set the field at `path` (`__`-separated, case-insensitive) to `raw`,
+// read as the type of the value it replaces
+fn apply_override(root: &mut Value, path: &str, raw: &str) -> Result<()> {
+    let mut target = root;
+    for segment in path.split("__") {
+        target = match target {
+            Value::Array(items) => {
+                let index: usize = segment
+                    .parse()
+                    .map_err(|_| AlphaForgeError::config(format!("Override {}: {} is not an index", path, segment)))?;
+                items
+                    .get_mut(index)
+                    .ok_or_else(|| AlphaForgeError::config(format!("Override {}: no element {}", path, index)))?
+            }
+            Value::Object(fields) => {
+                let key = fields
+                    .keys()
+                    .find(|key| *key == segment)
+                    .or_else(|| fields.keys().find(|key| key.eq_ignore_ascii_case(segment)))
+                    .cloned()
+                    .unwrap_or_else(|| segment.to_ascii_lowercase());
+                fields.entry(key).or_insert(Value::Null)
+            }
+            other => {
+                *other = Value::Object(Default::default());
+                other.as_object_mut().expect("just set").entry(segment.to_ascii_lowercase()).or_insert(Value::Null)
+            }
+        };
+    }
+    *target = override_value(target, raw)
+        .ok_or_else(|| AlphaForgeError::config(format!("Override {}: {:?} does not match the type of {}", path, raw, target)))?;
+    Ok(())
+}
+
+// `raw` read as the type of `current`, or as JSON falling back to a string
+// when there is no value to go by
+fn override_value(current: &Value, raw: &str) -> Option<Value> {
+    match current {
+        Value::Null => Some(serde_json::from_str(raw).unwrap_or_else(|_| Value::String(raw.to_string()))),
+        Value::String(_) => Some(Value::String(raw.to_string())),
+        Value::Bool(_) => raw.trim().parse().ok().map(Value::Bool),
+        Value::Number(_) => serde_json::from_str(raw.trim()).ok().filter(Value::is_number),
+        Value::Array(_) => serde_json::from_str(raw).ok().filter(Value::is_array),
+        Value::Object(_) => serde_json::from_str(raw).ok().filter(Value::is_object),
+    }
+}
+
+pub(crate) fn deserialize_log_level<'de, D: Deserializer<'de>>(deserializer: D) -> std::result::Result<LogLevel, D::Error> {
+    String::deserialize(deserializer)?.parse().map_err(serde::de::Error::custom)
+}
+
+pub(crate) fn deserialize_log_levels<'de, D: Deserializer<'de>>(
+    deserializer: D,
+) -> std::result::Result<HashMap<String, LogLevel>, D::Error> {
+    HashMap::<String, String>::deserialize(deserializer)?
+        .into_iter()
+        .map(|(component, level)| Ok((component, level.parse().map_err(serde::de::Error::custom)?)))
+        .collect()
+}
+
+/// An ID written as a number, a string or the serialized `{ id = .. }` form
+#[derive(Deserialize)]
+#[serde(untagged)]
+enum IdRepr {
+    Number(u64),
+    Text(String),
+    Struct { id: u64 },
+}
+
+pub(crate) fn deserialize_strategy_id<'de, D: Deserializer<'de>>(deserializer: D) -> std::result::Result<StrategyId, D::Error> {
+    match IdRepr::deserialize(deserializer)? {
+        IdRepr::Number(id) | IdRepr::Struct { id } => Ok(StrategyId::new(id)),
+        IdRepr::Text(text) => text
+            .parse()
+            .map(StrategyId::new)
+            .map_err(|_| serde::de::Error::custom(format!("Invalid strategy ID: {}", text))),
+    }
+}
+
+pub(crate) fn deserialize_instrument_ids<'de, D: Deserializer<'de>>(
+    deserializer: D,
+) -> std::result::Result<Vec<InstrumentId>, D::Error> {
+    Vec::<IdRepr>::deserialize(deserializer)?
+        .into_iter()
+        .map(instrument_id::<D::Error>)
+        .collect()
+}
+
+pub(crate) fn deserialize_optional_instrument_id<'de, D: Deserializer<'de>>(
+    deserializer: D,
+) -> std::result::Result<Option<InstrumentId>, D::Error> {
+    Option::<IdRepr>::deserialize(deserializer)?.map(instrument_id::<D::Error>).transpose()
+}
+
+fn instrument_id<E: serde::de::Error>(repr: IdRepr) -> std::result::Result<InstrumentId, E> {
+    match repr {
+        IdRepr::Number(id) | IdRepr::Struct { id } => Ok(InstrumentId::new(id)),
+        IdRepr::Text(text) => InstrumentId::from_str(&text).map_err(E::custom),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::data_channel::BackpressurePolicy;
+    use crate::strategy_engine::ExecutionMode;
+
+    const TOML: &str = r#"
+[data_engine]
+max_tick_buffer_size = 2000
+backpressure_policy = "DropOldest"
+
+[data_engine.persistence]
+directory = "/var/lib/alphaforge/ticks"
+
+[[strategies]]
+strategy_id = 1
+name = "Trend"
+instruments = ["BTCUSDT.BINANCE", 7]
+max_daily_loss = 500.0
+execution_mode = "Shadow"
+
+[[strategies]]
+strategy_id = "2"
+name = "Carry"
+
+[risk]
+total_capital = 1000000.0
+allocations = [{ strategy_id = 1, weight = 0.25 }]
+exposure_limits = [{ group = "crypto", instruments = ["BTCUSDT.BINANCE"], max_notional = 50000.0 }]
+
+[execution.routing]
+"BTCUSDT.BINANCE" = "BINANCE"
+
+[cache]
+max_items_per_type = 5000
+eviction_policy = "FIFO"
+
+[logging]
+level = "warn"
+format = "Json"
+components = { data_engine = "debug" }
+file = { directory = "/var/log/alphaforge", max_files = 3 }
+"#;
+
+    #[test]
+    fn test_toml_with_env_overrides() {
+        let overrides = vec![
+            ("ALPHAFORGE__DATA_ENGINE__ROLLING_WINDOW".to_string(), "30".to_string()),
+            ("ALPHAFORGE__STRATEGIES__1__MAX_DAILY_LOSS".to_string(), "250".to_string()),
+            ("ALPHAFORGE__RISK__TOTAL_CAPITAL".to_string(), "2000000".to_string()),
+            ("HOME".to_string(), "/root".to_string()),
+        ];
+        let config = NodeConfig::parse(TOML, ConfigFormat::Toml, overrides).unwrap();
+
+        let btc = InstrumentId::from_str("BTCUSDT.BINANCE").unwrap();
+        assert_eq!(config.data_engine.max_tick_buffer_size, 2000);
+        assert_eq!(config.data_engine.rolling_window, 30);
+        assert_eq!(config.data_engine.backpressure_policy, BackpressurePolicy::DropOldest);
+        assert_eq!(config.data_engine.persistence.as_ref().unwrap().file_prefix, "ticks");
+        assert_eq!(config.strategies[0].instruments, vec![btc, InstrumentId::new(7)]);
+        assert_eq!(config.strategies[0].execution_mode, ExecutionMode::Shadow);
+        assert_eq!(config.strategies[0].starting_equity, StrategyConfig::default().starting_equity);
+        assert_eq!(config.strategies[1].strategy_id, StrategyId::new(2));
+        assert_eq!(config.strategies[1].max_daily_loss, Some(250.0));
+
+        let mut portfolio = Portfolio::default();
+        config.risk.apply(&mut portfolio).unwrap();
+        assert_eq!(portfolio.allocation().capital(StrategyId::new(1)), Some(500_000.0));
+        assert_eq!(portfolio.exposure_group("crypto").unwrap().max_notional, 50_000.0);
+        assert_eq!(config.execution.routing["BTCUSDT.BINANCE"], "BINANCE");
+        assert_eq!(config.cache.max_items_per_type, 5000);
+        assert_eq!(config.cache.eviction_policy, crate::cache::EvictionPolicy::FIFO);
+        assert_eq!(config.logging.level, LogLevel::Warn);
+        assert_eq!(config.logging.level_for("alphaforge_core::data_engine"), LogLevel::Debug);
+        assert_eq!(config.logging.format, crate::logging::LogFormat::Json);
+        assert_eq!(config.logging.file.as_ref().unwrap().max_files, 3);
+    }
+
+    #[test]
+    fn test_yaml_and_invalid_configs() {
+        let yaml = "
+strategies:
+  - strategy_id: 3
+    name: MeanReversion
+    parameters:
+      values:
+        lookback:
+          Int: 20
+        maxHold:
+          Int: 5
+      ranges: {}
+";
+        let config = NodeConfig::parse(yaml, ConfigFormat::Yaml, Vec::new()).unwrap();
+        assert_eq!(config.strategies[0].name, "MeanReversion");
+        assert_eq!(config.strategies[0].parameters.get_int("lookback").unwrap(), 20);
+        assert_eq!(config.data_engine.max_tick_buffer_size, DataEngineConfig::default().max_tick_buffer_size);
+
+        // Keys keep their case and values are read as the type they replace
+        let overrides = vec![
+            ("ALPHAFORGE__STRATEGIES__0__PARAMETERS__VALUES__MAXHOLD__INT".to_string(), "8".to_string()),
+            ("ALPHAFORGE__STRATEGIES__0__NAME".to_string(), "123".to_string()),
+        ];
+        let overridden = NodeConfig::parse(yaml, ConfigFormat::Yaml, overrides).unwrap();
+        assert_eq!(overridden.strategies[0].parameters.get_int("maxHold").unwrap(), 8);
+        assert_eq!(overridden.strategies[0].name, "123");
+        let not_a_number = vec![("ALPHAFORGE__DATA_ENGINE__ROLLING_WINDOW".to_string(), "wide".to_string())];
+        assert!(NodeConfig::parse(yaml, ConfigFormat::Yaml, not_a_number).is_err());
+
+        let duplicate = "strategies:\n  - strategy_id: 1\n  - strategy_id: 1\n";
+        assert!(NodeConfig::parse(duplicate, ConfigFormat::Yaml, Vec::new()).is_err());
+        let unallocated = "risk:\n  allocations:\n    - { strategy_id: 9, weight: 0.5 }\n";
+        assert!(NodeConfig::parse(unallocated, ConfigFormat::Yaml, Vec::new()).is_err());
+        let bad_index = vec![("ALPHAFORGE__STRATEGIES__5__NAME".to_string(), "x".to_string())];
+        assert!(NodeConfig::parse(yaml, ConfigFormat::Yaml, bad_index).is_err());
+        assert!(ConfigFormat::from_path(Path::new("node.json")).is_err());
+        assert_eq!(config.logging, LoggingConfig::default());
+        for invalid in ["cache:\n  max_items_per_type: 0\n", "data_engine:\n  max_tick_buffer_size: 0\n", "logging:\n  level: loud\n"] {
+            assert!(NodeConfig::parse(invalid, ConfigFormat::Yaml, Vec::new()).is_err());
+        }
+    }
+}
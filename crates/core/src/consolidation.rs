@@ -0,0 +1,224 @@
+//! AlphaForge Quote Consolidation
+//!
+//! Builds a composite best bid/offer across venues listing the same symbol.
+//! The composite is published as an ordinary `QuoteTick` under a synthetic
+//! instrument ID, so strategies subscribe to it like any other instrument.
+
+use std::collections::HashMap;
+
+use crate::data::QuoteTick;
+use crate::identifiers::InstrumentId;
+use crate::time::UnixNanos;
+
+/// Venue name used for composite instrument IDs
+pub const COMPOSITE_VENUE: &str = "NBBO";
+
+/// Composite quote with the venues supplying each side
+#[derive(Debug, Clone)]
+pub struct CompositeQuote {
+    pub quote: QuoteTick,
+    pub bid_venue: String,
+    pub ask_venue: String,
+}
+
+/// Latest quote from one venue of a consolidated symbol
+#[derive(Debug, Clone)]
+struct VenueQuote {
+    venue: String,
+    quote: Option<QuoteTick>,
+}
+
+/// Consolidated book for one symbol
+#[derive(Debug, Clone)]
+struct ConsolidatedSymbol {
+    composite_id: InstrumentId,
+    venues: Vec<VenueQuote>,
+    last: Option<CompositeQuote>,
+}
+
+/// Produces composite best bid/offer quotes across venues
+#[derive(Debug, Default)]
+pub struct QuoteConsolidator {
+    /// Venue quotes older than this relative to the triggering quote are ignored
+    stale_after_ns: Option<u64>,
+    symbols: HashMap<InstrumentId, ConsolidatedSymbol>,
+    // Venue instrument -> (composite ID, index into venues)
+    constituents: HashMap<InstrumentId, (InstrumentId, usize)>,
+}
+
+impl QuoteConsolidator {
+    pub fn new(stale_after_ns: Option<u64>) -> Self {
+        Self {
+            stale_after_ns,
+            ..Default::default()
+        }
+    }
+
+    /// Composite instrument ID for a symbol
+    pub fn composite_id(symbol: &str) -> InstrumentId {
+        InstrumentId::from_symbol_venue(symbol, COMPOSITE_VENUE)
+    }
+
+    /// Add a venue listing of `symbol` to its composite, returning the composite ID.
+    ///
+    /// Registering a listing again is a no-op; a second listing on a venue
+    /// already in the composite is rejected.
+    pub fn register(&mut self, symbol: &str, venue: &str, instrument_id: InstrumentId) -> Result<InstrumentId, String> {
+        let composite_id = Self::composite_id(symbol);
+        if let Some((existing, _)) = self.constituents.get(&instrument_id) {
+            return Ok(*existing);
+        }
+
+        let entry = self.symbols.entry(composite_id).or_insert_with(|| ConsolidatedSymbol {
+            composite_id,
+            venues: Vec::new(),
+            last: None,
+        });
+        if entry.venues.iter().any(|v| v.venue == venue) {
+            return Err(format!("Composite {} already has a listing on {}", composite_id, venue));
+        }
+        entry.venues.push(VenueQuote {
+            venue: venue.to_string(),
+            quote: None,
+        });
+        self.constituents.insert(instrument_id, (composite_id, entry.venues.len() - 1));
+        Ok(composite_id)
+    }
+
+    /// Check if an instrument feeds a composite
+    pub fn is_constituent(&self, instrument_id: InstrumentId) -> bool {
+        self.constituents.contains_key(&instrument_id)
+    }
+
+    /// Update with a venue quote.
+    ///
+    /// Returns a new composite quote when the best bid or offer (price,
+    /// size or source venue) changed.
+    pub fn update(&mut self, tick: &QuoteTick) -> Option<CompositeQuote> {
+        let &(composite_id, idx) = self.constituents.get(&tick.instrument_id)?;
+        let symbol = self.symbols.get_mut(&composite_id)?;
+        symbol.venues[idx].quote = Some(tick.clone());
+
+        let cutoff = self.stale_after_ns.and_then(|age| tick.ts_event.checked_sub(age));
+        let composite = Self::best_of(symbol, tick.ts_event, tick.ts_init, cutoff)?;
+
+        let changed = symbol.last.as_ref().is_none_or(|last| {
+            last.bid_venue != composite.bid_venue
+                || last.ask_venue != composite.ask_venue
+                || last.quote.bid_price != composite.quote.bid_price
+                || last.quote.ask_price != composite.quote.ask_price
+                || last.quote.bid_size != composite.quote.bid_size
+                || last.quote.ask_size != composite.quote.ask_size
+        });
+        if !changed {
+            return None;
+        }
+
+        symbol.last = Some(composite.clone());
+        Some(composite)
+    }
+
+    /// Latest composite quote
+    pub fn get(&self, composite_id: InstrumentId) -> Option<&CompositeQuote> {
+        self.symbols.get(&composite_id).and_then(|s| s.last.as_ref())
+    }
+
+    fn best_of(
+        symbol: &ConsolidatedSymbol,
+        ts_event: UnixNanos,
+        ts_init: UnixNanos,
+        cutoff: Option<UnixNanos>,
+    ) -> Option<CompositeQuote> {
+        let live = symbol.venues.iter().filter_map(|v| {
+            let quote = v.quote.as_ref()?;
+            match cutoff {
+                Some(cutoff) if quote.ts_event < cutoff => None,
+                _ => Some((v.venue.as_str(), quote)),
+            }
+        });
+
+        let mut best: Option<(&str, &QuoteTick, &str, &QuoteTick)> = None;
+        for (venue, quote) in live {
+            best = Some(match best {
+                None => (venue, quote, venue, quote),
+                Some((bid_venue, bid, ask_venue, ask)) => {
+                    let (bid_venue, bid) = if quote.bid_price > bid.bid_price { (venue, quote) } else { (bid_venue, bid) };
+                    let (ask_venue, ask) = if quote.ask_price < ask.ask_price { (venue, quote) } else { (ask_venue, ask) };
+                    (bid_venue, bid, ask_venue, ask)
+                }
+            });
+        }
+
+        let (bid_venue, bid, ask_venue, ask) = best?;
+        Some(CompositeQuote {
+            quote: QuoteTick {
+                instrument_id: symbol.composite_id,
+                bid_price: bid.bid_price,
+                ask_price: ask.ask_price,
+                bid_size: bid.bid_size,
+                ask_size: ask.ask_size,
+                ts_event,
+                ts_init,
+            },
+            bid_venue: bid_venue.to_string(),
+            ask_venue: ask_venue.to_string(),
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn quote(instrument_id: InstrumentId, bid: f64, ask: f64, ts: UnixNanos) -> QuoteTick {
+        QuoteTick {
+            instrument_id,
+            bid_price: bid,
+            ask_price: ask,
+            bid_size: 1.0,
+            ask_size: 2.0,
+            ts_event: ts,
+            ts_init: ts,
+        }
+    }
+
+    #[test]
+    fn test_best_bid_offer_across_venues() {
+        let binance = InstrumentId::from_symbol_venue("BTCUSDT", "BINANCE");
+        let okx = InstrumentId::from_symbol_venue("BTCUSDT", "OKX");
+
+        let mut consolidator = QuoteConsolidator::new(None);
+        let composite_id = consolidator.register("BTCUSDT", "BINANCE", binance).unwrap();
+        consolidator.register("BTCUSDT", "OKX", okx).unwrap();
+        assert_eq!(consolidator.register("BTCUSDT", "OKX", okx), Ok(composite_id));
+        let okx_perp = InstrumentId::from_symbol_venue("BTCUSDT-PERP", "OKX");
+        assert!(consolidator.register("BTCUSDT", "OKX", okx_perp).is_err());
+
+        consolidator.update(&quote(binance, 100.0, 101.0, 1)).unwrap();
+        let composite = consolidator.update(&quote(okx, 100.5, 101.5, 2)).unwrap();
+
+        assert_eq!(composite.quote.instrument_id, composite_id);
+        assert_eq!(composite.quote.bid_price, 100.5);
+        assert_eq!(composite.quote.ask_price, 101.0);
+        assert_eq!(composite.bid_venue, "OKX");
+        assert_eq!(composite.ask_venue, "BINANCE");
+
+        // No change to the composite, nothing published
+        assert!(consolidator.update(&quote(okx, 100.5, 101.4, 3)).is_none());
+    }
+
+    #[test]
+    fn test_stale_venue_excluded() {
+        let binance = InstrumentId::from_symbol_venue("BTCUSDT", "BINANCE");
+        let okx = InstrumentId::from_symbol_venue("BTCUSDT", "OKX");
+
+        let mut consolidator = QuoteConsolidator::new(Some(10));
+        consolidator.register("BTCUSDT", "BINANCE", binance).unwrap();
+        consolidator.register("BTCUSDT", "OKX", okx).unwrap();
+
+        consolidator.update(&quote(binance, 100.9, 101.0, 1));
+        let composite = consolidator.update(&quote(okx, 100.5, 101.5, 20)).unwrap();
+        assert_eq!(composite.bid_venue, "OKX");
+        assert_eq!(composite.quote.ask_price, 101.5);
+    }
+}
@@ -1,8 +1,14 @@
 //! AlphaForge Data Types
 //! 
 //! Core data types for market data, orders, and trading events.
+//!
+//! Tick prices and sizes are still `f64` on the wire and in memory; the
+//! `*_decimal()` accessors convert them exactly for aggregation. Migrating
+//! the tick types to fixed-point `Price`/`Quantity` fields is not done yet.
 
 use serde::{Serialize, Deserialize};
+use rust_decimal::Decimal;
+use rust_decimal::prelude::FromPrimitive;
 use crate::identifiers::*;
 use crate::time::UnixNanos;
 
@@ -18,6 +24,28 @@ pub struct QuoteTick {
     pub ts_init: UnixNanos,
 }
 
+impl QuoteTick {
+    /// Bid price as an exact decimal, None if not finite
+    pub fn bid_price_decimal(&self) -> Option<Decimal> {
+        to_decimal(self.bid_price)
+    }
+
+    /// Ask price as an exact decimal, None if not finite
+    pub fn ask_price_decimal(&self) -> Option<Decimal> {
+        to_decimal(self.ask_price)
+    }
+
+    /// Bid size as an exact decimal, None if not finite
+    pub fn bid_size_decimal(&self) -> Option<Decimal> {
+        to_decimal(self.bid_size)
+    }
+
+    /// Ask size as an exact decimal, None if not finite
+    pub fn ask_size_decimal(&self) -> Option<Decimal> {
+        to_decimal(self.ask_size)
+    }
+}
+
 /// Market data trade tick
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct TradeTick {
@@ -30,6 +58,158 @@ pub struct TradeTick {
     pub ts_init: UnixNanos,
 }
 
+impl TradeTick {
+    /// Trade price as an exact decimal, None if not finite
+    pub fn price_decimal(&self) -> Option<Decimal> {
+        to_decimal(self.price)
+    }
+
+    /// Trade size as an exact decimal, None if not finite
+    pub fn size_decimal(&self) -> Option<Decimal> {
+        to_decimal(self.size)
+    }
+}
+
+/// Convert a wire-format float to the shortest decimal that round-trips to it,
+/// so `0.1` becomes exactly `0.1` rather than its binary approximation.
+/// NaN, infinities and values beyond `Decimal`'s range give None.
+pub fn to_decimal(value: f64) -> Option<Decimal> {
+    Decimal::from_f64(value)
+}
+
+/// Order book delta buffer for efficient updates
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct OrderBookDeltas {
+    pub instrument_id: InstrumentId,
+    pub deltas: Vec<OrderBookDelta>,
+    pub sequence_number: u64,
+    pub ts_last_update: UnixNanos,
+}
+
+/// Individual order book delta
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct OrderBookDelta {
+    pub side: BookSide,
+    pub action: DeltaAction,
+    pub price: f64,
+    pub size: f64,
+    pub order_id: Option<String>,
+    pub ts: UnixNanos,
+}
+
+/// Order book side
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum BookSide {
+    Bid,
+    Ask,
+}
+
+/// Delta action type
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum DeltaAction {
+    Add,
+    Update,
+    Delete,
+}
+
+/// Perpetual swap funding rate update
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct FundingRateUpdate {
+    pub instrument_id: InstrumentId,
+    /// Funding rate for the current interval (e.g. `0.0001` = 0.01%)
+    pub rate: f64,
+    /// Time of the next funding settlement, if known
+    pub next_funding_ts: Option<UnixNanos>,
+    pub ts_event: UnixNanos,
+    pub ts_init: UnixNanos,
+}
+
+/// Open interest update for a derivatives instrument
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct OpenInterestUpdate {
+    pub instrument_id: InstrumentId,
+    /// Outstanding contracts
+    pub open_interest: f64,
+    pub ts_event: UnixNanos,
+    pub ts_init: UnixNanos,
+}
+
+/// Derivatives mark price used for PnL and margin
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct MarkPriceUpdate {
+    pub instrument_id: InstrumentId,
+    pub mark_price: f64,
+    pub ts_event: UnixNanos,
+    pub ts_init: UnixNanos,
+}
+
+/// Underlying index price referenced by a derivatives instrument
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct IndexPriceUpdate {
+    pub instrument_id: InstrumentId,
+    pub index_price: f64,
+    pub ts_event: UnixNanos,
+    pub ts_init: UnixNanos,
+}
+
+/// Named numeric signal (e.g. a model output) shared between components
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SignalData {
+    pub name: String,
+    pub value: f64,
+    pub ts: UnixNanos,
+}
+
+impl SignalData {
+    pub fn new(name: &str, value: f64, ts: UnixNanos) -> Self {
+        Self {
+            name: name.to_string(),
+            value,
+            ts,
+        }
+    }
+}
+
+/// Market data item accepted by the Data Engine ingestion path
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub enum MarketData {
+    Trade(TradeTick),
+    Quote(QuoteTick),
+    BookDeltas(OrderBookDeltas),
+    FundingRate(FundingRateUpdate),
+    OpenInterest(OpenInterestUpdate),
+    MarkPrice(MarkPriceUpdate),
+    IndexPrice(IndexPriceUpdate),
+}
+
+impl MarketData {
+    /// Instrument the data item belongs to
+    pub fn instrument_id(&self) -> InstrumentId {
+        match self {
+            MarketData::Trade(tick) => tick.instrument_id,
+            MarketData::Quote(tick) => tick.instrument_id,
+            MarketData::BookDeltas(deltas) => deltas.instrument_id,
+            MarketData::FundingRate(update) => update.instrument_id,
+            MarketData::OpenInterest(update) => update.instrument_id,
+            MarketData::MarkPrice(update) => update.instrument_id,
+            MarketData::IndexPrice(update) => update.instrument_id,
+        }
+    }
+
+    /// Time the item was received by the system
+    pub fn ts_init(&self) -> UnixNanos {
+        match self {
+            MarketData::Trade(tick) => tick.ts_init,
+            MarketData::Quote(tick) => tick.ts_init,
+            MarketData::BookDeltas(deltas) => deltas.ts_last_update,
+            MarketData::FundingRate(update) => update.ts_init,
+            MarketData::OpenInterest(update) => update.ts_init,
+            MarketData::MarkPrice(update) => update.ts_init,
+            MarketData::IndexPrice(update) => update.ts_init,
+        }
+    }
+}
+
 /// OHLCV bar data
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct Bar {
@@ -50,6 +230,12 @@ pub struct BarType {
     pub bar_spec: BarSpecification,
 }
 
+impl std::fmt::Display for BarType {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}-{}-{:?}", self.instrument_id, self.bar_spec.step, self.bar_spec.aggregation)
+    }
+}
+
 /// Bar specification with aggregation method
 #[derive(Debug, Clone, Hash, PartialEq, Eq, Serialize, Deserialize)]
 pub struct BarSpecification {
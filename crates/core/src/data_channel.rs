@@ -0,0 +1,305 @@
+//! Bounded ingestion channel for the Data Engine
+//!
+//! Feed handlers push market data into a `DataSender` and the Data Engine
+//! drains the matching `DataReceiver` on its own task. When the buffer is
+//! full the configured `BackpressurePolicy` decides what happens.
+
+use std::collections::VecDeque;
+use std::sync::Arc;
+use std::sync::atomic::{AtomicBool, AtomicU64, AtomicUsize, Ordering};
+use parking_lot::Mutex;
+use serde::{Deserialize, Serialize};
+use tokio::sync::Notify;
+
+use crate::data::MarketData;
+use crate::error::{AlphaForgeError, Result};
+
+/// Behaviour of the ingestion channel when producers outpace the engine
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub enum BackpressurePolicy {
+    /// Wait for free capacity (lossless)
+    #[default]
+    Block,
+    /// Discard the oldest buffered item to make room
+    DropOldest,
+    /// Replace a pending quote for the same instrument with the newer one,
+    /// blocking for everything else
+    CoalesceQuotes,
+}
+
+/// State shared between the sending and receiving halves
+#[derive(Debug)]
+struct Shared {
+    queue: Mutex<VecDeque<MarketData>>,
+    capacity: usize,
+    policy: BackpressurePolicy,
+    senders: AtomicUsize,
+    receiver_alive: AtomicBool,
+    not_empty: Notify,
+    not_full: Notify,
+    dropped: AtomicU64,
+    coalesced: AtomicU64,
+}
+
+/// Create a bounded ingestion channel
+pub fn data_channel(capacity: usize, policy: BackpressurePolicy) -> (DataSender, DataReceiver) {
+    let shared = Arc::new(Shared {
+        queue: Mutex::new(VecDeque::with_capacity(capacity)),
+        capacity: capacity.max(1),
+        policy,
+        senders: AtomicUsize::new(1),
+        receiver_alive: AtomicBool::new(true),
+        not_empty: Notify::new(),
+        not_full: Notify::new(),
+        dropped: AtomicU64::new(0),
+        coalesced: AtomicU64::new(0),
+    });
+
+    (
+        DataSender { shared: Arc::clone(&shared) },
+        DataReceiver { shared },
+    )
+}
+
+/// Outcome of a single non-blocking push attempt
+enum PushOutcome {
+    Pushed,
+    Full(MarketData),
+}
+
+/// Producer half of the ingestion channel
+#[derive(Debug)]
+pub struct DataSender {
+    shared: Arc<Shared>,
+}
+
+impl DataSender {
+    /// Send a data item, waiting for capacity if the policy requires it
+    pub async fn send(&self, mut data: MarketData) -> Result<()> {
+        loop {
+            let notified = self.shared.not_full.notified();
+            data = match self.try_push(data)? {
+                PushOutcome::Pushed => return Ok(()),
+                PushOutcome::Full(data) => data,
+            };
+            notified.await;
+        }
+    }
+
+    /// Send a data item from synchronous code, parking the thread while full
+    pub fn blocking_send(&self, data: MarketData) -> Result<()> {
+        futures::executor::block_on(self.send(data))
+    }
+
+    /// Send a data item without waiting; fails if the buffer is full
+    pub fn try_send(&self, data: MarketData) -> Result<()> {
+        match self.try_push(data)? {
+            PushOutcome::Pushed => Ok(()),
+            PushOutcome::Full(_) => Err(AlphaForgeError::runtime("Data channel is full")),
+        }
+    }
+
+    /// Number of items discarded by the `DropOldest` policy
+    pub fn dropped_count(&self) -> u64 {
+        self.shared.dropped.load(Ordering::Relaxed)
+    }
+
+    /// Number of quotes superseded by the `CoalesceQuotes` policy
+    pub fn coalesced_count(&self) -> u64 {
+        self.shared.coalesced.load(Ordering::Relaxed)
+    }
+
+    /// Number of items currently buffered
+    pub fn len(&self) -> usize {
+        self.shared.queue.lock().len()
+    }
+
+    /// Check if the buffer is empty
+    pub fn is_empty(&self) -> bool {
+        self.shared.queue.lock().is_empty()
+    }
+
+    fn try_push(&self, data: MarketData) -> Result<PushOutcome> {
+        if !self.shared.receiver_alive.load(Ordering::Acquire) {
+            return Err(AlphaForgeError::runtime("Data channel receiver dropped"));
+        }
+
+        let mut queue = self.shared.queue.lock();
+
+        if self.shared.policy == BackpressurePolicy::CoalesceQuotes {
+            if let MarketData::Quote(quote) = &data {
+                let pending = queue.iter().position(|item| {
+                    matches!(item, MarketData::Quote(q) if q.instrument_id == quote.instrument_id)
+                });
+                if let Some(idx) = pending {
+                    // Re-queue at the back so ordering against trades is preserved
+                    queue.remove(idx);
+                    queue.push_back(data);
+                    self.shared.coalesced.fetch_add(1, Ordering::Relaxed);
+                    return Ok(PushOutcome::Pushed);
+                }
+            }
+        }
+
+        if queue.len() >= self.shared.capacity {
+            match self.shared.policy {
+                BackpressurePolicy::DropOldest => {
+                    queue.pop_front();
+                    self.shared.dropped.fetch_add(1, Ordering::Relaxed);
+                }
+                BackpressurePolicy::Block | BackpressurePolicy::CoalesceQuotes => {
+                    return Ok(PushOutcome::Full(data));
+                }
+            }
+        }
+
+        queue.push_back(data);
+        drop(queue);
+        self.shared.not_empty.notify_one();
+        Ok(PushOutcome::Pushed)
+    }
+}
+
+impl Clone for DataSender {
+    fn clone(&self) -> Self {
+        self.shared.senders.fetch_add(1, Ordering::Relaxed);
+        Self { shared: Arc::clone(&self.shared) }
+    }
+}
+
+impl Drop for DataSender {
+    fn drop(&mut self) {
+        if self.shared.senders.fetch_sub(1, Ordering::AcqRel) == 1 {
+            self.shared.not_empty.notify_one();
+        }
+    }
+}
+
+/// Consumer half of the ingestion channel
+#[derive(Debug)]
+pub struct DataReceiver {
+    shared: Arc<Shared>,
+}
+
+impl DataReceiver {
+    /// Receive the next data item; returns `None` once all senders are gone
+    /// and the buffer has been drained
+    pub async fn recv(&mut self) -> Option<MarketData> {
+        let shared = Arc::clone(&self.shared);
+        loop {
+            let notified = shared.not_empty.notified();
+            if let Some(data) = self.try_recv() {
+                return Some(data);
+            }
+            if shared.senders.load(Ordering::Acquire) == 0 {
+                return None;
+            }
+            notified.await;
+        }
+    }
+
+    /// Receive a data item if one is buffered
+    pub fn try_recv(&mut self) -> Option<MarketData> {
+        let data = self.shared.queue.lock().pop_front();
+        if data.is_some() {
+            self.shared.not_full.notify_one();
+        }
+        data
+    }
+}
+
+impl Drop for DataReceiver {
+    fn drop(&mut self) {
+        self.shared.receiver_alive.store(false, Ordering::Release);
+        self.shared.not_full.notify_waiters();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::data::{QuoteTick, TradeTick, AggressorSide};
+    use crate::identifiers::InstrumentId;
+
+    fn quote(instrument: u64, bid: f64) -> MarketData {
+        MarketData::Quote(QuoteTick {
+            instrument_id: InstrumentId::new(instrument),
+            bid_price: bid,
+            ask_price: bid + 1.0,
+            bid_size: 1.0,
+            ask_size: 1.0,
+            ts_event: 0,
+            ts_init: 0,
+        })
+    }
+
+    fn trade(instrument: u64, price: f64) -> MarketData {
+        MarketData::Trade(TradeTick {
+            instrument_id: InstrumentId::new(instrument),
+            price,
+            size: 1.0,
+            aggressor_side: AggressorSide::Buyer,
+            trade_id: price.to_string(),
+            ts_event: 0,
+            ts_init: 0,
+        })
+    }
+
+    #[test]
+    fn test_block_policy_rejects_when_full() {
+        let (tx, mut rx) = data_channel(1, BackpressurePolicy::Block);
+
+        tx.try_send(trade(1, 100.0)).unwrap();
+        assert!(tx.try_send(trade(1, 101.0)).is_err());
+
+        assert!(rx.try_recv().is_some());
+        tx.try_send(trade(1, 101.0)).unwrap();
+    }
+
+    #[test]
+    fn test_drop_oldest_policy() {
+        let (tx, mut rx) = data_channel(2, BackpressurePolicy::DropOldest);
+
+        for price in [100.0, 101.0, 102.0] {
+            tx.try_send(trade(1, price)).unwrap();
+        }
+
+        assert_eq!(tx.dropped_count(), 1);
+        match rx.try_recv() {
+            Some(MarketData::Trade(tick)) => assert_eq!(tick.price, 101.0),
+            other => panic!("unexpected item: {:?}", other),
+        }
+    }
+
+    #[test]
+    fn test_coalesce_quotes_policy() {
+        let (tx, mut rx) = data_channel(8, BackpressurePolicy::CoalesceQuotes);
+
+        tx.try_send(quote(1, 100.0)).unwrap();
+        tx.try_send(trade(1, 100.5)).unwrap();
+        tx.try_send(quote(2, 50.0)).unwrap();
+        tx.try_send(quote(1, 100.2)).unwrap();
+
+        assert_eq!(tx.len(), 3);
+        assert_eq!(tx.coalesced_count(), 1);
+
+        assert!(matches!(rx.try_recv(), Some(MarketData::Trade(_))));
+        assert!(matches!(rx.try_recv(), Some(MarketData::Quote(q)) if q.bid_price == 50.0));
+        assert!(matches!(rx.try_recv(), Some(MarketData::Quote(q)) if q.bid_price == 100.2));
+    }
+
+    #[tokio::test]
+    async fn test_blocked_sender_resumes_after_recv() {
+        let (tx, mut rx) = data_channel(1, BackpressurePolicy::Block);
+        tx.send(trade(1, 100.0)).await.unwrap();
+
+        let producer = tokio::spawn(async move {
+            tx.send(trade(1, 101.0)).await.unwrap();
+        });
+
+        assert!(rx.recv().await.is_some());
+        producer.await.unwrap();
+        assert!(rx.recv().await.is_some());
+        assert!(rx.recv().await.is_none());
+    }
+}
@@ -3,27 +3,67 @@
 //! Central orchestrator for market data processing with high-performance
 //! tick aggregation, bar construction, and order book management.
 
-use std::collections::HashMap;
-use std::sync::{Arc, RwLock};
+use std::collections::{HashMap, HashSet, VecDeque};
+use std::path::PathBuf;
+use std::sync::{Arc, Mutex, RwLock};
+use std::time::Duration;
+use rust_decimal::Decimal;
+use rust_decimal::prelude::ToPrimitive;
+use serde::{Deserialize, Serialize};
+use tokio::task::JoinHandle;
+use tracing::{debug, info, warn};
 
+use crate::cache::{Cache, CacheConfig, InstrumentAny};
+use crate::clock::{Clock, LiveClock, TimerSchedule};
+use crate::consolidation::{CompositeQuote, QuoteConsolidator};
+use crate::synthetic::{SyntheticEngine, SyntheticInstrument};
+use crate::analytics::{
+    FeedLatency, FeedLatencySnapshot, OrderFlow, OrderFlowSnapshot, RollingStatistics, SessionPrices,
+};
+use crate::message_bus::MessageBus;
+use crate::persistence::{PersistenceConfig, TickWriter};
+use crate::schedule::AlignedSchedule;
 use crate::data::*;
+use crate::data_channel::{BackpressurePolicy, DataReceiver};
 use crate::identifiers::*;
 use crate::time::UnixNanos;
+use crate::validation::{DataValidator, ValidationConfig, ViolationCounts};
 use crate::generic_cache::GenericCache;
 
 /// Configuration for the Data Engine
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
 pub struct DataEngineConfig {
     /// Maximum number of bars to cache per instrument
     pub max_bars_per_instrument: usize,
     /// Maximum number of ticks to buffer before processing
     pub max_tick_buffer_size: usize,
+    /// Policy applied by the ingestion channel when the buffer is full
+    pub backpressure_policy: BackpressurePolicy,
     /// Enable real-time bar aggregation
     pub enable_bar_aggregation: bool,
     /// Enable order book delta buffering
     pub enable_order_book_deltas: bool,
     /// Enable statistics collection
     pub enable_statistics: bool,
+    /// Number of recent trade IDs remembered per instrument for duplicate
+    /// filtering (`None` disables deduplication)
+    pub trade_dedup_window: Option<usize>,
+    /// Number of bars used for rolling volatility, ATR and high/low
+    pub rolling_window: usize,
+    /// Time windows (nanoseconds) for buy/sell aggressor volume aggregates
+    pub order_flow_windows: Vec<u64>,
+    /// Sanity checks applied to incoming ticks (`None`, the default,
+    /// disables validation)
+    pub validation: Option<ValidationConfig>,
+    /// Venue quotes older than this (nanoseconds) are left out of composite
+    /// cross-venue quotes (`None` keeps every venue's last quote)
+    pub composite_quote_stale_ns: Option<u64>,
+    /// Feed latency (`ts_init - ts_event`, nanoseconds) above which an alert
+    /// is published on the message bus (`None` disables alerts)
+    pub latency_alert_threshold_ns: Option<u64>,
+    /// Record every accepted data item to rotating files (`None` disables)
+    pub persistence: Option<PersistenceConfig>,
 }
 
 impl Default for DataEngineConfig {
@@ -31,9 +71,17 @@ impl Default for DataEngineConfig {
         Self {
             max_bars_per_instrument: 10_000,
             max_tick_buffer_size: 1_000,
+            backpressure_policy: BackpressurePolicy::Block,
             enable_bar_aggregation: true,
             enable_order_book_deltas: true,
             enable_statistics: true,
+            trade_dedup_window: None,
+            rolling_window: 14,
+            order_flow_windows: vec![60_000_000_000, 300_000_000_000], // 1m, 5m
+            validation: None,
+            composite_quote_stale_ns: Some(5_000_000_000), // 5s
+            latency_alert_threshold_ns: Some(1_000_000_000), // 1s
+            persistence: None,
         }
     }
 }
@@ -47,6 +95,14 @@ pub struct DataEngineStatistics {
     pub bars_generated: u64,
     /// Total order book updates
     pub order_book_updates: u64,
+    /// Duplicate trade ticks filtered out
+    pub duplicate_trades_filtered: u64,
+    /// Completed bars evicted from aggregator history
+    pub bars_evicted: u64,
+    /// Ticks rejected by validation
+    pub invalid_data_rejected: u64,
+    /// Feed latency threshold breaches
+    pub latency_alerts: u64,
     /// Processing rate (ticks per second)
     pub processing_rate: f64,
     /// Current memory usage (bytes)
@@ -55,44 +111,175 @@ pub struct DataEngineStatistics {
     pub cache_hit_rate: f64,
 }
 
-/// Bar aggregator for creating OHLCV bars from ticks
+/// Message bus topic for feed latency alerts
+pub const LATENCY_ALERT_TOPIC: &str = "data.latency_alert";
+
+/// Message bus topic prefix for signals; the signal name is appended
+pub const SIGNAL_TOPIC_PREFIX: &str = "signals.";
+
+/// Message bus topic prefix for mark prices; the instrument ID is appended
+pub const MARK_PRICE_TOPIC_PREFIX: &str = "data.mark_prices.";
+
+/// Message bus topic prefix for index prices; the instrument ID is appended
+pub const INDEX_PRICE_TOPIC_PREFIX: &str = "data.index_prices.";
+
+/// Message bus topic prefix for composite and synthetic quotes; the
+/// instrument ID is appended
+pub const DERIVED_QUOTE_TOPIC_PREFIX: &str = "data.derived_quotes.";
+
+/// Number of recent samples used for feed latency percentiles
+const LATENCY_SAMPLE_WINDOW: usize = 1_000;
+
+/// Engine clock time between refreshes of the memory usage estimate
+const MEMORY_ESTIMATE_INTERVAL_NS: u64 = 1_000_000_000;
+
+/// Published when an instrument's feed latency first exceeds the threshold
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct FeedLatencyAlert {
+    pub instrument_id: InstrumentId,
+    pub latency_ns: i64,
+    pub threshold_ns: u64,
+    pub ts_event: UnixNanos,
+    pub ts_init: UnixNanos,
+}
+
+/// Ticks-per-second over a sliding window of the engine clock's time,
+/// counted in coarse buckets so the cost per update stays constant
 #[derive(Debug)]
+struct RateMeter {
+    window_ns: u64,
+    bucket_ns: u64,
+    buckets: VecDeque<(UnixNanos, u64)>,
+    /// Time of the first event since creation or the last reset
+    started: Option<UnixNanos>,
+}
+
+impl RateMeter {
+    fn new(window: Duration) -> Self {
+        let window_ns = window.as_nanos() as u64;
+        Self {
+            window_ns,
+            bucket_ns: window_ns / 100,
+            buckets: VecDeque::new(),
+            started: None,
+        }
+    }
+
+    fn record(&mut self, count: u64, now: UnixNanos) {
+        self.started.get_or_insert(now);
+        match self.buckets.back_mut() {
+            Some((start, total)) if now.saturating_sub(*start) < self.bucket_ns => *total += count,
+            _ => self.buckets.push_back((now, count)),
+        }
+        while self.buckets.front().is_some_and(|(start, _)| now.saturating_sub(*start) > self.window_ns) {
+            self.buckets.pop_front();
+        }
+    }
+
+    fn rate(&self, now: UnixNanos) -> f64 {
+        let total: u64 = self.buckets
+            .iter()
+            .filter(|(start, _)| now.saturating_sub(*start) <= self.window_ns)
+            .map(|(_, count)| count)
+            .sum();
+
+        // Until a full window has elapsed, average over the time observed so far
+        let Some(started) = self.started else {
+            return 0.0;
+        };
+        let elapsed = now.saturating_sub(started).min(self.window_ns) as f64 / 1e9;
+        if elapsed > 0.0 { total as f64 / elapsed } else { 0.0 }
+    }
+
+    fn reset(&mut self) {
+        self.buckets.clear();
+        self.started = None;
+    }
+}
+
+/// Statistics increments accumulated while processing ticks
+#[derive(Debug, Default, PartialEq, Eq)]
+struct StatisticsDelta {
+    ticks_processed: u64,
+    bars_generated: u64,
+    bars_evicted: u64,
+    duplicate_trades_filtered: u64,
+    invalid_data_rejected: u64,
+    latency_alerts: u64,
+}
+
+/// Bar aggregator for creating OHLCV bars from ticks
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct BarAggregator {
     bar_type: BarType,
     current_bar: Option<PartialBar>,
-    completed_bars: Vec<Bar>,
-    last_close: Option<f64>,
+    completed_bars: VecDeque<Bar>,
+    max_bars: usize,
+    evicted_count: u64,
+    last_close: Option<Decimal>,
+    price_precision: Option<u32>,
+    size_precision: Option<u32>,
 }
 
-/// Partial bar being constructed
-#[derive(Debug, Clone)]
+/// Partial bar being constructed.
+///
+/// Prices and volumes are accumulated as decimals so that long runs of
+/// fractional sizes do not drift and miss volume/dollar thresholds.
+#[derive(Debug, Clone, Serialize, Deserialize)]
 struct PartialBar {
-    open: f64,
-    high: f64,
-    low: f64,
-    close: f64,
-    volume: f64,
+    open: Decimal,
+    high: Decimal,
+    low: Decimal,
+    close: Decimal,
+    volume: Decimal,
+    notional: Decimal,
     ts_start: UnixNanos,
     ts_last: UnixNanos,
+    /// Boundary at which a time bar closes
+    #[serde(default)]
+    ts_end: Option<UnixNanos>,
     tick_count: u64,
 }
 
 impl BarAggregator {
-    pub fn new(bar_type: BarType) -> Self {
+    /// Create an aggregator retaining at most `max_bars` completed bars
+    pub fn new(bar_type: BarType, max_bars: usize) -> Self {
+        let max_bars = max_bars.max(1);
         Self {
             bar_type,
             current_bar: None,
-            completed_bars: Vec::new(),
+            completed_bars: VecDeque::with_capacity(max_bars),
+            max_bars,
+            evicted_count: 0,
             last_close: None,
+            price_precision: None,
+            size_precision: None,
         }
     }
 
+    /// Round emitted bars to the instrument's price and size precision
+    pub fn set_precision(&mut self, price_precision: u8, size_precision: u8) {
+        self.price_precision = Some(price_precision as u32);
+        self.size_precision = Some(size_precision as u32);
+    }
+
     /// Process a trade tick and update the current bar
     pub fn update_with_trade(&mut self, tick: &TradeTick) -> Option<Bar> {
-        let price = tick.price;
-        let volume = tick.size;
+        crate::profile_scope!("data_engine.bar_aggregation");
+        let (Some(price), Some(volume)) = (tick.price_decimal(), tick.size_decimal()) else {
+            warn!("Skipping trade {} of {} with a non-finite price or size", tick.trade_id, tick.instrument_id);
+            return None;
+        };
         let ts = tick.ts_event;
 
+        // A trade at or past the boundary closes the time bar without it
+        let mut boundary_bar = None;
+        if let Some(end) = self.current_bar.as_ref().and_then(|partial| partial.ts_end) {
+            if ts >= end {
+                boundary_bar = self.close_current_bar(end);
+            }
+        }
+
         let should_close = match &mut self.current_bar {
             Some(partial) => {
                 // Update existing partial bar
@@ -100,6 +287,7 @@ impl BarAggregator {
                 partial.low = partial.low.min(price);
                 partial.close = price;
                 partial.volume += volume;
+                partial.notional += price * volume;
                 partial.ts_last = ts;
                 partial.tick_count += 1;
 
@@ -114,8 +302,10 @@ impl BarAggregator {
                     low: price,
                     close: price,
                     volume,
+                    notional: price * volume,
                     ts_start: ts,
                     ts_last: ts,
+                    ts_end: self.bar_end(ts),
                     tick_count: 1,
                 });
                 false
@@ -124,44 +314,88 @@ impl BarAggregator {
 
         if should_close {
             self.close_current_bar(ts)
+        } else {
+            boundary_bar
+        }
+    }
+
+    /// Close the current time bar if its boundary is at or before `now`
+    pub fn close_if_due(&mut self, now: UnixNanos) -> Option<Bar> {
+        let end = self.current_bar.as_ref()?.ts_end?;
+        if now >= end {
+            self.close_current_bar(end)
         } else {
             None
         }
     }
 
+    /// Schedule of the boundaries time bars close on, if aligned
+    pub fn close_schedule(&self) -> Option<AlignedSchedule> {
+        match self.bar_type.bar_spec.aggregation {
+            BarAggregation::Time(duration_nanos) => {
+                AlignedSchedule::utc(chrono::Duration::nanoseconds(duration_nanos as i64)).ok()
+            }
+            _ => None,
+        }
+    }
+
+    /// End of a time bar starting at `ts_start`.
+    ///
+    /// Intervals dividing a day close on round UTC boundaries; others
+    /// close one interval after the first trade.
+    fn bar_end(&self, ts_start: UnixNanos) -> Option<UnixNanos> {
+        match self.bar_type.bar_spec.aggregation {
+            BarAggregation::Time(duration_nanos) => Some(
+                self.close_schedule()
+                    .and_then(|schedule| schedule.next_after(ts_start))
+                    .unwrap_or(ts_start + duration_nanos),
+            ),
+            _ => None,
+        }
+    }
+
     /// Check if the current bar should be closed
     fn should_close_bar(bar_type: &BarType, partial: &PartialBar, current_ts: UnixNanos) -> bool {
         match &bar_type.bar_spec.aggregation {
             BarAggregation::Tick(count) => partial.tick_count >= *count,
-            BarAggregation::Volume(volume) => partial.volume >= *volume as f64,
-            BarAggregation::Dollar(dollar_amount) => partial.volume * partial.close >= *dollar_amount as f64,
-            BarAggregation::Time(duration_nanos) => {
-                (current_ts - partial.ts_start) >= *duration_nanos
-            }
+            BarAggregation::Volume(volume) => partial.volume >= Decimal::from(*volume),
+            BarAggregation::Dollar(dollar_amount) => partial.notional >= Decimal::from(*dollar_amount),
+            BarAggregation::Time(_) => partial.ts_end.is_some_and(|end| current_ts >= end),
         }
     }
 
     /// Close the current bar and return it
     fn close_current_bar(&mut self, ts_close: UnixNanos) -> Option<Bar> {
         if let Some(partial) = self.current_bar.take() {
+            let price = |value: Decimal| match self.price_precision {
+                Some(dp) => value.round_dp(dp),
+                None => value,
+            }
+            .to_f64()
+            .unwrap_or_default();
+            let volume = match self.size_precision {
+                Some(dp) => partial.volume.round_dp(dp),
+                None => partial.volume,
+            };
+
             let bar = Bar {
                 bar_type: self.bar_type.clone(),
-                open: partial.open,
-                high: partial.high,
-                low: partial.low,
-                close: partial.close,
-                volume: partial.volume,
-                ts_event: partial.ts_last,
+                open: price(partial.open),
+                high: price(partial.high),
+                low: price(partial.low),
+                close: price(partial.close),
+                volume: volume.to_f64().unwrap_or_default(),
+                ts_event: partial.ts_end.unwrap_or(partial.ts_last),
                 ts_init: ts_close,
             };
 
             self.last_close = Some(partial.close);
-            self.completed_bars.push(bar.clone());
-            
-            // Limit memory usage
-            if self.completed_bars.len() > 1000 {
-                self.completed_bars.remove(0);
+            // Ring buffer: drop the oldest bar once at capacity
+            if self.completed_bars.len() == self.max_bars {
+                self.completed_bars.pop_front();
+                self.evicted_count += 1;
             }
+            self.completed_bars.push_back(bar.clone());
 
             Some(bar)
         } else {
@@ -172,43 +406,68 @@ impl BarAggregator {
     /// Get the most recent completed bars
     pub fn get_recent_bars(&self, count: usize) -> Vec<Bar> {
         let start_idx = self.completed_bars.len().saturating_sub(count);
-        self.completed_bars[start_idx..].to_vec()
+        self.completed_bars.range(start_idx..).cloned().collect()
     }
-}
 
-/// Order book delta buffer for efficient updates
-#[derive(Debug)]
-pub struct OrderBookDeltas {
-    pub instrument_id: InstrumentId,
-    pub deltas: Vec<OrderBookDelta>,
-    pub sequence_number: u64,
-    pub ts_last_update: UnixNanos,
+    /// Number of completed bars evicted from history
+    pub fn evicted_count(&self) -> u64 {
+        self.evicted_count
+    }
 }
 
-/// Individual order book delta
-#[derive(Debug, Clone)]
-pub struct OrderBookDelta {
-    pub side: BookSide,
-    pub action: DeltaAction,
-    pub price: f64,
-    pub size: f64,
-    pub order_id: Option<String>,
-    pub ts: UnixNanos,
+/// Sliding-window filter for replayed trade ticks
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TradeDeduplicator {
+    window: usize,
+    seen: HashMap<InstrumentId, (HashSet<String>, VecDeque<String>)>,
 }
 
-/// Order book side
-#[derive(Debug, Clone, Copy, PartialEq, Eq)]
-pub enum BookSide {
-    Bid,
-    Ask,
+impl TradeDeduplicator {
+    pub fn new(window: usize) -> Self {
+        Self {
+            window: window.max(1),
+            seen: HashMap::new(),
+        }
+    }
+
+    /// Record the trade and return `true` if it was already seen within the window
+    pub fn is_duplicate(&mut self, tick: &TradeTick) -> bool {
+        let (ids, order) = self.seen.entry(tick.instrument_id).or_default();
+
+        if ids.contains(&tick.trade_id) {
+            return true;
+        }
+
+        ids.insert(tick.trade_id.clone());
+        order.push_back(tick.trade_id.clone());
+
+        if order.len() > self.window {
+            if let Some(oldest) = order.pop_front() {
+                ids.remove(&oldest);
+            }
+        }
+
+        false
+    }
+
+    /// Forget all remembered trade IDs
+    pub fn clear(&mut self) {
+        self.seen.clear();
+    }
 }
 
-/// Delta action type
-#[derive(Debug, Clone, Copy, PartialEq, Eq)]
-pub enum DeltaAction {
-    Add,
-    Update,
-    Delete,
+/// Restorable Data Engine state: in-progress bars, aggregator history and
+/// the incremental per-instrument analytics
+#[derive(Debug, Serialize, Deserialize)]
+struct DataEngineSnapshot {
+    ts_snapshot: UnixNanos,
+    processed_count: u64,
+    bar_aggregators: Vec<BarAggregator>,
+    rolling_stats: Vec<(BarType, RollingStatistics)>,
+    session_prices: Vec<(InstrumentId, SessionPrices)>,
+    order_flow: Vec<(InstrumentId, OrderFlow)>,
+    order_book_deltas: Vec<OrderBookDeltas>,
+    trade_dedup: Option<TradeDeduplicator>,
 }
 
 /// High-performance Data Engine for market data processing
@@ -224,27 +483,87 @@ pub struct DataEngine {
     // Bar aggregation
     bar_aggregators: HashMap<BarType, BarAggregator>,
     
+    // Replayed trade filtering
+    trade_dedup: Option<TradeDeduplicator>,
+    
+    // Bad data rejection
+    validator: Option<DataValidator>,
+    
+    // Session VWAP/TWAP per instrument
+    session_prices: HashMap<InstrumentId, SessionPrices>,
+    
+    // Rolling bar statistics per bar type
+    rolling_stats: HashMap<BarType, RollingStatistics>,
+    
+    // Aggressor volume per instrument
+    order_flow: HashMap<InstrumentId, OrderFlow>,
+    
     // Order book delta management
     order_book_deltas: HashMap<InstrumentId, OrderBookDeltas>,
     
+    // Latest derivatives data per instrument
+    funding_rates: HashMap<InstrumentId, FundingRateUpdate>,
+    open_interest: HashMap<InstrumentId, OpenInterestUpdate>,
+    mark_prices: HashMap<InstrumentId, MarkPriceUpdate>,
+    index_prices: HashMap<InstrumentId, IndexPriceUpdate>,
+    
+    // Instrument metadata
+    cache: Arc<Cache>,
+    
+    // Cross-venue composite quotes
+    consolidator: QuoteConsolidator,
+    
+    // Latest value per signal name and signals awaiting delivery
+    signals: HashMap<String, SignalData>,
+    pending_signals: VecDeque<SignalData>,
+    
+    // Spreads and baskets priced from constituent data
+    synthetics: SyntheticEngine,
+    
+    // Feed latency tracking and alerting
+    feed_latency: HashMap<InstrumentId, FeedLatency>,
+    latency_alerting: HashSet<InstrumentId>,
+    message_bus: Option<Arc<MessageBus>>,
+    
+    // Recording of accepted data for replay
+    tick_writer: Option<TickWriter>,
+    
+    // Clock engine timestamps are read from
+    clock: Arc<dyn Clock>,
+    
     // Statistics and metrics
     stats: Arc<RwLock<DataEngineStatistics>>,
+    throughput: RateMeter,
+    memory_estimated_at: Option<UnixNanos>,
     
     // Processing state
     is_running: bool,
+    /// Whether state was restored since the last start, which keeps it
+    restored: bool,
     processed_count: u64,
 }
 
 impl DataEngine {
     /// Create a new Data Engine with specified configuration
     pub fn new(config: DataEngineConfig) -> Self {
+        Self::with_cache(config, Arc::new(Cache::new(CacheConfig::default())))
+    }
+
+    /// Create a new Data Engine that reads instrument metadata from a shared cache
+    pub fn with_cache(config: DataEngineConfig, cache: Arc<Cache>) -> Self {
         use crate::generic_cache::GenericCacheConfig;
         
         let cache_config = GenericCacheConfig {
             max_size: config.max_bars_per_instrument * 100, // Generous cache size
             ttl_seconds: Some(3600), // 1 hour TTL for market data
             enable_statistics: config.enable_statistics,
+            ..Default::default()
         };
+        let trade_dedup = config.trade_dedup_window.map(TradeDeduplicator::new);
+        let validator = config.validation.clone().map(DataValidator::new);
+        let consolidator = QuoteConsolidator::new(config.composite_quote_stale_ns);
+        let tick_writer = config.persistence.clone().map(TickWriter::new);
+        let clock: Arc<dyn Clock> = Arc::new(LiveClock::new());
         
         Self {
             config,
@@ -252,9 +571,31 @@ impl DataEngine {
             quote_cache: Arc::new(GenericCache::new(cache_config.clone())),
             bar_cache: Arc::new(GenericCache::new(cache_config)),
             bar_aggregators: HashMap::new(),
+            trade_dedup,
+            validator,
+            session_prices: HashMap::new(),
+            rolling_stats: HashMap::new(),
+            order_flow: HashMap::new(),
             order_book_deltas: HashMap::new(),
+            funding_rates: HashMap::new(),
+            open_interest: HashMap::new(),
+            mark_prices: HashMap::new(),
+            index_prices: HashMap::new(),
+            consolidator,
+            signals: HashMap::new(),
+            pending_signals: VecDeque::new(),
+            synthetics: SyntheticEngine::new(),
+            feed_latency: HashMap::new(),
+            latency_alerting: HashSet::new(),
+            message_bus: None,
+            tick_writer,
+            cache,
+            throughput: RateMeter::new(Duration::from_secs(10)),
+            memory_estimated_at: None,
+            clock,
             stats: Arc::new(RwLock::new(DataEngineStatistics::default())),
             is_running: false,
+            restored: false,
             processed_count: 0,
         }
     }
@@ -266,7 +607,15 @@ impl DataEngine {
         }
         
         self.is_running = true;
-        self.processed_count = 0;
+        if !std::mem::take(&mut self.restored) {
+            self.processed_count = 0;
+            if let Some(dedup) = self.trade_dedup.as_mut() {
+                dedup.clear();
+            }
+        }
+        if let Some(validator) = self.validator.as_mut() {
+            validator.clear();
+        }
         
         // Initialize statistics
         if let Ok(mut stats) = self.stats.write() {
@@ -276,9 +625,28 @@ impl DataEngine {
         Ok(())
     }
 
-    /// Stop the Data Engine
+    /// Stop the Data Engine, completing any open persistence file
     pub fn stop(&mut self) {
         self.is_running = false;
+        if let Some(writer) = self.tick_writer.as_mut() {
+            if let Err(e) = writer.finish() {
+                warn!("Failed to finish persistence file: {}", e);
+            }
+        }
+    }
+
+    /// Files written by the persistence sink, oldest first
+    pub fn persistence_files(&self) -> &[PathBuf] {
+        self.tick_writer.as_ref().map_or(&[], |writer| writer.files())
+    }
+
+    /// Append accepted data to the persistence sink, if configured
+    fn persist(&mut self, data: impl FnOnce() -> MarketData) {
+        if let Some(writer) = self.tick_writer.as_mut() {
+            if let Err(e) = writer.write(&data()) {
+                warn!("Failed to persist market data: {}", e);
+            }
+        }
     }
 
     /// Process a trade tick with high performance
@@ -287,44 +655,149 @@ impl DataEngine {
             return Err("Data Engine is not running".to_string());
         }
 
+        let mut counters = StatisticsDelta::default();
+        let mut completed_bars = Vec::new();
+        let result = self.handle_trade_tick(&tick, &mut counters, &mut completed_bars);
+        self.apply_statistics(&counters);
+
+        result.map(|_| completed_bars.into_iter().next())
+    }
+
+    /// Process a batch of trade ticks, returning every bar completed.
+    ///
+    /// Statistics are updated once per batch. Ticks rejected by validation
+    /// are skipped (and counted) rather than aborting the batch.
+    pub fn process_trade_ticks(&mut self, ticks: &[TradeTick]) -> Result<Vec<Bar>, String> {
+        if !self.is_running {
+            return Err("Data Engine is not running".to_string());
+        }
+
+        let mut counters = StatisticsDelta::default();
+        let mut completed_bars = Vec::new();
+        for tick in ticks {
+            if let Err(e) = self.handle_trade_tick(tick, &mut counters, &mut completed_bars) {
+                debug!("{}", e);
+            }
+        }
+        self.apply_statistics(&counters);
+
+        Ok(completed_bars)
+    }
+
+    /// Run a single trade tick through validation, caching, analytics and aggregation
+    fn handle_trade_tick(
+        &mut self,
+        tick: &TradeTick,
+        counters: &mut StatisticsDelta,
+        completed_bars: &mut Vec<Bar>,
+    ) -> Result<(), String> {
+        if let Some(validator) = self.validator.as_mut() {
+            if let Err(violation) = validator.validate_trade(tick) {
+                counters.invalid_data_rejected += 1;
+                return Err(format!("Rejected trade tick for {}: {}", tick.instrument_id, violation));
+            }
+        }
+
+        // Rejected even without validation, as they have no decimal value
+        if !tick.price.is_finite() || !tick.size.is_finite() {
+            counters.invalid_data_rejected += 1;
+            return Err(format!("Rejected trade tick for {}: non-finite price or size", tick.instrument_id));
+        }
+
+        // Drop trades replayed by the venue before they reach aggregation
+        if let Some(dedup) = self.trade_dedup.as_mut() {
+            if dedup.is_duplicate(tick) {
+                counters.duplicate_trades_filtered += 1;
+                return Ok(());
+            }
+        }
+
+        self.persist(|| MarketData::Trade(tick.clone()));
+        self.record_latency(tick.instrument_id, tick.ts_event, tick.ts_init, counters);
+
+        let synthetic_quotes = self.synthetics.update_trade(tick);
+        self.publish_derived_quotes(synthetic_quotes);
+
         // Cache the tick for fast retrieval
         let cache_key = format!("trade_{}_{}", tick.instrument_id, tick.ts_event);
         self.tick_cache.put(cache_key, tick.clone());
 
-        // Update statistics
         self.processed_count += 1;
-        if let Ok(mut stats) = self.stats.write() {
-            stats.ticks_processed += 1;
-        }
-
-        // Process bar aggregation if enabled
-        let mut new_bar = None;
-        if self.config.enable_bar_aggregation {
-            // Find relevant bar aggregators for this instrument
-            let mut completed_bars = Vec::new();
-            
-            for (bar_type, aggregator) in self.bar_aggregators.iter_mut() {
-                if bar_type.instrument_id == tick.instrument_id {
-                    if let Some(bar) = aggregator.update_with_trade(&tick) {
-                        completed_bars.push(bar);
-                    }
-                }
-            }
-            
-            // Cache completed bars
-            for bar in completed_bars.iter() {
-                let cache_key = format!("bar_{}_{}", bar.bar_type.instrument_id, bar.ts_event);
-                self.bar_cache.put(cache_key, bar.clone());
-                
-                if let Ok(mut stats) = self.stats.write() {
-                    stats.bars_generated += 1;
+        counters.ticks_processed += 1;
+
+        self.session_prices
+            .entry(tick.instrument_id)
+            .or_default()
+            .update(tick);
+
+        if !self.config.order_flow_windows.is_empty() {
+            let windows = &self.config.order_flow_windows;
+            self.order_flow
+                .entry(tick.instrument_id)
+                .or_insert_with(|| OrderFlow::new(windows))
+                .update(tick);
+        }
+
+        if !self.config.enable_bar_aggregation {
+            return Ok(());
+        }
+
+        // Find relevant bar aggregators for this instrument
+        let first_new = completed_bars.len();
+        for (bar_type, aggregator) in self.bar_aggregators.iter_mut() {
+            if bar_type.instrument_id == tick.instrument_id {
+                let evicted_before = aggregator.evicted_count();
+                if let Some(bar) = aggregator.update_with_trade(tick) {
+                    completed_bars.push(bar);
                 }
+                counters.bars_evicted += aggregator.evicted_count() - evicted_before;
             }
-            
-            new_bar = completed_bars.into_iter().next();
         }
 
-        Ok(new_bar)
+        self.store_bars(&completed_bars[first_new..], counters);
+
+        Ok(())
+    }
+
+    /// Close time bars whose boundary has passed on the engine clock.
+    ///
+    /// Call on each bar's `close_schedule` so bars close at the boundary
+    /// even when no trade follows it.
+    pub fn close_due_bars(&mut self) -> Vec<Bar> {
+        let now = self.clock.timestamp_ns();
+        let mut counters = StatisticsDelta::default();
+        let completed_bars: Vec<Bar> = self
+            .bar_aggregators
+            .values_mut()
+            .filter_map(|aggregator| {
+                let evicted_before = aggregator.evicted_count();
+                let bar = aggregator.close_if_due(now);
+                counters.bars_evicted += aggregator.evicted_count() - evicted_before;
+                bar
+            })
+            .collect();
+        self.store_bars(&completed_bars, &mut counters);
+        self.apply_statistics(&counters);
+        completed_bars
+    }
+
+    /// Cache completed bars and update their rolling statistics
+    fn store_bars(&mut self, bars: &[Bar], counters: &mut StatisticsDelta) {
+        for bar in bars {
+            let window = self.config.rolling_window;
+            self.rolling_stats
+                .entry(bar.bar_type.clone())
+                .or_insert_with(|| RollingStatistics::new(window))
+                .update(bar);
+
+            let cache_key = format!("bar_{}_{}", bar.bar_type.instrument_id, bar.ts_event);
+            self.bar_cache.put(cache_key, bar.clone());
+            if let Err(e) = self.cache.add_bar(bar.clone()) {
+                warn!("Failed to store bar: {}", e);
+            }
+
+            counters.bars_generated += 1;
+        }
     }
 
     /// Process a quote tick
@@ -333,30 +806,477 @@ impl DataEngine {
             return Err("Data Engine is not running".to_string());
         }
 
+        let mut counters = StatisticsDelta::default();
+        let result = self.handle_quote_tick(tick, &mut counters);
+        self.apply_statistics(&counters);
+        result
+    }
+
+    /// Process a batch of quote ticks, updating statistics once per batch.
+    ///
+    /// Quotes rejected by validation are skipped (and counted).
+    pub fn process_quote_ticks(&mut self, ticks: &[QuoteTick]) -> Result<(), String> {
+        if !self.is_running {
+            return Err("Data Engine is not running".to_string());
+        }
+
+        let mut counters = StatisticsDelta::default();
+        for tick in ticks {
+            if let Err(e) = self.handle_quote_tick(tick.clone(), &mut counters) {
+                debug!("{}", e);
+            }
+        }
+        self.apply_statistics(&counters);
+
+        Ok(())
+    }
+
+    fn handle_quote_tick(&mut self, tick: QuoteTick, counters: &mut StatisticsDelta) -> Result<(), String> {
+        if let Some(validator) = self.validator.as_mut() {
+            if let Err(violation) = validator.validate_quote(&tick) {
+                counters.invalid_data_rejected += 1;
+                return Err(format!("Rejected quote tick for {}: {}", tick.instrument_id, violation));
+            }
+        }
+
+        self.persist(|| MarketData::Quote(tick.clone()));
+        self.record_latency(tick.instrument_id, tick.ts_event, tick.ts_init, counters);
+
+        let mut derived_quotes = self.synthetics.update_quote(&tick);
+        derived_quotes.extend(self.consolidator.update(&tick).map(|composite| composite.quote));
+        self.publish_derived_quotes(derived_quotes);
+
         // Cache the quote
         let cache_key = format!("quote_{}_{}", tick.instrument_id, tick.ts_event);
         self.quote_cache.put(cache_key, tick);
 
-        // Update statistics
         self.processed_count += 1;
+        counters.ticks_processed += 1;
+
+        Ok(())
+    }
+
+    /// Cache composite and synthetic quotes and publish them on the message bus
+    fn publish_derived_quotes(&mut self, quotes: Vec<QuoteTick>) {
+        for quote in quotes {
+            if let Some(bus) = &self.message_bus {
+                let topic = format!("{}{}", DERIVED_QUOTE_TOPIC_PREFIX, quote.instrument_id);
+                bus.publish_from("data_engine", &topic, &quote);
+            }
+            let cache_key = format!("quote_{}_{}", quote.instrument_id, quote.ts_event);
+            self.quote_cache.put(cache_key, quote);
+        }
+    }
+
+    /// Track feed latency and alert once per breach of the configured threshold
+    fn record_latency(
+        &mut self,
+        instrument_id: InstrumentId,
+        ts_event: UnixNanos,
+        ts_init: UnixNanos,
+        counters: &mut StatisticsDelta,
+    ) {
+        let latency = self.feed_latency
+            .entry(instrument_id)
+            .or_insert_with(|| FeedLatency::new(LATENCY_SAMPLE_WINDOW))
+            .update(ts_event, ts_init);
+
+        let Some(threshold_ns) = self.config.latency_alert_threshold_ns else {
+            return;
+        };
+
+        if latency <= threshold_ns as i64 {
+            self.latency_alerting.remove(&instrument_id);
+            return;
+        }
+        if !self.latency_alerting.insert(instrument_id) {
+            return; // Already alerted for this breach
+        }
+
+        counters.latency_alerts += 1;
+        warn!("Feed latency for {} is {}ns (threshold {}ns)", instrument_id, latency, threshold_ns);
+        if let Some(bus) = &self.message_bus {
+            let alert = FeedLatencyAlert {
+                instrument_id,
+                latency_ns: latency,
+                threshold_ns,
+                ts_event,
+                ts_init,
+            };
+            bus.publish_from("data_engine", LATENCY_ALERT_TOPIC, &alert);
+        }
+    }
+
+    /// Fold per-call counters into the shared statistics under a single lock
+    fn apply_statistics(&mut self, counters: &StatisticsDelta) {
+        if *counters == StatisticsDelta::default() {
+            return;
+        }
+        let now = self.clock.timestamp_ns();
+        if counters.ticks_processed > 0 {
+            self.throughput.record(counters.ticks_processed, now);
+        }
+        let mut memory_usage = None;
+        if self.memory_estimated_at.is_none_or(|at| now.saturating_sub(at) >= MEMORY_ESTIMATE_INTERVAL_NS) {
+            self.memory_estimated_at = Some(now);
+            memory_usage = Some(self.estimated_memory_usage());
+        }
         if let Ok(mut stats) = self.stats.write() {
-            stats.ticks_processed += 1;
+            if let Some(memory_usage) = memory_usage {
+                stats.memory_usage = memory_usage;
+            }
+            stats.ticks_processed += counters.ticks_processed;
+            stats.bars_generated += counters.bars_generated;
+            stats.bars_evicted += counters.bars_evicted;
+            stats.duplicate_trades_filtered += counters.duplicate_trades_filtered;
+            stats.invalid_data_rejected += counters.invalid_data_rejected;
+            stats.latency_alerts += counters.latency_alerts;
+        }
+    }
+
+    /// Buffer a batch of order book deltas for an instrument
+    pub fn process_order_book_deltas(&mut self, deltas: OrderBookDeltas) -> Result<(), String> {
+        if !self.is_running {
+            return Err("Data Engine is not running".to_string());
+        }
+        crate::profile_scope!("data_engine.book_update");
+
+        let update_count = deltas.deltas.len() as u64;
+        self.processed_count += 1;
+        if let Ok(mut stats) = self.stats.write() {
+            stats.order_book_updates += update_count;
+        }
+        self.persist(|| MarketData::BookDeltas(deltas.clone()));
+
+        if !self.config.enable_order_book_deltas {
+            return Ok(());
+        }
+
+        let max_buffered = self.config.max_tick_buffer_size;
+        let buffer = self.order_book_deltas
+            .entry(deltas.instrument_id)
+            .or_insert_with(|| OrderBookDeltas {
+                instrument_id: deltas.instrument_id,
+                deltas: Vec::new(),
+                sequence_number: 0,
+                ts_last_update: 0,
+            });
+
+        buffer.deltas.extend(deltas.deltas);
+        buffer.sequence_number = deltas.sequence_number;
+        buffer.ts_last_update = deltas.ts_last_update;
+
+        // Keep only the most recent deltas
+        if buffer.deltas.len() > max_buffered {
+            let excess = buffer.deltas.len() - max_buffered;
+            buffer.deltas.drain(..excess);
+        }
+
+        Ok(())
+    }
+
+    /// Get buffered order book deltas for an instrument
+    pub fn get_order_book_deltas(&self, instrument_id: InstrumentId) -> Option<&OrderBookDeltas> {
+        self.order_book_deltas.get(&instrument_id)
+    }
+
+    /// Record the latest funding rate for a perpetual swap
+    pub fn process_funding_rate(&mut self, update: FundingRateUpdate) -> Result<(), String> {
+        if !self.is_running {
+            return Err("Data Engine is not running".to_string());
+        }
+        if !update.rate.is_finite() {
+            return Err(format!("Rejected funding rate for {}: non-finite rate", update.instrument_id));
+        }
+
+        self.processed_count += 1;
+        self.persist(|| MarketData::FundingRate(update.clone()));
+        self.funding_rates.insert(update.instrument_id, update);
+        Ok(())
+    }
+
+    /// Record the latest open interest for an instrument
+    pub fn process_open_interest(&mut self, update: OpenInterestUpdate) -> Result<(), String> {
+        if !self.is_running {
+            return Err("Data Engine is not running".to_string());
+        }
+        if !update.open_interest.is_finite() || update.open_interest < 0.0 {
+            return Err(format!("Rejected open interest for {}: invalid value", update.instrument_id));
+        }
+
+        self.processed_count += 1;
+        self.persist(|| MarketData::OpenInterest(update.clone()));
+        self.open_interest.insert(update.instrument_id, update);
+        Ok(())
+    }
+
+    /// Record the latest mark price for an instrument
+    pub fn process_mark_price(&mut self, update: MarkPriceUpdate) -> Result<(), String> {
+        if !self.is_running {
+            return Err("Data Engine is not running".to_string());
+        }
+        if !update.mark_price.is_finite() || update.mark_price <= 0.0 {
+            return Err(format!("Rejected mark price for {}: invalid price", update.instrument_id));
+        }
+
+        self.processed_count += 1;
+        self.persist(|| MarketData::MarkPrice(update.clone()));
+        if let Some(bus) = &self.message_bus {
+            let topic = format!("{}{}", MARK_PRICE_TOPIC_PREFIX, update.instrument_id);
+            bus.publish_from("data_engine", &topic, &update);
+        }
+        self.mark_prices.insert(update.instrument_id, update);
+        Ok(())
+    }
+
+    /// Record the latest index price for an instrument
+    pub fn process_index_price(&mut self, update: IndexPriceUpdate) -> Result<(), String> {
+        if !self.is_running {
+            return Err("Data Engine is not running".to_string());
+        }
+        if !update.index_price.is_finite() || update.index_price <= 0.0 {
+            return Err(format!("Rejected index price for {}: invalid price", update.instrument_id));
+        }
+
+        self.processed_count += 1;
+        self.persist(|| MarketData::IndexPrice(update.clone()));
+        if let Some(bus) = &self.message_bus {
+            let topic = format!("{}{}", INDEX_PRICE_TOPIC_PREFIX, update.instrument_id);
+            bus.publish_from("data_engine", &topic, &update);
+        }
+        self.index_prices.insert(update.instrument_id, update);
+        Ok(())
+    }
+
+    /// Get the latest mark price update for an instrument
+    pub fn get_mark_price(&self, instrument_id: InstrumentId) -> Option<&MarkPriceUpdate> {
+        self.mark_prices.get(&instrument_id)
+    }
+
+    /// Get the latest index price update for an instrument
+    pub fn get_index_price(&self, instrument_id: InstrumentId) -> Option<&IndexPriceUpdate> {
+        self.index_prices.get(&instrument_id)
+    }
+
+    /// Get the latest funding rate update for an instrument
+    pub fn get_funding_rate(&self, instrument_id: InstrumentId) -> Option<&FundingRateUpdate> {
+        self.funding_rates.get(&instrument_id)
+    }
+
+    /// Get the latest open interest update for an instrument
+    pub fn get_open_interest(&self, instrument_id: InstrumentId) -> Option<&OpenInterestUpdate> {
+        self.open_interest.get(&instrument_id)
+    }
+
+    /// Process any market data item
+    pub fn process_data(&mut self, data: MarketData) -> Result<Option<Bar>, String> {
+        match data {
+            MarketData::Trade(tick) => self.process_trade_tick(tick),
+            MarketData::Quote(tick) => self.process_quote_tick(tick).map(|_| None),
+            MarketData::BookDeltas(deltas) => self.process_order_book_deltas(deltas).map(|_| None),
+            MarketData::FundingRate(update) => self.process_funding_rate(update).map(|_| None),
+            MarketData::OpenInterest(update) => self.process_open_interest(update).map(|_| None),
+            MarketData::MarkPrice(update) => self.process_mark_price(update).map(|_| None),
+            MarketData::IndexPrice(update) => self.process_index_price(update).map(|_| None),
         }
+    }
+
+    /// Create an ingestion channel sized and configured from this engine's config
+    pub fn data_channel(&self) -> (crate::data_channel::DataSender, DataReceiver) {
+        crate::data_channel::data_channel(
+            self.config.max_tick_buffer_size,
+            self.config.backpressure_policy,
+        )
+    }
+
+    /// Consume data from an ingestion channel on a dedicated task.
+    ///
+    /// Items already buffered are drained in batches under a single engine
+    /// lock. The task exits once every sender has been dropped and the buffer is drained.
+    pub fn spawn(engine: Arc<Mutex<DataEngine>>, mut rx: DataReceiver) -> JoinHandle<()> {
+        tokio::spawn(async move {
+            while let Some(data) = rx.recv().await {
+                let Ok(mut engine) = engine.lock() else {
+                    warn!("Data Engine lock poisoned, stopping ingestion task");
+                    break;
+                };
+
+                // Drain whatever else is already buffered under the same lock
+                let max_batch = engine.config.max_tick_buffer_size;
+                let mut next = Some(data);
+                let mut processed = 0;
+                while let Some(data) = next.take() {
+                    if let Err(e) = engine.process_data(data) {
+                        warn!("Failed to process market data: {}", e);
+                    }
+                    processed += 1;
+                    if processed < max_batch {
+                        next = rx.try_recv();
+                    }
+                }
+            }
+        })
+    }
+
+    /// Register a spread or basket, returning its synthetic instrument ID.
+    ///
+    /// Synthetic quotes are cached like venue quotes and published on the
+    /// message bus under `data.derived_quotes.<instrument_id>`.
+    pub fn add_synthetic(&mut self, synthetic: SyntheticInstrument) -> Result<InstrumentId, String> {
+        let synthetic_id = synthetic.id;
+        self.synthetics.add(synthetic)?;
+        Ok(synthetic_id)
+    }
 
+    /// Remove a synthetic instrument
+    pub fn remove_synthetic(&mut self, synthetic_id: InstrumentId) -> bool {
+        self.synthetics.remove(synthetic_id)
+    }
+
+    /// Publish a signal to subscribed strategies and on the message bus
+    /// under `signals.<name>`
+    pub fn publish_signal(&mut self, signal: SignalData) -> Result<(), String> {
+        if !self.is_running {
+            return Err("Data Engine is not running".to_string());
+        }
+        if !signal.value.is_finite() {
+            return Err(format!("Rejected signal {}: non-finite value", signal.name));
+        }
+
+        if let Some(bus) = &self.message_bus {
+            let topic = format!("{}{}", SIGNAL_TOPIC_PREFIX, signal.name);
+            bus.publish_from("data_engine", &topic, &signal);
+        }
+
+        if self.pending_signals.len() >= self.config.max_tick_buffer_size {
+            self.pending_signals.pop_front();
+        }
+        self.pending_signals.push_back(signal.clone());
+        self.signals.insert(signal.name.clone(), signal);
         Ok(())
     }
 
+    /// Get the latest value of a signal
+    pub fn get_signal(&self, name: &str) -> Option<&SignalData> {
+        self.signals.get(name)
+    }
+
+    /// Take signals published since the last call, oldest first
+    pub fn drain_signals(&mut self) -> Vec<SignalData> {
+        self.pending_signals.drain(..).collect()
+    }
+
+    /// Publish engine alerts (e.g. feed latency), signals, mark and index
+    /// prices, and composite and synthetic quotes on a message bus
+    pub fn set_message_bus(&mut self, message_bus: Arc<MessageBus>) {
+        self.message_bus = Some(message_bus);
+    }
+
+    /// Read engine timestamps, including those the processing rate is
+    /// measured over, from `clock` (a `LiveClock` live, a `TestClock`
+    /// in backtests and replay)
+    pub fn set_clock(&mut self, clock: Arc<dyn Clock>) {
+        self.throughput.reset();
+        self.memory_estimated_at = None;
+        self.clock = clock;
+    }
+
+    /// Clock engine timestamps are read from
+    pub fn clock(&self) -> Arc<dyn Clock> {
+        Arc::clone(&self.clock)
+    }
+
+    /// Get the feed latency distribution for an instrument
+    pub fn get_feed_latency(&self, instrument_id: InstrumentId) -> Option<FeedLatencySnapshot> {
+        self.feed_latency.get(&instrument_id).and_then(|l| l.snapshot())
+    }
+
+    /// Register instrument metadata and apply its precision to existing aggregators
+    pub fn add_instrument(&mut self, instrument: InstrumentAny) -> Result<(), String> {
+        let instrument_id = instrument.id();
+        for (bar_type, aggregator) in self.bar_aggregators.iter_mut() {
+            if bar_type.instrument_id == instrument_id {
+                aggregator.set_precision(instrument.price_precision, instrument.size_precision);
+            }
+        }
+
+        self.cache
+            .add_instrument(instrument)
+            .map_err(|e| format!("Failed to add instrument: {}", e))
+    }
+
+    /// Get instrument metadata
+    pub fn get_instrument(&self, instrument_id: InstrumentId) -> Option<InstrumentAny> {
+        self.cache.get_instrument(&instrument_id)
+    }
+
+    /// Get the cache backing instrument lookups
+    pub fn cache(&self) -> Arc<Cache> {
+        Arc::clone(&self.cache)
+    }
+
+    /// Consolidate quotes for every cached venue listing of `symbol` into a
+    /// composite best bid/offer, returning the composite instrument ID.
+    ///
+    /// Composite quotes are cached like venue quotes and published on the
+    /// message bus under `data.derived_quotes.<instrument_id>`.
+    pub fn enable_quote_consolidation(&mut self, symbol: &str) -> Result<InstrumentId, String> {
+        let listings = self.cache.get_instruments_by_symbol(symbol);
+        if listings.is_empty() {
+            return Err(format!("No instruments cached for symbol {}", symbol));
+        }
+
+        let mut composite_id = QuoteConsolidator::composite_id(symbol);
+        for instrument in listings {
+            composite_id = self.consolidator.register(symbol, instrument.venue(), instrument.id())?;
+        }
+        Ok(composite_id)
+    }
+
+    /// Get the latest composite quote with source venues
+    pub fn get_composite_quote(&self, composite_id: InstrumentId) -> Option<&CompositeQuote> {
+        self.consolidator.get(composite_id)
+    }
+
     /// Add a bar aggregator for the specified bar type
     pub fn add_bar_aggregator(&mut self, bar_type: BarType) {
-        let aggregator = BarAggregator::new(bar_type.clone());
+        let mut aggregator = BarAggregator::new(bar_type.clone(), self.config.max_bars_per_instrument);
+        if let Some(instrument) = self.cache.get_instrument(&bar_type.instrument_id) {
+            aggregator.set_precision(instrument.price_precision, instrument.size_precision);
+        }
         self.bar_aggregators.insert(bar_type, aggregator);
     }
 
     /// Remove a bar aggregator
     pub fn remove_bar_aggregator(&mut self, bar_type: &BarType) -> bool {
+        self.rolling_stats.remove(bar_type);
         self.bar_aggregators.remove(bar_type).is_some()
     }
 
+    /// Load historical bars into the bar store, e.g. to warm up strategies
+    pub fn add_bars(&mut self, bars: Vec<Bar>) -> Result<(), String> {
+        for bar in bars {
+            self.cache
+                .add_bar(bar)
+                .map_err(|e| format!("Failed to store bar: {}", e))?;
+        }
+        Ok(())
+    }
+
+    /// Query stored bars for a bar type in ascending time order.
+    ///
+    /// Covers both loaded history and bars built by live aggregators;
+    /// with a `limit` the most recent bars in the range are returned.
+    pub fn get_bars(
+        &self,
+        bar_type: &BarType,
+        from_ts: Option<UnixNanos>,
+        to_ts: Option<UnixNanos>,
+        limit: Option<usize>,
+    ) -> Vec<Bar> {
+        self.cache.get_bars(bar_type, from_ts, to_ts, limit)
+    }
+
     /// Get recent bars for an instrument
     pub fn get_recent_bars(&self, bar_type: &BarType, count: usize) -> Vec<Bar> {
         if let Some(aggregator) = self.bar_aggregators.get(bar_type) {
@@ -366,6 +1286,56 @@ impl DataEngine {
         }
     }
 
+    /// Get the session volume-weighted average price for an instrument
+    pub fn get_vwap(&self, instrument_id: InstrumentId) -> Option<f64> {
+        self.session_prices.get(&instrument_id).and_then(|p| p.vwap())
+    }
+
+    /// Get the session time-weighted average price for an instrument
+    pub fn get_twap(&self, instrument_id: InstrumentId) -> Option<f64> {
+        self.session_prices.get(&instrument_id).and_then(|p| p.twap())
+    }
+
+    /// Start a new VWAP/TWAP session for one instrument, or all when `None`
+    pub fn reset_session(&mut self, instrument_id: Option<InstrumentId>) {
+        match instrument_id {
+            Some(id) => {
+                if let Some(prices) = self.session_prices.get_mut(&id) {
+                    prices.reset();
+                }
+            }
+            None => self.session_prices.clear(),
+        }
+    }
+
+    /// Get rolling volatility, ATR and high/low for a bar type
+    pub fn get_rolling_statistics(&self, bar_type: &BarType) -> Option<&RollingStatistics> {
+        self.rolling_stats.get(bar_type)
+    }
+
+    /// Get the average true range for a bar type
+    pub fn get_atr(&self, bar_type: &BarType) -> Option<f64> {
+        self.rolling_stats.get(bar_type).and_then(|s| s.atr())
+    }
+
+    /// Get the realized volatility for a bar type
+    pub fn get_realized_volatility(&self, bar_type: &BarType) -> Option<f64> {
+        self.rolling_stats.get(bar_type).and_then(|s| s.realized_volatility())
+    }
+
+    /// Get buy/sell aggressor volume and delta over a configured window
+    /// ending at the engine clock's current time
+    pub fn get_order_flow(&self, instrument_id: InstrumentId, window_ns: u64) -> Option<OrderFlowSnapshot> {
+        self.order_flow
+            .get(&instrument_id)
+            .and_then(|flow| flow.snapshot(window_ns, self.clock.timestamp_ns()))
+    }
+
+    /// Get validation violation counts for an instrument
+    pub fn get_validation_violations(&self, instrument_id: InstrumentId) -> Option<&ViolationCounts> {
+        self.validator.as_ref().and_then(|v| v.violations(instrument_id))
+    }
+
     /// Get cached trade tick
     pub fn get_trade_tick(&self, instrument_id: InstrumentId, ts: UnixNanos) -> Option<TradeTick> {
         let cache_key = format!("trade_{}_{}", instrument_id, ts);
@@ -384,20 +1354,148 @@ impl DataEngine {
         self.bar_cache.get(&cache_key)
     }
 
-    /// Get current statistics
+    /// Get every cached trade tick of an instrument, oldest first
+    pub fn get_cached_trade_ticks(&self, instrument_id: InstrumentId) -> Vec<TradeTick> {
+        Self::scan_by_time(&self.tick_cache, &format!("trade_{}_", instrument_id), |tick| tick.ts_event)
+    }
+
+    /// Get every cached quote tick of an instrument, oldest first
+    pub fn get_cached_quote_ticks(&self, instrument_id: InstrumentId) -> Vec<QuoteTick> {
+        Self::scan_by_time(&self.quote_cache, &format!("quote_{}_", instrument_id), |quote| quote.ts_event)
+    }
+
+    /// Get every cached bar of an instrument, oldest first
+    pub fn get_cached_bars(&self, instrument_id: InstrumentId) -> Vec<Bar> {
+        Self::scan_by_time(&self.bar_cache, &format!("bar_{}_", instrument_id), |bar| bar.ts_event)
+    }
+
+    // Keys end in unpadded timestamps, so key order is not time order
+    fn scan_by_time<T: Clone>(cache: &GenericCache<T>, prefix: &str, ts: impl Fn(&T) -> UnixNanos) -> Vec<T> {
+        let mut values: Vec<T> = cache.scan(prefix).into_iter().map(|(_, value)| value).collect();
+        values.sort_by_key(|value| ts(value));
+        values
+    }
+
+    /// Serialize in-progress bars, bar history and rolling analytics so a
+    /// restarted node can resume without losing partial state
+    pub fn snapshot(&self) -> Result<Vec<u8>, String> {
+        let snapshot = DataEngineSnapshot {
+            ts_snapshot: self.clock.timestamp_ns(),
+            processed_count: self.processed_count,
+            bar_aggregators: self.bar_aggregators.values().cloned().collect(),
+            rolling_stats: self.rolling_stats.iter().map(|(k, v)| (k.clone(), v.clone())).collect(),
+            session_prices: self.session_prices.iter().map(|(k, v)| (*k, v.clone())).collect(),
+            order_flow: self.order_flow.iter().map(|(k, v)| (*k, v.clone())).collect(),
+            order_book_deltas: self.order_book_deltas.values().cloned().collect(),
+            trade_dedup: self.trade_dedup.clone(),
+        };
+
+        rmp_serde::to_vec(&snapshot).map_err(|e| format!("Failed to serialize snapshot: {}", e))
+    }
+
+    /// Replace engine state with a snapshot taken by `snapshot`.
+    ///
+    /// Configuration is kept from this engine; trade deduplication state is
+    /// only restored when deduplication is enabled here. Restored bar
+    /// history is also written to the bar store. The next `start` keeps
+    /// the restored state.
+    pub fn restore(&mut self, data: &[u8]) -> Result<(), String> {
+        let snapshot: DataEngineSnapshot = rmp_serde::from_slice(data)
+            .map_err(|e| format!("Failed to deserialize snapshot: {}", e))?;
+
+        for aggregator in &snapshot.bar_aggregators {
+            for bar in aggregator.completed_bars.iter() {
+                self.cache
+                    .add_bar(bar.clone())
+                    .map_err(|e| format!("Failed to store bar: {}", e))?;
+            }
+        }
+
+        self.processed_count = snapshot.processed_count;
+        self.bar_aggregators = snapshot.bar_aggregators
+            .into_iter()
+            .map(|aggregator| (aggregator.bar_type.clone(), aggregator))
+            .collect();
+        self.rolling_stats = snapshot.rolling_stats.into_iter().collect();
+        self.session_prices = snapshot.session_prices.into_iter().collect();
+        self.order_flow = snapshot.order_flow.into_iter().collect();
+        self.order_book_deltas = snapshot.order_book_deltas
+            .into_iter()
+            .map(|deltas| (deltas.instrument_id, deltas))
+            .collect();
+        if self.trade_dedup.is_some() {
+            self.trade_dedup = snapshot.trade_dedup;
+        }
+        self.restored = true;
+
+        info!("Restored Data Engine snapshot taken at {}", snapshot.ts_snapshot);
+        Ok(())
+    }
+
+    /// Get current statistics.
+    ///
+    /// Processing rate covers the last 10 seconds since the first tick;
+    /// memory usage is an estimate of cached data and buffers, refreshed at
+    /// most once a second of engine clock time while processing.
     pub fn statistics(&self) -> DataEngineStatistics {
-        if let Ok(stats) = self.stats.read() {
+        let mut stats = if let Ok(stats) = self.stats.read() {
             stats.clone()
         } else {
             DataEngineStatistics::default()
+        };
+
+        stats.processing_rate = self.throughput.rate(self.clock.timestamp_ns());
+        stats.cache_hit_rate = self.combined_cache_hit_rate();
+        stats
+    }
+
+    /// Approximate bytes held by caches, aggregator history and pending buffers
+    fn estimated_memory_usage(&self) -> usize {
+        use std::mem::size_of;
+
+        let caches = self.tick_cache.estimated_memory_usage()
+            + self.quote_cache.estimated_memory_usage()
+            + self.bar_cache.estimated_memory_usage();
+        let aggregated_bars: usize = self.bar_aggregators
+            .values()
+            .map(|aggregator| aggregator.completed_bars.len())
+            .sum();
+        let stored_bars = self.cache.get_stats().bars_count;
+        let book_deltas: usize = self.order_book_deltas.values().map(|d| d.deltas.len()).sum();
+
+        caches
+            + (aggregated_bars + stored_bars) * size_of::<Bar>()
+            + book_deltas * size_of::<OrderBookDelta>()
+            + self.pending_signals.len() * size_of::<SignalData>()
+    }
+
+    /// Hit rate percentage across the tick, quote and bar caches
+    fn combined_cache_hit_rate(&self) -> f64 {
+        let (hits, misses) = [
+            self.tick_cache.statistics(),
+            self.quote_cache.statistics(),
+            self.bar_cache.statistics(),
+        ]
+        .into_iter()
+        .flatten()
+        .fold((0, 0), |(hits, misses), s| (hits + s.hits, misses + s.misses));
+
+        if hits + misses == 0 {
+            0.0
+        } else {
+            hits as f64 / (hits + misses) as f64 * 100.0
         }
     }
 
-    /// Reset statistics
+    /// Reset statistics; the memory usage estimate is kept
     pub fn reset_statistics(&mut self) {
         if let Ok(mut stats) = self.stats.write() {
-            *stats = DataEngineStatistics::default();
+            *stats = DataEngineStatistics {
+                memory_usage: stats.memory_usage,
+                ..Default::default()
+            };
         }
+        self.throughput.reset();
     }
 
     /// Check if the engine is running
@@ -421,3 +1519,620 @@ impl DataEngine {
         )
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn trade_tick(instrument_id: InstrumentId, price: f64, ts: UnixNanos) -> TradeTick {
+        TradeTick {
+            instrument_id,
+            price,
+            size: 1.0,
+            aggressor_side: AggressorSide::Buyer,
+            trade_id: ts.to_string(),
+            ts_event: ts,
+            ts_init: ts,
+        }
+    }
+
+    #[tokio::test]
+    async fn test_spawned_ingestion_task() {
+        let mut engine = DataEngine::new(DataEngineConfig::default());
+        engine.start().unwrap();
+        let (tx, rx) = engine.data_channel();
+
+        let engine = Arc::new(Mutex::new(engine));
+        let handle = DataEngine::spawn(Arc::clone(&engine), rx);
+
+        let instrument_id = InstrumentId::new(1);
+        for ts in 1..=5 {
+            tx.send(MarketData::Trade(trade_tick(instrument_id, 100.0, ts))).await.unwrap();
+        }
+        drop(tx);
+        handle.await.unwrap();
+
+        let engine = engine.lock().unwrap();
+        assert_eq!(engine.processed_count(), 5);
+        assert!(engine.get_trade_tick(instrument_id, 3).is_some());
+    }
+
+    #[test]
+    fn test_trade_deduplication() {
+        let config = DataEngineConfig {
+            trade_dedup_window: Some(2),
+            ..Default::default()
+        };
+        let mut engine = DataEngine::new(config);
+        engine.start().unwrap();
+
+        let instrument_id = InstrumentId::new(1);
+        for ts in [1, 2, 2, 3, 1] {
+            engine.process_trade_tick(trade_tick(instrument_id, 100.0, ts)).unwrap();
+        }
+
+        // Trade 1 fell out of the two-trade window before it was replayed
+        let stats = engine.statistics();
+        assert_eq!(stats.ticks_processed, 4);
+        assert_eq!(stats.duplicate_trades_filtered, 1);
+    }
+
+    #[test]
+    fn test_volume_bar_closes_on_exact_fractional_volume() {
+        let instrument_id = InstrumentId::new(1);
+        let bar_type = BarType {
+            instrument_id,
+            bar_spec: BarSpecification {
+                step: 1,
+                aggregation: BarAggregation::Volume(1),
+            },
+        };
+        let mut aggregator = BarAggregator::new(bar_type, 10);
+
+        // Ten 0.1 lots sum to 0.9999999999999999 in f64
+        let mut bar = None;
+        for ts in 1..=10 {
+            let tick = TradeTick {
+                size: 0.1,
+                ..trade_tick(instrument_id, 100.1, ts)
+            };
+            bar = aggregator.update_with_trade(&tick);
+        }
+
+        let bar = bar.expect("bar should close on the tenth tick");
+        assert_eq!(bar.volume, 1.0);
+        assert_eq!(bar.close, 100.1);
+    }
+
+    #[test]
+    fn test_non_finite_trades_kept_out_of_bars() {
+        let instrument_id = InstrumentId::new(1);
+        let bar_type = BarType {
+            instrument_id,
+            bar_spec: BarSpecification {
+                step: 1,
+                aggregation: BarAggregation::Tick(2),
+            },
+        };
+        let nan_price = trade_tick(instrument_id, f64::NAN, 2);
+        let infinite_size = TradeTick {
+            size: f64::INFINITY,
+            ..trade_tick(instrument_id, 100.0, 3)
+        };
+
+        // Skipped by the aggregator rather than folded in as zeros
+        let mut aggregator = BarAggregator::new(bar_type.clone(), 10);
+        assert!(aggregator.update_with_trade(&trade_tick(instrument_id, 101.0, 1)).is_none());
+        assert!(aggregator.update_with_trade(&nan_price).is_none());
+        assert!(aggregator.update_with_trade(&infinite_size).is_none());
+        let bar = aggregator.update_with_trade(&trade_tick(instrument_id, 102.0, 4)).unwrap();
+        assert_eq!((bar.low, bar.high, bar.volume), (101.0, 102.0, 2.0));
+
+        // Rejected by the engine even with validation off
+        let mut engine = DataEngine::new(DataEngineConfig::default());
+        engine.start().unwrap();
+        engine.add_bar_aggregator(bar_type);
+        assert!(engine.process_trade_tick(nan_price).is_err());
+        assert!(engine.process_trade_ticks(&[infinite_size]).unwrap().is_empty());
+        assert_eq!(engine.statistics().invalid_data_rejected, 2);
+        assert_eq!(engine.processed_count(), 0);
+        assert_eq!(engine.get_vwap(instrument_id), None);
+    }
+
+    #[test]
+    fn test_time_bars_close_on_minute_boundaries() {
+        const SECOND: u64 = 1_000_000_000;
+        const MINUTE: u64 = 60 * SECOND;
+        let mut engine = DataEngine::new(DataEngineConfig::default());
+        engine.start().unwrap();
+        let clock = Arc::new(crate::clock::TestClock::new(0));
+        engine.set_clock(clock.clone());
+
+        let instrument_id = InstrumentId::new(1);
+        let bar_type = BarType {
+            instrument_id,
+            bar_spec: BarSpecification {
+                step: 1,
+                aggregation: BarAggregation::Time(MINUTE),
+            },
+        };
+        engine.add_bar_aggregator(bar_type.clone());
+
+        // The first trade lands mid-minute; the bar still ends at :00
+        let start = 1_000 * MINUTE;
+        assert!(engine.process_trade_tick(trade_tick(instrument_id, 100.0, start + 17 * SECOND)).unwrap().is_none());
+        assert!(engine.process_trade_tick(trade_tick(instrument_id, 101.0, start + 45 * SECOND)).unwrap().is_none());
+
+        // The next minute's first trade closes the bar without joining it
+        let bar = engine
+            .process_trade_tick(trade_tick(instrument_id, 105.0, start + MINUTE + 5 * SECOND))
+            .unwrap()
+            .expect("time bar should close at the boundary");
+        assert_eq!(bar.ts_event, start + MINUTE);
+        assert_eq!(bar.ts_init, start + MINUTE);
+        assert_eq!((bar.open, bar.close, bar.high), (100.0, 101.0, 101.0));
+
+        // Without further trades the clock closes the bar at its boundary
+        clock.advance_to(start + 2 * MINUTE - 1);
+        assert!(engine.close_due_bars().is_empty());
+        clock.advance_to(start + 2 * MINUTE);
+        let bars = engine.close_due_bars();
+        assert_eq!(bars.len(), 1);
+        assert_eq!(bars[0].ts_event, start + 2 * MINUTE);
+        assert_eq!(bars[0].open, 105.0);
+        assert_eq!(engine.get_recent_bars(&bar_type, 10).len(), 2);
+        assert_eq!(engine.statistics().bars_generated, 2);
+
+        let schedule = engine.bar_aggregators[&bar_type].close_schedule().unwrap();
+        assert_eq!(schedule.next_after(start + 2 * MINUTE + 1), Some(start + 3 * MINUTE));
+    }
+
+    #[test]
+    fn test_instrument_precision_applied_to_bars() {
+        let mut engine = DataEngine::new(DataEngineConfig::default());
+        engine.start().unwrap();
+
+        let instrument = InstrumentAny::new("BTCUSDT", "BINANCE", 2, 3, 0.01, 0.001, 1.0);
+        let instrument_id = instrument.id();
+        let bar_type = BarType {
+            instrument_id,
+            bar_spec: BarSpecification {
+                step: 1,
+                aggregation: BarAggregation::Tick(2),
+            },
+        };
+        engine.add_bar_aggregator(bar_type);
+        engine.add_instrument(instrument).unwrap();
+
+        let looked_up = engine.get_instrument(instrument_id).unwrap();
+        assert_eq!(looked_up.price_precision, 2);
+        assert_eq!(looked_up.tick_size, 0.01);
+
+        engine.process_trade_tick(trade_tick(instrument_id, 100.123, 1)).unwrap();
+        let bar = engine
+            .process_trade_tick(trade_tick(instrument_id, 100.456, 2))
+            .unwrap()
+            .expect("tick bar should close");
+        assert_eq!(bar.open, 100.12);
+        assert_eq!(bar.close, 100.46);
+    }
+
+    #[test]
+    fn test_bar_history_ring_buffer() {
+        let config = DataEngineConfig {
+            max_bars_per_instrument: 3,
+            ..Default::default()
+        };
+        let mut engine = DataEngine::new(config);
+        engine.start().unwrap();
+
+        let instrument_id = InstrumentId::new(1);
+        let bar_type = BarType {
+            instrument_id,
+            bar_spec: BarSpecification {
+                step: 1,
+                aggregation: BarAggregation::Tick(1),
+            },
+        };
+        engine.add_bar_aggregator(bar_type.clone());
+
+        // The opening tick of each bar does not close it, so this yields five bars
+        for ts in 1..=10 {
+            engine.process_trade_tick(trade_tick(instrument_id, 100.0 + ts as f64, ts)).unwrap();
+        }
+
+        let bars = engine.get_recent_bars(&bar_type, 10);
+        assert_eq!(bars.len(), 3);
+        assert_eq!(bars[0].close, 106.0);
+        assert_eq!(engine.statistics().bars_evicted, 2);
+    }
+
+    #[test]
+    fn test_cached_data_returned_in_time_order() {
+        let mut engine = DataEngine::new(DataEngineConfig::default());
+        engine.start().unwrap();
+
+        let instrument_id = InstrumentId::new(1);
+        let other_id = InstrumentId::new(11);
+        let bar_type = BarType {
+            instrument_id,
+            bar_spec: BarSpecification {
+                step: 1,
+                aggregation: BarAggregation::Tick(1),
+            },
+        };
+        engine.add_bar_aggregator(bar_type);
+
+        // Keys end in "_11" before "_9", so key order is not time order
+        for ts in [8, 9, 10, 11] {
+            engine.process_trade_tick(trade_tick(instrument_id, 100.0 + ts as f64, ts)).unwrap();
+        }
+        engine.process_trade_tick(trade_tick(other_id, 50.0, 5)).unwrap();
+        for ts in [11, 9] {
+            let quote = QuoteTick {
+                instrument_id,
+                bid_price: 99.0,
+                ask_price: 101.0,
+                bid_size: 1.0,
+                ask_size: 1.0,
+                ts_event: ts,
+                ts_init: ts,
+            };
+            engine.process_quote_tick(quote).unwrap();
+        }
+
+        let trades: Vec<UnixNanos> = engine.get_cached_trade_ticks(instrument_id).iter().map(|tick| tick.ts_event).collect();
+        assert_eq!(trades, vec![8, 9, 10, 11]);
+        assert_eq!(engine.get_cached_trade_ticks(other_id).len(), 1);
+        let quotes: Vec<UnixNanos> = engine.get_cached_quote_ticks(instrument_id).iter().map(|quote| quote.ts_event).collect();
+        assert_eq!(quotes, vec![9, 11]);
+        assert!(engine.get_cached_quote_ticks(other_id).is_empty());
+        let bars: Vec<UnixNanos> = engine.get_cached_bars(instrument_id).iter().map(|bar| bar.ts_event).collect();
+        assert_eq!(bars, vec![9, 11]);
+        assert!(engine.get_cached_bars(other_id).is_empty());
+    }
+
+    #[test]
+    fn test_invalid_ticks_rejected() {
+        let mut engine = DataEngine::new(DataEngineConfig {
+            validation: Some(ValidationConfig::default()),
+            ..Default::default()
+        });
+        engine.start().unwrap();
+
+        let instrument_id = InstrumentId::new(1);
+        engine.process_trade_tick(trade_tick(instrument_id, 100.0, 1)).unwrap();
+        assert!(engine.process_trade_tick(trade_tick(instrument_id, f64::INFINITY, 2)).is_err());
+
+        let crossed = QuoteTick {
+            instrument_id,
+            bid_price: 101.0,
+            ask_price: 100.0,
+            bid_size: 1.0,
+            ask_size: 1.0,
+            ts_event: 3,
+            ts_init: 3,
+        };
+        assert!(engine.process_quote_tick(crossed).is_err());
+
+        let stats = engine.statistics();
+        assert_eq!(stats.ticks_processed, 1);
+        assert_eq!(stats.invalid_data_rejected, 2);
+        assert_eq!(engine.get_validation_violations(instrument_id).unwrap().total(), 2);
+    }
+
+    #[test]
+    fn test_snapshot_restore_keeps_partial_bar() {
+        let instrument_id = InstrumentId::new(1);
+        let bar_type = BarType {
+            instrument_id,
+            bar_spec: BarSpecification {
+                step: 1,
+                aggregation: BarAggregation::Tick(3),
+            },
+        };
+
+        let config = DataEngineConfig { trade_dedup_window: Some(10), ..Default::default() };
+        let mut engine = DataEngine::new(config.clone());
+        engine.start().unwrap();
+        engine.add_bar_aggregator(bar_type.clone());
+        for ts in 1..=2 {
+            engine.process_trade_tick(trade_tick(instrument_id, 100.0 + ts as f64, ts)).unwrap();
+        }
+        let snapshot = engine.snapshot().unwrap();
+
+        let mut restored = DataEngine::new(config);
+        restored.restore(&snapshot).unwrap();
+        restored.start().unwrap();
+        assert_eq!(restored.get_vwap(instrument_id), engine.get_vwap(instrument_id));
+        assert_eq!(restored.processed_count, 2);
+
+        // Starting keeps the restored deduplication window
+        restored.process_trade_tick(trade_tick(instrument_id, 102.0, 2)).unwrap();
+        assert_eq!(restored.statistics().duplicate_trades_filtered, 1);
+
+        // The third tick completes the bar that was open at snapshot time
+        let bar = restored
+            .process_trade_tick(trade_tick(instrument_id, 103.0, 3))
+            .unwrap()
+            .expect("restored partial bar should close");
+        assert_eq!(bar.open, 101.0);
+        assert_eq!(bar.close, 103.0);
+
+        assert!(restored.restore(b"garbage").is_err());
+    }
+
+    #[test]
+    fn test_batch_processing_matches_single_ticks() {
+        let instrument_id = InstrumentId::new(1);
+        let bar_type = BarType {
+            instrument_id,
+            bar_spec: BarSpecification {
+                step: 1,
+                aggregation: BarAggregation::Tick(2),
+            },
+        };
+        let mut ticks: Vec<_> = (1..=9).map(|ts| trade_tick(instrument_id, 100.0, ts)).collect();
+        ticks[4].size = 0.0; // rejected by validation, skipped in the batch
+
+        let mut engine = DataEngine::new(DataEngineConfig {
+            validation: Some(ValidationConfig::default()),
+            ..Default::default()
+        });
+        engine.start().unwrap();
+        engine.add_bar_aggregator(bar_type.clone());
+
+        let bars = engine.process_trade_ticks(&ticks).unwrap();
+        assert_eq!(bars.len(), 4);
+
+        let stats = engine.statistics();
+        assert_eq!(stats.ticks_processed, 8);
+        assert_eq!(stats.bars_generated, 4);
+        assert_eq!(stats.invalid_data_rejected, 1);
+
+        engine.stop();
+        assert!(engine.process_trade_ticks(&ticks).is_err());
+    }
+
+    #[test]
+    fn test_funding_rate_and_open_interest() {
+        let mut engine = DataEngine::new(DataEngineConfig::default());
+        engine.start().unwrap();
+
+        let instrument_id = InstrumentId::new(1);
+        engine.process_data(MarketData::FundingRate(FundingRateUpdate {
+            instrument_id,
+            rate: 0.0001,
+            next_funding_ts: Some(28_800_000_000_000),
+            ts_event: 1,
+            ts_init: 1,
+        })).unwrap();
+        engine.process_data(MarketData::OpenInterest(OpenInterestUpdate {
+            instrument_id,
+            open_interest: 12_500.0,
+            ts_event: 2,
+            ts_init: 2,
+        })).unwrap();
+
+        assert_eq!(engine.get_funding_rate(instrument_id).unwrap().rate, 0.0001);
+        assert_eq!(engine.get_open_interest(instrument_id).unwrap().open_interest, 12_500.0);
+        assert_eq!(engine.processed_count(), 2);
+    }
+
+    #[test]
+    fn test_mark_and_index_prices() {
+        let mut engine = DataEngine::new(DataEngineConfig::default());
+        engine.start().unwrap();
+
+        let instrument_id = InstrumentId::new(1);
+        for (ts, mark) in [(1, 100.0), (2, 100.5)] {
+            engine.process_data(MarketData::MarkPrice(MarkPriceUpdate {
+                instrument_id,
+                mark_price: mark,
+                ts_event: ts,
+                ts_init: ts,
+            })).unwrap();
+        }
+        engine.process_data(MarketData::IndexPrice(IndexPriceUpdate {
+            instrument_id,
+            index_price: 100.4,
+            ts_event: 2,
+            ts_init: 2,
+        })).unwrap();
+
+        assert_eq!(engine.get_mark_price(instrument_id).unwrap().mark_price, 100.5);
+        assert_eq!(engine.get_index_price(instrument_id).unwrap().index_price, 100.4);
+        assert!(engine.process_mark_price(MarkPriceUpdate {
+            instrument_id,
+            mark_price: f64::NAN,
+            ts_event: 3,
+            ts_init: 3,
+        }).is_err());
+    }
+
+    #[test]
+    fn test_composite_quotes_across_venues() {
+        let mut engine = DataEngine::new(DataEngineConfig::default());
+        let bus = Arc::new(MessageBus::new());
+        let mut published = bus.subscribe(&format!("{}*", DERIVED_QUOTE_TOPIC_PREFIX));
+        let mut next_quote = || published.try_recv().ok().map(|envelope| envelope.decode::<QuoteTick>().unwrap());
+        engine.set_message_bus(Arc::clone(&bus));
+        engine.start().unwrap();
+
+        let binance = InstrumentAny::new("BTCUSDT", "BINANCE", 2, 5, 0.01, 0.00001, 1.0);
+        let okx = InstrumentAny::new("BTCUSDT", "OKX", 1, 4, 0.1, 0.0001, 1.0);
+        let (binance_id, okx_id) = (binance.id(), okx.id());
+        engine.add_instrument(binance).unwrap();
+        engine.add_instrument(okx).unwrap();
+        let composite_id = engine.enable_quote_consolidation("BTCUSDT").unwrap();
+        assert!(engine.enable_quote_consolidation("ETHUSDT").is_err());
+
+        let quote = |instrument_id, bid, ask, ts| QuoteTick {
+            instrument_id,
+            bid_price: bid,
+            ask_price: ask,
+            bid_size: 1.0,
+            ask_size: 1.0,
+            ts_event: ts,
+            ts_init: ts,
+        };
+        engine.process_quote_tick(quote(binance_id, 100.0, 100.2, 1)).unwrap();
+        engine.process_quote_tick(quote(okx_id, 100.1, 100.3, 2)).unwrap();
+
+        let composite = engine.get_composite_quote(composite_id).unwrap();
+        assert_eq!(composite.bid_venue, "OKX");
+        assert_eq!(composite.ask_venue, "BINANCE");
+
+        assert_eq!(next_quote().unwrap().instrument_id, composite_id);
+        assert_eq!(next_quote().unwrap().bid_price, 100.1);
+        assert!(next_quote().is_none());
+        assert!(engine.get_quote_tick(composite_id, 2).is_some());
+    }
+
+    #[test]
+    fn test_feed_latency_alert_published_once_per_breach() {
+        let config = DataEngineConfig {
+            latency_alert_threshold_ns: Some(50),
+            ..Default::default()
+        };
+        let mut engine = DataEngine::new(config);
+        let bus = Arc::new(MessageBus::new());
+        let mut alerts = bus.subscribe(LATENCY_ALERT_TOPIC);
+        engine.set_message_bus(Arc::clone(&bus));
+        engine.start().unwrap();
+
+        let instrument_id = InstrumentId::new(1);
+        for (ts_event, delay) in [(100, 10), (200, 80), (300, 90), (400, 5), (500, 70)] {
+            let tick = TradeTick {
+                ts_init: ts_event + delay,
+                ..trade_tick(instrument_id, 100.0, ts_event)
+            };
+            engine.process_trade_tick(tick).unwrap();
+        }
+
+        let latency = engine.get_feed_latency(instrument_id).unwrap();
+        assert_eq!(latency.count, 5);
+        assert_eq!(latency.max_ns, 90);
+        assert_eq!(engine.statistics().latency_alerts, 2);
+
+        let alert: FeedLatencyAlert = bincode::deserialize(&alerts.try_recv().unwrap().payload).unwrap();
+        assert_eq!(alert.latency_ns, 80);
+        assert!(alerts.try_recv().is_ok());
+        assert!(alerts.try_recv().is_err());
+    }
+
+    #[test]
+    fn test_synthetic_spread_quotes() {
+        let mut engine = DataEngine::new(DataEngineConfig::default());
+        let bus = Arc::new(MessageBus::new());
+        engine.set_message_bus(Arc::clone(&bus));
+        engine.start().unwrap();
+
+        let (front, back) = (InstrumentId::new(1), InstrumentId::new(2));
+        let spread_id = engine
+            .add_synthetic(SyntheticInstrument::spread("CAL", back, front))
+            .unwrap();
+        let mut quotes = bus.subscribe(&format!("{}{}", DERIVED_QUOTE_TOPIC_PREFIX, spread_id));
+
+        engine.process_trade_tick(trade_tick(front, 100.0, 1)).unwrap();
+        engine.process_quote_tick(QuoteTick {
+            instrument_id: back,
+            bid_price: 103.0,
+            ask_price: 104.0,
+            bid_size: 1.0,
+            ask_size: 1.0,
+            ts_event: 2,
+            ts_init: 2,
+        }).unwrap();
+
+        let quote: QuoteTick = quotes.try_recv().unwrap().decode().unwrap();
+        assert_eq!(quote.bid_price, 3.0);
+        assert_eq!(quote.ask_price, 4.0);
+        assert!(quotes.try_recv().is_err());
+        assert!(engine.get_quote_tick(spread_id, 2).is_some());
+    }
+
+    #[test]
+    fn test_signal_publication() {
+        let mut engine = DataEngine::new(DataEngineConfig::default());
+        let bus = Arc::new(MessageBus::new());
+        let mut subscriber = bus.subscribe("signals.alpha");
+        engine.set_message_bus(Arc::clone(&bus));
+
+        assert!(engine.publish_signal(SignalData::new("alpha", 0.5, 1)).is_err());
+        engine.start().unwrap();
+        engine.publish_signal(SignalData::new("alpha", 0.5, 1)).unwrap();
+        engine.publish_signal(SignalData::new("alpha", 0.7, 2)).unwrap();
+
+        assert_eq!(engine.get_signal("alpha").unwrap().value, 0.7);
+        assert_eq!(engine.drain_signals().len(), 2);
+
+        let signal: SignalData = bincode::deserialize(&subscriber.try_recv().unwrap().payload).unwrap();
+        assert_eq!(signal.value, 0.5);
+    }
+
+    #[test]
+    fn test_runtime_statistics_are_populated() {
+        let mut engine = DataEngine::new(DataEngineConfig::default());
+        engine.start().unwrap();
+
+        let instrument_id = InstrumentId::new(1);
+        for ts in 1..=100 {
+            engine.process_trade_tick(trade_tick(instrument_id, 100.0, ts)).unwrap();
+        }
+        assert!(engine.get_trade_tick(instrument_id, 1).is_some());
+        assert!(engine.get_trade_tick(instrument_id, 1_000).is_none());
+
+        let stats = engine.statistics();
+        assert!(stats.processing_rate > 0.0);
+        assert!(stats.memory_usage > 0);
+        assert_eq!(stats.cache_hit_rate, 50.0);
+
+        engine.reset_statistics();
+        assert_eq!(engine.statistics().processing_rate, 0.0);
+
+        // Measured in the engine clock's time, so replays report the rate
+        // of the replayed data
+        let clock = Arc::new(crate::clock::TestClock::new(1_000_000_000));
+        engine.set_clock(clock.clone());
+        for ts in 101..=200 {
+            engine.process_trade_tick(trade_tick(instrument_id, 100.0, ts)).unwrap();
+        }
+        clock.advance_time(2_000_000_000);
+        assert_eq!(engine.statistics().processing_rate, 50.0);
+
+        // The rate is averaged from the first tick, not from the reset
+        engine.reset_statistics();
+        clock.advance_time(5_000_000_000);
+        assert_eq!(engine.statistics().processing_rate, 0.0);
+        for ts in 201..=300 {
+            engine.process_trade_tick(trade_tick(instrument_id, 100.0, ts)).unwrap();
+        }
+        clock.advance_time(1_000_000_000);
+        assert_eq!(engine.statistics().processing_rate, 100.0);
+    }
+
+    #[test]
+    fn test_persistence_records_accepted_data() {
+        let dir = std::env::temp_dir().join(format!("alphaforge-engine-{}", crate::uuid::UUID4::new()));
+        let mut engine = DataEngine::new(DataEngineConfig {
+            persistence: Some(PersistenceConfig::new(&dir)),
+            validation: Some(ValidationConfig::default()),
+            ..Default::default()
+        });
+        engine.start().unwrap();
+
+        let instrument_id = InstrumentId::new(1);
+        engine.process_trade_tick(trade_tick(instrument_id, 100.0, 1)).unwrap();
+        assert!(engine.process_trade_tick(trade_tick(instrument_id, f64::NAN, 2)).is_err());
+        engine.process_trade_tick(trade_tick(instrument_id, 101.0, 3)).unwrap();
+        engine.stop();
+
+        assert_eq!(engine.persistence_files().len(), 1);
+        let records = crate::persistence::read_file(&engine.persistence_files()[0]).unwrap();
+        let timestamps: Vec<_> = records.iter().map(|data| data.ts_init()).collect();
+        assert_eq!(timestamps, vec![1, 3]);
+
+        std::fs::remove_dir_all(dir).unwrap();
+    }
+}
@@ -0,0 +1,505 @@
+//! AlphaForge Strategy Decision Log
+//!
+//! Append-only record, per strategy, of every event the engine delivered to
+//! it and every order command it emitted, in the order they happened.
+//! `replay_decisions` re-runs a fresh strategy over the logged events on a
+//! `TestClock` set to the logged times, and `first_divergence` finds where
+//! its decisions stop matching the original run.
+
+use std::collections::HashMap;
+use std::fs::{self, File, OpenOptions};
+use std::io::{BufReader, BufWriter, Write};
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::{Arc, Mutex};
+
+use serde::{Deserialize, Serialize};
+
+use crate::clock::{TestClock, TimeEvent};
+use crate::data::{
+    Bar, FundingRateUpdate, IndexPriceUpdate, MarkPriceUpdate, OpenInterestUpdate, QuoteTick, SignalData,
+    TradeTick,
+};
+use crate::data_engine::DataEngine;
+use crate::error::{AlphaForgeError, Result};
+use crate::execution_engine::{OrderCommand, OrderEvent};
+use crate::identifiers::{OrderId, StrategyId};
+use crate::persistence::{length_prefix, read_records};
+use crate::strategy_engine::{Strategy, StrategyCell, StrategyConfig, StrategyContext, StrategyState};
+use crate::time::UnixNanos;
+
+/// An event delivered to a strategy
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub enum LoggedEvent {
+    Trade(TradeTick),
+    Quote(QuoteTick),
+    Bar(Bar),
+    FundingRate(FundingRateUpdate),
+    OpenInterest(OpenInterestUpdate),
+    MarkPrice(MarkPriceUpdate),
+    IndexPrice(IndexPriceUpdate),
+    Signal(SignalData),
+    /// Read from older logs, which hold only the timer's name, at the
+    /// record's time
+    #[serde(deserialize_with = "deserialize_timer")]
+    Timer(TimeEvent),
+    /// Carries the order IDs of the original run
+    Order(OrderEvent),
+}
+
+/// What happened
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub enum DecisionEntry {
+    /// An event was delivered to the strategy
+    Event(LoggedEvent),
+    /// The strategy emitted an order command
+    Command(OrderCommand),
+}
+
+/// One entry of a strategy's decision log
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct DecisionRecord {
+    /// Position in the whole log, increasing across strategies
+    pub sequence: u64,
+    /// Strategy time when the entry was recorded
+    pub ts: UnixNanos,
+    pub entry: DecisionEntry,
+}
+
+enum DecisionSink {
+    Memory(HashMap<StrategyId, Vec<DecisionRecord>>),
+    Directory {
+        directory: PathBuf,
+        files: HashMap<StrategyId, BufWriter<File>>,
+    },
+}
+
+/// Append-only log of strategy decisions, kept in memory or written to one
+/// file per strategy
+pub struct DecisionLog {
+    sink: Mutex<DecisionSink>,
+    sequence: AtomicU64,
+}
+
+impl DecisionLog {
+    /// Keep records in memory
+    pub fn in_memory() -> Self {
+        Self {
+            sink: Mutex::new(DecisionSink::Memory(HashMap::new())),
+            sequence: AtomicU64::new(0),
+        }
+    }
+
+    /// Append records to `decisions-{strategy_id}.msgpack` files in
+    /// `directory`, extending files left by earlier runs. The sequence
+    /// continues from theirs, and a record torn by a crash is cut off.
+    pub fn open(directory: impl Into<PathBuf>) -> Result<Self> {
+        let directory = directory.into();
+        fs::create_dir_all(&directory)?;
+        let mut sequence = 0;
+        for entry in fs::read_dir(&directory)? {
+            let path = entry?.path();
+            let is_log = path
+                .file_name()
+                .and_then(|name| name.to_str())
+                .is_some_and(|name| name.starts_with("decisions-") && name.ends_with(".msgpack"));
+            if !is_log {
+                continue;
+            }
+            let file = OpenOptions::new().read(true).write(true).open(&path)?;
+            let complete = read_records(&mut BufReader::new(&file), |bytes| {
+                let record: DecisionRecord = rmp_serde::from_slice(bytes)?;
+                sequence = sequence.max(record.sequence + 1);
+                Ok(())
+            })?;
+            if complete < file.metadata()?.len() {
+                file.set_len(complete)?;
+            }
+        }
+        Ok(Self {
+            sink: Mutex::new(DecisionSink::Directory {
+                directory,
+                files: HashMap::new(),
+            }),
+            sequence: AtomicU64::new(sequence),
+        })
+    }
+
+    /// Path of a strategy's log file under `directory`
+    pub fn file_path(directory: impl AsRef<Path>, strategy_id: StrategyId) -> PathBuf {
+        directory.as_ref().join(format!("decisions-{}.msgpack", strategy_id))
+    }
+
+    /// Append an entry to a strategy's log
+    pub fn append(&self, strategy_id: StrategyId, ts: UnixNanos, entry: DecisionEntry) -> Result<()> {
+        let mut sink = self.lock()?;
+        let record = DecisionRecord {
+            sequence: self.sequence.fetch_add(1, Ordering::Relaxed),
+            ts,
+            entry,
+        };
+
+        match &mut *sink {
+            DecisionSink::Memory(records) => records.entry(strategy_id).or_default().push(record),
+            DecisionSink::Directory { directory, files } => {
+                let writer = match files.entry(strategy_id) {
+                    std::collections::hash_map::Entry::Occupied(entry) => entry.into_mut(),
+                    std::collections::hash_map::Entry::Vacant(entry) => {
+                        let file = OpenOptions::new()
+                            .create(true)
+                            .append(true)
+                            .open(Self::file_path(directory, strategy_id))?;
+                        entry.insert(BufWriter::new(file))
+                    }
+                };
+                // Length-prefixed MessagePack, as in tick files
+                let bytes = rmp_serde::to_vec(&record)?;
+                let len = length_prefix(&bytes)
+                    .ok_or_else(|| AlphaForgeError::validation("Decision record too large to log"))?;
+                writer.write_all(&len)?;
+                writer.write_all(&bytes)?;
+            }
+        }
+        Ok(())
+    }
+
+    /// Write buffered records to disk
+    pub fn flush(&self) -> Result<()> {
+        if let DecisionSink::Directory { files, .. } = &mut *self.lock()? {
+            for writer in files.values_mut() {
+                writer.flush()?;
+            }
+        }
+        Ok(())
+    }
+
+    /// Write buffered records to disk and wait until they are durable
+    pub fn sync(&self) -> Result<()> {
+        if let DecisionSink::Directory { files, .. } = &mut *self.lock()? {
+            for writer in files.values_mut() {
+                writer.flush()?;
+                writer.get_ref().sync_all()?;
+            }
+        }
+        Ok(())
+    }
+
+    /// Every record logged for a strategy, oldest first
+    pub fn records(&self, strategy_id: StrategyId) -> Result<Vec<DecisionRecord>> {
+        let directory = match &*self.lock()? {
+            DecisionSink::Memory(records) => return Ok(records.get(&strategy_id).cloned().unwrap_or_default()),
+            DecisionSink::Directory { directory, .. } => directory.clone(),
+        };
+
+        self.flush()?;
+        let path = Self::file_path(&directory, strategy_id);
+        if !path.exists() {
+            return Ok(Vec::new());
+        }
+        read_file(path)
+    }
+
+    fn lock(&self) -> Result<std::sync::MutexGuard<'_, DecisionSink>> {
+        self.sink
+            .lock()
+            .map_err(|_| AlphaForgeError::runtime("Decision log lock poisoned"))
+    }
+}
+
+impl Drop for DecisionLog {
+    fn drop(&mut self) {
+        if let Err(e) = self.sync() {
+            tracing::warn!("Failed to sync decision log: {}", e);
+        }
+    }
+}
+
+/// Read every complete record from a strategy's decision log file
+pub fn read_file(path: impl AsRef<Path>) -> Result<Vec<DecisionRecord>> {
+    let mut reader = BufReader::new(File::open(path)?);
+    let mut records = Vec::new();
+    read_records(&mut reader, |bytes| {
+        let mut record: DecisionRecord = rmp_serde::from_slice(bytes)?;
+        if let DecisionEntry::Event(LoggedEvent::Timer(event)) = &mut record.entry {
+            if event.ts_event == 0 && event.ts_init == 0 {
+                event.ts_event = record.ts;
+                event.ts_init = record.ts;
+            }
+        }
+        records.push(record);
+        Ok(())
+    })?;
+    Ok(records)
+}
+
+// A timer firing, or the bare name older logs recorded, without times
+fn deserialize_timer<'de, D: serde::Deserializer<'de>>(deserializer: D) -> std::result::Result<TimeEvent, D::Error> {
+    #[derive(Deserialize)]
+    #[serde(untagged)]
+    enum Timer {
+        Event(TimeEvent),
+        Name(String),
+    }
+
+    Ok(match Timer::deserialize(deserializer)? {
+        Timer::Event(event) => event,
+        Timer::Name(name) => TimeEvent { name, ts_event: 0, ts_init: 0 },
+    })
+}
+
+/// Re-run `strategy` over the events in `records` and return the decision
+/// log of the replay.
+///
+/// The strategy is started, then each logged event is delivered with the
+/// clock set to the time it was logged at. Timer firings come from the log
+/// rather than the clock, and order commands are captured, not executed.
+pub fn replay_decisions(
+    strategy: Box<dyn Strategy>,
+    config: StrategyConfig,
+    data_engine: Arc<Mutex<DataEngine>>,
+    records: &[DecisionRecord],
+) -> std::result::Result<Vec<DecisionRecord>, String> {
+    let strategy_id = config.strategy_id;
+    let clock = Arc::new(TestClock::new(records.first().map_or(0, |record| record.ts)));
+    let log = Arc::new(DecisionLog::in_memory());
+    let (sender, _commands) = tokio::sync::mpsc::unbounded_channel();
+
+    let mut context = StrategyContext::new(config, data_engine, clock.clone());
+    context.order_commands = Some(sender);
+    context.decision_log = Some(Arc::clone(&log));
+    let mut cell = StrategyCell { strategy, context };
+
+    cell.context.set_state(StrategyState::Running);
+    let StrategyCell { strategy, context } = &mut cell;
+    strategy.on_start(context)?;
+
+    for record in records {
+        if let DecisionEntry::Event(event) = &record.entry {
+            clock.set_time(record.ts);
+            cell.process(&event.clone().into(), None);
+        }
+    }
+
+    cell.context.cancel_all_timers();
+    log.records(strategy_id).map_err(|e| e.to_string())
+}
+
+/// Index of the first entry where two decision logs disagree, if any.
+///
+/// Events must match exactly. Order commands match when they ask for the
+/// same thing; order IDs are fresh in every run, so an order is identified
+/// by its position among the log's submissions.
+pub fn first_divergence(original: &[DecisionRecord], replayed: &[DecisionRecord]) -> Option<usize> {
+    let mut original_orders = Vec::new();
+    let mut replayed_orders = Vec::new();
+
+    for (index, (a, b)) in original.iter().zip(replayed).enumerate() {
+        let same = match (&a.entry, &b.entry) {
+            (DecisionEntry::Event(a), DecisionEntry::Event(b)) => {
+                matches!((rmp_serde::to_vec(a), rmp_serde::to_vec(b)), (Ok(a), Ok(b)) if a == b)
+            }
+            (DecisionEntry::Command(a), DecisionEntry::Command(b)) => {
+                let position = |orders: &[OrderId], order_id: &OrderId| orders.iter().position(|id| id == order_id);
+                match (a, b) {
+                    (OrderCommand::Submit(a), OrderCommand::Submit(b)) => {
+                        original_orders.push(a.order_id);
+                        replayed_orders.push(b.order_id);
+                        a.instrument_id == b.instrument_id
+                            && a.side == b.side
+                            && a.order_type == b.order_type
+                            && a.quantity == b.quantity
+                            && a.price == b.price
+                            && a.stop_price == b.stop_price
+                    }
+                    (OrderCommand::Cancel { order_id: a }, OrderCommand::Cancel { order_id: b }) => {
+                        position(&original_orders, a) == position(&replayed_orders, b)
+                    }
+                    (
+                        OrderCommand::Modify { order_id: a, quantity: qa, price: pa },
+                        OrderCommand::Modify { order_id: b, quantity: qb, price: pb },
+                    ) => position(&original_orders, a) == position(&replayed_orders, b) && qa == qb && pa == pb,
+                    _ => false,
+                }
+            }
+            _ => false,
+        };
+        if !same {
+            return Some(index);
+        }
+    }
+
+    (original.len() != replayed.len()).then(|| original.len().min(replayed.len()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::data::AggressorSide;
+    use crate::data_engine::DataEngineConfig;
+    use crate::identifiers::InstrumentId;
+    use crate::strategy_engine::StrategyEngine;
+    use std::result::Result;
+
+    /// Bids at the trade price above `threshold`, cancelling on a timer
+    struct Breakout {
+        threshold: f64,
+        working: Option<OrderId>,
+    }
+
+    impl Strategy for Breakout {
+        fn on_quote_tick(&mut self, _context: &mut StrategyContext, _tick: &QuoteTick) -> Result<(), String> { Ok(()) }
+        fn on_bar(&mut self, _context: &mut StrategyContext, _bar: &Bar) -> Result<(), String> { Ok(()) }
+        fn on_stop(&mut self, _context: &mut StrategyContext) -> Result<(), String> { Ok(()) }
+        fn name(&self) -> &str { "Breakout" }
+
+        fn on_start(&mut self, context: &mut StrategyContext) -> Result<(), String> {
+            context.set_timer("cancel", 15)
+        }
+
+        fn on_trade_tick(&mut self, context: &mut StrategyContext, tick: &TradeTick) -> Result<(), String> {
+            if tick.price > self.threshold && self.working.is_none() {
+                self.working = Some(context.buy_limit(tick.instrument_id, 1.0, tick.price)?);
+            }
+            Ok(())
+        }
+
+        fn on_timer(&mut self, context: &mut StrategyContext, _name: &str) -> Result<(), String> {
+            match self.working.take() {
+                Some(order_id) => context.cancel_order(order_id),
+                None => Ok(()),
+            }
+        }
+    }
+
+    fn data_engine() -> Arc<Mutex<DataEngine>> {
+        Arc::new(Mutex::new(DataEngine::new(DataEngineConfig::default())))
+    }
+
+    #[test]
+    fn test_logged_run_replays_identically() {
+        let dir = std::env::temp_dir().join(format!("alphaforge-decisions-{}", crate::uuid::UUID4::new()));
+        let log = Arc::new(DecisionLog::open(&dir).unwrap());
+        let clock = Arc::new(TestClock::new(0));
+        let (sender, _orders) = tokio::sync::mpsc::unbounded_channel();
+        let config = StrategyConfig {
+            instruments: vec![InstrumentId::new(1)],
+            ..Default::default()
+        };
+        let strategy_id = config.strategy_id;
+
+        let mut engine = StrategyEngine::new(data_engine());
+        engine.set_clock(clock.clone()).unwrap();
+        engine.set_order_commands(sender).unwrap();
+        engine.set_decision_log(Arc::clone(&log)).unwrap();
+        engine.add_strategy(Box::new(Breakout { threshold: 100.0, working: None }), config.clone()).unwrap();
+        engine.start().unwrap();
+
+        for (i, price) in [99.0, 101.0, 102.0, 98.0, 103.0].into_iter().enumerate() {
+            let ts = i as u64 * 10;
+            engine.advance_clock_to(&clock, ts).unwrap();
+            engine.process_trade_tick(&TradeTick {
+                instrument_id: InstrumentId::new(1),
+                price,
+                size: 1.0,
+                aggressor_side: AggressorSide::Buyer,
+                trade_id: i.to_string(),
+                ts_event: ts,
+                ts_init: ts,
+            }).unwrap();
+        }
+        engine.stop().unwrap();
+
+        // Trades at 0 and 10, bid, timer at 15 cancels, trades at 20 and 30,
+        // bid again, timer at 30 cancels, trade at 40, bid
+        let original = read_file(DecisionLog::file_path(&dir, strategy_id)).unwrap();
+        let commands: Vec<_> = original
+            .iter()
+            .filter_map(|record| match &record.entry {
+                DecisionEntry::Command(command) => Some((record.ts, command)),
+                DecisionEntry::Event(_) => None,
+            })
+            .collect();
+        assert_eq!(commands.len(), 5);
+        assert!(matches!(commands[0], (10, OrderCommand::Submit(order)) if order.price == Some(101.0)));
+        assert!(matches!(commands[1], (15, OrderCommand::Cancel { .. })));
+        assert!(original.windows(2).all(|pair| pair[0].sequence < pair[1].sequence));
+        assert_eq!(log.records(strategy_id).unwrap().len(), original.len());
+
+        let strategy = Box::new(Breakout { threshold: 100.0, working: None });
+        let replayed = replay_decisions(strategy, config.clone(), data_engine(), &original).unwrap();
+        assert_eq!(replayed.len(), original.len());
+        assert_eq!(first_divergence(&original, &replayed), None);
+
+        // A different threshold skips the first bid
+        let strategy = Box::new(Breakout { threshold: 101.5, working: None });
+        let replayed = replay_decisions(strategy, config, data_engine(), &original).unwrap();
+        let divergence = first_divergence(&original, &replayed).unwrap();
+        assert!(matches!(original[divergence].entry, DecisionEntry::Command(OrderCommand::Submit(_))));
+
+        std::fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[test]
+    fn test_reopened_log_resumes_after_torn_record() {
+        let dir = std::env::temp_dir().join(format!("alphaforge-decisions-{}", crate::uuid::UUID4::new()));
+        let strategy_id = StrategyId::new(7);
+        let timer = |name: &str| DecisionEntry::Event(LoggedEvent::Timer(TimeEvent { name: name.to_string(), ts_event: 1, ts_init: 1 }));
+        let log = DecisionLog::open(&dir).unwrap();
+        log.append(strategy_id, 1, timer("first")).unwrap();
+        log.append(strategy_id, 2, timer("second")).unwrap();
+        drop(log);
+
+        // A crash mid-write leaves part of a record behind
+        let path = DecisionLog::file_path(&dir, strategy_id);
+        let mut file = OpenOptions::new().append(true).open(&path).unwrap();
+        file.write_all(&64u32.to_le_bytes()).unwrap();
+        file.write_all(&[0x92, 0x02]).unwrap();
+        drop(file);
+        assert_eq!(read_file(&path).unwrap().len(), 2);
+
+        let log = DecisionLog::open(&dir).unwrap();
+        log.append(strategy_id, 3, timer("third")).unwrap();
+        let sequences: Vec<u64> = log.records(strategy_id).unwrap().iter().map(|record| record.sequence).collect();
+        assert_eq!(sequences, vec![0, 1, 2]);
+
+        drop(log);
+        std::fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[test]
+    fn test_reads_timer_names_of_older_logs() {
+        #[derive(Serialize)]
+        enum OldEvent {
+            Timer(String),
+        }
+        #[derive(Serialize)]
+        enum OldEntry {
+            Event(OldEvent),
+        }
+        #[derive(Serialize)]
+        struct OldRecord {
+            sequence: u64,
+            ts: UnixNanos,
+            entry: OldEntry,
+        }
+
+        let path = std::env::temp_dir().join(format!("alphaforge-old-decisions-{}.log", std::process::id()));
+        let bytes = rmp_serde::to_vec(&OldRecord { sequence: 0, ts: 42, entry: OldEntry::Event(OldEvent::Timer("cancel".to_string())) }).unwrap();
+        let mut file = File::create(&path).unwrap();
+        file.write_all(&(bytes.len() as u32).to_le_bytes()).unwrap();
+        file.write_all(&bytes).unwrap();
+        drop(file);
+
+        let records = read_file(&path).unwrap();
+        fs::remove_file(&path).unwrap();
+        assert!(matches!(
+            &records[0].entry,
+            DecisionEntry::Event(LoggedEvent::Timer(TimeEvent { name, ts_event: 42, ts_init: 42 })) if name == "cancel"
+        ));
+
+        // Current logs keep the firing's own times
+        let event = LoggedEvent::Timer(TimeEvent { name: "cancel".to_string(), ts_event: 7, ts_init: 9 });
+        let decoded: LoggedEvent = rmp_serde::from_slice(&rmp_serde::to_vec(&event).unwrap()).unwrap();
+        assert!(matches!(decoded, LoggedEvent::Timer(TimeEvent { ts_event: 7, ts_init: 9, .. })));
+    }
+}
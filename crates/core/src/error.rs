@@ -1,12 +1,18 @@
 //! Error types for AlphaForge core components
 
+use std::sync::Arc;
+
 use thiserror::Error;
 
+use crate::cache::CacheError;
+use crate::execution_engine::ExecutionError;
+
 /// Result type alias for AlphaForge operations
 pub type Result<T> = std::result::Result<T, AlphaForgeError>;
 
 /// Core error types for AlphaForge system
 #[derive(Debug, Error, Clone)]
+#[non_exhaustive]
 pub enum AlphaForgeError {
     #[error("Invalid configuration: {msg}")]
     InvalidConfiguration { msg: String },
@@ -31,6 +37,16 @@ pub enum AlphaForgeError {
     
     #[error("Runtime error: {msg}")]
     Runtime { msg: String },
+
+    #[error("Execution error: {source}")]
+    Execution { source: Arc<ExecutionError> },
+
+    #[error("Cache error: {source}")]
+    Cache { source: Arc<CacheError> },
+
+    /// What was being done when `source` occurred
+    #[error("{context}")]
+    Context { context: String, source: Box<AlphaForgeError> },
 }
 
 impl AlphaForgeError {
@@ -53,6 +69,81 @@ impl AlphaForgeError {
     pub fn runtime(msg: impl Into<String>) -> Self {
         Self::Runtime { msg: msg.into() }
     }
+
+    /// Wrap the error with what was being done, e.g. `"Loading alphaforge.toml"`;
+    /// the code and `source()` chain are kept
+    pub fn with_context(self, context: impl Into<String>) -> Self {
+        Self::Context {
+            context: context.into(),
+            source: Box::new(self),
+        }
+    }
+
+    /// Message of the error with every context it was wrapped in,
+    /// e.g. `"Loading alphaforge.toml: Invalid configuration: ..."`
+    pub fn report(&self) -> String {
+        match self {
+            Self::Context { context, source } => format!("{}: {}", context, source.report()),
+            // Messages of the other errors include their source's
+            error => error.to_string(),
+        }
+    }
+
+    /// The error beneath any context
+    pub fn root(&self) -> &AlphaForgeError {
+        match self {
+            Self::Context { source, .. } => source.root(),
+            error => error,
+        }
+    }
+
+    /// Stable numeric code. The code divided by 100 gives the category,
+    /// e.g. 9xx for execution and 10xx for cache errors, and the remainder
+    /// the specific error within it. Codes are never reused or renumbered.
+    pub fn code(&self) -> u32 {
+        match self {
+            Self::InvalidConfiguration { .. } => 100,
+            Self::Network { .. } => 200,
+            Self::Serialization { .. } => 300,
+            Self::Time { .. } => 400,
+            Self::Validation { .. } => 500,
+            Self::Component { .. } => 600,
+            Self::MessageBus { .. } => 700,
+            Self::Runtime { .. } => 800,
+            Self::Execution { source } => match **source {
+                ExecutionError::OrderNotFound(_) => 901,
+                ExecutionError::OrderNotActive(_) => 902,
+                ExecutionError::ExchangeNotFound(_) => 903,
+                ExecutionError::NoRoutingConfigured(_) => 904,
+                ExecutionError::ExchangeError(_) => 905,
+                ExecutionError::InvalidOrderParameters(_) => 906,
+                ExecutionError::RiskCheckFailed(_) => 907,
+                ExecutionError::InsufficientFunds => 908,
+                ExecutionError::MarketClosed => 909,
+                ExecutionError::OrderTimeout => 910,
+            },
+            Self::Cache { source } => match **source {
+                CacheError::CacheFull => 1001,
+                CacheError::KeyNotFound { .. } => 1002,
+                CacheError::Serialization(_) => 1003,
+                CacheError::Database(_) => 1004,
+            },
+            Self::Context { source, .. } => source.code(),
+        }
+    }
+}
+
+// Conversion from engine errors, kept as the source
+impl From<ExecutionError> for AlphaForgeError {
+    fn from(err: ExecutionError) -> Self {
+        Self::Execution { source: Arc::new(err) }
+    }
+}
+
+impl From<CacheError> for AlphaForgeError {
+    fn from(err: CacheError) -> Self {
+        Self::Cache { source: Arc::new(err) }
+    }
 }
 
 // Conversion from common error types
@@ -62,6 +153,24 @@ impl From<serde_json::Error> for AlphaForgeError {
     }
 }
 
+impl From<std::io::Error> for AlphaForgeError {
+    fn from(err: std::io::Error) -> Self {
+        Self::Runtime { msg: err.to_string() }
+    }
+}
+
+impl From<bincode::Error> for AlphaForgeError {
+    fn from(err: bincode::Error) -> Self {
+        Self::Serialization { msg: err.to_string() }
+    }
+}
+
+impl From<lz4_flex::block::DecompressError> for AlphaForgeError {
+    fn from(err: lz4_flex::block::DecompressError) -> Self {
+        Self::Serialization { msg: err.to_string() }
+    }
+}
+
 impl From<rmp_serde::encode::Error> for AlphaForgeError {
     fn from(err: rmp_serde::encode::Error) -> Self {
         Self::Serialization { msg: err.to_string() }
@@ -73,3 +182,34 @@ impl From<rmp_serde::decode::Error> for AlphaForgeError {
         Self::Serialization { msg: err.to_string() }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::identifiers::OrderId;
+    use std::error::Error as _;
+
+    #[test]
+    fn test_error_codes_and_context() {
+        let error = AlphaForgeError::from(ExecutionError::OrderNotFound(OrderId::from_u64(5)));
+        assert_eq!(error.code(), 901);
+        let error = error.with_context("Cancelling order 5").with_context("Flattening strategy 1");
+        assert_eq!(error.to_string(), "Flattening strategy 1");
+        assert_eq!(error.report(), "Flattening strategy 1: Cancelling order 5: Execution error: Order not found: 5");
+        assert_eq!(error.code(), 901);
+        assert!(matches!(error.root(), AlphaForgeError::Execution { .. }));
+
+        // The engine error is reachable through the source chain
+        let mut source = error.source();
+        while let Some(inner) = source.and_then(|e| e.source()) {
+            source = Some(inner);
+        }
+        assert!(matches!(source.unwrap().downcast_ref::<Arc<ExecutionError>>(), Some(e) if matches!(**e, ExecutionError::OrderNotFound(_))));
+
+        let error = AlphaForgeError::from(CacheError::KeyNotFound { key: "k".into() });
+        assert_eq!(error.code(), 1002);
+        assert_eq!(error.to_string(), "Cache error: Key not found: k");
+        assert_eq!(error.report(), error.to_string());
+        assert_eq!(AlphaForgeError::config("bad").with_context("Starting node").code(), 100);
+    }
+}
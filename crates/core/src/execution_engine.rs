@@ -1,10 +1,18 @@
 use crate::identifiers::{OrderId, InstrumentId, StrategyId, VenueOrderId};
-use crate::message_bus::MessageBus;
-use crate::generic_cache::{GenericCache, GenericCacheConfig};
-use crate::time::{AtomicTime, UnixNanos};
+use crate::message_bus::{correlate, current_correlation_id, strategy_topic, MessageBus, ORDERS_CHANNEL};
+use crate::generic_cache::{GenericCache, GenericCacheConfig, MemSize};
+use crate::logging::order_span;
+use crate::portfolio::Portfolio;
+use crate::profiling::{ScopeHistogram, ScopeStatistics};
+use crate::clock::{Clock, LiveClock};
+use crate::time::UnixNanos;
+use crate::uuid::UUID7;
 use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
-use std::sync::{Arc, RwLock};
+use std::sync::{Arc, Mutex, RwLock};
+use tokio::sync::mpsc;
+use tokio::task::JoinHandle;
+use tracing::Instrument;
 
 // ============================================================================
 // ORDER TYPES AND ENUMS
@@ -109,18 +117,30 @@ pub struct Order {
     pub commission: f64,
     /// Order tags/metadata
     pub tags: HashMap<String, String>,
+    /// Message chain the order was created in, stamped on its events
+    #[serde(default)]
+    pub correlation_id: Option<UUID7>,
 }
 
 impl Order {
-    /// Create a new market order
+    /// Create a new market order stamped with the wall-clock time
     pub fn market(
         strategy_id: StrategyId,
         instrument_id: InstrumentId,
         side: OrderSide,
         quantity: f64,
     ) -> Self {
-        let now = crate::time::unix_nanos_now();
-        
+        Self::market_at(strategy_id, instrument_id, side, quantity, crate::time::unix_nanos_now())
+    }
+
+    /// Create a new market order stamped with `now`, e.g. a clock's time
+    pub fn market_at(
+        strategy_id: StrategyId,
+        instrument_id: InstrumentId,
+        side: OrderSide,
+        quantity: f64,
+        now: UnixNanos,
+    ) -> Self {
         Self {
             order_id: OrderId::new(),
             strategy_id,
@@ -139,10 +159,11 @@ impl Order {
             updated_time: now,
             commission: 0.0,
             tags: HashMap::new(),
+            correlation_id: current_correlation_id(),
         }
     }
 
-    /// Create a new limit order
+    /// Create a new limit order stamped with the wall-clock time
     pub fn limit(
         strategy_id: StrategyId,
         instrument_id: InstrumentId,
@@ -150,8 +171,18 @@ impl Order {
         quantity: f64,
         price: f64,
     ) -> Self {
-        let now = crate::time::unix_nanos_now();
-        
+        Self::limit_at(strategy_id, instrument_id, side, quantity, price, crate::time::unix_nanos_now())
+    }
+
+    /// Create a new limit order stamped with `now`, e.g. a clock's time
+    pub fn limit_at(
+        strategy_id: StrategyId,
+        instrument_id: InstrumentId,
+        side: OrderSide,
+        quantity: f64,
+        price: f64,
+        now: UnixNanos,
+    ) -> Self {
         Self {
             order_id: OrderId::new(),
             strategy_id,
@@ -170,6 +201,7 @@ impl Order {
             updated_time: now,
             commission: 0.0,
             tags: HashMap::new(),
+            correlation_id: current_correlation_id(),
         }
     }
 
@@ -200,6 +232,13 @@ impl Order {
     }
 }
 
+impl MemSize for Order {
+    fn mem_size(&self) -> usize {
+        let venue_order_id = self.venue_order_id.as_ref().map_or(0, |id| id.value.capacity());
+        std::mem::size_of::<Self>() + venue_order_id + self.tags.mem_size() - std::mem::size_of_val(&self.tags)
+    }
+}
+
 // ============================================================================
 // FILL STRUCTURE
 // ============================================================================
@@ -266,6 +305,42 @@ pub enum OrderEvent {
     },
 }
 
+impl OrderEvent {
+    /// Order the event concerns
+    pub fn order_id(&self) -> OrderId {
+        match self {
+            OrderEvent::OrderSubmitted { order, .. } => order.order_id,
+            OrderEvent::OrderAccepted { order_id, .. }
+            | OrderEvent::OrderRejected { order_id, .. }
+            | OrderEvent::OrderFilled { order_id, .. }
+            | OrderEvent::OrderCancelled { order_id, .. }
+            | OrderEvent::OrderModified { order_id, .. } => *order_id,
+        }
+    }
+}
+
+// ============================================================================
+// ORDER COMMANDS
+// ============================================================================
+
+/// Order instruction sent from a strategy to the execution engine
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub enum OrderCommand {
+    /// Submit a new order
+    Submit(Order),
+    /// Cancel an active order
+    Cancel { order_id: OrderId },
+    /// Amend quantity and/or price of an active order
+    Modify {
+        order_id: OrderId,
+        quantity: f64,
+        price: Option<f64>,
+    },
+}
+
+/// Sending half of an order command channel
+pub type OrderCommandSender = mpsc::UnboundedSender<OrderCommand>;
+
 // ============================================================================
 // EXECUTION ENGINE
 // ============================================================================
@@ -286,8 +361,12 @@ pub struct ExecutionEngine {
     routing_config: Arc<RwLock<HashMap<InstrumentId, String>>>,
     /// Execution statistics
     stats: Arc<RwLock<ExecutionStats>>,
-    /// Atomic time for timestamps
-    clock: Arc<AtomicTime>,
+    /// Time from creating an order to its complete fill
+    execution_latency: Arc<ScopeHistogram>,
+    /// Clock order events are stamped with
+    clock: Arc<RwLock<Arc<dyn Clock>>>,
+    /// Portfolio fills are booked into
+    portfolio: Arc<RwLock<Option<Arc<Mutex<Portfolio>>>>>,
 }
 
 /// Execution performance statistics
@@ -307,6 +386,8 @@ pub struct ExecutionStats {
     pub total_commission: f64,
     /// Average execution latency (nanoseconds)
     pub avg_execution_latency_ns: u64,
+    /// Distribution of the time from creating an order to its complete fill
+    pub execution_latency: ScopeStatistics,
 }
 
 impl ExecutionEngine {
@@ -316,23 +397,52 @@ impl ExecutionEngine {
             max_size: 10000,
             ttl_seconds: Some(3600), // 1 hour TTL for orders
             enable_statistics: true,
+            ..Default::default()
         };
 
         Self {
             message_bus,
-            order_cache: Arc::new(GenericCache::new(cache_config)),
+            order_cache: Arc::new(GenericCache::new(cache_config).with_mem_size()),
             active_orders: Arc::new(RwLock::new(HashMap::new())),
             strategy_orders: Arc::new(RwLock::new(HashMap::new())),
             exchange_adapters: Arc::new(RwLock::new(HashMap::new())),
             routing_config: Arc::new(RwLock::new(HashMap::new())),
             stats: Arc::new(RwLock::new(ExecutionStats::default())),
-            clock: Arc::new(AtomicTime::new()),
+            execution_latency: Arc::new(ScopeHistogram::new("execution_latency")),
+            clock: Arc::new(RwLock::new(Arc::new(LiveClock::new()))),
+            portfolio: Arc::new(RwLock::new(None)),
         }
     }
 
+    /// Stamp order events with `clock` (a `LiveClock` live, a `TestClock` in
+    /// backtests and replay)
+    pub fn set_clock(&self, clock: Arc<dyn Clock>) {
+        *self.clock.write().unwrap() = clock;
+    }
+
+    /// Clock order events are stamped with
+    pub fn clock(&self) -> Arc<dyn Clock> {
+        Arc::clone(&self.clock.read().unwrap())
+    }
+
+    fn now(&self) -> UnixNanos {
+        self.clock.read().unwrap().timestamp_ns()
+    }
+
+    /// Book every subsequent fill into `portfolio` and reject orders that
+    /// exceed its capital allocation or exposure limits, reserving working
+    /// orders against them until they fill or end
+    pub fn attach_portfolio(&self, portfolio: Arc<Mutex<Portfolio>>) {
+        *self.portfolio.write().unwrap() = Some(portfolio);
+    }
+
     /// Submit order for execution
     pub async fn submit_order(&self, mut order: Order) -> Result<OrderId, ExecutionError> {
-        let submit_time = self.clock.get();
+        if let Some(portfolio) = self.portfolio.read().unwrap().as_ref() {
+            portfolio.lock().unwrap().reserve_order(&order).map_err(ExecutionError::RiskCheckFailed)?;
+        }
+
+        let submit_time = self.now();
         order.status = OrderStatus::Submitted;
         order.updated_time = submit_time;
 
@@ -352,7 +462,7 @@ impl ExecutionEngine {
             let mut strategy_orders = self.strategy_orders.write().unwrap();
             strategy_orders
                 .entry(order.strategy_id)
-                .or_insert_with(Vec::new)
+                .or_default()
                 .push(order_id);
         }
 
@@ -366,11 +476,39 @@ impl ExecutionEngine {
                 tokio::spawn({
                     let adapter = adapter.clone_box();
                     let order = order.clone();
+                    let message_bus = Arc::clone(&self.message_bus);
+                    let order_cache = Arc::clone(&self.order_cache);
+                    let active_orders = Arc::clone(&self.active_orders);
+                    let strategy_orders = Arc::clone(&self.strategy_orders);
+                    let portfolio = Arc::clone(&self.portfolio);
+                    let stats = Arc::clone(&self.stats);
+                    let clock = self.clock();
                     async move {
-                        if let Err(e) = adapter.submit_order(order).await {
-                            eprintln!("Failed to submit order to exchange: {}", e);
-                        }
+                        let (strategy_id, correlation_id) = (order.strategy_id, order.correlation_id);
+                        let event = match adapter.submit_order(order.clone()).await {
+                            Ok(venue_order_id) => OrderEvent::OrderAccepted {
+                                order_id,
+                                venue_order_id,
+                                timestamp: clock.timestamp_ns(),
+                            },
+                            Err(e) => {
+                                tracing::warn!("Failed to submit order {} to exchange: {}", order_id, e);
+                                Self::retire(&order_cache, &active_orders, &strategy_orders, &portfolio, order, OrderStatus::Rejected);
+                                stats.write().unwrap().orders_rejected += 1;
+                                OrderEvent::OrderRejected {
+                                    order_id,
+                                    reason: e.to_string(),
+                                    timestamp: clock.timestamp_ns(),
+                                }
+                            }
+                        };
+                        let topic = match event {
+                            OrderEvent::OrderAccepted { .. } => "orders.accepted",
+                            _ => "orders.rejected",
+                        };
+                        Self::publish_event(&message_bus, topic, strategy_id, correlation_id, event);
                     }
+                    .in_current_span()
                 });
             } else {
                 return Err(ExecutionError::ExchangeNotFound(exchange_name));
@@ -389,14 +527,14 @@ impl ExecutionEngine {
             timestamp: submit_time,
         };
         
-        self.message_bus.publish("orders.submitted", &event);
+        Self::publish_event(&self.message_bus, "orders.submitted", order.strategy_id, order.correlation_id, event);
 
         Ok(order_id)
     }
 
     /// Cancel an active order
     pub async fn cancel_order(&self, order_id: OrderId) -> Result<(), ExecutionError> {
-        let cancel_time = self.clock.get();
+        let cancel_time = self.now();
 
         // Get order from active orders
         let order = {
@@ -413,29 +551,23 @@ impl ExecutionEngine {
         // Route to appropriate exchange for cancellation
         let exchange_name = self.get_exchange_for_instrument(&order.instrument_id)?;
         
-        {
+        let adapter = {
             let adapters = self.exchange_adapters.read().unwrap();
-            if let Some(adapter) = adapters.get(&exchange_name) {
-                if let Err(e) = adapter.cancel_order(order_id).await {
-                    return Err(ExecutionError::ExchangeError(e.to_string()));
-                }
-            } else {
-                return Err(ExecutionError::ExchangeNotFound(exchange_name));
+            match adapters.get(&exchange_name) {
+                Some(adapter) => adapter.clone_box(),
+                None => return Err(ExecutionError::ExchangeNotFound(exchange_name)),
             }
+        };
+
+        if let Err(e) = adapter.cancel_order(order_id).await {
+            return Err(ExecutionError::ExchangeError(e.to_string()));
         }
 
-        // Update order status
-        order.status = OrderStatus::Cancelled;
         order.updated_time = cancel_time;
 
-        // Update cache
-        self.order_cache.put(order_id.to_string(), order.clone());
-
         // Remove from active orders
-        {
-            let mut active_orders = self.active_orders.write().unwrap();
-            active_orders.remove(&order_id);
-        }
+        let (strategy_id, correlation_id) = (order.strategy_id, order.correlation_id);
+        Self::retire(&self.order_cache, &self.active_orders, &self.strategy_orders, &self.portfolio, order, OrderStatus::Cancelled);
 
         // Update statistics
         {
@@ -449,14 +581,146 @@ impl ExecutionEngine {
             timestamp: cancel_time,
         };
         
-        self.message_bus.publish("orders.cancelled", &event);
+        Self::publish_event(&self.message_bus, "orders.cancelled", strategy_id, correlation_id, event);
+
+        Ok(())
+    }
+
+    /// Modify quantity and/or price of an active order
+    pub async fn modify_order(
+        &self,
+        order_id: OrderId,
+        quantity: f64,
+        price: Option<f64>,
+    ) -> Result<(), ExecutionError> {
+        let modify_time = self.now();
+
+        let order = {
+            let active_orders = self.active_orders.read().unwrap();
+            active_orders.get(&order_id).cloned()
+        };
+
+        let mut order = order.ok_or(ExecutionError::OrderNotFound(order_id))?;
+
+        if !order.is_active() {
+            return Err(ExecutionError::OrderNotActive(order_id));
+        }
+        if !quantity.is_finite() || quantity <= order.filled_quantity {
+            return Err(ExecutionError::InvalidOrderParameters(format!(
+                "Quantity {} must exceed filled quantity {}",
+                quantity, order.filled_quantity
+            )));
+        }
+        if price.is_some_and(|p| !p.is_finite() || p <= 0.0) {
+            return Err(ExecutionError::InvalidOrderParameters("Price must be positive".to_string()));
+        }
+
+        // Route to appropriate exchange for amendment
+        let exchange_name = self.get_exchange_for_instrument(&order.instrument_id)?;
+
+        let adapter = {
+            let adapters = self.exchange_adapters.read().unwrap();
+            match adapters.get(&exchange_name) {
+                Some(adapter) => adapter.clone_box(),
+                None => return Err(ExecutionError::ExchangeNotFound(exchange_name)),
+            }
+        };
+
+        let original = order.clone();
+        order.quantity = quantity;
+        if price.is_some() {
+            order.price = price;
+        }
+        order.updated_time = modify_time;
+
+        let portfolio = self.portfolio.read().unwrap().clone();
+        if let Some(portfolio) = &portfolio {
+            portfolio.lock().unwrap().reserve_order(&order).map_err(ExecutionError::RiskCheckFailed)?;
+        }
+        if let Err(e) = adapter.modify_order(order_id, quantity, price).await {
+            if let Some(portfolio) = &portfolio {
+                portfolio.lock().unwrap().reserve_order_unchecked(&original);
+            }
+            return Err(ExecutionError::ExchangeError(e.to_string()));
+        }
+
+        self.order_cache.put(order_id.to_string(), order.clone());
+        {
+            let mut active_orders = self.active_orders.write().unwrap();
+            active_orders.insert(order_id, order.clone());
+        }
+
+        // Publish modification event
+        let (strategy_id, correlation_id) = (order.strategy_id, order.correlation_id);
+        let event = OrderEvent::OrderModified {
+            order_id,
+            modified_order: order,
+            timestamp: modify_time,
+        };
+
+        Self::publish_event(&self.message_bus, "orders.modified", strategy_id, correlation_id, event);
 
         Ok(())
     }
 
+    /// Execute a single order command, logging within a span tagged with
+    /// the order. A submission that fails is published as `OrderRejected`
+    /// so the strategy hears about it.
+    pub async fn execute(&self, command: OrderCommand) -> Result<(), ExecutionError> {
+        let span = match &command {
+            OrderCommand::Submit(order) => {
+                order_span(order.order_id, Some(order.strategy_id), Some(order.instrument_id), order.correlation_id)
+            }
+            OrderCommand::Cancel { order_id } | OrderCommand::Modify { order_id, .. } => self.order_span(*order_id),
+        };
+        self.execute_command(command).instrument(span).await
+    }
+
+    async fn execute_command(&self, command: OrderCommand) -> Result<(), ExecutionError> {
+        match command {
+            OrderCommand::Submit(order) => {
+                let (order_id, strategy_id, correlation_id) = (order.order_id, order.strategy_id, order.correlation_id);
+                let result = self.submit_order(order).await.map(|_| ());
+                if let Err(e) = &result {
+                    // Indexed before routing failed
+                    if let Some(mut order) = self.order_cache.get(&order_id.to_string()) {
+                        order.updated_time = self.now();
+                        Self::retire(&self.order_cache, &self.active_orders, &self.strategy_orders, &self.portfolio, order, OrderStatus::Rejected);
+                    }
+                    self.stats.write().unwrap().orders_rejected += 1;
+                    let event = OrderEvent::OrderRejected {
+                        order_id,
+                        reason: e.to_string(),
+                        timestamp: self.now(),
+                    };
+                    Self::publish_event(&self.message_bus, "orders.rejected", strategy_id, correlation_id, event);
+                }
+                result
+            }
+            OrderCommand::Cancel { order_id } => self.cancel_order(order_id).await,
+            OrderCommand::Modify { order_id, quantity, price } => {
+                self.modify_order(order_id, quantity, price).await
+            }
+        }
+    }
+
+    /// Execute order commands from a channel on a dedicated task.
+    ///
+    /// Failures are logged; the task ends when every sender is dropped.
+    pub fn spawn(engine: Arc<ExecutionEngine>, mut commands: mpsc::UnboundedReceiver<OrderCommand>) -> JoinHandle<()> {
+        tokio::spawn(async move {
+            while let Some(command) = commands.recv().await {
+                if let Err(e) = engine.execute(command).await {
+                    tracing::warn!("Order command failed: {}", e);
+                }
+            }
+        })
+    }
+
     /// Handle order fill from exchange
     pub fn handle_fill(&self, fill: Fill) -> Result<(), ExecutionError> {
-        let fill_time = self.clock.get();
+        let _span = self.order_span(fill.order_id).entered();
+        let fill_time = self.now();
 
         // Get order from active orders
         let order = {
@@ -487,14 +751,11 @@ impl ExecutionEngine {
             order.status = OrderStatus::PartiallyFilled;
         }
 
-        // Update cache
-        self.order_cache.put(fill.order_id.to_string(), order.clone());
-
         // Update active orders or remove if filled
         if order.is_complete() {
-            let mut active_orders = self.active_orders.write().unwrap();
-            active_orders.remove(&fill.order_id);
+            Self::retire(&self.order_cache, &self.active_orders, &self.strategy_orders, &self.portfolio, order.clone(), OrderStatus::Filled);
         } else {
+            self.order_cache.put(fill.order_id.to_string(), order.clone());
             let mut active_orders = self.active_orders.write().unwrap();
             active_orders.insert(fill.order_id, order.clone());
         }
@@ -504,11 +765,16 @@ impl ExecutionEngine {
             let mut stats = self.stats.write().unwrap();
             if order.status == OrderStatus::Filled {
                 stats.orders_filled += 1;
+                self.execution_latency.record(fill_time.saturating_sub(order.created_time));
             }
             stats.total_fill_volume += fill.quantity;
             stats.total_commission += fill.commission;
         }
 
+        if let Some(portfolio) = self.portfolio.read().unwrap().as_ref() {
+            portfolio.lock().unwrap().apply_fill(&order, &fill);
+        }
+
         // Publish fill event
         let event = OrderEvent::OrderFilled {
             order_id: fill.order_id,
@@ -516,14 +782,87 @@ impl ExecutionEngine {
             timestamp: fill_time,
         };
         
-        self.message_bus.publish("orders.filled", &event);
+        Self::publish_event(&self.message_bus, "orders.filled", order.strategy_id, order.correlation_id, event);
 
         Ok(())
     }
 
+    // Move an order to a terminal `status`: its final state stays in the
+    // order cache while it leaves the working indexes and stops counting
+    // against the portfolio's limits
+    fn retire(
+        order_cache: &GenericCache<Order>,
+        active_orders: &RwLock<HashMap<OrderId, Order>>,
+        strategy_orders: &RwLock<HashMap<StrategyId, Vec<OrderId>>>,
+        portfolio: &RwLock<Option<Arc<Mutex<Portfolio>>>>,
+        mut order: Order,
+        status: OrderStatus,
+    ) {
+        let (order_id, strategy_id) = (order.order_id, order.strategy_id);
+        order.status = status;
+        order_cache.put(order_id.to_string(), order);
+        active_orders.write().unwrap().remove(&order_id);
+        if let Some(portfolio) = portfolio.read().unwrap().as_ref() {
+            portfolio.lock().unwrap().release_order(order_id);
+        }
+
+        let mut strategy_orders = strategy_orders.write().unwrap();
+        if let Some(order_ids) = strategy_orders.get_mut(&strategy_id) {
+            order_ids.retain(|id| *id != order_id);
+            if order_ids.is_empty() {
+                strategy_orders.remove(&strategy_id);
+            }
+        }
+    }
+
+    /// Receive the events of `strategy_id`'s orders, in the order they occur
+    pub fn subscribe_strategy_events(&self, strategy_id: StrategyId) -> mpsc::UnboundedReceiver<Arc<OrderEvent>> {
+        self.message_bus.subscribe_typed(&strategy_topic(strategy_id, ORDERS_CHANNEL))
+    }
+
+    /// Bus order events are published on
+    pub fn message_bus(&self) -> &Arc<MessageBus> {
+        &self.message_bus
+    }
+
+    // Span for an order, tagged with its owner and instrument while active
+    fn order_span(&self, order_id: OrderId) -> tracing::Span {
+        let active_orders = self.active_orders.read().unwrap();
+        let order = active_orders.get(&order_id);
+        order_span(order_id, order.map(|o| o.strategy_id), order.map(|o| o.instrument_id), order.and_then(|o| o.correlation_id))
+    }
+
+    /// Message chain an active or recently completed order was created in
+    pub fn order_correlation(&self, order_id: OrderId) -> Option<UUID7> {
+        let active = self.active_orders.read().unwrap().get(&order_id).map(|order| order.correlation_id);
+        active
+            .or_else(|| self.order_cache.get(&order_id.to_string()).map(|order| order.correlation_id))
+            .flatten()
+    }
+
+    // Publish on the event's own topic and in the owning strategy's
+    // namespace, where the strategy engine receives it unserialized, as a
+    // hop of the order's message chain. Every publish shares the one event,
+    // and is only encoded for topics with envelope subscribers.
+    fn publish_event(
+        message_bus: &MessageBus,
+        topic: &str,
+        strategy_id: StrategyId,
+        correlation_id: Option<UUID7>,
+        event: OrderEvent,
+    ) {
+        let _correlation = correlation_id.map(|id| correlate("execution", id));
+        let strategy_topic = strategy_topic(strategy_id, ORDERS_CHANNEL);
+        let event = Arc::new(event);
+        message_bus.publish(topic, &*event);
+        message_bus.publish(&strategy_topic, &*event);
+        message_bus.publish_typed(&strategy_topic, event);
+    }
+
     /// Get execution statistics
     pub fn get_statistics(&self) -> ExecutionStats {
         let stats = self.stats.read().unwrap();
+        let execution_latency = self.execution_latency.statistics();
         ExecutionStats {
             orders_submitted: stats.orders_submitted,
             orders_filled: stats.orders_filled,
@@ -531,11 +870,13 @@ impl ExecutionEngine {
             orders_rejected: stats.orders_rejected,
             total_fill_volume: stats.total_fill_volume,
             total_commission: stats.total_commission,
-            avg_execution_latency_ns: stats.avg_execution_latency_ns,
+            avg_execution_latency_ns: execution_latency.mean_ns as u64,
+            execution_latency,
         }
     }
 
-    /// Get orders for a strategy
+    /// Working orders of a strategy; completed orders are dropped once they
+    /// are filled, cancelled or rejected
     pub fn get_strategy_orders(&self, strategy_id: StrategyId) -> Vec<Order> {
         let strategy_orders = self.strategy_orders.read().unwrap();
         if let Some(order_ids) = strategy_orders.get(&strategy_id) {
@@ -548,6 +889,12 @@ impl ExecutionEngine {
         }
     }
 
+    /// Current state of an order, working or recently completed
+    pub fn get_order(&self, order_id: OrderId) -> Option<Order> {
+        let active = self.active_orders.read().unwrap().get(&order_id).cloned();
+        active.or_else(|| self.order_cache.get(&order_id.to_string()))
+    }
+
     /// Get active orders count
     pub fn get_active_orders_count(&self) -> usize {
         let active_orders = self.active_orders.read().unwrap();
@@ -576,7 +923,7 @@ impl ExecutionEngine {
         routing
             .get(instrument_id)
             .cloned()
-            .ok_or_else(|| ExecutionError::NoRoutingConfigured(*instrument_id))
+            .ok_or(ExecutionError::NoRoutingConfigured(*instrument_id))
     }
 }
 
@@ -671,6 +1018,7 @@ mod tests {
     }
 
     #[test]
+    #[allow(clippy::bool_comparison)]
     fn test_order_states() {
         let strategy_id = StrategyId::new(1);
         let instrument_id = InstrumentId::from_str("ETHUSD.COINBASE").unwrap();
@@ -709,4 +1057,18 @@ mod tests {
         assert_eq!(order.remaining_quantity(), 0.0);
         assert!(order.is_filled());
     }
+
+    #[tokio::test]
+    async fn test_rejected_orders_release_their_reservation() {
+        let engine = ExecutionEngine::new(Arc::new(MessageBus::new()));
+        let portfolio = Arc::new(Mutex::new(Portfolio::default()));
+        engine.attach_portfolio(Arc::clone(&portfolio));
+
+        let strategy_id = StrategyId::new(1);
+        let instrument_id = InstrumentId::new(7);
+        let order = Order::limit(strategy_id, instrument_id, OrderSide::Buy, 2.0, 100.0);
+        // Reserved, then rejected for want of a route
+        assert!(engine.execute(OrderCommand::Submit(order)).await.is_err());
+        assert_eq!(portfolio.lock().unwrap().open_order_quantity(strategy_id, instrument_id), (0.0, 0.0));
+    }
 }
@@ -1,11 +1,51 @@
 //! Generic key-value cache for PyO3 integration
 //! 
 //! High-performance generic cache that can work with any serializable data types.
+//! With a persistence path configured, `GenericCache::open` restores the
+//! entries saved there and the cache writes bincode snapshots back to it.
+//! Read-heavy caches can keep their entries in one `DashMap` with
+//! `CacheConcurrency::Concurrent`, and caches of large values trade CPU for
+//! memory by compressing them.
 
-use std::collections::HashMap;
-use std::sync::{Arc, RwLock};
-use std::time::{Duration, SystemTime, UNIX_EPOCH};
-use serde::{Serialize, Deserialize};
+use std::collections::{BTreeMap, HashMap};
+use std::fs::File;
+use std::io::{BufReader, BufWriter};
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicBool, AtomicU64, AtomicUsize, Ordering};
+use std::sync::mpsc::{self, RecvTimeoutError};
+use std::sync::{Arc, Mutex, OnceLock, RwLock, Weak};
+use std::time::Duration;
+
+use dashmap::DashMap;
+use serde::de::DeserializeOwned;
+use serde::{Deserialize, Serialize};
+
+use crate::clock::{Clock, TimeEventSender};
+use crate::error::{AlphaForgeError, Result};
+use crate::time::{unix_nanos_now, NANOS_PER_SECOND};
+
+pub use crate::cache::EvictionPolicy;
+
+/// Shards of a cache unless configured otherwise
+pub const DEFAULT_SHARDS: usize = 16;
+/// Fewest entries a shard is sized for; smaller caches get fewer shards
+const MIN_SHARD_CAPACITY: usize = 64;
+/// Shortest wait between background saves, so a zero interval does not spin
+const MIN_FLUSH_INTERVAL: Duration = Duration::from_millis(10);
+
+/// How a cache stores its entries and synchronizes access to them
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum CacheConcurrency {
+    /// Sharded behind read-write locks. Reads take their shard's read lock,
+    /// recording use in atomics that writes fold into eviction order.
+    #[default]
+    Locked,
+    /// In one `DashMap`, reads taking only its shared locks and tracking use
+    /// in atomics: for read-heavy data such as instrument metadata or
+    /// routing tables. Eviction order is exact, as in a single-shard locked
+    /// cache, at the cost of inserts and removals taking turns.
+    Concurrent,
+}
 
 /// Configuration for generic cache
 #[derive(Debug, Clone)]
@@ -13,6 +53,24 @@ pub struct GenericCacheConfig {
     pub max_size: usize,
     pub ttl_seconds: Option<u64>,
     pub enable_statistics: bool,
+    /// Which entry makes room when the cache is full
+    pub eviction_policy: EvictionPolicy,
+    /// Independently locked partitions of the keys, so threads touching
+    /// different keys rarely wait on each other. Small caches use fewer.
+    pub shards: usize,
+    /// Snapshot file the cache is loaded from and saved to
+    pub persistence_path: Option<PathBuf>,
+    /// Save changes in the background this often; without it they are
+    /// saved only by `save_to_disk` and when the cache is dropped
+    pub flush_interval_seconds: Option<u64>,
+    /// Purge expired entries in the background this often, rather than
+    /// only when they are read
+    pub sweep_interval_seconds: Option<u64>,
+    /// Storage and locking of the entries; `shards` applies when `Locked`
+    pub concurrency: CacheConcurrency,
+    /// LZ4-compress values whose bincode encoding takes at least this many
+    /// bytes, decompressing them on every read; caches built by `open` only
+    pub compression_threshold: Option<usize>,
 }
 
 impl Default for GenericCacheConfig {
@@ -21,6 +79,13 @@ impl Default for GenericCacheConfig {
             max_size: 10_000,
             ttl_seconds: None,
             enable_statistics: true,
+            eviction_policy: EvictionPolicy::LRU,
+            shards: DEFAULT_SHARDS,
+            persistence_path: None,
+            flush_interval_seconds: None,
+            sweep_interval_seconds: None,
+            concurrency: CacheConcurrency::Locked,
+            compression_threshold: None,
         }
     }
 }
@@ -36,11 +101,11 @@ pub struct CacheEntry<T> {
 
 impl<T> CacheEntry<T> {
     pub fn new(value: T, ttl_seconds: Option<u64>) -> Self {
-        let now = SystemTime::now()
-            .duration_since(UNIX_EPOCH)
-            .unwrap()
-            .as_secs();
-        
+        Self::new_at(value, ttl_seconds, unix_seconds_now())
+    }
+    
+    /// An entry created at `now`, in seconds since the epoch
+    pub fn new_at(value: T, ttl_seconds: Option<u64>, now: u64) -> Self {
         Self {
             value,
             created_at: now,
@@ -50,15 +115,12 @@ impl<T> CacheEntry<T> {
     }
     
     pub fn is_expired(&self) -> bool {
-        if let Some(expires_at) = self.expires_at {
-            let now = SystemTime::now()
-                .duration_since(UNIX_EPOCH)
-                .unwrap()
-                .as_secs();
-            now > expires_at
-        } else {
-            false
-        }
+        self.is_expired_at(unix_seconds_now())
+    }
+    
+    /// Whether the entry has expired by `now`, in seconds since the epoch
+    pub fn is_expired_at(&self, now: u64) -> bool {
+        self.expires_at.is_some_and(|expires_at| now > expires_at)
     }
     
     pub fn touch(&mut self) {
@@ -66,6 +128,90 @@ impl<T> CacheEntry<T> {
     }
 }
 
+fn unix_seconds_now() -> u64 {
+    unix_nanos_now() / NANOS_PER_SECOND
+}
+
+// Seconds since the epoch by `clock`, or the system clock
+fn clock_seconds(clock: Option<&dyn Clock>) -> u64 {
+    clock.map_or_else(unix_seconds_now, |clock| clock.timestamp_ns() / NANOS_PER_SECOND)
+}
+
+/// Approximate bytes a value occupies, inline and on the heap, for cache
+/// memory accounting
+pub trait MemSize {
+    fn mem_size(&self) -> usize;
+}
+
+macro_rules! inline_mem_size {
+    ($($t:ty),*) => {
+        $(impl MemSize for $t {
+            fn mem_size(&self) -> usize {
+                std::mem::size_of::<Self>()
+            }
+        })*
+    };
+}
+
+inline_mem_size!(bool, char, u8, u16, u32, u64, u128, usize, i8, i16, i32, i64, i128, isize, f32, f64);
+
+impl MemSize for String {
+    fn mem_size(&self) -> usize {
+        std::mem::size_of::<Self>() + self.capacity()
+    }
+}
+
+impl<T: MemSize> MemSize for Option<T> {
+    fn mem_size(&self) -> usize {
+        std::mem::size_of::<Self>() + self.as_ref().map_or(0, |value| value.mem_size() - std::mem::size_of::<T>())
+    }
+}
+
+impl<T: MemSize> MemSize for Vec<T> {
+    fn mem_size(&self) -> usize {
+        let unused = self.capacity() - self.len();
+        std::mem::size_of::<Self>() + unused * std::mem::size_of::<T>() + self.iter().map(MemSize::mem_size).sum::<usize>()
+    }
+}
+
+impl<K: MemSize, V: MemSize> MemSize for HashMap<K, V> {
+    fn mem_size(&self) -> usize {
+        let unused = self.capacity() - self.len();
+        std::mem::size_of::<Self>()
+            + unused * std::mem::size_of::<(K, V)>()
+            + self.iter().map(|(key, value)| key.mem_size() + value.mem_size()).sum::<usize>()
+    }
+}
+
+/// Bookkeeping bytes of an entry besides its key's and value's
+const ENTRY_OVERHEAD: usize = std::mem::size_of::<Slot<()>>()
+    + std::mem::size_of::<String>()
+    + std::mem::size_of::<(Rank, String)>();
+/// Bookkeeping bytes of a concurrent cache's entry besides its key's and value's
+const SHARED_ENTRY_OVERHEAD: usize = std::mem::size_of::<SharedSlot<()>>()
+    + std::mem::size_of::<String>()
+    + std::mem::size_of::<(Rank, String)>();
+
+/// A value as the cache holds it
+#[derive(Debug, Clone)]
+enum Stored<T> {
+    Plain(T),
+    /// LZ4-compressed bincode, shared by clones taken under a lock and
+    /// decompressed once it is released
+    Compressed(Arc<[u8]>),
+}
+
+/// Bincode encoding of values for compression, which the rest of the cache
+/// cannot name without requiring `T: Serialize`
+#[derive(Debug, Clone)]
+struct Codec<T> {
+    threshold: usize,
+    /// Bytes of the encoding, measured without encoding
+    size: fn(&T) -> Result<u64>,
+    encode: fn(&T) -> Result<Vec<u8>>,
+    decode: fn(&[u8]) -> Result<T>,
+}
+
 /// Cache statistics
 #[derive(Debug, Clone, Default)]
 pub struct GenericCacheStatistics {
@@ -87,119 +233,1129 @@ impl GenericCacheStatistics {
     }
 }
 
-/// High-performance generic cache
+/// Position of an entry in eviction order; the smallest is evicted first
+type Rank = (u64, u64);
+
+/// A cached entry with its position in eviction order
+#[derive(Debug)]
+struct Slot<T> {
+    /// Its access count is the count when inserted; reads since are `reads`
+    entry: CacheEntry<T>,
+    /// Key of the entry in `Entries::order`, which reads since leave behind
+    rank: Rank,
+    reads: AtomicU64,
+    last_used: AtomicU64,
+    /// Bytes counted for the entry, key and bookkeeping included
+    size: usize,
+}
+
+impl<T> Slot<T> {
+    fn access_count(&self) -> u64 {
+        self.entry.access_count + self.reads.load(Ordering::Relaxed)
+    }
+
+    /// Position in eviction order counting reads not yet in `rank`, which
+    /// only ever move an entry back
+    fn current_rank(&self, policy: EvictionPolicy) -> Rank {
+        match policy {
+            EvictionPolicy::LRU => (0, self.last_used.load(Ordering::Relaxed)),
+            EvictionPolicy::LFU => (self.access_count(), self.last_used.load(Ordering::Relaxed)),
+            EvictionPolicy::FIFO => self.rank,
+        }
+    }
+}
+
+/// One shard's entries with an index ordering them for eviction
+#[derive(Debug)]
+struct Entries<T> {
+    map: HashMap<String, Slot<T>>,
+    order: BTreeMap<Rank, String>,
+    /// Entries the shard holds before evicting
+    capacity: usize,
+    /// Sum of the entries' sizes
+    bytes: usize,
+    /// Advanced on every insert and read of the shard, ordering them in time
+    clock: AtomicU64,
+}
+
+impl<T> Entries<T> {
+    fn new(capacity: usize) -> Self {
+        Self {
+            map: HashMap::new(),
+            order: BTreeMap::new(),
+            capacity,
+            bytes: 0,
+            clock: AtomicU64::new(0),
+        }
+    }
+
+    fn tick(&self) -> u64 {
+        self.clock.fetch_add(1, Ordering::Relaxed) + 1
+    }
+
+    /// Insert an entry whose value takes `value_size` bytes. An overwrite
+    /// keeps the access count of the entry it replaces.
+    fn insert(&mut self, key: String, mut entry: CacheEntry<T>, value_size: usize, policy: EvictionPolicy) {
+        if let Some(old) = self.map.get(&key) {
+            entry.access_count = entry.access_count.max(old.access_count());
+        }
+        let tick = self.tick();
+        let rank = match policy {
+            EvictionPolicy::LFU => (entry.access_count, tick),
+            EvictionPolicy::LRU | EvictionPolicy::FIFO => (0, tick),
+        };
+        let size = ENTRY_OVERHEAD + key.capacity() + value_size;
+        self.bytes += size;
+        let slot = Slot { entry, rank, reads: AtomicU64::new(0), last_used: AtomicU64::new(tick), size };
+        if let Some(old) = self.map.insert(key.clone(), slot) {
+            self.order.remove(&old.rank);
+            self.bytes -= old.size;
+        }
+        self.order.insert(rank, key);
+    }
+
+    // Record a read of `key`, needing only the shard's read lock
+    fn touch(&self, key: &str) -> Option<&CacheEntry<T>> {
+        let slot = self.map.get(key)?;
+        slot.reads.fetch_add(1, Ordering::Relaxed);
+        slot.last_used.store(self.tick(), Ordering::Relaxed);
+        Some(&slot.entry)
+    }
+
+    fn remove(&mut self, key: &str) -> Option<CacheEntry<T>> {
+        let slot = self.map.remove(key)?;
+        self.order.remove(&slot.rank);
+        self.bytes -= slot.size;
+        Some(slot.entry)
+    }
+
+    /// Remove the entry first in eviction order. Entries read since they
+    /// were ranked are re-ranked as they come up, so the one removed is
+    /// first counting every read.
+    fn evict(&mut self, policy: EvictionPolicy) -> Option<(String, CacheEntry<T>)> {
+        loop {
+            let (rank, key) = self.order.pop_first()?;
+            let slot = self.map.get_mut(&key)?;
+            let current = slot.current_rank(policy);
+            if current != rank {
+                slot.rank = current;
+                self.order.insert(current, key);
+                continue;
+            }
+            let slot = self.map.remove(&key)?;
+            self.bytes -= slot.size;
+            return Some((key, slot.entry));
+        }
+    }
+
+    fn clear(&mut self) {
+        self.map.clear();
+        self.order.clear();
+        self.bytes = 0;
+    }
+}
+
+/// A concurrent cache's entry, its use tracked in atomics so reads need only
+/// a shared lock
+#[derive(Debug)]
+struct SharedSlot<T> {
+    /// Its access count is the count when inserted; reads since are `reads`
+    entry: CacheEntry<T>,
+    /// Key of the entry in `SharedEntries::order`, which reads since leave
+    /// behind
+    filed: Rank,
+    reads: AtomicU64,
+    inserted: u64,
+    last_used: AtomicU64,
+    size: usize,
+}
+
+impl<T> SharedSlot<T> {
+    fn access_count(&self) -> u64 {
+        self.entry.access_count + self.reads.load(Ordering::Relaxed)
+    }
+
+    fn rank(&self, policy: EvictionPolicy) -> Rank {
+        match policy {
+            EvictionPolicy::LRU => (0, self.last_used.load(Ordering::Relaxed)),
+            EvictionPolicy::LFU => (self.access_count(), self.last_used.load(Ordering::Relaxed)),
+            EvictionPolicy::FIFO => (0, self.inserted),
+        }
+    }
+}
+
+/// Entries of a `CacheConcurrency::Concurrent` cache. Reads lock only their
+/// key's `DashMap` shard, for reading; inserts and removals also take the
+/// eviction index, one at a time, so the cache never exceeds its capacity.
+#[derive(Debug)]
+struct SharedEntries<T> {
+    map: DashMap<String, SharedSlot<T>, ahash::RandomState>,
+    /// Keys by rank when last filed, which trails their reads. Taken before
+    /// any lock of `map`.
+    order: Mutex<BTreeMap<Rank, String>>,
+    capacity: usize,
+    bytes: AtomicUsize,
+    clock: AtomicU64,
+}
+
+impl<T: Clone> SharedEntries<T> {
+    fn new(capacity: usize) -> Self {
+        Self {
+            map: DashMap::with_hasher(ahash::RandomState::new()),
+            order: Mutex::new(BTreeMap::new()),
+            capacity,
+            bytes: AtomicUsize::new(0),
+            clock: AtomicU64::new(0),
+        }
+    }
+
+    fn tick(&self) -> u64 {
+        self.clock.fetch_add(1, Ordering::Relaxed) + 1
+    }
+
+    fn lookup(&self, key: &str, now: u64, counts: &mut GenericCacheStatistics) -> Option<T> {
+        if let Some(slot) = self.map.get(key) {
+            if !slot.entry.is_expired_at(now) {
+                slot.reads.fetch_add(1, Ordering::Relaxed);
+                slot.last_used.store(self.tick(), Ordering::Relaxed);
+                counts.hits += 1;
+                return Some(slot.entry.value.clone());
+            }
+        }
+        counts.misses += 1;
+        let mut order = self.order.lock().unwrap();
+        if let Some((_, slot)) = self.map.remove_if(key, |_, slot| slot.entry.is_expired_at(now)) {
+            order.remove(&slot.filed);
+            self.release(&slot);
+            counts.evictions += 1;
+        }
+        None
+    }
+
+    fn insert(&self, key: String, mut entry: CacheEntry<T>, value_size: usize, policy: EvictionPolicy, counts: &mut GenericCacheStatistics) {
+        let mut order = self.order.lock().unwrap();
+        // Make room for a new key, evicting in policy order
+        while !self.map.contains_key(&key) && !self.map.is_empty() && self.map.len() >= self.capacity {
+            if self.evict(&mut order, policy) {
+                counts.evictions += 1;
+            }
+        }
+        // An overwrite keeps the access count of the entry it replaces
+        if let Some(old) = self.map.get(&key) {
+            entry.access_count = entry.access_count.max(old.access_count());
+        }
+        let tick = self.tick();
+        let size = SHARED_ENTRY_OVERHEAD + key.capacity() + value_size;
+        self.bytes.fetch_add(size, Ordering::Relaxed);
+        let mut slot = SharedSlot { entry, filed: (0, 0), reads: AtomicU64::new(0), inserted: tick, last_used: AtomicU64::new(tick), size };
+        slot.filed = slot.rank(policy);
+        order.insert(slot.filed, key.clone());
+        match self.map.insert(key, slot) {
+            Some(old) => {
+                order.remove(&old.filed);
+                self.release(&old);
+            }
+            None => counts.inserts += 1,
+        }
+    }
+
+    // Remove the entry first in eviction order, re-filing entries read
+    // since they were filed as they come up
+    fn evict(&self, order: &mut BTreeMap<Rank, String>, policy: EvictionPolicy) -> bool {
+        while let Some((filed, key)) = order.pop_first() {
+            let Some(mut slot) = self.map.get_mut(&key) else {
+                continue;
+            };
+            let rank = slot.rank(policy);
+            if rank != filed {
+                slot.filed = rank;
+                drop(slot);
+                order.insert(rank, key);
+                continue;
+            }
+            drop(slot);
+            if let Some((_, slot)) = self.map.remove(&key) {
+                self.release(&slot);
+                return true;
+            }
+        }
+        false
+    }
+
+    fn remove(&self, key: &str) -> bool {
+        self.remove_if(key, |_| true)
+    }
+
+    // Remove `key` if its value is `wanted`
+    fn remove_if(&self, key: &str, wanted: impl Fn(&T) -> bool) -> bool {
+        let mut order = self.order.lock().unwrap();
+        let removed = self.map.remove_if(key, |_, slot| wanted(&slot.entry.value));
+        if let Some((_, slot)) = &removed {
+            order.remove(&slot.filed);
+            self.release(slot);
+        }
+        removed.is_some()
+    }
+
+    // Give back the bytes of a removed slot
+    fn release(&self, slot: &SharedSlot<T>) {
+        self.bytes.fetch_sub(slot.size, Ordering::Relaxed);
+    }
+
+    fn purge_expired(&self, now: u64) -> usize {
+        let mut order = self.order.lock().unwrap();
+        let mut purged = 0;
+        self.map.retain(|_, slot| {
+            let expired = slot.entry.is_expired_at(now);
+            if expired {
+                order.remove(&slot.filed);
+                self.release(slot);
+                purged += 1;
+            }
+            !expired
+        });
+        purged
+    }
+
+    fn clear(&self) {
+        let mut order = self.order.lock().unwrap();
+        self.map.clear();
+        order.clear();
+        self.bytes.store(0, Ordering::Relaxed);
+    }
+
+    fn live_entries(&self, now: u64, wanted: impl Fn(&str) -> bool) -> Vec<(String, T)> {
+        self.map
+            .iter()
+            .filter(|slot| wanted(slot.key()) && !slot.entry.is_expired_at(now))
+            .map(|slot| (slot.key().clone(), slot.entry.value.clone()))
+            .collect()
+    }
+}
+
+/// An entry as saved in a snapshot
+#[derive(Serialize, Deserialize)]
+struct PersistedEntry<T> {
+    key: String,
+    value: T,
+    created_at: u64,
+    expires_at: Option<u64>,
+    access_count: u64,
+}
+
+/// Snapshot state of a cache opened with a persistence path
+#[derive(Debug)]
+struct Persistence<T> {
+    /// `GenericCache::save_to_disk` for `T`, which the rest of the cache
+    /// cannot name without requiring `T: Serialize`
+    save: fn(&GenericCache<T>) -> Result<usize>,
+    /// `GenericCache::start_flusher` for `T`, restarted by `with_clock`
+    start_flusher: fn(&GenericCache<T>, &Persistence<T>) -> Option<mpsc::Sender<()>>,
+    /// Whether entries changed since the last save
+    dirty: Arc<AtomicBool>,
+    /// Held while writing a snapshot, so the flusher and other savers take
+    /// turns with the snapshot file
+    saving: Arc<Mutex<()>>,
+    /// Stops the flusher thread when dropped
+    flusher: Option<mpsc::Sender<()>>,
+}
+
+// A value the cache holds, decompressed; None if it cannot be
+fn decode<T>(codec: Option<&Codec<T>>, stored: Stored<T>) -> Option<T> {
+    let bytes = match stored {
+        Stored::Plain(value) => return Some(value),
+        Stored::Compressed(bytes) => bytes,
+    };
+    let codec = codec?;
+    let decoded = lz4_flex::decompress_size_prepended(&bytes)
+        .map_err(AlphaForgeError::from)
+        .and_then(|encoded| (codec.decode)(&encoded));
+    decoded.inspect_err(|e| tracing::warn!("Failed to decompress cache value: {}", e)).ok()
+}
+
+// Write the unexpired entries to `path` in eviction order, returning how many
+// were written. The snapshot is written to a temporary file, synced and then
+// renamed over the previous one, so a crash leaves one or the other whole.
+fn write_snapshot<T: Clone + Serialize>(
+    shards: &[RwLock<Entries<Stored<T>>>],
+    shared: Option<&SharedEntries<Stored<T>>>,
+    policy: EvictionPolicy,
+    codec: Option<&Codec<T>>,
+    path: &Path,
+    now: u64,
+) -> Result<usize> {
+    // Ranks are ordered within a shard, each of which is saved in eviction
+    // order, which is all loading into the same shards keeps
+    let mut ranked = Vec::new();
+    for (index, shard) in shards.iter().enumerate() {
+        let data = shard.read().unwrap();
+        ranked.extend(data.map.iter().filter(|(_, slot)| !slot.entry.is_expired_at(now)).map(|(key, slot)| {
+            let entry = PersistedEntry {
+                key: key.clone(),
+                value: slot.entry.value.clone(),
+                created_at: slot.entry.created_at,
+                expires_at: slot.entry.expires_at,
+                access_count: slot.access_count(),
+            };
+            ((index, slot.current_rank(policy)), entry)
+        }));
+    }
+    if let Some(shared) = shared {
+        ranked.extend(shared.map.iter().filter(|slot| !slot.entry.is_expired_at(now)).map(|slot| {
+            let entry = PersistedEntry {
+                key: slot.key().clone(),
+                value: slot.entry.value.clone(),
+                created_at: slot.entry.created_at,
+                expires_at: slot.entry.expires_at,
+                access_count: slot.access_count(),
+            };
+            ((0, slot.rank(policy)), entry)
+        }));
+    }
+    ranked.sort_by_key(|(rank, _)| *rank);
+    let entries: Vec<PersistedEntry<T>> = ranked
+        .into_iter()
+        .filter_map(|(_, entry)| {
+            Some(PersistedEntry {
+                value: decode(codec, entry.value)?,
+                key: entry.key,
+                created_at: entry.created_at,
+                expires_at: entry.expires_at,
+                access_count: entry.access_count,
+            })
+        })
+        .collect();
+    let parent = path.parent().filter(|parent| !parent.as_os_str().is_empty());
+    if let Some(parent) = parent {
+        std::fs::create_dir_all(parent)?;
+    }
+    let partial = path.with_extension("partial");
+    let mut writer = BufWriter::new(File::create(&partial)?);
+    bincode::serialize_into(&mut writer, &entries)?;
+    writer.into_inner().map_err(|e| e.into_error())?.sync_all()?;
+    std::fs::rename(&partial, path)?;
+    // Persist the rename itself
+    #[cfg(unix)]
+    File::open(parent.unwrap_or(Path::new(".")))?.sync_all()?;
+    Ok(entries.len())
+}
+
+// Remove the entries of `shards` expired by `now`, returning how many there
+// were
+fn purge_expired<T>(shards: &[RwLock<Entries<T>>], now: u64) -> usize {
+    shards
+        .iter()
+        .map(|shard| {
+            let mut data = shard.write().unwrap();
+            let expired: Vec<String> = data
+                .map
+                .iter()
+                .filter(|(_, slot)| slot.entry.is_expired_at(now))
+                .map(|(key, _)| key.clone())
+                .collect();
+            for key in &expired {
+                data.remove(key);
+            }
+            expired.len()
+        })
+        .sum()
+}
+
+/// What a sweeper holds of its cache, which it does not keep alive
+struct SweepTarget<T> {
+    shards: Weak<[RwLock<Entries<Stored<T>>>]>,
+    shared: Option<Weak<SharedEntries<Stored<T>>>>,
+    stats: Arc<StatCounters>,
+    enable_statistics: bool,
+}
+
+impl<T: Clone> SweepTarget<T> {
+    /// Purge the entries expired by `now`, returning false once the cache
+    /// is dropped
+    fn sweep(&self, now: u64) -> bool {
+        let Some(shards) = self.shards.upgrade() else {
+            return false;
+        };
+        let shared = self.shared.as_ref().and_then(Weak::upgrade);
+        let purged = purge_expired(&shards, now) + shared.map_or(0, |shared| shared.purge_expired(now));
+        if purged > 0 {
+            if self.enable_statistics {
+                self.stats.evictions.fetch_add(purged as u64, Ordering::Relaxed);
+            }
+            tracing::debug!("Swept {} expired cache entries", purged);
+        }
+        true
+    }
+}
+
+/// Runs a cache's sweeps until dropped
+enum Sweeper {
+    /// A thread sweeping by the system clock, stopped through the sender
+    Thread(mpsc::Sender<()>),
+    /// A timer of the cache's clock
+    Timer { clock: Arc<dyn Clock>, name: String },
+}
+
+impl std::fmt::Debug for Sweeper {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Thread(_) => f.write_str("Thread"),
+            Self::Timer { name, .. } => f.debug_struct("Timer").field("name", name).finish_non_exhaustive(),
+        }
+    }
+}
+
+impl Drop for Sweeper {
+    fn drop(&mut self) {
+        match self {
+            Self::Thread(stop) => {
+                let _ = stop.send(());
+            }
+            Self::Timer { clock, name } => {
+                if let Err(e) = clock.cancel_timer(name.clone()) {
+                    tracing::warn!("Failed to cancel cache sweeper: {}", e);
+                }
+            }
+        }
+    }
+}
+
+/// Statistics counters, updated without locking
+#[derive(Debug, Default)]
+struct StatCounters {
+    hits: AtomicU64,
+    misses: AtomicU64,
+    inserts: AtomicU64,
+    evictions: AtomicU64,
+}
+
+impl StatCounters {
+    fn add(&self, counts: &GenericCacheStatistics) {
+        for (counter, count) in [
+            (&self.hits, counts.hits),
+            (&self.misses, counts.misses),
+            (&self.inserts, counts.inserts),
+            (&self.evictions, counts.evictions),
+        ] {
+            if count > 0 {
+                counter.fetch_add(count, Ordering::Relaxed);
+            }
+        }
+    }
+
+    fn snapshot(&self) -> GenericCacheStatistics {
+        GenericCacheStatistics {
+            hits: self.hits.load(Ordering::Relaxed),
+            misses: self.misses.load(Ordering::Relaxed),
+            inserts: self.inserts.load(Ordering::Relaxed),
+            evictions: self.evictions.load(Ordering::Relaxed),
+            memory_usage: 0,
+        }
+    }
+
+    fn reset(&self) {
+        for counter in [&self.hits, &self.misses, &self.inserts, &self.evictions] {
+            counter.store(0, Ordering::Relaxed);
+        }
+    }
+}
+
+/// High-performance generic cache. When full, the entry least recently
+/// used (LRU), first inserted (FIFO) or least often read (LFU, ties broken
+/// by recency) makes room, per `GenericCacheConfig::eviction_policy`.
+///
+/// Keys are spread over up to `shards` shards by hash, each with its own
+/// lock, clock and an equal share of `max_size`, and eviction picks from the
+/// shard being written. Eviction order is exact in a single-shard cache and
+/// approximate in a sharded one: the entry evicted is first in its shard,
+/// not necessarily in the cache, and a shard its keys hash to unevenly may
+/// evict while the cache as a whole holds fewer than `max_size` entries.
+/// Caches of fewer than 128 entries have one shard. A concurrent cache
+/// instead keeps its entries in one `DashMap`, with no shards.
 #[derive(Debug)]
 pub struct GenericCache<T> {
     config: GenericCacheConfig,
-    data: Arc<RwLock<HashMap<String, CacheEntry<T>>>>,
-    stats: Arc<RwLock<GenericCacheStatistics>>,
+    shards: Arc<[RwLock<Entries<Stored<T>>>]>,
+    /// Entries of a concurrent cache
+    shared: Option<Arc<SharedEntries<Stored<T>>>>,
+    hasher: ahash::RandomState,
+    stats: Arc<StatCounters>,
+    /// Bytes a value takes, for memory accounting
+    sizer: fn(&T) -> usize,
+    persistence: Option<Persistence<T>>,
+    /// Compresses values from its threshold on
+    codec: Option<Codec<T>>,
+    /// Stops sweeping when dropped
+    sweeper: Option<Sweeper>,
+    /// Tells the time entries are created and expire at, the system's
+    /// unless set by `with_clock`
+    clock: Option<Arc<dyn Clock>>,
+    /// Values being computed by `get_or_insert_with`, shared by the callers
+    /// waiting on them
+    pending: DashMap<String, Arc<OnceLock<T>>, ahash::RandomState>,
 }
 
 impl<T: Clone> GenericCache<T> {
-    pub fn new(config: GenericCacheConfig) -> Self {
+    // An empty cache without compression, which needs `T: Serialize`, or a
+    // sweeper, which needs `T: Send + Sync`
+    fn empty(config: GenericCacheConfig) -> Self {
+        let shared = (config.concurrency == CacheConcurrency::Concurrent).then(|| Arc::new(SharedEntries::new(config.max_size)));
+        let count = match shared {
+            Some(_) => 0,
+            None => config.shards.min(config.max_size / MIN_SHARD_CAPACITY).max(1),
+        };
+        // The remainder of max_size goes to the first shards
+        let shards = (0..count)
+            .map(|index| {
+                let capacity = config.max_size / count + usize::from(index < config.max_size % count);
+                RwLock::new(Entries::new(capacity))
+            })
+            .collect();
         Self {
             config,
-            data: Arc::new(RwLock::new(HashMap::new())),
-            stats: Arc::new(RwLock::new(GenericCacheStatistics::default())),
+            shards,
+            shared,
+            hasher: ahash::RandomState::new(),
+            stats: Arc::new(StatCounters::default()),
+            sizer: |_| std::mem::size_of::<T>(),
+            persistence: None,
+            codec: None,
+            sweeper: None,
+            clock: None,
+            pending: DashMap::with_hasher(ahash::RandomState::new()),
         }
     }
     
-    pub fn get(&self, key: &str) -> Option<T> {
-        let mut data = self.data.write().unwrap();
-        
-        if let Some(entry) = data.get_mut(key) {
-            if entry.is_expired() {
-                data.remove(key);
-                if self.config.enable_statistics {
-                    let mut stats = self.stats.write().unwrap();
-                    stats.misses += 1;
-                    stats.evictions += 1;
+    /// An empty cache; see `open` for one backed by its persistence path or
+    /// compressing values
+    pub fn new(config: GenericCacheConfig) -> Self
+    where
+        T: Send + Sync + 'static,
+    {
+        let mut cache = Self::empty(config);
+        cache.start_sweeper();
+        cache
+    }
+
+    /// Tell the time entries are created and expire at by `clock` rather
+    /// than the system clock, before any are stored. The configured sweeper
+    /// then runs as a timer of `clock`, so a `TestClock` sweeps as it is
+    /// advanced and a `LiveClock` needs a Tokio runtime to sweep.
+    pub fn with_clock(mut self, clock: Arc<dyn Clock>) -> Self
+    where
+        T: Send + Sync + 'static,
+    {
+        self.sweeper = None;
+        self.clock = Some(clock);
+        self.start_sweeper();
+        if let Some(mut persistence) = self.persistence.take() {
+            persistence.flusher = None;
+            persistence.flusher = (persistence.start_flusher)(&self, &persistence);
+            self.persistence = Some(persistence);
+        }
+        self
+    }
+    
+    // Seconds since the epoch by the cache's clock
+    fn now(&self) -> u64 {
+        clock_seconds(self.clock.as_deref())
+    }
+    
+    // Purge expired entries every configured sweep interval until the cache
+    // is dropped
+    fn start_sweeper(&mut self)
+    where
+        T: Send + Sync + 'static,
+    {
+        let Some(interval) = self.config.sweep_interval_seconds.map(|interval| interval.max(1)) else {
+            return;
+        };
+        let target = SweepTarget {
+            shards: Arc::downgrade(&self.shards),
+            shared: self.shared.as_ref().map(Arc::downgrade),
+            stats: Arc::clone(&self.stats),
+            enable_statistics: self.config.enable_statistics,
+        };
+        if let Some(clock) = &self.clock {
+            let name = format!("cache-sweeper-{}", crate::uuid::UUID4::new());
+            let interval_ns = interval * NANOS_PER_SECOND;
+            let sweep = TimeEventSender::Handler(Arc::new(move |event| {
+                target.sweep(event.ts_event / NANOS_PER_SECOND);
+            }));
+            match clock.set_timer(name.clone(), interval_ns, clock.timestamp_ns() + interval_ns, None, sweep) {
+                Ok(()) => self.sweeper = Some(Sweeper::Timer { clock: Arc::clone(clock), name }),
+                Err(e) => tracing::warn!("Failed to start cache sweeper: {}", e),
+            }
+            return;
+        }
+        let (stop, stopped) = mpsc::channel::<()>();
+        let spawned = std::thread::Builder::new().name("cache-sweeper".to_string()).spawn(move || {
+            while let Err(RecvTimeoutError::Timeout) = stopped.recv_timeout(Duration::from_secs(interval)) {
+                if !target.sweep(unix_seconds_now()) {
+                    break;
                 }
-                return None;
             }
-            
-            entry.touch();
-            if self.config.enable_statistics {
-                let mut stats = self.stats.write().unwrap();
-                stats.hits += 1;
+        });
+        match spawned {
+            Ok(_) => self.sweeper = Some(Sweeper::Thread(stop)),
+            Err(e) => tracing::warn!("Failed to start cache sweeper: {}", e),
+        }
+    }
+    
+    /// Remove every expired entry now, returning how many there were
+    pub fn purge_expired(&self) -> usize {
+        let now = self.now();
+        let purged = purge_expired(&self.shards, now) + self.shared.as_ref().map_or(0, |shared| shared.purge_expired(now));
+        self.record(&GenericCacheStatistics { evictions: purged as u64, ..Default::default() });
+        purged
+    }
+    
+    /// Measure values with `sizer` rather than by their inline size, which
+    /// misses heap data they own. Entries already cached are re-measured.
+    pub fn with_sizer(mut self, sizer: fn(&T) -> usize) -> Self {
+        self.sizer = sizer;
+        for shard in self.shards.iter() {
+            let mut data = shard.write().unwrap();
+            let data = &mut *data;
+            data.bytes = 0;
+            for (key, slot) in data.map.iter_mut() {
+                slot.size = ENTRY_OVERHEAD + key.capacity() + self.measure(&slot.entry.value);
+                data.bytes += slot.size;
             }
-            Some(entry.value.clone())
-        } else {
-            if self.config.enable_statistics {
-                let mut stats = self.stats.write().unwrap();
-                stats.misses += 1;
+        }
+        if let Some(shared) = &self.shared {
+            shared.bytes.store(0, Ordering::Relaxed);
+            for mut slot in shared.map.iter_mut() {
+                let size = SHARED_ENTRY_OVERHEAD + slot.key().capacity() + self.measure(&slot.entry.value);
+                slot.size = size;
+                shared.bytes.fetch_add(size, Ordering::Relaxed);
+            }
+        }
+        self
+    }
+
+    fn shard_index(&self, key: &str) -> usize {
+        (self.hasher.hash_one(key) % self.shards.len() as u64) as usize
+    }
+
+    fn shard(&self, key: &str) -> &RwLock<Entries<Stored<T>>> {
+        &self.shards[self.shard_index(key)]
+    }
+
+    // A value as the cache holds it, compressed when its encoding reaches
+    // the threshold and shrinks, with the bytes it takes
+    fn stored(&self, value: T) -> (Stored<T>, usize) {
+        // Measured first, so values below the threshold are never encoded
+        let encoded = self.codec.as_ref().and_then(|codec| {
+            let encoded = (codec.size)(&value).and_then(|size| match size >= codec.threshold as u64 {
+                true => (codec.encode)(&value).map(Some),
+                false => Ok(None),
+            });
+            encoded.unwrap_or_else(|e| {
+                tracing::warn!("Failed to encode cache value for compression: {}", e);
+                None
+            })
+        });
+        let compressed = encoded.map(|encoded| (lz4_flex::compress_prepend_size(&encoded), encoded.len()));
+        match compressed {
+            Some((compressed, encoded_len)) if compressed.len() < encoded_len => {
+                let stored = Stored::Compressed(compressed.into());
+                let size = self.measure(&stored);
+                (stored, size)
+            }
+            _ => {
+                let size = (self.sizer)(&value);
+                (Stored::Plain(value), size)
+            }
+        }
+    }
+
+    fn measure(&self, stored: &Stored<T>) -> usize {
+        match stored {
+            Stored::Plain(value) => (self.sizer)(value),
+            Stored::Compressed(bytes) => std::mem::size_of::<Stored<T>>() + bytes.len(),
+        }
+    }
+
+    // A value the cache holds, decompressed; None if it cannot be
+    fn load(&self, stored: Stored<T>) -> Option<T> {
+        decode(self.codec.as_ref(), stored)
+    }
+
+    // Note a change for the flusher, or the drop, to save
+    fn changed(&self) {
+        if let Some(persistence) = &self.persistence {
+            persistence.dirty.store(true, Ordering::Relaxed);
+        }
+    }
+    
+    pub fn get(&self, key: &str) -> Option<T> {
+        let mut counts = GenericCacheStatistics::default();
+        let stored = match &self.shared {
+            Some(shared) => shared.lookup(key, self.now(), &mut counts),
+            None => {
+                let (shard, now) = (self.shard(key), self.now());
+                let mut expired = Vec::new();
+                let stored = self.lookup(&shard.read().unwrap(), key, now, &mut counts, &mut expired);
+                Self::remove_expired(shard, &expired, now, &mut counts);
+                stored
+            }
+        };
+        let value = self.loaded(key, stored, &mut counts);
+        self.record(&counts);
+        value
+    }
+
+    // A value found for `key`, decompressed. One that cannot be is counted
+    // as a miss rather than a hit and removed, if `key` still holds it.
+    fn loaded(&self, key: &str, stored: Option<Stored<T>>, counts: &mut GenericCacheStatistics) -> Option<T> {
+        let bytes = match stored? {
+            Stored::Plain(value) => return Some(value),
+            Stored::Compressed(bytes) => bytes,
+        };
+        let value = self.load(Stored::Compressed(Arc::clone(&bytes)));
+        if value.is_none() {
+            counts.hits -= 1;
+            counts.misses += 1;
+            let corrupt = |held: &Stored<T>| matches!(held, Stored::Compressed(held) if Arc::ptr_eq(held, &bytes));
+            let removed = match &self.shared {
+                Some(shared) => shared.remove_if(key, corrupt),
+                None => {
+                    let mut data = self.shard(key).write().unwrap();
+                    let held = data.map.get(key).is_some_and(|slot| corrupt(&slot.entry.value));
+                    held && data.remove(key).is_some()
+                }
+            };
+            if removed {
+                counts.evictions += 1;
+                self.changed();
             }
-            None
         }
+        value
+    }
+    
+    /// Look up several keys, locking each shard once, in order
+    pub fn get_many(&self, keys: &[&str]) -> Vec<Option<T>> {
+        let mut counts = GenericCacheStatistics::default();
+        let now = self.now();
+        if let Some(shared) = &self.shared {
+            let stored: Vec<_> = keys.iter().map(|key| shared.lookup(key, now, &mut counts)).collect();
+            let values = keys.iter().zip(stored).map(|(key, stored)| self.loaded(key, stored, &mut counts)).collect();
+            self.record(&counts);
+            return values;
+        }
+        let mut values = vec![None; keys.len()];
+        let mut by_shard = vec![Vec::new(); self.shards.len()];
+        for (position, key) in keys.iter().enumerate() {
+            by_shard[self.shard_index(key)].push(position);
+        }
+        for (shard, positions) in self.shards.iter().zip(by_shard).filter(|(_, positions)| !positions.is_empty()) {
+            let mut expired = Vec::new();
+            let data = shard.read().unwrap();
+            for position in positions {
+                values[position] = self.lookup(&data, keys[position], now, &mut counts, &mut expired);
+            }
+            drop(data);
+            Self::remove_expired(shard, &expired, now, &mut counts);
+        }
+        let values = keys.iter().zip(values).map(|(key, stored)| self.loaded(key, stored, &mut counts)).collect();
+        self.record(&counts);
+        values
     }
     
     pub fn put(&self, key: String, value: T) -> bool {
-        let mut data = self.data.write().unwrap();
+        self.put_with_ttl(key, value, self.config.ttl_seconds)
+    }
+    
+    /// Store an entry expiring after `ttl_seconds` rather than the configured
+    /// TTL, or never when None
+    pub fn put_with_ttl(&self, key: String, value: T, ttl_seconds: Option<u64>) -> bool {
+        let mut counts = GenericCacheStatistics::default();
+        let (stored, value_size) = self.stored(value);
+        match &self.shared {
+            Some(shared) => {
+                shared.insert(key, CacheEntry::new_at(stored, ttl_seconds, self.now()), value_size, self.config.eviction_policy, &mut counts);
+            }
+            None => self.store(&mut self.shard(&key).write().unwrap(), key, stored, value_size, ttl_seconds, &mut counts),
+        }
+        self.record(&counts);
+        self.changed();
         
-        // Check size limit and evict if necessary (simple random eviction for now)
-        while data.len() >= self.config.max_size {
-            if let Some((oldest_key, _)) = data.iter().next() {
-                let oldest_key = oldest_key.clone();
-                data.remove(&oldest_key);
-                if self.config.enable_statistics {
-                    let mut stats = self.stats.write().unwrap();
-                    stats.evictions += 1;
+        true
+    }
+    
+    /// The value cached for `key`, else the one `compute` returns, cached
+    /// with the configured TTL. Racing callers compute a key's value once,
+    /// waiting on whichever computes it; no lock of the cache is held while
+    /// computing, so `compute` may use the cache for other keys.
+    pub fn get_or_insert_with(&self, key: &str, compute: impl FnOnce() -> T) -> T {
+        if let Some(value) = self.get(key) {
+            return value;
+        }
+        // Checked again under the key's pending entry, which the caller that
+        // computed it removes only after storing the value
+        let cell = match self.pending.entry(key.to_string()) {
+            dashmap::Entry::Occupied(pending) => Arc::clone(pending.get()),
+            dashmap::Entry::Vacant(pending) => match self.peek(key) {
+                Some(value) => return value,
+                None => Arc::clone(pending.insert(Arc::default()).value()),
+            },
+        };
+        // Removes the pending entry once the value is stored, or if `compute`
+        // panics, so later callers compute it again
+        struct Pending<'a, T> {
+            pending: &'a DashMap<String, Arc<OnceLock<T>>, ahash::RandomState>,
+            key: &'a str,
+            cell: &'a Arc<OnceLock<T>>,
+            computing: bool,
+        }
+        impl<T> Drop for Pending<'_, T> {
+            fn drop(&mut self) {
+                if self.computing {
+                    self.pending.remove_if(self.key, |_, pending| Arc::ptr_eq(pending, self.cell));
                 }
-            } else {
-                break;
             }
         }
+        let mut pending = Pending { pending: &self.pending, key, cell: &cell, computing: false };
+        let value = cell
+            .get_or_init(|| {
+                pending.computing = true;
+                compute()
+            })
+            .clone();
+        if pending.computing {
+            self.put(key.to_string(), value.clone());
+        }
+        value
+    }
+
+    // The unexpired value of `key`, without counting it as a read
+    fn peek(&self, key: &str) -> Option<T> {
+        let now = self.now();
+        let stored = match &self.shared {
+            Some(shared) => shared.map.get(key).filter(|slot| !slot.entry.is_expired_at(now)).map(|slot| slot.entry.value.clone()),
+            None => {
+                let data = self.shard(key).read().unwrap();
+                data.map.get(key).filter(|slot| !slot.entry.is_expired_at(now)).map(|slot| slot.entry.value.clone())
+            }
+        };
+        stored.and_then(|stored| self.load(stored))
+    }
+    
+    /// Store several entries, locking each shard once, returning how many
+    /// were stored. Later entries for a key replace earlier ones.
+    pub fn put_many(&self, entries: Vec<(String, T)>) -> usize {
+        let mut counts = GenericCacheStatistics::default();
+        let stored = entries.len();
+        if let Some(shared) = &self.shared {
+            for (key, value) in entries {
+                let (stored, value_size) = self.stored(value);
+                let entry = CacheEntry::new_at(stored, self.config.ttl_seconds, self.now());
+                shared.insert(key, entry, value_size, self.config.eviction_policy, &mut counts);
+            }
+        } else {
+            self.store_by_shard(entries, &mut counts);
+        }
+        self.record(&counts);
+        if stored > 0 {
+            self.changed();
+        }
+        stored
+    }
+    
+    // Store entries in a sharded cache, locking each shard once
+    fn store_by_shard(&self, entries: Vec<(String, T)>, counts: &mut GenericCacheStatistics) {
+        let mut by_shard: Vec<Vec<(String, Stored<T>, usize)>> = (0..self.shards.len()).map(|_| Vec::new()).collect();
+        for (key, value) in entries {
+            let (stored, value_size) = self.stored(value);
+            by_shard[self.shard_index(&key)].push((key, stored, value_size));
+        }
+        for (shard, entries) in self.shards.iter().zip(by_shard).filter(|(_, entries)| !entries.is_empty()) {
+            let mut data = shard.write().unwrap();
+            for (key, stored, value_size) in entries {
+                self.store(&mut data, key, stored, value_size, self.config.ttl_seconds, counts);
+            }
+        }
+    }
+    
+    // Read `key` from entries locked for reading, counting the outcome and
+    // noting it in `expired` if it has expired
+    fn lookup<'k>(
+        &self,
+        data: &Entries<Stored<T>>,
+        key: &'k str,
+        now: u64,
+        counts: &mut GenericCacheStatistics,
+        expired: &mut Vec<&'k str>,
+    ) -> Option<Stored<T>> {
+        if data.map.get(key).is_some_and(|slot| slot.entry.is_expired_at(now)) {
+            expired.push(key);
+            counts.misses += 1;
+            return None;
+        }
         
-        let was_new = !data.contains_key(&key);
-        let entry = CacheEntry::new(value, self.config.ttl_seconds);
-        data.insert(key, entry);
+        match data.touch(key) {
+            Some(entry) => {
+                counts.hits += 1;
+                Some(entry.value.clone())
+            }
+            None => {
+                counts.misses += 1;
+                None
+            }
+        }
+    }
+
+    // Remove the entries a lookup found expired, unless replaced since
+    fn remove_expired(shard: &RwLock<Entries<Stored<T>>>, keys: &[&str], now: u64, counts: &mut GenericCacheStatistics) {
+        if keys.is_empty() {
+            return;
+        }
+        let mut data = shard.write().unwrap();
+        for key in keys {
+            if data.map.get(*key).is_some_and(|slot| slot.entry.is_expired_at(now)) {
+                data.remove(key);
+                counts.evictions += 1;
+            }
+        }
+    }
+    
+    // Write `key` to the locked entries, counting inserts and evictions
+    fn store(
+        &self,
+        data: &mut Entries<Stored<T>>,
+        key: String,
+        value: Stored<T>,
+        value_size: usize,
+        ttl_seconds: Option<u64>,
+        counts: &mut GenericCacheStatistics,
+    ) {
+        let was_new = !data.map.contains_key(&key);
         
-        if self.config.enable_statistics && was_new {
-            let mut stats = self.stats.write().unwrap();
-            stats.inserts += 1;
+        // Make room for a new key, evicting in policy order
+        while was_new && !data.map.is_empty() && data.map.len() >= data.capacity {
+            if data.evict(self.config.eviction_policy).is_some() {
+                counts.evictions += 1;
+            }
         }
         
-        true
+        let entry = CacheEntry::new_at(value, ttl_seconds, self.now());
+        data.insert(key, entry, value_size, self.config.eviction_policy);
+        if was_new {
+            counts.inserts += 1;
+        }
+    }
+    
+    // Add counts gathered under a shard lock to the statistics
+    fn record(&self, counts: &GenericCacheStatistics) {
+        if self.config.enable_statistics {
+            self.stats.add(counts);
+        }
     }
     
     pub fn contains(&self, key: &str) -> bool {
-        let data = self.data.read().unwrap();
-        if let Some(entry) = data.get(key) {
-            !entry.is_expired()
+        if let Some(shared) = &self.shared {
+            return shared.map.get(key).is_some_and(|slot| !slot.entry.is_expired_at(self.now()));
+        }
+        let data = self.shard(key).read().unwrap();
+        if let Some(slot) = data.map.get(key) {
+            !slot.entry.is_expired_at(self.now())
         } else {
             false
         }
     }
     
     pub fn remove(&self, key: &str) -> bool {
-        let mut data = self.data.write().unwrap();
-        data.remove(key).is_some()
+        let removed = match &self.shared {
+            Some(shared) => shared.remove(key),
+            None => self.shard(key).write().unwrap().remove(key).is_some(),
+        };
+        if removed {
+            self.changed();
+        }
+        removed
     }
     
     pub fn clear(&self) {
-        let mut data = self.data.write().unwrap();
-        data.clear();
+        for shard in self.shards.iter() {
+            shard.write().unwrap().clear();
+        }
+        if let Some(shared) = &self.shared {
+            shared.clear();
+        }
         
         if self.config.enable_statistics {
-            let mut stats = self.stats.write().unwrap();
-            *stats = GenericCacheStatistics::default();
+            self.stats.reset();
         }
+        self.changed();
     }
     
     pub fn size(&self) -> usize {
-        let data = self.data.read().unwrap();
-        data.len()
+        let shared = self.shared.as_ref().map_or(0, |shared| shared.map.len());
+        shared + self.shards.iter().map(|shard| shard.read().unwrap().map.len()).sum::<usize>()
     }
     
     pub fn keys(&self) -> Vec<String> {
-        let data = self.data.read().unwrap();
-        data.keys().cloned().collect()
+        self.shards
+            .iter()
+            .flat_map(|shard| shard.read().unwrap().map.keys().cloned().collect::<Vec<_>>())
+            .chain(self.shared.iter().flat_map(|shared| shared.map.iter().map(|slot| slot.key().clone()).collect::<Vec<_>>()))
+            .collect()
+    }
+    
+    /// Unexpired entries whose keys start with `prefix`, sorted by key.
+    /// Every entry is visited, copying one shard at a time as `iter` does.
+    /// Scanning neither counts as access nor updates statistics.
+    pub fn scan(&self, prefix: &str) -> Vec<(String, T)> {
+        let now = self.now();
+        let mut entries: Vec<(String, T)> = self
+            .shards
+            .iter()
+            .flat_map(|shard| Self::live_entries(shard, now, |key| key.starts_with(prefix)))
+            .chain(self.shared.iter().flat_map(|shared| shared.live_entries(now, |key| key.starts_with(prefix))))
+            .filter_map(|(key, stored)| Some((key, self.load(stored)?)))
+            .collect();
+        entries.sort_by(|a, b| a.0.cmp(&b.0));
+        entries
+    }
+    
+    /// Iterate over unexpired entries in no particular order, copying one
+    /// shard at a time so writers to other shards are not held up. Entries
+    /// written to a shard after it was copied may be missed.
+    pub fn iter(&self) -> impl Iterator<Item = (String, T)> + '_ {
+        let now = self.now();
+        self.shards
+            .iter()
+            .flat_map(move |shard| Self::live_entries(shard, now, |_| true))
+            .chain(self.shared.iter().flat_map(move |shared| shared.live_entries(now, |_| true)))
+            .filter_map(|(key, stored)| Some((key, self.load(stored)?)))
+    }
+    
+    // Copies of the unexpired entries of a shard, decompressed by the caller
+    // once it is unlocked
+    fn live_entries(shard: &RwLock<Entries<Stored<T>>>, now: u64, wanted: impl Fn(&str) -> bool) -> Vec<(String, Stored<T>)> {
+        shard
+            .read()
+            .unwrap()
+            .map
+            .iter()
+            .filter(|(key, slot)| wanted(key) && !slot.entry.is_expired_at(now))
+            .map(|(key, slot)| (key.clone(), slot.entry.value.clone()))
+            .collect()
+    }
+    
+    /// Approximate bytes held by entries, keys and their bookkeeping, with
+    /// values measured as set by `with_sizer`
+    pub fn estimated_memory_usage(&self) -> usize {
+        let shared = self.shared.as_ref().map_or(0, |shared| shared.bytes.load(Ordering::Relaxed));
+        shared + self.shards.iter().map(|shard| shard.read().unwrap().bytes).sum::<usize>()
     }
     
     pub fn statistics(&self) -> Option<GenericCacheStatistics> {
         if self.config.enable_statistics {
-            let stats = self.stats.read().unwrap();
-            Some(stats.clone())
+            let mut stats = self.stats.snapshot();
+            stats.memory_usage = self.estimated_memory_usage();
+            Some(stats)
         } else {
             None
         }
@@ -207,8 +1363,560 @@ impl<T: Clone> GenericCache<T> {
     
     pub fn reset_statistics(&self) {
         if self.config.enable_statistics {
-            let mut stats = self.stats.write().unwrap();
-            *stats = GenericCacheStatistics::default();
+            self.stats.reset();
+        }
+    }
+}
+
+impl<T: Clone + MemSize> GenericCache<T> {
+    /// Measure values by their `MemSize`
+    pub fn with_mem_size(self) -> Self {
+        self.with_sizer(T::mem_size)
+    }
+}
+
+impl<T: Clone + Serialize + DeserializeOwned> GenericCache<T> {
+    /// A cache holding the entries saved at the configured persistence path,
+    /// if any, that saves back to it, compressing values if configured to
+    pub fn open(config: GenericCacheConfig) -> Result<Self>
+    where
+        T: Send + Sync + 'static,
+    {
+        let mut cache = Self::empty(config);
+        cache.codec = cache.config.compression_threshold.map(|threshold| Codec {
+            threshold,
+            size: |value| Ok(bincode::serialized_size(value)?),
+            encode: |value| Ok(bincode::serialize(value)?),
+            decode: |bytes| Ok(bincode::deserialize(bytes)?),
+        });
+        cache.start_sweeper();
+        if let Some(path) = &cache.config.persistence_path {
+            if path.exists() {
+                let loaded = cache.load_from_disk()?;
+                tracing::info!("Loaded {} cache entries from {}", loaded, path.display());
+            }
+            let mut persistence = Persistence {
+                save: Self::save_to_disk,
+                start_flusher: Self::start_flusher,
+                dirty: Arc::default(),
+                saving: Arc::default(),
+                flusher: None,
+            };
+            persistence.flusher = cache.start_flusher(&persistence);
+            cache.persistence = Some(persistence);
         }
+        Ok(cache)
+    }
+
+    // Save changes every configured flush interval until the cache is
+    // dropped, returning the sender that stops it
+    fn start_flusher(&self, persistence: &Persistence<T>) -> Option<mpsc::Sender<()>>
+    where
+        T: Send + Sync + 'static,
+    {
+        let interval = Duration::from_secs(self.config.flush_interval_seconds?).max(MIN_FLUSH_INTERVAL);
+        let (stop, stopped) = mpsc::channel::<()>();
+        let shards = Arc::downgrade(&self.shards);
+        let shared = self.shared.as_ref().map(Arc::downgrade);
+        let (dirty, saving) = (Arc::clone(&persistence.dirty), Arc::clone(&persistence.saving));
+        let (policy, codec, path) = (self.config.eviction_policy, self.codec.clone(), self.persistence_path().ok()?.clone());
+        let clock = self.clock.clone();
+        let spawned = std::thread::Builder::new().name("cache-flusher".to_string()).spawn(move || {
+            while let Err(RecvTimeoutError::Timeout) = stopped.recv_timeout(interval) {
+                let Some(shards) = shards.upgrade() else {
+                    break;
+                };
+                let _saving = saving.lock().unwrap();
+                if !dirty.swap(false, Ordering::Relaxed) {
+                    continue;
+                }
+                let shared = shared.as_ref().and_then(Weak::upgrade);
+                let now = clock_seconds(clock.as_deref());
+                if let Err(e) = write_snapshot(&shards, shared.as_deref(), policy, codec.as_ref(), &path, now) {
+                    dirty.store(true, Ordering::Relaxed);
+                    tracing::warn!("Failed to save cache snapshot: {}", e);
+                }
+            }
+        });
+        match spawned {
+            Ok(_) => Some(stop),
+            Err(e) => {
+                tracing::warn!("Failed to start cache flusher: {}", e);
+                None
+            }
+        }
+    }
+
+    fn persistence_path(&self) -> Result<&PathBuf> {
+        self.config
+            .persistence_path
+            .as_ref()
+            .ok_or_else(|| AlphaForgeError::config("Cache has no persistence path"))
+    }
+
+    /// Write the unexpired entries to the persistence path, returning how
+    /// many were written. The previous snapshot is replaced only once the
+    /// new one is complete and synced to disk.
+    pub fn save_to_disk(&self) -> Result<usize> {
+        let path = self.persistence_path()?;
+        let _saving = self.persistence.as_ref().map(|persistence| persistence.saving.lock().unwrap());
+        if let Some(persistence) = &self.persistence {
+            persistence.dirty.store(false, Ordering::Relaxed);
+        }
+        let saved = write_snapshot(&self.shards, self.shared.as_deref(), self.config.eviction_policy, self.codec.as_ref(), path, self.now());
+        if let (Err(_), Some(persistence)) = (&saved, &self.persistence) {
+            persistence.dirty.store(true, Ordering::Relaxed);
+        }
+        saved
+    }
+
+    /// Add the unexpired entries saved at the persistence path, returning
+    /// how many were added. They keep their creation times, expiries and
+    /// access counts, and are evicted in the order they were saved in.
+    pub fn load_from_disk(&self) -> Result<usize> {
+        let reader = BufReader::new(File::open(self.persistence_path()?)?);
+        let entries: Vec<PersistedEntry<T>> = bincode::deserialize_from(reader)?;
+        let mut loaded = 0;
+        for persisted in entries {
+            let (value, value_size) = self.stored(persisted.value);
+            let entry = CacheEntry {
+                value,
+                created_at: persisted.created_at,
+                expires_at: persisted.expires_at,
+                access_count: persisted.access_count,
+            };
+            if entry.is_expired_at(self.now()) {
+                continue;
+            }
+            if let Some(shared) = &self.shared {
+                shared.insert(persisted.key, entry, value_size, self.config.eviction_policy, &mut GenericCacheStatistics::default());
+                loaded += 1;
+                continue;
+            }
+            let mut data = self.shard(&persisted.key).write().unwrap();
+            if !data.map.contains_key(&persisted.key) && data.map.len() >= data.capacity {
+                data.evict(self.config.eviction_policy);
+            }
+            data.insert(persisted.key, entry, value_size, self.config.eviction_policy);
+            loaded += 1;
+        }
+        Ok(loaded)
+    }
+}
+
+impl<T> Drop for GenericCache<T> {
+    fn drop(&mut self) {
+        if let Some(persistence) = &self.persistence {
+            if persistence.dirty.load(Ordering::Relaxed) {
+                if let Err(e) = (persistence.save)(self) {
+                    tracing::warn!("Failed to save cache snapshot: {}", e);
+                }
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::clock::TestClock;
+    use std::time::Instant;
+
+    fn cache(max_size: usize, eviction_policy: EvictionPolicy) -> GenericCache<u32> {
+        GenericCache::new(GenericCacheConfig {
+            max_size,
+            eviction_policy,
+            ..GenericCacheConfig::default()
+        })
+    }
+
+    fn sorted_keys(cache: &GenericCache<u32>) -> Vec<String> {
+        let mut keys = cache.keys();
+        keys.sort();
+        keys
+    }
+
+    #[test]
+    fn test_eviction_policies() {
+        // LRU: reading "a" makes "b" the least recently used
+        let lru = cache(2, EvictionPolicy::LRU);
+        lru.put("a".to_string(), 1);
+        lru.put("b".to_string(), 2);
+        assert_eq!(lru.get("a"), Some(1));
+        lru.put("c".to_string(), 3);
+        assert_eq!(sorted_keys(&lru), vec!["a", "c"]);
+        // Overwriting a key never evicts
+        lru.put("a".to_string(), 10);
+        assert_eq!(sorted_keys(&lru), vec!["a", "c"]);
+        assert_eq!(lru.statistics().unwrap().evictions, 1);
+
+        // FIFO: reads do not matter
+        let fifo = cache(2, EvictionPolicy::FIFO);
+        fifo.put("a".to_string(), 1);
+        fifo.put("b".to_string(), 2);
+        assert_eq!(fifo.get("a"), Some(1));
+        fifo.put("c".to_string(), 3);
+        assert_eq!(sorted_keys(&fifo), vec!["b", "c"]);
+
+        // LFU: the least read goes, the least recent of equally read ones
+        let lfu = cache(3, EvictionPolicy::LFU);
+        for key in ["a", "b", "c"] {
+            lfu.put(key.to_string(), 0);
+        }
+        for key in ["a", "a", "b", "c", "b"] {
+            lfu.get(key);
+        }
+        lfu.put("d".to_string(), 0);
+        assert_eq!(sorted_keys(&lfu), vec!["a", "b", "d"]);
+        lfu.put("e".to_string(), 0);
+        assert_eq!(sorted_keys(&lfu), vec!["a", "b", "e"]);
+    }
+
+    #[test]
+    fn test_lfu_overwrite_keeps_frequency() {
+        for concurrency in [CacheConcurrency::Locked, CacheConcurrency::Concurrent] {
+            let lfu = GenericCache::new(GenericCacheConfig {
+                max_size: 2,
+                eviction_policy: EvictionPolicy::LFU,
+                concurrency,
+                ..GenericCacheConfig::default()
+            });
+            lfu.put("a".to_string(), 1);
+            lfu.put("b".to_string(), 2);
+            for key in ["a", "a", "b"] {
+                lfu.get(key);
+            }
+            // Updating the often-read "a" does not make it the least read
+            lfu.put("a".to_string(), 10);
+            lfu.put("c".to_string(), 3);
+            assert_eq!(sorted_keys(&lfu), vec!["a", "c"], "{:?}", concurrency);
+            assert_eq!(lfu.get("a"), Some(10));
+        }
+    }
+
+    #[test]
+    fn test_reads_take_the_read_lock() {
+        let cache = cache(2, EvictionPolicy::LRU);
+        cache.put("a".to_string(), 1);
+        cache.put("b".to_string(), 2);
+        // Would deadlock waiting for the write lock
+        let guard = cache.shards[0].read().unwrap();
+        assert_eq!(cache.get("a"), Some(1));
+        assert_eq!(cache.get_many(&["b", "c"]), vec![Some(2), None]);
+        drop(guard);
+
+        // Reads made under the read lock still order eviction: "a" was read
+        // before "b"
+        cache.put("c".to_string(), 3);
+        assert_eq!(sorted_keys(&cache), vec!["b", "c"]);
+    }
+
+    #[test]
+    fn test_snapshots_restore_entries() {
+        let dir = std::env::temp_dir().join(format!("alphaforge-cache-{}", crate::uuid::UUID4::new()));
+        let config = GenericCacheConfig {
+            max_size: 3,
+            persistence_path: Some(dir.join("cache.bin")),
+            ..GenericCacheConfig::default()
+        };
+
+        let cache = GenericCache::open(config.clone()).unwrap();
+        for (key, value) in [("a", 1u32), ("b", 2), ("c", 3)] {
+            cache.put(key.to_string(), value);
+        }
+        cache.get("a");
+        // Saved on drop
+        drop(cache);
+
+        let restored = GenericCache::<u32>::open(config.clone()).unwrap();
+        assert_eq!(restored.size(), 3);
+        assert_eq!(restored.get("b"), Some(2));
+        // Eviction order survives: "c" is now the least recently used
+        restored.put("d".to_string(), 4);
+        assert!(!restored.contains("c"));
+        assert_eq!(restored.save_to_disk().unwrap(), 3);
+
+        // Changes are saved in the background every flush interval
+        let flushing = GenericCache::<u32>::open(GenericCacheConfig { flush_interval_seconds: Some(0), ..config.clone() }).unwrap();
+        flushing.remove("a");
+        let deadline = Instant::now() + Duration::from_secs(5);
+        while GenericCache::<u32>::new(config.clone()).load_from_disk().unwrap() != 2 {
+            assert!(Instant::now() < deadline, "changes were not saved");
+            std::thread::sleep(Duration::from_millis(1));
+        }
+        assert!(!config.persistence_path.as_ref().unwrap().with_extension("partial").exists());
+        drop((restored, flushing));
+
+        assert!(GenericCache::<u32>::new(GenericCacheConfig::default()).save_to_disk().is_err());
+        std::fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[test]
+    fn test_batch_get_and_put() {
+        let cache = cache(3, EvictionPolicy::LRU);
+        assert_eq!(cache.put_many(vec![("a".to_string(), 1), ("b".to_string(), 2), ("a".to_string(), 3)]), 3);
+        assert_eq!(cache.get_many(&["a", "b", "c"]), vec![Some(3), Some(2), None]);
+
+        // Evicts as individual puts would: "a" was read before "b"
+        cache.put_many(vec![("c".to_string(), 4), ("d".to_string(), 5)]);
+        assert_eq!(sorted_keys(&cache), vec!["b", "c", "d"]);
+
+        let stats = cache.statistics().unwrap();
+        assert_eq!((stats.hits, stats.misses, stats.inserts, stats.evictions), (2, 1, 4, 1));
+    }
+
+    #[test]
+    fn test_sharded_cache_under_concurrent_access() {
+        let cache = Arc::new(GenericCache::new(GenericCacheConfig { max_size: 1000, ..Default::default() }));
+        assert_eq!(cache.shards.len(), 1000 / MIN_SHARD_CAPACITY);
+        assert_eq!(cache.shards.iter().map(|shard| shard.read().unwrap().capacity).sum::<usize>(), 1000);
+
+        let handles: Vec<_> = (0..4)
+            .map(|thread| {
+                let cache = Arc::clone(&cache);
+                std::thread::spawn(move || {
+                    for i in 0..500 {
+                        let key = format!("{}-{}", thread, i);
+                        cache.put(key.clone(), i);
+                        // Other threads may have evicted it since
+                        assert!(cache.get(&key).is_none_or(|value| value == i));
+                    }
+                })
+            })
+            .collect();
+        for handle in handles {
+            handle.join().unwrap();
+        }
+
+        assert!(cache.size() <= 1000);
+        let stats = cache.statistics().unwrap();
+        assert_eq!((stats.hits + stats.misses, stats.inserts), (2000, 2000));
+        assert_eq!(stats.evictions as usize, 2000 - cache.size());
+    }
+
+    #[test]
+    fn test_scan_and_iter() {
+        let cache = GenericCache::new(GenericCacheConfig { max_size: 1000, ..Default::default() });
+        for (key, value) in [("bar_ETH_2", 3), ("bar_BTC_2", 2), ("bar_BTC_1", 1), ("quote_BTC_1", 4)] {
+            cache.put(key.to_string(), value);
+        }
+        // Expired, but not yet removed
+        cache.put("bar_BTC_0".to_string(), 0);
+        cache.shard("bar_BTC_0").write().unwrap().map.get_mut("bar_BTC_0").unwrap().entry.expires_at = Some(0);
+
+        assert_eq!(cache.scan("bar_BTC_"), vec![("bar_BTC_1".to_string(), 1), ("bar_BTC_2".to_string(), 2)]);
+        assert!(cache.scan("trade_").is_empty());
+        let mut all: Vec<i32> = cache.iter().map(|(_, value)| value).collect();
+        all.sort();
+        assert_eq!(all, vec![1, 2, 3, 4]);
+
+        // Scans are not reads
+        assert_eq!(cache.statistics().unwrap().hits, 0);
+    }
+
+    #[test]
+    fn test_memory_usage_tracks_entry_sizes() {
+        let cache = GenericCache::<String>::new(GenericCacheConfig { max_size: 2, ..Default::default() });
+        let inline = cache.estimated_memory_usage();
+        assert_eq!(inline, 0);
+        cache.put("a".to_string(), "x".repeat(1000));
+        let inline = cache.estimated_memory_usage();
+        assert!(inline < 1000);
+
+        // Measured by MemSize, the string's heap buffer counts
+        let cache = cache.with_mem_size();
+        let measured = cache.estimated_memory_usage();
+        assert!(measured >= inline + 1000);
+        cache.put("b".to_string(), "y".repeat(500));
+        assert!(cache.statistics().unwrap().memory_usage >= measured + 500);
+
+        // Evicting, replacing and removing give the bytes back
+        cache.put("c".to_string(), String::new());
+        cache.put("c".to_string(), String::new());
+        cache.remove("b");
+        assert_eq!(cache.estimated_memory_usage(), inline);
+        cache.clear();
+        assert_eq!(cache.estimated_memory_usage(), 0);
+    }
+
+    #[test]
+    fn test_sweeper_purges_expired_entries() {
+        let clock = Arc::new(TestClock::new(1_000 * NANOS_PER_SECOND));
+        let config = GenericCacheConfig { sweep_interval_seconds: Some(1), ..Default::default() };
+        let cache = GenericCache::new(config).with_clock(clock.clone());
+        cache.put_with_ttl("stale".to_string(), 1, Some(1));
+        cache.put("fresh".to_string(), 2);
+
+        assert_eq!(clock.advance_time(NANOS_PER_SECOND), 1);
+        assert_eq!(cache.keys().len(), 2);
+        assert_eq!(clock.advance_time(NANOS_PER_SECOND), 1);
+        assert_eq!(cache.keys(), vec!["fresh"]);
+        assert_eq!(cache.statistics().unwrap().evictions, 1);
+
+        // The sweeper stops with the cache rather than keeping its entries alive
+        let shards = Arc::downgrade(&cache.shards);
+        drop(cache);
+        assert!(shards.upgrade().is_none());
+        assert_eq!(clock.advance_time(NANOS_PER_SECOND), 0);
+        assert_eq!(purge_expired::<i32>(&[], 0), 0);
+    }
+
+    #[test]
+    fn test_put_with_ttl_overrides_configured_ttl() {
+        let clock = Arc::new(TestClock::new(1_000 * NANOS_PER_SECOND));
+        let cache = GenericCache::new(GenericCacheConfig { ttl_seconds: Some(60), ..Default::default() }).with_clock(clock.clone());
+        cache.put("quote".to_string(), 1);
+        cache.put_with_ttl("instrument".to_string(), 2, None);
+        cache.put_with_ttl("stale".to_string(), 3, Some(0));
+
+        let expiry = |key: &str| cache.shard(key).read().unwrap().map[key].entry.expires_at;
+        assert_eq!(expiry("quote"), Some(1_060));
+        assert_eq!(expiry("instrument"), None);
+
+        // Replacing an entry takes the new entry's TTL
+        cache.put_with_ttl("quote".to_string(), 4, Some(3600));
+        assert_eq!(expiry("quote"), Some(4_600));
+        assert_eq!(cache.get("stale"), Some(3));
+
+        clock.advance_time(NANOS_PER_SECOND);
+        assert_eq!(cache.get("instrument"), Some(2));
+        assert_eq!(cache.get("stale"), None);
+        assert_eq!(cache.get("quote"), Some(4));
+    }
+
+    #[test]
+    fn test_concurrent_cache() {
+        let cache = GenericCache::new(GenericCacheConfig {
+            max_size: 2,
+            concurrency: CacheConcurrency::Concurrent,
+            ..Default::default()
+        });
+        assert!(cache.shards.is_empty());
+        cache.put("a".to_string(), 1);
+        cache.put("b".to_string(), 2);
+        assert_eq!(cache.get("a"), Some(1));
+        // LRU as in a locked cache: "b" is the least recently used
+        cache.put("c".to_string(), 3);
+        assert_eq!(sorted_keys(&cache), vec!["a", "c"]);
+        assert_eq!(cache.get_many(&["a", "b"]), vec![Some(1), None]);
+
+        cache.shared.as_ref().unwrap().map.get_mut("c").unwrap().entry.expires_at = Some(0);
+        assert!(!cache.contains("c"));
+        assert_eq!(cache.purge_expired(), 1);
+        assert!(cache.remove("a"));
+        assert_eq!(cache.estimated_memory_usage(), 0);
+
+        let stats = cache.statistics().unwrap();
+        assert_eq!((stats.hits, stats.misses, stats.inserts, stats.evictions), (2, 1, 3, 2));
+
+        // Readers never wait on each other, and racing writers never take
+        // the cache past capacity
+        let cache = Arc::new(GenericCache::new(GenericCacheConfig {
+            max_size: 100,
+            concurrency: CacheConcurrency::Concurrent,
+            ..Default::default()
+        }));
+        let handles: Vec<_> = (0..4)
+            .map(|thread| {
+                let cache = Arc::clone(&cache);
+                std::thread::spawn(move || {
+                    for i in 0..500u32 {
+                        let key = format!("{}", i % 150);
+                        if i % 5 == thread {
+                            cache.put(key, i);
+                        } else {
+                            cache.get(&key);
+                        }
+                    }
+                })
+            })
+            .collect();
+        for handle in handles {
+            handle.join().unwrap();
+        }
+        assert!(cache.size() <= 100);
+        assert_eq!(cache.shared.as_ref().unwrap().order.lock().unwrap().len(), cache.size());
+    }
+
+    #[test]
+    fn test_get_or_insert_with_computes_once() {
+        for concurrency in [CacheConcurrency::Locked, CacheConcurrency::Concurrent] {
+            let cache = Arc::new(GenericCache::new(GenericCacheConfig { concurrency, ..Default::default() }));
+            let computed = Arc::new(AtomicU64::new(0));
+            let handles: Vec<_> = (0..4)
+                .map(|_| {
+                    let (cache, computed) = (Arc::clone(&cache), Arc::clone(&computed));
+                    std::thread::spawn(move || {
+                        cache.get_or_insert_with("state", || {
+                            computed.fetch_add(1, Ordering::Relaxed);
+                            std::thread::sleep(Duration::from_millis(20));
+                            42u32
+                        })
+                    })
+                })
+                .collect();
+            for handle in handles {
+                assert_eq!(handle.join().unwrap(), 42);
+            }
+            assert_eq!(computed.load(Ordering::Relaxed), 1);
+
+            // A present value is returned without computing
+            assert_eq!(cache.get_or_insert_with("state", || unreachable!()), 42);
+            let stats = cache.statistics().unwrap();
+            // Callers that missed while another computed count a miss
+            assert_eq!((stats.hits + stats.misses, stats.inserts), (5, 1));
+            assert!(cache.pending.is_empty());
+
+            // Computing may use the cache
+            let total = cache.get_or_insert_with("total", || cache.get_or_insert_with("state", || unreachable!()) + 1);
+            assert_eq!(total, 43);
+        }
+    }
+
+    #[test]
+    fn test_get_or_insert_with_panic_clears_pending() {
+        // Values need not be serializable outside persistence
+        #[derive(Debug, Clone, PartialEq)]
+        struct Handle(Arc<u32>);
+        let cache = GenericCache::new(GenericCacheConfig::default());
+        let panicked = std::panic::catch_unwind(std::panic::AssertUnwindSafe(|| {
+            cache.get_or_insert_with("state", || panic!("compute failed"))
+        }));
+        assert!(panicked.is_err());
+        assert!(cache.pending.is_empty());
+        let handle = Handle(Arc::new(42));
+        assert_eq!(cache.get_or_insert_with("state", || handle.clone()), handle);
+        assert!(cache.pending.is_empty());
+    }
+
+    #[test]
+    fn test_large_values_compressed() {
+        let config = GenericCacheConfig { compression_threshold: Some(256), ..Default::default() };
+        let plain = GenericCache::<String>::new(GenericCacheConfig { compression_threshold: None, ..config.clone() }).with_mem_size();
+        let compressed = GenericCache::<String>::open(config.clone()).unwrap().with_mem_size();
+        let history = "bar,100.0,101.5,99.5,100.5;".repeat(100);
+        for cache in [&plain, &compressed] {
+            cache.put("bars".to_string(), history.clone());
+            cache.put("symbol".to_string(), "BTCUSDT".to_string());
+        }
+        assert!(compressed.estimated_memory_usage() * 4 < plain.estimated_memory_usage());
+        let is_compressed = |key: &str| matches!(compressed.shard(key).read().unwrap().map[key].entry.value, Stored::Compressed(_));
+        assert!(is_compressed("bars"));
+        assert!(!is_compressed("symbol"));
+
+        // Reads see the original values
+        assert_eq!(compressed.get("bars"), Some(history.clone()));
+        assert_eq!(compressed.scan("ba"), vec![("bars".to_string(), history.clone())]);
+        let concurrent = GenericCache::<String>::open(GenericCacheConfig { concurrency: CacheConcurrency::Concurrent, ..config }).unwrap();
+        assert_eq!(concurrent.get_or_insert_with("bars", || history.clone()), history);
+        assert_eq!(concurrent.get("bars"), Some(history));
+
+        // Values that cannot be decompressed are misses, and dropped
+        compressed.reset_statistics();
+        compressed.shard("bars").write().unwrap().map.get_mut("bars").unwrap().entry.value = Stored::Compressed(Arc::from(&b"corrupt"[..]));
+        assert_eq!(compressed.get("bars"), None);
+        assert!(!compressed.contains("bars"));
+        let stats = compressed.statistics().unwrap();
+        assert_eq!((stats.hits, stats.misses, stats.evictions), (0, 1, 1));
     }
 }
@@ -7,7 +7,7 @@ use std::fmt::{self, Display};
 use std::str::FromStr;
 
 /// Instrument identifier
-#[derive(Debug, Clone, Copy, Hash, PartialEq, Eq, Serialize, Deserialize)]
+#[derive(Debug, Clone, Copy, Default, Hash, PartialEq, Eq, Serialize, Deserialize)]
 pub struct InstrumentId {
     // Use a simpler representation for Copy trait
     pub id: u64,  // Use numeric ID for performance
@@ -31,12 +31,6 @@ impl InstrumentId {
     }
 }
 
-impl Default for InstrumentId {
-    fn default() -> Self {
-        Self { id: 0 }
-    }
-}
-
 impl Display for InstrumentId {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         write!(f, "{}", self.id)
@@ -8,20 +8,54 @@
 pub mod error;
 pub mod message;
 pub mod message_bus;
+pub mod schema;
+pub mod journal;
+#[cfg(feature = "redis")]
+pub mod redis_bridge;
+pub mod transport;
+#[cfg(feature = "nats")]
+pub mod nats_transport;
+#[cfg(feature = "shm")]
+pub mod shm_bus;
 pub mod time;
+pub mod clock;
+pub mod calendar;
+pub mod schedule;
 pub mod uuid;
 pub mod cache;
 pub mod generic_cache;
 pub mod data;
+pub mod data_channel;
+pub mod analytics;
+pub mod validation;
+pub mod consolidation;
+pub mod synthetic;
+pub mod throttler;
+pub mod persistence;
+pub mod replay;
 pub mod data_engine;
 pub mod identifiers;
 pub mod strategy_engine;
+pub mod decision_log;
+pub mod logging;
+#[cfg(feature = "logger")]
+pub mod logger;
 pub mod execution_engine;
+pub mod portfolio;
+pub mod profiling;
+pub mod allocation;
+pub mod sizing;
+pub mod parameters;
+pub mod backtest;
+pub mod optimizer;
+pub mod config;
+pub mod metrics;
+pub mod rate_limiter;
 
 // Re-export commonly used types
 pub use error::{AlphaForgeError, Result};
 pub use time::{UnixNanos, AtomicTime};
-pub use uuid::UUID4;
+pub use uuid::{UUID4, UUID7};
 pub use data_engine::{DataEngine, DataEngineConfig, DataEngineStatistics};
 
 /// AlphaForge version information
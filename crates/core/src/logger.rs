@@ -0,0 +1,728 @@
+//! AlphaForge Logger
+//!
+//! A `tracing` subscriber layer writing log lines as text or JSON to
+//! standard output, optionally rate limited, and to size-rotated files,
+//! with levels per component and per strategy (the `log_level` of a
+//! `logging::strategy_span`). Lines carry the fields of the spans they
+//! were logged in, such as the strategy and order of `logging::order_span`.
+//! `init` installs it for the process; its `LogHandle` swaps the
+//! `LoggingConfig` at runtime.
+//!
+//! Whether a callsite logs is decided once per level change rather than on
+//! every call, except that lines below its level are checked against the
+//! strategy they are logged for once any strategy logs more.
+//!
+//! Lines are written by a background thread, so logging threads never wait
+//! on the terminal or the disk.
+
+use std::fs::{self, File};
+use std::io::{BufWriter, Write};
+use std::path::PathBuf;
+use std::sync::atomic::{AtomicBool, AtomicU64, AtomicU8, Ordering};
+use std::sync::mpsc::{self, Receiver, SyncSender, TryRecvError};
+use std::sync::{Arc, OnceLock};
+use std::thread;
+
+use parking_lot::RwLock;
+use serde_json::{Map, Value};
+use tracing::field::{Field, Visit};
+use tracing::span::{Attributes, Id, Record};
+use tracing::subscriber::Interest;
+use tracing::{Event, Level, Metadata, Subscriber};
+use tracing_subscriber::layer::{Context, SubscriberExt};
+use tracing_subscriber::registry::{LookupSpan, SpanRef};
+use tracing_subscriber::{EnvFilter, Layer};
+
+use crate::error::{AlphaForgeError, Result};
+use crate::logging::{LogFileConfig, LogFormat, LogLevel, LoggingConfig, LOG_LEVEL_FIELD};
+use crate::rate_limiter::{RateLimit, RateLimiter};
+use crate::time::{format_iso8601, unix_nanos_now};
+
+/// Lines waiting for the writer thread before logging threads block
+const LINE_QUEUE_CAPACITY: usize = 65_536;
+
+static HANDLE: OnceLock<LogHandle> = OnceLock::new();
+
+/// Install the logger as the process's `tracing` subscriber. Fails if a
+/// subscriber is already installed.
+pub fn init(config: LoggingConfig) -> Result<&'static LogHandle> {
+    install(config, None)
+}
+
+/// Like `init`, also filtering lines by `RUST_LOG` style `directives`,
+/// e.g. `warn,alphaforge_core::cache=trace`
+pub fn init_with_directives(config: LoggingConfig, directives: &str) -> Result<&'static LogHandle> {
+    let filter = EnvFilter::try_new(directives)
+        .map_err(|e| AlphaForgeError::config(format!("Invalid log directives {:?}: {}", directives, e)))?;
+    install(config, Some(filter))
+}
+
+fn install(config: LoggingConfig, filter: Option<EnvFilter>) -> Result<&'static LogHandle> {
+    let (layer, handle) = LogLayer::new(config)?;
+    tracing::subscriber::set_global_default(tracing_subscriber::registry().with(filter).with(layer))
+        .map_err(|e| AlphaForgeError::config(format!("Logging already initialized: {}", e)))?;
+    Ok(HANDLE.get_or_init(|| handle))
+}
+
+/// Handle of the logger installed by `init`
+pub fn handle() -> Option<&'static LogHandle> {
+    HANDLE.get()
+}
+
+fn to_tracing(level: LogLevel) -> Level {
+    match level {
+        LogLevel::Trace => Level::TRACE,
+        LogLevel::Debug => Level::DEBUG,
+        LogLevel::Info => Level::INFO,
+        LogLevel::Warn => Level::WARN,
+        LogLevel::Error => Level::ERROR,
+    }
+}
+
+/// Current file of a rotating log
+#[derive(Debug)]
+struct RotatingFile {
+    config: LogFileConfig,
+    writer: BufWriter<File>,
+    written: u64,
+}
+
+impl RotatingFile {
+    fn open(config: LogFileConfig) -> Result<Self> {
+        fs::create_dir_all(&config.directory)?;
+        let writer = BufWriter::new(File::create(Self::next_path(&config))?);
+        let file = Self {
+            config,
+            writer,
+            written: 0,
+        };
+        file.prune()?;
+        Ok(file)
+    }
+
+    // Named by creation time, so names sort oldest first
+    fn next_path(config: &LogFileConfig) -> PathBuf {
+        let mut ts = unix_nanos_now();
+        loop {
+            let path = config.directory.join(format!("{}-{:020}.log", config.file_prefix, ts));
+            if !path.exists() {
+                return path;
+            }
+            ts += 1;
+        }
+    }
+
+    fn write_line(&mut self, line: &str) -> Result<()> {
+        if self.written > 0 && self.written + line.len() as u64 + 1 > self.config.max_file_bytes {
+            self.writer.flush()?;
+            self.writer = BufWriter::new(File::create(Self::next_path(&self.config))?);
+            self.written = 0;
+            self.prune()?;
+        }
+        writeln!(self.writer, "{}", line)?;
+        self.written += line.len() as u64 + 1;
+        Ok(())
+    }
+
+    // Delete the oldest files beyond `max_files`
+    fn prune(&self) -> Result<()> {
+        let prefix = format!("{}-", self.config.file_prefix);
+        let mut files: Vec<PathBuf> = fs::read_dir(&self.config.directory)?
+            .filter_map(|entry| entry.ok().map(|entry| entry.path()))
+            .filter(|path| {
+                path.file_name()
+                    .and_then(|name| name.to_str())
+                    .is_some_and(|name| name.starts_with(&prefix) && name.ends_with(".log"))
+            })
+            .collect();
+        files.sort();
+        let excess = files.len().saturating_sub(self.config.max_files.max(1));
+        for path in &files[..excess] {
+            fs::remove_file(path)?;
+        }
+        Ok(())
+    }
+}
+
+/// Work for the writer thread
+#[derive(Debug)]
+enum WriterCommand {
+    /// A line for the log file, and for standard output if `stdout`
+    Line { line: String, stdout: bool },
+    /// A line for standard output only
+    Stdout(String),
+    /// Write to another log file, or to none
+    File(Option<RotatingFile>),
+    /// Flush what was written, then acknowledge
+    Flush(mpsc::Sender<()>),
+}
+
+// Loop of the writer thread, flushing whenever its queue runs dry
+fn run_writer(commands: Receiver<WriterCommand>, write_errors: Arc<AtomicU64>) {
+    let mut stdout = BufWriter::new(std::io::stdout());
+    let mut file: Option<RotatingFile> = None;
+    let mut failing = false;
+    let flush = |stdout: &mut BufWriter<std::io::Stdout>, file: &mut Option<RotatingFile>| {
+        let _ = stdout.flush();
+        if file.as_mut().is_some_and(|file| file.writer.flush().is_err()) {
+            write_errors.fetch_add(1, Ordering::Relaxed);
+        }
+    };
+    loop {
+        let command = match commands.try_recv() {
+            Ok(command) => command,
+            Err(TryRecvError::Empty) => {
+                flush(&mut stdout, &mut file);
+                match commands.recv() {
+                    Ok(command) => command,
+                    Err(_) => break,
+                }
+            }
+            Err(TryRecvError::Disconnected) => break,
+        };
+        match command {
+            WriterCommand::Line { line, stdout: to_stdout } => {
+                if to_stdout {
+                    let _ = writeln!(stdout, "{}", line);
+                }
+                let Some(file) = &mut file else {
+                    continue;
+                };
+                match file.write_line(&line) {
+                    Ok(()) => failing = false,
+                    Err(e) => {
+                        write_errors.fetch_add(1, Ordering::Relaxed);
+                        // Reported once until writing works again, so a
+                        // full disk does not flood standard error
+                        if !std::mem::replace(&mut failing, true) {
+                            eprintln!("Failed to write log file, counting further failures: {}", e);
+                        }
+                    }
+                }
+            }
+            WriterCommand::Stdout(line) => {
+                let _ = writeln!(stdout, "{}", line);
+            }
+            WriterCommand::File(next) => file = next,
+            WriterCommand::Flush(done) => {
+                flush(&mut stdout, &mut file);
+                let _ = done.send(());
+            }
+        }
+    }
+    flush(&mut stdout, &mut file);
+}
+
+#[derive(Debug)]
+struct LoggerState {
+    config: RwLock<LoggingConfig>,
+    // Output settings of `config`, read without locking it
+    json: AtomicBool,
+    stdout: AtomicBool,
+    threads: AtomicBool,
+    stdout_limiter: RwLock<Option<RateLimiter>>,
+    /// Least severe level any strategy span has asked for, as `LogLevel as
+    /// u8`; less severe lines need not look for their strategy
+    strategy_floor: AtomicU8,
+    /// Standard output lines dropped since the last one written
+    suppressed: AtomicU64,
+    writer: SyncSender<WriterCommand>,
+    /// Lines the writer thread failed to write to the log file
+    write_errors: Arc<AtomicU64>,
+}
+
+impl LoggerState {
+    // Whether a callsite's lines or spans are kept at the current levels.
+    // Info spans are always kept so higher level lines inside them carry
+    // their fields.
+    fn interested(&self, metadata: &Metadata<'_>) -> bool {
+        if metadata.is_span() && *metadata.level() <= Level::INFO {
+            return true;
+        }
+        *metadata.level() <= to_tracing(self.config.read().level_for(metadata.target()))
+    }
+
+    // Whether some strategy logs lines at `level`
+    fn strategy_may_log(&self, level: LogLevel) -> bool {
+        level as u8 >= self.strategy_floor.load(Ordering::Relaxed)
+    }
+
+    fn lower_strategy_floor(&self, level: LogLevel) {
+        self.strategy_floor.fetch_min(level as u8, Ordering::Relaxed);
+    }
+
+    fn format(&self) -> LogFormat {
+        if self.json.load(Ordering::Relaxed) {
+            LogFormat::Json
+        } else {
+            LogFormat::Text
+        }
+    }
+
+    // Whether a standard output line may be written, and how many were
+    // dropped before it
+    fn admit_stdout(&self) -> Option<u64> {
+        if let Some(limiter) = &*self.stdout_limiter.read() {
+            if !limiter.try_acquire(1) {
+                self.suppressed.fetch_add(1, Ordering::Relaxed);
+                return None;
+            }
+        }
+        Some(self.suppressed.swap(0, Ordering::Relaxed))
+    }
+
+    fn write(&self, line: String) {
+        let stdout = self.stdout.load(Ordering::Relaxed) && match self.admit_stdout() {
+            Some(0) => true,
+            Some(suppressed) => {
+                let notice = format_line(&self.format(), unix_nanos_now(), LogLevel::Warn, module_path!(), &format!("{} log lines suppressed", suppressed), &Map::new());
+                let _ = self.writer.send(WriterCommand::Stdout(notice));
+                true
+            }
+            None => false,
+        };
+        let _ = self.writer.send(WriterCommand::Line { line, stdout });
+    }
+}
+
+/// Changes the configuration of a running logger
+#[derive(Debug, Clone)]
+pub struct LogHandle {
+    state: Arc<LoggerState>,
+}
+
+impl LogHandle {
+    pub fn config(&self) -> LoggingConfig {
+        self.state.config.read().clone()
+    }
+
+    /// Apply `config`, reopening the log file if its settings changed
+    pub fn reconfigure(&self, config: LoggingConfig) -> Result<()> {
+        if config.stdout_max_per_sec == Some(0) {
+            return Err(AlphaForgeError::config("stdout_max_per_sec must be positive"));
+        }
+        let current = self.config();
+        if config.file != current.file {
+            let file = config.file.clone().map(RotatingFile::open).transpose()?;
+            let _ = self.state.writer.send(WriterCommand::File(file));
+        }
+        if config.stdout_max_per_sec != current.stdout_max_per_sec || self.state.stdout_limiter.read().is_none() {
+            *self.state.stdout_limiter.write() = config
+                .stdout_max_per_sec
+                .map(|count| RateLimiter::new(RateLimit::per_second(count)))
+                .transpose()?;
+        }
+        self.state.json.store(config.format == LogFormat::Json, Ordering::Relaxed);
+        self.state.stdout.store(config.stdout, Ordering::Relaxed);
+        self.state.threads.store(config.threads, Ordering::Relaxed);
+        *self.state.config.write() = config;
+        tracing::callsite::rebuild_interest_cache();
+        Ok(())
+    }
+
+    pub fn set_level(&self, level: LogLevel) {
+        self.state.config.write().level = level;
+        tracing::callsite::rebuild_interest_cache();
+    }
+
+    /// Set the level of a component, or remove it with None
+    pub fn set_component_level(&self, component: &str, level: Option<LogLevel>) {
+        {
+            let mut config = self.state.config.write();
+            match level {
+                Some(level) => config.components.insert(component.to_string(), level),
+                None => config.components.remove(component),
+            };
+        }
+        tracing::callsite::rebuild_interest_cache();
+    }
+
+    /// Wait until the lines logged so far are written out
+    pub fn flush(&self) {
+        let (done, flushed) = mpsc::channel();
+        if self.state.writer.send(WriterCommand::Flush(done)).is_ok() {
+            let _ = flushed.recv();
+        }
+    }
+
+    /// Lines that could not be written to the log file
+    pub fn write_errors(&self) -> u64 {
+        self.state.write_errors.load(Ordering::Relaxed)
+    }
+}
+/// Span fields, stored on each span for the events inside it
+struct SpanFields(Map<String, Value>);
+
+/// Log level of a strategy span, applying to the events inside it
+struct SpanLevel(LogLevel);
+
+// Level of the innermost strategy span in `span` and its parents that has one
+fn strategy_level<'a, R: LookupSpan<'a>>(span: Option<SpanRef<'a, R>>) -> Option<LogLevel> {
+    span?.scope().find_map(|span| span.extensions().get::<SpanLevel>().map(|level| level.0))
+}
+
+#[derive(Default)]
+struct FieldVisitor {
+    message: Option<String>,
+    fields: Map<String, Value>,
+}
+
+impl Visit for FieldVisitor {
+    fn record_str(&mut self, field: &Field, value: &str) {
+        self.record_value(field, Value::from(value));
+    }
+
+    fn record_i64(&mut self, field: &Field, value: i64) {
+        self.record_value(field, Value::from(value));
+    }
+
+    fn record_u64(&mut self, field: &Field, value: u64) {
+        self.record_value(field, Value::from(value));
+    }
+
+    fn record_f64(&mut self, field: &Field, value: f64) {
+        self.record_value(field, Value::from(value));
+    }
+
+    fn record_bool(&mut self, field: &Field, value: bool) {
+        self.record_value(field, Value::from(value));
+    }
+
+    fn record_debug(&mut self, field: &Field, value: &dyn std::fmt::Debug) {
+        self.record_value(field, Value::from(format!("{:?}", value)));
+    }
+}
+
+impl FieldVisitor {
+    fn record_value(&mut self, field: &Field, value: Value) {
+        match (field.name(), value) {
+            ("message", Value::String(message)) => self.message = Some(message),
+            (name, value) => {
+                self.fields.insert(name.to_string(), value);
+            }
+        }
+    }
+}
+
+fn format_line(format: &LogFormat, ts: u64, level: LogLevel, target: &str, message: &str, fields: &Map<String, Value>) -> String {
+    match format {
+        LogFormat::Text => {
+            let mut line = format!("{} {:>5} {}: {}", format_iso8601(ts), level.as_str().to_ascii_uppercase(), target, message);
+            for (name, value) in fields {
+                match value {
+                    Value::String(text) => line.push_str(&format!(" {}={}", name, text)),
+                    other => line.push_str(&format!(" {}={}", name, other)),
+                }
+            }
+            line
+        }
+        LogFormat::Json => {
+            let mut object = Map::new();
+            object.insert("timestamp".to_string(), Value::from(format_iso8601(ts)));
+            object.insert("level".to_string(), Value::from(level.as_str()));
+            object.insert("target".to_string(), Value::from(target));
+            object.insert("message".to_string(), Value::from(message));
+            for (name, value) in fields {
+                object.entry(name.clone()).or_insert_with(|| value.clone());
+            }
+            Value::Object(object).to_string()
+        }
+    }
+}
+
+fn from_tracing(level: &Level) -> LogLevel {
+    match *level {
+        Level::TRACE => LogLevel::Trace,
+        Level::DEBUG => LogLevel::Debug,
+        Level::INFO => LogLevel::Info,
+        Level::WARN => LogLevel::Warn,
+        _ => LogLevel::Error,
+    }
+}
+
+
+/// The logger's `tracing` layer
+#[derive(Debug)]
+pub struct LogLayer {
+    state: Arc<LoggerState>,
+}
+
+impl LogLayer {
+    /// A layer for subscribers set up by the caller, with its handle
+    pub fn new(config: LoggingConfig) -> Result<(Self, LogHandle)> {
+        let (writer, commands) = mpsc::sync_channel(LINE_QUEUE_CAPACITY);
+        let write_errors = Arc::new(AtomicU64::new(0));
+        let thread_errors = Arc::clone(&write_errors);
+        thread::Builder::new()
+            .name("alphaforge-logger".to_string())
+            .spawn(move || run_writer(commands, thread_errors))
+            .map_err(|e| AlphaForgeError::runtime(format!("Failed to start the log writer: {}", e)))?;
+
+        let state = Arc::new(LoggerState {
+            config: RwLock::new(LoggingConfig {
+                file: None,
+                stdout_max_per_sec: None,
+                ..config.clone()
+            }),
+            json: AtomicBool::new(false),
+            stdout: AtomicBool::new(false),
+            threads: AtomicBool::new(false),
+            stdout_limiter: RwLock::new(None),
+            strategy_floor: AtomicU8::new(u8::MAX),
+            suppressed: AtomicU64::new(0),
+            writer,
+            write_errors,
+        });
+        let handle = LogHandle { state: Arc::clone(&state) };
+        handle.reconfigure(config)?;
+        Ok((Self { state }, handle))
+    }
+}
+
+impl<S: Subscriber + for<'a> LookupSpan<'a>> Layer<S> for LogLayer {
+    // Cached by the callsite until a level change rebuilds the cache. Lines
+    // below the levels are left to `enabled`, as a strategy may log them.
+    fn register_callsite(&self, metadata: &'static Metadata<'static>) -> Interest {
+        if self.state.interested(metadata) {
+            Interest::always()
+        } else if metadata.is_event() {
+            Interest::sometimes()
+        } else {
+            Interest::never()
+        }
+    }
+
+    // Asked for lines below the levels, and for callsites other layers or
+    // subscribers filter dynamically
+    fn enabled(&self, metadata: &Metadata<'_>, ctx: Context<'_, S>) -> bool {
+        let level = from_tracing(metadata.level());
+        if metadata.is_event() && self.state.strategy_may_log(level) {
+            if let Some(min) = strategy_level(ctx.lookup_current()) {
+                return level >= min;
+            }
+        }
+        self.state.interested(metadata)
+    }
+
+    fn on_new_span(&self, attrs: &Attributes<'_>, id: &Id, ctx: Context<'_, S>) {
+        let mut visitor = FieldVisitor::default();
+        attrs.record(&mut visitor);
+        let level = visitor.fields
+            .remove(LOG_LEVEL_FIELD)
+            .and_then(|level| level.as_str().and_then(|level| level.parse::<LogLevel>().ok()));
+        if let Some(span) = ctx.span(id) {
+            let mut extensions = span.extensions_mut();
+            extensions.insert(SpanFields(visitor.fields));
+            if let Some(level) = level {
+                extensions.insert(SpanLevel(level));
+            }
+        }
+        if let Some(level) = level {
+            self.state.lower_strategy_floor(level);
+        }
+    }
+
+    fn on_record(&self, id: &Id, values: &Record<'_>, ctx: Context<'_, S>) {
+        let mut visitor = FieldVisitor::default();
+        values.record(&mut visitor);
+        if let Some(span) = ctx.span(id) {
+            if let Some(fields) = span.extensions_mut().get_mut::<SpanFields>() {
+                fields.0.extend(visitor.fields);
+            }
+        }
+    }
+
+    fn on_event(&self, event: &Event<'_>, ctx: Context<'_, S>) {
+        let metadata = event.metadata();
+        let level = from_tracing(metadata.level());
+        // Kept by the levels, but maybe not by a quieter strategy
+        if strategy_level(ctx.event_span(event)).is_some_and(|min| level < min) {
+            return;
+        }
+
+        let mut visitor = FieldVisitor::default();
+        event.record(&mut visitor);
+        let mut fields = Map::new();
+        if self.state.threads.load(Ordering::Relaxed) {
+            let thread = thread::current();
+            if let Some(name) = thread.name() {
+                fields.insert("thread_name".to_string(), Value::from(name));
+            }
+            fields.insert("thread_id".to_string(), Value::from(format!("{:?}", thread.id())));
+        }
+        // Outer span fields first, so inner spans and the event win
+        if let Some(scope) = ctx.event_scope(event) {
+            for span in scope.from_root() {
+                if let Some(span_fields) = span.extensions().get::<SpanFields>() {
+                    fields.extend(span_fields.0.clone());
+                }
+            }
+        }
+        fields.extend(visitor.fields);
+
+        let line = format_line(&self.state.format(), unix_nanos_now(), level, metadata.target(), visitor.message.as_deref().unwrap_or(""), &fields);
+        self.state.write(line);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::identifiers::{OrderId, StrategyId};
+    use crate::logging::{order_span, strategy_span};
+
+    fn temp_dir() -> PathBuf {
+        std::env::temp_dir().join(format!("alphaforge-logs-{}", crate::uuid::UUID4::new()))
+    }
+
+    fn read_lines(dir: &PathBuf) -> (usize, Vec<Value>) {
+        let mut files: Vec<PathBuf> = fs::read_dir(dir).unwrap().map(|entry| entry.unwrap().path()).collect();
+        files.sort();
+        let lines = files
+            .iter()
+            .flat_map(|path| fs::read_to_string(path).unwrap().lines().map(|line| serde_json::from_str(line).unwrap()).collect::<Vec<_>>())
+            .collect();
+        (files.len(), lines)
+    }
+
+    #[test]
+    fn test_json_lines_follow_component_levels() {
+        let dir = temp_dir();
+        let config = LoggingConfig {
+            level: LogLevel::Warn,
+            components: [("chatty".to_string(), LogLevel::Debug)].into(),
+            format: LogFormat::Json,
+            stdout: false,
+            file: Some(LogFileConfig::new(&dir)),
+            threads: true,
+            ..Default::default()
+        };
+        let (layer, handle) = LogLayer::new(config).unwrap();
+
+        tracing::subscriber::with_default(tracing_subscriber::registry().with(layer), || {
+            let span = order_span(OrderId::from_u64(7), Some(StrategyId::new(3)), None, None);
+            let _entered = span.enter();
+            tracing::info!("dropped by the warn level");
+            tracing::warn!(price = 101.5, "order rejected");
+            tracing::debug!(target: "alphaforge_core::chatty::inner", "kept by the component level");
+            tracing::debug!(target: "alphaforge_core::chattier", "dropped as another component");
+            handle.set_level(LogLevel::Error);
+            tracing::warn!("dropped after the level change");
+        });
+        handle.flush();
+
+        let (_, lines) = read_lines(&dir);
+        let messages: Vec<&str> = lines.iter().map(|line| line["message"].as_str().unwrap()).collect();
+        assert_eq!(messages, ["order rejected", "kept by the component level"]);
+        assert_eq!(lines[0]["price"], 101.5);
+        assert_eq!(lines[0]["order_id"], "7");
+        assert_eq!(lines[0]["strategy_id"], "3");
+        assert_eq!(lines[1]["level"], "debug");
+        assert!(lines[0]["thread_id"].as_str().unwrap().starts_with("ThreadId("));
+        assert_eq!(handle.write_errors(), 0);
+        fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[test]
+    fn test_strategy_spans_apply_strategy_levels() {
+        let dir = temp_dir();
+        let config = LoggingConfig {
+            format: LogFormat::Json,
+            stdout: false,
+            file: Some(LogFileConfig::new(&dir)),
+            ..Default::default()
+        };
+        let (layer, handle) = LogLayer::new(config).unwrap();
+
+        tracing::subscriber::with_default(tracing_subscriber::registry().with(layer), || {
+            let quiet = strategy_span(StrategyId::new(1), "quiet", None, Some(LogLevel::Warn));
+            let verbose = strategy_span(StrategyId::new(2), "verbose", None, Some(LogLevel::Debug));
+            let default = strategy_span(StrategyId::new(3), "default", None, None);
+            quiet.in_scope(|| {
+                tracing::info!("dropped by the quiet strategy");
+                tracing::warn!("kept by the quiet strategy");
+                // The innermost strategy's level applies
+                verbose.in_scope(|| tracing::debug!("kept by the verbose strategy"));
+            });
+            default.in_scope(|| {
+                tracing::debug!("dropped by the default level");
+                tracing::info!("kept by the default level");
+            });
+            tracing::debug!("dropped outside strategies");
+        });
+        handle.flush();
+
+        let (_, lines) = read_lines(&dir);
+        let messages: Vec<&str> = lines.iter().map(|line| line["message"].as_str().unwrap()).collect();
+        assert_eq!(messages, ["kept by the quiet strategy", "kept by the verbose strategy", "kept by the default level"]);
+        assert_eq!(lines[0]["strategy"], "quiet");
+        assert_eq!(lines[1]["strategy"], "verbose");
+        assert!(lines.iter().all(|line| line.get(LOG_LEVEL_FIELD).is_none()));
+        fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[test]
+    fn test_log_files_rotate() {
+        let dir = temp_dir();
+        let mut file = LogFileConfig::new(&dir);
+        file.max_file_bytes = 600;
+        file.max_files = 2;
+        let config = LoggingConfig {
+            format: LogFormat::Json,
+            stdout: false,
+            file: Some(file),
+            ..Default::default()
+        };
+        let (layer, handle) = LogLayer::new(config).unwrap();
+
+        tracing::subscriber::with_default(tracing_subscriber::registry().with(layer), || {
+            for i in 0..20 {
+                tracing::error!(i, "filling the files");
+            }
+        });
+        handle.flush();
+
+        let (files, lines) = read_lines(&dir);
+        assert_eq!(files, 2);
+        assert!(lines.len() < 20);
+        assert_eq!(lines.last().unwrap()["i"], 19);
+        fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[test]
+    fn test_stdout_keeps_to_its_rate() {
+        let config = LoggingConfig {
+            stdout_max_per_sec: Some(2),
+            ..Default::default()
+        };
+        let (_layer, handle) = LogLayer::new(config).unwrap();
+        assert_eq!(handle.state.admit_stdout(), Some(0));
+        assert_eq!(handle.state.admit_stdout(), Some(0));
+        assert_eq!(handle.state.admit_stdout(), None);
+        assert_eq!(handle.state.admit_stdout(), None);
+        assert_eq!(handle.state.suppressed.load(Ordering::Relaxed), 2);
+    }
+
+    #[test]
+    fn test_text_lines_and_component_matching() {
+        let text = format_line(&LogFormat::Text, 0, LogLevel::Info, "alphaforge_core::data_engine", "started", &Map::from_iter([("ticks".to_string(), Value::from(3))]));
+        assert_eq!(text, "1970-01-01T00:00:00.000000000Z  INFO alphaforge_core::data_engine: started ticks=3");
+
+        let config = LoggingConfig {
+            components: [
+                ("chatty".to_string(), LogLevel::Debug),
+                ("alphaforge_network::binance".to_string(), LogLevel::Warn),
+            ]
+            .into(),
+            ..Default::default()
+        };
+        assert_eq!(config.level_for("alphaforge_core::chatty"), LogLevel::Debug);
+        assert_eq!(config.level_for("chatty::inner"), LogLevel::Debug);
+        assert_eq!(config.level_for("alphaforge_core::chattier"), LogLevel::Info);
+        assert_eq!(config.level_for("alphaforge_network::binance::ws"), LogLevel::Warn);
+        assert_eq!(config.level_for("alphaforge_network::binance_us"), LogLevel::Info);
+        assert_eq!(LoggingConfig::default().level_for("alphaforge_core::chatty"), LogLevel::Info);
+
+        assert!(init_with_directives(LoggingConfig::default(), "alphaforge_core=loud").is_err());
+    }
+}
@@ -0,0 +1,260 @@
+//! AlphaForge Strategy Logging
+//!
+//! Tracing spans that tag log lines with the strategy, instrument and order
+//! they concern, and log levels that can be raised or lowered per strategy.
+//! The strategy engine runs every strategy callback inside a `strategy_span`
+//! and the execution engine handles every order inside an `order_span`, so
+//! any `tracing` event emitted there carries those fields. A strategy's log
+//! level rides on its span, so the logger applies it to every event inside,
+//! including those of `StrategyContext::log`.
+//! Each hop of a message chain runs inside a `hop_span` carrying the
+//! chain's correlation ID (see `message_bus::correlate`).
+
+use std::collections::HashMap;
+use std::fmt;
+use std::path::PathBuf;
+use std::str::FromStr;
+
+use serde::{Deserialize, Serialize};
+use tracing::field::Empty;
+use tracing::Span;
+
+use crate::identifiers::{InstrumentId, OrderId, StrategyId};
+use crate::uuid::UUID7;
+
+/// Severity of a log line, least severe first
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize)]
+pub enum LogLevel {
+    Trace,
+    Debug,
+    Info,
+    Warn,
+    Error,
+}
+
+impl LogLevel {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            LogLevel::Trace => "trace",
+            LogLevel::Debug => "debug",
+            LogLevel::Info => "info",
+            LogLevel::Warn => "warn",
+            LogLevel::Error => "error",
+        }
+    }
+}
+
+impl fmt::Display for LogLevel {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+impl FromStr for LogLevel {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_ascii_lowercase().as_str() {
+            "trace" => Ok(LogLevel::Trace),
+            "debug" => Ok(LogLevel::Debug),
+            "info" => Ok(LogLevel::Info),
+            "warn" | "warning" => Ok(LogLevel::Warn),
+            "error" => Ok(LogLevel::Error),
+            _ => Err(format!("Invalid log level: {}", s)),
+        }
+    }
+}
+
+/// How log lines are written
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub enum LogFormat {
+    /// `timestamp LEVEL target: message key=value ...`
+    #[default]
+    Text,
+    /// One JSON object per line
+    Json,
+}
+
+/// Rotating log files; only `directory` is required when deserialized
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct LogFileConfig {
+    /// Directory the files are written to (created if missing)
+    pub directory: PathBuf,
+    #[serde(default = "default_log_file_prefix")]
+    pub file_prefix: String,
+    /// Start a new file once this many bytes were written to one
+    #[serde(default = "default_max_log_file_bytes")]
+    pub max_file_bytes: u64,
+    /// Files kept, oldest deleted first
+    #[serde(default = "default_max_log_files")]
+    pub max_files: usize,
+}
+
+fn default_log_file_prefix() -> String {
+    "alphaforge".to_string()
+}
+
+fn default_max_log_file_bytes() -> u64 {
+    64 * 1024 * 1024
+}
+
+fn default_max_log_files() -> usize {
+    10
+}
+
+impl LogFileConfig {
+    /// `alphaforge` files rotated at 64 MiB, keeping 10
+    pub fn new(directory: impl Into<PathBuf>) -> Self {
+        Self {
+            directory: directory.into(),
+            file_prefix: default_log_file_prefix(),
+            max_file_bytes: default_max_log_file_bytes(),
+            max_files: default_max_log_files(),
+        }
+    }
+}
+
+/// Node logging settings
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct LoggingConfig {
+    /// Least severe level logged, written in any case, e.g. `"info"`
+    #[serde(deserialize_with = "crate::config::deserialize_log_level")]
+    pub level: LogLevel,
+    /// Levels of components, overriding `level`. A component is a run of
+    /// module path segments of the log target, e.g. `data_engine` or
+    /// `alphaforge_network::binance`; the longest match applies.
+    #[serde(deserialize_with = "crate::config::deserialize_log_levels")]
+    pub components: HashMap<String, LogLevel>,
+    pub format: LogFormat,
+    /// Write to standard output
+    pub stdout: bool,
+    /// Most lines written to standard output a second, dropping the rest
+    /// with a count of those dropped; files get every line
+    pub stdout_max_per_sec: Option<u32>,
+    /// Also write to rotating files
+    pub file: Option<LogFileConfig>,
+    /// Add the name and ID of the logging thread to each line
+    pub threads: bool,
+}
+
+impl Default for LoggingConfig {
+    fn default() -> Self {
+        Self {
+            level: LogLevel::Info,
+            components: HashMap::new(),
+            format: LogFormat::Text,
+            stdout: true,
+            stdout_max_per_sec: None,
+            file: None,
+            threads: false,
+        }
+    }
+}
+
+impl LoggingConfig {
+    /// Level applying to log lines from `target`
+    pub fn level_for(&self, target: &str) -> LogLevel {
+        self.components
+            .iter()
+            .filter(|(component, _)| has_segments(target, component))
+            .max_by_key(|(component, _)| component.len())
+            .map_or(self.level, |(_, level)| *level)
+    }
+}
+
+// Whether `path` contains `segments` as whole `::`-separated segments
+fn has_segments(path: &str, segments: &str) -> bool {
+    !segments.is_empty()
+        && path.match_indices(segments).any(|(start, _)| {
+            let end = start + segments.len();
+            (start == 0 || path[..start].ends_with("::")) && (end == path.len() || path[end..].starts_with("::"))
+        })
+}
+
+/// Emit `message` at `level` in the current span
+pub fn emit(level: LogLevel, message: &str) {
+    match level {
+        LogLevel::Trace => tracing::trace!("{}", message),
+        LogLevel::Debug => tracing::debug!("{}", message),
+        LogLevel::Info => tracing::info!("{}", message),
+        LogLevel::Warn => tracing::warn!("{}", message),
+        LogLevel::Error => tracing::error!("{}", message),
+    }
+}
+
+/// Name of the span field carrying a strategy's log level
+pub const LOG_LEVEL_FIELD: &str = "log_level";
+
+/// Span for work done by or for a strategy, logging at `log_level` when
+/// given; `instrument` and `order_id` can be recorded later
+pub fn strategy_span(
+    strategy_id: StrategyId,
+    strategy: &str,
+    instrument: Option<InstrumentId>,
+    log_level: Option<LogLevel>,
+) -> Span {
+    let span = tracing::info_span!(
+        "strategy",
+        strategy_id = %strategy_id,
+        strategy,
+        instrument = Empty,
+        order_id = Empty,
+        log_level = log_level.map(|level| level.as_str())
+    );
+    if let Some(instrument) = instrument {
+        span.record("instrument", tracing::field::display(instrument));
+    }
+    span
+}
+
+/// Span for the handling of one order; the owner, instrument and
+/// correlation ID are left out when unknown
+pub fn order_span(
+    order_id: OrderId,
+    strategy_id: Option<StrategyId>,
+    instrument: Option<InstrumentId>,
+    correlation_id: Option<UUID7>,
+) -> Span {
+    let span = tracing::info_span!(
+        "order",
+        strategy_id = Empty,
+        instrument = Empty,
+        order_id = %order_id,
+        correlation_id = Empty
+    );
+    if let Some(correlation_id) = correlation_id {
+        span.record("correlation_id", tracing::field::display(correlation_id));
+    }
+    if let Some(strategy_id) = strategy_id {
+        span.record("strategy_id", tracing::field::display(strategy_id));
+    }
+    if let Some(instrument) = instrument {
+        span.record("instrument", tracing::field::display(instrument));
+    }
+    span
+}
+
+/// Span for one hop (e.g. `strategy`, `execution`) of the message chain
+/// identified by `correlation_id`, recorded later when not known yet
+pub fn hop_span(hop: &str, correlation_id: Option<UUID7>) -> Span {
+    let span = tracing::info_span!("hop", hop, correlation_id = Empty);
+    if let Some(correlation_id) = correlation_id {
+        span.record("correlation_id", tracing::field::display(correlation_id));
+    }
+    span
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_log_level_parsing_and_order() {
+        assert_eq!("WARNING".parse::<LogLevel>().unwrap(), LogLevel::Warn);
+        assert_eq!("debug".parse::<LogLevel>().unwrap(), LogLevel::Debug);
+        assert!("loud".parse::<LogLevel>().is_err());
+        assert!(LogLevel::Trace < LogLevel::Debug && LogLevel::Warn < LogLevel::Error);
+        assert_eq!(LogLevel::Info.to_string(), "info");
+    }
+}
@@ -1,26 +1,94 @@
 //! High-performance message passing system for AlphaForge
 
+use std::collections::HashMap;
+use std::fmt;
+use std::str::FromStr;
 use std::sync::Arc;
 use std::sync::atomic::{AtomicU64, Ordering};
 use dashmap::DashMap;
+use parking_lot::RwLock;
 use tokio::sync::{mpsc, oneshot};
+use serde::de::DeserializeOwned;
 use serde::{Serialize, Deserialize};
 use tracing::{debug, warn};
 
 use crate::time::UnixNanos;
-use crate::uuid::UUID4;
+use crate::uuid::UUID7;
 use crate::error::{AlphaForgeError, Result};
 
+/// Encoding of an envelope payload. Bincode is the compact default for
+/// in-process and Rust-to-Rust traffic; MessagePack and JSON suit bridges
+/// to other languages.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Codec {
+    #[default]
+    Bincode,
+    MessagePack,
+    Json,
+}
+
+impl Codec {
+    pub fn encode<T: Serialize + ?Sized>(&self, message: &T) -> Result<Vec<u8>> {
+        Ok(match self {
+            Codec::Bincode => bincode::serialize(message)?,
+            Codec::MessagePack => rmp_serde::to_vec_named(message)?,
+            Codec::Json => serde_json::to_vec(message)?,
+        })
+    }
+
+    pub fn decode<T: DeserializeOwned>(&self, payload: &[u8]) -> Result<T> {
+        Ok(match self {
+            Codec::Bincode => bincode::deserialize(payload)?,
+            Codec::MessagePack => rmp_serde::from_slice(payload)?,
+            Codec::Json => serde_json::from_slice(payload)?,
+        })
+    }
+
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Codec::Bincode => "bincode",
+            Codec::MessagePack => "messagepack",
+            Codec::Json => "json",
+        }
+    }
+}
+
+impl fmt::Display for Codec {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+impl FromStr for Codec {
+    type Err = AlphaForgeError;
+
+    fn from_str(s: &str) -> Result<Self> {
+        match s.to_ascii_lowercase().as_str() {
+            "bincode" => Ok(Codec::Bincode),
+            "messagepack" | "msgpack" => Ok(Codec::MessagePack),
+            "json" => Ok(Codec::Json),
+            _ => Err(AlphaForgeError::validation(format!("Unknown codec: {}", s))),
+        }
+    }
+}
+
 /// Message envelope for all system messages
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct MessageEnvelope {
-    pub id: UUID4,
+    pub id: UUID7,
     pub timestamp: UnixNanos,
     pub sender: String,
     pub recipient: Option<String>,
-    pub correlation_id: Option<UUID4>,
+    pub correlation_id: Option<UUID7>,
     pub message_type: String,
     pub payload: Vec<u8>,
+    /// How `payload` is encoded
+    #[serde(default)]
+    pub codec: Codec,
+    /// Schema version of `payload`, when its message type has a schema
+    #[serde(default)]
+    pub schema_version: Option<u32>,
 }
 
 impl MessageEnvelope {
@@ -31,15 +99,22 @@ impl MessageEnvelope {
         payload: Vec<u8>,
     ) -> Self {
         Self {
-            id: UUID4::new(),
+            id: UUID7::new(),
             timestamp: crate::time::unix_nanos_now(),
             sender,
             recipient: None,
             correlation_id: None,
             message_type,
             payload,
+            codec: Codec::default(),
+            schema_version: None,
         }
     }
+
+    /// Decode the payload with the envelope's codec
+    pub fn decode<T: DeserializeOwned>(&self) -> Result<T> {
+        self.codec.decode(&self.payload)
+    }
     
     /// Create a response message
     pub fn create_response(
@@ -49,17 +124,153 @@ impl MessageEnvelope {
         payload: Vec<u8>,
     ) -> Self {
         Self {
-            id: UUID4::new(),
+            id: UUID7::new(),
             timestamp: crate::time::unix_nanos_now(),
             sender,
             recipient: Some(self.sender.clone()),
             correlation_id: Some(self.id),
             message_type,
             payload,
+            codec: self.codec,
+            schema_version: None,
         }
     }
 }
 
+/// Glob pattern over topics: `*` matches any run of characters (dots
+/// included) and `?` any single character, so `orders.*` matches every
+/// order topic and `data.quotes.BTC*` every BTC quote topic
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct TopicPattern {
+    pattern: String,
+    /// Length of the literal text before the first wildcard
+    prefix_len: usize,
+}
+
+impl TopicPattern {
+    pub fn new(pattern: impl Into<String>) -> Self {
+        let pattern = pattern.into();
+        let prefix_len = pattern.find(['*', '?']).unwrap_or(pattern.len());
+        Self { pattern, prefix_len }
+    }
+
+    /// Whether `topic` contains wildcards
+    pub fn is_pattern(topic: &str) -> bool {
+        topic.contains(['*', '?'])
+    }
+
+    pub fn as_str(&self) -> &str {
+        &self.pattern
+    }
+
+    /// Text every matching topic starts with
+    pub fn literal_prefix(&self) -> &str {
+        &self.pattern[..self.prefix_len]
+    }
+
+    pub fn matches(&self, topic: &str) -> bool {
+        let (pattern, topic) = (self.pattern.as_bytes(), topic.as_bytes());
+        let (mut p, mut t) = (0, 0);
+        // Last `*` seen and the topic position it currently absorbs up to
+        let mut star: Option<(usize, usize)> = None;
+        while t < topic.len() {
+            match pattern.get(p) {
+                Some(b'*') => {
+                    star = Some((p, t));
+                    p += 1;
+                }
+                Some(c) if *c == b'?' || *c == topic[t] => {
+                    p += 1;
+                    t += 1;
+                }
+                _ => match star {
+                    Some((star_p, star_t)) => {
+                        // Let the `*` absorb one more character and retry
+                        star = Some((star_p, star_t + 1));
+                        p = star_p + 1;
+                        t = star_t + 1;
+                    }
+                    None => return false,
+                },
+            }
+        }
+        pattern[p..].iter().all(|c| *c == b'*')
+    }
+}
+
+/// Pattern subscriptions indexed by literal prefix, so a publish only tests
+/// the patterns whose prefix the topic starts with
+#[derive(Debug)]
+pub struct PatternIndex<S> {
+    by_prefix: HashMap<String, Vec<(TopicPattern, S)>>,
+}
+
+impl<S> PatternIndex<S> {
+    pub fn new() -> Self {
+        Self { by_prefix: HashMap::new() }
+    }
+
+    pub fn insert(&mut self, pattern: TopicPattern, subscriber: S) {
+        self.by_prefix
+            .entry(pattern.literal_prefix().to_string())
+            .or_default()
+            .push((pattern, subscriber));
+    }
+
+    /// Subscribers whose pattern matches `topic`
+    pub fn matching<'a>(&'a self, topic: &'a str) -> impl Iterator<Item = &'a S> + 'a {
+        // Most topics have no pattern subscribers: skip the prefix lookups
+        let ends = if self.by_prefix.is_empty() { 0..0 } else { 0..topic.len() + 1 };
+        ends
+            .filter(|end| topic.is_char_boundary(*end))
+            .filter_map(|end| self.by_prefix.get(&topic[..end]))
+            .flatten()
+            .filter(|(pattern, _)| pattern.matches(topic))
+            .map(|(_, subscriber)| subscriber)
+    }
+
+    /// Subscribers to exactly `pattern`
+    pub fn subscribers<'a>(&'a self, pattern: &'a str) -> impl Iterator<Item = &'a S> + 'a {
+        let pattern = TopicPattern::new(pattern);
+        self.by_prefix
+            .get(pattern.literal_prefix())
+            .into_iter()
+            .flatten()
+            .filter(move |(existing, _)| *existing == pattern)
+            .map(|(_, subscriber)| subscriber)
+    }
+
+    /// Every subscriber with the pattern it subscribed to
+    pub fn iter(&self) -> impl Iterator<Item = (&TopicPattern, &S)> + '_ {
+        self.by_prefix.values().flatten().map(|(pattern, subscriber)| (pattern, subscriber))
+    }
+
+    /// Keep the subscriptions for which `keep` returns true
+    pub fn retain(&mut self, mut keep: impl FnMut(&TopicPattern, &S) -> bool) {
+        self.by_prefix.retain(|_, entries| {
+            entries.retain(|(pattern, subscriber)| keep(pattern, subscriber));
+            !entries.is_empty()
+        });
+    }
+
+    pub fn len(&self) -> usize {
+        self.by_prefix.values().map(Vec::len).sum()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.by_prefix.is_empty()
+    }
+}
+
+impl<S> Default for PatternIndex<S> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Channel used to hand a request and its reply slot to a registered handler
+type RequestSender = mpsc::UnboundedSender<(MessageEnvelope, oneshot::Sender<MessageEnvelope>)>;
+
 /// Message bus patterns
 #[derive(Debug, Clone)]
 pub enum MessagePattern {
@@ -76,8 +287,11 @@ pub struct MessageBus {
     // Publish-Subscribe subscriptions
     pub_sub_subs: Arc<DashMap<String, Vec<mpsc::UnboundedSender<MessageEnvelope>>>>,
     
+    // Publish-Subscribe subscriptions to topic patterns
+    pattern_subs: Arc<RwLock<PatternIndex<mpsc::UnboundedSender<MessageEnvelope>>>>,
+    
     // Request-Response handlers
-    req_resp_handlers: Arc<DashMap<String, mpsc::UnboundedSender<(MessageEnvelope, oneshot::Sender<MessageEnvelope>)>>>,
+    req_resp_handlers: Arc<DashMap<String, RequestSender>>,
     
     // Point-to-Point endpoints
     p2p_endpoints: Arc<DashMap<String, mpsc::UnboundedSender<MessageEnvelope>>>,
@@ -90,6 +304,7 @@ impl Clone for MessageBus {
     fn clone(&self) -> Self {
         Self {
             pub_sub_subs: self.pub_sub_subs.clone(),
+            pattern_subs: self.pattern_subs.clone(),
             req_resp_handlers: self.req_resp_handlers.clone(),
             p2p_endpoints: self.p2p_endpoints.clone(),
             stats: self.stats.clone(),
@@ -102,6 +317,7 @@ impl MessageBus {
     pub fn new() -> Self {
         Self {
             pub_sub_subs: Arc::new(DashMap::new()),
+            pattern_subs: Arc::new(RwLock::new(PatternIndex::new())),
             req_resp_handlers: Arc::new(DashMap::new()),
             p2p_endpoints: Arc::new(DashMap::new()),
             stats: Arc::new(MessageBusStats::default()),
@@ -114,7 +330,7 @@ impl MessageBus {
         
         self.pub_sub_subs
             .entry(topic.clone())
-            .or_insert_with(Vec::new)
+            .or_default()
             .push(tx);
             
         debug!("Subscribed to topic: {}", topic);
@@ -125,24 +341,32 @@ impl MessageBus {
     pub async fn publish(&self, topic: String, envelope: MessageEnvelope) -> Result<()> {
         let start = std::time::Instant::now();
         
-        if let Some(subscribers) = self.pub_sub_subs.get(&topic) {
-            let mut delivered = 0;
-            let mut failed = 0;
-            
-            for subscriber in subscribers.value() {
-                match subscriber.send(envelope.clone()) {
-                    Ok(()) => delivered += 1,
-                    Err(_) => failed += 1, // Receiver dropped
-                }
-            }
-            
-            if failed > 0 {
-                warn!("Failed to deliver to {} subscribers for topic: {}", failed, topic);
+        let exact = self.pub_sub_subs.get(&topic);
+        let patterns = self.pattern_subs.read();
+        let mut subscribers = exact
+            .iter()
+            .flat_map(|subscribers| subscribers.value().iter())
+            .chain(patterns.matching(&topic))
+            .peekable();
+        if subscribers.peek().is_none() {
+            return Ok(());
+        }
+        
+        let mut delivered = 0;
+        let mut failed = 0;
+        
+        for subscriber in subscribers {
+            match subscriber.send(envelope.clone()) {
+                Ok(()) => delivered += 1,
+                Err(_) => failed += 1, // Receiver dropped
             }
-            
-            self.stats.record_publish(delivered, start.elapsed());
         }
         
+        if failed > 0 {
+            warn!("Failed to deliver to {} subscribers for topic: {}", failed, topic);
+        }
+        
+        self.stats.record_publish(delivered, start.elapsed());
         Ok(())
     }
     
@@ -221,11 +445,17 @@ impl MessageBus {
         self.stats.snapshot()
     }
     
-    /// Pattern matching for wildcard subscriptions
+    /// Subscribe to every topic matching a glob pattern (see `TopicPattern`);
+    /// a pattern without wildcards is an ordinary subscription
     pub fn subscribe_pattern(&self, pattern: String) -> mpsc::UnboundedReceiver<MessageEnvelope> {
-        // TODO: Implement wildcard pattern matching
-        // For now, exact match only
-        self.subscribe(pattern)
+        if !TopicPattern::is_pattern(&pattern) {
+            return self.subscribe(pattern);
+        }
+        let (tx, rx) = mpsc::unbounded_channel();
+        
+        debug!("Subscribed to pattern: {}", pattern);
+        self.pattern_subs.write().insert(TopicPattern::new(pattern), tx);
+        rx
     }
 }
 
@@ -292,6 +522,7 @@ impl MessageBusStats {
 #[cfg(test)]
 mod tests {
     use super::*;
+    #[allow(unused_imports)]
     use tokio::time::{sleep, Duration};
     
     #[tokio::test]
@@ -312,7 +543,51 @@ mod tests {
         assert_eq!(received.payload, b"test payload");
     }
     
+    #[test]
+    fn test_topic_pattern_matching() {
+        let orders = TopicPattern::new("orders.*");
+        assert_eq!(orders.literal_prefix(), "orders.");
+        assert!(orders.matches("orders.filled"));
+        assert!(orders.matches("orders.strategy.7"));
+        assert!(!orders.matches("orders"));
+        assert!(!orders.matches("positions.opened"));
+
+        let btc = TopicPattern::new("data.quotes.BTC*");
+        assert!(btc.matches("data.quotes.BTC"));
+        assert!(btc.matches("data.quotes.BTCUSDT"));
+        assert!(!btc.matches("data.quotes.ETHBTC"));
+
+        let middle = TopicPattern::new("data.*.BTC?");
+        assert!(middle.matches("data.trades.BTCX"));
+        assert!(middle.matches("data.quotes.l2.BTCX"));
+        assert!(!middle.matches("data.trades.BTC"));
+        assert!(TopicPattern::new("*.filled").matches("orders.filled"));
+        assert!(!TopicPattern::is_pattern("orders.filled"));
+    }
+
+    #[tokio::test]
+    async fn test_pattern_subscriptions() {
+        let bus = MessageBus::new();
+        let mut all_orders = bus.subscribe_pattern("orders.*".to_string());
+        let mut btc_quotes = bus.subscribe_pattern("data.quotes.BTC*".to_string());
+        let mut exact = bus.subscribe_pattern("orders.filled".to_string());
+
+        for topic in ["orders.filled", "orders.cancelled", "data.quotes.BTCUSDT", "data.quotes.ETHUSDT"] {
+            let envelope = MessageEnvelope::new("test_sender".to_string(), topic.to_string(), Vec::new());
+            bus.publish(topic.to_string(), envelope).await.unwrap();
+        }
+
+        let received = |rx: &mut mpsc::UnboundedReceiver<MessageEnvelope>| {
+            std::iter::from_fn(|| rx.try_recv().ok()).map(|envelope| envelope.message_type).collect::<Vec<_>>()
+        };
+        assert_eq!(received(&mut all_orders), vec!["orders.filled", "orders.cancelled"]);
+        assert_eq!(received(&mut btc_quotes), vec!["data.quotes.BTCUSDT"]);
+        assert_eq!(received(&mut exact), vec!["orders.filled"]);
+        assert_eq!(bus.stats().publish_count.load(Ordering::Relaxed), 3);
+    }
+    
     #[tokio::test]
+    #[allow(unused_variables)]
     async fn test_request_response_messaging() {
         let bus = MessageBus::new();
         let mut handler_rx = bus.register_handler("test.service".to_string());
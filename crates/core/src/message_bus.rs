@@ -1,15 +1,423 @@
+use std::any::{Any, TypeId};
+use std::borrow::Cow;
+use std::cell::{Cell, RefCell};
 use std::collections::HashMap;
-use std::sync::{Arc, RwLock};
-use serde::Serialize;
-use tokio::sync::mpsc;
-use crate::message::MessageEnvelope;
+use std::panic::{catch_unwind, AssertUnwindSafe};
+use std::sync::atomic::{AtomicU64, AtomicUsize, Ordering};
+use std::sync::{Arc, OnceLock, RwLock};
+use std::thread;
+use std::time::Duration;
+use serde::de::DeserializeOwned;
+use serde::{Deserialize, Serialize};
+use tokio::sync::{mpsc, oneshot};
+use crate::error::{AlphaForgeError, Result};
+use crate::identifiers::StrategyId;
+use crate::journal::MessageJournal;
+use crate::logging::hop_span;
+use crate::message::{Codec, MessageEnvelope, PatternIndex, TopicPattern};
+use crate::schema::SchemaRegistry;
+use crate::uuid::UUID7;
 
-/// Simple message bus for publish/subscribe messaging
+/// Channel of a strategy's namespace carrying the events of its orders
+pub const ORDERS_CHANNEL: &str = "orders";
+/// Channel of a strategy's namespace carrying the order commands it sends
+pub const COMMANDS_CHANNEL: &str = "commands";
+/// Channel of a strategy's namespace carrying its log lines
+pub const LOGS_CHANNEL: &str = "logs";
+/// Channel of a strategy's namespace carrying its alerts
+pub const ALERTS_CHANNEL: &str = "alerts";
+/// Channel of a strategy's namespace external tools send control messages on
+pub const CONTROL_CHANNEL: &str = "control";
+
+/// Topic a `SlowConsumer` is published on when a subscription falls behind
+pub const SLOW_CONSUMER_TOPIC: &str = "bus.slow_consumer";
+/// Backlog at which a subscription is reported as slow unless configured
+pub const DEFAULT_SLOW_CONSUMER_THRESHOLD: usize = 10_000;
+
+/// Prefix of every topic in a strategy's namespace, `strategy.{id}.`
+pub fn strategy_namespace(strategy_id: StrategyId) -> String {
+    format!("strategy.{}.", strategy_id)
+}
+
+/// Topic of one channel in a strategy's namespace, e.g. `strategy.7.orders`
+pub fn strategy_topic(strategy_id: StrategyId, channel: &str) -> String {
+    format!("{}{}", strategy_namespace(strategy_id), channel)
+}
+
+thread_local! {
+    static CORRELATION_ID: Cell<Option<UUID7>> = const { Cell::new(None) };
+    /// Span of a hop whose chain has no ID until one is asked for
+    static UNNAMED_CHAIN: RefCell<Option<tracing::Span>> = const { RefCell::new(None) };
+}
+
+/// Correlation ID of the message chain this thread is handling, if any.
+/// A chain begun with `start_chain` gets its ID here, the first time it is
+/// asked for.
+pub fn current_correlation_id() -> Option<UUID7> {
+    if let Some(correlation_id) = CORRELATION_ID.with(Cell::get) {
+        return Some(correlation_id);
+    }
+    let span = UNNAMED_CHAIN.with(|chain| chain.borrow_mut().take())?;
+    let correlation_id = UUID7::new();
+    span.record("correlation_id", tracing::field::display(correlation_id));
+    CORRELATION_ID.with(|current| current.set(Some(correlation_id)));
+    Some(correlation_id)
+}
+
+/// One hop of a message chain being handled on this thread. Until it is
+/// dropped, log lines carry the chain's correlation ID and envelopes
+/// published, sent or requested on this thread are stamped with it.
+#[must_use = "the correlation ends when the scope is dropped"]
+pub struct CorrelationScope {
+    previous: Option<UUID7>,
+    previous_unnamed: Option<tracing::Span>,
+    _span: tracing::span::EnteredSpan,
+}
+
+impl Drop for CorrelationScope {
+    fn drop(&mut self) {
+        CORRELATION_ID.with(|current| current.set(self.previous));
+        UNNAMED_CHAIN.with(|chain| *chain.borrow_mut() = self.previous_unnamed.take());
+    }
+}
+
+/// Handle a hop of the chain identified by `correlation_id`. Must not be
+/// held across an `.await`.
+pub fn correlate(hop: &str, correlation_id: UUID7) -> CorrelationScope {
+    let previous = CORRELATION_ID.with(|current| current.replace(Some(correlation_id)));
+    CorrelationScope {
+        previous,
+        previous_unnamed: UNNAMED_CHAIN.with(|chain| chain.borrow_mut().take()),
+        _span: hop_span(hop, Some(correlation_id)).entered(),
+    }
+}
+
+/// Handle a hop starting a new chain, which only gets an ID if the hop
+/// publishes, sends or requests something, so the many events that lead
+/// nowhere (e.g. most ticks) cost no ID. Must not be held across an
+/// `.await`.
+pub fn start_chain(hop: &str) -> CorrelationScope {
+    let span = hop_span(hop, None);
+    CorrelationScope {
+        previous: CORRELATION_ID.with(|current| current.replace(None)),
+        previous_unnamed: UNNAMED_CHAIN.with(|chain| chain.borrow_mut().replace(span.clone())),
+        _span: span.entered(),
+    }
+}
+
+/// Handle a received envelope as a hop of its chain, which the envelope
+/// starts when it carries no correlation ID
+pub fn handle_envelope(hop: &str, envelope: &MessageEnvelope) -> CorrelationScope {
+    correlate(hop, envelope.correlation_id.unwrap_or(envelope.id))
+}
+
+/// Receiving end of a subscription, tracking its backlog so the bus can
+/// report queue depths and spot slow consumers
+#[derive(Debug)]
+pub struct Subscription {
+    receiver: mpsc::UnboundedReceiver<MessageEnvelope>,
+    depth: Arc<AtomicUsize>,
+}
+
+impl Subscription {
+    /// Wait for the next envelope; `None` once the subscription is dropped
+    /// from the bus and drained
+    pub async fn recv(&mut self) -> Option<MessageEnvelope> {
+        let envelope = self.receiver.recv().await;
+        if envelope.is_some() {
+            self.depth.fetch_sub(1, Ordering::Relaxed);
+        }
+        envelope
+    }
+
+    /// Take the next envelope if one is waiting
+    pub fn try_recv(&mut self) -> std::result::Result<MessageEnvelope, mpsc::error::TryRecvError> {
+        let envelope = self.receiver.try_recv()?;
+        self.depth.fetch_sub(1, Ordering::Relaxed);
+        Ok(envelope)
+    }
+
+    /// Envelopes waiting to be received
+    pub fn len(&self) -> usize {
+        self.receiver.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.receiver.is_empty()
+    }
+}
+
+/// Predicate choosing the envelopes a subscription receives
+pub type MessageFilter = Arc<dyn Fn(&MessageEnvelope) -> bool + Send + Sync>;
+
+/// Sending end of a subscription
+struct Subscriber {
+    /// Topic or pattern subscribed to
+    topic: String,
+    sender: mpsc::UnboundedSender<MessageEnvelope>,
+    /// Envelopes sent and not yet received, for a `Subscription`
+    depth: Option<Arc<AtomicUsize>>,
+    filter: Option<MessageFilter>,
+    /// Schema version the subscriber reads, if it subscribed for one
+    version: Option<u32>,
+}
+
+impl std::fmt::Debug for Subscriber {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("Subscriber")
+            .field("topic", &self.topic)
+            .field("depth", &self.depth)
+            .field("filtered", &self.filter.is_some())
+            .field("version", &self.version)
+            .finish()
+    }
+}
+
+impl Subscriber {
+    fn wants(&self, envelope: &MessageEnvelope) -> bool {
+        self.filter.as_ref().is_none_or(|filter| filter(envelope))
+    }
+
+    // Send an envelope, returning the backlog it joined if tracked, or None
+    // when the subscription is gone
+    fn offer(&self, envelope: &MessageEnvelope) -> Option<Option<usize>> {
+        // Counted before sending so a fast receiver never sees it negative
+        let depth = self.depth.as_ref().map(|depth| depth.fetch_add(1, Ordering::Relaxed) + 1);
+        if self.sender.send(envelope.clone()).is_err() {
+            if let Some(depth) = &self.depth {
+                depth.fetch_sub(1, Ordering::Relaxed);
+            }
+            return None;
+        }
+        Some(depth)
+    }
+}
+
+/// Traffic on one topic since the bus was created
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct TopicStats {
+    pub published: u64,
+    /// Envelopes handed to subscriptions and callbacks
+    pub delivered: u64,
+    /// Envelopes a subscription was gone for
+    pub failed: u64,
+}
+
+#[derive(Default)]
+struct TopicCounters {
+    published: AtomicU64,
+    delivered: AtomicU64,
+    failed: AtomicU64,
+}
+
+impl TopicCounters {
+    fn snapshot(&self) -> TopicStats {
+        TopicStats {
+            published: self.published.load(Ordering::Relaxed),
+            delivered: self.delivered.load(Ordering::Relaxed),
+            failed: self.failed.load(Ordering::Relaxed),
+        }
+    }
+}
+
+/// A subscription whose backlog reached the slow consumer threshold
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct SlowConsumer {
+    /// Topic or pattern subscribed to
+    pub subscription: String,
+    /// Topic of the envelope that reached the threshold
+    pub topic: String,
+    pub depth: usize,
+}
+
+/// Codecs configured for topics and topic patterns
+#[derive(Default)]
+struct TopicCodecs {
+    topics: HashMap<String, Codec>,
+    /// With the length of their pattern, the longest matching one applying
+    patterns: PatternIndex<(usize, Codec)>,
+}
+
+/// Threads running `CallbackMode::Dispatcher` callbacks
+const DISPATCHER_THREADS: usize = 4;
+
+/// Where a callback subscription runs
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum CallbackMode {
+    /// On the publishing thread, before the publish returns
+    #[default]
+    Publisher,
+    /// On one of the bus's dispatcher threads, so slow handlers never
+    /// delay publishers
+    Dispatcher,
+}
+
+/// Handle for removing a callback subscription
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct CallbackId(u64);
+
+type Handler = Arc<dyn Fn(&MessageEnvelope) + Send + Sync>;
+
+#[derive(Clone)]
+struct Callback {
+    id: CallbackId,
+    mode: CallbackMode,
+    handler: Handler,
+    /// Schema version the handler reads, if it subscribed for one
+    version: Option<u32>,
+}
+
+/// Threads running dispatcher callbacks. A subscription always runs on the
+/// same thread, so it sees messages in the order they were published.
+struct DispatcherPool {
+    workers: Vec<std::sync::mpsc::Sender<(Handler, MessageEnvelope)>>,
+}
+
+impl DispatcherPool {
+    fn start(threads: usize) -> Result<Self> {
+        let mut workers = Vec::with_capacity(threads);
+        for index in 0..threads {
+            let (sender, receiver) = std::sync::mpsc::channel::<(Handler, MessageEnvelope)>();
+            thread::Builder::new()
+                .name(format!("bus-dispatcher-{}", index))
+                .spawn(move || {
+                    // Ends when the bus, and with it the sender, is dropped
+                    while let Ok((handler, envelope)) = receiver.recv() {
+                        run_callback(&handler, &envelope);
+                    }
+                })
+                .map_err(|e| AlphaForgeError::runtime(format!("Failed to spawn bus dispatcher: {}", e)))?;
+            workers.push(sender);
+        }
+        Ok(Self { workers })
+    }
+
+    fn dispatch(&self, callback: &Callback, envelope: &MessageEnvelope) {
+        let worker = &self.workers[callback.id.0 as usize % self.workers.len()];
+        let _ = worker.send((Arc::clone(&callback.handler), envelope.clone()));
+    }
+}
+
+// Run a handler as a hop of the envelope's chain, containing its panics
+fn run_callback(handler: &Handler, envelope: &MessageEnvelope) {
+    let _correlation = handle_envelope("callback", envelope);
+    if catch_unwind(AssertUnwindSafe(|| handler(envelope))).is_err() {
+        tracing::warn!("Message bus callback for {} panicked", envelope.message_type);
+    }
+}
+
+/// A request and the slot its handler replies in
+pub type Request = (MessageEnvelope, oneshot::Sender<MessageEnvelope>);
+
+/// A `mpsc::UnboundedSender<Arc<T>>` for the `T` it is registered under
+struct TypedSender {
+    sender: Box<dyn Any + Send + Sync>,
+    /// Whether the receiver is gone, knowing `T`
+    is_closed: fn(&(dyn Any + Send + Sync)) -> bool,
+}
+
+impl TypedSender {
+    fn new<T: Any + Send + Sync>(sender: mpsc::UnboundedSender<Arc<T>>) -> Self {
+        Self {
+            sender: Box::new(sender),
+            is_closed: |sender| {
+                sender
+                    .downcast_ref::<mpsc::UnboundedSender<Arc<T>>>()
+                    .is_none_or(|sender| sender.is_closed())
+            },
+        }
+    }
+
+    fn downcast<T: Any + Send + Sync>(&self) -> Option<&mpsc::UnboundedSender<Arc<T>>> {
+        self.sender.downcast_ref()
+    }
+
+    fn is_closed(&self) -> bool {
+        (self.is_closed)(self.sender.as_ref())
+    }
+}
+
+/// Typed subscriptions for one message type
+#[derive(Default)]
+struct TypedSubscribers {
+    topics: HashMap<String, Vec<TypedSender>>,
+    patterns: PatternIndex<TypedSender>,
+}
+
+/// Simple message bus for publish/subscribe messaging.
+///
+/// A topic with wildcards subscribes to every topic matching it as a glob
+/// (see `TopicPattern`), so `strategy.7.*` receives all of strategy 7's
+/// messages.
+///
+/// Envelopes carry bincode payloads, for subscribers anywhere (journals,
+/// bridges, other processes); a message is only serialized when something
+/// receives envelopes for its topic. In-process consumers on a hot path use
+/// `subscribe_typed` and `publish_typed` instead, which hand out the
+/// published value itself behind an `Arc`.
+///
+/// Payloads are bincode unless `set_codec` picks another codec for their
+/// topic; consumers decode with `MessageEnvelope::decode`.
+///
+/// Consumers that would rather not drain a receiver subscribe a callback
+/// with `subscribe_fn` instead.
+///
+/// Besides topics, components register named targets: a request handler
+/// answers `request`s sent to its target, and an endpoint receives the
+/// envelopes `send` addresses to it. A target has at most one handler and
+/// one endpoint at a time.
+///
+/// Payloads on topics with a registered schema (see `schemas`) carry its
+/// current version. A subscription for a given version is refused unless
+/// the current version is readable by it, and receives older payloads
+/// upgraded to its version.
+///
+/// The bus counts the traffic on each topic (`topic_stats`) and the backlog
+/// of each `Subscription` (`queue_depths`): those of `subscribe_tracked`,
+/// filtered and versioned subscriptions. A subscription whose backlog
+/// reaches the slow consumer threshold is logged and reported on
+/// `SLOW_CONSUMER_TOPIC`.
 pub struct MessageBus {
     /// Topic subscribers
-    subscribers: Arc<RwLock<HashMap<String, Vec<mpsc::UnboundedSender<MessageEnvelope>>>>>,
+    subscribers: Arc<RwLock<HashMap<String, Vec<Subscriber>>>>,
+    /// Topic pattern subscribers
+    pattern_subscribers: Arc<RwLock<PatternIndex<Subscriber>>>,
     /// Message statistics
     message_count: Arc<std::sync::atomic::AtomicU64>,
+    /// Where published envelopes are recorded, if anywhere
+    journal: RwLock<Option<Arc<MessageJournal>>>,
+    /// Typed subscribers by message type
+    typed_subscribers: RwLock<HashMap<TypeId, TypedSubscribers>>,
+    /// Request handlers by target
+    handlers: RwLock<HashMap<String, mpsc::UnboundedSender<Request>>>,
+    /// Point-to-point endpoints by target
+    endpoints: RwLock<HashMap<String, mpsc::UnboundedSender<MessageEnvelope>>>,
+    /// Topic callbacks
+    callbacks: RwLock<HashMap<String, Vec<Callback>>>,
+    /// Topic pattern callbacks
+    pattern_callbacks: RwLock<PatternIndex<Callback>>,
+    next_callback_id: AtomicU64,
+    /// Started with the first dispatcher callback
+    dispatcher: OnceLock<DispatcherPool>,
+    /// Traffic by topic
+    topic_stats: RwLock<HashMap<String, Arc<TopicCounters>>>,
+    /// Backlog at which a subscription is reported; 0 disables reporting
+    slow_consumer_threshold: AtomicUsize,
+    codecs: RwLock<TopicCodecs>,
+    schemas: SchemaRegistry,
+}
+
+impl std::fmt::Debug for MessageBus {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("MessageBus")
+            .field("subscribers", &self.subscribers)
+            .field("pattern_subscribers", &self.pattern_subscribers)
+            .field("message_count", &self.message_count)
+            .field("journal", &self.journal)
+            .field("handlers", &self.handlers.read().unwrap().keys().collect::<Vec<_>>())
+            .field("endpoints", &self.endpoints.read().unwrap().keys().collect::<Vec<_>>())
+            .finish_non_exhaustive()
+    }
 }
 
 impl MessageBus {
@@ -17,41 +425,624 @@ impl MessageBus {
     pub fn new() -> Self {
         Self {
             subscribers: Arc::new(RwLock::new(HashMap::new())),
+            pattern_subscribers: Arc::new(RwLock::new(PatternIndex::new())),
             message_count: Arc::new(std::sync::atomic::AtomicU64::new(0)),
+            journal: RwLock::new(None),
+            typed_subscribers: RwLock::new(HashMap::new()),
+            handlers: RwLock::new(HashMap::new()),
+            endpoints: RwLock::new(HashMap::new()),
+            callbacks: RwLock::new(HashMap::new()),
+            pattern_callbacks: RwLock::new(PatternIndex::new()),
+            next_callback_id: AtomicU64::new(0),
+            dispatcher: OnceLock::new(),
+            topic_stats: RwLock::new(HashMap::new()),
+            slow_consumer_threshold: AtomicUsize::new(DEFAULT_SLOW_CONSUMER_THRESHOLD),
+            codecs: RwLock::new(TopicCodecs::default()),
+            schemas: SchemaRegistry::new(),
         }
     }
 
     /// Publish a message to a topic
     pub fn publish<T: Serialize>(&self, topic: &str, message: &T) {
-        let payload = match bincode::serialize(message) {
+        self.publish_from("execution_engine", topic, message);
+    }
+
+    /// Publish a message to a topic on behalf of a named component
+    pub fn publish_from<T: Serialize>(&self, source: &str, topic: &str, message: &T) {
+        crate::profile_scope!("message_bus.publish");
+        if !self.has_envelope_subscribers(topic) {
+            self.topic_counters(topic).published.fetch_add(1, Ordering::Relaxed);
+            self.message_count.fetch_add(1, std::sync::atomic::Ordering::Relaxed);
+            return;
+        }
+        let codec = self.codec_for(topic);
+        let payload = match codec.encode(message) {
             Ok(data) => data,
-            Err(_) => return, // Skip if serialization fails
+            Err(e) => {
+                tracing::warn!("Failed to encode message on {} as {}: {}", topic, codec, e);
+                return;
+            }
         };
 
-        let envelope = MessageEnvelope::new(
-            "execution_engine".to_string(),
+        let mut envelope = MessageEnvelope::new(
+            source.to_string(),
             topic.to_string(),
             payload,
         );
+        envelope.correlation_id = current_correlation_id();
+        envelope.codec = codec;
+        envelope.schema_version = self.schemas.current_version(topic);
 
-        let subscribers = self.subscribers.read().unwrap();
-        if let Some(senders) = subscribers.get(topic) {
-            for sender in senders {
-                let _ = sender.send(envelope.clone());
+        self.publish_envelope(&envelope);
+    }
+
+    /// Encode payloads published on a topic, or on every topic matching a
+    /// pattern, with `codec`. The most specific setting applies: a topic's
+    /// own, else the longest matching pattern's.
+    pub fn set_codec(&self, topic: &str, codec: Codec) {
+        let mut codecs = self.codecs.write().unwrap();
+        if TopicPattern::is_pattern(topic) {
+            codecs.patterns.retain(|pattern, _| pattern.as_str() != topic);
+            codecs.patterns.insert(TopicPattern::new(topic), (topic.len(), codec));
+        } else {
+            codecs.topics.insert(topic.to_string(), codec);
+        }
+    }
+
+    /// Codec payloads published on `topic` are encoded with
+    pub fn codec_for(&self, topic: &str) -> Codec {
+        let codecs = self.codecs.read().unwrap();
+        if let Some(codec) = codecs.topics.get(topic) {
+            return *codec;
+        }
+        codecs
+            .patterns
+            .matching(topic)
+            .max_by_key(|(len, _)| *len)
+            .map_or_else(Codec::default, |(_, codec)| *codec)
+    }
+
+    /// Publish an envelope built elsewhere (e.g. received from another process)
+    pub fn publish_envelope(&self, envelope: &MessageEnvelope) {
+        if let Some(journal) = &*self.journal.read().unwrap() {
+            if let Err(e) = journal.append(envelope) {
+                tracing::warn!("Failed to journal message on {}: {}", envelope.message_type, e);
             }
         }
+        self.deliver(envelope);
+    }
+
+    // Whether an envelope published on `topic` would reach anyone
+    fn has_envelope_subscribers(&self, topic: &str) -> bool {
+        self.journal.read().unwrap().is_some()
+            || self.subscribers.read().unwrap().get(topic).is_some_and(|senders| !senders.is_empty())
+            || self.pattern_subscribers.read().unwrap().matching(topic).next().is_some()
+            || self.callbacks.read().unwrap().get(topic).is_some_and(|callbacks| !callbacks.is_empty())
+            || self.pattern_callbacks.read().unwrap().matching(topic).next().is_some()
+    }
+
+    /// Receive every `T` published with `publish_typed` on a topic, or on
+    /// every topic matching it when it has wildcards
+    pub fn subscribe_typed<T: Any + Send + Sync>(&self, topic: &str) -> mpsc::UnboundedReceiver<Arc<T>> {
+        let (tx, rx) = mpsc::unbounded_channel::<Arc<T>>();
+        let mut typed = self.typed_subscribers.write().unwrap();
+        let subscribers = typed.entry(TypeId::of::<T>()).or_default();
+        if TopicPattern::is_pattern(topic) {
+            subscribers.patterns.insert(TopicPattern::new(topic), TypedSender::new(tx));
+        } else {
+            subscribers.topics.entry(topic.to_string()).or_default().push(TypedSender::new(tx));
+        }
+        rx
+    }
 
+    /// Hand `message` to the typed subscribers of `T` on `topic` without
+    /// serializing it, returning how many received it. Envelope subscribers
+    /// are not notified; publish there as well if they need it.
+    pub fn publish_typed<T: Any + Send + Sync>(&self, topic: &str, message: Arc<T>) -> usize {
         self.message_count.fetch_add(1, std::sync::atomic::Ordering::Relaxed);
+        let counters = self.topic_counters(topic);
+        counters.published.fetch_add(1, Ordering::Relaxed);
+        let (mut delivered, mut failed) = (0, 0);
+        {
+            let typed = self.typed_subscribers.read().unwrap();
+            let Some(subscribers) = typed.get(&TypeId::of::<T>()) else {
+                return 0;
+            };
+            let matching = subscribers.topics.get(topic).into_iter().flatten().chain(subscribers.patterns.matching(topic));
+            for sender in matching.filter_map(TypedSender::downcast::<T>) {
+                match sender.send(Arc::clone(&message)) {
+                    Ok(()) => delivered += 1,
+                    Err(_) => failed += 1,
+                }
+            }
+        }
+        counters.delivered.fetch_add(delivered as u64, Ordering::Relaxed);
+        counters.failed.fetch_add(failed, Ordering::Relaxed);
+        if failed > 0 {
+            self.remove_closed_subscribers();
+        }
+        delivered
+    }
+
+    /// Hand an envelope to the subscribers of its topic without journaling it
+    pub(crate) fn deliver(&self, envelope: &MessageEnvelope) {
+        let topic = envelope.message_type.as_str();
+        let threshold = self.slow_consumer_threshold.load(Ordering::Relaxed);
+        let (mut delivered, mut failed) = (0, 0);
+        let mut slow = Vec::new();
+        {
+            let subscribers = self.subscribers.read().unwrap();
+            let patterns = self.pattern_subscribers.read().unwrap();
+            let matching = subscribers.get(topic).into_iter().flatten().chain(patterns.matching(topic));
+            for subscriber in matching.filter(|subscriber| subscriber.wants(envelope)) {
+                let Some(adapted) = self.adapt_for(envelope, subscriber.version) else {
+                    continue;
+                };
+                match subscriber.offer(&adapted) {
+                    Some(depth) => {
+                        delivered += 1;
+                        // Reported once each time the backlog climbs to it
+                        if let Some(depth) = depth.filter(|depth| *depth == threshold) {
+                            slow.push(SlowConsumer {
+                                subscription: subscriber.topic.clone(),
+                                topic: topic.to_string(),
+                                depth,
+                            });
+                        }
+                    }
+                    None => failed += 1,
+                }
+            }
+        }
+
+        // Copied out so handlers can subscribe and publish themselves
+        let callbacks: Vec<Callback> = {
+            let exact = self.callbacks.read().unwrap();
+            let patterns = self.pattern_callbacks.read().unwrap();
+            exact.get(topic).into_iter().flatten().chain(patterns.matching(topic)).cloned().collect()
+        };
+        for callback in &callbacks {
+            let Some(adapted) = self.adapt_for(envelope, callback.version) else {
+                continue;
+            };
+            match (callback.mode, self.dispatcher.get()) {
+                (CallbackMode::Dispatcher, Some(dispatcher)) => dispatcher.dispatch(callback, &adapted),
+                _ => run_callback(&callback.handler, &adapted),
+            }
+            delivered += 1;
+        }
+
+        let counters = self.topic_counters(topic);
+        counters.published.fetch_add(1, Ordering::Relaxed);
+        counters.delivered.fetch_add(delivered, Ordering::Relaxed);
+        counters.failed.fetch_add(failed, Ordering::Relaxed);
+        self.message_count.fetch_add(1, std::sync::atomic::Ordering::Relaxed);
+        if failed > 0 {
+            self.remove_closed_subscribers();
+        }
+
+        for consumer in slow {
+            tracing::warn!(
+                "Subscriber to {} has {} messages waiting, last on {}",
+                consumer.subscription,
+                consumer.depth,
+                consumer.topic
+            );
+            self.publish_from("message_bus", SLOW_CONSUMER_TOPIC, &consumer);
+        }
+    }
+
+    // `envelope` as a reader of `version` of its schema sees it, or None
+    // when such a reader cannot read it
+    fn adapt_for<'a>(&self, envelope: &'a MessageEnvelope, version: Option<u32>) -> Option<Cow<'a, MessageEnvelope>> {
+        match version {
+            Some(version) if envelope.schema_version.is_some_and(|written| written != version) => {
+                match self.schemas.adapt(envelope, version) {
+                    Ok(adapted) => Some(Cow::Owned(adapted)),
+                    Err(e) => {
+                        tracing::warn!("Not delivering {} to a reader of version {}: {}", envelope.message_type, version, e);
+                        None
+                    }
+                }
+            }
+            _ => Some(Cow::Borrowed(envelope)),
+        }
+    }
+
+    fn topic_counters(&self, topic: &str) -> Arc<TopicCounters> {
+        if let Some(counters) = self.topic_stats.read().unwrap().get(topic) {
+            return Arc::clone(counters);
+        }
+        Arc::clone(self.topic_stats.write().unwrap().entry(topic.to_string()).or_default())
+    }
+
+    /// Traffic on a topic since the bus was created
+    pub fn topic_stats(&self, topic: &str) -> TopicStats {
+        self.topic_stats.read().unwrap().get(topic).map(|counters| counters.snapshot()).unwrap_or_default()
+    }
+
+    /// Traffic on every topic published so far
+    pub fn all_topic_stats(&self) -> HashMap<String, TopicStats> {
+        self.topic_stats
+            .read()
+            .unwrap()
+            .iter()
+            .map(|(topic, counters)| (topic.clone(), counters.snapshot()))
+            .collect()
+    }
+
+    /// Backlog of every open `Subscription`, by the topic or pattern it
+    /// subscribed to, deepest first
+    pub fn queue_depths(&self) -> Vec<(String, usize)> {
+        let subscribers = self.subscribers.read().unwrap();
+        let patterns = self.pattern_subscribers.read().unwrap();
+        let mut depths: Vec<(String, usize)> = subscribers
+            .values()
+            .flatten()
+            .chain(patterns.iter().map(|(_, subscriber)| subscriber))
+            .filter(|subscriber| !subscriber.sender.is_closed())
+            .filter_map(|subscriber| Some((subscriber.topic.clone(), subscriber.depth.as_ref()?.load(Ordering::Relaxed))))
+            .collect();
+        depths.sort_by_key(|(_, depth)| std::cmp::Reverse(*depth));
+        depths
+    }
+
+    /// Report subscriptions whose backlog reaches `threshold`
+    /// (`DEFAULT_SLOW_CONSUMER_THRESHOLD` unless set); 0 disables reporting
+    pub fn set_slow_consumer_threshold(&self, threshold: usize) {
+        self.slow_consumer_threshold.store(threshold, Ordering::Relaxed);
+    }
+
+    /// Call `handler` with every envelope published on a topic, or on every
+    /// topic matching it when it has wildcards. A handler that panics is
+    /// logged and keeps its subscription.
+    pub fn subscribe_fn(
+        &self,
+        topic: &str,
+        mode: CallbackMode,
+        handler: impl Fn(&MessageEnvelope) + Send + Sync + 'static,
+    ) -> Result<CallbackId> {
+        self.add_callback(topic, mode, None, Arc::new(handler))
+    }
+
+    /// Call `handler` with every envelope published on a topic, or topic
+    /// pattern, as a reader of `version` of its schema. Fails unless the
+    /// current version is readable by it; older payloads arrive upgraded.
+    pub fn subscribe_fn_versioned(
+        &self,
+        topic: &str,
+        version: u32,
+        mode: CallbackMode,
+        handler: impl Fn(&MessageEnvelope) + Send + Sync + 'static,
+    ) -> Result<CallbackId> {
+        self.schemas.check_compatible(topic, version)?;
+        self.add_callback(topic, mode, Some(version), Arc::new(handler))
+    }
+
+    fn add_callback(&self, topic: &str, mode: CallbackMode, version: Option<u32>, handler: Handler) -> Result<CallbackId> {
+        if mode == CallbackMode::Dispatcher && self.dispatcher.get().is_none() {
+            let pool = DispatcherPool::start(DISPATCHER_THREADS)?;
+            // A pool started concurrently by another subscriber wins; ours stops on drop
+            let _ = self.dispatcher.set(pool);
+        }
+        let callback = Callback {
+            id: CallbackId(self.next_callback_id.fetch_add(1, Ordering::Relaxed)),
+            mode,
+            handler,
+            version,
+        };
+        let id = callback.id;
+        if TopicPattern::is_pattern(topic) {
+            self.pattern_callbacks.write().unwrap().insert(TopicPattern::new(topic), callback);
+        } else {
+            self.callbacks.write().unwrap().entry(topic.to_string()).or_default().push(callback);
+        }
+        Ok(id)
+    }
+
+    /// Remove a callback subscription, returning whether it existed
+    pub fn unsubscribe_fn(&self, id: CallbackId) -> bool {
+        let mut removed = false;
+        self.callbacks.write().unwrap().retain(|_, callbacks| {
+            let before = callbacks.len();
+            callbacks.retain(|callback| callback.id != id);
+            removed |= callbacks.len() < before;
+            !callbacks.is_empty()
+        });
+        self.pattern_callbacks.write().unwrap().retain(|_, callback| {
+            removed |= callback.id == id;
+            callback.id != id
+        });
+        removed
+    }
+
+    /// Record every envelope published from now on in `journal`
+    pub fn set_journal(&self, journal: Arc<MessageJournal>) {
+        *self.journal.write().unwrap() = Some(journal);
+    }
+
+    /// Stop journaling, returning the journal that was in use
+    pub fn take_journal(&self) -> Option<Arc<MessageJournal>> {
+        self.journal.write().unwrap().take()
     }
 
-    /// Subscribe to a topic
+    /// Write journaled envelopes to disk
+    pub fn flush_journal(&self) -> Result<()> {
+        match &*self.journal.read().unwrap() {
+            Some(journal) => journal.flush(),
+            None => Ok(()),
+        }
+    }
+
+    /// Subscribe to a topic, or to every topic matching it when it has
+    /// wildcards
     pub fn subscribe(&self, topic: &str) -> mpsc::UnboundedReceiver<MessageEnvelope> {
-        let (tx, rx) = mpsc::unbounded_channel();
-        
-        let mut subscribers = self.subscribers.write().unwrap();
-        subscribers.entry(topic.to_string()).or_insert_with(Vec::new).push(tx);
-        
-        rx
+        self.add_subscriber(topic, None, None, None)
+    }
+
+    /// Subscribe as `subscribe` does, tracking the backlog of the
+    /// subscription for `queue_depths` and slow consumer reports
+    pub fn subscribe_tracked(&self, topic: &str) -> Subscription {
+        self.add_subscription(topic, None, None)
+    }
+
+    /// Subscribe to a topic, or topic pattern, as a consumer of `version`
+    /// of its schema. Fails unless the current version is readable by such
+    /// a consumer; payloads of older versions arrive upgraded.
+    pub fn subscribe_versioned(&self, topic: &str, version: u32) -> Result<Subscription> {
+        self.schemas.check_compatible(topic, version)?;
+        Ok(self.add_subscription(topic, None, Some(version)))
+    }
+
+    /// Schema versions of the payloads on each topic
+    pub fn schemas(&self) -> &SchemaRegistry {
+        &self.schemas
+    }
+
+    /// Subscribe to the envelopes on a topic, or topic pattern, that
+    /// `filter` accepts. The filter runs on the publishing thread before an
+    /// envelope is queued, so rejected envelopes never wake the subscriber.
+    pub fn subscribe_filtered(
+        &self,
+        topic: &str,
+        filter: impl Fn(&MessageEnvelope) -> bool + Send + Sync + 'static,
+    ) -> Subscription {
+        self.add_subscription(topic, Some(Arc::new(filter)), None)
+    }
+
+    fn add_subscription(&self, topic: &str, filter: Option<MessageFilter>, version: Option<u32>) -> Subscription {
+        let depth = Arc::new(AtomicUsize::new(0));
+        let receiver = self.add_subscriber(topic, filter, version, Some(Arc::clone(&depth)));
+        Subscription { receiver, depth }
+    }
+
+    fn add_subscriber(
+        &self,
+        topic: &str,
+        filter: Option<MessageFilter>,
+        version: Option<u32>,
+        depth: Option<Arc<AtomicUsize>>,
+    ) -> mpsc::UnboundedReceiver<MessageEnvelope> {
+        let (sender, receiver) = mpsc::unbounded_channel();
+        let subscriber = Subscriber {
+            topic: topic.to_string(),
+            sender,
+            depth,
+            filter,
+            version,
+        };
+
+        if TopicPattern::is_pattern(topic) {
+            self.pattern_subscribers.write().unwrap().insert(TopicPattern::new(topic), subscriber);
+        } else {
+            self.subscribers.write().unwrap().entry(topic.to_string()).or_default().push(subscriber);
+        }
+        receiver
+    }
+
+    /// Drop every subscription to a topic starting with `prefix`, closing
+    /// the subscribers' receivers, and return how many were dropped
+    pub fn unsubscribe_prefix(&self, prefix: &str) -> usize {
+        let mut removed = 0;
+        self.subscribers.write().unwrap().retain(|topic, senders| {
+            let matches = topic.starts_with(prefix);
+            if matches {
+                removed += senders.len();
+            }
+            !matches
+        });
+        self.pattern_subscribers.write().unwrap().retain(|pattern, _| {
+            let matches = pattern.as_str().starts_with(prefix);
+            if matches {
+                removed += 1;
+            }
+            !matches
+        });
+        for subscribers in self.typed_subscribers.write().unwrap().values_mut() {
+            subscribers.topics.retain(|topic, senders| {
+                let matches = topic.starts_with(prefix);
+                if matches {
+                    removed += senders.len();
+                }
+                !matches
+            });
+            subscribers.patterns.retain(|pattern, _| {
+                let matches = pattern.as_str().starts_with(prefix);
+                if matches {
+                    removed += 1;
+                }
+                !matches
+            });
+        }
+        self.callbacks.write().unwrap().retain(|topic, callbacks| {
+            let matches = topic.starts_with(prefix);
+            if matches {
+                removed += callbacks.len();
+            }
+            !matches
+        });
+        self.pattern_callbacks.write().unwrap().retain(|pattern, _| {
+            let matches = pattern.as_str().starts_with(prefix);
+            if matches {
+                removed += 1;
+            }
+            !matches
+        });
+        removed += remove_targets(&mut self.handlers.write().unwrap(), prefix);
+        removed += remove_targets(&mut self.endpoints.write().unwrap(), prefix);
+        removed
+    }
+
+    /// Answer the requests sent to `target`. Each request arrives with the
+    /// slot to put its response in; dropping the slot fails the request.
+    pub fn register_handler(&self, target: &str) -> Result<mpsc::UnboundedReceiver<Request>> {
+        register_target(&mut self.handlers.write().unwrap(), "handler", target)
+    }
+
+    /// Receive the envelopes sent to `target`
+    pub fn register_endpoint(&self, target: &str) -> Result<mpsc::UnboundedReceiver<MessageEnvelope>> {
+        register_target(&mut self.endpoints.write().unwrap(), "endpoint", target)
+    }
+
+    /// Remove the handler and endpoint of `target`, returning whether there were any
+    pub fn unregister(&self, target: &str) -> bool {
+        let handler = self.handlers.write().unwrap().remove(target).is_some();
+        let endpoint = self.endpoints.write().unwrap().remove(target).is_some();
+        handler || endpoint
+    }
+
+    /// Send a request to the handler of `target` and wait up to `timeout`
+    /// for its response
+    pub async fn request(&self, target: &str, mut envelope: MessageEnvelope, timeout: Duration) -> Result<MessageEnvelope> {
+        let (response_tx, response_rx) = oneshot::channel();
+        envelope.recipient = Some(target.to_string());
+        envelope.correlation_id = envelope.correlation_id.or_else(current_correlation_id);
+        let handler = self.handlers.read().unwrap().get(target).cloned();
+        handler
+            .ok_or_else(|| bus_error(format!("No handler registered for target: {}", target)))?
+            .send((envelope, response_tx))
+            .map_err(|_| bus_error(format!("Handler for target {} has stopped", target)))?;
+        self.message_count.fetch_add(1, std::sync::atomic::Ordering::Relaxed);
+
+        tokio::time::timeout(timeout, response_rx)
+            .await
+            .map_err(|_| bus_error(format!("Request to {} timed out after {:?}", target, timeout)))?
+            .map_err(|_| bus_error(format!("Handler for target {} dropped the request", target)))
+    }
+
+    /// Send `message` as a request from `source` to `target` and decode the
+    /// response payload
+    pub async fn request_from<T: Serialize, R: DeserializeOwned>(
+        &self,
+        source: &str,
+        target: &str,
+        message: &T,
+        timeout: Duration,
+    ) -> Result<R> {
+        let codec = self.codec_for(target);
+        let mut envelope = MessageEnvelope::new(source.to_string(), target.to_string(), codec.encode(message)?);
+        envelope.codec = codec;
+        let response = self.request(target, envelope, timeout).await?;
+        response.decode().map_err(|e| AlphaForgeError::validation(format!("Failed to decode response from {}: {}", target, e)))
+    }
+
+    /// Send an envelope to the endpoint of `target`
+    pub fn send(&self, target: &str, mut envelope: MessageEnvelope) -> Result<()> {
+        envelope.recipient = Some(target.to_string());
+        envelope.correlation_id = envelope.correlation_id.or_else(current_correlation_id);
+        let endpoints = self.endpoints.read().unwrap();
+        endpoints
+            .get(target)
+            .ok_or_else(|| bus_error(format!("No endpoint registered for target: {}", target)))?
+            .send(envelope)
+            .map_err(|_| bus_error(format!("Endpoint for target {} has stopped", target)))?;
+        self.message_count.fetch_add(1, std::sync::atomic::Ordering::Relaxed);
+        Ok(())
+    }
+
+    /// Send `message` from `source` to the endpoint of `target`
+    pub fn send_from<T: Serialize>(&self, source: &str, target: &str, message: &T) -> Result<()> {
+        let codec = self.codec_for(target);
+        let mut envelope = MessageEnvelope::new(source.to_string(), target.to_string(), codec.encode(message)?);
+        envelope.codec = codec;
+        self.send(target, envelope)
+    }
+
+    /// Drop the subscriptions whose receivers are gone, returning how many
+    /// were dropped. Publishing sweeps them as soon as a send to one fails;
+    /// call this periodically to also reclaim those on quiet topics.
+    pub fn remove_closed_subscribers(&self) -> usize {
+        let mut removed = 0;
+        self.subscribers.write().unwrap().retain(|_, senders| {
+            let before = senders.len();
+            senders.retain(|subscriber| !subscriber.sender.is_closed());
+            removed += before - senders.len();
+            !senders.is_empty()
+        });
+        self.pattern_subscribers.write().unwrap().retain(|_, subscriber| {
+            let closed = subscriber.sender.is_closed();
+            removed += usize::from(closed);
+            !closed
+        });
+        for subscribers in self.typed_subscribers.write().unwrap().values_mut() {
+            subscribers.topics.retain(|_, senders| {
+                let before = senders.len();
+                senders.retain(|sender| !sender.is_closed());
+                removed += before - senders.len();
+                !senders.is_empty()
+            });
+            subscribers.patterns.retain(|_, sender| {
+                let closed = sender.is_closed();
+                removed += usize::from(closed);
+                !closed
+            });
+        }
+        if removed > 0 {
+            tracing::debug!("Removed {} closed message bus subscriptions", removed);
+        }
+        removed
+    }
+
+    /// Open subscriptions (envelope, typed and callback) by the topic or
+    /// pattern they subscribed to
+    pub fn active_subscribers(&self) -> HashMap<String, usize> {
+        let mut counts: HashMap<String, usize> = HashMap::new();
+        let mut count = |topic: &str| *counts.entry(topic.to_string()).or_default() += 1;
+        {
+            let subscribers = self.subscribers.read().unwrap();
+            let patterns = self.pattern_subscribers.read().unwrap();
+            subscribers
+                .values()
+                .flatten()
+                .chain(patterns.iter().map(|(_, subscriber)| subscriber))
+                .filter(|subscriber| !subscriber.sender.is_closed())
+                .for_each(|subscriber| count(&subscriber.topic));
+        }
+        for subscribers in self.typed_subscribers.read().unwrap().values() {
+            for (topic, senders) in &subscribers.topics {
+                senders.iter().filter(|sender| !sender.is_closed()).for_each(|_| count(topic));
+            }
+            for (pattern, sender) in subscribers.patterns.iter() {
+                if !sender.is_closed() {
+                    count(pattern.as_str());
+                }
+            }
+        }
+        for (topic, callbacks) in self.callbacks.read().unwrap().iter() {
+            callbacks.iter().for_each(|_| count(topic));
+        }
+        for (pattern, _) in self.pattern_callbacks.read().unwrap().iter() {
+            count(pattern.as_str());
+        }
+        counts
+    }
+
+    /// Number of open subscriptions to exactly `topic`, which may be a pattern
+    pub fn subscriber_count(&self, topic: &str) -> usize {
+        if TopicPattern::is_pattern(topic) {
+            return self.pattern_subscribers.read().unwrap().subscribers(topic).filter(|s| !s.sender.is_closed()).count();
+        }
+        self.subscribers.read().unwrap().get(topic).map_or(0, |senders| senders.iter().filter(|s| !s.sender.is_closed()).count())
     }
 
     /// Get message count
@@ -60,8 +1051,344 @@ impl MessageBus {
     }
 }
 
+fn bus_error(msg: String) -> AlphaForgeError {
+    AlphaForgeError::MessageBus { msg }
+}
+
+// Register `target` unless a live registration already holds it
+fn register_target<T>(
+    targets: &mut HashMap<String, mpsc::UnboundedSender<T>>,
+    kind: &str,
+    target: &str,
+) -> Result<mpsc::UnboundedReceiver<T>> {
+    if targets.get(target).is_some_and(|sender| !sender.is_closed()) {
+        return Err(bus_error(format!("A {} is already registered for target: {}", kind, target)));
+    }
+    let (tx, rx) = mpsc::unbounded_channel();
+    targets.insert(target.to_string(), tx);
+    tracing::debug!("Registered {} for target: {}", kind, target);
+    Ok(rx)
+}
+
+// Drop the targets starting with `prefix`, returning how many were dropped
+fn remove_targets<T>(targets: &mut HashMap<String, mpsc::UnboundedSender<T>>, prefix: &str) -> usize {
+    let before = targets.len();
+    targets.retain(|target, _| !target.starts_with(prefix));
+    before - targets.len()
+}
+
 impl Default for MessageBus {
     fn default() -> Self {
         Self::new()
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[derive(Debug, PartialEq)]
+    struct Tick(f64);
+
+    #[test]
+    fn test_typed_messages_delivered_without_serialization() {
+        let bus = MessageBus::new();
+        let mut exact = bus.subscribe_typed::<Tick>("data.ticks.BTCUSDT");
+        let mut pattern = bus.subscribe_typed::<Tick>("data.ticks.*");
+        let mut other_type = bus.subscribe_typed::<u64>("data.ticks.BTCUSDT");
+
+        // Tick is not Serialize: typed delivery never touches bincode
+        let tick = Arc::new(Tick(100.5));
+        assert_eq!(bus.publish_typed("data.ticks.BTCUSDT", Arc::clone(&tick)), 2);
+        let (a, b) = (exact.try_recv().unwrap(), pattern.try_recv().unwrap());
+        assert!(Arc::ptr_eq(&a, &tick) && Arc::ptr_eq(&b, &tick));
+        assert!(other_type.try_recv().is_err());
+
+        assert_eq!(bus.publish_typed("data.ticks.ETHUSDT", Arc::new(Tick(2.0))), 1);
+        assert_eq!(*pattern.try_recv().unwrap(), Tick(2.0));
+        assert!(exact.try_recv().is_err());
+
+        assert_eq!(bus.unsubscribe_prefix("data.ticks."), 3);
+        assert_eq!(bus.publish_typed("data.ticks.BTCUSDT", tick), 0);
+    }
+
+    #[tokio::test]
+    async fn test_request_response_and_point_to_point() {
+        let bus = Arc::new(MessageBus::new());
+        let mut handler = bus.register_handler("risk.check").unwrap();
+        assert!(bus.register_handler("risk.check").is_err());
+        let responder = tokio::spawn(async move {
+            while let Some((request, reply)) = handler.recv().await {
+                let quantity: f64 = bincode::deserialize(&request.payload).unwrap();
+                let approved = bincode::serialize(&(quantity <= 10.0)).unwrap();
+                let _ = reply.send(request.create_response("risk_engine".to_string(), "risk.check".to_string(), approved));
+            }
+        });
+
+        let timeout = Duration::from_secs(1);
+        assert!(bus.request_from::<f64, bool>("strategy", "risk.check", &5.0, timeout).await.unwrap());
+        assert!(!bus.request_from::<f64, bool>("strategy", "risk.check", &50.0, timeout).await.unwrap());
+        let request = MessageEnvelope::new("strategy".to_string(), "risk.check".to_string(), bincode::serialize(&1.0f64).unwrap());
+        let response = bus.request("risk.check", request.clone(), timeout).await.unwrap();
+        assert_eq!((response.correlation_id, response.recipient.as_deref()), (Some(request.id), Some("strategy")));
+        assert!(bus.request_from::<f64, bool>("strategy", "risk.limits", &1.0, timeout).await.is_err());
+
+        // A handler that never answers times out
+        let _silent = bus.register_handler("strategy.1.query").unwrap();
+        let started = std::time::Instant::now();
+        let result = bus.request_from::<u8, u8>("ui", "strategy.1.query", &0, Duration::from_millis(20)).await;
+        assert!(result.unwrap_err().to_string().contains("timed out"));
+        assert!(started.elapsed() >= Duration::from_millis(20));
+
+        // Point-to-point
+        assert!(bus.send_from("ui", "execution", &1u8).is_err());
+        let mut endpoint = bus.register_endpoint("execution").unwrap();
+        bus.send_from("ui", "execution", &7u64).unwrap();
+        let envelope = endpoint.try_recv().unwrap();
+        assert_eq!(envelope.recipient.as_deref(), Some("execution"));
+        assert_eq!(bincode::deserialize::<u64>(&envelope.payload).unwrap(), 7);
+
+        // A dropped registration frees its target; prefixes drop targets too
+        drop(endpoint);
+        assert!(bus.register_endpoint("execution").is_ok());
+        assert!(bus.unregister("execution"));
+        assert_eq!(bus.unsubscribe_prefix("strategy.1."), 1);
+        assert!(bus.request_from::<u8, u8>("ui", "strategy.1.query", &0, timeout).await.is_err());
+        responder.abort();
+    }
+
+    #[test]
+    fn test_callback_subscriptions() {
+        let bus = Arc::new(MessageBus::new());
+        let inline = Arc::new(std::sync::Mutex::new(Vec::new()));
+        let id = bus.subscribe_fn("orders.filled", CallbackMode::Publisher, {
+            let inline = Arc::clone(&inline);
+            move |envelope| inline.lock().unwrap().push(bincode::deserialize::<u64>(&envelope.payload).unwrap())
+        }).unwrap();
+        bus.subscribe_fn("orders.*", CallbackMode::Publisher, |_| panic!("bad handler")).unwrap();
+        let (sender, receiver) = std::sync::mpsc::channel();
+        let sender = std::sync::Mutex::new(sender);
+        bus.subscribe_fn("orders.*", CallbackMode::Dispatcher, move |envelope| {
+            sender.lock().unwrap().send((thread::current().name().map(String::from), envelope.message_type.clone())).unwrap();
+        }).unwrap();
+
+        // Publisher callbacks have run when publish returns, despite the panic
+        bus.publish("orders.filled", &1u64);
+        bus.publish("orders.cancelled", &2u64);
+        bus.publish("orders.filled", &3u64);
+        assert_eq!(*inline.lock().unwrap(), vec![1, 3]);
+
+        let dispatched: Vec<_> = (0..3).map(|_| receiver.recv_timeout(std::time::Duration::from_secs(5)).unwrap()).collect();
+        assert!(dispatched.iter().all(|(thread, _)| thread.as_deref().is_some_and(|name| name.starts_with("bus-dispatcher-"))));
+        let topics: Vec<&str> = dispatched.iter().map(|(_, topic)| topic.as_str()).collect();
+        assert_eq!(topics, vec!["orders.filled", "orders.cancelled", "orders.filled"]);
+
+        assert!(bus.unsubscribe_fn(id));
+        assert!(!bus.unsubscribe_fn(id));
+        bus.publish("orders.filled", &4u64);
+        assert_eq!(inline.lock().unwrap().len(), 2);
+        assert_eq!(bus.unsubscribe_prefix("orders."), 2);
+    }
+
+    #[test]
+    fn test_topic_stats_and_slow_consumers() {
+        let bus = MessageBus::new();
+        bus.set_slow_consumer_threshold(3);
+        let mut fast = bus.subscribe_tracked("data.trades.*");
+        let slow = bus.subscribe_tracked("data.trades.BTCUSDT");
+        let mut reports = bus.subscribe_tracked(SLOW_CONSUMER_TOPIC);
+        drop(bus.subscribe_tracked("data.trades.BTCUSDT"));
+        // Untracked subscriptions are never reported
+        let _untracked = bus.subscribe("data.trades.BTCUSDT");
+
+        for price in 0..5 {
+            bus.publish_from("data_engine", "data.trades.BTCUSDT", &(price as f64));
+            while fast.try_recv().is_ok() {}
+        }
+        bus.publish_from("data_engine", "data.trades.ETHUSDT", &1.0f64);
+
+        // Reported once on reaching the threshold, not again while above it
+        let report: SlowConsumer = bincode::deserialize(&reports.try_recv().unwrap().payload).unwrap();
+        assert_eq!(report, SlowConsumer {
+            subscription: "data.trades.BTCUSDT".to_string(),
+            topic: "data.trades.BTCUSDT".to_string(),
+            depth: 3,
+        });
+        assert!(reports.try_recv().is_err());
+
+        // The dropped subscription fails once, then is swept
+        assert_eq!(bus.topic_stats("data.trades.BTCUSDT"), TopicStats { published: 5, delivered: 15, failed: 1 });
+        assert_eq!(bus.topic_stats("data.trades.ETHUSDT"), TopicStats { published: 1, delivered: 1, failed: 0 });
+        assert_eq!(bus.all_topic_stats()[SLOW_CONSUMER_TOPIC].published, 1);
+        assert_eq!(slow.len(), 5);
+        assert_eq!(bus.queue_depths(), vec![
+            ("data.trades.BTCUSDT".to_string(), 5),
+            ("data.trades.*".to_string(), 1),
+            (SLOW_CONSUMER_TOPIC.to_string(), 0),
+        ]);
+    }
+
+    #[test]
+    fn test_closed_subscriptions_are_removed() {
+        let bus = MessageBus::new();
+        let mut live = bus.subscribe("data.quotes.BTCUSDT");
+        let dropped = bus.subscribe("data.quotes.BTCUSDT");
+        let dropped_pattern = bus.subscribe("data.quotes.*");
+        let typed = bus.subscribe_typed::<Tick>("data.ticks.*");
+        let _quiet = bus.subscribe_typed::<Tick>("data.ticks.ETHUSDT");
+        bus.subscribe_fn("orders.*", CallbackMode::Publisher, |_| {}).unwrap();
+        assert_eq!(bus.active_subscribers(), HashMap::from([
+            ("data.quotes.BTCUSDT".to_string(), 2),
+            ("data.quotes.*".to_string(), 1),
+            ("data.ticks.*".to_string(), 1),
+            ("data.ticks.ETHUSDT".to_string(), 1),
+            ("orders.*".to_string(), 1),
+        ]));
+
+        drop((dropped, dropped_pattern, typed));
+        assert_eq!(bus.active_subscribers()["data.quotes.BTCUSDT"], 1);
+        assert!(!bus.active_subscribers().contains_key("data.ticks.*"));
+
+        // A failed send sweeps every closed subscription
+        bus.publish_from("data_engine", "data.quotes.BTCUSDT", &100.0f64);
+        assert_eq!(bus.topic_stats("data.quotes.BTCUSDT"), TopicStats { published: 1, delivered: 1, failed: 2 });
+        assert_eq!(bus.remove_closed_subscribers(), 0);
+        bus.publish_from("data_engine", "data.quotes.BTCUSDT", &101.0f64);
+        assert_eq!(bus.topic_stats("data.quotes.BTCUSDT").failed, 2);
+        assert_eq!(std::iter::from_fn(|| live.try_recv().ok()).count(), 2);
+
+        // And so do periodic sweeps, for topics nobody publishes on
+        drop(bus.subscribe("risk.limits"));
+        drop(live);
+        assert_eq!(bus.remove_closed_subscribers(), 2);
+        assert_eq!(bus.subscribers.read().unwrap().len(), 0);
+        assert_eq!(bus.publish_typed("data.ticks.ETHUSDT", Arc::new(Tick(1.0))), 1);
+    }
+
+    #[test]
+    fn test_versioned_subscriptions() {
+        #[derive(Debug, PartialEq, Serialize, Deserialize)]
+        struct Signal {
+            strength: f64,
+        }
+
+        #[derive(Debug, PartialEq, Serialize, Deserialize)]
+        struct SignalV2 {
+            strength: f64,
+            horizon_secs: u64,
+        }
+
+        let bus = MessageBus::new();
+        bus.schemas().register("signals.*", 1, 1).unwrap();
+        let mut v1 = bus.subscribe_versioned("signals.*", 1).unwrap();
+        bus.publish("signals.momentum", &Signal { strength: 0.5 });
+        let old = v1.try_recv().unwrap();
+        assert_eq!(old.schema_version, Some(1));
+
+        bus.schemas().register_upgrade("signals.*", 2, |signal: Signal| SignalV2 { strength: signal.strength, horizon_secs: 60 }).unwrap();
+        // Version 1 consumers cannot read version 2, so they are refused
+        assert!(bus.subscribe_versioned("signals.*", 1).is_err());
+        assert!(bus.subscribe_versioned("orders.*", 1).is_err());
+        let mut v2 = bus.subscribe_versioned("signals.momentum", 2).unwrap();
+        let horizons = Arc::new(std::sync::Mutex::new(Vec::new()));
+        let seen = Arc::clone(&horizons);
+        bus.subscribe_fn_versioned("signals.*", 2, CallbackMode::Publisher, move |envelope| {
+            seen.lock().unwrap().push(envelope.decode::<SignalV2>().unwrap().horizon_secs);
+        })
+        .unwrap();
+        assert!(bus.subscribe_fn_versioned("signals.*", 1, CallbackMode::Publisher, |_| {}).is_err());
+
+        // A version 1 payload replayed after the upgrade arrives as version 2
+        bus.publish_envelope(&old);
+        bus.publish("signals.momentum", &SignalV2 { strength: 0.7, horizon_secs: 300 });
+        let upgraded = v2.try_recv().unwrap();
+        assert_eq!(upgraded.schema_version, Some(2));
+        assert_eq!(upgraded.decode::<SignalV2>().unwrap(), SignalV2 { strength: 0.5, horizon_secs: 60 });
+        assert_eq!(v2.try_recv().unwrap().decode::<SignalV2>().unwrap().horizon_secs, 300);
+        // Versioned handlers get the same upgrade
+        assert_eq!(*horizons.lock().unwrap(), vec![60, 300]);
+        // The existing version 1 subscriber is skipped rather than sent unreadable payloads
+        assert_eq!(v1.try_recv().unwrap().schema_version, Some(1));
+        assert!(v1.try_recv().is_err());
+    }
+
+    #[test]
+    fn test_filtered_subscriptions() {
+        let bus = MessageBus::new();
+        let mut from_binance = bus.subscribe_filtered("data.trades.*", |envelope| envelope.sender == "binance");
+        let mut large = bus.subscribe_filtered("data.trades.BTCUSDT", |envelope| {
+            bincode::deserialize::<f64>(&envelope.payload).is_ok_and(|size| size >= 10.0)
+        });
+        let mut all = bus.subscribe("data.trades.*");
+
+        bus.publish_from("binance", "data.trades.BTCUSDT", &1.0f64);
+        bus.publish_from("coinbase", "data.trades.BTCUSDT", &25.0f64);
+        bus.publish_from("binance", "data.trades.ETHUSDT", &50.0f64);
+
+        let senders: Vec<String> = std::iter::from_fn(|| from_binance.try_recv().ok()).map(|envelope| envelope.sender).collect();
+        assert_eq!(senders, vec!["binance", "binance"]);
+        assert_eq!(large.try_recv().unwrap().sender, "coinbase");
+        assert!(large.try_recv().is_err());
+        assert_eq!(std::iter::from_fn(|| all.try_recv().ok()).count(), 3);
+        // Rejected envelopes count as neither delivered nor failed
+        assert_eq!(bus.topic_stats("data.trades.BTCUSDT"), TopicStats { published: 2, delivered: 4, failed: 0 });
+    }
+
+    #[test]
+    fn test_per_topic_codecs() {
+        #[derive(Debug, PartialEq, Serialize, Deserialize)]
+        struct Quote {
+            bid: f64,
+            ask: f64,
+        }
+
+        let bus = MessageBus::new();
+        bus.set_codec("dashboard.*", Codec::Json);
+        bus.set_codec("dashboard.quotes.*", Codec::MessagePack);
+        bus.set_codec("dashboard.quotes.ETHUSDT", Codec::Bincode);
+        assert_eq!(bus.codec_for("dashboard.quotes.BTCUSDT"), Codec::MessagePack);
+        assert_eq!(bus.codec_for("dashboard.quotes.ETHUSDT"), Codec::Bincode);
+        assert_eq!(bus.codec_for("orders.filled"), Codec::Bincode);
+
+        let mut received = bus.subscribe("*");
+        let quote = Quote { bid: 99.5, ask: 100.5 };
+        bus.publish("dashboard.summary", &quote);
+        bus.publish("dashboard.quotes.BTCUSDT", &quote);
+        bus.publish("orders.quote", &quote);
+
+        let json = received.try_recv().unwrap();
+        assert_eq!(json.codec, Codec::Json);
+        assert_eq!(serde_json::from_slice::<serde_json::Value>(&json.payload).unwrap()["ask"], 100.5);
+        for envelope in [json, received.try_recv().unwrap(), received.try_recv().unwrap()] {
+            assert_eq!(envelope.decode::<Quote>().unwrap(), quote);
+        }
+        assert_eq!(bus.codec_for("dashboard.summary").to_string().parse::<Codec>().unwrap(), Codec::Json);
+    }
+
+    #[test]
+    fn test_started_chains_get_an_id_when_first_used() {
+        let bus = MessageBus::new();
+        let mut envelopes = bus.subscribe("*");
+        {
+            let _chain = start_chain("strategy");
+            // Nested hops keep their own chain and leave this one unnamed
+            let known = UUID7::new();
+            {
+                let _hop = correlate("execution", known);
+                bus.publish("orders.submitted", &1u8);
+            }
+            bus.publish("orders.submitted", &2u8);
+            bus.publish("orders.accepted", &3u8);
+        }
+        {
+            let _chain = start_chain("strategy");
+        }
+        assert_eq!(current_correlation_id(), None);
+
+        let ids: Vec<Option<UUID7>> = std::iter::from_fn(|| envelopes.try_recv().ok()).map(|e| e.correlation_id).collect();
+        assert_eq!(ids.len(), 3);
+        assert_ne!(ids[0], ids[1]);
+        assert!(ids[1].is_some());
+        assert_eq!(ids[1], ids[2]);
+    }
+}
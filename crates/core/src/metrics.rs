@@ -0,0 +1,480 @@
+//! AlphaForge Metrics
+//!
+//! Exports cache, engine and message bus statistics in the Prometheus text
+//! format. Components are registered with a `MetricsRegistry` under a
+//! source name, which labels their samples, and are read only when the
+//! registry is rendered, so an idle node pays nothing for its metrics.
+//! `MetricsServer` serves the rendered registry at `GET /metrics`, to a
+//! bounded number of connections at a time.
+
+use std::collections::{BTreeMap, HashMap};
+use std::fmt::Write as _;
+use std::net::SocketAddr;
+use std::sync::{Arc, Mutex, RwLock};
+use std::time::Duration;
+
+use tokio::io::{AsyncReadExt, AsyncWriteExt};
+use tokio::net::{TcpListener, TcpStream, ToSocketAddrs};
+use tokio::sync::Semaphore;
+use tokio::task::JoinHandle;
+
+use crate::cache::Cache;
+use crate::data_engine::DataEngine;
+use crate::error::Result;
+use crate::execution_engine::ExecutionEngine;
+use crate::generic_cache::GenericCache;
+use crate::message_bus::MessageBus;
+use crate::profiling::Profiler;
+
+/// Content type of the Prometheus text exposition format
+pub const PROMETHEUS_CONTENT_TYPE: &str = "text/plain; version=0.0.4; charset=utf-8";
+
+/// How a metric's value behaves over time
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum MetricKind {
+    /// Only ever increases, e.g. hits since start
+    Counter,
+    /// Goes up and down, e.g. entries held
+    Gauge,
+    /// Quantiles of observations, with their sum and count
+    Summary,
+}
+
+impl MetricKind {
+    fn as_str(&self) -> &'static str {
+        match self {
+            MetricKind::Counter => "counter",
+            MetricKind::Gauge => "gauge",
+            MetricKind::Summary => "summary",
+        }
+    }
+}
+
+/// Samples of one metric across every source
+#[derive(Debug)]
+struct Family {
+    help: &'static str,
+    kind: MetricKind,
+    /// Name suffixes and rendered label sets with their values
+    samples: Vec<(&'static str, String, f64)>,
+}
+
+/// Collects samples from sources for rendering, grouped by metric name as
+/// the text format requires
+#[derive(Debug, Default)]
+pub struct MetricsWriter {
+    families: BTreeMap<&'static str, Family>,
+    /// Source of the samples being written
+    source: String,
+}
+
+impl MetricsWriter {
+    pub fn counter(&mut self, name: &'static str, help: &'static str, labels: &[(&str, &str)], value: f64) {
+        self.sample(MetricKind::Counter, name, help, labels, value);
+    }
+
+    pub fn gauge(&mut self, name: &'static str, help: &'static str, labels: &[(&str, &str)], value: f64) {
+        self.sample(MetricKind::Gauge, name, help, labels, value);
+    }
+
+    /// Add a summary of `name` from `quantiles`, as (quantile, value) pairs,
+    /// and the sum and count of the observations
+    pub fn summary(
+        &mut self,
+        name: &'static str,
+        help: &'static str,
+        labels: &[(&str, &str)],
+        quantiles: &[(f64, f64)],
+        sum: f64,
+        count: u64,
+    ) {
+        for (quantile, value) in quantiles {
+            let quantile = quantile.to_string();
+            let mut labels = labels.to_vec();
+            labels.push(("quantile", &quantile));
+            self.push(MetricKind::Summary, name, "", help, &labels, *value);
+        }
+        self.push(MetricKind::Summary, name, "_sum", help, labels, sum);
+        self.push(MetricKind::Summary, name, "_count", help, labels, count as f64);
+    }
+
+    /// Add a sample of `name`, labelled with its source. The first sample of
+    /// a name sets its help and kind.
+    pub fn sample(&mut self, kind: MetricKind, name: &'static str, help: &'static str, labels: &[(&str, &str)], value: f64) {
+        self.push(kind, name, "", help, labels, value);
+    }
+
+    fn push(&mut self, kind: MetricKind, name: &'static str, suffix: &'static str, help: &'static str, labels: &[(&str, &str)], value: f64) {
+        let mut rendered = format!("source=\"{}\"", escape_label(&self.source));
+        for (label, value) in labels {
+            let _ = write!(rendered, ",{}=\"{}\"", label, escape_label(value));
+        }
+        self.families
+            .entry(name)
+            .or_insert_with(|| Family { help, kind, samples: Vec::new() })
+            .samples
+            .push((suffix, rendered, value));
+    }
+
+    fn render(&self) -> String {
+        let mut text = String::new();
+        for (name, family) in &self.families {
+            let _ = writeln!(text, "# HELP {} {}", name, family.help);
+            let _ = writeln!(text, "# TYPE {} {}", name, family.kind.as_str());
+            for (suffix, labels, value) in &family.samples {
+                let _ = writeln!(text, "{}{}{{{}}} {}", name, suffix, labels, format_value(*value));
+            }
+        }
+        text
+    }
+}
+
+fn escape_label(value: &str) -> String {
+    value.replace('\\', "\\\\").replace('"', "\\\"").replace('\n', "\\n")
+}
+
+fn format_value(value: f64) -> String {
+    if value.is_nan() {
+        "NaN".to_string()
+    } else if value.is_infinite() {
+        if value > 0.0 { "+Inf" } else { "-Inf" }.to_string()
+    } else {
+        value.to_string()
+    }
+}
+
+/// A component whose statistics are exported
+pub trait MetricsSource: Send + Sync {
+    fn write_metrics(&self, metrics: &mut MetricsWriter);
+}
+
+impl MetricsSource for Cache {
+    fn write_metrics(&self, metrics: &mut MetricsWriter) {
+        let stats = self.get_stats();
+        metrics.counter("alphaforge_cache_hits_total", "Cache lookups that found an item", &[], stats.total_hits as f64);
+        metrics.counter("alphaforge_cache_misses_total", "Cache lookups that found nothing", &[], stats.total_misses as f64);
+        metrics.counter("alphaforge_cache_writes_total", "Items written to the cache", &[], stats.total_writes as f64);
+        metrics.gauge("alphaforge_cache_hit_ratio", "Share of cache lookups that hit, 0 to 1", &[], stats.hit_ratio);
+        let evictions = stats.evictions_by_type;
+        for (data_type, count) in [
+            ("currencies", evictions.currencies),
+            ("books", evictions.books),
+            ("quotes", evictions.quotes),
+            ("trades", evictions.trades),
+            ("bars", evictions.bars),
+        ] {
+            metrics.counter("alphaforge_cache_evictions_total", "Items evicted from the cache", &[("type", data_type)], count as f64);
+        }
+        for (data_type, count) in [
+            ("currencies", stats.currencies_count),
+            ("instruments", stats.instruments_count),
+            ("books", stats.books_count),
+            ("quotes", stats.quotes_count),
+            ("trades", stats.trades_count),
+            ("bars", stats.bars_count),
+            ("accounts", stats.accounts_count),
+            ("orders", stats.orders_count),
+            ("positions", stats.positions_count),
+        ] {
+            metrics.gauge("alphaforge_cache_entries", "Items held by the cache", &[("type", data_type)], count as f64);
+        }
+    }
+}
+
+impl<T: Clone + Send + Sync + 'static> MetricsSource for GenericCache<T> {
+    fn write_metrics(&self, metrics: &mut MetricsWriter) {
+        // Labelled like `Cache`'s per-type series, as one family needs one label set
+        let labels = [("type", "all")];
+        metrics.gauge("alphaforge_cache_entries", "Items held by the cache", &labels, self.size() as f64);
+        // Counters are only kept with statistics enabled
+        let Some(stats) = self.statistics() else {
+            return;
+        };
+        metrics.counter("alphaforge_cache_hits_total", "Cache lookups that found an item", &[], stats.hits as f64);
+        metrics.counter("alphaforge_cache_misses_total", "Cache lookups that found nothing", &[], stats.misses as f64);
+        metrics.counter("alphaforge_cache_writes_total", "Items written to the cache", &[], stats.inserts as f64);
+        metrics.counter("alphaforge_cache_evictions_total", "Items evicted from the cache", &labels, stats.evictions as f64);
+        metrics.gauge("alphaforge_cache_hit_ratio", "Share of cache lookups that hit, 0 to 1", &[], stats.hit_rate() / 100.0);
+        metrics.gauge("alphaforge_cache_memory_bytes", "Approximate bytes held by the cache", &[], stats.memory_usage as f64);
+    }
+}
+
+impl MetricsSource for MessageBus {
+    fn write_metrics(&self, metrics: &mut MetricsWriter) {
+        for (topic, stats) in self.all_topic_stats() {
+            let labels = [("topic", topic.as_str())];
+            metrics.counter("alphaforge_bus_published_total", "Envelopes published on a topic", &labels, stats.published as f64);
+            metrics.counter("alphaforge_bus_delivered_total", "Envelopes delivered to subscribers", &labels, stats.delivered as f64);
+            metrics.counter("alphaforge_bus_failed_total", "Envelopes whose subscriber was gone", &labels, stats.failed as f64);
+        }
+        let mut backlogs: HashMap<String, usize> = HashMap::new();
+        for (subscription, depth) in self.queue_depths() {
+            *backlogs.entry(subscription).or_default() += depth;
+        }
+        for (subscription, depth) in backlogs {
+            let labels = [("subscription", subscription.as_str())];
+            metrics.gauge("alphaforge_bus_backlog", "Envelopes waiting to be received", &labels, depth as f64);
+        }
+    }
+}
+
+impl MetricsSource for DataEngine {
+    fn write_metrics(&self, metrics: &mut MetricsWriter) {
+        let stats = self.statistics();
+        for (name, help, value) in [
+            ("alphaforge_data_ticks_processed_total", "Ticks processed", stats.ticks_processed),
+            ("alphaforge_data_bars_generated_total", "Bars generated", stats.bars_generated),
+            ("alphaforge_data_book_updates_total", "Order book updates applied", stats.order_book_updates),
+            ("alphaforge_data_duplicate_trades_total", "Duplicate trade ticks filtered out", stats.duplicate_trades_filtered),
+            ("alphaforge_data_rejected_total", "Ticks rejected by validation", stats.invalid_data_rejected),
+            ("alphaforge_data_latency_alerts_total", "Feed latency threshold breaches", stats.latency_alerts),
+        ] {
+            metrics.counter(name, help, &[], value as f64);
+        }
+        metrics.gauge("alphaforge_data_processing_rate", "Ticks processed per second", &[], stats.processing_rate);
+        metrics.gauge("alphaforge_data_memory_bytes", "Approximate bytes of cached data and buffers", &[], stats.memory_usage as f64);
+    }
+}
+
+impl MetricsSource for Profiler {
+    fn write_metrics(&self, metrics: &mut MetricsWriter) {
+        for stats in self.statistics() {
+            let labels = [("scope", stats.name.as_str())];
+            let quantiles = [(0.5, stats.p50_ns), (0.9, stats.p90_ns), (0.99, stats.p99_ns)].map(|(q, ns)| (q, ns as f64 / 1e9));
+            metrics.summary(
+                "alphaforge_profile_seconds",
+                "Time spent in a profiled scope",
+                &labels,
+                &quantiles,
+                stats.total_ns as f64 / 1e9,
+                stats.count,
+            );
+            metrics.gauge("alphaforge_profile_max_seconds", "Longest run of a profiled scope", &labels, stats.max_ns as f64 / 1e9);
+        }
+    }
+}
+
+impl MetricsSource for ExecutionEngine {
+    fn write_metrics(&self, metrics: &mut MetricsWriter) {
+        let stats = self.get_statistics();
+        for (name, help, value) in [
+            ("alphaforge_orders_submitted_total", "Orders submitted", stats.orders_submitted),
+            ("alphaforge_orders_filled_total", "Orders filled", stats.orders_filled),
+            ("alphaforge_orders_cancelled_total", "Orders cancelled", stats.orders_cancelled),
+            ("alphaforge_orders_rejected_total", "Orders rejected", stats.orders_rejected),
+        ] {
+            metrics.counter(name, help, &[], value as f64);
+        }
+        metrics.counter("alphaforge_fill_volume_total", "Quantity filled", &[], stats.total_fill_volume);
+        metrics.counter("alphaforge_commission_total", "Commission paid", &[], stats.total_commission);
+        let latency = stats.execution_latency;
+        let quantiles = [(0.5, latency.p50_ns), (0.9, latency.p90_ns), (0.99, latency.p99_ns)].map(|(q, ns)| (q, ns as f64 / 1e9));
+        metrics.summary(
+            "alphaforge_execution_latency_seconds",
+            "Time from creating an order to its complete fill",
+            &[],
+            &quantiles,
+            latency.total_ns as f64 / 1e9,
+            latency.count,
+        );
+    }
+}
+
+// Engines shared behind a lock, as the data engine is
+impl<S: MetricsSource> MetricsSource for Mutex<S> {
+    fn write_metrics(&self, metrics: &mut MetricsWriter) {
+        match self.lock() {
+            Ok(source) => source.write_metrics(metrics),
+            Err(_) => tracing::warn!("Skipping metrics of a poisoned source"),
+        }
+    }
+}
+
+/// Sources whose statistics are exported, by name
+#[derive(Default)]
+pub struct MetricsRegistry {
+    sources: RwLock<Vec<(String, Arc<dyn MetricsSource>)>>,
+}
+
+impl std::fmt::Debug for MetricsRegistry {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let sources: Vec<String> = self.sources.read().unwrap().iter().map(|(name, _)| name.clone()).collect();
+        f.debug_struct("MetricsRegistry").field("sources", &sources).finish()
+    }
+}
+
+impl MetricsRegistry {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Export `source`'s statistics labelled `source="{name}"`, replacing a
+    /// source registered under the same name
+    pub fn register(&self, name: &str, source: Arc<dyn MetricsSource>) {
+        let mut sources = self.sources.write().unwrap();
+        sources.retain(|(registered, _)| registered != name);
+        sources.push((name.to_string(), source));
+    }
+
+    /// Stop exporting a source, returning whether it was registered
+    pub fn unregister(&self, name: &str) -> bool {
+        let mut sources = self.sources.write().unwrap();
+        let count = sources.len();
+        sources.retain(|(registered, _)| registered != name);
+        sources.len() < count
+    }
+
+    /// Every source's current statistics in the Prometheus text format
+    pub fn render(&self) -> String {
+        let sources = self.sources.read().unwrap().clone();
+        let mut writer = MetricsWriter::default();
+        for (name, source) in sources {
+            writer.source = name;
+            source.write_metrics(&mut writer);
+        }
+        writer.render()
+    }
+}
+
+/// Serves a registry at `GET /metrics` over HTTP/1.1 until dropped
+#[derive(Debug)]
+pub struct MetricsServer {
+    local_addr: SocketAddr,
+    task: JoinHandle<()>,
+}
+
+impl MetricsServer {
+    /// Start serving `registry` on `addr`, e.g. `0.0.0.0:9100`; port 0
+    /// picks a free port
+    pub async fn bind(addr: impl ToSocketAddrs, registry: Arc<MetricsRegistry>) -> Result<Self> {
+        let listener = TcpListener::bind(addr).await?;
+        let local_addr = listener.local_addr()?;
+        tracing::info!("Serving metrics on http://{}/metrics", local_addr);
+        let connections = Arc::new(Semaphore::new(MAX_CONNECTIONS));
+        let task = tokio::spawn(async move {
+            loop {
+                // Stop accepting while every connection slot is taken
+                let Ok(permit) = Arc::clone(&connections).acquire_owned().await else {
+                    return;
+                };
+                match listener.accept().await {
+                    Ok((stream, _)) => {
+                        let registry = Arc::clone(&registry);
+                        tokio::spawn(async move {
+                            match tokio::time::timeout(REQUEST_TIMEOUT, respond(stream, &registry)).await {
+                                Ok(Ok(())) => {}
+                                Ok(Err(e)) => tracing::debug!("Metrics request failed: {}", e),
+                                Err(_) => tracing::debug!("Metrics request timed out"),
+                            }
+                            drop(permit);
+                        });
+                    }
+                    Err(e) => tracing::warn!("Failed to accept metrics connection: {}", e),
+                }
+            }
+        });
+        Ok(Self { local_addr, task })
+    }
+
+    pub fn local_addr(&self) -> SocketAddr {
+        self.local_addr
+    }
+}
+
+impl Drop for MetricsServer {
+    fn drop(&mut self) {
+        self.task.abort();
+    }
+}
+
+/// Longest request head read; scrapes send far less
+const MAX_REQUEST_BYTES: usize = 8 * 1024;
+
+/// Connections served at once; further ones wait to be accepted
+const MAX_CONNECTIONS: usize = 16;
+
+/// Time a connection has to send its request and read the response
+const REQUEST_TIMEOUT: Duration = Duration::from_secs(10);
+
+// Answer one request, closing the connection after it
+async fn respond(mut stream: TcpStream, registry: &MetricsRegistry) -> std::io::Result<()> {
+    let mut request = Vec::new();
+    let mut buffer = [0u8; 1024];
+    while !request.windows(4).any(|window| window == b"\r\n\r\n") && request.len() < MAX_REQUEST_BYTES {
+        let read = stream.read(&mut buffer).await?;
+        if read == 0 {
+            break;
+        }
+        request.extend_from_slice(&buffer[..read]);
+    }
+    let request = String::from_utf8_lossy(&request);
+    let mut parts = request.split_whitespace();
+    let (status, content_type, body) = match (parts.next(), parts.next()) {
+        (Some("GET"), Some(path)) if path == "/metrics" || path.starts_with("/metrics?") => {
+            ("200 OK", PROMETHEUS_CONTENT_TYPE, registry.render())
+        }
+        (Some("GET"), Some(_)) => ("404 Not Found", "text/plain", "Not found\n".to_string()),
+        _ => ("405 Method Not Allowed", "text/plain", "Method not allowed\n".to_string()),
+    };
+    let head = format!(
+        "HTTP/1.1 {}\r\nContent-Type: {}\r\nContent-Length: {}\r\nConnection: close\r\n\r\n",
+        status,
+        content_type,
+        body.len()
+    );
+    stream.write_all(head.as_bytes()).await?;
+    stream.write_all(body.as_bytes()).await?;
+    stream.shutdown().await
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::cache::CacheConfig;
+    use crate::generic_cache::GenericCacheConfig;
+
+    #[tokio::test]
+    async fn test_registry_served_in_prometheus_format() {
+        let registry = Arc::new(MetricsRegistry::new());
+        let cache = Arc::new(Cache::new(CacheConfig::default()));
+        cache.get_currency("USD");
+        let quotes = Arc::new(GenericCache::<u64>::new(GenericCacheConfig::default()));
+        quotes.put("BTCUSDT".to_string(), 1);
+        quotes.get("BTCUSDT");
+        let bus = Arc::new(MessageBus::new());
+        bus.publish("orders.\"filled\"", &1u32);
+        registry.register("core", cache);
+        registry.register("quotes", quotes);
+        registry.register("bus", bus.clone());
+        registry.register("execution", Arc::new(ExecutionEngine::new(Arc::clone(&bus))));
+
+        let text = registry.render();
+        assert!(text.contains("# TYPE alphaforge_cache_hits_total counter\n"));
+        // One family per metric, whichever sources report it
+        assert_eq!(text.matches("# HELP alphaforge_cache_hit_ratio ").count(), 1);
+        assert!(text.contains("alphaforge_cache_misses_total{source=\"core\"} 1\n"));
+        assert!(text.contains("alphaforge_cache_hit_ratio{source=\"quotes\"} 1\n"));
+        assert!(text.contains("alphaforge_cache_entries{source=\"core\",type=\"bars\"} 0\n"));
+        assert!(text.contains("alphaforge_cache_entries{source=\"quotes\",type=\"all\"} 1\n"));
+        assert!(text.contains("# TYPE alphaforge_execution_latency_seconds summary\n"));
+        assert!(text.contains("alphaforge_execution_latency_seconds_count{source=\"execution\"} 0\n"));
+        assert!(text.contains("alphaforge_bus_published_total{source=\"bus\",topic=\"orders.\\\"filled\\\"\"} 1\n"));
+        assert!(registry.unregister("bus"));
+        assert!(!registry.render().contains("alphaforge_bus_"));
+
+        let server = MetricsServer::bind("127.0.0.1:0", Arc::clone(&registry)).await.unwrap();
+        let addr = server.local_addr();
+        let get = |path: &'static str| async move {
+            let mut stream = TcpStream::connect(addr).await.unwrap();
+            stream.write_all(format!("GET {} HTTP/1.1\r\nHost: localhost\r\n\r\n", path).as_bytes()).await.unwrap();
+            let mut response = String::new();
+            stream.read_to_string(&mut response).await.unwrap();
+            response
+        };
+        let response = get("/metrics").await;
+        assert!(response.starts_with("HTTP/1.1 200 OK\r\n"));
+        assert!(response.contains(PROMETHEUS_CONTENT_TYPE));
+        assert!(response.ends_with(&registry.render()));
+        assert!(get("/").await.starts_with("HTTP/1.1 404"));
+    }
+}
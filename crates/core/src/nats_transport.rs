@@ -0,0 +1,115 @@
+//! AlphaForge NATS Transport
+//!
+//! `BusTransport` over a NATS server: topic `t` is sent on the subject
+//! `{subject_prefix}.t`, and each node subscribes to the subjects of the
+//! topics it imports. Requires the `nats` feature.
+
+use async_trait::async_trait;
+use futures::StreamExt;
+use tokio::sync::mpsc;
+
+use crate::error::{AlphaForgeError, Result};
+use crate::message::TopicPattern;
+use crate::transport::BusTransport;
+
+/// Connection to a NATS server shared by the nodes of a deployment
+pub struct NatsTransport {
+    client: async_nats::Client,
+    subject_prefix: String,
+}
+
+impl NatsTransport {
+    /// Connect to `url` (e.g. `nats://127.0.0.1:4222`), sending under `subject_prefix`
+    pub async fn connect(url: &str, subject_prefix: impl Into<String>) -> Result<Self> {
+        let subject_prefix = subject_prefix.into();
+        if subject_prefix.is_empty() || subject_prefix.contains(['*', '>', ' ']) {
+            return Err(AlphaForgeError::config(format!("Invalid NATS subject prefix: {:?}", subject_prefix)));
+        }
+        let client = async_nats::connect(url)
+            .await
+            .map_err(|e| AlphaForgeError::network(format!("Failed to connect to NATS at {}: {}", url, e)))?;
+        Ok(Self { client, subject_prefix })
+    }
+
+    /// Subject a topic is sent on
+    pub fn subject(&self, topic: &str) -> String {
+        format!("{}.{}", self.subject_prefix, topic)
+    }
+}
+
+/// Subjects under `subject_prefix` receiving every topic matching `topics`:
+/// a topic's own subject, or for a pattern the `>` wildcard under its whole
+/// literal tokens. Subjects another one covers are left out, so the server
+/// never delivers a frame twice.
+fn subjects(subject_prefix: &str, topics: &[String]) -> Vec<String> {
+    let mut subjects: Vec<String> = topics
+        .iter()
+        .map(|topic| {
+            if !TopicPattern::is_pattern(topic) {
+                return format!("{}.{}", subject_prefix, topic);
+            }
+            // Bus wildcards match across dots, as only `>` does in NATS
+            match TopicPattern::new(topic.as_str()).literal_prefix().rsplit_once('.') {
+                Some((tokens, _)) => format!("{}.{}.>", subject_prefix, tokens),
+                None => format!("{}.>", subject_prefix),
+            }
+        })
+        .collect();
+    subjects.sort();
+    subjects.dedup();
+    let covered = |subject: &String, wider: &String| {
+        subject != wider && wider.strip_suffix('>').is_some_and(|prefix| subject.starts_with(prefix))
+    };
+    subjects.iter().filter(|subject| !subjects.iter().any(|wider| covered(subject, wider))).cloned().collect()
+}
+
+#[async_trait]
+impl BusTransport for NatsTransport {
+    async fn send(&self, topic: &str, frame: Vec<u8>) -> Result<()> {
+        self.client
+            .publish(self.subject(topic), frame.into())
+            .await
+            .map_err(|e| AlphaForgeError::network(format!("Failed to publish {} to NATS: {}", topic, e)))
+    }
+
+    async fn receive(&self, topics: &[String]) -> Result<mpsc::UnboundedReceiver<(String, Vec<u8>)>> {
+        let mut subscribers = Vec::new();
+        for subject in subjects(&self.subject_prefix, topics) {
+            let subscriber = self.client
+                .subscribe(subject.clone())
+                .await
+                .map_err(|e| AlphaForgeError::network(format!("Failed to subscribe to NATS subject {}: {}", subject, e)))?;
+            subscribers.push(subscriber);
+        }
+        let mut messages = futures::stream::select_all(subscribers);
+        let prefix = format!("{}.", self.subject_prefix);
+        let (sender, receiver) = mpsc::unbounded_channel();
+        tokio::spawn(async move {
+            while let Some(message) = messages.next().await {
+                let Some(topic) = message.subject.as_str().strip_prefix(&prefix) else {
+                    continue;
+                };
+                if sender.send((topic.to_string(), message.payload.to_vec())).is_err() {
+                    break;
+                }
+            }
+        });
+        Ok(receiver)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_subjects_cover_imported_topics_once() {
+        let topics = |topics: &[&str]| topics.iter().map(|topic| topic.to_string()).collect::<Vec<_>>();
+        assert_eq!(
+            subjects("af", &topics(&["data.quotes.BTC*", "orders.filled", "orders.*", "risk.*.limits"])),
+            vec!["af.data.quotes.>", "af.orders.>", "af.risk.>"]
+        );
+        assert_eq!(subjects("af", &topics(&["orders.filled", "data?"])), vec!["af.>"]);
+        assert_eq!(subjects("af", &topics(&["orders.filled"])), vec!["af.orders.filled"]);
+    }
+}
@@ -0,0 +1,308 @@
+//! AlphaForge Parameter Optimization
+//!
+//! Backtests a strategy across combinations of its parameters, in parallel
+//! on the rayon thread pool, and ranks the runs by an objective. Use
+//! `grid_search` for the full cartesian product of a `ParameterGrid` or
+//! `random_search` to sample it.
+
+use std::cmp::Ordering;
+
+use rayon::prelude::*;
+
+use crate::backtest::{run_backtest, BacktestResult};
+use crate::data::MarketData;
+use crate::parameters::ParameterValue;
+use crate::strategy_engine::{Strategy, StrategyConfig};
+
+/// One assignment of parameter values
+pub type ParameterSet = Vec<(String, ParameterValue)>;
+
+/// Candidate values per parameter
+#[derive(Debug, Clone, Default)]
+pub struct ParameterGrid {
+    axes: Vec<(String, Vec<ParameterValue>)>,
+}
+
+impl ParameterGrid {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Add a parameter and the values to try
+    pub fn axis(mut self, name: &str, values: Vec<ParameterValue>) -> Self {
+        self.axes.push((name.to_string(), values));
+        self
+    }
+
+    /// Number of combinations in the grid
+    pub fn len(&self) -> usize {
+        if self.axes.is_empty() {
+            return 0;
+        }
+        self.axes.iter().map(|(_, values)| values.len()).product()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Every combination, varying the last axis fastest
+    pub fn combinations(&self) -> Vec<ParameterSet> {
+        (0..self.len()).map(|index| self.combination(index)).collect()
+    }
+
+    /// Up to `count` distinct combinations chosen uniformly at random;
+    /// the same `seed` gives the same sample
+    pub fn sample(&self, count: usize, seed: u64) -> Vec<ParameterSet> {
+        let total = self.len();
+        let mut indices: Vec<usize> = (0..total).collect();
+        let mut state = seed;
+        // Partial Fisher-Yates shuffle
+        for i in 0..count.min(total) {
+            let j = i + (splitmix64(&mut state) % (total - i) as u64) as usize;
+            indices.swap(i, j);
+        }
+        indices.truncate(count.min(total));
+        indices.into_iter().map(|index| self.combination(index)).collect()
+    }
+
+    fn combination(&self, mut index: usize) -> ParameterSet {
+        let mut set = Vec::with_capacity(self.axes.len());
+        for (name, values) in self.axes.iter().rev() {
+            set.push((name.clone(), values[index % values.len()].clone()));
+            index /= values.len();
+        }
+        set.reverse();
+        set
+    }
+}
+
+fn splitmix64(state: &mut u64) -> u64 {
+    *state = state.wrapping_add(0x9E37_79B9_7F4A_7C15);
+    let mut z = *state;
+    z = (z ^ (z >> 30)).wrapping_mul(0xBF58_476D_1CE4_E5B9);
+    z = (z ^ (z >> 27)).wrapping_mul(0x94D0_49BB_1331_11EB);
+    z ^ (z >> 31)
+}
+
+/// What the optimizer maximizes
+#[derive(Debug, Clone, Copy)]
+pub enum Objective {
+    TotalPnl,
+    ProfitFactor,
+    /// Total PnL per unit of maximum drawdown (in currency)
+    PnlOverDrawdown,
+    Custom(fn(&BacktestResult) -> f64),
+}
+
+impl Objective {
+    fn score(&self, result: &BacktestResult, config: &StrategyConfig) -> f64 {
+        let metrics = &result.metrics;
+        let score = match self {
+            Objective::TotalPnl => metrics.total_pnl,
+            Objective::ProfitFactor if metrics.gross_loss == 0.0 => metrics.gross_profit,
+            Objective::ProfitFactor => metrics.gross_profit / metrics.gross_loss,
+            Objective::PnlOverDrawdown => {
+                let drawdown = metrics.max_drawdown * config.starting_equity;
+                metrics.total_pnl / drawdown.max(f64::EPSILON)
+            }
+            Objective::Custom(score) => score(result),
+        };
+        // Rank undefined scores last
+        if score.is_nan() { f64::NEG_INFINITY } else { score }
+    }
+}
+
+/// A completed backtest of one parameter set
+#[derive(Debug, Clone)]
+pub struct Trial {
+    pub parameters: ParameterSet,
+    pub score: f64,
+    pub result: BacktestResult,
+}
+
+/// Trials ranked best first
+#[derive(Debug, Clone, Default)]
+pub struct OptimizationReport {
+    pub trials: Vec<Trial>,
+    /// Parameter sets that could not be backtested, with the reason
+    pub failures: Vec<(ParameterSet, String)>,
+}
+
+impl OptimizationReport {
+    pub fn best(&self) -> Option<&Trial> {
+        self.trials.first()
+    }
+
+    pub fn top(&self, count: usize) -> &[Trial] {
+        &self.trials[..count.min(self.trials.len())]
+    }
+}
+
+/// Runs backtests of one strategy over many parameter sets
+pub struct Optimizer<F> {
+    base_config: StrategyConfig,
+    data: Vec<MarketData>,
+    factory: F,
+    objective: Objective,
+}
+
+impl<F> Optimizer<F>
+where
+    F: Fn(&StrategyConfig) -> Box<dyn Strategy> + Sync,
+{
+    /// `factory` builds the strategy from a config whose parameters hold
+    /// the trial's values; every parameter varied must be defined in
+    /// `base_config.parameters`
+    pub fn new(base_config: StrategyConfig, data: Vec<MarketData>, factory: F, objective: Objective) -> Self {
+        Self {
+            base_config,
+            data,
+            factory,
+            objective,
+        }
+    }
+
+    /// Backtest every combination in `grid`
+    pub fn grid_search(&self, grid: &ParameterGrid) -> OptimizationReport {
+        self.run(grid.combinations())
+    }
+
+    /// Backtest `samples` random combinations from `grid`
+    pub fn random_search(&self, grid: &ParameterGrid, samples: usize, seed: u64) -> OptimizationReport {
+        self.run(grid.sample(samples, seed))
+    }
+
+    /// Backtest each parameter set in parallel and rank the results
+    pub fn run(&self, parameter_sets: Vec<ParameterSet>) -> OptimizationReport {
+        let outcomes: Vec<_> = parameter_sets
+            .into_par_iter()
+            .map(|parameters| {
+                let outcome = self.trial(&parameters);
+                (parameters, outcome)
+            })
+            .collect();
+
+        let mut report = OptimizationReport::default();
+        for (parameters, outcome) in outcomes {
+            match outcome {
+                Ok((score, result)) => report.trials.push(Trial { parameters, score, result }),
+                Err(e) => report.failures.push((parameters, e)),
+            }
+        }
+        report.trials.sort_by(|a, b| b.score.partial_cmp(&a.score).unwrap_or(Ordering::Equal));
+        report
+    }
+
+    fn trial(&self, parameters: &ParameterSet) -> Result<(f64, BacktestResult), String> {
+        let mut config = self.base_config.clone();
+        for (name, value) in parameters {
+            config.parameters.set(name, value.clone())?;
+        }
+
+        let strategy = (self.factory)(&config);
+        let result = run_backtest(strategy, config.clone(), &self.data)?;
+        Ok((self.objective.score(&result, &config), result))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::data::{AggressorSide, Bar, QuoteTick, TradeTick};
+    use crate::identifiers::InstrumentId;
+    use crate::parameters::Parameters;
+    use crate::strategy_engine::StrategyContext;
+
+    /// Earns `edge * size` per trade, minus a fixed cost per unit
+    struct EdgeStrategy {
+        edge: f64,
+        size: f64,
+    }
+
+    impl Strategy for EdgeStrategy {
+        fn on_start(&mut self, _context: &mut StrategyContext) -> Result<(), String> { Ok(()) }
+        fn on_quote_tick(&mut self, _context: &mut StrategyContext, _tick: &QuoteTick) -> Result<(), String> { Ok(()) }
+        fn on_bar(&mut self, _context: &mut StrategyContext, _bar: &Bar) -> Result<(), String> { Ok(()) }
+        fn on_timer(&mut self, _context: &mut StrategyContext, _name: &str) -> Result<(), String> { Ok(()) }
+        fn on_stop(&mut self, _context: &mut StrategyContext) -> Result<(), String> { Ok(()) }
+        fn name(&self) -> &str { "Edge" }
+
+        fn on_trade_tick(&mut self, context: &mut StrategyContext, tick: &TradeTick) -> Result<(), String> {
+            let pnl = (self.edge * tick.price - 1.0) * self.size;
+            context.record_trade(tick.instrument_id, pnl, 0.0);
+            Ok(())
+        }
+    }
+
+    fn optimizer() -> Optimizer<impl Fn(&StrategyConfig) -> Box<dyn Strategy> + Sync> {
+        let data = (0..10)
+            .map(|i| MarketData::Trade(TradeTick {
+                instrument_id: InstrumentId::new(1),
+                price: 100.0,
+                size: 1.0,
+                aggressor_side: AggressorSide::Buyer,
+                trade_id: i.to_string(),
+                ts_event: i,
+                ts_init: i,
+            }))
+            .collect();
+        let config = StrategyConfig {
+            instruments: vec![InstrumentId::new(1)],
+            parameters: Parameters::new()
+                .with("edge", ParameterValue::Float(0.0))
+                .unwrap()
+                .with("size", ParameterValue::Int(1))
+                .unwrap(),
+            ..Default::default()
+        };
+        Optimizer::new(config, data, |config: &StrategyConfig| -> Box<dyn Strategy> {
+            Box::new(EdgeStrategy {
+                edge: config.parameters.get_float("edge").unwrap(),
+                size: config.parameters.get_float("size").unwrap(),
+            })
+        }, Objective::TotalPnl)
+    }
+
+    fn grid() -> ParameterGrid {
+        ParameterGrid::new()
+            .axis("edge", vec![ParameterValue::Float(0.0), ParameterValue::Float(0.02), ParameterValue::Float(0.005)])
+            .axis("size", vec![ParameterValue::Int(1), ParameterValue::Int(2)])
+    }
+
+    #[test]
+    fn test_grid_search_ranks_trials() {
+        let report = optimizer().grid_search(&grid());
+        assert_eq!(report.trials.len(), 6);
+        assert!(report.failures.is_empty());
+
+        // 10 trades of (0.02 * 100 - 1) * 2
+        let best = report.best().unwrap();
+        assert_eq!(best.parameters, vec![
+            ("edge".to_string(), ParameterValue::Float(0.02)),
+            ("size".to_string(), ParameterValue::Int(2)),
+        ]);
+        assert_eq!(best.score, 20.0);
+        assert_eq!(best.result.records, 10);
+        assert_eq!(best.result.equity_curve.last().unwrap().1, 100_020.0);
+        assert_eq!(report.trials.last().unwrap().score, -20.0);
+    }
+
+    #[test]
+    fn test_random_search_and_failures() {
+        let grid = grid();
+        let sample = grid.sample(4, 7);
+        assert_eq!(sample.len(), 4);
+        assert!(sample.iter().all(|set| grid.combinations().contains(set)));
+        assert_eq!(sample, grid.sample(4, 7));
+        assert_eq!(grid.sample(10, 7).len(), 6);
+
+        let report = optimizer().random_search(&grid, 3, 1);
+        assert_eq!(report.trials.len(), 3);
+
+        let typo = ParameterGrid::new().axis("edgee", vec![ParameterValue::Float(0.1)]);
+        let report = optimizer().grid_search(&typo);
+        assert!(report.trials.is_empty());
+        assert_eq!(report.failures.len(), 1);
+    }
+}
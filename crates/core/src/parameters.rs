@@ -0,0 +1,238 @@
+//! AlphaForge Strategy Parameters
+//!
+//! Named, typed strategy settings carried on `StrategyConfig`. Each
+//! parameter keeps the type it was defined with, numeric parameters can be
+//! bounded, and `StrategyEngine::update_parameters` re-tunes a running
+//! strategy and notifies it of the change.
+
+use std::collections::BTreeMap;
+use std::fmt;
+use std::time::Duration;
+
+use serde::{Deserialize, Serialize};
+
+/// A parameter value
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub enum ParameterValue {
+    Int(i64),
+    Float(f64),
+    Bool(bool),
+    String(String),
+    Duration(Duration),
+}
+
+impl ParameterValue {
+    /// Name of the value's type, for error messages
+    pub fn type_name(&self) -> &'static str {
+        match self {
+            ParameterValue::Int(_) => "int",
+            ParameterValue::Float(_) => "float",
+            ParameterValue::Bool(_) => "bool",
+            ParameterValue::String(_) => "string",
+            ParameterValue::Duration(_) => "duration",
+        }
+    }
+
+    // Value compared against range bounds (durations in seconds)
+    fn as_number(&self) -> Option<f64> {
+        match self {
+            ParameterValue::Int(value) => Some(*value as f64),
+            ParameterValue::Float(value) => Some(*value),
+            ParameterValue::Duration(value) => Some(value.as_secs_f64()),
+            ParameterValue::Bool(_) | ParameterValue::String(_) => None,
+        }
+    }
+}
+
+impl fmt::Display for ParameterValue {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ParameterValue::Int(value) => write!(f, "{}", value),
+            ParameterValue::Float(value) => write!(f, "{}", value),
+            ParameterValue::Bool(value) => write!(f, "{}", value),
+            ParameterValue::String(value) => write!(f, "{}", value),
+            ParameterValue::Duration(value) => write!(f, "{:?}", value),
+        }
+    }
+}
+
+/// Inclusive bounds of a numeric or duration parameter (durations in seconds)
+#[derive(Debug, Clone, Copy, Default, PartialEq, Serialize, Deserialize)]
+pub struct ParameterRange {
+    pub min: Option<f64>,
+    pub max: Option<f64>,
+}
+
+impl ParameterRange {
+    fn contains(&self, value: f64) -> bool {
+        self.min.is_none_or(|min| value >= min) && self.max.is_none_or(|max| value <= max)
+    }
+}
+
+/// Typed strategy parameters
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct Parameters {
+    values: BTreeMap<String, ParameterValue>,
+    ranges: BTreeMap<String, ParameterRange>,
+}
+
+impl Parameters {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Define a parameter with its initial value; fails if `value` could
+    /// not replace a value `name` already has
+    pub fn with(mut self, name: &str, value: ParameterValue) -> Result<Self, String> {
+        self.validate(name, &value)?;
+        self.values.insert(name.to_string(), value);
+        Ok(self)
+    }
+
+    /// Define a bounded parameter; fails if `value` is out of range
+    pub fn with_range(
+        mut self,
+        name: &str,
+        value: ParameterValue,
+        min: Option<f64>,
+        max: Option<f64>,
+    ) -> Result<Self, String> {
+        if value.as_number().is_none() {
+            return Err(format!("Parameter {} of type {} cannot have a range", name, value.type_name()));
+        }
+        self.ranges.insert(name.to_string(), ParameterRange { min, max });
+        self.validate(name, &value)?;
+        self.values.insert(name.to_string(), value);
+        Ok(self)
+    }
+
+    /// Check that `value` could replace the current value of `name`
+    pub fn validate(&self, name: &str, value: &ParameterValue) -> Result<(), String> {
+        if let Some(current) = self.values.get(name) {
+            if current.type_name() != value.type_name() {
+                return Err(format!(
+                    "Parameter {} is a {}, not a {}",
+                    name,
+                    current.type_name(),
+                    value.type_name()
+                ));
+            }
+        }
+        if let (Some(range), Some(number)) = (self.ranges.get(name), value.as_number()) {
+            if !range.contains(number) {
+                return Err(format!("Parameter {} value {} is out of range {:?}", name, value, range));
+            }
+        }
+        Ok(())
+    }
+
+    /// Change a defined parameter, returning whether its value changed
+    pub fn set(&mut self, name: &str, value: ParameterValue) -> Result<bool, String> {
+        if !self.values.contains_key(name) {
+            return Err(format!("Unknown parameter: {}", name));
+        }
+        self.validate(name, &value)?;
+        Ok(self.values.insert(name.to_string(), value.clone()).as_ref() != Some(&value))
+    }
+
+    pub fn get(&self, name: &str) -> Option<&ParameterValue> {
+        self.values.get(name)
+    }
+
+    pub fn range(&self, name: &str) -> Option<&ParameterRange> {
+        self.ranges.get(name)
+    }
+
+    pub fn names(&self) -> impl Iterator<Item = &str> {
+        self.values.keys().map(String::as_str)
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.values.is_empty()
+    }
+
+    pub fn get_int(&self, name: &str) -> Result<i64, String> {
+        match self.lookup(name)? {
+            ParameterValue::Int(value) => Ok(*value),
+            other => Err(Self::type_error(name, "int", other)),
+        }
+    }
+
+    /// Float parameter (int parameters are widened)
+    pub fn get_float(&self, name: &str) -> Result<f64, String> {
+        match self.lookup(name)? {
+            ParameterValue::Float(value) => Ok(*value),
+            ParameterValue::Int(value) => Ok(*value as f64),
+            other => Err(Self::type_error(name, "float", other)),
+        }
+    }
+
+    pub fn get_bool(&self, name: &str) -> Result<bool, String> {
+        match self.lookup(name)? {
+            ParameterValue::Bool(value) => Ok(*value),
+            other => Err(Self::type_error(name, "bool", other)),
+        }
+    }
+
+    pub fn get_str(&self, name: &str) -> Result<&str, String> {
+        match self.lookup(name)? {
+            ParameterValue::String(value) => Ok(value),
+            other => Err(Self::type_error(name, "string", other)),
+        }
+    }
+
+    pub fn get_duration(&self, name: &str) -> Result<Duration, String> {
+        match self.lookup(name)? {
+            ParameterValue::Duration(value) => Ok(*value),
+            other => Err(Self::type_error(name, "duration", other)),
+        }
+    }
+
+    fn lookup(&self, name: &str) -> Result<&ParameterValue, String> {
+        self.values.get(name).ok_or_else(|| format!("Unknown parameter: {}", name))
+    }
+
+    fn type_error(name: &str, expected: &str, actual: &ParameterValue) -> String {
+        format!("Parameter {} is a {}, not a {}", name, actual.type_name(), expected)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_typed_access_and_validation() {
+        let mut parameters = Parameters::new()
+            .with("symbol", ParameterValue::String("BTCUSDT".to_string()))
+            .unwrap()
+            .with("enabled", ParameterValue::Bool(true))
+            .unwrap()
+            .with_range("lookback", ParameterValue::Int(20), Some(1.0), Some(500.0))
+            .unwrap()
+            .with_range("interval", ParameterValue::Duration(Duration::from_secs(60)), Some(1.0), None)
+            .unwrap();
+
+        assert_eq!(parameters.get_int("lookback").unwrap(), 20);
+        assert_eq!(parameters.get_float("lookback").unwrap(), 20.0);
+        assert_eq!(parameters.get_str("symbol").unwrap(), "BTCUSDT");
+        assert!(parameters.get_bool("enabled").unwrap());
+        assert_eq!(parameters.get_duration("interval").unwrap(), Duration::from_secs(60));
+        assert!(parameters.get_bool("lookback").is_err());
+        assert!(parameters.get_int("missing").is_err());
+
+        assert!(parameters.set("lookback", ParameterValue::Int(50)).unwrap());
+        assert!(!parameters.set("lookback", ParameterValue::Int(50)).unwrap());
+        assert!(parameters.set("lookback", ParameterValue::Int(501)).is_err());
+        assert!(parameters.set("lookback", ParameterValue::Float(5.0)).is_err());
+        assert!(parameters.set("interval", ParameterValue::Duration(Duration::from_millis(10))).is_err());
+        assert!(parameters.set("missing", ParameterValue::Int(1)).is_err());
+        assert_eq!(parameters.get_int("lookback").unwrap(), 50);
+        assert!(parameters.clone().with("lookback", ParameterValue::Int(0)).is_err());
+        assert!(parameters.clone().with("symbol", ParameterValue::Int(1)).is_err());
+        assert_eq!(parameters.with("lookback", ParameterValue::Int(30)).unwrap().get_int("lookback").unwrap(), 30);
+
+        assert!(Parameters::new().with_range("flag", ParameterValue::Bool(true), None, None).is_err());
+        assert!(Parameters::new().with_range("n", ParameterValue::Int(0), Some(1.0), None).is_err());
+    }
+}
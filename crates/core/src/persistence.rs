@@ -0,0 +1,502 @@
+//! AlphaForge Tick Persistence
+//!
+//! Streams processed market data to disk as it arrives so a live session can
+//! be replayed exactly as the node saw it. Records are written in arrival
+//! order to gzip-compressed files that rotate by size and by data time.
+
+use std::fs::{self, File, OpenOptions};
+use std::io::{BufRead, BufReader, BufWriter, Read, Write};
+use std::path::{Path, PathBuf};
+
+use flate2::read::MultiGzDecoder;
+use flate2::write::GzEncoder;
+use flate2::Compression;
+use serde::{Deserialize, Serialize};
+
+use crate::data::MarketData;
+use crate::error::{AlphaForgeError, Result};
+use crate::time::UnixNanos;
+
+/// Largest length-prefixed record written or read; longer length prefixes
+/// are taken as corruption rather than allocated
+pub(crate) const MAX_RECORD_SIZE: usize = 64 * 1024 * 1024;
+
+/// Length prefix of `record`, or None if it exceeds `MAX_RECORD_SIZE`
+pub(crate) fn length_prefix(record: &[u8]) -> Option<[u8; 4]> {
+    (record.len() <= MAX_RECORD_SIZE).then(|| (record.len() as u32).to_le_bytes())
+}
+
+/// On-disk record encoding
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum PersistenceFormat {
+    /// One JSON object per line
+    Ndjson,
+    /// Length-prefixed (u32 little-endian) MessagePack records
+    MessagePack,
+}
+
+impl PersistenceFormat {
+    fn extension(&self) -> &'static str {
+        match self {
+            PersistenceFormat::Ndjson => "ndjson",
+            PersistenceFormat::MessagePack => "msgpack",
+        }
+    }
+}
+
+/// Persistence sink settings; only `directory` is required when
+/// deserialized, the rest default as in `new`
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PersistenceConfig {
+    /// Directory the files are written to (created if missing)
+    pub directory: PathBuf,
+    /// File name prefix
+    #[serde(default = "default_file_prefix")]
+    pub file_prefix: String,
+    #[serde(default = "default_format")]
+    pub format: PersistenceFormat,
+    /// Gzip-compress files
+    #[serde(default = "default_compress")]
+    pub compress: bool,
+    /// Rotate once this many uncompressed bytes were written to a file
+    #[serde(default = "default_max_file_bytes")]
+    pub max_file_bytes: u64,
+    /// Rotate once a file spans this much data time (`ts_init`, nanoseconds)
+    #[serde(default = "default_max_file_duration_ns")]
+    pub max_file_duration_ns: Option<u64>,
+}
+
+fn default_file_prefix() -> String {
+    "ticks".to_string()
+}
+
+fn default_format() -> PersistenceFormat {
+    PersistenceFormat::MessagePack
+}
+
+fn default_compress() -> bool {
+    true
+}
+
+fn default_max_file_bytes() -> u64 {
+    256 * 1024 * 1024
+}
+
+fn default_max_file_duration_ns() -> Option<u64> {
+    Some(3_600_000_000_000)
+}
+
+impl PersistenceConfig {
+    /// Compressed MessagePack files rotated at 256 MiB or one hour
+    pub fn new(directory: impl Into<PathBuf>) -> Self {
+        Self {
+            directory: directory.into(),
+            file_prefix: default_file_prefix(),
+            format: default_format(),
+            compress: default_compress(),
+            max_file_bytes: default_max_file_bytes(),
+            max_file_duration_ns: default_max_file_duration_ns(),
+        }
+    }
+}
+
+#[derive(Debug)]
+enum FileSink {
+    Plain(BufWriter<File>),
+    Gzip(GzEncoder<BufWriter<File>>),
+}
+
+impl FileSink {
+    fn writer(&mut self) -> &mut dyn Write {
+        match self {
+            FileSink::Plain(writer) => writer,
+            FileSink::Gzip(encoder) => encoder,
+        }
+    }
+
+    fn finish(self) -> Result<()> {
+        let mut writer = match self {
+            FileSink::Plain(writer) => writer,
+            FileSink::Gzip(encoder) => encoder.finish()?,
+        };
+        writer.flush()?;
+        writer.get_ref().sync_all()?;
+        Ok(())
+    }
+}
+
+#[derive(Debug)]
+struct ActiveFile {
+    sink: FileSink,
+    bytes_written: u64,
+    first_ts: UnixNanos,
+}
+
+/// Appends market data to rotating files
+#[derive(Debug)]
+pub struct TickWriter {
+    config: PersistenceConfig,
+    active: Option<ActiveFile>,
+    files: Vec<PathBuf>,
+    records_written: u64,
+    buffer: Vec<u8>,
+}
+
+impl TickWriter {
+    /// Create a writer; no file is opened until the first record
+    pub fn new(config: PersistenceConfig) -> Self {
+        Self {
+            config,
+            active: None,
+            files: Vec::new(),
+            records_written: 0,
+            buffer: Vec::new(),
+        }
+    }
+
+    /// Append a record, rotating the current file first if it is full
+    pub fn write(&mut self, data: &MarketData) -> Result<()> {
+        let ts = data.ts_init();
+        if self.should_rotate(ts) {
+            self.finish_file()?;
+        }
+
+        self.buffer.clear();
+        match self.config.format {
+            PersistenceFormat::Ndjson => {
+                serde_json::to_writer(&mut self.buffer, data)?;
+                self.buffer.push(b'\n');
+            }
+            PersistenceFormat::MessagePack => {
+                let record = rmp_serde::to_vec(data)?;
+                let len = length_prefix(&record)
+                    .ok_or_else(|| AlphaForgeError::validation("Record too large to persist"))?;
+                self.buffer.extend_from_slice(&len);
+                self.buffer.extend_from_slice(&record);
+            }
+        }
+
+        if self.active.is_none() {
+            self.active = Some(self.open_file(ts)?);
+        }
+        let active = self.active.as_mut().expect("file opened above");
+        active.sink.writer().write_all(&self.buffer)?;
+        active.bytes_written += self.buffer.len() as u64;
+        self.records_written += 1;
+        Ok(())
+    }
+
+    /// Write buffered records to the current file, which stays open. A
+    /// compressed file lacks its trailer until finished; readers stop at the
+    /// last complete record.
+    pub fn flush(&mut self) -> Result<()> {
+        if let Some(active) = &mut self.active {
+            active.sink.writer().flush()?;
+        }
+        Ok(())
+    }
+
+    /// Finish the current file so it is complete and synced to disk; the
+    /// next record starts a new file
+    pub fn finish(&mut self) -> Result<()> {
+        self.finish_file()
+    }
+
+    /// Files written so far, oldest first
+    pub fn files(&self) -> &[PathBuf] {
+        &self.files
+    }
+
+    /// Total records written
+    pub fn records_written(&self) -> u64 {
+        self.records_written
+    }
+
+    fn should_rotate(&self, ts: UnixNanos) -> bool {
+        let Some(active) = &self.active else {
+            return false;
+        };
+        active.bytes_written >= self.config.max_file_bytes
+            || self.config.max_file_duration_ns
+                .is_some_and(|duration| ts.saturating_sub(active.first_ts) >= duration)
+    }
+
+    fn open_file(&mut self, first_ts: UnixNanos) -> Result<ActiveFile> {
+        fs::create_dir_all(&self.config.directory)?;
+
+        // Zero-padded so lexical order matches recording order
+        let mut name = format!(
+            "{}-{:020}-{:06}.{}",
+            self.config.file_prefix,
+            first_ts,
+            self.files.len(),
+            self.config.format.extension(),
+        );
+        if self.config.compress {
+            name.push_str(".gz");
+        }
+        let path = self.config.directory.join(name);
+
+        // A file of an earlier session with the same name is extended, not
+        // truncated; compressed files then hold several gzip members
+        let file = BufWriter::new(OpenOptions::new().create(true).append(true).open(&path)?);
+        let sink = if self.config.compress {
+            FileSink::Gzip(GzEncoder::new(file, Compression::fast()))
+        } else {
+            FileSink::Plain(file)
+        };
+
+        self.files.push(path);
+        Ok(ActiveFile {
+            sink,
+            bytes_written: 0,
+            first_ts,
+        })
+    }
+
+    fn finish_file(&mut self) -> Result<()> {
+        match self.active.take() {
+            Some(active) => active.sink.finish(),
+            None => Ok(()),
+        }
+    }
+}
+
+impl Drop for TickWriter {
+    fn drop(&mut self) {
+        if let Err(e) = self.finish_file() {
+            tracing::warn!("Failed to finish tick file: {}", e);
+        }
+    }
+}
+
+/// Read every record from a file written by `TickWriter`.
+///
+/// Format and compression are taken from the file extension.
+pub fn read_file(path: impl AsRef<Path>) -> Result<Vec<MarketData>> {
+    let path = path.as_ref();
+    let name = path.to_string_lossy();
+    let stem = name.strip_suffix(".gz").unwrap_or(&name);
+
+    let file = File::open(path)?;
+    let reader: Box<dyn Read> = if stem.len() != name.len() {
+        Box::new(MultiGzDecoder::new(file))
+    } else {
+        Box::new(file)
+    };
+    let mut reader = BufReader::new(reader);
+
+    let mut records = Vec::new();
+    if stem.ends_with(PersistenceFormat::Ndjson.extension()) {
+        let mut line = String::new();
+        loop {
+            line.clear();
+            match reader.read_line(&mut line) {
+                Ok(0) => break,
+                Ok(_) if line.ends_with('\n') => {}
+                // A line cut short by a crash, or by a file not yet finished
+                Ok(_) => {
+                    tracing::warn!("Skipping a torn record at the end of {}", name);
+                    break;
+                }
+                Err(e) if e.kind() == std::io::ErrorKind::UnexpectedEof => break,
+                Err(e) => return Err(e.into()),
+            }
+            if !line.trim_end().is_empty() {
+                records.push(serde_json::from_str(line.trim_end())?);
+            }
+        }
+    } else if stem.ends_with(PersistenceFormat::MessagePack.extension()) {
+        read_records(&mut reader, |record| {
+            records.push(rmp_serde::from_slice(record)?);
+            Ok(())
+        })?;
+    } else {
+        return Err(AlphaForgeError::config(format!("Unknown tick file format: {}", name)));
+    }
+
+    Ok(records)
+}
+
+/// Hand each length-prefixed (u32 little-endian) record of `reader` to
+/// `record`, returning the bytes of complete records read. A torn record at
+/// the end, left by a crash mid-write or in an unfinished compressed file,
+/// is skipped; a length prefix over `MAX_RECORD_SIZE` is an error.
+pub(crate) fn read_records(reader: &mut impl BufRead, mut record: impl FnMut(&[u8]) -> Result<()>) -> Result<u64> {
+    let mut len = [0u8; 4];
+    let mut bytes = Vec::new();
+    let mut complete = 0;
+    loop {
+        let read = reader.fill_buf().map(|buffer| !buffer.is_empty()).and_then(|more| {
+            if more {
+                reader.read_exact(&mut len)?;
+                let size = u32::from_le_bytes(len) as usize;
+                if size > MAX_RECORD_SIZE {
+                    return Err(std::io::Error::new(
+                        std::io::ErrorKind::InvalidData,
+                        format!("Record length {} after byte {} exceeds {} bytes", size, complete, MAX_RECORD_SIZE),
+                    ));
+                }
+                bytes.resize(size, 0);
+                reader.read_exact(&mut bytes)?;
+            }
+            Ok(more)
+        });
+        match read {
+            Ok(false) => break,
+            Ok(true) => record(&bytes)?,
+            Err(e) if e.kind() == std::io::ErrorKind::UnexpectedEof => {
+                tracing::warn!("Skipping a torn record after byte {}", complete);
+                break;
+            }
+            Err(e) if e.kind() == std::io::ErrorKind::InvalidData => {
+                return Err(AlphaForgeError::Serialization { msg: e.to_string() });
+            }
+            Err(e) => return Err(e.into()),
+        }
+        complete += (len.len() + bytes.len()) as u64;
+    }
+    Ok(complete)
+}
+
+/// Tick files in a directory with the given prefix, in recording order
+pub fn list_files(directory: impl AsRef<Path>, file_prefix: &str) -> Result<Vec<PathBuf>> {
+    let prefix = format!("{}-", file_prefix);
+    let mut files = Vec::new();
+    for entry in fs::read_dir(directory)? {
+        let path = entry?.path();
+        let matches = path
+            .file_name()
+            .and_then(|name| name.to_str())
+            .is_some_and(|name| name.starts_with(&prefix));
+        if matches {
+            files.push(path);
+        }
+    }
+    files.sort();
+    Ok(files)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::data::{AggressorSide, QuoteTick, TradeTick};
+    use crate::identifiers::InstrumentId;
+
+    fn temp_dir() -> PathBuf {
+        std::env::temp_dir().join(format!("alphaforge-ticks-{}", crate::uuid::UUID4::new()))
+    }
+
+    fn trade(ts: UnixNanos) -> MarketData {
+        MarketData::Trade(TradeTick {
+            instrument_id: InstrumentId::new(1),
+            price: 100.0 + ts as f64,
+            size: 1.0,
+            aggressor_side: AggressorSide::Buyer,
+            trade_id: ts.to_string(),
+            ts_event: ts,
+            ts_init: ts,
+        })
+    }
+
+    #[test]
+    fn test_round_trip_both_formats() {
+        for format in [PersistenceFormat::Ndjson, PersistenceFormat::MessagePack] {
+            let dir = temp_dir();
+            let mut writer = TickWriter::new(PersistenceConfig {
+                format,
+                ..PersistenceConfig::new(&dir)
+            });
+
+            writer.write(&trade(1)).unwrap();
+            writer.write(&MarketData::Quote(QuoteTick {
+                instrument_id: InstrumentId::new(1),
+                bid_price: 99.0,
+                ask_price: 101.0,
+                bid_size: 1.0,
+                ask_size: 2.0,
+                ts_event: 2,
+                ts_init: 2,
+            })).unwrap();
+            writer.flush().unwrap();
+
+            let records = read_file(&writer.files()[0]).unwrap();
+            assert_eq!(records.len(), 2);
+            assert!(matches!(&records[0], MarketData::Trade(t) if t.price == 101.0));
+            assert!(matches!(&records[1], MarketData::Quote(q) if q.ask_size == 2.0));
+
+            // Flushing keeps the file open for more records
+            writer.write(&trade(3)).unwrap();
+            writer.finish().unwrap();
+            assert_eq!(writer.files().len(), 1);
+            assert_eq!(read_file(&writer.files()[0]).unwrap().len(), 3);
+
+            // A restarted session writing the same file name extends it
+            let mut restarted = TickWriter::new(PersistenceConfig {
+                format,
+                ..PersistenceConfig::new(&dir)
+            });
+            restarted.write(&trade(1)).unwrap();
+            drop(restarted);
+            assert_eq!(read_file(&writer.files()[0]).unwrap().len(), 4);
+
+            fs::remove_dir_all(dir).unwrap();
+        }
+    }
+
+    #[test]
+    fn test_oversized_record_length_rejected() {
+        // A complete record, then a corrupt prefix claiming 4 GiB
+        let mut file = Vec::new();
+        file.extend_from_slice(&length_prefix(b"tick").unwrap());
+        file.extend_from_slice(b"tick");
+        file.extend_from_slice(&u32::MAX.to_le_bytes());
+        file.extend_from_slice(&[0; 16]);
+
+        let mut records = Vec::new();
+        let result = read_records(&mut &file[..], |record| {
+            records.push(record.to_vec());
+            Ok(())
+        });
+        assert!(matches!(result, Err(AlphaForgeError::Serialization { .. })));
+        assert_eq!(records, vec![b"tick".to_vec()]);
+        assert!(length_prefix(&vec![0; MAX_RECORD_SIZE + 1]).is_none());
+    }
+
+    #[test]
+    fn test_rotation_by_size_and_time() {
+        let dir = temp_dir();
+        let mut writer = TickWriter::new(PersistenceConfig {
+            max_file_bytes: 1, // Every record fills a file
+            max_file_duration_ns: None,
+            ..PersistenceConfig::new(&dir)
+        });
+        for ts in 0..3 {
+            writer.write(&trade(ts)).unwrap();
+        }
+        assert_eq!(writer.files().len(), 3);
+        drop(writer);
+
+        let mut writer = TickWriter::new(PersistenceConfig {
+            file_prefix: "timed".to_string(),
+            max_file_duration_ns: Some(10),
+            ..PersistenceConfig::new(&dir)
+        });
+        for ts in [0, 5, 10, 15, 25] {
+            writer.write(&trade(ts)).unwrap();
+        }
+        assert_eq!(writer.files().len(), 3);
+        drop(writer);
+
+        let files = list_files(&dir, "timed").unwrap();
+        let replayed: Vec<_> = files
+            .iter()
+            .flat_map(|path| read_file(path).unwrap())
+            .map(|data| data.ts_init())
+            .collect();
+        assert_eq!(replayed, vec![0, 5, 10, 15, 25]);
+        assert_eq!(list_files(&dir, "ticks").unwrap().len(), 3);
+
+        fs::remove_dir_all(dir).unwrap();
+    }
+}
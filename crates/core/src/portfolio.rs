@@ -0,0 +1,766 @@
+//! AlphaForge Portfolio
+//!
+//! Aggregates fills and mark prices into positions and PnL, per strategy and
+//! across the whole node. Positions are netted per (strategy, instrument)
+//! using average-cost accounting: reducing a position realizes PnL against
+//! the average entry price, and flipping through zero opens the remainder at
+//! the fill price. A `CapitalAllocation` limits each strategy's gross
+//! exposure to its share of capital, and exposure groups cap the combined
+//! exposure of all strategies to a cluster of related instruments. Working
+//! orders reserved with `reserve_order` count against both as if they had
+//! filled. Fills arrive from the execution engine the portfolio is attached
+//! to, and mark prices from the message bus (`subscribe_mark_prices`).
+
+use std::collections::{HashMap, HashSet};
+use std::sync::{Arc, Mutex};
+
+use serde::{Deserialize, Serialize};
+
+use crate::allocation::CapitalAllocation;
+use crate::data::MarkPriceUpdate;
+use crate::data_engine::MARK_PRICE_TOPIC_PREFIX;
+use crate::execution_engine::{Fill, Order, OrderSide};
+use crate::identifiers::{InstrumentId, OrderId, StrategyId};
+use crate::message_bus::{CallbackId, CallbackMode, MessageBus};
+use crate::time::{unix_nanos_now, UnixNanos};
+
+/// Settlement details of an instrument
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct InstrumentSpec {
+    /// Currency PnL and exposure are expressed in
+    pub currency: String,
+    /// Contract multiplier (1.0 for spot)
+    pub multiplier: f64,
+}
+
+/// Net position of one strategy in one instrument
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct Position {
+    /// Signed quantity (negative when short)
+    pub quantity: f64,
+    /// Average entry price of the open quantity
+    pub avg_price: f64,
+    /// PnL realized by reducing the position, net of commission
+    pub realized_pnl: f64,
+    /// Commission paid on fills
+    pub commission: f64,
+}
+
+impl Position {
+    /// Whether no quantity is open
+    pub fn is_flat(&self) -> bool {
+        self.quantity == 0.0
+    }
+
+    /// PnL of the open quantity at `price`
+    pub fn unrealized_pnl(&self, price: f64, multiplier: f64) -> f64 {
+        (price - self.avg_price) * self.quantity * multiplier
+    }
+
+    // Apply a signed fill quantity, returning the PnL it realized
+    fn apply(&mut self, quantity: f64, price: f64, multiplier: f64) -> f64 {
+        let mut realized = 0.0;
+        if self.quantity == 0.0 || self.quantity.signum() == quantity.signum() {
+            let total = self.quantity + quantity;
+            self.avg_price = (self.avg_price * self.quantity + price * quantity) / total;
+            self.quantity = total;
+        } else {
+            let closed = quantity.abs().min(self.quantity.abs());
+            realized = (price - self.avg_price) * closed * self.quantity.signum() * multiplier;
+            self.quantity += quantity;
+            if self.quantity.abs() < 1e-12 {
+                self.quantity = 0.0;
+                self.avg_price = 0.0;
+            } else if self.quantity.signum() == quantity.signum() {
+                // Flipped through zero; the remainder opens at the fill price
+                self.avg_price = price;
+            }
+        }
+        self.realized_pnl += realized;
+        realized
+    }
+}
+
+/// PnL of a strategy
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct StrategyPnl {
+    pub realized_pnl: f64,
+    pub unrealized_pnl: f64,
+    pub commission: f64,
+}
+
+impl StrategyPnl {
+    pub fn total_pnl(&self) -> f64 {
+        self.realized_pnl + self.unrealized_pnl
+    }
+}
+
+/// Net exposure to an instrument across all strategies
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct Exposure {
+    /// Signed net quantity
+    pub quantity: f64,
+    /// Signed notional at the last known price
+    pub notional: f64,
+}
+
+/// Instruments whose combined exposure is capped (e.g. an asset class)
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ExposureGroup {
+    pub instruments: HashSet<InstrumentId>,
+    /// Cap on the summed absolute net notional of the instruments, across
+    /// all strategies
+    pub max_notional: f64,
+}
+
+/// Open quantity of a working order, held against the limits of later
+/// orders until it fills or ends
+#[derive(Debug, Clone, PartialEq)]
+struct Reservation {
+    strategy_id: StrategyId,
+    instrument_id: InstrumentId,
+    side: OrderSide,
+    quantity: f64,
+    /// Limit price, else the last price when reserved
+    price: Option<f64>,
+}
+
+/// Point-in-time view of the portfolio
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct PortfolioSnapshot {
+    pub ts: UnixNanos,
+    pub strategies: HashMap<StrategyId, StrategyPnl>,
+    pub realized_pnl: f64,
+    pub unrealized_pnl: f64,
+    pub commission: f64,
+    pub exposure_by_instrument: HashMap<InstrumentId, Exposure>,
+    /// Signed notional per settlement currency
+    pub exposure_by_currency: HashMap<String, f64>,
+}
+
+impl PortfolioSnapshot {
+    pub fn total_pnl(&self) -> f64 {
+        self.realized_pnl + self.unrealized_pnl
+    }
+}
+
+/// Positions and PnL across strategies
+#[derive(Debug, Clone)]
+pub struct Portfolio {
+    /// Currency of instruments without a registered spec
+    default_currency: String,
+    instruments: HashMap<InstrumentId, InstrumentSpec>,
+    positions: HashMap<(StrategyId, InstrumentId), Position>,
+    /// Last mark or fill price per instrument
+    prices: HashMap<InstrumentId, f64>,
+    allocation: CapitalAllocation,
+    /// Exposure limits by group name
+    exposure_groups: HashMap<String, ExposureGroup>,
+    /// Working orders counted against the limits
+    reservations: HashMap<OrderId, Reservation>,
+    fills_applied: u64,
+}
+
+impl Portfolio {
+    /// Create an empty portfolio; unregistered instruments settle in `default_currency`
+    pub fn new(default_currency: &str) -> Self {
+        Self {
+            default_currency: default_currency.to_string(),
+            instruments: HashMap::new(),
+            positions: HashMap::new(),
+            prices: HashMap::new(),
+            allocation: CapitalAllocation::default(),
+            exposure_groups: HashMap::new(),
+            reservations: HashMap::new(),
+            fills_applied: 0,
+        }
+    }
+
+    /// Set the settlement currency and multiplier of an instrument
+    pub fn register_instrument(&mut self, instrument_id: InstrumentId, currency: &str, multiplier: f64) {
+        self.instruments.insert(instrument_id, InstrumentSpec {
+            currency: currency.to_string(),
+            multiplier,
+        });
+    }
+
+    /// Apply a fill of `order`, returning the PnL it realized (before commission)
+    pub fn apply_fill(&mut self, order: &Order, fill: &Fill) -> f64 {
+        let multiplier = self.multiplier(&order.instrument_id);
+        let quantity = match order.side {
+            OrderSide::Buy => fill.quantity,
+            OrderSide::Sell => -fill.quantity,
+        };
+
+        let position = self.positions.entry((order.strategy_id, order.instrument_id)).or_default();
+        let realized = position.apply(quantity, fill.price, multiplier);
+        position.commission += fill.commission;
+        position.realized_pnl -= fill.commission;
+
+        if let Some(reservation) = self.reservations.get_mut(&order.order_id) {
+            reservation.quantity -= fill.quantity;
+            if reservation.quantity <= 1e-12 {
+                self.reservations.remove(&order.order_id);
+            }
+        }
+
+        self.prices.insert(order.instrument_id, fill.price);
+        self.fills_applied += 1;
+        realized
+    }
+
+    /// Revalue open positions in an instrument at its mark price
+    pub fn update_mark_price(&mut self, update: &MarkPriceUpdate) {
+        self.update_price(update.instrument_id, update.mark_price);
+    }
+
+    /// Revalue open positions at every mark price the data engine publishes
+    /// on `message_bus`, until the returned callback is removed with
+    /// `MessageBus::unsubscribe_fn` or the portfolio is dropped
+    pub fn subscribe_mark_prices(portfolio: &Arc<Mutex<Portfolio>>, message_bus: &MessageBus) -> crate::error::Result<CallbackId> {
+        let portfolio = Arc::downgrade(portfolio);
+        let topic = format!("{}*", MARK_PRICE_TOPIC_PREFIX);
+        message_bus.subscribe_fn(&topic, CallbackMode::Publisher, move |envelope| {
+            let Some(portfolio) = portfolio.upgrade() else {
+                return;
+            };
+            match envelope.decode::<MarkPriceUpdate>() {
+                Ok(update) => match portfolio.lock() {
+                    Ok(mut portfolio) => portfolio.update_mark_price(&update),
+                    Err(_) => tracing::warn!("Portfolio lock poisoned, dropping {}", envelope.message_type),
+                },
+                Err(e) => tracing::warn!("Dropping malformed mark price on {}: {}", envelope.message_type, e),
+            }
+        })
+    }
+
+    /// Revalue open positions in an instrument at `price`
+    pub fn update_price(&mut self, instrument_id: InstrumentId, price: f64) {
+        if price.is_finite() {
+            self.prices.insert(instrument_id, price);
+        }
+    }
+
+    /// Position of a strategy in an instrument
+    pub fn position(&self, strategy_id: StrategyId, instrument_id: InstrumentId) -> Option<&Position> {
+        self.positions.get(&(strategy_id, instrument_id))
+    }
+
+    /// Open positions of a strategy
+    pub fn open_positions(&self, strategy_id: StrategyId) -> HashMap<InstrumentId, f64> {
+        self.positions
+            .iter()
+            .filter(|((owner, _), position)| *owner == strategy_id && !position.is_flat())
+            .map(|((_, instrument_id), position)| (*instrument_id, position.quantity))
+            .collect()
+    }
+
+    /// PnL of a strategy
+    pub fn strategy_pnl(&self, strategy_id: StrategyId) -> StrategyPnl {
+        let mut pnl = StrategyPnl::default();
+        for ((owner, instrument_id), position) in &self.positions {
+            if *owner == strategy_id {
+                self.accumulate(&mut pnl, instrument_id, position);
+            }
+        }
+        pnl
+    }
+
+    /// Gross notional of a strategy's open positions at the latest prices
+    pub fn gross_exposure(&self, strategy_id: StrategyId) -> f64 {
+        self.positions
+            .iter()
+            .filter(|((owner, _), _)| *owner == strategy_id)
+            .map(|((_, instrument_id), position)| self.notional(instrument_id, position.quantity, position.avg_price).abs())
+            .sum()
+    }
+
+    pub fn allocation(&self) -> &CapitalAllocation {
+        &self.allocation
+    }
+
+    /// Allocation to change weights, limits or total capital
+    pub fn allocation_mut(&mut self) -> &mut CapitalAllocation {
+        &mut self.allocation
+    }
+
+    pub fn set_allocation(&mut self, allocation: CapitalAllocation) {
+        self.allocation = allocation;
+    }
+
+    /// Cap the combined exposure of all strategies to `instruments`,
+    /// replacing any group with the same name
+    pub fn set_exposure_limit(&mut self, group: &str, instruments: HashSet<InstrumentId>, max_notional: f64) -> Result<(), String> {
+        if !max_notional.is_finite() || max_notional < 0.0 {
+            return Err(format!("Invalid exposure limit for group {}: {}", group, max_notional));
+        }
+        self.exposure_groups.insert(group.to_string(), ExposureGroup { instruments, max_notional });
+        Ok(())
+    }
+
+    pub fn remove_exposure_limit(&mut self, group: &str) -> Option<ExposureGroup> {
+        self.exposure_groups.remove(group)
+    }
+
+    pub fn exposure_group(&self, group: &str) -> Option<&ExposureGroup> {
+        self.exposure_groups.get(group)
+    }
+
+    /// Summed absolute net notional of a group's instruments across all
+    /// strategies (`None` for an unknown group)
+    pub fn group_exposure(&self, group: &str) -> Option<f64> {
+        let group = self.exposure_groups.get(group)?;
+        Some(group.instruments.iter().map(|instrument_id| self.net_notional(instrument_id).abs()).sum())
+    }
+
+    /// Check that filling `order`, and every other working order of its
+    /// strategy, would keep the strategy's gross exposure within its
+    /// allocated capital, and that filling it with every working order of
+    /// any strategy would keep every group containing the instrument within
+    /// its limit. Orders that reduce exposure always pass, as do orders that
+    /// cannot be valued yet (market orders in an instrument with no known
+    /// price).
+    pub fn check_order(&self, order: &Order) -> Result<(), String> {
+        let Some(price) = order.price.or_else(|| self.prices.get(&order.instrument_id).copied()) else {
+            return Ok(());
+        };
+        let delta = match order.side {
+            OrderSide::Buy => order.remaining_quantity(),
+            OrderSide::Sell => -order.remaining_quantity(),
+        };
+        self.check_allocation(order, delta, price)?;
+        self.check_exposure_groups(order, delta, price)
+    }
+
+    /// Check `order` as `check_order` does and, if it passes, hold its open
+    /// quantity against the limits of later orders until it fills or is
+    /// released. Reserving an order again, e.g. once modified, replaces its
+    /// reservation.
+    pub fn reserve_order(&mut self, order: &Order) -> Result<(), String> {
+        self.check_order(order)?;
+        self.reserve_order_unchecked(order);
+        Ok(())
+    }
+
+    /// Hold `order`'s open quantity without checking it, e.g. to restore its
+    /// reservation after a venue refused to modify it
+    pub fn reserve_order_unchecked(&mut self, order: &Order) {
+        let quantity = order.remaining_quantity();
+        if quantity <= 0.0 {
+            self.reservations.remove(&order.order_id);
+            return;
+        }
+        self.reservations.insert(order.order_id, Reservation {
+            strategy_id: order.strategy_id,
+            instrument_id: order.instrument_id,
+            side: order.side,
+            quantity,
+            price: order.price.or_else(|| self.prices.get(&order.instrument_id).copied()),
+        });
+    }
+
+    /// Stop holding an order's open quantity, once it is cancelled or
+    /// rejected. Fills release it as they book.
+    pub fn release_order(&mut self, order_id: OrderId) -> bool {
+        self.reservations.remove(&order_id).is_some()
+    }
+
+    /// Open quantity reserved for a strategy's working orders in an
+    /// instrument, as (buy, sell)
+    pub fn open_order_quantity(&self, strategy_id: StrategyId, instrument_id: InstrumentId) -> (f64, f64) {
+        self.reservations
+            .values()
+            .filter(|reservation| reservation.strategy_id == strategy_id && reservation.instrument_id == instrument_id)
+            .fold((0.0, 0.0), |(buy, sell), reservation| match reservation.side {
+                OrderSide::Buy => (buy + reservation.quantity, sell),
+                OrderSide::Sell => (buy, sell + reservation.quantity),
+            })
+    }
+
+    fn check_allocation(&self, order: &Order, delta: f64, price: f64) -> Result<(), String> {
+        let Some(capital) = self.allocation.capital(order.strategy_id) else {
+            return Ok(());
+        };
+
+        let owner = Some(order.strategy_id);
+        let (long, short) = self.committed_quantity(owner, &order.instrument_id, order.order_id);
+        let projected_quantity = match order.side {
+            OrderSide::Buy if (long + delta).abs() > long.abs() => (long + delta).abs().max(short.abs()),
+            OrderSide::Sell if (short + delta).abs() > short.abs() => (short + delta).abs().max(long.abs()),
+            _ => return Ok(()),
+        };
+
+        let instruments: HashSet<InstrumentId> = self.positions
+            .keys()
+            .filter(|(owner, _)| *owner == order.strategy_id)
+            .map(|(_, instrument_id)| *instrument_id)
+            .chain(self.reservations.values().filter(|reservation| reservation.strategy_id == order.strategy_id).map(|reservation| reservation.instrument_id))
+            .collect();
+        let exposure: f64 = instruments
+            .iter()
+            .map(|instrument_id| self.committed_notional(owner, instrument_id, order.order_id))
+            .sum();
+        let projected = exposure
+            - self.committed_notional(owner, &order.instrument_id, order.order_id)
+            + projected_quantity * price * self.multiplier(&order.instrument_id);
+
+        if projected > capital + 1e-9 {
+            return Err(format!(
+                "Order would take strategy {} gross exposure to {:.2}, above its allocated capital of {:.2}",
+                order.strategy_id, projected, capital
+            ));
+        }
+        Ok(())
+    }
+
+    // Quantity held in an instrument by one strategy, or all when `owner` is
+    // None, once every working order on the buy side fills and once every
+    // one on the sell side does, leaving out `excluded`
+    fn committed_quantity(&self, owner: Option<StrategyId>, instrument_id: &InstrumentId, excluded: OrderId) -> (f64, f64) {
+        let held: f64 = self.positions
+            .iter()
+            .filter(|((strategy_id, id), _)| id == instrument_id && owner.is_none_or(|owner| owner == *strategy_id))
+            .map(|(_, position)| position.quantity)
+            .sum();
+        self.reservations
+            .iter()
+            .filter(|(order_id, reservation)| {
+                **order_id != excluded
+                    && reservation.instrument_id == *instrument_id
+                    && owner.is_none_or(|owner| owner == reservation.strategy_id)
+            })
+            .fold((held, held), |(long, short), (_, reservation)| match reservation.side {
+                OrderSide::Buy => (long + reservation.quantity, short),
+                OrderSide::Sell => (long, short - reservation.quantity),
+            })
+    }
+
+    // Larger absolute notional of `committed_quantity`, valued at the last
+    // price, else a reserved order's price
+    fn committed_notional(&self, owner: Option<StrategyId>, instrument_id: &InstrumentId, excluded: OrderId) -> f64 {
+        let (long, short) = self.committed_quantity(owner, instrument_id, excluded);
+        let price = self.prices.get(instrument_id).copied().or_else(|| {
+            self.reservations
+                .values()
+                .filter(|reservation| reservation.instrument_id == *instrument_id)
+                .find_map(|reservation| reservation.price)
+        });
+        long.abs().max(short.abs()) * price.unwrap_or_default() * self.multiplier(instrument_id)
+    }
+
+    fn check_exposure_groups(&self, order: &Order, delta: f64, price: f64) -> Result<(), String> {
+        let instrument_id = &order.instrument_id;
+        let (long, short) = self.committed_quantity(None, instrument_id, order.order_id);
+        let projected_quantity = match order.side {
+            OrderSide::Buy if (long + delta).abs() > long.abs() => (long + delta).abs().max(short.abs()),
+            OrderSide::Sell if (short + delta).abs() > short.abs() => (short + delta).abs().max(long.abs()),
+            _ => return Ok(()),
+        };
+        let current = self.committed_notional(None, instrument_id, order.order_id);
+        let projected = projected_quantity * price * self.multiplier(instrument_id);
+
+        for (name, group) in &self.exposure_groups {
+            if !group.instruments.contains(instrument_id) {
+                continue;
+            }
+            let exposure: f64 = group
+                .instruments
+                .iter()
+                .map(|instrument_id| self.committed_notional(None, instrument_id, order.order_id))
+                .sum();
+            let projected = exposure - current + projected;
+            if projected > group.max_notional + 1e-9 {
+                return Err(format!(
+                    "Order would take exposure group {} to {:.2}, above its limit of {:.2}",
+                    name, projected, group.max_notional
+                ));
+            }
+        }
+        Ok(())
+    }
+
+    /// Number of fills applied
+    pub fn fills_applied(&self) -> u64 {
+        self.fills_applied
+    }
+
+    /// Value the whole portfolio at the latest prices
+    pub fn snapshot(&self) -> PortfolioSnapshot {
+        let mut snapshot = PortfolioSnapshot {
+            ts: unix_nanos_now(),
+            ..Default::default()
+        };
+
+        for ((strategy_id, instrument_id), position) in &self.positions {
+            let pnl = snapshot.strategies.entry(*strategy_id).or_default();
+            self.accumulate(pnl, instrument_id, position);
+
+            if position.is_flat() {
+                continue;
+            }
+            let notional = self.notional(instrument_id, position.quantity, position.avg_price);
+
+            let exposure = snapshot.exposure_by_instrument.entry(*instrument_id).or_default();
+            exposure.quantity += position.quantity;
+            exposure.notional += notional;
+            *snapshot.exposure_by_currency.entry(self.currency(instrument_id).to_string()).or_default() += notional;
+        }
+
+        for pnl in snapshot.strategies.values() {
+            snapshot.realized_pnl += pnl.realized_pnl;
+            snapshot.unrealized_pnl += pnl.unrealized_pnl;
+            snapshot.commission += pnl.commission;
+        }
+        snapshot
+    }
+
+    fn accumulate(&self, pnl: &mut StrategyPnl, instrument_id: &InstrumentId, position: &Position) {
+        pnl.realized_pnl += position.realized_pnl;
+        pnl.commission += position.commission;
+        if let Some(price) = self.prices.get(instrument_id) {
+            pnl.unrealized_pnl += position.unrealized_pnl(*price, self.multiplier(instrument_id));
+        }
+    }
+
+    // Signed value of `quantity` at the last price, or `fallback_price` if none
+    fn notional(&self, instrument_id: &InstrumentId, quantity: f64, fallback_price: f64) -> f64 {
+        let price = self.prices.get(instrument_id).copied().unwrap_or(fallback_price);
+        quantity * price * self.multiplier(instrument_id)
+    }
+
+    // Signed notional of all strategies' positions in an instrument
+    fn net_notional(&self, instrument_id: &InstrumentId) -> f64 {
+        self.positions
+            .iter()
+            .filter(|((_, id), _)| id == instrument_id)
+            .map(|(_, position)| self.notional(instrument_id, position.quantity, position.avg_price))
+            .sum()
+    }
+
+    fn multiplier(&self, instrument_id: &InstrumentId) -> f64 {
+        self.instruments.get(instrument_id).map_or(1.0, |spec| spec.multiplier)
+    }
+
+    fn currency(&self, instrument_id: &InstrumentId) -> &str {
+        self.instruments
+            .get(instrument_id)
+            .map_or(self.default_currency.as_str(), |spec| spec.currency.as_str())
+    }
+}
+
+impl Default for Portfolio {
+    fn default() -> Self {
+        Self::new("USD")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn fill(order: &Order, quantity: f64, price: f64, commission: f64) -> Fill {
+        Fill {
+            order_id: order.order_id,
+            fill_id: "1".to_string(),
+            price,
+            quantity,
+            timestamp: 0,
+            commission,
+            commission_currency: "USD".to_string(),
+        }
+    }
+
+    #[test]
+    fn test_realized_pnl_through_flip() {
+        let strategy_id = StrategyId::new(1);
+        let instrument_id = InstrumentId::new(7);
+        let mut portfolio = Portfolio::default();
+
+        let buy = Order::market(strategy_id, instrument_id, OrderSide::Buy, 2.0);
+        let sell = Order::market(strategy_id, instrument_id, OrderSide::Sell, 3.0);
+        portfolio.apply_fill(&buy, &fill(&buy, 1.0, 100.0, 0.0));
+        portfolio.apply_fill(&buy, &fill(&buy, 1.0, 110.0, 0.0));
+        assert_eq!(portfolio.position(strategy_id, instrument_id).unwrap().avg_price, 105.0);
+
+        // Closes 2 at +15 each, then opens 1 short at 120
+        let realized = portfolio.apply_fill(&sell, &fill(&sell, 3.0, 120.0, 1.0));
+        assert_eq!(realized, 30.0);
+        let position = portfolio.position(strategy_id, instrument_id).unwrap();
+        assert_eq!(position.quantity, -1.0);
+        assert_eq!(position.avg_price, 120.0);
+        assert_eq!(position.realized_pnl, 29.0);
+
+        portfolio.update_price(instrument_id, 125.0);
+        let pnl = portfolio.strategy_pnl(strategy_id);
+        assert_eq!(pnl.unrealized_pnl, -5.0);
+        assert_eq!(pnl.total_pnl(), 24.0);
+    }
+
+    #[test]
+    fn test_snapshot_aggregates_strategies_and_currencies() {
+        let instrument_a = InstrumentId::new(1);
+        let instrument_b = InstrumentId::new(2);
+        let mut portfolio = Portfolio::new("USDT");
+        portfolio.register_instrument(instrument_b, "BTC", 10.0);
+
+        let long_a = Order::market(StrategyId::new(1), instrument_a, OrderSide::Buy, 2.0);
+        let short_a = Order::market(StrategyId::new(2), instrument_a, OrderSide::Sell, 1.0);
+        let long_b = Order::market(StrategyId::new(2), instrument_b, OrderSide::Buy, 1.0);
+        portfolio.apply_fill(&long_a, &fill(&long_a, 2.0, 50.0, 0.5));
+        portfolio.apply_fill(&short_a, &fill(&short_a, 1.0, 50.0, 0.5));
+        portfolio.apply_fill(&long_b, &fill(&long_b, 1.0, 3.0, 0.0));
+
+        portfolio.update_mark_price(&MarkPriceUpdate {
+            instrument_id: instrument_a,
+            mark_price: 60.0,
+            ts_event: 0,
+            ts_init: 0,
+        });
+        let snapshot = portfolio.snapshot();
+
+        assert_eq!(snapshot.strategies[&StrategyId::new(1)].unrealized_pnl, 20.0);
+        assert_eq!(snapshot.strategies[&StrategyId::new(2)].unrealized_pnl, -10.0);
+        assert_eq!(snapshot.unrealized_pnl, 10.0);
+        assert_eq!(snapshot.realized_pnl, -1.0);
+        assert_eq!(snapshot.commission, 1.0);
+        assert_eq!(snapshot.exposure_by_instrument[&instrument_a], Exposure { quantity: 1.0, notional: 60.0 });
+        assert_eq!(snapshot.exposure_by_currency["USDT"], 60.0);
+        assert_eq!(snapshot.exposure_by_currency["BTC"], 30.0);
+        assert_eq!(portfolio.open_positions(StrategyId::new(2)).len(), 2);
+    }
+
+    #[test]
+    fn test_mark_prices_consumed_from_bus() {
+        let strategy_id = StrategyId::new(1);
+        let instrument_id = InstrumentId::new(7);
+        let portfolio = Arc::new(Mutex::new(Portfolio::default()));
+        let buy = Order::market(strategy_id, instrument_id, OrderSide::Buy, 2.0);
+        portfolio.lock().unwrap().apply_fill(&buy, &fill(&buy, 2.0, 100.0, 0.0));
+
+        let bus = Arc::new(MessageBus::new());
+        let mut data_engine = crate::data_engine::DataEngine::new(crate::data_engine::DataEngineConfig::default());
+        data_engine.set_message_bus(Arc::clone(&bus));
+        data_engine.start().unwrap();
+        let callback = Portfolio::subscribe_mark_prices(&portfolio, &bus).unwrap();
+
+        let mark = |price| MarkPriceUpdate { instrument_id, mark_price: price, ts_event: 0, ts_init: 0 };
+        data_engine.process_mark_price(mark(105.0)).unwrap();
+        assert_eq!(portfolio.lock().unwrap().strategy_pnl(strategy_id).unrealized_pnl, 10.0);
+
+        assert!(bus.unsubscribe_fn(callback));
+        data_engine.process_mark_price(mark(110.0)).unwrap();
+        assert_eq!(portfolio.lock().unwrap().strategy_pnl(strategy_id).unrealized_pnl, 10.0);
+    }
+
+    #[test]
+    fn test_orders_checked_against_allocation() {
+        let (allocated, unallocated) = (StrategyId::new(1), StrategyId::new(2));
+        let instrument_id = InstrumentId::new(1);
+        let mut portfolio = Portfolio::default();
+        let mut allocation = CapitalAllocation::new(100_000.0).unwrap();
+        allocation.allocate(allocated, 0.1, None).unwrap();
+        portfolio.set_allocation(allocation);
+
+        let buy = Order::limit(allocated, instrument_id, OrderSide::Buy, 80.0, 100.0);
+        assert!(portfolio.check_order(&buy).is_ok());
+        portfolio.apply_fill(&buy, &fill(&buy, 80.0, 100.0, 0.0));
+        assert_eq!(portfolio.gross_exposure(allocated), 8_000.0);
+
+        // 80 + 30 units at 100 exceeds the 10,000 allocated
+        let more = Order::limit(allocated, instrument_id, OrderSide::Buy, 30.0, 100.0);
+        assert!(portfolio.check_order(&more).is_err());
+        let reduce = Order::market(allocated, instrument_id, OrderSide::Sell, 50.0);
+        assert!(portfolio.check_order(&reduce).is_ok());
+        // Flipping to 120 short grows exposure past the limit
+        let flip = Order::market(allocated, instrument_id, OrderSide::Sell, 200.0);
+        assert!(portfolio.check_order(&flip).is_err());
+        assert!(portfolio.check_order(&Order::market(unallocated, instrument_id, OrderSide::Buy, 1e6)).is_ok());
+
+        portfolio.allocation_mut().rebalance(&[(allocated, 0.2)]).unwrap();
+        assert!(portfolio.check_order(&more).is_ok());
+    }
+
+    #[test]
+    fn test_orders_checked_against_exposure_groups() {
+        let (trend, carry) = (StrategyId::new(1), StrategyId::new(2));
+        let (btc, eth, gold) = (InstrumentId::new(1), InstrumentId::new(2), InstrumentId::new(3));
+        let mut portfolio = Portfolio::default();
+        portfolio.set_exposure_limit("crypto", HashSet::from([btc, eth]), 10_000.0).unwrap();
+        assert!(portfolio.set_exposure_limit("metals", HashSet::from([gold]), -1.0).is_err());
+
+        let btc_buy = Order::limit(trend, btc, OrderSide::Buy, 60.0, 100.0);
+        portfolio.apply_fill(&btc_buy, &fill(&btc_buy, 60.0, 100.0, 0.0));
+        let eth_sell = Order::limit(carry, eth, OrderSide::Sell, 30.0, 100.0);
+        portfolio.apply_fill(&eth_sell, &fill(&eth_sell, 30.0, 100.0, 0.0));
+        assert_eq!(portfolio.group_exposure("crypto"), Some(9_000.0));
+        assert_eq!(portfolio.group_exposure("metals"), None);
+
+        // Another strategy's order counts against the same cluster
+        assert!(portfolio.check_order(&Order::limit(carry, btc, OrderSide::Buy, 20.0, 100.0)).is_err());
+        assert!(portfolio.check_order(&Order::limit(carry, btc, OrderSide::Buy, 10.0, 100.0)).is_ok());
+        // Reducing and offsetting orders pass
+        assert!(portfolio.check_order(&Order::limit(carry, btc, OrderSide::Sell, 100.0, 100.0)).is_ok());
+        assert!(portfolio.check_order(&Order::limit(trend, eth, OrderSide::Buy, 30.0, 100.0)).is_ok());
+        // Instruments outside the group are not limited
+        assert!(portfolio.check_order(&Order::limit(trend, gold, OrderSide::Buy, 1e6, 100.0)).is_ok());
+
+        portfolio.remove_exposure_limit("crypto");
+        assert!(portfolio.check_order(&Order::limit(carry, btc, OrderSide::Buy, 20.0, 100.0)).is_ok());
+    }
+
+    #[test]
+    fn test_working_orders_reserved_against_allocation() {
+        let strategy_id = StrategyId::new(1);
+        let instrument_id = InstrumentId::new(1);
+        let mut portfolio = Portfolio::default();
+        let mut allocation = CapitalAllocation::new(100_000.0).unwrap();
+        allocation.allocate(strategy_id, 0.1, None).unwrap();
+        portfolio.set_allocation(allocation);
+
+        let buy = Order::limit(strategy_id, instrument_id, OrderSide::Buy, 60.0, 100.0);
+        portfolio.reserve_order(&buy).unwrap();
+        // 60 working plus 50 more exceeds the 10,000 allocated
+        let more = Order::limit(strategy_id, instrument_id, OrderSide::Buy, 50.0, 100.0);
+        assert!(portfolio.check_order(&more).is_err());
+        assert!(portfolio.reserve_order(&more).is_err());
+        // Either side filling alone stays within it
+        let sell = Order::limit(strategy_id, instrument_id, OrderSide::Sell, 50.0, 100.0);
+        portfolio.reserve_order(&sell).unwrap();
+        assert_eq!(portfolio.open_order_quantity(strategy_id, instrument_id), (60.0, 50.0));
+
+        // Fills move quantity from the reservation into the position
+        portfolio.apply_fill(&buy, &fill(&buy, 20.0, 100.0, 0.0));
+        assert_eq!(portfolio.open_order_quantity(strategy_id, instrument_id), (40.0, 50.0));
+        assert!(portfolio.check_order(&more).is_err());
+        assert!(portfolio.release_order(buy.order_id));
+        portfolio.reserve_order(&more).unwrap();
+
+        // Reserving again replaces the order's own reservation
+        let mut modified = more.clone();
+        modified.quantity = 80.0;
+        portfolio.reserve_order(&modified).unwrap();
+        modified.quantity = 81.0;
+        assert!(portfolio.reserve_order(&modified).is_err());
+        assert_eq!(portfolio.open_order_quantity(strategy_id, instrument_id), (80.0, 50.0));
+    }
+
+    #[test]
+    fn test_working_orders_reserved_against_exposure_groups() {
+        let (trend, carry) = (StrategyId::new(1), StrategyId::new(2));
+        let (btc, eth) = (InstrumentId::new(1), InstrumentId::new(2));
+        let mut portfolio = Portfolio::default();
+        portfolio.set_exposure_limit("crypto", HashSet::from([btc, eth]), 10_000.0).unwrap();
+
+        // Working orders of different strategies share the group's limit
+        portfolio.reserve_order(&Order::limit(trend, btc, OrderSide::Buy, 60.0, 100.0)).unwrap();
+        let eth_sell = Order::limit(carry, eth, OrderSide::Sell, 30.0, 100.0);
+        portfolio.reserve_order(&eth_sell).unwrap();
+        assert!(portfolio.reserve_order(&Order::limit(carry, btc, OrderSide::Buy, 20.0, 100.0)).is_err());
+        portfolio.reserve_order(&Order::limit(carry, btc, OrderSide::Buy, 10.0, 100.0)).unwrap();
+        // Opposite working orders count one side at a time
+        assert!(portfolio.reserve_order(&Order::limit(trend, eth, OrderSide::Buy, 30.0, 100.0)).is_ok());
+
+        portfolio.release_order(eth_sell.order_id);
+        assert!(portfolio.check_order(&Order::limit(carry, eth, OrderSide::Sell, 30.0, 100.0)).is_ok());
+    }
+}
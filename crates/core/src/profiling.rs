@@ -0,0 +1,263 @@
+//! AlphaForge Profiling
+//!
+//! `profile_scope!("name")` times the rest of the enclosing block with a
+//! `PrecisionTimer` and records the duration in a histogram kept per scope
+//! name. Profiling is off until `profiler().set_enabled(true)`, as every
+//! thread timing a scope updates the same histogram. Scope statistics are
+//! read from `profiler()`, which is also a `MetricsSource` exporting them
+//! as a summary. Each profiled scope enters a trace level `profile_scope`
+//! span, so tracing subscribers see the same scopes.
+
+use std::sync::atomic::{AtomicBool, AtomicU64, Ordering};
+use std::sync::{Arc, OnceLock};
+
+use dashmap::DashMap;
+use serde::Serialize;
+
+use crate::time::PrecisionTimer;
+
+/// Histogram buckets: four per power of two up to 2^43ns, about 2.4 hours,
+/// so percentiles are within 25%
+pub const HISTOGRAM_BUCKETS: usize = 168;
+
+/// Time the rest of the enclosing block under a scope name, which must be
+/// a `&'static str`
+#[macro_export]
+macro_rules! profile_scope {
+    ($name:expr) => {
+        let _profile_scope = {
+            static SCOPE: ::std::sync::OnceLock<::std::sync::Arc<$crate::profiling::ScopeHistogram>> =
+                ::std::sync::OnceLock::new();
+            $crate::profiling::ProfileGuard::new(SCOPE.get_or_init(|| $crate::profiling::profiler().scope($name)))
+        };
+    };
+}
+
+/// Durations recorded under one scope name
+#[derive(Debug)]
+pub struct ScopeHistogram {
+    name: &'static str,
+    total_ns: AtomicU64,
+    max_ns: AtomicU64,
+    buckets: [AtomicU64; HISTOGRAM_BUCKETS],
+}
+
+impl ScopeHistogram {
+    /// Histogram not registered with the profiler, for durations a
+    /// component measures itself
+    pub fn new(name: &'static str) -> Self {
+        Self {
+            name,
+            total_ns: AtomicU64::new(0),
+            max_ns: AtomicU64::new(0),
+            buckets: std::array::from_fn(|_| AtomicU64::new(0)),
+        }
+    }
+
+    pub fn name(&self) -> &'static str {
+        self.name
+    }
+
+    pub fn record(&self, duration_ns: u64) {
+        self.total_ns.fetch_add(duration_ns, Ordering::Relaxed);
+        self.max_ns.fetch_max(duration_ns, Ordering::Relaxed);
+        self.buckets[bucket_index(duration_ns)].fetch_add(1, Ordering::Relaxed);
+    }
+
+    pub fn statistics(&self) -> ScopeStatistics {
+        let counts: Vec<u64> = self.buckets.iter().map(|bucket| bucket.load(Ordering::Relaxed)).collect();
+        let count: u64 = counts.iter().sum();
+        let total_ns = self.total_ns.load(Ordering::Relaxed);
+        let max_ns = self.max_ns.load(Ordering::Relaxed);
+        // Upper bound of the bucket holding the `q` quantile
+        let quantile = |q: f64| {
+            let rank = ((count as f64 * q).ceil() as u64).max(1);
+            let mut seen = 0;
+            for (index, bucket) in counts.iter().enumerate() {
+                seen += bucket;
+                if seen >= rank {
+                    return bucket_upper_bound(index).min(max_ns);
+                }
+            }
+            0
+        };
+        ScopeStatistics {
+            name: self.name.to_string(),
+            count,
+            total_ns,
+            mean_ns: if count == 0 { 0.0 } else { total_ns as f64 / count as f64 },
+            max_ns,
+            p50_ns: quantile(0.5),
+            p90_ns: quantile(0.9),
+            p99_ns: quantile(0.99),
+        }
+    }
+
+    fn reset(&self) {
+        self.total_ns.store(0, Ordering::Relaxed);
+        self.max_ns.store(0, Ordering::Relaxed);
+        for bucket in &self.buckets {
+            bucket.store(0, Ordering::Relaxed);
+        }
+    }
+}
+
+fn bucket_index(duration_ns: u64) -> usize {
+    if duration_ns < 4 {
+        return duration_ns as usize;
+    }
+    let exponent = 63 - duration_ns.leading_zeros() as usize;
+    let sub = ((duration_ns >> (exponent - 2)) & 3) as usize;
+    ((exponent - 1) * 4 + sub).min(HISTOGRAM_BUCKETS - 1)
+}
+
+fn bucket_upper_bound(index: usize) -> u64 {
+    if index < 4 {
+        return index as u64;
+    }
+    let exponent = index / 4 + 1;
+    let lower = (4 + (index % 4) as u64) << (exponent - 2);
+    lower + (1 << (exponent - 2)) - 1
+}
+
+/// Summary of a scope's recorded durations
+#[derive(Debug, Clone, Default, PartialEq, Serialize)]
+pub struct ScopeStatistics {
+    pub name: String,
+    pub count: u64,
+    pub total_ns: u64,
+    pub mean_ns: f64,
+    pub max_ns: u64,
+    pub p50_ns: u64,
+    pub p90_ns: u64,
+    pub p99_ns: u64,
+}
+
+/// Histograms of every profiled scope in the process
+#[derive(Debug)]
+pub struct Profiler {
+    enabled: AtomicBool,
+    scopes: DashMap<&'static str, Arc<ScopeHistogram>>,
+}
+
+/// The process-wide profiler that `profile_scope!` records into; clone it
+/// to register with a `MetricsRegistry`
+pub fn profiler() -> &'static Arc<Profiler> {
+    static PROFILER: OnceLock<Arc<Profiler>> = OnceLock::new();
+    PROFILER.get_or_init(|| {
+        Arc::new(Profiler {
+            enabled: AtomicBool::new(false),
+            scopes: DashMap::new(),
+        })
+    })
+}
+
+impl Profiler {
+    /// Start or stop timing; disabled scopes cost a flag check
+    pub fn set_enabled(&self, enabled: bool) {
+        self.enabled.store(enabled, Ordering::Relaxed);
+    }
+
+    pub fn is_enabled(&self) -> bool {
+        self.enabled.load(Ordering::Relaxed)
+    }
+
+    /// Histogram of `name`, created on first use
+    pub fn scope(&self, name: &'static str) -> Arc<ScopeHistogram> {
+        Arc::clone(&self.scopes.entry(name).or_insert_with(|| Arc::new(ScopeHistogram::new(name))))
+    }
+
+    /// Statistics of every scope, by name
+    pub fn statistics(&self) -> Vec<ScopeStatistics> {
+        let mut statistics: Vec<ScopeStatistics> = self.scopes.iter().map(|scope| scope.statistics()).collect();
+        statistics.sort_by(|a, b| a.name.cmp(&b.name));
+        statistics
+    }
+
+    /// Clear every scope's durations
+    pub fn reset(&self) {
+        for scope in self.scopes.iter() {
+            scope.reset();
+        }
+    }
+}
+
+/// Records the time until it is dropped into a scope's histogram
+pub struct ProfileGuard {
+    scope: &'static ScopeHistogram,
+    /// Present while profiling is enabled
+    timing: Option<(PrecisionTimer, tracing::span::EnteredSpan)>,
+}
+
+impl ProfileGuard {
+    pub fn new(scope: &'static ScopeHistogram) -> Self {
+        let timing = profiler().is_enabled().then(|| {
+            let span = tracing::trace_span!("profile_scope", scope = scope.name).entered();
+            (PrecisionTimer::start(), span)
+        });
+        Self { scope, timing }
+    }
+}
+
+impl Drop for ProfileGuard {
+    fn drop(&mut self) {
+        if let Some((timer, _span)) = &self.timing {
+            self.scope.record(timer.elapsed_nanos());
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_profile_scope_histogram() {
+        for ns in [0, 3, 4, 7, 8, 1_000, 123_456_789, u64::MAX] {
+            assert!(bucket_upper_bound(bucket_index(ns)) >= ns.min(bucket_upper_bound(HISTOGRAM_BUCKETS - 1)));
+        }
+
+        fn profiled() {
+            profile_scope!("test.profiled");
+            std::thread::sleep(std::time::Duration::from_millis(2));
+        }
+        profiled(); // Off by default
+
+        // Restores the global switch even if an assertion fails
+        struct RestoreEnabled(bool);
+        impl Drop for RestoreEnabled {
+            fn drop(&mut self) {
+                profiler().set_enabled(self.0);
+            }
+        }
+        let _restore = RestoreEnabled(profiler().is_enabled());
+        profiler().set_enabled(true);
+        for _ in 0..3 {
+            profiled();
+        }
+        let stats = profiler().statistics().into_iter().find(|stats| stats.name == "test.profiled").unwrap();
+        assert_eq!(stats.count, 3);
+        assert!(stats.p50_ns >= 2_000_000 && stats.p50_ns <= stats.max_ns);
+        assert!(stats.mean_ns >= 2_000_000.0);
+
+        // Direct records land in the right buckets
+        let scope = profiler().scope("test.direct");
+        for ns in 1..=100 {
+            scope.record(ns * 1_000);
+        }
+        let stats = scope.statistics();
+        assert_eq!((stats.count, stats.max_ns), (100, 100_000));
+        assert!(stats.p50_ns >= 50_000 && stats.p50_ns < 62_500);
+        assert!(stats.p99_ns >= 99_000);
+        scope.reset();
+        assert_eq!(scope.statistics().count, 0);
+
+        let registry = crate::metrics::MetricsRegistry::new();
+        registry.register("profiler", profiler().clone());
+        let text = registry.render();
+        assert!(text.contains("# TYPE alphaforge_profile_seconds summary\n"));
+        assert!(text.contains("alphaforge_profile_seconds_count{source=\"profiler\",scope=\"test.profiled\"} 3\n"));
+        assert!(text.contains("alphaforge_profile_seconds_sum{source=\"profiler\",scope=\"test.profiled\"} 0.00"));
+        assert!(text.contains("alphaforge_profile_seconds{source=\"profiler\",scope=\"test.profiled\",quantile=\"0.99\"}"));
+    }
+}
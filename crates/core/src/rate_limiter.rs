@@ -0,0 +1,206 @@
+//! AlphaForge Rate Limiter
+//!
+//! Keeps callers under a venue's request limits. A limiter hands out
+//! permits, by token bucket or sliding window, and callers wait for them
+//! blocking a thread or as a future. Share one limiter, in an `Arc`, among
+//! everything that counts against the same limit.
+
+use std::collections::VecDeque;
+use std::time::{Duration, Instant};
+
+use parking_lot::Mutex;
+
+use crate::error::{AlphaForgeError, Result};
+
+/// How a `RateLimiter` counts permits
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum RateLimit {
+    /// Bursts of up to `capacity` permits, refilled by `refill` every `per`
+    TokenBucket { capacity: u32, refill: u32, per: Duration },
+    /// At most `limit` permits in any `window`, as venues with weight
+    /// limits per minute count
+    SlidingWindow { limit: u32, window: Duration },
+}
+
+impl RateLimit {
+    /// `count` permits a second, all of which may be taken at once
+    pub fn per_second(count: u32) -> Self {
+        RateLimit::TokenBucket {
+            capacity: count,
+            refill: count,
+            per: Duration::from_secs(1),
+        }
+    }
+
+    /// Most permits that can ever be granted at once
+    pub fn max_permits(&self) -> u32 {
+        match *self {
+            RateLimit::TokenBucket { capacity, .. } => capacity,
+            RateLimit::SlidingWindow { limit, .. } => limit,
+        }
+    }
+}
+
+#[derive(Debug)]
+enum State {
+    Bucket { tokens: f64, updated: Instant },
+    /// Grants within the window, oldest first
+    Window { grants: VecDeque<(Instant, u32)>, granted: u32 },
+}
+
+/// Hands out permits at a limited rate
+#[derive(Debug)]
+pub struct RateLimiter {
+    limit: RateLimit,
+    state: Mutex<State>,
+}
+
+impl RateLimiter {
+    pub fn new(limit: RateLimit) -> Result<Self> {
+        let valid = match limit {
+            RateLimit::TokenBucket { capacity, refill, per } => capacity > 0 && refill > 0 && !per.is_zero(),
+            RateLimit::SlidingWindow { limit, window } => limit > 0 && !window.is_zero(),
+        };
+        if !valid {
+            return Err(AlphaForgeError::config(format!("Invalid rate limit: {:?}", limit)));
+        }
+        let state = match limit {
+            RateLimit::TokenBucket { capacity, .. } => State::Bucket {
+                tokens: capacity as f64,
+                updated: Instant::now(),
+            },
+            RateLimit::SlidingWindow { .. } => State::Window {
+                grants: VecDeque::new(),
+                granted: 0,
+            },
+        };
+        Ok(Self {
+            limit,
+            state: Mutex::new(state),
+        })
+    }
+
+    pub fn limit(&self) -> RateLimit {
+        self.limit
+    }
+
+    /// Take `permits` if available now, without waiting
+    pub fn try_acquire(&self, permits: u32) -> bool {
+        self.reserve(permits, Instant::now()).is_ok()
+    }
+
+    /// Take `permits`, blocking the thread until they are available
+    pub fn acquire_blocking(&self, permits: u32) -> Result<()> {
+        self.check_permits(permits)?;
+        while let Err(wait) = self.reserve(permits, Instant::now()) {
+            std::thread::sleep(wait);
+        }
+        Ok(())
+    }
+
+    /// Take `permits`, waiting until they are available
+    pub async fn acquire(&self, permits: u32) -> Result<()> {
+        self.check_permits(permits)?;
+        while let Err(wait) = self.reserve(permits, Instant::now()) {
+            tokio::time::sleep(wait).await;
+        }
+        Ok(())
+    }
+
+    fn check_permits(&self, permits: u32) -> Result<()> {
+        if permits > self.limit.max_permits() {
+            return Err(AlphaForgeError::validation(format!(
+                "{} permits exceed the limit of {}",
+                permits,
+                self.limit.max_permits()
+            )));
+        }
+        Ok(())
+    }
+
+    // Take `permits` at `now`, or say how long until they may be available
+    fn reserve(&self, permits: u32, now: Instant) -> std::result::Result<(), Duration> {
+        if permits > self.limit.max_permits() {
+            return Err(Duration::MAX);
+        }
+        let mut state = self.state.lock();
+        match (&mut *state, self.limit) {
+            (State::Bucket { tokens, updated }, RateLimit::TokenBucket { capacity, refill, per }) => {
+                let rate = refill as f64 / per.as_secs_f64();
+                *tokens = (*tokens + now.saturating_duration_since(*updated).as_secs_f64() * rate).min(capacity as f64);
+                *updated = now.max(*updated);
+                if *tokens >= permits as f64 {
+                    *tokens -= permits as f64;
+                    Ok(())
+                } else {
+                    Err(Duration::from_secs_f64((permits as f64 - *tokens) / rate))
+                }
+            }
+            (State::Window { grants, granted }, RateLimit::SlidingWindow { limit, window }) => {
+                while let Some(&(at, count)) = grants.front() {
+                    if now.saturating_duration_since(at) < window {
+                        break;
+                    }
+                    grants.pop_front();
+                    *granted -= count;
+                }
+                if *granted + permits <= limit {
+                    grants.push_back((now, permits));
+                    *granted += permits;
+                    return Ok(());
+                }
+                // Until enough of the oldest grants leave the window
+                let mut freed = 0;
+                for &(at, count) in grants.iter() {
+                    freed += count;
+                    if *granted - freed + permits <= limit {
+                        return Err((at + window).saturating_duration_since(now));
+                    }
+                }
+                Err(window)
+            }
+            _ => unreachable!("state matches the limit it was created for"),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::Arc;
+
+    #[tokio::test]
+    async fn test_token_bucket_and_sliding_window() {
+        let start = Instant::now();
+        let at = |ms: u64| start + Duration::from_millis(ms);
+
+        // Bursts of 2, then one permit every 100ms
+        let bucket = RateLimiter::new(RateLimit::TokenBucket { capacity: 2, refill: 1, per: Duration::from_millis(100) }).unwrap();
+        *bucket.state.lock() = State::Bucket { tokens: 2.0, updated: start };
+        assert!(bucket.reserve(2, at(0)).is_ok());
+        let wait = bucket.reserve(1, at(40)).unwrap_err();
+        assert!((wait.as_secs_f64() - 0.06).abs() < 1e-9);
+        assert!(bucket.reserve(1, at(100)).is_ok());
+        assert_eq!(bucket.reserve(3, at(1_000)), Err(Duration::MAX));
+
+        // 3 permits in any 1s
+        let window = RateLimiter::new(RateLimit::SlidingWindow { limit: 3, window: Duration::from_secs(1) }).unwrap();
+        assert!(window.reserve(2, at(0)).is_ok());
+        assert!(window.reserve(1, at(500)).is_ok());
+        assert_eq!(window.reserve(2, at(600)), Err(Duration::from_millis(400)));
+        assert!(window.reserve(2, at(1_000)).is_ok());
+        assert_eq!(window.reserve(1, at(1_200)), Err(Duration::from_millis(300)));
+
+        assert!(RateLimiter::new(RateLimit::per_second(0)).is_err());
+        let limiter = Arc::new(RateLimiter::new(RateLimit::SlidingWindow { limit: 2, window: Duration::from_millis(50) }).unwrap());
+        assert!(limiter.acquire(3).await.is_err());
+        let started = Instant::now();
+        for _ in 0..3 {
+            limiter.acquire(1).await.unwrap();
+        }
+        assert!(started.elapsed() >= Duration::from_millis(50));
+        assert!(!limiter.try_acquire(2));
+        let blocking = Arc::clone(&limiter);
+        tokio::task::spawn_blocking(move || blocking.acquire_blocking(2)).await.unwrap().unwrap();
+    }
+}
@@ -0,0 +1,277 @@
+//! AlphaForge Redis Bridge
+//!
+//! Mirrors message bus topics to Redis streams and publishes entries of
+//! Redis streams on the bus, so dashboards and other processes can take
+//! part in the bus without linking the Rust crates. Topic `t` maps to the
+//! stream `{stream_prefix}t`, whose entries carry the envelope's `id`,
+//! `sender`, `timestamp` (Unix nanoseconds), `payload` (the bytes published
+//! on the bus) and `codec`. Only `payload` is required of entries written
+//! by other processes; without a `codec` it is taken to be in the codec the
+//! bus uses for the topic (see `MessageBus::set_codec`), so configuring
+//! JSON for a topic lets any language read and write it.
+
+use std::sync::Arc;
+
+use redis::aio::MultiplexedConnection;
+use redis::streams::{StreamId, StreamMaxlen, StreamRangeReply, StreamReadOptions, StreamReadReply};
+use redis::AsyncCommands;
+use serde::{Deserialize, Serialize};
+use tokio::task::JoinHandle;
+
+use crate::error::{AlphaForgeError, Result};
+use crate::message::{Codec, MessageEnvelope, TopicPattern};
+use crate::message_bus::{MessageBus, Subscription};
+use crate::uuid::UUID7;
+
+/// How long a read of the consumed streams blocks before polling again
+const READ_BLOCK_MS: usize = 1_000;
+
+fn default_stream_prefix() -> String {
+    "alphaforge:".to_string()
+}
+
+/// Topics exchanged with a Redis server
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct RedisBridgeConfig {
+    /// Server URL, e.g. `redis://127.0.0.1:6379`
+    pub url: String,
+    #[serde(default = "default_stream_prefix")]
+    pub stream_prefix: String,
+    /// Topics, or topic patterns, appended to Redis when published on the bus
+    #[serde(default)]
+    pub mirror: Vec<String>,
+    /// Topics whose Redis streams are published on the bus
+    #[serde(default)]
+    pub consume: Vec<String>,
+    /// Approximate cap on the length of each mirrored stream
+    #[serde(default)]
+    pub max_stream_len: Option<usize>,
+}
+
+impl RedisBridgeConfig {
+    pub fn new(url: impl Into<String>) -> Self {
+        Self {
+            url: url.into(),
+            stream_prefix: default_stream_prefix(),
+            mirror: Vec::new(),
+            consume: Vec::new(),
+            max_stream_len: None,
+        }
+    }
+
+    /// Consumed topics must be concrete, and must not be mirrored as well or
+    /// every consumed entry would be appended back to its stream
+    pub fn validate(&self) -> Result<()> {
+        for topic in &self.consume {
+            if TopicPattern::is_pattern(topic) {
+                return Err(AlphaForgeError::config(format!("Cannot consume topic pattern {} from Redis", topic)));
+            }
+            if let Some(pattern) = self.mirror.iter().find(|pattern| TopicPattern::new(pattern.as_str()).matches(topic)) {
+                return Err(AlphaForgeError::config(format!(
+                    "Topic {} is both consumed from Redis and mirrored to it by {}",
+                    topic, pattern
+                )));
+            }
+        }
+        Ok(())
+    }
+
+    /// Redis stream of a topic
+    pub fn stream_key(&self, topic: &str) -> String {
+        format!("{}{}", self.stream_prefix, topic)
+    }
+}
+
+/// Fields of the stream entry mirroring `envelope`
+pub fn envelope_fields(envelope: &MessageEnvelope) -> [(&'static str, Vec<u8>); 5] {
+    [
+        ("id", envelope.id.to_string().into_bytes()),
+        ("sender", envelope.sender.clone().into_bytes()),
+        ("timestamp", envelope.timestamp.to_string().into_bytes()),
+        ("payload", envelope.payload.clone()),
+        ("codec", envelope.codec.as_str().as_bytes().to_vec()),
+    ]
+}
+
+/// Envelope for a stream entry of `topic`; an entry without an id, sender,
+/// timestamp or codec gets a fresh id, sender `redis`, the current time and
+/// `default_codec`
+pub fn envelope_from_entry(topic: &str, entry: &StreamId, default_codec: Codec) -> Result<MessageEnvelope> {
+    let payload: Vec<u8> = entry
+        .get("payload")
+        .ok_or_else(|| AlphaForgeError::validation(format!("Redis entry {} on {} has no payload", entry.id, topic)))?;
+    let mut envelope = MessageEnvelope::new(
+        entry.get("sender").unwrap_or_else(|| "redis".to_string()),
+        topic.to_string(),
+        payload,
+    );
+    if let Some(id) = entry.get::<String>("id").and_then(|id| UUID7::parse(&id).ok()) {
+        envelope.id = id;
+    }
+    if let Some(timestamp) = entry.get::<String>("timestamp").and_then(|ts| ts.parse().ok()) {
+        envelope.timestamp = timestamp;
+    }
+    envelope.codec = match entry.get::<String>("codec") {
+        Some(codec) => codec.parse()?,
+        None => default_codec,
+    };
+    Ok(envelope)
+}
+
+/// Running exchange between a message bus and a Redis server
+pub struct RedisBridge {
+    tasks: Vec<JoinHandle<()>>,
+}
+
+impl RedisBridge {
+    /// Connect to the server and start mirroring and consuming topics
+    pub async fn start(config: RedisBridgeConfig, message_bus: Arc<MessageBus>) -> Result<Self> {
+        config.validate()?;
+        let client = redis::Client::open(config.url.as_str())
+            .map_err(|e| AlphaForgeError::config(format!("Invalid Redis URL {}: {}", config.url, e)))?;
+        let connection = client
+            .get_multiplexed_async_connection()
+            .await
+            .map_err(|e| AlphaForgeError::network(format!("Failed to connect to Redis at {}: {}", config.url, e)))?;
+
+        let config = Arc::new(config);
+        let mut tasks = Vec::new();
+        for topic in &config.mirror {
+            let receiver = message_bus.subscribe_tracked(topic);
+            tasks.push(tokio::spawn(Self::mirror(Arc::clone(&config), connection.clone(), receiver)));
+        }
+        if !config.consume.is_empty() {
+            // Blocking reads get their own connection so they never delay mirroring
+            let reader = client
+                .get_multiplexed_async_connection()
+                .await
+                .map_err(|e| AlphaForgeError::network(format!("Failed to connect to Redis at {}: {}", config.url, e)))?;
+            tasks.push(tokio::spawn(Self::consume(Arc::clone(&config), reader, message_bus)));
+        }
+        tracing::info!(
+            "Redis bridge to {} mirroring {} and consuming {} topics",
+            config.url,
+            config.mirror.len(),
+            config.consume.len()
+        );
+        Ok(Self { tasks })
+    }
+
+    /// Stop mirroring and consuming
+    pub fn stop(self) {
+        for task in self.tasks {
+            task.abort();
+        }
+    }
+
+    async fn mirror(
+        config: Arc<RedisBridgeConfig>,
+        mut connection: MultiplexedConnection,
+        mut receiver: Subscription,
+    ) {
+        while let Some(envelope) = receiver.recv().await {
+            let key = config.stream_key(&envelope.message_type);
+            let fields = envelope_fields(&envelope);
+            let result: redis::RedisResult<String> = match config.max_stream_len {
+                Some(len) => connection.xadd_maxlen(&key, StreamMaxlen::Approx(len), "*", &fields).await,
+                None => connection.xadd(&key, "*", &fields).await,
+            };
+            if let Err(e) = result {
+                tracing::warn!("Failed to mirror {} to Redis: {}", envelope.message_type, e);
+            }
+        }
+    }
+
+    async fn consume(config: Arc<RedisBridgeConfig>, mut connection: MultiplexedConnection, message_bus: Arc<MessageBus>) {
+        let keys: Vec<String> = config.consume.iter().map(|topic| config.stream_key(topic)).collect();
+        // Only entries added after the bridge started
+        let mut last_ids = loop {
+            match Self::last_ids(&mut connection, &keys).await {
+                Ok(last_ids) => break last_ids,
+                Err(e) => {
+                    tracing::warn!("Failed to read Redis stream positions: {}", e);
+                    tokio::time::sleep(std::time::Duration::from_millis(READ_BLOCK_MS as u64)).await;
+                }
+            }
+        };
+        let options = StreamReadOptions::default().block(READ_BLOCK_MS);
+        loop {
+            let reply: StreamReadReply = match connection.xread_options(&keys, &last_ids, &options).await {
+                Ok(reply) => reply,
+                Err(e) => {
+                    tracing::warn!("Failed to read Redis streams: {}", e);
+                    tokio::time::sleep(std::time::Duration::from_millis(READ_BLOCK_MS as u64)).await;
+                    continue;
+                }
+            };
+            for stream in reply.keys {
+                let Some(index) = keys.iter().position(|key| *key == stream.key) else {
+                    continue;
+                };
+                let topic = &config.consume[index];
+                for entry in &stream.ids {
+                    match envelope_from_entry(topic, entry, message_bus.codec_for(topic)) {
+                        Ok(envelope) => message_bus.publish_envelope(&envelope),
+                        Err(e) => tracing::warn!("Dropping Redis entry: {}", e),
+                    }
+                    last_ids[index] = entry.id.clone();
+                }
+            }
+        }
+    }
+
+    // Id of the last entry of each stream, or 0-0 for a stream not created
+    // yet. Reading from `$` instead would skip entries added to one stream
+    // while a read returned those of another.
+    async fn last_ids(connection: &mut MultiplexedConnection, keys: &[String]) -> redis::RedisResult<Vec<String>> {
+        let mut last_ids = Vec::with_capacity(keys.len());
+        for key in keys {
+            let reply: StreamRangeReply = connection.xrevrange_count(key, "+", "-", 1).await?;
+            last_ids.push(reply.ids.first().map_or_else(|| "0-0".to_string(), |entry| entry.id.clone()));
+        }
+        Ok(last_ids)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::collections::HashMap;
+
+    #[test]
+    fn test_entries_round_trip_and_config_validation() {
+        let mut config = RedisBridgeConfig::new("redis://127.0.0.1:6379");
+        config.mirror = vec!["orders.*".to_string()];
+        config.consume = vec!["dashboard.commands".to_string()];
+        config.validate().unwrap();
+        assert_eq!(config.stream_key("orders.filled"), "alphaforge:orders.filled");
+
+        let envelope = MessageEnvelope::new("execution_engine".to_string(), "orders.filled".to_string(), vec![1, 2, 3]);
+        let entry = StreamId {
+            id: "1-0".to_string(),
+            map: envelope_fields(&envelope)
+                .into_iter()
+                .map(|(field, value)| (field.to_string(), redis::Value::BulkString(value)))
+                .collect(),
+        };
+        let decoded = envelope_from_entry("orders.filled", &entry, Codec::Json).unwrap();
+        assert_eq!(decoded.codec, Codec::Bincode);
+        assert_eq!((decoded.id, decoded.timestamp), (envelope.id, envelope.timestamp));
+        assert_eq!((decoded.sender.as_str(), decoded.payload.as_slice()), ("execution_engine", &[1u8, 2, 3][..]));
+
+        // Other processes need only send a payload
+        let external = StreamId {
+            id: "2-0".to_string(),
+            map: HashMap::from([("payload".to_string(), redis::Value::BulkString(vec![9]))]),
+        };
+        let external = envelope_from_entry("dashboard.commands", &external, Codec::Json).unwrap();
+        assert_eq!((external.sender.as_str(), external.codec), ("redis", Codec::Json));
+        assert!(envelope_from_entry("dashboard.commands", &StreamId::default(), Codec::Json).is_err());
+
+        // Consuming a mirrored topic would echo it back forever
+        config.consume.push("orders.filled".to_string());
+        assert!(config.validate().is_err());
+        config.consume = vec!["orders.*".to_string()];
+        assert!(config.validate().is_err());
+    }
+}
@@ -0,0 +1,366 @@
+//! AlphaForge Timer Schedules
+//!
+//! Fire times for clock timers that do not repeat at a fixed interval from
+//! when they were set: round boundaries such as every minute at :00, cron
+//! expressions evaluated in exchange time, and session opens and closes of
+//! a trading calendar. Set them with `Clock::set_scheduled_timer` or
+//! `StrategyContext::set_scheduled_timer`.
+
+use chrono::{DateTime, Datelike, Duration, NaiveDateTime, TimeZone, Timelike};
+use chrono_tz::Tz;
+
+use crate::calendar::TradingCalendar;
+use crate::clock::TimerSchedule;
+use crate::error::{AlphaForgeError, Result};
+use crate::time::UnixNanos;
+
+/// Years searched for the next match, enough for any valid day and month
+const CRON_SEARCH_DAYS: usize = 366 * 8;
+
+const NANOS_PER_DAY: i64 = 86_400_000_000_000;
+
+/// Fires on round multiples of an interval in local time, counted from
+/// midnight in a time zone, e.g. every minute at :00 so time bars close on
+/// the minute however late the engine started
+#[derive(Debug, Clone, PartialEq)]
+pub struct AlignedSchedule {
+    interval_ns: i64,
+    timezone: Tz,
+}
+
+impl AlignedSchedule {
+    /// `interval` must divide a day, so every day has the same boundaries
+    pub fn new(interval: Duration, timezone: Tz) -> Result<Self> {
+        let interval_ns = interval
+            .num_nanoseconds()
+            .filter(|interval_ns| *interval_ns > 0 && NANOS_PER_DAY % interval_ns == 0)
+            .ok_or_else(|| AlphaForgeError::config(format!("Aligned timer interval {} must divide a day", interval)))?;
+        Ok(Self { interval_ns, timezone })
+    }
+
+    /// Every `interval` aligned to UTC midnight
+    pub fn utc(interval: Duration) -> Result<Self> {
+        Self::new(interval, Tz::UTC)
+    }
+}
+
+impl TimerSchedule for AlignedSchedule {
+    fn next_after(&self, ts: UnixNanos) -> Option<UnixNanos> {
+        let local = DateTime::from_timestamp_nanos(ts as i64).with_timezone(&self.timezone).naive_local();
+        let midnight = local.date().and_hms_opt(0, 0, 0)?;
+        // Start from the boundary at or before `ts`, which a repeated hour
+        // after a daylight saving change can still map after it
+        let mut at = midnight + Duration::nanoseconds((local - midnight).num_nanoseconds()? / self.interval_ns * self.interval_ns);
+        let end = midnight + Duration::days(2);
+        while at <= end {
+            // Boundaries skipped by a daylight saving change never fire
+            if let Some(next) = instants(&self.timezone, at).find(|next| *next > ts) {
+                return Some(next);
+            }
+            at += Duration::nanoseconds(self.interval_ns);
+        }
+        None
+    }
+}
+
+/// A five-field cron expression, `minute hour day-of-month month
+/// day-of-week`, in a time zone. Fields take `*`, values, ranges `a-b`,
+/// lists `a,b` and steps `*/n` or `a-b/n`; Sunday is 0 or 7. As in cron, a
+/// day matches either day field when both are restricted. Times skipped by
+/// a daylight saving change never fire, and repeated ones fire once, at
+/// their first occurrence.
+#[derive(Debug, Clone, PartialEq)]
+pub struct CronSchedule {
+    expression: String,
+    timezone: Tz,
+    minutes: u64,
+    hours: u64,
+    days_of_month: u64,
+    months: u64,
+    days_of_week: u64,
+    any_day_of_month: bool,
+    any_day_of_week: bool,
+}
+
+impl CronSchedule {
+    /// Parse `expression`, e.g. `55 15 * * 1-5` for 15:55 on weekdays
+    pub fn parse(expression: &str, timezone: Tz) -> Result<Self> {
+        let fields: Vec<&str> = expression.split_whitespace().collect();
+        let [minute, hour, day_of_month, month, day_of_week] = fields[..] else {
+            return Err(AlphaForgeError::config(format!(
+                "Cron expression {:?} needs 5 fields, found {}",
+                expression,
+                fields.len()
+            )));
+        };
+        let invalid = |e: String| AlphaForgeError::config(format!("Invalid cron expression {:?}: {}", expression, e));
+        let mut days_of_week = parse_field(day_of_week, 0, 7).map_err(invalid)?;
+        // Sunday is both 0 and 7
+        if days_of_week & (1 << 7) != 0 {
+            days_of_week = (days_of_week | 1) & !(1 << 7);
+        }
+        Ok(Self {
+            expression: expression.to_string(),
+            timezone,
+            minutes: parse_field(minute, 0, 59).map_err(invalid)?,
+            hours: parse_field(hour, 0, 23).map_err(invalid)?,
+            days_of_month: parse_field(day_of_month, 1, 31).map_err(invalid)?,
+            months: parse_field(month, 1, 12).map_err(invalid)?,
+            days_of_week,
+            any_day_of_month: day_of_month.starts_with('*'),
+            any_day_of_week: day_of_week.starts_with('*'),
+        })
+    }
+
+    pub fn expression(&self) -> &str {
+        &self.expression
+    }
+
+    fn matches_day(&self, date: chrono::NaiveDate) -> bool {
+        if !has(self.months, date.month()) {
+            return false;
+        }
+        let day_of_month = has(self.days_of_month, date.day());
+        let day_of_week = has(self.days_of_week, date.weekday().num_days_from_sunday());
+        match (self.any_day_of_month, self.any_day_of_week) {
+            (true, true) => true,
+            (true, false) => day_of_week,
+            (false, true) => day_of_month,
+            (false, false) => day_of_month || day_of_week,
+        }
+    }
+}
+
+impl TimerSchedule for CronSchedule {
+    fn next_after(&self, ts: UnixNanos) -> Option<UnixNanos> {
+        let local = DateTime::from_timestamp_nanos(ts as i64).with_timezone(&self.timezone).naive_local();
+        let start = local.with_second(0)?.with_nanosecond(0)?;
+        for date in start.date().iter_days().take(CRON_SEARCH_DAYS) {
+            if !self.matches_day(date) {
+                continue;
+            }
+            for hour in (0..24).filter(|hour| has(self.hours, *hour)) {
+                for minute in (0..60).filter(|minute| has(self.minutes, *minute)) {
+                    let at = date.and_hms_opt(hour, minute, 0)?;
+                    if at < start {
+                        continue;
+                    }
+                    // Once passed, the first occurrence of a repeated time
+                    // is not followed by the second
+                    if let Some(at) = instants(&self.timezone, at).next().filter(|at| *at > ts) {
+                        return Some(at);
+                    }
+                }
+            }
+        }
+        None
+    }
+}
+
+fn has(set: u64, value: u32) -> bool {
+    set & (1 << value) != 0
+}
+
+// Values of one cron field as a bit set
+fn parse_field(field: &str, min: u32, max: u32) -> std::result::Result<u64, String> {
+    let mut set = 0u64;
+    for part in field.split(',') {
+        let (range, step) = match part.split_once('/') {
+            Some((range, step)) => {
+                let step: u32 = step.parse().map_err(|_| format!("bad step in {:?}", part))?;
+                if step == 0 {
+                    return Err(format!("zero step in {:?}", part));
+                }
+                (range, step)
+            }
+            None => (part, 1),
+        };
+        let value = |value: &str| -> std::result::Result<u32, String> {
+            let value: u32 = value.parse().map_err(|_| format!("bad value in {:?}", part))?;
+            if value < min || value > max {
+                return Err(format!("{} is outside {}-{}", value, min, max));
+            }
+            Ok(value)
+        };
+        let (first, last) = match range {
+            "*" => (min, max),
+            _ => match range.split_once('-') {
+                Some((first, last)) => (value(first)?, value(last)?),
+                // A stepped single value runs to the end, as in cron
+                None if step > 1 => (value(range)?, max),
+                None => (value(range)?, value(range)?),
+            },
+        };
+        if first > last {
+            return Err(format!("empty range {:?}", part));
+        }
+        for value in (first..=last).step_by(step as usize) {
+            set |= 1 << value;
+        }
+    }
+    Ok(set)
+}
+
+// Instants a local time maps to, earliest first
+fn instants(timezone: &Tz, local: NaiveDateTime) -> impl Iterator<Item = UnixNanos> {
+    let result = timezone.from_local_datetime(&local);
+    [result.earliest(), result.latest()]
+        .into_iter()
+        .flatten()
+        .filter_map(|at| at.timestamp_nanos_opt())
+        .map(|at| at as UnixNanos)
+}
+
+/// Which session boundary a `SessionSchedule` fires at
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SessionEvent {
+    Open,
+    Close,
+}
+
+/// Fires at every session open or close of a trading calendar, shifted by
+/// an offset, e.g. five minutes before each close
+#[derive(Debug, Clone, PartialEq)]
+pub struct SessionSchedule {
+    pub calendar: TradingCalendar,
+    pub event: SessionEvent,
+    /// Nanoseconds after the boundary to fire at, negative for before
+    pub offset_ns: i64,
+}
+
+impl SessionSchedule {
+    pub fn new(calendar: TradingCalendar, event: SessionEvent) -> Self {
+        Self {
+            calendar,
+            event,
+            offset_ns: 0,
+        }
+    }
+
+    /// Fails for offsets beyond the nanosecond range, about 292 years
+    pub fn with_offset(mut self, offset: Duration) -> Result<Self> {
+        self.offset_ns = offset
+            .num_nanoseconds()
+            .ok_or_else(|| AlphaForgeError::config(format!("Session timer offset {} is out of range", offset)))?;
+        Ok(self)
+    }
+}
+
+impl TimerSchedule for SessionSchedule {
+    fn next_after(&self, ts: UnixNanos) -> Option<UnixNanos> {
+        // The next boundary whose shifted time is after `ts`
+        let after = (ts as i128 - self.offset_ns as i128).max(0) as UnixNanos;
+        let boundary = match self.event {
+            SessionEvent::Open => self.calendar.next_open(after)?,
+            SessionEvent::Close => self.calendar.next_close(after)?,
+        };
+        u64::try_from(boundary as i128 + self.offset_ns as i128).ok()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::calendar::SessionWindow;
+    use crate::clock::{Clock, TestClock, TimeEventSender};
+    use std::sync::Arc;
+    use chrono::{NaiveDate, NaiveTime};
+
+    fn at(timezone: Tz, date: (i32, u32, u32), time: (u32, u32)) -> UnixNanos {
+        timezone
+            .with_ymd_and_hms(date.0, date.1, date.2, time.0, time.1, 0)
+            .unwrap()
+            .timestamp_nanos_opt()
+            .unwrap() as UnixNanos
+    }
+
+    #[test]
+    fn test_cron_schedule() {
+        let new_york = chrono_tz::America::New_York;
+        // Weekdays at 15:55 exchange time; Friday 5 July 2024 to Monday
+        let cron = CronSchedule::parse("55 15 * * 1-5", new_york).unwrap();
+        assert_eq!(cron.next_after(at(new_york, (2024, 7, 5), (12, 0))), Some(at(new_york, (2024, 7, 5), (15, 55))));
+        assert_eq!(cron.next_after(at(new_york, (2024, 7, 5), (15, 55))), Some(at(new_york, (2024, 7, 8), (15, 55))));
+        let quarter_hours = CronSchedule::parse("*/15 9-10 1,15 * *", new_york).unwrap();
+        assert_eq!(quarter_hours.next_after(at(new_york, (2024, 7, 1), (10, 50))), Some(at(new_york, (2024, 7, 15), (9, 0))));
+        // Either day field matches when both are restricted; Sunday is 7
+        let either = CronSchedule::parse("0 0 13 * 7", new_york).unwrap();
+        assert_eq!(either.next_after(at(new_york, (2024, 7, 8), (0, 0))), Some(at(new_york, (2024, 7, 13), (0, 0))));
+        assert_eq!(either.next_after(at(new_york, (2024, 7, 13), (0, 0))), Some(at(new_york, (2024, 7, 14), (0, 0))));
+        assert!(CronSchedule::parse("60 * * * *", new_york).is_err());
+        assert!(CronSchedule::parse("* * * *", new_york).is_err());
+        assert!(CronSchedule::parse("0 0 31 2 *", new_york).unwrap().next_after(0).is_none());
+    }
+
+    #[test]
+    fn test_cron_schedule_across_daylight_saving_changes() {
+        let new_york = chrono_tz::America::New_York;
+        // 02:30 does not exist on 10 March 2024
+        let night = CronSchedule::parse("30 2 * * *", new_york).unwrap();
+        assert_eq!(night.next_after(at(new_york, (2024, 3, 9), (12, 0))), Some(at(new_york, (2024, 3, 11), (2, 30))));
+
+        // 01:30 happens twice on 3 November 2024 and fires at the first
+        let repeated = CronSchedule::parse("30 1 * * *", new_york).unwrap();
+        let first = repeated.next_after(at(new_york, (2024, 11, 3), (0, 0))).unwrap();
+        assert_eq!(first, at(new_york, (2024, 11, 3), (0, 0)) + 5_400_000_000_000);
+        assert_eq!(repeated.next_after(first), Some(at(new_york, (2024, 11, 4), (1, 30))));
+        let second_hour = first + 2_700_000_000_000; // 01:15 standard time
+        assert_eq!(repeated.next_after(second_hour), Some(at(new_york, (2024, 11, 4), (1, 30))));
+    }
+
+    #[test]
+    fn test_session_schedule() {
+        let new_york = chrono_tz::America::New_York;
+        let hours = |h, m| NaiveTime::from_hms_opt(h, m, 0).unwrap();
+        let calendar = TradingCalendar::new(new_york, vec![SessionWindow::weekdays(hours(9, 30), hours(16, 0))])
+            .with_holidays(vec![NaiveDate::from_ymd_opt(2024, 7, 4).unwrap()]);
+        let open = SessionSchedule::new(calendar.clone(), SessionEvent::Open);
+        assert_eq!(open.next_after(at(new_york, (2024, 7, 3), (9, 30))), Some(at(new_york, (2024, 7, 5), (9, 30))));
+        let before_close = SessionSchedule::new(calendar, SessionEvent::Close).with_offset(Duration::minutes(-5)).unwrap();
+        assert_eq!(before_close.next_after(at(new_york, (2024, 7, 3), (15, 54))), Some(at(new_york, (2024, 7, 3), (15, 55))));
+        assert_eq!(before_close.next_after(at(new_york, (2024, 7, 3), (15, 55))), Some(at(new_york, (2024, 7, 5), (15, 55))));
+
+        // Wednesday, Friday and Monday, skipping the holiday and weekend
+        let clock = TestClock::new(at(new_york, (2024, 7, 3), (0, 0)));
+        let (sender, _events) = tokio::sync::mpsc::unbounded_channel();
+        clock.set_scheduled_timer("close".to_string(), Arc::new(before_close), None, TimeEventSender::Channel(sender)).unwrap();
+        assert_eq!(clock.advance_to(at(new_york, (2024, 7, 8), (23, 0))), 3);
+        assert_eq!(clock.next_timer_ns(), Some(at(new_york, (2024, 7, 9), (15, 55))));
+
+        // Overnight sessions close the day after they open
+        let chicago = chrono_tz::America::Chicago;
+        let futures = TradingCalendar::new(chicago, vec![SessionWindow::new(vec![chrono::Weekday::Sun], hours(17, 0), hours(16, 0))]);
+        let close = SessionSchedule::new(futures, SessionEvent::Close);
+        assert_eq!(close.next_after(at(chicago, (2024, 7, 8), (3, 0))), Some(at(chicago, (2024, 7, 8), (16, 0))));
+        assert!(close.with_offset(Duration::MAX).is_err());
+    }
+
+    #[test]
+    fn test_aligned_schedule() {
+        let new_york = chrono_tz::America::New_York;
+        let minute = AlignedSchedule::utc(Duration::minutes(1)).unwrap();
+        let start = at(new_york, (2024, 7, 3), (9, 30)) + 17_250_000_000;
+        assert_eq!(minute.next_after(start), Some(at(new_york, (2024, 7, 3), (9, 31))));
+        assert_eq!(minute.next_after(at(new_york, (2024, 7, 3), (9, 31))), Some(at(new_york, (2024, 7, 3), (9, 32))));
+
+        // Four hour boundaries in exchange time, across the spring change
+        let four_hours = AlignedSchedule::new(Duration::hours(4), new_york).unwrap();
+        assert_eq!(four_hours.next_after(at(new_york, (2024, 3, 9), (21, 0))), Some(at(new_york, (2024, 3, 10), (0, 0))));
+        assert_eq!(four_hours.next_after(at(new_york, (2024, 3, 10), (0, 0))), Some(at(new_york, (2024, 3, 10), (4, 0))));
+        // Hourly in the repeated hour of the autumn change fires at both 01:00s
+        let hourly = AlignedSchedule::new(Duration::hours(1), new_york).unwrap();
+        let first = hourly.next_after(at(new_york, (2024, 11, 3), (0, 30))).unwrap();
+        let second = hourly.next_after(first).unwrap();
+        assert_eq!(second - first, 3_600_000_000_000);
+        assert_eq!(hourly.next_after(second), Some(at(new_york, (2024, 11, 3), (2, 0))));
+        assert!(AlignedSchedule::utc(Duration::minutes(7)).is_err());
+        assert!(AlignedSchedule::utc(Duration::MAX).is_err());
+
+        let clock = TestClock::new(start);
+        let (sender, mut events) = tokio::sync::mpsc::unbounded_channel();
+        clock.set_scheduled_timer("bar".to_string(), Arc::new(minute), None, TimeEventSender::Channel(sender)).unwrap();
+        clock.advance_to(at(new_york, (2024, 7, 3), (9, 33)));
+        let fired: Vec<UnixNanos> = std::iter::from_fn(|| events.try_recv().ok()).map(|event| event.ts_event).collect();
+        assert_eq!(fired, vec![at(new_york, (2024, 7, 3), (9, 31)), at(new_york, (2024, 7, 3), (9, 32)), at(new_york, (2024, 7, 3), (9, 33))]);
+    }
+}
@@ -0,0 +1,276 @@
+//! AlphaForge Message Schemas
+//!
+//! Versions of the payloads published on each message type, so long-running
+//! deployments can evolve event payloads without breaking consumers. A
+//! version either stays readable by consumers of older versions (fields only
+//! added, with defaults) or comes with an upgrade from the previous version.
+//! Publishers stamp envelopes with the current version of their topic, and
+//! a consumer subscribing for a version is refused up front if it cannot
+//! read what is published, rather than failing on every message.
+
+use std::collections::{BTreeMap, HashMap};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{Arc, RwLock};
+
+use serde::de::DeserializeOwned;
+use serde::Serialize;
+
+use crate::error::{AlphaForgeError, Result};
+use crate::message::{MessageEnvelope, PatternIndex, TopicPattern};
+
+/// Turns a payload of the previous version into one of this version, in
+/// the envelope's codec
+type Upgrade = Arc<dyn Fn(&MessageEnvelope) -> Result<Vec<u8>> + Send + Sync>;
+
+#[derive(Clone)]
+struct SchemaVersion {
+    /// Oldest version whose consumers read this version's payloads unchanged
+    compatible_from: u32,
+    upgrade: Option<Upgrade>,
+}
+
+/// Every version registered for one message type or pattern
+#[derive(Clone, Default)]
+struct SchemaHistory {
+    versions: BTreeMap<u32, SchemaVersion>,
+}
+
+impl SchemaHistory {
+    fn current(&self) -> Option<u32> {
+        self.versions.keys().next_back().copied()
+    }
+
+    // The upgrades making a payload of `version` readable by a consumer of
+    // `reader`, or None when it cannot be
+    fn path(&self, version: u32, reader: u32) -> Option<Vec<Upgrade>> {
+        let (written, wanted) = (self.versions.get(&version)?, self.versions.get(&reader)?);
+        if version >= reader {
+            // Older consumers read newer payloads that stayed compatible
+            return (written.compatible_from <= reader).then(Vec::new);
+        }
+        let mut upgrades = Vec::new();
+        let mut current = version;
+        while current < reader && wanted.compatible_from > current {
+            let (&next, step) = self.versions.range(current + 1..).next()?;
+            match &step.upgrade {
+                Some(upgrade) => upgrades.push(Arc::clone(upgrade)),
+                None if step.compatible_from <= current => {}
+                None => return None,
+            }
+            current = next;
+        }
+        Some(upgrades)
+    }
+}
+
+#[derive(Default)]
+struct Schemas {
+    histories: HashMap<String, SchemaHistory>,
+    /// Patterns with schemas, with their length; the longest matching one applies
+    patterns: PatternIndex<(usize, String)>,
+}
+
+impl Schemas {
+    fn history(&self, topic: &str) -> Option<&SchemaHistory> {
+        if let Some(history) = self.histories.get(topic) {
+            return Some(history);
+        }
+        self.patterns
+            .matching(topic)
+            .max_by_key(|(len, _)| *len)
+            .and_then(|(_, pattern)| self.histories.get(pattern))
+    }
+}
+
+/// Schema versions of message types. Message types are topics or topic
+/// patterns; a topic's own schema applies, else the longest matching
+/// pattern's.
+#[derive(Default)]
+pub struct SchemaRegistry {
+    schemas: RwLock<Schemas>,
+    /// Whether any schema was registered, so unversioned buses skip the lock
+    registered: AtomicBool,
+}
+
+impl std::fmt::Debug for SchemaRegistry {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let schemas = self.schemas.read().unwrap();
+        let versions: HashMap<&String, Option<u32>> =
+            schemas.histories.iter().map(|(message_type, history)| (message_type, history.current())).collect();
+        f.debug_struct("SchemaRegistry").field("versions", &versions).finish()
+    }
+}
+
+impl SchemaRegistry {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Register `version` of `message_type`, whose payloads consumers of
+    /// versions from `compatible_from` on read unchanged. Versions are
+    /// registered in increasing order.
+    pub fn register(&self, message_type: &str, version: u32, compatible_from: u32) -> Result<()> {
+        if compatible_from > version {
+            return Err(AlphaForgeError::config(format!(
+                "Version {} of {} cannot be compatible from later version {}",
+                version, message_type, compatible_from
+            )));
+        }
+        self.add(message_type, version, SchemaVersion { compatible_from, upgrade: None })
+    }
+
+    /// Register `version` of `message_type` as a breaking change from the
+    /// previous version, whose payloads `upgrade` converts
+    pub fn register_upgrade<Old, New>(
+        &self,
+        message_type: &str,
+        version: u32,
+        upgrade: impl Fn(Old) -> New + Send + Sync + 'static,
+    ) -> Result<()>
+    where
+        Old: DeserializeOwned,
+        New: Serialize,
+    {
+        let upgrade: Upgrade = Arc::new(move |envelope: &MessageEnvelope| {
+            let old: Old = envelope.decode()?;
+            envelope.codec.encode(&upgrade(old))
+        });
+        self.add(message_type, version, SchemaVersion { compatible_from: version, upgrade: Some(upgrade) })
+    }
+
+    fn add(&self, message_type: &str, version: u32, schema: SchemaVersion) -> Result<()> {
+        let mut schemas = self.schemas.write().unwrap();
+        let is_new = !schemas.histories.contains_key(message_type);
+        let history = schemas.histories.entry(message_type.to_string()).or_default();
+        if let Some(current) = history.current().filter(|current| *current >= version) {
+            return Err(AlphaForgeError::config(format!(
+                "Version {} of {} is not after registered version {}",
+                version, message_type, current
+            )));
+        }
+        if schema.upgrade.is_some() && history.current().is_none() {
+            return Err(AlphaForgeError::config(format!(
+                "Version {} of {} has no earlier version to upgrade from",
+                version, message_type
+            )));
+        }
+        history.versions.insert(version, schema);
+        self.registered.store(true, Ordering::Release);
+        if is_new && TopicPattern::is_pattern(message_type) {
+            schemas
+                .patterns
+                .insert(TopicPattern::new(message_type), (message_type.len(), message_type.to_string()));
+        }
+        tracing::debug!("Registered version {} of {}", version, message_type);
+        Ok(())
+    }
+
+    /// Version of the payloads published on `topic`, if it has a schema
+    pub fn current_version(&self, topic: &str) -> Option<u32> {
+        if !self.registered.load(Ordering::Acquire) {
+            return None;
+        }
+        self.schemas.read().unwrap().history(topic).and_then(SchemaHistory::current)
+    }
+
+    /// Whether a consumer of `reader_version` can read what is published on
+    /// `topic`, which may be a pattern subscribed to
+    pub fn check_compatible(&self, topic: &str, reader_version: u32) -> Result<()> {
+        let schemas = self.schemas.read().unwrap();
+        let history = schemas
+            .history(topic)
+            .ok_or_else(|| AlphaForgeError::validation(format!("No schema registered for {}", topic)))?;
+        if !history.versions.contains_key(&reader_version) {
+            return Err(AlphaForgeError::validation(format!(
+                "Version {} of {} is not registered",
+                reader_version, topic
+            )));
+        }
+        let current = history.current().unwrap_or(reader_version);
+        if history.path(current, reader_version).is_none() {
+            return Err(AlphaForgeError::validation(format!(
+                "Consumers of version {} of {} cannot read version {} payloads",
+                reader_version, topic, current
+            )));
+        }
+        Ok(())
+    }
+
+    /// `envelope` as a consumer of `reader_version` reads it: unchanged when
+    /// unversioned or compatible, else upgraded
+    pub fn adapt(&self, envelope: &MessageEnvelope, reader_version: u32) -> Result<MessageEnvelope> {
+        let Some(version) = envelope.schema_version.filter(|version| *version != reader_version) else {
+            return Ok(envelope.clone());
+        };
+        let upgrades = {
+            let schemas = self.schemas.read().unwrap();
+            schemas.history(&envelope.message_type).and_then(|history| history.path(version, reader_version))
+        };
+        let upgrades = upgrades.ok_or_else(|| {
+            AlphaForgeError::validation(format!(
+                "Version {} of {} cannot be read as version {}",
+                version, envelope.message_type, reader_version
+            ))
+        })?;
+        let mut adapted = envelope.clone();
+        for upgrade in upgrades {
+            adapted.payload = upgrade(&adapted)?;
+        }
+        adapted.schema_version = Some(reader_version);
+        Ok(adapted)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde::Deserialize;
+
+    #[derive(Debug, PartialEq, Serialize, Deserialize)]
+    struct FillV1 {
+        price: f64,
+    }
+
+    #[derive(Debug, PartialEq, Serialize, Deserialize)]
+    struct FillV3 {
+        price: f64,
+        venue: String,
+    }
+
+    #[test]
+    fn test_versions_upgrade_and_compatibility() {
+        let registry = SchemaRegistry::new();
+        registry.register("orders.*", 1, 1).unwrap();
+        // Version 2 only added an optional field: version 1 consumers read it
+        registry.register("orders.*", 2, 1).unwrap();
+        registry.register_upgrade("orders.*", 3, |fill: FillV1| FillV3 { price: fill.price, venue: "unknown".to_string() }).unwrap();
+        assert!(registry.register("orders.*", 3, 3).is_err());
+        assert!(registry.register("risk.*", 2, 3).is_err());
+        assert!(registry.register_upgrade("risk.*", 1, |fill: FillV1| fill).is_err());
+        registry.register("orders.filled.BTC*", 1, 1).unwrap();
+
+        assert_eq!(registry.current_version("orders.filled.ETHUSDT"), Some(3));
+        assert_eq!(registry.current_version("orders.filled.BTCUSDT"), Some(1));
+        assert_eq!(registry.current_version("data.quotes.BTCUSDT"), None);
+        registry.check_compatible("orders.filled.ETHUSDT", 3).unwrap();
+        assert!(registry.check_compatible("orders.filled.ETHUSDT", 2).is_err());
+        assert!(registry.check_compatible("orders.filled.ETHUSDT", 4).is_err());
+        assert!(registry.check_compatible("data.quotes.BTCUSDT", 1).is_err());
+
+        // A journaled version 1 fill read by a version 3 consumer
+        let mut envelope = MessageEnvelope::new(
+            "execution_engine".to_string(),
+            "orders.filled.ETHUSDT".to_string(),
+            bincode::serialize(&FillV1 { price: 99.5 }).unwrap(),
+        );
+        envelope.schema_version = Some(1);
+        let upgraded = registry.adapt(&envelope, 3).unwrap();
+        assert_eq!(upgraded.schema_version, Some(3));
+        assert_eq!(upgraded.decode::<FillV3>().unwrap(), FillV3 { price: 99.5, venue: "unknown".to_string() });
+        assert_eq!(registry.adapt(&envelope, 2).unwrap().payload, envelope.payload);
+        envelope.schema_version = Some(3);
+        assert!(registry.adapt(&envelope, 1).is_err());
+        envelope.schema_version = None;
+        assert_eq!(registry.adapt(&envelope, 1).unwrap().payload, envelope.payload);
+    }
+}
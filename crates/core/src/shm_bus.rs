@@ -0,0 +1,470 @@
+//! AlphaForge Shared-Memory Bus
+//!
+//! Exports message bus topics into a ring buffer in a memory-mapped file
+//! (under `/dev/shm` on Linux), so processes on the same host, such as a
+//! Python analytics process, can consume market data without sockets. One
+//! process writes the ring; any number of readers follow it, each at its
+//! own pace. The writer never waits for readers: a reader that falls more
+//! than a ring behind skips ahead and counts what it lost.
+//!
+//! Layout, little-endian:
+//!
+//! | offset | size | field                                              |
+//! |--------|------|----------------------------------------------------|
+//! | 0      | 8    | magic, `AFSHMBUS`                                  |
+//! | 8      | 4    | layout version, 1                                  |
+//! | 16     | 8    | ring capacity in bytes, a multiple of 8            |
+//! | 24     | 8    | write position: bytes written since creation       |
+//! | 32     | 8    | reserved position: end of the record being written |
+//! | 64     | ...  | the ring                                           |
+//!
+//! A record starts at an 8-byte aligned ring offset with its length as a
+//! `u32`, followed by the envelope as named MessagePack (a map, so any
+//! MessagePack library can read it) and padding to the next multiple of 8.
+//! A length of `u32::MAX` marks the rest of the ring as unused; the next
+//! record is at offset 0. A reader copies a record at positions below the
+//! write position, then discards the copy if the reserved position has
+//! since moved more than a ring past its start.
+
+use std::fs::{File, OpenOptions};
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{fence, AtomicU64, Ordering};
+use std::sync::{Arc, Mutex};
+
+use memmap2::{MmapOptions, MmapRaw};
+use serde::{Deserialize, Serialize};
+
+use crate::error::{AlphaForgeError, Result};
+use crate::message::MessageEnvelope;
+use crate::message_bus::{CallbackId, CallbackMode, MessageBus};
+
+const MAGIC: &[u8; 8] = b"AFSHMBUS";
+const LAYOUT_VERSION: u32 = 1;
+const HEADER_LEN: usize = 64;
+const CAPACITY_OFFSET: usize = 16;
+const WRITE_POS_OFFSET: usize = 24;
+const RESERVE_POS_OFFSET: usize = 32;
+/// Length of a record marking the end of the ring as unused
+const WRAP_MARKER: u32 = u32::MAX;
+
+fn default_capacity() -> usize {
+    64 * 1024 * 1024
+}
+
+/// Topics exported to a shared-memory ring
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ShmBusConfig {
+    /// File backing the ring, e.g. `/dev/shm/alphaforge-market-data`
+    pub path: PathBuf,
+    /// Bytes of envelopes the ring holds before overwriting the oldest
+    #[serde(default = "default_capacity")]
+    pub capacity: usize,
+    /// Topics, or topic patterns, written to the ring when published on the bus
+    #[serde(default)]
+    pub topics: Vec<String>,
+}
+
+impl ShmBusConfig {
+    pub fn new(path: impl Into<PathBuf>) -> Self {
+        Self {
+            path: path.into(),
+            capacity: default_capacity(),
+            topics: Vec::new(),
+        }
+    }
+}
+
+fn align(len: usize) -> usize {
+    (len + 7) & !7
+}
+
+// The atomic header field at `offset` of a mapping
+fn header_atomic(map: &MmapRaw, offset: usize) -> &AtomicU64 {
+    // SAFETY: the mapping is page aligned and at least `HEADER_LEN` long,
+    // so an 8-aligned offset in the header is a valid `u64` for as long as
+    // the mapping lives; other processes only touch it atomically
+    unsafe { &*(map.as_ptr().add(offset) as *const AtomicU64) }
+}
+
+/// Writing end of a shared-memory ring
+pub struct ShmWriter {
+    path: PathBuf,
+    map: MmapRaw,
+    capacity: u64,
+    position: u64,
+}
+
+impl ShmWriter {
+    /// Create a ring of `capacity` bytes at `path`, replacing any file there.
+    /// Readers of a replaced ring see nothing further and must reopen.
+    pub fn create(path: impl Into<PathBuf>, capacity: usize) -> Result<Self> {
+        let path = path.into();
+        if capacity < 64 || !capacity.is_multiple_of(8) {
+            return Err(AlphaForgeError::config(format!(
+                "Shared-memory ring capacity must be a multiple of 8 of at least 64 bytes, not {}",
+                capacity
+            )));
+        }
+        if let Some(parent) = path.parent().filter(|parent| !parent.as_os_str().is_empty()) {
+            std::fs::create_dir_all(parent)?;
+        }
+        // Unlinked rather than truncated, so mapped readers never fault
+        if let Err(e) = std::fs::remove_file(&path) {
+            if e.kind() != std::io::ErrorKind::NotFound {
+                return Err(e.into());
+            }
+        }
+        let file = OpenOptions::new().read(true).write(true).create_new(true).open(&path)?;
+        file.set_len((HEADER_LEN + capacity) as u64)?;
+        let map = MmapOptions::new().map_raw(&file)?;
+
+        let mut header = [0u8; HEADER_LEN];
+        header[..8].copy_from_slice(MAGIC);
+        header[8..12].copy_from_slice(&LAYOUT_VERSION.to_le_bytes());
+        header[CAPACITY_OFFSET..CAPACITY_OFFSET + 8].copy_from_slice(&(capacity as u64).to_le_bytes());
+        // SAFETY: the mapping is `HEADER_LEN + capacity` bytes and nobody
+        // can have opened a ring whose magic is not written yet
+        unsafe { std::ptr::copy_nonoverlapping(header.as_ptr(), map.as_mut_ptr(), HEADER_LEN) };
+        map.flush()?;
+
+        Ok(Self {
+            path,
+            map,
+            capacity: capacity as u64,
+            position: 0,
+        })
+    }
+
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+
+    /// Bytes the ring holds
+    pub fn capacity(&self) -> usize {
+        self.capacity as usize
+    }
+
+    /// Bytes written since the ring was created
+    pub fn position(&self) -> u64 {
+        self.position
+    }
+
+    /// Append a record
+    pub fn write(&mut self, record: &[u8]) -> Result<()> {
+        let len = u32::try_from(record.len())
+            .ok()
+            .filter(|len| *len != WRAP_MARKER && align(4 + record.len()) as u64 <= self.capacity)
+            .ok_or_else(|| {
+                AlphaForgeError::validation(format!(
+                    "Record of {} bytes does not fit a {} byte shared-memory ring",
+                    record.len(),
+                    self.capacity
+                ))
+            })?;
+        let size = align(4 + record.len()) as u64;
+        let mut start = self.position;
+        let mut offset = start % self.capacity;
+        let wraps = offset + size > self.capacity;
+        if wraps {
+            start += self.capacity - offset;
+            offset = 0;
+        }
+        let end = start + size;
+
+        // Readers check the reserved position after copying, so it must be
+        // visible before any byte they might be copying changes
+        header_atomic(&self.map, RESERVE_POS_OFFSET).store(end, Ordering::Relaxed);
+        fence(Ordering::Release);
+        let ring = self.ring();
+        // SAFETY: offsets stay within the ring: records never straddle its
+        // end, and a wrap marker needs 4 of the at least 8 bytes left
+        unsafe {
+            if wraps {
+                let marker = self.position % self.capacity;
+                std::ptr::copy_nonoverlapping(WRAP_MARKER.to_le_bytes().as_ptr(), ring.add(marker as usize), 4);
+            }
+            let at = ring.add(offset as usize);
+            std::ptr::copy_nonoverlapping(len.to_le_bytes().as_ptr(), at, 4);
+            std::ptr::copy_nonoverlapping(record.as_ptr(), at.add(4), record.len());
+        }
+        header_atomic(&self.map, WRITE_POS_OFFSET).store(end, Ordering::Release);
+        self.position = end;
+        Ok(())
+    }
+
+    /// Append an envelope
+    pub fn write_envelope(&mut self, envelope: &MessageEnvelope) -> Result<()> {
+        self.write(&rmp_serde::to_vec_named(envelope)?)
+    }
+
+    fn ring(&self) -> *mut u8 {
+        // SAFETY: the mapping is `HEADER_LEN + capacity` bytes long
+        unsafe { self.map.as_mut_ptr().add(HEADER_LEN) }
+    }
+}
+
+impl std::fmt::Debug for ShmWriter {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("ShmWriter")
+            .field("path", &self.path)
+            .field("capacity", &self.capacity)
+            .field("position", &self.position)
+            .finish()
+    }
+}
+
+/// Reading end of a shared-memory ring, following it from where the writer
+/// was when it opened
+pub struct ShmReader {
+    map: MmapRaw,
+    capacity: u64,
+    position: u64,
+    lost: u64,
+}
+
+impl ShmReader {
+    /// Open the ring at `path`
+    pub fn open(path: impl AsRef<Path>) -> Result<Self> {
+        let path = path.as_ref();
+        let file = File::open(path)?;
+        let len = file.metadata()?.len();
+        if len < HEADER_LEN as u64 {
+            return Err(AlphaForgeError::validation(format!("{} is not a shared-memory ring", path.display())));
+        }
+        let map = MmapOptions::new().map_raw_read_only(&file)?;
+        let mut header = [0u8; HEADER_LEN];
+        // SAFETY: the mapping is at least `HEADER_LEN` bytes
+        unsafe { std::ptr::copy_nonoverlapping(map.as_ptr(), header.as_mut_ptr(), HEADER_LEN) };
+        let version = u32::from_le_bytes(header[8..12].try_into().unwrap());
+        let capacity = u64::from_le_bytes(header[CAPACITY_OFFSET..CAPACITY_OFFSET + 8].try_into().unwrap());
+        if &header[..8] != MAGIC || version != LAYOUT_VERSION || HEADER_LEN as u64 + capacity > len {
+            return Err(AlphaForgeError::validation(format!(
+                "{} is not a version {} shared-memory ring",
+                path.display(),
+                LAYOUT_VERSION
+            )));
+        }
+        let position = header_atomic(&map, WRITE_POS_OFFSET).load(Ordering::Acquire);
+        Ok(Self {
+            map,
+            capacity,
+            position,
+            lost: 0,
+        })
+    }
+
+    /// Bytes of records skipped because the writer overwrote them first
+    pub fn lost_bytes(&self) -> u64 {
+        self.lost
+    }
+
+    /// Take the next record if one is waiting
+    pub fn try_next(&mut self) -> Option<Vec<u8>> {
+        loop {
+            let written = header_atomic(&self.map, WRITE_POS_OFFSET).load(Ordering::Acquire);
+            if self.position >= written {
+                return None;
+            }
+            if written - self.position > self.capacity {
+                self.skip_to(written);
+                continue;
+            }
+            let offset = self.position % self.capacity;
+            let ring = self.ring();
+            let mut len = [0u8; 4];
+            // SAFETY: records start 8-aligned, so at least 8 bytes of the ring remain
+            unsafe { std::ptr::copy_nonoverlapping(ring.add(offset as usize), len.as_mut_ptr(), 4) };
+            let len = u32::from_le_bytes(len);
+            if len == WRAP_MARKER {
+                if self.overwritten() {
+                    continue;
+                }
+                self.position += self.capacity - offset;
+                continue;
+            }
+            // A torn length is caught by the overwrite check below
+            let size = align(4 + len as usize) as u64;
+            if offset + size > self.capacity {
+                if !self.overwritten() {
+                    tracing::warn!("Corrupt shared-memory record at position {}", self.position);
+                    self.skip_to(written);
+                }
+                continue;
+            }
+            let mut record = vec![0u8; len as usize];
+            // SAFETY: bounds checked against the ring just above
+            unsafe { std::ptr::copy_nonoverlapping(ring.add(offset as usize + 4), record.as_mut_ptr(), record.len()) };
+            if self.overwritten() {
+                continue;
+            }
+            self.position += size;
+            return Some(record);
+        }
+    }
+
+    /// Take the next envelope if one is waiting, skipping records that do
+    /// not decode
+    pub fn try_next_envelope(&mut self) -> Option<MessageEnvelope> {
+        while let Some(record) = self.try_next() {
+            match rmp_serde::from_slice(&record) {
+                Ok(envelope) => return Some(envelope),
+                Err(e) => tracing::warn!("Dropping malformed shared-memory record: {}", e),
+            }
+        }
+        None
+    }
+
+    // Whether the writer may have changed the record at the read position
+    // while it was copied; if so, skip to the latest record
+    fn overwritten(&mut self) -> bool {
+        fence(Ordering::Acquire);
+        let reserved = header_atomic(&self.map, RESERVE_POS_OFFSET).load(Ordering::Relaxed);
+        if reserved - self.position <= self.capacity {
+            return false;
+        }
+        let written = header_atomic(&self.map, WRITE_POS_OFFSET).load(Ordering::Acquire);
+        self.skip_to(written);
+        true
+    }
+
+    fn skip_to(&mut self, position: u64) {
+        self.lost += position - self.position;
+        self.position = position;
+    }
+
+    fn ring(&self) -> *const u8 {
+        // SAFETY: the mapping holds the header and the whole ring
+        unsafe { self.map.as_ptr().add(HEADER_LEN) }
+    }
+}
+
+impl std::fmt::Debug for ShmReader {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("ShmReader")
+            .field("capacity", &self.capacity)
+            .field("position", &self.position)
+            .field("lost", &self.lost)
+            .finish()
+    }
+}
+
+/// Running export of message bus topics to a shared-memory ring. Envelopes
+/// are written on the publishing thread, before the publish returns.
+pub struct ShmBus {
+    writer: Arc<Mutex<ShmWriter>>,
+    callbacks: Vec<CallbackId>,
+    message_bus: Arc<MessageBus>,
+}
+
+impl ShmBus {
+    /// Create the ring and start exporting the configured topics
+    pub fn start(config: ShmBusConfig, message_bus: Arc<MessageBus>) -> Result<Self> {
+        let writer = Arc::new(Mutex::new(ShmWriter::create(&config.path, config.capacity)?));
+        let mut callbacks = Vec::with_capacity(config.topics.len());
+        for topic in &config.topics {
+            let writer = Arc::clone(&writer);
+            let id = message_bus.subscribe_fn(topic, CallbackMode::Publisher, move |envelope| {
+                let result = match writer.lock() {
+                    Ok(mut writer) => writer.write_envelope(envelope),
+                    Err(_) => Err(AlphaForgeError::runtime("Shared-memory writer lock poisoned")),
+                };
+                if let Err(e) = result {
+                    tracing::warn!("Failed to export {} to shared memory: {}", envelope.message_type, e);
+                }
+            })?;
+            callbacks.push(id);
+        }
+        tracing::info!(
+            "Shared-memory bus at {} exporting {:?}",
+            config.path.display(),
+            config.topics
+        );
+        Ok(Self {
+            writer,
+            callbacks,
+            message_bus,
+        })
+    }
+
+    /// Bytes written to the ring so far
+    pub fn position(&self) -> u64 {
+        self.writer.lock().map(|writer| writer.position()).unwrap_or(0)
+    }
+
+    /// Stop exporting; the ring stays in place for readers to drain
+    pub fn stop(self) {
+        for id in &self.callbacks {
+            self.message_bus.unsubscribe_fn(*id);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn ring_path(name: &str) -> PathBuf {
+        std::env::temp_dir().join(format!("alphaforge-shm-{}-{}", name, crate::uuid::UUID4::new()))
+    }
+
+    #[test]
+    fn test_readers_follow_exported_topics() {
+        let path = ring_path("bus");
+        let bus = Arc::new(MessageBus::new());
+        let mut config = ShmBusConfig::new(&path);
+        config.capacity = 4_096;
+        config.topics = vec!["data.quotes.*".to_string()];
+        let shm = ShmBus::start(config, Arc::clone(&bus)).unwrap();
+
+        let (mut first, mut second) = (ShmReader::open(&path).unwrap(), ShmReader::open(&path).unwrap());
+        bus.publish_from("data_engine", "data.quotes.BTCUSDT", &100.5f64);
+        bus.publish_from("data_engine", "data.trades.BTCUSDT", &1u8);
+        bus.publish_from("data_engine", "data.quotes.ETHUSDT", &2_000.25f64);
+
+        for reader in [&mut first, &mut second] {
+            let quotes: Vec<MessageEnvelope> = std::iter::from_fn(|| reader.try_next_envelope()).collect();
+            let topics: Vec<&str> = quotes.iter().map(|envelope| envelope.message_type.as_str()).collect();
+            assert_eq!(topics, vec!["data.quotes.BTCUSDT", "data.quotes.ETHUSDT"]);
+            assert_eq!(quotes[0].sender, "data_engine");
+            assert_eq!(quotes[1].decode::<f64>().unwrap(), 2_000.25);
+            assert_eq!(reader.lost_bytes(), 0);
+        }
+
+        // A reader opened later starts at the current position
+        let mut late = ShmReader::open(&path).unwrap();
+        assert!(late.try_next().is_none());
+        shm.stop();
+        bus.publish_from("data_engine", "data.quotes.BTCUSDT", &101.0f64);
+        assert!(late.try_next().is_none());
+
+        std::fs::remove_file(&path).unwrap();
+    }
+
+    #[test]
+    fn test_ring_wraps_and_lapped_readers_skip_ahead() {
+        let path = ring_path("ring");
+        let mut writer = ShmWriter::create(&path, 128).unwrap();
+        let mut reader = ShmReader::open(&path).unwrap();
+
+        // 40-byte records: three fit before the ring end, the fourth wraps
+        for round in 0..8u8 {
+            writer.write(&[round; 36]).unwrap();
+            assert_eq!(reader.try_next().unwrap(), vec![round; 36]);
+        }
+        assert!(reader.try_next().is_none());
+        assert_eq!(reader.lost_bytes(), 0);
+
+        // Lapped while away: the reader resumes at the newest data
+        for round in 0..10u8 {
+            writer.write(&[round; 36]).unwrap();
+        }
+        assert!(reader.try_next().is_none());
+        assert!(reader.lost_bytes() > 0);
+        writer.write(&[42; 36]).unwrap();
+        assert_eq!(reader.try_next().unwrap(), vec![42; 36]);
+
+        assert!(writer.write(&[0; 125]).is_err());
+        assert!(ShmWriter::create(&path, 100).is_err());
+        std::fs::remove_file(&path).unwrap();
+    }
+}
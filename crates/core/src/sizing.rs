@@ -0,0 +1,151 @@
+//! AlphaForge Position Sizing
+//!
+//! Turns account equity, price and risk estimates into an order quantity.
+//! Strategies pick a `PositionSizer` in their config and call
+//! `StrategyContext::position_size`, which also applies the strategy's
+//! position limit and the instrument's lot size.
+
+use serde::{Deserialize, Serialize};
+
+/// What a sizer knows about the trade being sized
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct SizingInputs {
+    /// Expected entry price
+    pub price: f64,
+    /// Account equity available to the strategy
+    pub equity: f64,
+    /// Distance from entry to the stop, in price units
+    pub stop_distance: Option<f64>,
+    /// Volatility of returns over the sizer's horizon (e.g. 0.02 for 2%)
+    pub volatility: Option<f64>,
+    /// Contract multiplier (1.0 for spot)
+    pub multiplier: f64,
+}
+
+impl SizingInputs {
+    pub fn new(price: f64, equity: f64) -> Self {
+        Self {
+            price,
+            equity,
+            stop_distance: None,
+            volatility: None,
+            multiplier: 1.0,
+        }
+    }
+
+    pub fn with_stop_distance(mut self, stop_distance: f64) -> Self {
+        self.stop_distance = Some(stop_distance);
+        self
+    }
+
+    pub fn with_volatility(mut self, volatility: f64) -> Self {
+        self.volatility = Some(volatility);
+        self
+    }
+
+    pub fn with_multiplier(mut self, multiplier: f64) -> Self {
+        self.multiplier = multiplier;
+        self
+    }
+
+    // Value of one unit of quantity
+    fn unit_value(&self) -> Result<f64, String> {
+        let value = self.price * self.multiplier;
+        if !value.is_finite() || value <= 0.0 {
+            return Err(format!("Invalid price for sizing: {}", self.price));
+        }
+        Ok(value)
+    }
+}
+
+/// Position sizing rule
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub enum PositionSizer {
+    /// Always trade the same quantity
+    FixedQuantity(f64),
+    /// Trade a fixed notional value
+    FixedNotional(f64),
+    /// Risk a fraction of equity between entry and stop
+    FixedFractional { risk_fraction: f64 },
+    /// Allocate `fraction` of the Kelly-optimal share of equity
+    Kelly {
+        /// Probability a trade wins
+        win_probability: f64,
+        /// Average win divided by average loss
+        payoff_ratio: f64,
+        /// Kelly fraction to use (0.5 for half Kelly)
+        fraction: f64,
+    },
+    /// Size so the position's volatility matches `target_volatility` of equity
+    VolatilityTarget { target_volatility: f64 },
+}
+
+impl Default for PositionSizer {
+    fn default() -> Self {
+        PositionSizer::FixedQuantity(1.0)
+    }
+}
+
+impl PositionSizer {
+    /// Unrounded quantity for a trade (never negative)
+    pub fn size(&self, inputs: &SizingInputs) -> Result<f64, String> {
+        let quantity = match self {
+            PositionSizer::FixedQuantity(quantity) => *quantity,
+            PositionSizer::FixedNotional(notional) => notional / inputs.unit_value()?,
+            PositionSizer::FixedFractional { risk_fraction } => {
+                let stop_distance = inputs.stop_distance
+                    .filter(|distance| *distance > 0.0)
+                    .ok_or("Fixed fractional sizing needs a positive stop distance")?;
+                inputs.equity * risk_fraction / (stop_distance * inputs.multiplier)
+            }
+            PositionSizer::Kelly { win_probability, payoff_ratio, fraction } => {
+                if *payoff_ratio <= 0.0 {
+                    return Err(format!("Invalid Kelly payoff ratio: {}", payoff_ratio));
+                }
+                let kelly = win_probability - (1.0 - win_probability) / payoff_ratio;
+                inputs.equity * kelly.max(0.0) * fraction / inputs.unit_value()?
+            }
+            PositionSizer::VolatilityTarget { target_volatility } => {
+                let volatility = inputs.volatility
+                    .filter(|volatility| *volatility > 0.0)
+                    .ok_or("Volatility targeting needs a positive volatility estimate")?;
+                inputs.equity * target_volatility / (volatility * inputs.unit_value()?)
+            }
+        };
+
+        if !quantity.is_finite() {
+            return Err(format!("Sizing produced an invalid quantity: {}", quantity));
+        }
+        Ok(quantity.max(0.0))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_sizing_rules() {
+        let inputs = SizingInputs::new(50.0, 100_000.0);
+
+        assert_eq!(PositionSizer::FixedQuantity(3.0).size(&inputs).unwrap(), 3.0);
+        assert_eq!(PositionSizer::FixedNotional(10_000.0).size(&inputs).unwrap(), 200.0);
+        assert_eq!(PositionSizer::FixedNotional(10_000.0).size(&inputs.with_multiplier(10.0)).unwrap(), 20.0);
+
+        // Risking 1% with a $2 stop
+        let fractional = PositionSizer::FixedFractional { risk_fraction: 0.01 };
+        assert!(fractional.size(&inputs).is_err());
+        assert_eq!(fractional.size(&inputs.with_stop_distance(2.0)).unwrap(), 500.0);
+
+        // Kelly: 0.6 - 0.4 / 2 = 0.4, halved
+        let kelly = PositionSizer::Kelly { win_probability: 0.6, payoff_ratio: 2.0, fraction: 0.5 };
+        assert!((kelly.size(&inputs).unwrap() - 400.0).abs() < 1e-9);
+        let losing = PositionSizer::Kelly { win_probability: 0.2, payoff_ratio: 1.0, fraction: 1.0 };
+        assert_eq!(losing.size(&inputs).unwrap(), 0.0);
+
+        // 10% target with 20% volatility puts half of equity to work
+        let target = PositionSizer::VolatilityTarget { target_volatility: 0.1 };
+        assert!(target.size(&inputs).is_err());
+        assert_eq!(target.size(&inputs.with_volatility(0.2)).unwrap(), 1_000.0);
+    }
+}
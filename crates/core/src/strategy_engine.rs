@@ -1,12 +1,32 @@
-use std::collections::HashMap;
-use std::sync::{Arc, Mutex};
-use std::time::{SystemTime, UNIX_EPOCH};
+use std::collections::{HashMap, HashSet};
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{mpsc, Arc, Mutex, MutexGuard};
+use std::thread;
+use std::time::{Duration, Instant, SystemTime};
 use serde::{Serialize, Deserialize};
 
-use crate::data::{TradeTick, QuoteTick, Bar};
-use crate::identifiers::{InstrumentId, StrategyId};
-use crate::data_engine::DataEngine;
+use crate::data::{
+    TradeTick, QuoteTick, Bar, BarType, FundingRateUpdate, OpenInterestUpdate, MarkPriceUpdate, IndexPriceUpdate,
+    MarketData, SignalData,
+};
+use crate::calendar::SessionConfig;
+use crate::schedule::{SessionEvent, SessionSchedule};
+use crate::clock::{Clock, LiveClock, TimeEvent, TimeEventSender, TimerSchedule};
+use crate::identifiers::{InstrumentId, OrderId, StrategyId};
+use crate::data_engine::{DataEngine, DERIVED_QUOTE_TOPIC_PREFIX};
+use crate::message_bus::{
+    correlate, current_correlation_id, handle_envelope, start_chain, strategy_topic, MessageBus, ALERTS_CHANNEL,
+    COMMANDS_CHANNEL, CONTROL_CHANNEL, LOGS_CHANNEL, Subscription,
+};
+use crate::decision_log::{DecisionEntry, DecisionLog, LoggedEvent};
+use crate::logging::{self, LogLevel};
+use crate::parameters::{ParameterValue, Parameters};
+use crate::sizing::{PositionSizer, SizingInputs};
+use crate::execution_engine::{ExecutionEngine, Fill, Order, OrderCommand, OrderCommandSender, OrderEvent, OrderSide};
 use crate::generic_cache::GenericCache;
+use crate::uuid::UUID7;
 
 /// Strategy state enumeration
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
@@ -21,26 +41,103 @@ pub enum StrategyState {
     Stopped,
     /// Strategy encountered an error
     Error,
+    /// Strategy breached a risk limit and was stopped (cannot be resumed)
+    RiskBreached,
 }
 
-/// Base configuration for all strategies
+/// Base configuration for all strategies; fields missing when deserialized
+/// take their default
 #[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
 pub struct StrategyConfig {
     /// Unique identifier for the strategy
+    #[serde(deserialize_with = "crate::config::deserialize_strategy_id")]
     pub strategy_id: StrategyId,
     /// Strategy name for logging and identification
     pub name: String,
     /// Instruments this strategy will trade
+    #[serde(deserialize_with = "crate::config::deserialize_instrument_ids")]
     pub instruments: Vec<InstrumentId>,
     /// Maximum position size per instrument
     pub max_position_size: f64,
-    /// Risk management parameters
-    pub max_daily_loss: f64,
-    pub max_drawdown: f64,
+    /// Loss over a UTC day at which the strategy engine stops the strategy
+    /// (`None` disables the limit)
+    pub max_daily_loss: Option<f64>,
+    /// Fall from peak equity, as a fraction of the peak, at which the
+    /// strategy engine stops the strategy (`None` disables the limit)
+    pub max_drawdown: Option<f64>,
+    /// Equity drawdown is measured from before any PnL
+    #[serde(default = "default_starting_equity")]
+    pub starting_equity: f64,
+    /// Close open positions when a risk limit is breached
+    #[serde(default)]
+    pub flatten_on_risk_breach: bool,
     /// Enable/disable features
     pub enable_logging: bool,
     pub enable_metrics: bool,
     pub enable_backtesting: bool,
+    /// Rule used by `StrategyContext::position_size`
+    #[serde(default)]
+    pub position_sizer: PositionSizer,
+    /// Strategy-specific settings, re-tunable with `StrategyEngine::update_parameters`
+    #[serde(default)]
+    pub parameters: Parameters,
+    /// History streamed into the strategy when the engine starts
+    #[serde(default)]
+    pub warmup: WarmupConfig,
+    /// Whether orders reach a venue or are only published as signals
+    #[serde(default)]
+    pub execution_mode: ExecutionMode,
+    /// Least severe level logged by the strategy, through
+    /// `StrategyContext::log` or any event inside its callbacks (`None`
+    /// leaves it to the subscriber)
+    #[serde(default)]
+    pub log_level: Option<LogLevel>,
+    /// Hours the strategy receives market data (`None` trades around the clock)
+    #[serde(default)]
+    pub session: Option<SessionConfig>,
+    /// Instrument whose buy-and-hold returns the strategy is measured
+    /// against (alpha, beta, information ratio, relative drawdown)
+    #[serde(default, deserialize_with = "crate::config::deserialize_optional_instrument_id")]
+    pub benchmark: Option<InstrumentId>,
+}
+
+/// Where a strategy's orders go
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub enum ExecutionMode {
+    /// Orders are sent to the execution engine
+    #[default]
+    Live,
+    /// Orders are logged and published as signals but never routed to a
+    /// venue, to validate a strategy on production data before giving it
+    /// capital
+    Shadow,
+}
+
+/// Signal name prefix for shadow orders; `{strategy_id}.{instrument_id}` is
+/// appended and the value is the signed order quantity
+pub const SHADOW_SIGNAL_PREFIX: &str = "shadow.";
+
+/// Longest `StrategyEngine::stop` waits for a strategy thread to drain its
+/// channel unless configured otherwise
+pub const DEFAULT_WORKER_STOP_TIMEOUT: Duration = Duration::from_secs(5);
+
+// Clock timer firing at a strategy's session closes; strategies' own timers
+// are named `{strategy_id}.{name}`, so they never clash with it
+fn session_close_timer(strategy_id: StrategyId) -> String {
+    format!("strategy_engine.session_close.{}", strategy_id)
+}
+
+/// Cached history delivered to a strategy before it goes live, so its
+/// indicators are primed; order submission is disabled meanwhile
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct WarmupConfig {
+    /// Most recent bars to deliver per bar type
+    pub bars: Vec<(BarType, usize)>,
+    /// Most recent trade ticks to deliver per configured instrument
+    pub trade_ticks: usize,
+    /// Most recent quote ticks to deliver per configured instrument
+    pub quote_ticks: usize,
 }
 
 impl Default for StrategyConfig {
@@ -50,15 +147,28 @@ impl Default for StrategyConfig {
             name: "DefaultStrategy".to_string(),
             instruments: vec![],
             max_position_size: 1000.0,
-            max_daily_loss: 10000.0,
-            max_drawdown: 0.05, // 5%
+            max_daily_loss: None,
+            max_drawdown: None,
+            starting_equity: default_starting_equity(),
+            flatten_on_risk_breach: false,
             enable_logging: true,
             enable_metrics: true,
             enable_backtesting: false,
+            position_sizer: PositionSizer::default(),
+            parameters: Parameters::default(),
+            warmup: WarmupConfig::default(),
+            execution_mode: ExecutionMode::default(),
+            log_level: None,
+            session: None,
+            benchmark: None,
         }
     }
 }
 
+fn default_starting_equity() -> f64 {
+    100_000.0
+}
+
 /// Strategy performance metrics
 #[derive(Debug, Clone, Default, Serialize, Deserialize)]
 pub struct StrategyMetrics {
@@ -78,10 +188,26 @@ pub struct StrategyMetrics {
     pub max_consecutive_wins: u64,
     /// Maximum consecutive losses
     pub max_consecutive_losses: u64,
-    /// Maximum drawdown experienced
+    /// Maximum fall from peak equity, as a fraction of the peak
     pub max_drawdown: f64,
-    /// Sharpe ratio (if applicable)
+    /// Mean over standard deviation of per-trade returns (not annualized)
     pub sharpe_ratio: f64,
+    /// Mean over downside deviation of per-trade returns (not annualized)
+    #[serde(default)]
+    pub sortino_ratio: f64,
+    /// Return between fills not explained by the benchmark (not annualized)
+    #[serde(default)]
+    pub alpha: f64,
+    /// Sensitivity of returns between fills to the benchmark's over the same periods
+    #[serde(default)]
+    pub beta: f64,
+    /// Mean over standard deviation of returns in excess of the benchmark
+    #[serde(default)]
+    pub information_ratio: f64,
+    /// Maximum fall of equity relative to the benchmark from its peak, as a
+    /// fraction of the peak
+    #[serde(default)]
+    pub max_relative_drawdown: f64,
     /// Current open positions
     pub open_positions: HashMap<InstrumentId, f64>,
     /// Strategy uptime in seconds
@@ -90,6 +216,293 @@ pub struct StrategyMetrics {
     pub last_update_ts: u64,
 }
 
+/// Published on `strategies.error` when a strategy fails and is isolated,
+/// and on `strategies.risk_breached` when it is stopped by a risk limit
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct StrategyAlert {
+    pub strategy_id: StrategyId,
+    pub strategy_name: String,
+    /// Error returned by the strategy
+    pub reason: String,
+    pub ts: u64,
+}
+
+/// A log line of a strategy, published on `strategy.{id}.logs`
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct StrategyLogRecord {
+    pub strategy_id: StrategyId,
+    pub level: LogLevel,
+    pub message: String,
+    pub ts: u64,
+}
+
+/// Instruction for one strategy, sent by external tools on
+/// `strategy.{id}.control` and applied ahead of the next event the engine
+/// handles (or by `process_control_messages`)
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub enum StrategyControl {
+    Pause { cancel_orders: bool },
+    Resume { force: bool },
+    UpdateParameters(Vec<(String, ParameterValue)>),
+    SetLogLevel(Option<LogLevel>),
+    SetExecutionMode(ExecutionMode),
+}
+
+/// Daily loss and drawdown bookkeeping, on equity booked from fills
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+struct RiskTracker {
+    /// UTC day of the last check
+    day: Option<u64>,
+    /// Booked PnL when the current day began
+    day_start_pnl: f64,
+    /// Booked PnL at the last check
+    last_pnl: f64,
+    peak_equity: f64,
+    /// Peak of equity reported through `record_trade`, for `max_drawdown`
+    #[serde(default)]
+    metrics_peak_equity: f64,
+}
+
+/// Positions and PnL booked from the fills of a strategy's own orders
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+struct FillBook {
+    /// Orders submitted and not yet complete
+    orders: HashMap<OrderId, Order>,
+    /// Signed quantity and average entry price per instrument
+    positions: HashMap<InstrumentId, (f64, f64)>,
+    /// Latest price of each instrument with a position
+    marks: HashMap<InstrumentId, f64>,
+    /// Realized PnL net of commission
+    realized_pnl: f64,
+}
+
+impl FillBook {
+    // Track a submitted or amended order until it completes
+    fn track(&mut self, event: &OrderEvent) {
+        match event {
+            OrderEvent::OrderSubmitted { order, .. } => {
+                self.orders.insert(order.order_id, order.clone());
+            }
+            OrderEvent::OrderModified { order_id, modified_order, .. } => {
+                if let Some(order) = self.orders.get_mut(order_id) {
+                    order.quantity = modified_order.quantity;
+                    order.price = modified_order.price;
+                }
+            }
+            OrderEvent::OrderRejected { order_id, .. } | OrderEvent::OrderCancelled { order_id, .. } => {
+                self.orders.remove(order_id);
+            }
+            OrderEvent::OrderAccepted { .. } | OrderEvent::OrderFilled { .. } => {}
+        }
+    }
+
+    // Book a fill, returning the instrument, the PnL it realized net of
+    // commission and its signed quantity (`None` for an unknown order)
+    fn book(&mut self, fill: &Fill) -> Option<(InstrumentId, f64, f64)> {
+        let order = self.orders.get_mut(&fill.order_id)?;
+        order.filled_quantity += fill.quantity;
+        let instrument_id = order.instrument_id;
+        let traded = match order.side {
+            OrderSide::Buy => fill.quantity,
+            OrderSide::Sell => -fill.quantity,
+        };
+        if order.is_filled() {
+            self.orders.remove(&fill.order_id);
+        }
+
+        let (quantity, avg_price) = self.positions.get(&instrument_id).copied().unwrap_or((0.0, 0.0));
+        let mut pnl = -fill.commission;
+        if quantity * traded < 0.0 {
+            pnl += traded.abs().min(quantity.abs()) * (fill.price - avg_price) * quantity.signum();
+        }
+        let remaining = quantity + traded;
+        let avg_price = if remaining == 0.0 {
+            0.0
+        } else if quantity * remaining <= 0.0 {
+            // Opened from flat or flipped through it
+            fill.price
+        } else if remaining.abs() > quantity.abs() {
+            (avg_price * quantity.abs() + fill.price * traded.abs()) / remaining.abs()
+        } else {
+            avg_price
+        };
+        if remaining == 0.0 {
+            self.positions.remove(&instrument_id);
+            self.marks.remove(&instrument_id);
+        } else {
+            self.positions.insert(instrument_id, (remaining, avg_price));
+            self.marks.insert(instrument_id, fill.price);
+        }
+        self.realized_pnl += pnl;
+        Some((instrument_id, pnl, traded))
+    }
+
+    // Mark an open position at the latest market price
+    fn mark(&mut self, instrument_id: InstrumentId, price: f64) {
+        if self.positions.contains_key(&instrument_id) {
+            self.marks.insert(instrument_id, price);
+        }
+    }
+
+    /// Realized PnL plus open positions marked to market
+    fn pnl(&self) -> f64 {
+        let unrealized: f64 = self.positions
+            .iter()
+            .map(|(instrument_id, (quantity, avg_price))| {
+                self.marks.get(instrument_id).map_or(0.0, |mark| quantity * (mark - avg_price))
+            })
+            .sum();
+        self.realized_pnl + unrealized
+    }
+}
+
+/// Running statistics of per-trade returns on equity
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+struct ReturnTracker {
+    count: u64,
+    mean: f64,
+    /// Sum of squared deviations from the mean (Welford)
+    m2: f64,
+    /// Sum of squared negative returns
+    downside_sq: f64,
+    win_streak: u64,
+    loss_streak: u64,
+}
+
+impl ReturnTracker {
+    fn record(&mut self, value: f64) {
+        self.count += 1;
+        let delta = value - self.mean;
+        self.mean += delta / self.count as f64;
+        self.m2 += delta * (value - self.mean);
+        if value < 0.0 {
+            self.downside_sq += value * value;
+        }
+    }
+
+    fn sharpe_ratio(&self) -> f64 {
+        if self.count < 2 {
+            return 0.0;
+        }
+        let std = (self.m2 / (self.count - 1) as f64).sqrt();
+        if std > 0.0 { self.mean / std } else { 0.0 }
+    }
+
+    fn sortino_ratio(&self) -> f64 {
+        if self.count < 2 {
+            return 0.0;
+        }
+        let downside = (self.downside_sq / self.count as f64).sqrt();
+        if downside > 0.0 { self.mean / downside } else { 0.0 }
+    }
+}
+
+/// Returns of a strategy's marked equity between fills paired with the
+/// benchmark's return over the same period
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+struct BenchmarkTracker {
+    /// Benchmark price at the last fill
+    last_price: Option<f64>,
+    /// Equity, open positions marked to market, at the last fill
+    #[serde(default)]
+    last_equity: Option<f64>,
+    count: u64,
+    mean: f64,
+    benchmark_mean: f64,
+    /// Sum of squared deviations of benchmark returns
+    benchmark_m2: f64,
+    /// Sum of products of strategy and benchmark deviations
+    co_moment: f64,
+    active_mean: f64,
+    active_m2: f64,
+    /// Growth of equity over growth of the benchmark, and its peak
+    relative: f64,
+    relative_peak: f64,
+}
+
+impl BenchmarkTracker {
+    // Pair the strategy's move to `equity` with the benchmark's move to
+    // `price`, returning the relative drawdown afterwards (`None` until base
+    // values are known)
+    fn record(&mut self, equity: f64, price: f64) -> Option<f64> {
+        let last_price = self.last_price.replace(price).filter(|last| *last > 0.0);
+        let last_equity = self.last_equity.replace(equity).filter(|last| *last > 0.0);
+        let (last_price, last_equity) = (last_price?, last_equity?);
+        let strategy_return = equity / last_equity - 1.0;
+        let benchmark_return = price / last_price - 1.0;
+
+        self.count += 1;
+        let n = self.count as f64;
+        let delta = strategy_return - self.mean;
+        self.mean += delta / n;
+        let benchmark_delta = benchmark_return - self.benchmark_mean;
+        self.benchmark_mean += benchmark_delta / n;
+        self.benchmark_m2 += benchmark_delta * (benchmark_return - self.benchmark_mean);
+        self.co_moment += delta * (benchmark_return - self.benchmark_mean);
+        let active = strategy_return - benchmark_return;
+        let active_delta = active - self.active_mean;
+        self.active_mean += active_delta / n;
+        self.active_m2 += active_delta * (active - self.active_mean);
+
+        if self.count == 1 {
+            self.relative = 1.0;
+            self.relative_peak = 1.0;
+        }
+        self.relative *= (1.0 + strategy_return) / (1.0 + benchmark_return);
+        self.relative_peak = self.relative_peak.max(self.relative);
+        Some(if self.relative_peak > 0.0 { (self.relative_peak - self.relative) / self.relative_peak } else { 0.0 })
+    }
+
+    fn beta(&self) -> f64 {
+        if self.benchmark_m2 > 0.0 { self.co_moment / self.benchmark_m2 } else { 0.0 }
+    }
+
+    fn alpha(&self) -> f64 {
+        self.mean - self.beta() * self.benchmark_mean
+    }
+
+    fn information_ratio(&self) -> f64 {
+        if self.count < 2 {
+            return 0.0;
+        }
+        let std = (self.active_m2 / (self.count - 1) as f64).sqrt();
+        if std > 0.0 { self.active_mean / std } else { 0.0 }
+    }
+}
+
+/// Latest price of a benchmark instrument, shared by the engine and the
+/// strategies measured against it
+type BenchmarkPrice = Arc<Mutex<Option<f64>>>;
+
+/// Persisted state of a strategy, written by `StrategyEngine::save_state`
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct StrategySnapshot {
+    pub strategy_id: StrategyId,
+    pub strategy_name: String,
+    pub strategy_version: String,
+    pub saved_at: u64,
+    pub metrics: StrategyMetrics,
+    /// Opaque state from `Strategy::save_state`
+    pub state: Vec<u8>,
+    risk: RiskTracker,
+    returns: ReturnTracker,
+    #[serde(default)]
+    benchmark: BenchmarkTracker,
+    #[serde(default)]
+    fills: FillBook,
+}
+
+impl StrategySnapshot {
+    /// Encode as MessagePack
+    pub fn to_bytes(&self) -> Result<Vec<u8>, String> {
+        rmp_serde::to_vec(self).map_err(|e| format!("Failed to encode strategy state: {}", e))
+    }
+
+    pub fn from_bytes(bytes: &[u8]) -> Result<Self, String> {
+        rmp_serde::from_slice(bytes).map_err(|e| format!("Failed to decode strategy state: {}", e))
+    }
+}
+
 /// Strategy execution context
 pub struct StrategyContext {
     /// Strategy configuration
@@ -106,15 +519,38 @@ pub struct StrategyContext {
     pub start_time: SystemTime,
     /// Last heartbeat time
     pub last_heartbeat: SystemTime,
+    /// Channel to the execution engine (`None` until one is connected)
+    pub order_commands: Option<OrderCommandSender>,
+    /// Clock the strategy reads time from and sets timers on
+    pub clock: Arc<dyn Clock>,
+    /// Log of delivered events and emitted order commands (`None` unless
+    /// the engine records decisions)
+    pub decision_log: Option<Arc<DecisionLog>>,
+    /// Bus the strategy's namespace lives on (`None` unless the engine has one)
+    pub message_bus: Option<Arc<MessageBus>>,
+    /// Where the clock sends this strategy's timer events, set by the
+    /// engine the strategy is added to
+    timer_events: TimeEventSender,
+    /// Names of this strategy's active timers
+    timers: HashSet<String>,
+    /// Set while history is being delivered before going live
+    warming_up: bool,
+    risk: RiskTracker,
+    returns: ReturnTracker,
+    benchmark: BenchmarkTracker,
+    fills: FillBook,
+    /// Latest price of the configured benchmark, fed by the engine
+    benchmark_price: BenchmarkPrice,
 }
 
 impl StrategyContext {
-    /// Create a new strategy context
-    pub fn new(config: StrategyConfig, data_engine: Arc<Mutex<DataEngine>>) -> Self {
+    /// Create a new strategy context reading time from `clock`
+    pub fn new(config: StrategyConfig, data_engine: Arc<Mutex<DataEngine>>, clock: Arc<dyn Clock>) -> Self {
         let cache_config = crate::generic_cache::GenericCacheConfig {
             max_size: 10000,
             ttl_seconds: Some(300), // 5 minutes
             enable_statistics: true,
+            ..Default::default()
         };
         
         Self {
@@ -125,15 +561,359 @@ impl StrategyContext {
             cache: Arc::new(Mutex::new(GenericCache::new(cache_config))),
             start_time: SystemTime::now(),
             last_heartbeat: SystemTime::now(),
+            order_commands: None,
+            clock,
+            decision_log: None,
+            message_bus: None,
+            timer_events: TimeEventSender::Channel(tokio::sync::mpsc::unbounded_channel().0),
+            timers: HashSet::new(),
+            warming_up: false,
+            risk: RiskTracker::default(),
+            returns: ReturnTracker::default(),
+            benchmark: BenchmarkTracker::default(),
+            fills: FillBook::default(),
+            benchmark_price: Arc::new(Mutex::new(None)),
+        }
+    }
+
+    /// Start a repeating timer delivering `on_timer(name)` every `interval_ns`,
+    /// first firing one interval from now. Replaces a timer with the same name.
+    pub fn set_timer(&mut self, name: &str, interval_ns: u64) -> Result<(), String> {
+        if interval_ns == 0 {
+            return Err(format!("Timer {} needs a positive interval", name));
+        }
+        let clock = &self.clock;
+        clock
+            .set_timer(
+                self.clock_timer_name(name),
+                interval_ns,
+                clock.timestamp_ns() + interval_ns,
+                None,
+                self.timer_events.clone(),
+            )
+            .map_err(|e| e.to_string())?;
+
+        self.timers.insert(name.to_string());
+        Ok(())
+    }
+
+    /// Start a timer delivering `on_timer(name)` at each time of `schedule`,
+    /// such as an `AlignedSchedule`, `CronSchedule` or `SessionSchedule`.
+    /// Replaces a timer with the same name.
+    pub fn set_scheduled_timer(&mut self, name: &str, schedule: Arc<dyn TimerSchedule>) -> Result<(), String> {
+        self.clock
+            .set_scheduled_timer(
+                self.clock_timer_name(name),
+                schedule,
+                None,
+                self.timer_events.clone(),
+            )
+            .map_err(|e| e.to_string())?;
+
+        self.timers.insert(name.to_string());
+        Ok(())
+    }
+
+    /// Cancel one of this strategy's timers
+    pub fn cancel_timer(&mut self, name: &str) -> Result<(), String> {
+        if !self.timers.remove(name) {
+            return Err(format!("Timer {} not found", name));
+        }
+        self.clock.cancel_timer(self.clock_timer_name(name)).map_err(|e| e.to_string())
+    }
+
+    /// Cancel every timer owned by this strategy
+    pub fn cancel_all_timers(&mut self) {
+        let names: Vec<_> = self.timers.iter().cloned().collect();
+        for name in names {
+            if let Err(e) = self.cancel_timer(&name) {
+                tracing::warn!("Failed to cancel timer {}: {}", name, e);
+            }
+        }
+    }
+
+    /// Names of this strategy's active timers
+    pub fn timer_names(&self) -> Vec<String> {
+        self.timers.iter().cloned().collect()
+    }
+
+    // Timers share the clock, so names are scoped by strategy
+    fn clock_timer_name(&self, name: &str) -> String {
+        format!("{}.{}", self.config.strategy_id, name)
+    }
+
+    /// Submit an order, returning its ID.
+    ///
+    /// The order is queued for the execution engine; submission results
+    /// arrive as order events.
+    pub fn submit_order(&self, order: Order) -> Result<OrderId, String> {
+        if order.strategy_id != self.config.strategy_id {
+            return Err(format!(
+                "Order belongs to strategy {} not {}",
+                order.strategy_id, self.config.strategy_id
+            ));
+        }
+        if !order.quantity.is_finite() || order.quantity <= 0.0 {
+            return Err(format!("Invalid order quantity: {}", order.quantity));
+        }
+
+        let order_id = order.order_id;
+        self.send_order_command(OrderCommand::Submit(order))?;
+        Ok(order_id)
+    }
+
+    /// Cancel an active order
+    pub fn cancel_order(&self, order_id: OrderId) -> Result<(), String> {
+        self.send_order_command(OrderCommand::Cancel { order_id })
+    }
+
+    /// Amend the quantity and, if given, the price of an active order
+    pub fn modify_order(&self, order_id: OrderId, quantity: f64, price: Option<f64>) -> Result<(), String> {
+        self.send_order_command(OrderCommand::Modify { order_id, quantity, price })
+    }
+
+    /// Buy at market
+    pub fn buy_market(&self, instrument_id: InstrumentId, quantity: f64) -> Result<OrderId, String> {
+        self.submit_new_order(instrument_id, OrderSide::Buy, quantity, None)
+    }
+
+    /// Sell at market
+    pub fn sell_market(&self, instrument_id: InstrumentId, quantity: f64) -> Result<OrderId, String> {
+        self.submit_new_order(instrument_id, OrderSide::Sell, quantity, None)
+    }
+
+    /// Buy with a limit price
+    pub fn buy_limit(&self, instrument_id: InstrumentId, quantity: f64, price: f64) -> Result<OrderId, String> {
+        self.submit_new_order(instrument_id, OrderSide::Buy, quantity, Some(price))
+    }
+
+    /// Sell with a limit price
+    pub fn sell_limit(&self, instrument_id: InstrumentId, quantity: f64, price: f64) -> Result<OrderId, String> {
+        self.submit_new_order(instrument_id, OrderSide::Sell, quantity, Some(price))
+    }
+
+    /// Flatten the open position in an instrument with a market order.
+    ///
+    /// Returns `None` when there is no position.
+    pub fn close_position(&self, instrument_id: InstrumentId) -> Result<Option<OrderId>, String> {
+        let position = self.metrics.open_positions.get(&instrument_id).copied().unwrap_or(0.0);
+        if position == 0.0 {
+            return Ok(None);
+        }
+
+        let side = if position > 0.0 { OrderSide::Sell } else { OrderSide::Buy };
+        self.submit_new_order(instrument_id, side, position.abs(), None).map(Some)
+    }
+
+    /// Submit orders closing every open position
+    pub fn flatten_positions(&self) {
+        for instrument_id in self.metrics.open_positions.keys().copied() {
+            if let Err(e) = self.close_position(instrument_id) {
+                tracing::warn!("Failed to flatten {} for {}: {}", instrument_id, self.config.name, e);
+            }
+        }
+    }
+
+    /// Quantity for a trade in an instrument under the configured position
+    /// sizer, capped at `max_position_size` and rounded down to the
+    /// instrument's lot size when the instrument is known
+    pub fn position_size(&self, instrument_id: InstrumentId, inputs: SizingInputs) -> Result<f64, String> {
+        let instrument = self.data_engine
+            .lock()
+            .map_err(|_| "Data engine lock poisoned".to_string())?
+            .get_instrument(instrument_id);
+
+        let inputs = match &instrument {
+            Some(instrument) => inputs.with_multiplier(instrument.multiplier),
+            None => inputs,
+        };
+        let quantity = self.config.position_sizer.size(&inputs)?.min(self.config.max_position_size);
+        Ok(match &instrument {
+            Some(instrument) => instrument.round_size(quantity),
+            None => quantity,
+        })
+    }
+
+    /// Build an order for this strategy, rounded to the instrument's lot and
+    /// tick size when the instrument is known, and submit it
+    fn submit_new_order(
+        &self,
+        instrument_id: InstrumentId,
+        side: OrderSide,
+        quantity: f64,
+        price: Option<f64>,
+    ) -> Result<OrderId, String> {
+        let instrument = self.data_engine
+            .lock()
+            .map_err(|_| "Data engine lock poisoned".to_string())?
+            .get_instrument(instrument_id);
+
+        let (quantity, price) = match &instrument {
+            Some(instrument) => (instrument.round_size(quantity), price.map(|p| instrument.round_price(p))),
+            None => (quantity, price),
+        };
+        if quantity <= 0.0 {
+            return Err(format!("Order quantity for {} rounds to zero", instrument_id));
+        }
+
+        let strategy_id = self.config.strategy_id;
+        let now = self.clock.timestamp_ns();
+        let order = match price {
+            Some(price) => Order::limit_at(strategy_id, instrument_id, side, quantity, price, now),
+            None => Order::market_at(strategy_id, instrument_id, side, quantity, now),
+        };
+        self.submit_order(order)
+    }
+
+    fn send_order_command(&self, command: OrderCommand) -> Result<(), String> {
+        if !self.is_active() {
+            return Err(format!("Strategy {} is not running", self.config.name));
+        }
+        if self.warming_up {
+            return Err(format!("Strategy {} is warming up", self.config.name));
+        }
+        if self.config.execution_mode == ExecutionMode::Shadow {
+            return self.shadow_order_command(command);
+        }
+        let sender = self.order_commands
+            .as_ref()
+            .ok_or_else(|| "No execution engine connected".to_string())?;
+        sender
+            .send(command.clone())
+            .map_err(|_| "Execution engine is not accepting orders".to_string())?;
+        self.publish(COMMANDS_CHANNEL, &command);
+        self.record_decision(DecisionEntry::Command(command));
+        Ok(())
+    }
+
+    // Record an order command in place of sending it
+    fn shadow_order_command(&self, command: OrderCommand) -> Result<(), String> {
+        tracing::info!("Shadow strategy {}: {:?}", self.config.name, command);
+        if let OrderCommand::Submit(order) = &command {
+            let quantity = match order.side {
+                OrderSide::Buy => order.quantity,
+                OrderSide::Sell => -order.quantity,
+            };
+            let name = format!("{}{}.{}", SHADOW_SIGNAL_PREFIX, self.config.strategy_id, order.instrument_id);
+            self.publish_signal(&name, quantity)?;
+        }
+        self.record_decision(DecisionEntry::Command(command));
+        Ok(())
+    }
+
+    fn record_decision(&self, entry: DecisionEntry) {
+        if let Some(log) = &self.decision_log {
+            if let Err(e) = log.append(self.config.strategy_id, self.current_time_ns(), entry) {
+                tracing::warn!("Failed to log decision of strategy {}: {}", self.config.name, e);
+            }
+        }
+    }
+
+    /// Whether `log` emits messages at `level` for this strategy
+    pub fn log_enabled(&self, level: LogLevel) -> bool {
+        self.config.log_level.is_none_or(|min| level >= min)
+    }
+
+    /// Log a message, subject to the strategy's log level. Inside a
+    /// callback the line carries the strategy, instrument and order.
+    pub fn log(&self, level: LogLevel, message: &str) {
+        if self.log_enabled(level) {
+            logging::emit(level, message);
+            let record = StrategyLogRecord {
+                strategy_id: self.config.strategy_id,
+                level,
+                message: message.to_string(),
+                ts: self.current_time_ns(),
+            };
+            self.publish(LOGS_CHANNEL, &record);
+        }
+    }
+
+    /// Publish a message on a channel of the strategy's namespace
+    pub fn publish<T: Serialize>(&self, channel: &str, message: &T) {
+        if let Some(message_bus) = &self.message_bus {
+            message_bus.publish_from("strategy_engine", &strategy_topic(self.config.strategy_id, channel), message);
         }
     }
 
-    /// Get current timestamp in nanoseconds
+    /// Publish a named signal for other strategies and components
+    pub fn publish_signal(&self, name: &str, value: f64) -> Result<(), String> {
+        let signal = SignalData::new(name, value, self.current_time_ns());
+        self.data_engine
+            .lock()
+            .map_err(|_| "Data engine lock poisoned".to_string())?
+            .publish_signal(signal)
+    }
+
+    /// Get current timestamp in nanoseconds from the strategy's clock
     pub fn current_time_ns(&self) -> u64 {
-        SystemTime::now()
-            .duration_since(UNIX_EPOCH)
-            .expect("Time went backwards")
-            .as_nanos() as u64
+        self.clock.timestamp_ns()
+    }
+
+    // Update the benchmark comparison with the strategy's equity from its
+    // fills, open positions marked to market
+    fn update_benchmark(&mut self) {
+        if self.config.benchmark.is_none() {
+            return;
+        }
+        let Some(price) = self.benchmark_price.lock().ok().and_then(|price| *price) else {
+            return;
+        };
+        let equity = self.config.starting_equity + self.booked_pnl();
+        let benchmark = &mut self.benchmark;
+        if let Some(relative_drawdown) = benchmark.record(equity, price) {
+            self.metrics.alpha = benchmark.alpha();
+            self.metrics.beta = benchmark.beta();
+            self.metrics.information_ratio = benchmark.information_ratio();
+            self.metrics.max_relative_drawdown = self.metrics.max_relative_drawdown.max(relative_drawdown);
+        }
+    }
+
+    /// Update the peak of reported equity and `max_drawdown`
+    fn update_drawdown(&mut self) {
+        let equity = self.config.starting_equity + self.metrics.total_pnl;
+        let peak = &mut self.risk.metrics_peak_equity;
+        *peak = peak.max(self.config.starting_equity).max(equity);
+        let drawdown = if *peak > 0.0 { (*peak - equity) / *peak } else { 0.0 };
+        self.metrics.max_drawdown = self.metrics.max_drawdown.max(drawdown);
+    }
+
+    /// PnL booked from the strategy's fills, net of commission, with open
+    /// positions marked at the latest price the strategy received
+    pub fn booked_pnl(&self) -> f64 {
+        self.fills.pnl()
+    }
+
+    /// Update daily loss and drawdown from the booked PnL, returning the
+    /// configured limit breached, if any
+    pub fn check_risk_limits(&mut self) -> Option<String> {
+        const DAY_NS: u64 = 86_400_000_000_000;
+
+        let (max_daily_loss, max_drawdown) = (self.config.max_daily_loss, self.config.max_drawdown);
+        if max_daily_loss.is_none() && max_drawdown.is_none() {
+            return None;
+        }
+        let pnl = self.booked_pnl();
+        let day = self.current_time_ns() / DAY_NS;
+        let starting_equity = self.config.starting_equity;
+        let risk = &mut self.risk;
+        if risk.day != Some(day) {
+            risk.day = Some(day);
+            risk.day_start_pnl = risk.last_pnl;
+        }
+        risk.last_pnl = pnl;
+
+        let equity = starting_equity + pnl;
+        risk.peak_equity = risk.peak_equity.max(starting_equity).max(equity);
+        let drawdown = if risk.peak_equity > 0.0 { (risk.peak_equity - equity) / risk.peak_equity } else { 0.0 };
+        let daily_loss = risk.day_start_pnl - pnl;
+        if let Some(limit) = max_daily_loss.filter(|limit| daily_loss >= *limit) {
+            return Some(format!("Daily loss {:.2} reached limit {:.2}", daily_loss, limit));
+        }
+        if let Some(limit) = max_drawdown.filter(|limit| drawdown >= *limit) {
+            return Some(format!("Drawdown {:.2}% reached limit {:.2}%", drawdown * 100.0, limit * 100.0));
+        }
+        None
     }
 
     /// Update strategy state
@@ -147,11 +927,53 @@ impl StrategyContext {
         matches!(self.state, StrategyState::Running)
     }
 
-    /// Update metrics with a new trade
+    /// Whether the strategy is receiving warm-up history, during which
+    /// orders are rejected
+    pub fn is_warming_up(&self) -> bool {
+        self.warming_up
+    }
+
+    /// Book a fill of one of the strategy's orders into its metrics as a
+    /// trade realizing the PnL of the quantity it closes, net of commission.
+    /// With a benchmark, the return of the strategy's marked equity since
+    /// the previous fill is paired with the benchmark's.
+    fn book_fill(&mut self, fill: &Fill) {
+        match self.fills.book(fill) {
+            Some((instrument_id, pnl, quantity)) => {
+                self.record_trade(instrument_id, pnl, quantity);
+                self.update_benchmark();
+            }
+            None => tracing::warn!("Fill {} of unknown order {} not booked", fill.fill_id, fill.order_id),
+        }
+    }
+
+    /// Update metrics with a new trade; fills of the strategy's orders are
+    /// booked automatically, so this is for trades made elsewhere.
+    ///
+    /// Trades realizing PnL also feed the return statistics (Sharpe,
+    /// Sortino, streaks), with each return taken on equity before the trade.
     pub fn record_trade(&mut self, instrument_id: InstrumentId, pnl: f64, size: f64) {
+        let equity = self.config.starting_equity + self.metrics.total_pnl;
         self.metrics.total_trades += 1;
         self.metrics.total_pnl += pnl;
 
+        if pnl != 0.0 && equity > 0.0 {
+            let returns = &mut self.returns;
+            returns.record(pnl / equity);
+            if pnl > 0.0 {
+                returns.win_streak += 1;
+                returns.loss_streak = 0;
+            } else {
+                returns.loss_streak += 1;
+                returns.win_streak = 0;
+            }
+            self.metrics.max_consecutive_wins = self.metrics.max_consecutive_wins.max(returns.win_streak);
+            self.metrics.max_consecutive_losses = self.metrics.max_consecutive_losses.max(returns.loss_streak);
+            self.metrics.sharpe_ratio = returns.sharpe_ratio();
+            self.metrics.sortino_ratio = returns.sortino_ratio();
+        }
+        self.update_drawdown();
+
         if pnl > 0.0 {
             self.metrics.winning_trades += 1;
             self.metrics.gross_profit += pnl;
@@ -199,8 +1021,60 @@ pub trait Strategy: Send + Sync {
     /// Handle incoming bar data
     fn on_bar(&mut self, context: &mut StrategyContext, bar: &Bar) -> Result<(), String>;
 
-    /// Handle strategy timer events
-    fn on_timer(&mut self, context: &mut StrategyContext) -> Result<(), String>;
+    /// Handle funding rate updates for perpetual swaps
+    fn on_funding_rate(&mut self, _context: &mut StrategyContext, _update: &FundingRateUpdate) -> Result<(), String> {
+        Ok(())
+    }
+
+    /// Handle a change to one of the strategy's orders (submitted, accepted,
+    /// rejected, filled, cancelled or modified)
+    fn on_order_event(&mut self, _context: &mut StrategyContext, _event: &OrderEvent) -> Result<(), String> {
+        Ok(())
+    }
+
+    /// Handle a fill of one of the strategy's orders, after `on_order_event`
+    fn on_fill(&mut self, _context: &mut StrategyContext, _fill: &Fill) -> Result<(), String> {
+        Ok(())
+    }
+
+    /// Handle open interest updates
+    fn on_open_interest(&mut self, _context: &mut StrategyContext, _update: &OpenInterestUpdate) -> Result<(), String> {
+        Ok(())
+    }
+
+    /// Handle mark price updates
+    fn on_mark_price(&mut self, _context: &mut StrategyContext, _update: &MarkPriceUpdate) -> Result<(), String> {
+        Ok(())
+    }
+
+    /// Handle index price updates
+    fn on_index_price(&mut self, _context: &mut StrategyContext, _update: &IndexPriceUpdate) -> Result<(), String> {
+        Ok(())
+    }
+
+    /// Handle signals this strategy subscribed to
+    fn on_signal(&mut self, _context: &mut StrategyContext, _signal: &SignalData) -> Result<(), String> {
+        Ok(())
+    }
+
+    /// Handle a timer set with `StrategyContext::set_timer`
+    fn on_timer(&mut self, context: &mut StrategyContext, name: &str) -> Result<(), String>;
+
+    /// Called when the strategy is paused; no events are delivered until it resumes
+    fn on_pause(&mut self, _context: &mut StrategyContext) -> Result<(), String> {
+        Ok(())
+    }
+
+    /// Called when a paused strategy resumes
+    fn on_resume(&mut self, _context: &mut StrategyContext) -> Result<(), String> {
+        Ok(())
+    }
+
+    /// Called after `StrategyEngine::update_parameters` changed the named
+    /// parameters in `context.config.parameters`
+    fn on_parameters_changed(&mut self, _context: &mut StrategyContext, _changed: &[String]) -> Result<(), String> {
+        Ok(())
+    }
 
     /// Stop the strategy
     fn on_stop(&mut self, context: &mut StrategyContext) -> Result<(), String>;
@@ -212,33 +1086,678 @@ pub trait Strategy: Send + Sync {
     fn version(&self) -> &str {
         "1.0.0"
     }
+
+    /// Serialize strategy-specific state (indicator values, flags) so a
+    /// restarted node can resume; empty when the strategy keeps none
+    fn save_state(&self) -> Result<Vec<u8>, String> {
+        Ok(Vec::new())
+    }
+
+    /// Restore state produced by `save_state`; called before `on_start`
+    fn load_state(&mut self, _state: &[u8]) -> Result<(), String> {
+        Ok(())
+    }
 }
 
-/// Strategy engine that manages multiple strategies
-pub struct StrategyEngine {
-    /// Registered strategies
-    strategies: HashMap<StrategyId, (Box<dyn Strategy>, StrategyContext)>,
-    /// Reference to data engine
-    data_engine: Arc<Mutex<DataEngine>>,
-    /// Engine state
-    is_running: bool,
-    /// Engine statistics
-    total_strategies: usize,
-    active_strategies: usize,
+/// Event delivered to a strategy
+#[derive(Debug, Clone)]
+pub(crate) enum StrategyEvent {
+    Trade(TradeTick),
+    Quote(QuoteTick),
+    Bar(Bar),
+    FundingRate(FundingRateUpdate),
+    OpenInterest(OpenInterestUpdate),
+    MarkPrice(MarkPriceUpdate),
+    IndexPrice(IndexPriceUpdate),
+    Signal(SignalData),
+    Timer(TimeEvent),
+    Order(OrderEvent),
+    /// Acknowledged once every earlier event has been handled
+    Barrier(mpsc::Sender<()>),
 }
 
-impl StrategyEngine {
-    /// Create a new strategy engine
-    pub fn new(data_engine: Arc<Mutex<DataEngine>>) -> Self {
-        Self {
-            strategies: HashMap::new(),
-            data_engine,
-            is_running: false,
-            total_strategies: 0,
-            active_strategies: 0,
+impl StrategyEvent {
+    fn logged(&self) -> Option<LoggedEvent> {
+        Some(match self {
+            StrategyEvent::Trade(tick) => LoggedEvent::Trade(tick.clone()),
+            StrategyEvent::Quote(tick) => LoggedEvent::Quote(tick.clone()),
+            StrategyEvent::Bar(bar) => LoggedEvent::Bar(bar.clone()),
+            StrategyEvent::FundingRate(update) => LoggedEvent::FundingRate(update.clone()),
+            StrategyEvent::OpenInterest(update) => LoggedEvent::OpenInterest(update.clone()),
+            StrategyEvent::MarkPrice(update) => LoggedEvent::MarkPrice(update.clone()),
+            StrategyEvent::IndexPrice(update) => LoggedEvent::IndexPrice(update.clone()),
+            StrategyEvent::Signal(signal) => LoggedEvent::Signal(signal.clone()),
+            StrategyEvent::Timer(event) => LoggedEvent::Timer(event.clone()),
+            StrategyEvent::Order(event) => LoggedEvent::Order(event.clone()),
+            StrategyEvent::Barrier(_) => return None,
+        })
+    }
+}
+
+impl StrategyEvent {
+    /// Market data, which a strategy falling behind in actor mode may miss
+    fn is_market_data(&self) -> bool {
+        matches!(
+            self,
+            StrategyEvent::Trade(_)
+                | StrategyEvent::Quote(_)
+                | StrategyEvent::Bar(_)
+                | StrategyEvent::FundingRate(_)
+                | StrategyEvent::OpenInterest(_)
+                | StrategyEvent::MarkPrice(_)
+                | StrategyEvent::IndexPrice(_)
+        )
+    }
+
+    /// Market data and signals, which only reach a strategy in session
+    fn is_session_bound(&self) -> bool {
+        !matches!(self, StrategyEvent::Timer(_) | StrategyEvent::Order(_) | StrategyEvent::Barrier(_))
+    }
+
+    /// Price of the instrument the event is about, for benchmarks
+    fn price(&self) -> Option<f64> {
+        match self {
+            StrategyEvent::Trade(tick) => Some(tick.price),
+            StrategyEvent::Quote(tick) => Some((tick.bid_price + tick.ask_price) / 2.0),
+            StrategyEvent::Bar(bar) => Some(bar.close),
+            _ => None,
         }
     }
 
+    fn instrument_id(&self) -> Option<InstrumentId> {
+        match self {
+            StrategyEvent::Trade(tick) => Some(tick.instrument_id),
+            StrategyEvent::Quote(tick) => Some(tick.instrument_id),
+            StrategyEvent::Bar(bar) => Some(bar.bar_type.instrument_id),
+            StrategyEvent::FundingRate(update) => Some(update.instrument_id),
+            StrategyEvent::OpenInterest(update) => Some(update.instrument_id),
+            StrategyEvent::MarkPrice(update) => Some(update.instrument_id),
+            StrategyEvent::IndexPrice(update) => Some(update.instrument_id),
+            StrategyEvent::Order(OrderEvent::OrderSubmitted { order, .. }) => Some(order.instrument_id),
+            StrategyEvent::Order(OrderEvent::OrderModified { modified_order, .. }) => Some(modified_order.instrument_id),
+            _ => None,
+        }
+    }
+
+    fn from_data(data: MarketData) -> Option<Self> {
+        Some(match data {
+            MarketData::Trade(tick) => StrategyEvent::Trade(tick),
+            MarketData::Quote(tick) => StrategyEvent::Quote(tick),
+            MarketData::BookDeltas(_) => return None,
+            MarketData::FundingRate(update) => StrategyEvent::FundingRate(update),
+            MarketData::OpenInterest(update) => StrategyEvent::OpenInterest(update),
+            MarketData::MarkPrice(update) => StrategyEvent::MarkPrice(update),
+            MarketData::IndexPrice(update) => StrategyEvent::IndexPrice(update),
+        })
+    }
+}
+
+impl From<LoggedEvent> for StrategyEvent {
+    fn from(event: LoggedEvent) -> Self {
+        match event {
+            LoggedEvent::Trade(tick) => StrategyEvent::Trade(tick),
+            LoggedEvent::Quote(tick) => StrategyEvent::Quote(tick),
+            LoggedEvent::Bar(bar) => StrategyEvent::Bar(bar),
+            LoggedEvent::FundingRate(update) => StrategyEvent::FundingRate(update),
+            LoggedEvent::OpenInterest(update) => StrategyEvent::OpenInterest(update),
+            LoggedEvent::MarkPrice(update) => StrategyEvent::MarkPrice(update),
+            LoggedEvent::IndexPrice(update) => StrategyEvent::IndexPrice(update),
+            LoggedEvent::Signal(signal) => StrategyEvent::Signal(signal),
+            LoggedEvent::Timer(event) => StrategyEvent::Timer(event),
+            LoggedEvent::Order(event) => StrategyEvent::Order(event),
+        }
+    }
+}
+
+/// A strategy together with its context
+pub(crate) struct StrategyCell {
+    pub(crate) strategy: Box<dyn Strategy>,
+    pub(crate) context: StrategyContext,
+}
+
+impl StrategyCell {
+    /// Span tagging log lines with the strategy and, for `event`, its
+    /// instrument and order, filtering them by the strategy's log level
+    fn span(&self, event: Option<&StrategyEvent>) -> tracing::Span {
+        let config = &self.context.config;
+        let instrument = event.and_then(StrategyEvent::instrument_id);
+        let span = logging::strategy_span(config.strategy_id, &config.name, instrument, config.log_level);
+        if let Some(StrategyEvent::Order(order_event)) = event {
+            span.record("order_id", tracing::field::display(order_event.order_id()));
+        }
+        span
+    }
+
+    // Move to `Stopped`, run `on_stop` and cancel the strategy's timers
+    fn halt(&mut self) -> Result<(), String> {
+        let _span = self.span(None).entered();
+        let StrategyCell { strategy, context } = self;
+        context.set_state(StrategyState::Stopped);
+        strategy.on_stop(context)?;
+        context.cancel_all_timers();
+        Ok(())
+    }
+
+    fn snapshot(&self) -> Result<StrategySnapshot, String> {
+        let context = &self.context;
+        Ok(StrategySnapshot {
+            strategy_id: context.config.strategy_id,
+            strategy_name: self.strategy.name().to_string(),
+            strategy_version: self.strategy.version().to_string(),
+            saved_at: context.current_time_ns(),
+            metrics: context.metrics.clone(),
+            state: self.strategy.save_state()?,
+            risk: context.risk.clone(),
+            returns: context.returns.clone(),
+            benchmark: context.benchmark.clone(),
+            fills: context.fills.clone(),
+        })
+    }
+
+    fn restore(&mut self, snapshot: StrategySnapshot) -> Result<(), String> {
+        if snapshot.strategy_id != self.context.config.strategy_id {
+            return Err(format!(
+                "State belongs to strategy {} not {}",
+                snapshot.strategy_id, self.context.config.strategy_id
+            ));
+        }
+        self.strategy.load_state(&snapshot.state)?;
+        let context = &mut self.context;
+        context.metrics = snapshot.metrics;
+        context.risk = snapshot.risk;
+        context.returns = snapshot.returns;
+        context.benchmark = snapshot.benchmark;
+        context.fills = snapshot.fills;
+        Ok(())
+    }
+
+    fn handle(&mut self, event: &StrategyEvent) -> Result<(), String> {
+        if let StrategyEvent::Barrier(ack) = event {
+            let _ = ack.send(());
+            return Ok(());
+        }
+        if !self.context.is_active() {
+            return Ok(());
+        }
+
+        let (strategy, context) = (&mut self.strategy, &mut self.context);
+        // Skip firings queued before the timer was cancelled
+        if let StrategyEvent::Timer(timer) = event {
+            if !context.timers.contains(&timer.name) {
+                return Ok(());
+            }
+        }
+        if let (Some(instrument_id), Some(price)) = (event.instrument_id(), event.price()) {
+            context.fills.mark(instrument_id, price);
+        }
+        if context.decision_log.is_some() {
+            if let Some(logged) = event.logged() {
+                context.record_decision(DecisionEntry::Event(logged));
+            }
+        }
+
+        match event {
+            StrategyEvent::Trade(tick) => strategy.on_trade_tick(context, tick),
+            StrategyEvent::Quote(tick) => strategy.on_quote_tick(context, tick),
+            StrategyEvent::Bar(bar) => strategy.on_bar(context, bar),
+            StrategyEvent::FundingRate(update) => strategy.on_funding_rate(context, update),
+            StrategyEvent::OpenInterest(update) => strategy.on_open_interest(context, update),
+            StrategyEvent::MarkPrice(update) => strategy.on_mark_price(context, update),
+            StrategyEvent::IndexPrice(update) => strategy.on_index_price(context, update),
+            StrategyEvent::Signal(signal) => strategy.on_signal(context, signal),
+            StrategyEvent::Timer(timer) => strategy.on_timer(context, &timer.name),
+            StrategyEvent::Order(event) => {
+                context.fills.track(event);
+                if let OrderEvent::OrderFilled { fill, .. } = event {
+                    context.book_fill(fill);
+                }
+                strategy.on_order_event(context, event)?;
+                match event {
+                    OrderEvent::OrderFilled { fill, .. } => strategy.on_fill(context, fill),
+                    _ => Ok(()),
+                }
+            }
+            StrategyEvent::Barrier(_) => Ok(()),
+        }
+    }
+
+    /// Handle an event, isolating the strategy if it fails and stopping it
+    /// if it breaches a risk limit
+    pub(crate) fn process(&mut self, event: &StrategyEvent, message_bus: Option<&MessageBus>) {
+        let _span = self.span(Some(event)).entered();
+        if let Err(e) = self.handle(event) {
+            self.fail(e, message_bus);
+            return;
+        }
+        if self.context.is_active() {
+            if let Some(reason) = self.context.check_risk_limits() {
+                self.breach(reason, message_bus);
+            }
+        }
+    }
+
+    /// Deliver history with order submission disabled, stopping early if
+    /// the strategy fails
+    fn warm_up(&mut self, events: Vec<StrategyEvent>, message_bus: Option<&MessageBus>) -> usize {
+        self.context.warming_up = true;
+        let mut delivered = 0;
+        for event in &events {
+            if !self.context.is_active() {
+                break;
+            }
+            self.process(event, message_bus);
+            delivered += 1;
+        }
+        self.context.warming_up = false;
+        delivered
+    }
+
+    /// Stop the strategy after a risk limit breach, flattening it first when
+    /// configured
+    fn breach(&mut self, reason: String, message_bus: Option<&MessageBus>) {
+        let context = &mut self.context;
+        tracing::error!("Strategy {} breached a risk limit: {}", context.config.name, reason);
+
+        if context.config.flatten_on_risk_breach {
+            context.flatten_positions();
+        }
+        // Blocks further orders and events
+        context.set_state(StrategyState::RiskBreached);
+        context.cancel_all_timers();
+        Self::alert(context, "strategies.risk_breached", reason, message_bus);
+    }
+
+    /// Move the strategy to `Error` so it receives no further events, and
+    /// raise an alert
+    fn fail(&mut self, reason: String, message_bus: Option<&MessageBus>) {
+        let context = &mut self.context;
+        context.set_state(StrategyState::Error);
+        tracing::error!("Strategy {} failed and was isolated: {}", context.config.name, reason);
+        Self::alert(context, "strategies.error", reason, message_bus);
+    }
+
+    // Publish an alert on `topic` and in the strategy's namespace
+    fn alert(context: &StrategyContext, topic: &str, reason: String, message_bus: Option<&MessageBus>) {
+        let alert = StrategyAlert {
+            strategy_id: context.config.strategy_id,
+            strategy_name: context.config.name.clone(),
+            reason,
+            ts: context.current_time_ns(),
+        };
+        if let Some(message_bus) = message_bus {
+            message_bus.publish_from("strategy_engine", topic, &alert);
+        }
+        context.publish(ALERTS_CHANNEL, &alert);
+    }
+}
+
+/// Queue of a strategy's worker, with the message chain each event belongs to
+type WorkerQueue = mpsc::SyncSender<(StrategyEvent, Option<UUID7>)>;
+
+/// Thread running one strategy in actor mode
+struct StrategyWorker {
+    sender: WorkerQueue,
+    /// The strategy's timer route, given a sender while the worker runs
+    timer_queue: Arc<Mutex<Option<WorkerQueue>>>,
+    /// Set to stop the worker once its current event is handled, halting
+    /// the strategy itself
+    shutdown: Arc<AtomicBool>,
+    handle: thread::JoinHandle<()>,
+}
+
+impl StrategyWorker {
+    fn spawn(
+        strategy_id: StrategyId,
+        cell: Arc<Mutex<StrategyCell>>,
+        capacity: usize,
+        message_bus: Option<Arc<MessageBus>>,
+        timer_queue: Arc<Mutex<Option<WorkerQueue>>>,
+    ) -> Result<Self, String> {
+        let (sender, receiver) = mpsc::sync_channel::<(StrategyEvent, Option<UUID7>)>(capacity);
+        let shutdown = Arc::new(AtomicBool::new(false));
+        let stopping = Arc::clone(&shutdown);
+        let handle = thread::Builder::new()
+            .name(format!("strategy-{}", strategy_id))
+            .spawn(move || {
+                while let Ok((event, correlation_id)) = receiver.recv() {
+                    let Ok(mut cell) = cell.lock() else {
+                        tracing::warn!("Strategy {} lock poisoned, stopping worker", strategy_id);
+                        return;
+                    };
+                    if stopping.load(Ordering::Acquire) {
+                        break;
+                    }
+                    let _correlation = match correlation_id {
+                        Some(correlation_id) => correlate("strategy", correlation_id),
+                        None => start_chain("strategy"),
+                    };
+                    cell.process(&event, message_bus.as_deref());
+                }
+                // Claim the flag, unless the engine gave up waiting first and
+                // left halting the strategy to this thread
+                if stopping.swap(true, Ordering::AcqRel) {
+                    if let Err(e) = cell.lock().map_err(|_| "Strategy lock poisoned".to_string()).and_then(|mut cell| cell.halt()) {
+                        tracing::warn!("Strategy {} failed to stop: {}", strategy_id, e);
+                    }
+                }
+            })
+            .map_err(|e| format!("Failed to spawn worker for strategy {}: {}", strategy_id, e))?;
+
+        *timer_queue.lock().map_err(|_| "Timer queue lock poisoned".to_string())? = Some(sender.clone());
+        Ok(Self { sender, timer_queue, shutdown, handle })
+    }
+
+    // Let the worker handle the events already queued for up to `timeout`,
+    // returning whether it finished. One still busy is told to stop after
+    // its current event, skipping the rest, and left to halt its strategy.
+    fn shut_down(self, strategy_id: StrategyId, timeout: Duration) -> bool {
+        let Self { sender, timer_queue, shutdown, handle } = self;
+        if let Ok(mut queue) = timer_queue.lock() {
+            queue.take();
+        }
+        drop(sender);
+        let deadline = Instant::now() + timeout;
+        while !handle.is_finished() && Instant::now() < deadline {
+            thread::sleep(Duration::from_millis(1));
+        }
+        if !handle.is_finished() && !shutdown.swap(true, Ordering::AcqRel) {
+            tracing::warn!("Strategy {} worker did not finish its queued events within {:?}, abandoning them", strategy_id, timeout);
+            return false;
+        }
+        if handle.join().is_err() {
+            tracing::warn!("Strategy {} worker panicked", strategy_id);
+        }
+        true
+    }
+}
+
+/// Strategies an event is delivered to
+enum Route<'a> {
+    /// Strategies trading an instrument
+    Instrument(InstrumentId),
+    /// Subscribers of a signal
+    Signal(&'a str),
+    Strategy(StrategyId),
+    All,
+}
+
+/// A registered strategy
+struct StrategySlot {
+    cell: Arc<Mutex<StrategyCell>>,
+    /// History queued with `add_warmup_data` for the next start
+    warmup_data: Vec<MarketData>,
+    /// Events of the strategy's orders from the connected execution engine
+    order_events: Option<tokio::sync::mpsc::UnboundedReceiver<Arc<OrderEvent>>>,
+    /// Time events of the strategy's timers while it has no worker
+    timer_events: tokio::sync::mpsc::UnboundedReceiver<TimeEvent>,
+    /// Worker queue the strategy's timer events go to in actor mode
+    timer_queue: Arc<Mutex<Option<WorkerQueue>>>,
+    /// Present while running in actor mode
+    worker: Option<StrategyWorker>,
+    /// Market data dropped because the strategy's channel was full
+    dropped_events: u64,
+    /// Trading hours from the strategy's config, checked without locking it
+    session: Option<SessionConfig>,
+    /// Whether the strategy's session is open (always for strategies without one)
+    in_session: bool,
+}
+
+impl StrategySlot {
+    fn lock(&self) -> Result<MutexGuard<'_, StrategyCell>, String> {
+        self.cell.lock().map_err(|_| "Strategy lock poisoned".to_string())
+    }
+}
+
+/// Strategy engine that manages multiple strategies.
+///
+/// By default events are delivered on the caller's thread, one strategy
+/// after another. With `enable_actors` each strategy runs on its own thread
+/// fed by a bounded channel, so a slow strategy drops its own market data
+/// rather than delaying delivery to the others. Timer, order and signal
+/// events are never dropped: delivering them waits for room in the channel.
+pub struct StrategyEngine {
+    /// Registered strategies
+    strategies: HashMap<StrategyId, StrategySlot>,
+    /// Strategy IDs in registration order, the order events are delivered in
+    strategy_order: Vec<StrategyId>,
+    /// Instrument -> strategies trading it, built at registration so routing
+    /// never scans every strategy or waits on a busy one
+    instrument_index: HashMap<InstrumentId, Vec<StrategyId>>,
+    /// Reference to data engine
+    data_engine: Arc<Mutex<DataEngine>>,
+    /// Engine state
+    is_running: bool,
+    /// Per-strategy channel capacity when running strategies on their own threads
+    actor_capacity: Option<usize>,
+    /// Longest `stop` waits for a strategy thread to drain its channel
+    worker_stop_timeout: Duration,
+    /// Signal name -> subscribed strategies
+    signal_subscriptions: HashMap<String, Vec<StrategyId>>,
+    /// Latest prices of the instruments strategies are benchmarked against
+    benchmark_prices: HashMap<InstrumentId, BenchmarkPrice>,
+    /// Channel to the execution engine handed to every strategy context
+    order_commands: Option<OrderCommandSender>,
+    /// Execution engine the strategies trade through, for working order lookups
+    execution_engine: Option<Arc<ExecutionEngine>>,
+    /// Clock handed to every strategy context (a `LiveClock` unless set)
+    clock: Arc<dyn Clock>,
+    /// Bus strategy alerts and namespaces are published on
+    message_bus: Option<Arc<MessageBus>>,
+    /// Control messages sent to every strategy namespace on the bus
+    control: Option<Subscription>,
+    /// Composite and synthetic quotes the data engine publishes on the bus
+    derived_quotes: Option<Subscription>,
+    /// Log strategies record their decisions to
+    decision_log: Option<Arc<DecisionLog>>,
+    /// Where strategy state is loaded from on start and saved to on stop
+    state_directory: Option<PathBuf>,
+    /// Clock time between saves of strategy state while running
+    state_save_interval: Option<Duration>,
+    /// Clock time state was last saved at, or the engine started
+    last_state_save: u64,
+    /// Session closes of strategies flattening at the close, fired by the clock
+    session_closes: tokio::sync::mpsc::UnboundedReceiver<TimeEvent>,
+    session_close_sender: tokio::sync::mpsc::UnboundedSender<TimeEvent>,
+    /// Engine statistics
+    total_strategies: usize,
+}
+
+impl StrategyEngine {
+    /// Create a new strategy engine
+    pub fn new(data_engine: Arc<Mutex<DataEngine>>) -> Self {
+        let (session_close_sender, session_closes) = tokio::sync::mpsc::unbounded_channel();
+        Self {
+            strategies: HashMap::new(),
+            strategy_order: Vec::new(),
+            instrument_index: HashMap::new(),
+            data_engine,
+            is_running: false,
+            actor_capacity: None,
+            worker_stop_timeout: DEFAULT_WORKER_STOP_TIMEOUT,
+            signal_subscriptions: HashMap::new(),
+            benchmark_prices: HashMap::new(),
+            order_commands: None,
+            execution_engine: None,
+            clock: Arc::new(LiveClock::new()),
+            message_bus: None,
+            control: None,
+            derived_quotes: None,
+            decision_log: None,
+            state_directory: None,
+            state_save_interval: None,
+            last_state_save: 0,
+            session_closes,
+            session_close_sender,
+            total_strategies: 0,
+        }
+    }
+
+    /// Run each strategy on its own thread with a bounded event channel of
+    /// `channel_capacity` events; takes effect on the next `start`
+    pub fn enable_actors(&mut self, channel_capacity: usize) -> Result<(), String> {
+        if self.is_running {
+            return Err("Cannot change dispatch mode while running".to_string());
+        }
+        if channel_capacity == 0 {
+            return Err("Strategy channel capacity must be positive".to_string());
+        }
+        self.actor_capacity = Some(channel_capacity);
+        Ok(())
+    }
+
+    /// Wait at most `timeout` on `stop` for a strategy thread to handle the
+    /// events queued for it. A strategy still busy then skips the rest, and
+    /// is halted and its `on_stop` run on its own thread once its current
+    /// event is handled; its state is not saved.
+    pub fn set_worker_stop_timeout(&mut self, timeout: Duration) {
+        self.worker_stop_timeout = timeout;
+    }
+
+    /// Publish strategy alerts and every strategy's `strategy.{id}.*`
+    /// channels on `message_bus`, and subscribe to their control channels
+    /// and to composite and synthetic quotes, applied before each event and
+    /// by `process_control_messages` and `process_derived_quotes`. Actor
+    /// workers already running keep publishing alerts on the previous bus.
+    pub fn set_message_bus(&mut self, message_bus: Arc<MessageBus>) -> Result<(), String> {
+        for slot in self.strategies.values() {
+            slot.lock()?.context.message_bus = Some(Arc::clone(&message_bus));
+        }
+        self.control = Some(message_bus.subscribe_tracked(&format!("strategy.*.{}", CONTROL_CHANNEL)));
+        self.derived_quotes = Some(message_bus.subscribe_tracked(&format!("{}*", DERIVED_QUOTE_TOPIC_PREFIX)));
+        self.message_bus = Some(message_bus);
+        Ok(())
+    }
+
+    /// Load strategy state from `directory` on every `start` and save it
+    /// there on every `stop`
+    pub fn set_state_directory(&mut self, directory: impl Into<PathBuf>) {
+        self.state_directory = Some(directory.into());
+    }
+
+    /// Also save state to the state directory whenever `interval` of clock
+    /// time passed since the last save, checked as events are dispatched,
+    /// so a crash loses no more than that
+    pub fn set_state_save_interval(&mut self, interval: Duration) {
+        self.state_save_interval = Some(interval);
+    }
+
+    // Save strategy state if the save interval elapsed
+    fn save_state_if_due(&mut self) {
+        let (Some(directory), Some(interval)) = (&self.state_directory, self.state_save_interval) else {
+            return;
+        };
+        let now = self.clock.timestamp_ns();
+        if now.saturating_sub(self.last_state_save) < interval.as_nanos() as u64 {
+            return;
+        }
+        self.last_state_save = now;
+        if let Err(e) = self.save_states(directory, &HashSet::new()) {
+            tracing::warn!("Failed to save strategy state: {}", e);
+        }
+    }
+
+    /// Write the state of every strategy to `directory`, returning how many
+    /// were saved
+    pub fn save_state(&self, directory: impl AsRef<Path>) -> Result<usize, String> {
+        self.save_states(directory.as_ref(), &HashSet::new())
+    }
+
+    // Write the state of every strategy but those `skipped`
+    fn save_states(&self, directory: &Path, skipped: &HashSet<StrategyId>) -> Result<usize, String> {
+        fs::create_dir_all(directory)
+            .map_err(|e| format!("Failed to create state directory {}: {}", directory.display(), e))?;
+
+        let mut saved = 0;
+        for (strategy_id, slot) in self.strategies.iter().filter(|(strategy_id, _)| !skipped.contains(strategy_id)) {
+            let bytes = slot.lock()?.snapshot()?.to_bytes()?;
+            // Write then rename so a crash never leaves a truncated file
+            let path = Self::state_path(directory, strategy_id);
+            let partial = path.with_extension("state.tmp");
+            fs::write(&partial, bytes)
+                .and_then(|_| fs::rename(&partial, &path))
+                .map_err(|e| format!("Failed to save state to {}: {}", path.display(), e))?;
+            saved += 1;
+        }
+        Ok(saved)
+    }
+
+    /// Restore strategies with a saved state in `directory`, returning how
+    /// many were restored (strategies without one start fresh)
+    pub fn load_state(&mut self, directory: impl AsRef<Path>) -> Result<usize, String> {
+        if self.is_running {
+            return Err("Cannot load state while running".to_string());
+        }
+
+        let mut restored = 0;
+        for (strategy_id, slot) in &self.strategies {
+            let path = Self::state_path(directory.as_ref(), strategy_id);
+            let bytes = match fs::read(&path) {
+                Ok(bytes) => bytes,
+                Err(e) if e.kind() == std::io::ErrorKind::NotFound => continue,
+                Err(e) => return Err(format!("Failed to read state from {}: {}", path.display(), e)),
+            };
+            slot.lock()?.restore(StrategySnapshot::from_bytes(&bytes)?)?;
+            restored += 1;
+        }
+        Ok(restored)
+    }
+
+    fn state_path(directory: &Path, strategy_id: &StrategyId) -> PathBuf {
+        directory.join(format!("strategy-{}.state", strategy_id))
+    }
+
+    /// Queue history (e.g. read from tick files) to deliver to a strategy,
+    /// with order submission disabled, when the engine next starts. It is
+    /// merged in time order with the cached history in the strategy's
+    /// `WarmupConfig`.
+    pub fn add_warmup_data(&mut self, strategy_id: StrategyId, data: Vec<MarketData>) -> Result<(), String> {
+        let slot = self.strategies
+            .get_mut(&strategy_id)
+            .ok_or_else(|| format!("Strategy with ID {:?} not found", strategy_id))?;
+        slot.warmup_data.extend(data);
+        Ok(())
+    }
+
+    // Queued and cached warm-up history in `ts_init` order
+    fn warmup_events(
+        data_engine: &Mutex<DataEngine>,
+        config: &StrategyConfig,
+        queued: Vec<MarketData>,
+    ) -> Result<Vec<StrategyEvent>, String> {
+        let warmup = &config.warmup;
+        let mut history: Vec<(u64, StrategyEvent)> = queued
+            .into_iter()
+            .filter_map(|data| {
+                let ts = data.ts_init();
+                StrategyEvent::from_data(data).map(|event| (ts, event))
+            })
+            .collect();
+
+        if !warmup.bars.is_empty() || warmup.trade_ticks > 0 || warmup.quote_ticks > 0 {
+            let cache = data_engine.lock().map_err(|_| "Data engine lock poisoned".to_string())?.cache();
+            for (bar_type, count) in &warmup.bars {
+                for bar in cache.get_bars(bar_type, None, None, Some(*count)) {
+                    history.push((bar.ts_init, StrategyEvent::Bar(bar)));
+                }
+            }
+            for instrument_id in &config.instruments {
+                if warmup.trade_ticks > 0 {
+                    for tick in cache.get_trades(instrument_id, Some(warmup.trade_ticks)) {
+                        history.push((tick.ts_init, StrategyEvent::Trade(tick)));
+                    }
+                }
+                if warmup.quote_ticks > 0 {
+                    for tick in cache.get_quotes(instrument_id, Some(warmup.quote_ticks)) {
+                        history.push((tick.ts_init, StrategyEvent::Quote(tick)));
+                    }
+                }
+            }
+        }
+
+        history.sort_by_key(|(ts, _)| *ts);
+        Ok(history.into_iter().map(|(_, event)| event).collect())
+    }
+
     /// Register a new strategy
     pub fn add_strategy(&mut self, strategy: Box<dyn Strategy>, config: StrategyConfig) -> Result<(), String> {
         let strategy_id = config.strategy_id;
@@ -246,124 +1765,775 @@ impl StrategyEngine {
         if self.strategies.contains_key(&strategy_id) {
             return Err(format!("Strategy with ID {:?} already exists", strategy_id));
         }
+        if self.is_running {
+            return Err("Cannot add strategies while running".to_string());
+        }
 
-        let context = StrategyContext::new(config, Arc::clone(&self.data_engine));
-        self.strategies.insert(strategy_id, (strategy, context));
+        for instrument_id in config.instruments.iter().copied().collect::<HashSet<_>>() {
+            self.instrument_index.entry(instrument_id).or_default().push(strategy_id);
+        }
+        let session = config.session.clone();
+        let mut context = StrategyContext::new(config, Arc::clone(&self.data_engine), Arc::clone(&self.clock));
+        context.order_commands = self.order_commands.clone();
+        context.decision_log = self.decision_log.clone();
+        context.message_bus = self.message_bus.clone();
+        let (timer_sender, timer_events) = tokio::sync::mpsc::unbounded_channel();
+        let timer_queue = Arc::default();
+        context.timer_events = Self::timer_route(strategy_id, timer_sender, Arc::clone(&timer_queue));
+        if let Some(benchmark) = context.config.benchmark {
+            context.benchmark_price = Arc::clone(self.benchmark_prices.entry(benchmark).or_default());
+        }
+        self.strategies.insert(strategy_id, StrategySlot {
+            cell: Arc::new(Mutex::new(StrategyCell { strategy, context })),
+            warmup_data: Vec::new(),
+            order_events: self.execution_engine
+                .as_ref()
+                .map(|engine| engine.subscribe_strategy_events(strategy_id)),
+            timer_events,
+            timer_queue,
+            worker: None,
+            dropped_events: 0,
+            session,
+            in_session: true,
+        });
+        self.strategy_order.push(strategy_id);
         self.total_strategies += 1;
 
         Ok(())
     }
 
+    /// Use `clock` for strategy time and timers (a `LiveClock` live, a `TestClock`
+    /// in backtests and replay), and for the data and execution engines'
+    /// timestamps so the whole system advances with it
+    pub fn set_clock(&mut self, clock: Arc<dyn Clock>) -> Result<(), String> {
+        self.data_engine
+            .lock()
+            .map_err(|_| "Data engine lock poisoned".to_string())?
+            .set_clock(Arc::clone(&clock));
+        if let Some(execution_engine) = &self.execution_engine {
+            execution_engine.set_clock(Arc::clone(&clock));
+        }
+        for slot in self.strategies.values() {
+            let mut cell = slot.lock()?;
+            cell.context.cancel_all_timers();
+            cell.context.clock = Arc::clone(&clock);
+        }
+        self.clock = clock;
+        Ok(())
+    }
+
+    /// Route strategy order commands to `sender`
+    pub fn set_order_commands(&mut self, sender: OrderCommandSender) -> Result<(), String> {
+        for slot in self.strategies.values() {
+            slot.lock()?.context.order_commands = Some(sender.clone());
+        }
+        self.order_commands = Some(sender);
+        Ok(())
+    }
+
+    /// Record every event delivered to a strategy and every order command
+    /// it emits to `log`
+    pub fn set_decision_log(&mut self, log: Arc<DecisionLog>) -> Result<(), String> {
+        for slot in self.strategies.values() {
+            slot.lock()?.context.decision_log = Some(Arc::clone(&log));
+        }
+        self.decision_log = Some(log);
+        Ok(())
+    }
+
+    /// Let strategies trade through `execution_engine`, returning the task
+    /// that executes their order commands. Events of their orders are
+    /// delivered by `process_order_events`.
+    pub fn connect_execution_engine(
+        &mut self,
+        execution_engine: Arc<ExecutionEngine>,
+    ) -> Result<tokio::task::JoinHandle<()>, String> {
+        let (sender, receiver) = tokio::sync::mpsc::unbounded_channel();
+        self.set_order_commands(sender)?;
+        for (strategy_id, slot) in self.strategies.iter_mut() {
+            slot.order_events = Some(execution_engine.subscribe_strategy_events(*strategy_id));
+        }
+        execution_engine.set_clock(Arc::clone(&self.clock));
+        self.execution_engine = Some(Arc::clone(&execution_engine));
+        Ok(ExecutionEngine::spawn(execution_engine, receiver))
+    }
+
+    // Sender of a strategy's timer events, renamed back to the strategy's
+    // own names. They go straight to its worker's queue in actor mode, and
+    // otherwise wait in `pending` for the engine to deliver them.
+    fn timer_route(
+        strategy_id: StrategyId,
+        pending: tokio::sync::mpsc::UnboundedSender<TimeEvent>,
+        worker: Arc<Mutex<Option<WorkerQueue>>>,
+    ) -> TimeEventSender {
+        let prefix = format!("{}.", strategy_id);
+        TimeEventSender::Handler(Arc::new(move |mut event: TimeEvent| {
+            if let Some(name) = event.name.strip_prefix(&prefix) {
+                event.name = name.to_string();
+            }
+            match worker.lock().ok().and_then(|queue| queue.clone()) {
+                Some(queue) => {
+                    if queue.send((StrategyEvent::Timer(event), None)).is_err() {
+                        tracing::warn!("Strategy {} worker has stopped", strategy_id);
+                    }
+                }
+                None => {
+                    let _ = pending.send(event);
+                }
+            }
+        }))
+    }
+
+    /// Unregister a stopped engine's strategy, closing the subscriptions the
+    /// engine held for it; other components' subscriptions to its namespace
+    /// stay open
+    pub fn remove_strategy(&mut self, strategy_id: StrategyId) -> Result<(), String> {
+        if self.is_running {
+            return Err("Cannot remove strategies while running".to_string());
+        }
+        let slot = self.strategies
+            .remove(&strategy_id)
+            .ok_or_else(|| format!("Strategy with ID {:?} not found", strategy_id))?;
+        slot.lock()?.context.cancel_all_timers();
+        drop(slot);
+
+        self.strategy_order.retain(|id| *id != strategy_id);
+        for strategies in self.instrument_index.values_mut().chain(self.signal_subscriptions.values_mut()) {
+            strategies.retain(|id| *id != strategy_id);
+        }
+        self.instrument_index.retain(|_, strategies| !strategies.is_empty());
+        self.signal_subscriptions.retain(|_, strategies| !strategies.is_empty());
+
+        // Its order events receiver closed with the slot
+        if let Some(execution_engine) = &self.execution_engine {
+            execution_engine.message_bus().remove_closed_subscribers();
+        }
+        self.total_strategies -= 1;
+        tracing::info!("Removed strategy {}", strategy_id);
+        Ok(())
+    }
+
     /// Start the strategy engine
     pub fn start(&mut self) -> Result<(), String> {
         if self.is_running {
             return Err("Strategy engine is already running".to_string());
         }
+        if let Some(directory) = self.state_directory.clone() {
+            self.load_state(directory)?;
+        }
 
-        // Start all strategies
-        for (_, (strategy, context)) in &mut self.strategies {
+        // Start all strategies; one failing to start does not hold back the others
+        for slot in self.strategies.values_mut() {
+            let mut cell = slot.cell.lock().map_err(|_| "Strategy lock poisoned".to_string())?;
+            let span = cell.span(None);
+            let _span = span.enter();
+            let StrategyCell { strategy, context } = &mut *cell;
             context.set_state(StrategyState::Running);
-            strategy.on_start(context)?;
+            if let Err(e) = strategy.on_start(context) {
+                cell.fail(e, self.message_bus.as_deref());
+                continue;
+            }
+
+            let history = Self::warmup_events(&self.data_engine, &cell.context.config, std::mem::take(&mut slot.warmup_data))?;
+            if !history.is_empty() {
+                let delivered = cell.warm_up(history, self.message_bus.as_deref());
+                tracing::info!("Strategy {} warmed up on {} events", cell.context.config.name, delivered);
+            }
+        }
+
+        if let Some(capacity) = self.actor_capacity {
+            for (strategy_id, slot) in self.strategies.iter_mut() {
+                let cell = Arc::clone(&slot.cell);
+                let timer_queue = Arc::clone(&slot.timer_queue);
+                slot.worker = Some(StrategyWorker::spawn(*strategy_id, cell, capacity, self.message_bus.clone(), timer_queue)?);
+            }
+        }
+
+        let now = self.clock.timestamp_ns();
+        for slot in self.strategies.values_mut() {
+            slot.in_session = slot.session.as_ref().is_none_or(|session| session.calendar.is_open(now));
+        }
+        // Flattening must not wait for market data that may never come
+        for (strategy_id, slot) in &self.strategies {
+            let Some(session) = slot.session.as_ref().filter(|session| session.flatten_at_close) else {
+                continue;
+            };
+            let schedule = SessionSchedule::new(session.calendar.clone(), SessionEvent::Close);
+            self.clock
+                .set_scheduled_timer(
+                    session_close_timer(*strategy_id),
+                    Arc::new(schedule),
+                    None,
+                    TimeEventSender::Channel(self.session_close_sender.clone()),
+                )
+                .map_err(|e| e.to_string())?;
         }
 
+        self.last_state_save = now;
         self.is_running = true;
-        self.active_strategies = self.strategies.len();
         Ok(())
     }
 
-    /// Stop the strategy engine
+    /// Stop the strategy engine.
+    ///
+    /// In actor mode, events already queued are handled before `on_stop`,
+    /// for as long as `set_worker_stop_timeout` allows.
     pub fn stop(&mut self) -> Result<(), String> {
         if !self.is_running {
             return Ok(());
         }
 
+        // Strategies whose threads are still handling an event halt themselves
+        let mut busy = HashSet::new();
+        for (strategy_id, slot) in self.strategies.iter_mut() {
+            if let Some(worker) = slot.worker.take() {
+                if !worker.shut_down(*strategy_id, self.worker_stop_timeout) {
+                    busy.insert(*strategy_id);
+                }
+            }
+        }
+
         // Stop all strategies
-        for (_, (strategy, context)) in &mut self.strategies {
-            context.set_state(StrategyState::Stopped);
-            strategy.on_stop(context)?;
+        for (strategy_id, slot) in &self.strategies {
+            if !busy.contains(strategy_id) {
+                slot.lock()?.halt()?;
+            }
         }
+        for slot in self.strategies.values_mut() {
+            while slot.timer_events.try_recv().is_ok() {}
+        }
+        for (strategy_id, slot) in &self.strategies {
+            if slot.session.as_ref().is_some_and(|session| session.flatten_at_close) {
+                self.clock.cancel_timer(session_close_timer(*strategy_id)).map_err(|e| e.to_string())?;
+            }
+        }
+        while self.session_closes.try_recv().is_ok() {}
 
         self.is_running = false;
-        self.active_strategies = 0;
+        if let Some(log) = &self.decision_log {
+            log.flush().map_err(|e| e.to_string())?;
+        }
+        if let Some(directory) = &self.state_directory {
+            self.save_states(directory, &busy)?;
+        }
         Ok(())
     }
 
-    /// Process a trade tick for all relevant strategies
-    pub fn process_trade_tick(&mut self, tick: &TradeTick) -> Result<(), String> {
-        if !self.is_running {
-            return Ok(());
+    /// Pause a running strategy.
+    ///
+    /// Events arriving while paused are discarded and the strategy cannot
+    /// submit orders. With `cancel_orders`, its working orders at the
+    /// connected execution engine are cancelled.
+    pub fn pause_strategy(&mut self, strategy_id: StrategyId, cancel_orders: bool) -> Result<(), String> {
+        let slot = self.strategies
+            .get(&strategy_id)
+            .ok_or_else(|| format!("Strategy with ID {:?} not found", strategy_id))?;
+        if cancel_orders && self.order_commands.is_none() {
+            return Err("No execution engine connected".to_string());
+        }
+        {
+            let mut cell = slot.lock()?;
+            if cell.context.state != StrategyState::Running {
+                return Err(format!("Strategy {} is not running", strategy_id));
+            }
+            let StrategyCell { strategy, context } = &mut *cell;
+            context.set_state(StrategyState::Paused);
+            strategy.on_pause(context)?;
         }
 
-        for (_, (strategy, context)) in &mut self.strategies {
-            if context.is_active() && context.config.instruments.contains(&tick.instrument_id) {
-                strategy.on_trade_tick(context, tick)?;
+        if let Some(sender) = self.order_commands.as_ref().filter(|_| cancel_orders) {
+            for order_id in self.working_orders(strategy_id) {
+                sender
+                    .send(OrderCommand::Cancel { order_id })
+                    .map_err(|_| "Execution engine is not accepting orders".to_string())?;
             }
         }
 
         Ok(())
     }
 
-    /// Process a quote tick for all relevant strategies
-    pub fn process_quote_tick(&mut self, tick: &QuoteTick) -> Result<(), String> {
-        if !self.is_running {
-            return Ok(());
+    /// Resume a paused strategy.
+    ///
+    /// The strategy must be flat, with no open positions and no working
+    /// orders, unless `force` is set; it then resumes holding them, for
+    /// `on_resume` to take over.
+    pub fn resume_strategy(&mut self, strategy_id: StrategyId, force: bool) -> Result<(), String> {
+        let working_orders = self.working_orders(strategy_id).len();
+        let slot = self.strategies
+            .get(&strategy_id)
+            .ok_or_else(|| format!("Strategy with ID {:?} not found", strategy_id))?;
+
+        let mut cell = slot.lock()?;
+        if cell.context.state != StrategyState::Paused {
+            return Err(format!("Strategy {} is not paused", strategy_id));
         }
+        let open_positions = cell.context.metrics.open_positions.values().filter(|size| **size != 0.0).count();
+        if !force && (open_positions > 0 || working_orders > 0) {
+            return Err(format!(
+                "Strategy {} is not flat ({} open positions, {} working orders)",
+                strategy_id, open_positions, working_orders
+            ));
+        }
+
+        let StrategyCell { strategy, context } = &mut *cell;
+        context.set_state(StrategyState::Running);
+        strategy.on_resume(context)?;
+        Ok(())
+    }
+
+    /// Change parameters of a strategy, returning the names whose value
+    /// changed.
+    ///
+    /// Every update is validated before any is applied. The strategy is
+    /// notified through `on_parameters_changed` when something changed.
+    pub fn update_parameters(
+        &mut self,
+        strategy_id: StrategyId,
+        updates: Vec<(String, ParameterValue)>,
+    ) -> Result<Vec<String>, String> {
+        let slot = self.strategies
+            .get(&strategy_id)
+            .ok_or_else(|| format!("Strategy with ID {:?} not found", strategy_id))?;
+        let mut cell = slot.lock()?;
 
-        for (_, (strategy, context)) in &mut self.strategies {
-            if context.is_active() && context.config.instruments.contains(&tick.instrument_id) {
-                strategy.on_quote_tick(context, tick)?;
+        let mut parameters = cell.context.config.parameters.clone();
+        let mut changed = Vec::new();
+        for (name, value) in updates {
+            if parameters.set(&name, value)? && !changed.contains(&name) {
+                changed.push(name);
             }
         }
+        if changed.is_empty() {
+            return Ok(changed);
+        }
+
+        let StrategyCell { strategy, context } = &mut *cell;
+        context.config.parameters = parameters;
+        strategy.on_parameters_changed(context, &changed)?;
+        Ok(changed)
+    }
 
+    /// Override the log level of one strategy (`None` leaves it to the
+    /// subscriber)
+    pub fn set_log_level(&mut self, strategy_id: StrategyId, level: Option<LogLevel>) -> Result<(), String> {
+        let slot = self.strategies
+            .get(&strategy_id)
+            .ok_or_else(|| format!("Strategy with ID {:?} not found", strategy_id))?;
+        slot.lock()?.context.config.log_level = level;
         Ok(())
     }
 
-    /// Process a bar for all relevant strategies
-    pub fn process_bar(&mut self, bar: &Bar) -> Result<(), String> {
+    /// Switch a strategy between live and shadow execution, e.g. to give a
+    /// validated shadow strategy capital. Orders already sent are unaffected.
+    pub fn set_execution_mode(&mut self, strategy_id: StrategyId, mode: ExecutionMode) -> Result<(), String> {
+        let slot = self.strategies
+            .get(&strategy_id)
+            .ok_or_else(|| format!("Strategy with ID {:?} not found", strategy_id))?;
+        slot.lock()?.context.config.execution_mode = mode;
+        Ok(())
+    }
+
+    /// State of a strategy
+    pub fn strategy_state(&self, strategy_id: &StrategyId) -> Option<StrategyState> {
+        let slot = self.strategies.get(strategy_id)?;
+        slot.lock().ok().map(|cell| cell.context.state)
+    }
+
+    // Orders of a strategy still working at the connected execution engine
+    fn working_orders(&self, strategy_id: StrategyId) -> Vec<OrderId> {
+        match &self.execution_engine {
+            Some(engine) => engine
+                .get_strategy_orders(strategy_id)
+                .iter()
+                .filter(|order| order.is_active())
+                .map(|order| order.order_id)
+                .collect(),
+            None => Vec::new(),
+        }
+    }
+
+    /// Deliver an event to the strategies on `route`, in registration order.
+    ///
+    /// A strategy returning an error is moved to `Error`, and one breaching a
+    /// risk limit to `RiskBreached`, raising an alert; the others still
+    /// receive the event.
+    fn dispatch(
+        &mut self,
+        event: StrategyEvent,
+        route: Route<'_>,
+    ) -> Result<(), String> {
         if !self.is_running {
             return Ok(());
         }
+        // Control messages, derived quotes and timers that arrived since the
+        // last event come first
+        if !matches!(event, StrategyEvent::Timer(_)) {
+            self.process_control_messages()?;
+            self.process_derived_quotes()?;
+            self.process_timers()?;
+        }
+        if event.is_session_bound() {
+            self.update_sessions()?;
+        }
+        if let (Some(instrument_id), Some(price)) = (event.instrument_id(), event.price()) {
+            if let Some(latest) = self.benchmark_prices.get(&instrument_id) {
+                *latest.lock().map_err(|_| "Benchmark price lock poisoned".to_string())? = Some(price);
+            }
+        }
+
+        // Continue the chain being handled, or start one with this event if
+        // a strategy acts on it
+        let correlation_id = current_correlation_id();
+        let _correlation = correlation_id.is_none().then(|| start_chain("strategy"));
 
-        for (_, (strategy, context)) in &mut self.strategies {
-            if context.is_active() {
-                strategy.on_bar(context, bar)?;
+        let targets: &[StrategyId] = match &route {
+            Route::Instrument(instrument_id) => self.instrument_index.get(instrument_id).map_or(&[], Vec::as_slice),
+            Route::Signal(name) => self.signal_subscriptions.get(*name).map_or(&[], Vec::as_slice),
+            Route::Strategy(strategy_id) => std::slice::from_ref(strategy_id),
+            Route::All => &self.strategy_order,
+        };
+        for strategy_id in targets {
+            let Some(slot) = self.strategies.get_mut(strategy_id) else {
+                continue;
+            };
+            if !slot.in_session && event.is_session_bound() {
+                continue;
+            }
+            let Some(worker) = &slot.worker else {
+                slot.lock()?.process(&event, self.message_bus.as_deref());
+                continue;
+            };
+
+            // Only market data is dropped; anything else waits for room
+            if !event.is_market_data() {
+                if worker.sender.send((event.clone(), correlation_id)).is_err() {
+                    tracing::warn!("Strategy {} worker has stopped", strategy_id);
+                }
+                continue;
+            }
+            match worker.sender.try_send((event.clone(), correlation_id)) {
+                Ok(()) => {}
+                Err(mpsc::TrySendError::Full(_)) => {
+                    slot.dropped_events += 1;
+                    if slot.dropped_events.is_power_of_two() {
+                        tracing::warn!(
+                            "Strategy {} is falling behind, {} events dropped",
+                            strategy_id,
+                            slot.dropped_events
+                        );
+                    }
+                }
+                Err(mpsc::TrySendError::Disconnected(_)) => {
+                    tracing::warn!("Strategy {} worker has stopped", strategy_id);
+                }
             }
         }
 
+        self.save_state_if_due();
         Ok(())
     }
 
-    /// Run timer events for all strategies
-    pub fn process_timer(&mut self) -> Result<(), String> {
+    /// Open and close strategy sessions at the current clock time, returning
+    /// how many changed. Runs before market data is delivered, and from
+    /// `process_timers` when the close of a strategy flattening at the close
+    /// fires, so it flattens even when no data arrives.
+    pub fn update_sessions(&mut self) -> Result<usize, String> {
         if !self.is_running {
-            return Ok(());
+            return Ok(0);
         }
+        let now = self.clock.timestamp_ns();
+        let mut changed = 0;
+        for strategy_id in &self.strategy_order {
+            let Some(slot) = self.strategies.get_mut(strategy_id) else {
+                continue;
+            };
+            let Some(session) = &slot.session else {
+                continue;
+            };
+            let in_session = session.calendar.is_open(now);
+            if in_session == slot.in_session {
+                continue;
+            }
 
-        for (_, (strategy, context)) in &mut self.strategies {
-            if context.is_active() {
-                strategy.on_timer(context)?;
+            if in_session {
+                tracing::info!("Session opened for strategy {}", strategy_id);
+            } else {
+                tracing::info!("Session closed for strategy {}", strategy_id);
+                if session.flatten_at_close {
+                    let cell = slot.lock()?;
+                    let _span = cell.span(None).entered();
+                    if cell.context.is_active() {
+                        cell.context.flatten_positions();
+                    }
+                }
             }
+            slot.in_session = in_session;
+            changed += 1;
         }
+        Ok(changed)
+    }
 
-        Ok(())
+    /// Whether a strategy's session is open (`None` for an unknown strategy)
+    pub fn in_session(&self, strategy_id: &StrategyId) -> Option<bool> {
+        self.strategies.get(strategy_id).map(|slot| slot.in_session)
     }
 
-    /// Get strategy metrics
-    pub fn get_strategy_metrics(&self, strategy_id: &StrategyId) -> Option<&StrategyMetrics> {
-        self.strategies.get(strategy_id).map(|(_, context)| &context.metrics)
+    /// Wait until every strategy worker has handled the events queued so far
+    /// (returns immediately when not in actor mode)
+    pub fn wait_until_idle(&self) -> Result<(), String> {
+        let (ack, acks) = mpsc::channel();
+        let mut pending = 0;
+        for slot in self.strategies.values() {
+            if let Some(worker) = &slot.worker {
+                if worker.sender.send((StrategyEvent::Barrier(ack.clone()), None)).is_ok() {
+                    pending += 1;
+                }
+            }
+        }
+        for _ in 0..pending {
+            acks.recv().map_err(|_| "Strategy worker stopped".to_string())?;
+        }
+        Ok(())
     }
 
-    /// Get all strategy metrics
-    pub fn get_all_metrics(&self) -> HashMap<StrategyId, &StrategyMetrics> {
-        self.strategies
-            .iter()
-            .map(|(id, (_, context))| (*id, &context.metrics))
-            .collect()
+    /// Process a trade tick for all relevant strategies
+    pub fn process_trade_tick(&mut self, tick: &TradeTick) -> Result<(), String> {
+        self.dispatch(StrategyEvent::Trade(tick.clone()), Route::Instrument(tick.instrument_id))
     }
 
-    /// Check if engine is running
-    pub fn is_running(&self) -> bool {
-        self.is_running
+    /// Process a quote tick for all relevant strategies
+    pub fn process_quote_tick(&mut self, tick: &QuoteTick) -> Result<(), String> {
+        self.dispatch(StrategyEvent::Quote(tick.clone()), Route::Instrument(tick.instrument_id))
+    }
+
+    /// Process a bar for all relevant strategies
+    pub fn process_bar(&mut self, bar: &Bar) -> Result<(), String> {
+        self.dispatch(StrategyEvent::Bar(bar.clone()), Route::All)
+    }
+
+    /// Process a funding rate update for all relevant strategies
+    pub fn process_funding_rate(&mut self, update: &FundingRateUpdate) -> Result<(), String> {
+        self.dispatch(StrategyEvent::FundingRate(update.clone()), Route::Instrument(update.instrument_id))
+    }
+
+    /// Process an open interest update for all relevant strategies
+    pub fn process_open_interest(&mut self, update: &OpenInterestUpdate) -> Result<(), String> {
+        self.dispatch(StrategyEvent::OpenInterest(update.clone()), Route::Instrument(update.instrument_id))
+    }
+
+    /// Process a mark price update for all relevant strategies
+    pub fn process_mark_price(&mut self, update: &MarkPriceUpdate) -> Result<(), String> {
+        self.dispatch(StrategyEvent::MarkPrice(update.clone()), Route::Instrument(update.instrument_id))
+    }
+
+    /// Process an index price update for all relevant strategies
+    pub fn process_index_price(&mut self, update: &IndexPriceUpdate) -> Result<(), String> {
+        self.dispatch(StrategyEvent::IndexPrice(update.clone()), Route::Instrument(update.instrument_id))
+    }
+
+    /// Route any market data item to the matching `process_*` method
+    /// (order book deltas have no strategy callback and are skipped)
+    pub fn process_data(&mut self, data: &MarketData) -> Result<(), String> {
+        match data {
+            MarketData::Trade(tick) => self.process_trade_tick(tick),
+            MarketData::Quote(tick) => self.process_quote_tick(tick),
+            MarketData::BookDeltas(_) => Ok(()),
+            MarketData::FundingRate(update) => self.process_funding_rate(update),
+            MarketData::OpenInterest(update) => self.process_open_interest(update),
+            MarketData::MarkPrice(update) => self.process_mark_price(update),
+            MarketData::IndexPrice(update) => self.process_index_price(update),
+        }
+    }
+
+    /// Subscribe a strategy to a named signal
+    pub fn subscribe_signal(&mut self, strategy_id: StrategyId, name: &str) -> Result<(), String> {
+        if !self.strategies.contains_key(&strategy_id) {
+            return Err(format!("Strategy with ID {:?} not found", strategy_id));
+        }
+
+        let subscribers = self.signal_subscriptions.entry(name.to_string()).or_default();
+        if !subscribers.contains(&strategy_id) {
+            subscribers.push(strategy_id);
+        }
+        Ok(())
+    }
+
+    /// Deliver a signal to its subscribed strategies
+    pub fn process_signal(&mut self, signal: &SignalData) -> Result<(), String> {
+        self.dispatch(StrategyEvent::Signal(signal.clone()), Route::Signal(&signal.name))
+    }
+
+    /// Deliver every signal published to the data engine since the last call
+    pub fn process_pending_signals(&mut self) -> Result<usize, String> {
+        let signals = self.data_engine
+            .lock()
+            .map_err(|_| "Data engine lock poisoned".to_string())?
+            .drain_signals();
+
+        for signal in &signals {
+            self.process_signal(signal)?;
+        }
+        Ok(signals.len())
+    }
+
+    /// Advance a `TestClock` to `target_ns`, delivering each timer firing
+    /// while the clock reads its scheduled time
+    pub fn advance_clock_to(&mut self, clock: &crate::clock::TestClock, target_ns: u64) -> Result<usize, String> {
+        let mut delivered = 0;
+        while let Some(next) = clock.next_timer_ns().filter(|next| *next <= target_ns) {
+            clock.advance_to(next);
+            delivered += self.process_timers()?;
+        }
+        clock.advance_to(target_ns);
+        Ok(delivered)
+    }
+
+    /// Deliver timer firings to their owning strategies, in firing order.
+    /// Also done ahead of every other event; in actor mode timers go
+    /// straight to the strategies' queues instead.
+    ///
+    /// With a `TestClock`, prefer `advance_clock_to` so strategies observe
+    /// each timer's scheduled time.
+    pub fn process_timers(&mut self) -> Result<usize, String> {
+        let mut events = Vec::new();
+        for strategy_id in &self.strategy_order {
+            let Some(slot) = self.strategies.get_mut(strategy_id) else {
+                continue;
+            };
+            while let Ok(event) = slot.timer_events.try_recv() {
+                events.push((*strategy_id, event));
+            }
+        }
+        let mut closes = 0;
+        while self.session_closes.try_recv().is_ok() {
+            closes += 1;
+        }
+        if !self.is_running {
+            return Ok(0);
+        }
+        if closes > 0 {
+            self.update_sessions()?;
+        }
+
+        // By time, each strategy's in the order the clock fired them
+        events.sort_by_key(|(_, event)| event.ts_event);
+        let count = events.len();
+        for (owner, event) in events {
+            self.dispatch(StrategyEvent::Timer(event), Route::Strategy(owner))?;
+        }
+        Ok(count)
+    }
+
+    /// Deliver the order events the connected execution engine has
+    /// published since the last call to the strategies owning the orders
+    pub fn process_order_events(&mut self) -> Result<usize, String> {
+        let mut events = Vec::new();
+        for strategy_id in &self.strategy_order {
+            let Some(receiver) = self.strategies.get_mut(strategy_id).and_then(|slot| slot.order_events.as_mut()) else {
+                continue;
+            };
+            while let Ok(event) = receiver.try_recv() {
+                events.push((*strategy_id, event));
+            }
+        }
+        if !self.is_running {
+            return Ok(0);
+        }
+
+        for (owner, event) in &events {
+            // Delivered as a hop of the chain that created the order
+            let correlation_id = self.execution_engine.as_ref().and_then(|engine| engine.order_correlation(event.order_id()));
+            let _correlation = correlation_id.map(|id| correlate("strategy", id));
+            self.dispatch(StrategyEvent::Order(OrderEvent::clone(event)), Route::Strategy(*owner))?;
+        }
+        Ok(events.len())
+    }
+
+    /// Apply the control messages sent to strategies' namespaces since the
+    /// last event was handled, returning how many were applied. Messages
+    /// that fail are logged and skipped; those for strategies of other
+    /// engines sharing the bus are ignored.
+    pub fn process_control_messages(&mut self) -> Result<usize, String> {
+        let Some(receiver) = self.control.as_mut() else {
+            return Ok(0);
+        };
+        let mut messages = Vec::new();
+        while let Ok(envelope) = receiver.try_recv() {
+            let owner = self
+                .strategy_order
+                .iter()
+                .find(|strategy_id| envelope.message_type == strategy_topic(**strategy_id, CONTROL_CHANNEL));
+            let Some(&strategy_id) = owner else {
+                continue;
+            };
+            match envelope.decode::<StrategyControl>() {
+                Ok(control) => messages.push((strategy_id, control, envelope)),
+                Err(e) => tracing::warn!("Dropping malformed control message for strategy {}: {}", strategy_id, e),
+            }
+        }
+
+        let mut applied = 0;
+        for (strategy_id, control, envelope) in messages {
+            let _correlation = handle_envelope("strategy.control", &envelope);
+            let result = match control {
+                StrategyControl::Pause { cancel_orders } => self.pause_strategy(strategy_id, cancel_orders),
+                StrategyControl::Resume { force } => self.resume_strategy(strategy_id, force),
+                StrategyControl::UpdateParameters(updates) => self.update_parameters(strategy_id, updates).map(|_| ()),
+                StrategyControl::SetLogLevel(level) => self.set_log_level(strategy_id, level),
+                StrategyControl::SetExecutionMode(mode) => self.set_execution_mode(strategy_id, mode),
+            };
+            match result {
+                Ok(()) => applied += 1,
+                Err(e) => tracing::warn!("Control message for strategy {} failed: {}", strategy_id, e),
+            }
+        }
+        Ok(applied)
+    }
+
+    /// Deliver the composite and synthetic quotes the data engine published
+    /// on the bus since the last event was handled to the strategies trading
+    /// their instrument, returning how many were delivered
+    pub fn process_derived_quotes(&mut self) -> Result<usize, String> {
+        let Some(receiver) = self.derived_quotes.as_mut() else {
+            return Ok(0);
+        };
+        let mut quotes = Vec::new();
+        while let Ok(envelope) = receiver.try_recv() {
+            match envelope.decode::<QuoteTick>() {
+                Ok(quote) => quotes.push((quote, envelope)),
+                Err(e) => tracing::warn!("Dropping malformed quote on {}: {}", envelope.message_type, e),
+            }
+        }
+
+        for (quote, envelope) in &quotes {
+            let _correlation = handle_envelope("strategy.quotes", envelope);
+            self.process_quote_tick(quote)?;
+        }
+        Ok(quotes.len())
+    }
+
+    /// Get strategy metrics
+    pub fn get_strategy_metrics(&self, strategy_id: &StrategyId) -> Option<StrategyMetrics> {
+        let slot = self.strategies.get(strategy_id)?;
+        slot.lock().ok().map(|cell| cell.context.metrics.clone())
+    }
+
+    /// Get all strategy metrics
+    pub fn get_all_metrics(&self) -> HashMap<StrategyId, StrategyMetrics> {
+        self.strategies
+            .iter()
+            .filter_map(|(id, slot)| Some((*id, slot.lock().ok()?.context.metrics.clone())))
+            .collect()
+    }
+
+    /// Market data dropped for a strategy because its channel was full
+    pub fn dropped_events(&self, strategy_id: &StrategyId) -> Option<u64> {
+        self.strategies.get(strategy_id).map(|slot| slot.dropped_events)
+    }
+
+    /// Check if engine is running
+    pub fn is_running(&self) -> bool {
+        self.is_running
     }
 
     /// Get total number of strategies
@@ -371,9 +2541,24 @@ impl StrategyEngine {
         self.total_strategies
     }
 
-    /// Get number of active strategies
+    /// Get number of running strategies
     pub fn active_strategies(&self) -> usize {
-        self.active_strategies
+        self.strategies
+            .values()
+            .filter(|slot| slot.lock().is_ok_and(|cell| cell.context.is_active()))
+            .count()
+    }
+}
+
+impl Drop for StrategyEngine {
+    fn drop(&mut self) {
+        // Let workers finish queued events, for a while, rather than leaving
+        // threads behind
+        for (strategy_id, slot) in self.strategies.iter_mut() {
+            if let Some(worker) = slot.worker.take() {
+                worker.shut_down(*strategy_id, self.worker_stop_timeout);
+            }
+        }
     }
 }
 
@@ -381,6 +2566,48 @@ impl StrategyEngine {
 mod tests {
     use super::*;
 
+    // No-op bodies for the required callbacks a test strategy ignores
+    macro_rules! ignore_callbacks {
+        ($($callback:ident),* $(,)?) => {
+            $(ignore_callbacks!(@ $callback);)*
+        };
+        (@ on_start) => {
+            fn on_start(&mut self, _context: &mut StrategyContext) -> Result<(), String> { Ok(()) }
+        };
+        (@ on_trade_tick) => {
+            fn on_trade_tick(&mut self, _context: &mut StrategyContext, _tick: &TradeTick) -> Result<(), String> { Ok(()) }
+        };
+        (@ on_quote_tick) => {
+            fn on_quote_tick(&mut self, _context: &mut StrategyContext, _tick: &QuoteTick) -> Result<(), String> { Ok(()) }
+        };
+        (@ on_bar) => {
+            fn on_bar(&mut self, _context: &mut StrategyContext, _bar: &Bar) -> Result<(), String> { Ok(()) }
+        };
+        (@ on_timer) => {
+            fn on_timer(&mut self, _context: &mut StrategyContext, _name: &str) -> Result<(), String> { Ok(()) }
+        };
+        (@ on_stop) => {
+            fn on_stop(&mut self, _context: &mut StrategyContext) -> Result<(), String> { Ok(()) }
+        };
+    }
+
+    fn new_data_engine() -> Arc<Mutex<DataEngine>> {
+        Arc::new(Mutex::new(DataEngine::new(crate::data_engine::DataEngineConfig::default())))
+    }
+
+    // A buy of `size` at `price`, with its trade ID and timestamps from `ts`
+    fn trade_tick(instrument_id: InstrumentId, price: f64, size: f64, ts: u64) -> TradeTick {
+        TradeTick {
+            instrument_id,
+            price,
+            size,
+            aggressor_side: crate::data::AggressorSide::Buyer,
+            trade_id: ts.to_string(),
+            ts_event: ts,
+            ts_init: ts,
+        }
+    }
+
     // Mock strategy for testing
     struct TestStrategy {
         name: String,
@@ -394,11 +2621,9 @@ mod tests {
     }
 
     impl Strategy for TestStrategy {
-        fn on_start(&mut self, _context: &mut StrategyContext) -> Result<(), String> {
-            println!("Strategy {} started", self.name);
-            Ok(())
-        }
+        ignore_callbacks!(on_start, on_quote_tick, on_bar, on_timer, on_stop);
 
+        #[allow(clippy::manual_is_multiple_of)]
         fn on_trade_tick(&mut self, context: &mut StrategyContext, tick: &TradeTick) -> Result<(), String> {
             self.trade_count += 1;
             
@@ -409,23 +2634,6 @@ mod tests {
             Ok(())
         }
 
-        fn on_quote_tick(&mut self, _context: &mut StrategyContext, _tick: &QuoteTick) -> Result<(), String> {
-            Ok(())
-        }
-
-        fn on_bar(&mut self, _context: &mut StrategyContext, _bar: &Bar) -> Result<(), String> {
-            Ok(())
-        }
-
-        fn on_timer(&mut self, _context: &mut StrategyContext) -> Result<(), String> {
-            Ok(())
-        }
-
-        fn on_stop(&mut self, _context: &mut StrategyContext) -> Result<(), String> {
-            println!("Strategy {} stopped", self.name);
-            Ok(())
-        }
-
         fn name(&self) -> &str {
             &self.name
         }
@@ -434,14 +2642,16 @@ mod tests {
     #[test]
     fn test_strategy_context() {
         let config = StrategyConfig::default();
-        let data_engine = Arc::new(Mutex::new(crate::data_engine::DataEngine::new(
-            crate::data_engine::DataEngineConfig::default()
-        )));
+        let data_engine = new_data_engine();
         
-        let mut context = StrategyContext::new(config, data_engine);
+        let clock = Arc::new(crate::clock::TestClock::new(1_000));
+        let mut context = StrategyContext::new(config, data_engine, clock.clone());
         
         assert_eq!(context.state, StrategyState::Initialized);
         assert!(!context.is_active());
+        assert_eq!(context.current_time_ns(), 1_000);
+        clock.advance_to(2_000);
+        assert_eq!(context.current_time_ns(), 2_000);
         
         context.set_state(StrategyState::Running);
         assert!(context.is_active());
@@ -454,13 +2664,16 @@ mod tests {
         assert_eq!(context.metrics.winning_trades, 1);
         assert_eq!(context.metrics.total_pnl, 100.0);
         assert_eq!(context.win_rate(), 1.0);
+        assert!(context.log_enabled(LogLevel::Trace));
+        context.config.log_level = Some(LogLevel::Warn);
+        assert!(!context.log_enabled(LogLevel::Info));
+        assert!(context.log_enabled(LogLevel::Error));
     }
 
     #[test]
+    #[allow(clippy::field_reassign_with_default)]
     fn test_strategy_engine() {
-        let data_engine = Arc::new(Mutex::new(crate::data_engine::DataEngine::new(
-            crate::data_engine::DataEngineConfig::default()
-        )));
+        let data_engine = new_data_engine();
         
         let mut engine = StrategyEngine::new(Arc::clone(&data_engine));
         
@@ -484,4 +2697,1521 @@ mod tests {
         engine.stop().unwrap();
         assert!(!engine.is_running());
     }
+
+    #[test]
+    fn test_signal_routing_between_strategies() {
+        struct SignalListener {
+            received: Arc<Mutex<Vec<f64>>>,
+        }
+
+        impl Strategy for SignalListener {
+            ignore_callbacks!(on_start, on_trade_tick, on_quote_tick, on_bar, on_timer, on_stop);
+            fn name(&self) -> &str { "SignalListener" }
+
+            fn on_signal(&mut self, _context: &mut StrategyContext, signal: &SignalData) -> Result<(), String> {
+                self.received.lock().unwrap().push(signal.value);
+                Ok(())
+            }
+        }
+
+        let data_engine = new_data_engine();
+        data_engine.lock().unwrap().start().unwrap();
+
+        let received = Arc::new(Mutex::new(Vec::new()));
+        let mut engine = StrategyEngine::new(Arc::clone(&data_engine));
+        let config = StrategyConfig {
+            strategy_id: StrategyId::new(2),
+            ..Default::default()
+        };
+        engine.add_strategy(Box::new(SignalListener { received: Arc::clone(&received) }), config.clone()).unwrap();
+        engine.subscribe_signal(StrategyId::new(2), "alpha").unwrap();
+        assert!(engine.subscribe_signal(StrategyId::new(9), "alpha").is_err());
+        engine.start().unwrap();
+
+        // A producer publishes through its own context
+        let producer = StrategyContext::new(config, Arc::clone(&data_engine), Arc::new(crate::clock::TestClock::new(0)));
+        producer.publish_signal("alpha", 1.5).unwrap();
+        producer.publish_signal("beta", 2.0).unwrap();
+
+        assert_eq!(engine.process_pending_signals().unwrap(), 2);
+        assert_eq!(*received.lock().unwrap(), vec![1.5]);
+    }
+
+    #[test]
+    fn test_synthetic_quotes_delivered_from_bus() {
+        use crate::message_bus::MessageBus;
+        use crate::synthetic::SyntheticInstrument;
+
+        struct SpreadListener {
+            received: Arc<Mutex<Vec<f64>>>,
+        }
+
+        impl Strategy for SpreadListener {
+            ignore_callbacks!(on_start, on_trade_tick, on_bar, on_timer, on_stop);
+            fn name(&self) -> &str { "SpreadListener" }
+
+            fn on_quote_tick(&mut self, _context: &mut StrategyContext, tick: &QuoteTick) -> Result<(), String> {
+                self.received.lock().unwrap().push(tick.bid_price);
+                Ok(())
+            }
+        }
+
+        let bus = Arc::new(MessageBus::new());
+        let data_engine = new_data_engine();
+        let (front, back) = (InstrumentId::new(1), InstrumentId::new(2));
+        let spread_id = {
+            let mut data_engine = data_engine.lock().unwrap();
+            data_engine.set_message_bus(Arc::clone(&bus));
+            data_engine.start().unwrap();
+            data_engine.add_synthetic(SyntheticInstrument::spread("CAL", back, front)).unwrap()
+        };
+
+        let received = Arc::new(Mutex::new(Vec::new()));
+        let mut engine = StrategyEngine::new(Arc::clone(&data_engine));
+        engine.set_message_bus(Arc::clone(&bus)).unwrap();
+        let config = StrategyConfig {
+            strategy_id: StrategyId::new(3),
+            instruments: vec![spread_id],
+            ..Default::default()
+        };
+        engine.add_strategy(Box::new(SpreadListener { received: Arc::clone(&received) }), config).unwrap();
+        engine.start().unwrap();
+
+        for tick in [trade_tick(front, 100.0, 1.0, 1), trade_tick(back, 103.0, 1.0, 2)] {
+            data_engine.lock().unwrap().process_trade_tick(tick.clone()).unwrap();
+            engine.process_trade_tick(&tick).unwrap();
+        }
+        assert_eq!(*received.lock().unwrap(), vec![3.0]);
+        assert_eq!(engine.process_derived_quotes().unwrap(), 0);
+    }
+
+    #[tokio::test]
+    async fn test_strategy_orders_reach_execution_engine() {
+        use crate::execution_engine::{ExchangeAdapter, OrderSide, OrderStatus};
+        use crate::identifiers::VenueOrderId;
+        use crate::message_bus::MessageBus;
+
+        type AdapterResult<T> = Result<T, Box<dyn std::error::Error + Send + Sync>>;
+
+        #[derive(Clone)]
+        struct MockAdapter;
+
+        #[async_trait::async_trait]
+        impl ExchangeAdapter for MockAdapter {
+            async fn submit_order(&self, _order: Order) -> AdapterResult<VenueOrderId> {
+                Ok(VenueOrderId::new("1".to_string()))
+            }
+            async fn cancel_order(&self, _order_id: OrderId) -> AdapterResult<()> {
+                Ok(())
+            }
+            async fn modify_order(&self, _order_id: OrderId, _quantity: f64, _price: Option<f64>) -> AdapterResult<()> {
+                Ok(())
+            }
+            fn clone_box(&self) -> Box<dyn ExchangeAdapter> {
+                Box::new(self.clone())
+            }
+        }
+
+        struct Trader {
+            order_id: Arc<Mutex<Option<OrderId>>>,
+        }
+
+        impl Strategy for Trader {
+            ignore_callbacks!(on_start, on_quote_tick, on_bar, on_timer, on_stop);
+            fn name(&self) -> &str { "Trader" }
+
+            fn on_trade_tick(&mut self, context: &mut StrategyContext, tick: &TradeTick) -> Result<(), String> {
+                let mut order_id = self.order_id.lock().unwrap();
+                match *order_id {
+                    None => {
+                        let order = Order::limit(
+                            context.config.strategy_id,
+                            tick.instrument_id,
+                            OrderSide::Buy,
+                            1.0,
+                            tick.price,
+                        );
+                        *order_id = Some(context.submit_order(order)?);
+                    }
+                    Some(order_id) if tick.price > 100.0 => context.cancel_order(order_id)?,
+                    Some(order_id) => context.modify_order(order_id, 2.0, Some(99.0))?,
+                }
+                Ok(())
+            }
+        }
+
+        let instrument_id = InstrumentId::new(123);
+        let execution_engine = Arc::new(ExecutionEngine::new(Arc::new(MessageBus::new())));
+        execution_engine.register_exchange_adapter("MOCK".to_string(), Box::new(MockAdapter));
+        execution_engine.configure_routing(instrument_id, "MOCK".to_string());
+
+        let data_engine = new_data_engine();
+        let mut engine = StrategyEngine::new(data_engine);
+        let config = StrategyConfig {
+            instruments: vec![instrument_id],
+            ..Default::default()
+        };
+        let order_id = Arc::new(Mutex::new(None));
+        engine.add_strategy(Box::new(Trader { order_id: Arc::clone(&order_id) }), config.clone()).unwrap();
+        let handle = engine.connect_execution_engine(Arc::clone(&execution_engine)).unwrap();
+        engine.start().unwrap();
+
+        let tick = |price| trade_tick(instrument_id, price, 1.0, 0);
+        for price in [100.0, 100.0, 101.0] {
+            engine.process_trade_tick(&tick(price)).unwrap();
+        }
+
+        // Dropping the engine closes the command channel once it is drained
+        drop(engine);
+        handle.await.unwrap();
+
+        // Cancelled, so no longer working
+        assert!(execution_engine.get_strategy_orders(config.strategy_id).is_empty());
+        let order = execution_engine.get_order(order_id.lock().unwrap().unwrap()).unwrap();
+        assert_eq!(order.quantity, 2.0);
+        assert_eq!(order.price, Some(99.0));
+        assert_eq!(order.status, OrderStatus::Cancelled);
+        assert_eq!(execution_engine.get_statistics().orders_cancelled, 1);
+    }
+
+    #[tokio::test]
+    async fn test_order_events_delivered_to_owning_strategy() {
+        use crate::execution_engine::ExchangeAdapter;
+        use crate::identifiers::VenueOrderId;
+        use crate::message_bus::MessageBus;
+
+        type AdapterResult<T> = Result<T, Box<dyn std::error::Error + Send + Sync>>;
+
+        /// Rejects orders priced below 50
+        #[derive(Clone)]
+        struct MockAdapter;
+
+        #[async_trait::async_trait]
+        impl ExchangeAdapter for MockAdapter {
+            async fn submit_order(&self, order: Order) -> AdapterResult<VenueOrderId> {
+                match order.price {
+                    Some(price) if price < 50.0 => Err("price too far from market".into()),
+                    _ => Ok(VenueOrderId::new("1".to_string())),
+                }
+            }
+            async fn cancel_order(&self, _order_id: OrderId) -> AdapterResult<()> {
+                Ok(())
+            }
+            async fn modify_order(&self, _order_id: OrderId, _quantity: f64, _price: Option<f64>) -> AdapterResult<()> {
+                Ok(())
+            }
+            fn clone_box(&self) -> Box<dyn ExchangeAdapter> {
+                Box::new(self.clone())
+            }
+        }
+
+        struct Recorder {
+            trades: bool,
+            events: Arc<Mutex<Vec<String>>>,
+        }
+
+        impl Strategy for Recorder {
+            ignore_callbacks!(on_start, on_quote_tick, on_bar, on_timer, on_stop);
+            fn name(&self) -> &str { "Recorder" }
+
+            fn on_trade_tick(&mut self, context: &mut StrategyContext, tick: &TradeTick) -> Result<(), String> {
+                if self.trades {
+                    context.buy_limit(tick.instrument_id, 1.0, tick.price)?;
+                }
+                Ok(())
+            }
+
+            fn on_order_event(&mut self, _context: &mut StrategyContext, event: &OrderEvent) -> Result<(), String> {
+                let name = match event {
+                    OrderEvent::OrderSubmitted { .. } => "submitted",
+                    OrderEvent::OrderAccepted { .. } => "accepted",
+                    OrderEvent::OrderRejected { .. } => "rejected",
+                    OrderEvent::OrderFilled { .. } => "filled",
+                    OrderEvent::OrderCancelled { .. } => "cancelled",
+                    OrderEvent::OrderModified { .. } => "modified",
+                };
+                self.events.lock().unwrap().push(name.to_string());
+                Ok(())
+            }
+
+            fn on_fill(&mut self, _context: &mut StrategyContext, fill: &Fill) -> Result<(), String> {
+                self.events.lock().unwrap().push(format!("fill {}@{}", fill.quantity, fill.price));
+                Ok(())
+            }
+        }
+
+        // Deliver order events until `count` more have arrived
+        async fn settle(engine: &mut StrategyEngine, count: usize) {
+            let mut delivered = 0;
+            while delivered < count {
+                tokio::task::yield_now().await;
+                delivered += engine.process_order_events().unwrap();
+            }
+        }
+
+        let instrument_id = InstrumentId::new(123);
+        let execution_engine = Arc::new(ExecutionEngine::new(Arc::new(MessageBus::new())));
+        execution_engine.register_exchange_adapter("MOCK".to_string(), Box::new(MockAdapter));
+        execution_engine.configure_routing(instrument_id, "MOCK".to_string());
+
+        let data_engine = new_data_engine();
+        let mut engine = StrategyEngine::new(data_engine);
+        let (trader_events, watcher_events) = (Arc::new(Mutex::new(Vec::new())), Arc::new(Mutex::new(Vec::new())));
+        let _handle = engine.connect_execution_engine(Arc::clone(&execution_engine)).unwrap();
+        // Registered after connecting, so subscribed on registration
+        let trader = StrategyConfig {
+            strategy_id: StrategyId::new(1),
+            instruments: vec![instrument_id],
+            ..Default::default()
+        };
+        engine.add_strategy(Box::new(Recorder { trades: true, events: Arc::clone(&trader_events) }), trader).unwrap();
+        let watcher = StrategyConfig {
+            strategy_id: StrategyId::new(2),
+            instruments: vec![instrument_id],
+            ..Default::default()
+        };
+        engine.add_strategy(Box::new(Recorder { trades: false, events: Arc::clone(&watcher_events) }), watcher).unwrap();
+        engine.start().unwrap();
+
+        let tick = |price| trade_tick(instrument_id, price, 1.0, 0);
+        engine.process_trade_tick(&tick(100.0)).unwrap();
+        settle(&mut engine, 2).await;
+        engine.process_trade_tick(&tick(10.0)).unwrap();
+        settle(&mut engine, 2).await;
+
+        // The rejected order is no longer working
+        let orders = execution_engine.get_strategy_orders(StrategyId::new(1));
+        assert_eq!(orders.len(), 1);
+        assert_eq!(engine.working_orders(StrategyId::new(1)).len(), 1);
+        execution_engine.handle_fill(Fill {
+            order_id: orders[0].order_id,
+            fill_id: "f1".to_string(),
+            price: 100.0,
+            quantity: 1.0,
+            timestamp: 0,
+            commission: 0.5,
+            commission_currency: "USDT".to_string(),
+        }).unwrap();
+        settle(&mut engine, 1).await;
+
+        assert_eq!(*trader_events.lock().unwrap(), vec![
+            "submitted", "accepted", "submitted", "rejected", "filled", "fill 1@100",
+        ]);
+        assert!(watcher_events.lock().unwrap().is_empty());
+        assert_eq!(execution_engine.get_statistics().orders_rejected, 1);
+        assert_eq!(execution_engine.get_statistics().execution_latency.count, 1);
+        assert_eq!(execution_engine.get_active_orders_count(), 0);
+        assert!(execution_engine.get_strategy_orders(StrategyId::new(1)).is_empty());
+
+        // The fill is booked without the strategy reporting it
+        let metrics = engine.get_strategy_metrics(&StrategyId::new(1)).unwrap();
+        assert_eq!(metrics.total_trades, 1);
+        assert_eq!(metrics.total_pnl, -0.5);
+        assert_eq!(metrics.open_positions[&instrument_id], 1.0);
+    }
+
+    #[tokio::test]
+    async fn test_correlation_id_follows_order_lifecycle() {
+        use crate::execution_engine::ExchangeAdapter;
+        use crate::identifiers::VenueOrderId;
+
+        type AdapterResult<T> = Result<T, Box<dyn std::error::Error + Send + Sync>>;
+
+        #[derive(Clone)]
+        struct AcceptingAdapter;
+
+        #[async_trait::async_trait]
+        impl ExchangeAdapter for AcceptingAdapter {
+            async fn submit_order(&self, _order: Order) -> AdapterResult<VenueOrderId> {
+                Ok(VenueOrderId::new("1".to_string()))
+            }
+            async fn cancel_order(&self, _order_id: OrderId) -> AdapterResult<()> {
+                Ok(())
+            }
+            async fn modify_order(&self, _order_id: OrderId, _quantity: f64, _price: Option<f64>) -> AdapterResult<()> {
+                Ok(())
+            }
+            fn clone_box(&self) -> Box<dyn ExchangeAdapter> {
+                Box::new(self.clone())
+            }
+        }
+
+        /// Buys on every trade and logs every order event
+        struct Buyer;
+
+        impl Strategy for Buyer {
+            ignore_callbacks!(on_start, on_quote_tick, on_bar, on_timer, on_stop);
+            fn name(&self) -> &str { "Buyer" }
+
+            fn on_trade_tick(&mut self, context: &mut StrategyContext, tick: &TradeTick) -> Result<(), String> {
+                context.buy_limit(tick.instrument_id, 1.0, tick.price).map(|_| ())
+            }
+
+            fn on_order_event(&mut self, context: &mut StrategyContext, _event: &OrderEvent) -> Result<(), String> {
+                context.log(LogLevel::Info, "order event");
+                Ok(())
+            }
+        }
+
+        let instrument_id = InstrumentId::new(123);
+        let message_bus = Arc::new(MessageBus::new());
+        let execution_engine = Arc::new(ExecutionEngine::new(Arc::clone(&message_bus)));
+        execution_engine.register_exchange_adapter("MOCK".to_string(), Box::new(AcceptingAdapter));
+        execution_engine.configure_routing(instrument_id, "MOCK".to_string());
+
+        let data_engine = new_data_engine();
+        let mut engine = StrategyEngine::new(data_engine);
+        engine.set_message_bus(Arc::clone(&message_bus)).unwrap();
+        let _handle = engine.connect_execution_engine(Arc::clone(&execution_engine)).unwrap();
+        let config = StrategyConfig {
+            strategy_id: StrategyId::new(1),
+            instruments: vec![instrument_id],
+            ..Default::default()
+        };
+        engine.add_strategy(Box::new(Buyer), config).unwrap();
+        engine.start().unwrap();
+        let mut envelopes = message_bus.subscribe("*");
+
+        let tick = |price| trade_tick(instrument_id, price, 1.0, 0);
+        engine.process_trade_tick(&tick(100.0)).unwrap();
+        engine.process_trade_tick(&tick(101.0)).unwrap();
+        let mut delivered = 0;
+        let deadline = std::time::Instant::now() + std::time::Duration::from_secs(5);
+        while delivered < 4 {
+            assert!(std::time::Instant::now() < deadline, "timed out waiting for order events");
+            tokio::task::yield_now().await;
+            delivered += engine.process_order_events().unwrap();
+        }
+
+        // Each tick started a chain covering its order's command, events
+        // and the logs written while handling them
+        let orders = execution_engine.get_strategy_orders(StrategyId::new(1));
+        let chains: Vec<UUID7> = orders.iter().map(|order| order.correlation_id.unwrap()).collect();
+        assert_eq!(chains.len(), 2);
+        assert_ne!(chains[0], chains[1]);
+        let received: Vec<crate::message::MessageEnvelope> = std::iter::from_fn(|| envelopes.try_recv().ok()).collect();
+        for chain in &chains {
+            let topics: HashSet<&str> = received
+                .iter()
+                .filter(|envelope| envelope.correlation_id == Some(*chain))
+                .map(|envelope| envelope.message_type.as_str())
+                .collect();
+            assert_eq!(topics, HashSet::from([
+                "strategy.1.commands", "orders.submitted", "orders.accepted", "strategy.1.orders", "strategy.1.logs",
+            ]));
+        }
+        assert!(received.iter().all(|envelope| envelope.correlation_id.is_some()));
+    }
+
+    #[test]
+    fn test_order_factory_applies_instrument_precision() {
+        let data_engine = new_data_engine();
+        let instrument = crate::cache::InstrumentAny::new("BTCUSDT", "BINANCE", 1, 2, 0.5, 0.01, 1.0);
+        let instrument_id = instrument.id();
+        data_engine.lock().unwrap().add_instrument(instrument).unwrap();
+
+        let mut context = StrategyContext::new(StrategyConfig::default(), data_engine, Arc::new(crate::clock::TestClock::new(0)));
+        let (sender, mut receiver) = tokio::sync::mpsc::unbounded_channel();
+        context.order_commands = Some(sender);
+        assert!(context.buy_market(instrument_id, 1.0).is_err()); // Not running
+
+        context.set_state(StrategyState::Running);
+        context.buy_limit(instrument_id, 1.239, 100.26).unwrap();
+        let Ok(OrderCommand::Submit(order)) = receiver.try_recv() else {
+            panic!("expected a submitted order");
+        };
+        assert_eq!(order.quantity, 1.23);
+        assert_eq!(order.price, Some(100.5));
+        assert_eq!(order.strategy_id, context.config.strategy_id);
+        assert!(context.sell_market(instrument_id, 0.001).is_err()); // Below one lot
+
+        assert_eq!(context.close_position(instrument_id).unwrap(), None);
+        context.record_trade(instrument_id, 0.0, -2.0);
+        context.close_position(instrument_id).unwrap().unwrap();
+        let Ok(OrderCommand::Submit(order)) = receiver.try_recv() else {
+            panic!("expected a closing order");
+        };
+        assert_eq!(order.side, OrderSide::Buy);
+        assert_eq!(order.quantity, 2.0);
+        assert_eq!(order.price, None);
+
+        // 1% risk of 10k with a $7 stop is 14.2857..., rounded down to the lot
+        context.config.position_sizer = PositionSizer::FixedFractional { risk_fraction: 0.01 };
+        let inputs = SizingInputs::new(100.0, 10_000.0).with_stop_distance(7.0);
+        assert_eq!(context.position_size(instrument_id, inputs).unwrap(), 14.28);
+        context.config.max_position_size = 5.0;
+        assert_eq!(context.position_size(instrument_id, inputs).unwrap(), 5.0);
+    }
+
+    #[test]
+    fn test_timers_delivered_to_owning_strategy() {
+        struct TimedStrategy {
+            fired: Arc<Mutex<Vec<(String, u64)>>>,
+            timer: &'static str,
+        }
+
+        impl Strategy for TimedStrategy {
+            ignore_callbacks!(on_trade_tick, on_quote_tick, on_bar, on_stop);
+            fn name(&self) -> &str { self.timer }
+
+            fn on_start(&mut self, context: &mut StrategyContext) -> Result<(), String> {
+                let interval = if self.timer == "fast" { 10 } else { 25 };
+                context.set_timer(self.timer, interval)
+            }
+
+            fn on_timer(&mut self, context: &mut StrategyContext, name: &str) -> Result<(), String> {
+                let now = context.clock.timestamp_ns();
+                self.fired.lock().unwrap().push((name.to_string(), now));
+                if now >= 20 {
+                    context.cancel_timer(name)?;
+                }
+                Ok(())
+            }
+        }
+
+        let data_engine = new_data_engine();
+        let clock = Arc::new(crate::clock::TestClock::new(0));
+        let mut engine = StrategyEngine::new(data_engine);
+        engine.set_clock(clock.clone()).unwrap();
+
+        let fast = Arc::new(Mutex::new(Vec::new()));
+        let slow = Arc::new(Mutex::new(Vec::new()));
+        for (id, fired, timer) in [(1, &fast, "fast"), (2, &slow, "slow")] {
+            let strategy = Box::new(TimedStrategy { fired: Arc::clone(fired), timer });
+            let config = StrategyConfig { strategy_id: StrategyId::new(id), ..Default::default() };
+            engine.add_strategy(strategy, config).unwrap();
+        }
+        engine.start().unwrap();
+
+        assert_eq!(engine.advance_clock_to(&clock, 60).unwrap(), 3);
+        assert_eq!(clock.timestamp_ns(), 60);
+
+        assert_eq!(*fast.lock().unwrap(), vec![("fast".to_string(), 10), ("fast".to_string(), 20)]);
+        assert_eq!(*slow.lock().unwrap(), vec![("slow".to_string(), 25)]);
+    }
+
+    #[test]
+    fn test_timers_fire_without_polling() {
+        struct Timed {
+            seen: Arc<Mutex<Vec<String>>>,
+        }
+
+        impl Strategy for Timed {
+            ignore_callbacks!(on_quote_tick, on_bar, on_stop);
+            fn name(&self) -> &str { "Timed" }
+
+            fn on_start(&mut self, context: &mut StrategyContext) -> Result<(), String> {
+                context.set_timer("tick", 10)
+            }
+
+            fn on_trade_tick(&mut self, _context: &mut StrategyContext, _tick: &TradeTick) -> Result<(), String> {
+                self.seen.lock().unwrap().push("trade".to_string());
+                Ok(())
+            }
+
+            fn on_timer(&mut self, _context: &mut StrategyContext, name: &str) -> Result<(), String> {
+                self.seen.lock().unwrap().push(name.to_string());
+                Ok(())
+            }
+        }
+
+        let instrument_id = InstrumentId::new(7);
+        let tick = trade_tick(instrument_id, 100.0, 1.0, 15);
+        for actors in [false, true] {
+            let data_engine = new_data_engine();
+            let clock = Arc::new(crate::clock::TestClock::new(0));
+            let mut engine = StrategyEngine::new(data_engine);
+            engine.set_clock(clock.clone()).unwrap();
+            if actors {
+                engine.enable_actors(4).unwrap();
+            }
+            let seen = Arc::new(Mutex::new(Vec::new()));
+            let config = StrategyConfig { instruments: vec![instrument_id], ..Default::default() };
+            engine.add_strategy(Box::new(Timed { seen: Arc::clone(&seen) }), config).unwrap();
+            engine.start().unwrap();
+
+            // Fired on the clock alone, then handled ahead of the next event
+            clock.advance_to(15);
+            engine.process_trade_tick(&tick).unwrap();
+            engine.wait_until_idle().unwrap();
+            assert_eq!(*seen.lock().unwrap(), ["tick", "trade"], "actors: {}", actors);
+            engine.stop().unwrap();
+        }
+    }
+
+    #[test]
+    fn test_slow_actor_does_not_stall_others() {
+        struct Counter {
+            received: Arc<Mutex<u64>>,
+            gate: Option<(mpsc::Sender<()>, Mutex<mpsc::Receiver<()>>)>,
+        }
+
+        impl Strategy for Counter {
+            ignore_callbacks!(on_start, on_quote_tick, on_bar, on_timer, on_stop);
+            fn name(&self) -> &str { "Counter" }
+
+            fn on_trade_tick(&mut self, _context: &mut StrategyContext, _tick: &TradeTick) -> Result<(), String> {
+                // Block on the first tick until released
+                if let Some((entered, gate)) = self.gate.take() {
+                    entered.send(()).unwrap();
+                    gate.lock().unwrap().recv().unwrap();
+                }
+                *self.received.lock().unwrap() += 1;
+                Ok(())
+            }
+
+            fn on_order_event(&mut self, _context: &mut StrategyContext, _event: &OrderEvent) -> Result<(), String> {
+                *self.received.lock().unwrap() += 100;
+                Ok(())
+            }
+        }
+
+        fn wait_for(count: &Arc<Mutex<u64>>, expected: u64) {
+            let deadline = std::time::Instant::now() + std::time::Duration::from_secs(5);
+            while *count.lock().unwrap() < expected {
+                assert!(std::time::Instant::now() < deadline, "timed out waiting for {} events", expected);
+                thread::sleep(std::time::Duration::from_millis(1));
+            }
+        }
+
+        let data_engine = new_data_engine();
+        let mut engine = StrategyEngine::new(data_engine);
+        assert!(engine.enable_actors(0).is_err());
+        engine.enable_actors(4).unwrap();
+
+        let instrument_id = InstrumentId::new(7);
+        let (entered, blocked) = mpsc::channel();
+        let (release, gate) = mpsc::channel();
+        let slow = Arc::new(Mutex::new(0));
+        let fast = Arc::new(Mutex::new(0));
+        for (id, received, gate) in [(1, &slow, Some((entered, Mutex::new(gate)))), (2, &fast, None)] {
+            let strategy = Box::new(Counter { received: Arc::clone(received), gate });
+            let config = StrategyConfig {
+                strategy_id: StrategyId::new(id),
+                instruments: vec![instrument_id],
+                ..Default::default()
+            };
+            engine.add_strategy(strategy, config).unwrap();
+        }
+        engine.start().unwrap();
+        assert!(engine.enable_actors(8).is_err());
+
+        let tick = trade_tick(instrument_id, 100.0, 1.0, 0);
+        engine.process_trade_tick(&tick).unwrap();
+        blocked.recv().unwrap();
+        wait_for(&fast, 1);
+
+        // The slow strategy queues the next four while stuck on the first
+        for _ in 0..4 {
+            engine.process_trade_tick(&tick).unwrap();
+        }
+        wait_for(&fast, 5);
+        // Its channel is full, so these are dropped for it alone
+        for _ in 0..2 {
+            engine.process_trade_tick(&tick).unwrap();
+        }
+        wait_for(&fast, 7);
+        assert_eq!(*slow.lock().unwrap(), 0);
+
+        // Order events wait for room instead
+        let releaser = thread::spawn(move || {
+            thread::sleep(std::time::Duration::from_millis(20));
+            release.send(()).unwrap();
+        });
+        let cancelled = OrderEvent::OrderCancelled { order_id: OrderId::new(), timestamp: 0 };
+        engine.dispatch(StrategyEvent::Order(cancelled), Route::Strategy(StrategyId::new(1))).unwrap();
+        releaser.join().unwrap();
+        engine.wait_until_idle().unwrap();
+        assert_eq!(*slow.lock().unwrap(), 105);
+        assert_eq!(engine.dropped_events(&StrategyId::new(1)), Some(2));
+        assert_eq!(engine.dropped_events(&StrategyId::new(2)), Some(0));
+
+        engine.stop().unwrap();
+        assert!(engine.get_strategy_metrics(&StrategyId::new(1)).is_some());
+    }
+
+    #[test]
+    fn test_pause_and_resume_strategy() {
+        let data_engine = new_data_engine();
+        let mut engine = StrategyEngine::new(data_engine);
+        let strategy_id = StrategyId::new(1);
+        let instrument_id = InstrumentId::new(123);
+        let config = StrategyConfig {
+            strategy_id,
+            instruments: vec![instrument_id],
+            ..Default::default()
+        };
+        engine.add_strategy(Box::new(TestStrategy::new("Pausable".to_string())), config).unwrap();
+        assert!(engine.pause_strategy(strategy_id, false).is_err());
+        engine.start().unwrap();
+
+        let tick = |size| trade_tick(instrument_id, 100.0, size, 0);
+        let trades = |engine: &StrategyEngine| engine.get_strategy_metrics(&strategy_id).unwrap().total_trades;
+
+        // Cancelling orders needs an execution engine
+        assert!(engine.pause_strategy(strategy_id, true).is_err());
+        assert_eq!(engine.strategy_state(&strategy_id), Some(StrategyState::Running));
+
+        engine.process_trade_tick(&tick(1.0)).unwrap();
+        engine.process_trade_tick(&tick(-1.0)).unwrap();
+        engine.pause_strategy(strategy_id, false).unwrap();
+        assert_eq!(engine.strategy_state(&strategy_id), Some(StrategyState::Paused));
+        assert_eq!(engine.active_strategies(), 0);
+        assert!(engine.pause_strategy(strategy_id, false).is_err());
+
+        // Nothing is delivered while paused
+        engine.process_trade_tick(&tick(1.0)).unwrap();
+        assert_eq!(trades(&engine), 2);
+
+        engine.resume_strategy(strategy_id, false).unwrap();
+        assert_eq!(engine.active_strategies(), 1);
+        assert!(engine.resume_strategy(strategy_id, false).is_err());
+        engine.process_trade_tick(&tick(1.0)).unwrap();
+        assert_eq!(trades(&engine), 3);
+
+        // An open position blocks resuming
+        engine.pause_strategy(strategy_id, false).unwrap();
+        let err = engine.resume_strategy(strategy_id, false).unwrap_err();
+        assert!(err.contains("1 open positions"), "{}", err);
+        assert!(engine.resume_strategy(StrategyId::new(9), true).is_err());
+
+        // Forcing resumes it with the position
+        engine.resume_strategy(strategy_id, true).unwrap();
+        assert_eq!(engine.strategy_state(&strategy_id), Some(StrategyState::Running));
+        engine.process_trade_tick(&tick(-1.0)).unwrap();
+        assert_eq!(trades(&engine), 4);
+    }
+
+    #[test]
+    fn test_failing_strategy_is_isolated() {
+        struct Faulty {
+            received: Arc<Mutex<u64>>,
+            fail: bool,
+        }
+
+        impl Strategy for Faulty {
+            ignore_callbacks!(on_start, on_quote_tick, on_bar, on_timer, on_stop);
+            fn name(&self) -> &str { "Faulty" }
+
+            fn on_trade_tick(&mut self, _context: &mut StrategyContext, _tick: &TradeTick) -> Result<(), String> {
+                *self.received.lock().unwrap() += 1;
+                if self.fail {
+                    return Err("bad tick".to_string());
+                }
+                Ok(())
+            }
+        }
+
+        let message_bus = Arc::new(MessageBus::new());
+        let mut alerts = message_bus.subscribe("strategies.error");
+        let data_engine = new_data_engine();
+        let mut engine = StrategyEngine::new(data_engine);
+        engine.set_message_bus(Arc::clone(&message_bus)).unwrap();
+
+        let instrument_id = InstrumentId::new(123);
+        let counts: Vec<_> = (0..3).map(|_| Arc::new(Mutex::new(0))).collect();
+        for (id, received) in counts.iter().enumerate() {
+            let strategy = Box::new(Faulty { received: Arc::clone(received), fail: id == 1 });
+            let config = StrategyConfig {
+                strategy_id: StrategyId::new(id as u64),
+                instruments: vec![instrument_id],
+                ..Default::default()
+            };
+            engine.add_strategy(strategy, config).unwrap();
+        }
+        engine.start().unwrap();
+
+        let tick = trade_tick(instrument_id, 100.0, 1.0, 0);
+        for _ in 0..3 {
+            engine.process_trade_tick(&tick).unwrap();
+        }
+
+        let received: Vec<u64> = counts.iter().map(|count| *count.lock().unwrap()).collect();
+        assert_eq!(received, vec![3, 1, 3]);
+        assert_eq!(engine.strategy_state(&StrategyId::new(1)), Some(StrategyState::Error));
+        assert_eq!(engine.active_strategies(), 2);
+
+        let envelope = alerts.try_recv().unwrap();
+        let alert: StrategyAlert = bincode::deserialize(&envelope.payload).unwrap();
+        assert_eq!(alert.strategy_id, StrategyId::new(1));
+        assert_eq!(alert.reason, "bad tick");
+        assert!(alerts.try_recv().is_err());
+    }
+
+    #[test]
+    fn test_risk_limits_stop_strategy() {
+        /// Reports a large loss of its own on every tick, which the limits ignore
+        struct Misreporting;
+
+        impl Strategy for Misreporting {
+            ignore_callbacks!(on_start, on_quote_tick, on_bar, on_timer, on_stop);
+            fn name(&self) -> &str { "Misreporting" }
+
+            fn on_trade_tick(&mut self, context: &mut StrategyContext, tick: &TradeTick) -> Result<(), String> {
+                context.record_trade(tick.instrument_id, -1_000_000.0, 0.0);
+                Ok(())
+            }
+        }
+
+        // Book a filled buy for a strategy
+        fn buy(engine: &mut StrategyEngine, strategy_id: StrategyId, instrument_id: InstrumentId, quantity: f64, price: f64) {
+            let order = Order::market(strategy_id, instrument_id, OrderSide::Buy, quantity);
+            let fill = Fill {
+                order_id: order.order_id,
+                fill_id: "1".to_string(),
+                price,
+                quantity,
+                timestamp: 0,
+                commission: 0.0,
+                commission_currency: "USD".to_string(),
+            };
+            let order_id = order.order_id;
+            engine.dispatch(StrategyEvent::Order(OrderEvent::OrderSubmitted { order, timestamp: 0 }), Route::Strategy(strategy_id)).unwrap();
+            engine.dispatch(StrategyEvent::Order(OrderEvent::OrderFilled { order_id, fill, timestamp: 0 }), Route::Strategy(strategy_id)).unwrap();
+        }
+
+        let message_bus = Arc::new(MessageBus::new());
+        let mut alerts = message_bus.subscribe("strategies.risk_breached");
+        let data_engine = new_data_engine();
+        let mut engine = StrategyEngine::new(data_engine);
+        engine.set_message_bus(message_bus).unwrap();
+        let (sender, mut orders) = tokio::sync::mpsc::unbounded_channel();
+        engine.set_order_commands(sender).unwrap();
+
+        let (first, second) = (InstrumentId::new(1), InstrumentId::new(2));
+        let daily_loss = StrategyConfig {
+            strategy_id: StrategyId::new(1),
+            instruments: vec![first],
+            max_daily_loss: Some(100.0),
+            flatten_on_risk_breach: true,
+            ..Default::default()
+        };
+        let drawdown = StrategyConfig {
+            strategy_id: StrategyId::new(2),
+            instruments: vec![second],
+            starting_equity: 1_000.0,
+            max_drawdown: Some(0.1),
+            ..Default::default()
+        };
+        // No limits configured, so never stopped
+        let unlimited = StrategyConfig {
+            strategy_id: StrategyId::new(3),
+            instruments: vec![first],
+            ..Default::default()
+        };
+        for config in [daily_loss, drawdown, unlimited] {
+            engine.add_strategy(Box::new(Misreporting), config).unwrap();
+        }
+        engine.start().unwrap();
+        buy(&mut engine, StrategyId::new(1), first, 2.0, 100.0);
+        buy(&mut engine, StrategyId::new(2), second, 10.0, 100.0);
+        buy(&mut engine, StrategyId::new(3), first, 100.0, 100.0);
+
+        let tick = |instrument_id, price| trade_tick(instrument_id, price, 1.0, 0);
+        engine.process_trade_tick(&tick(first, 60.0)).unwrap();
+        engine.process_trade_tick(&tick(second, 120.0)).unwrap();
+        assert_eq!(engine.active_strategies(), 3);
+
+        // Marked at 40, the two lots lost 120 and the first is flattened
+        engine.process_trade_tick(&tick(first, 40.0)).unwrap();
+        assert_eq!(engine.strategy_state(&StrategyId::new(1)), Some(StrategyState::RiskBreached));
+        let Ok(OrderCommand::Submit(order)) = orders.try_recv() else {
+            panic!("expected a flattening order");
+        };
+        assert_eq!((order.side, order.quantity), (OrderSide::Sell, 2.0));
+        assert!(orders.try_recv().is_err());
+
+        // Equity 1200 -> 1070 is a 10.8% drawdown
+        engine.process_trade_tick(&tick(second, 107.0)).unwrap();
+        assert_eq!(engine.strategy_state(&StrategyId::new(2)), Some(StrategyState::RiskBreached));
+        assert_eq!(engine.strategy_state(&StrategyId::new(3)), Some(StrategyState::Running));
+
+        let breached: Vec<StrategyId> = std::iter::from_fn(|| alerts.try_recv().ok())
+            .map(|envelope| bincode::deserialize::<StrategyAlert>(&envelope.payload).unwrap().strategy_id)
+            .collect();
+        assert_eq!(breached, vec![StrategyId::new(1), StrategyId::new(2)]);
+        assert!(engine.resume_strategy(StrategyId::new(1), true).is_err());
+    }
+
+    #[test]
+    fn test_parameter_updates_notify_strategy() {
+        struct Tunable {
+            lookback: Arc<Mutex<i64>>,
+            notifications: Arc<Mutex<Vec<Vec<String>>>>,
+        }
+
+        impl Strategy for Tunable {
+            ignore_callbacks!(on_trade_tick, on_quote_tick, on_bar, on_timer, on_stop);
+            fn name(&self) -> &str { "Tunable" }
+
+            fn on_start(&mut self, context: &mut StrategyContext) -> Result<(), String> {
+                *self.lookback.lock().unwrap() = context.config.parameters.get_int("lookback")?;
+                Ok(())
+            }
+
+            fn on_parameters_changed(&mut self, context: &mut StrategyContext, changed: &[String]) -> Result<(), String> {
+                *self.lookback.lock().unwrap() = context.config.parameters.get_int("lookback")?;
+                self.notifications.lock().unwrap().push(changed.to_vec());
+                Ok(())
+            }
+        }
+
+        let data_engine = new_data_engine();
+        let mut engine = StrategyEngine::new(data_engine);
+        let parameters = Parameters::new()
+            .with_range("lookback", ParameterValue::Int(20), Some(1.0), Some(100.0))
+            .unwrap()
+            .with("threshold", ParameterValue::Float(0.5))
+            .unwrap();
+        let config = StrategyConfig { parameters, ..Default::default() };
+        let strategy_id = config.strategy_id;
+
+        let lookback = Arc::new(Mutex::new(0));
+        let notifications = Arc::new(Mutex::new(Vec::new()));
+        let strategy = Tunable { lookback: Arc::clone(&lookback), notifications: Arc::clone(&notifications) };
+        engine.add_strategy(Box::new(strategy), config).unwrap();
+        engine.start().unwrap();
+        assert_eq!(*lookback.lock().unwrap(), 20);
+
+        let changed = engine.update_parameters(strategy_id, vec![
+            ("lookback".to_string(), ParameterValue::Int(30)),
+            ("threshold".to_string(), ParameterValue::Float(0.5)),
+        ]).unwrap();
+        assert_eq!(changed, vec!["lookback".to_string()]);
+        assert_eq!(*lookback.lock().unwrap(), 30);
+
+        // An invalid update leaves every parameter untouched
+        assert!(engine.update_parameters(strategy_id, vec![
+            ("threshold".to_string(), ParameterValue::Float(0.9)),
+            ("lookback".to_string(), ParameterValue::Int(500)),
+        ]).is_err());
+        assert!(engine.update_parameters(strategy_id, vec![
+            ("lookback".to_string(), ParameterValue::Int(30)),
+        ]).unwrap().is_empty());
+
+        assert_eq!(*notifications.lock().unwrap(), vec![vec!["lookback".to_string()]]);
+    }
+
+    #[test]
+    fn test_return_statistics() {
+        let data_engine = new_data_engine();
+        let config = StrategyConfig { starting_equity: 1_000.0, ..Default::default() };
+        let mut context = StrategyContext::new(config, data_engine, Arc::new(crate::clock::TestClock::new(0)));
+        let instrument_id = InstrumentId::new(1);
+
+        // Opening trades realize nothing and are not returns
+        context.record_trade(instrument_id, 0.0, 1.0);
+        for pnl in [100.0, 110.0, -242.0, -96.8, 50.0] {
+            context.record_trade(instrument_id, pnl, 0.0);
+        }
+
+        // Equity 1000 -> 1100 -> 1210 -> 968 -> 871.2 -> 921.2
+        let returns = [0.1, 0.1, -0.2, -0.1, 50.0 / 871.2];
+        let n = returns.len() as f64;
+        let mean = returns.iter().sum::<f64>() / n;
+        let std = (returns.iter().map(|r| (r - mean).powi(2)).sum::<f64>() / (n - 1.0)).sqrt();
+        let downside = (returns.iter().map(|r| r.min(0.0).powi(2)).sum::<f64>() / n).sqrt();
+
+        let metrics = &context.metrics;
+        assert_eq!(metrics.total_trades, 6);
+        assert!((metrics.sharpe_ratio - mean / std).abs() < 1e-12);
+        assert!((metrics.sortino_ratio - mean / downside).abs() < 1e-12);
+        assert!((metrics.max_drawdown - (1210.0 - 871.2) / 1210.0).abs() < 1e-12);
+        assert_eq!(metrics.max_consecutive_wins, 2);
+        assert_eq!(metrics.max_consecutive_losses, 2);
+    }
+
+    #[test]
+    fn test_strategy_state_survives_restart() {
+        struct Counter {
+            ticks: u64,
+            restored: Arc<Mutex<u64>>,
+        }
+
+        impl Strategy for Counter {
+            ignore_callbacks!(on_quote_tick, on_bar, on_timer, on_stop);
+            fn name(&self) -> &str { "Counter" }
+
+            fn on_start(&mut self, _context: &mut StrategyContext) -> Result<(), String> {
+                *self.restored.lock().unwrap() = self.ticks;
+                Ok(())
+            }
+
+            fn on_trade_tick(&mut self, context: &mut StrategyContext, tick: &TradeTick) -> Result<(), String> {
+                self.ticks += 1;
+                context.record_trade(tick.instrument_id, 10.0, 0.0);
+                Ok(())
+            }
+
+            fn save_state(&self) -> Result<Vec<u8>, String> {
+                Ok(self.ticks.to_le_bytes().to_vec())
+            }
+
+            fn load_state(&mut self, state: &[u8]) -> Result<(), String> {
+                self.ticks = u64::from_le_bytes(state.try_into().map_err(|_| "Bad counter state")?);
+                Ok(())
+            }
+        }
+
+        let dir = std::env::temp_dir().join(format!("alphaforge-strategies-{}", crate::uuid::UUID4::new()));
+        let config = StrategyConfig {
+            instruments: vec![InstrumentId::new(1)],
+            ..Default::default()
+        };
+        let strategy_id = config.strategy_id;
+        let tick = trade_tick(InstrumentId::new(1), 100.0, 1.0, 1);
+        let new_engine = |restored: &Arc<Mutex<u64>>| {
+            let data_engine = new_data_engine();
+            let mut engine = StrategyEngine::new(data_engine);
+            engine.set_state_directory(&dir);
+            let strategy = Counter { ticks: 0, restored: Arc::clone(restored) };
+            engine.add_strategy(Box::new(strategy), config.clone()).unwrap();
+            engine
+        };
+
+        // Nothing saved yet, so the first run starts fresh and saves on stop
+        let restored = Arc::new(Mutex::new(u64::MAX));
+        let mut engine = new_engine(&restored);
+        engine.start().unwrap();
+        assert_eq!(*restored.lock().unwrap(), 0);
+        for _ in 0..3 {
+            engine.process_trade_tick(&tick).unwrap();
+        }
+        engine.stop().unwrap();
+
+        let mut engine = new_engine(&restored);
+        engine.start().unwrap();
+        assert_eq!(*restored.lock().unwrap(), 3);
+        let metrics = engine.get_strategy_metrics(&strategy_id).unwrap();
+        assert_eq!(metrics.total_trades, 3);
+        assert_eq!(metrics.total_pnl, 30.0);
+        assert!(engine.load_state(&dir).is_err());
+        engine.stop().unwrap();
+
+        // Saved on an interval too, so a crash loses only what came after
+        let clock = Arc::new(crate::clock::TestClock::new(0));
+        let mut engine = new_engine(&restored);
+        engine.set_clock(clock.clone()).unwrap();
+        engine.set_state_save_interval(Duration::from_secs(10));
+        engine.start().unwrap();
+        engine.process_trade_tick(&tick).unwrap();
+        clock.set_time(10_000_000_000);
+        engine.process_trade_tick(&tick).unwrap();
+        clock.set_time(15_000_000_000);
+        engine.process_trade_tick(&tick).unwrap();
+        std::mem::forget(engine);
+        let mut engine = new_engine(&restored);
+        engine.start().unwrap();
+        assert_eq!(*restored.lock().unwrap(), 5);
+        engine.stop().unwrap();
+
+        // State saved for another strategy is rejected
+        let path = StrategyEngine::state_path(&dir, &strategy_id);
+        let mut snapshot = StrategySnapshot::from_bytes(&std::fs::read(&path).unwrap()).unwrap();
+        snapshot.strategy_id = StrategyId::new(u64::MAX);
+        std::fs::write(&path, snapshot.to_bytes().unwrap()).unwrap();
+        assert!(new_engine(&restored).start().is_err());
+
+        std::fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[test]
+    fn test_events_routed_through_instrument_index() {
+        struct Recorder {
+            id: u64,
+            delivered: Arc<Mutex<Vec<u64>>>,
+        }
+
+        impl Strategy for Recorder {
+            ignore_callbacks!(on_start, on_quote_tick, on_timer, on_stop);
+            fn name(&self) -> &str { "Recorder" }
+
+            fn on_trade_tick(&mut self, _context: &mut StrategyContext, _tick: &TradeTick) -> Result<(), String> {
+                self.delivered.lock().unwrap().push(self.id);
+                Ok(())
+            }
+
+            fn on_bar(&mut self, _context: &mut StrategyContext, _bar: &Bar) -> Result<(), String> {
+                self.delivered.lock().unwrap().push(self.id);
+                Ok(())
+            }
+        }
+
+        let data_engine = new_data_engine();
+        let mut engine = StrategyEngine::new(data_engine);
+        let delivered = Arc::new(Mutex::new(Vec::new()));
+
+        // 300 strategies spread over 10 instruments, registered out of ID order
+        for id in (0..300).rev() {
+            let strategy = Recorder { id, delivered: Arc::clone(&delivered) };
+            let config = StrategyConfig {
+                strategy_id: StrategyId::new(id),
+                // Listing an instrument twice must not deliver twice
+                instruments: vec![InstrumentId::new(id % 10), InstrumentId::new(id % 10)],
+                ..Default::default()
+            };
+            engine.add_strategy(Box::new(strategy), config).unwrap();
+        }
+        engine.start().unwrap();
+
+        engine.process_trade_tick(&trade_tick(InstrumentId::new(3), 100.0, 1.0, 1)).unwrap();
+        let expected: Vec<u64> = (0..300).rev().filter(|id| id % 10 == 3).collect();
+        assert_eq!(*delivered.lock().unwrap(), expected);
+
+        delivered.lock().unwrap().clear();
+        engine.process_trade_tick(&trade_tick(InstrumentId::new(42), 100.0, 1.0, 2)).unwrap();
+        assert!(delivered.lock().unwrap().is_empty());
+
+        // Bars still go to every strategy
+        engine.process_bar(&Bar {
+            bar_type: crate::data::BarType {
+                instrument_id: InstrumentId::new(3),
+                bar_spec: crate::data::BarSpecification {
+                    step: 1,
+                    aggregation: crate::data::BarAggregation::Tick(1),
+                },
+            },
+            open: 100.0,
+            high: 100.0,
+            low: 100.0,
+            close: 100.0,
+            volume: 1.0,
+            ts_event: 3,
+            ts_init: 3,
+        }).unwrap();
+        assert_eq!(*delivered.lock().unwrap(), (0..300).rev().collect::<Vec<_>>());
+    }
+
+    #[test]
+    fn test_warmup_primes_strategy_without_orders() {
+        struct Averager {
+            prices: Arc<Mutex<Vec<f64>>>,
+            rejected: Arc<Mutex<u64>>,
+        }
+
+        impl Strategy for Averager {
+            ignore_callbacks!(on_start, on_quote_tick, on_timer, on_stop);
+            fn name(&self) -> &str { "Averager" }
+
+            fn on_bar(&mut self, _context: &mut StrategyContext, bar: &Bar) -> Result<(), String> {
+                self.prices.lock().unwrap().push(bar.close);
+                Ok(())
+            }
+
+            fn on_trade_tick(&mut self, context: &mut StrategyContext, tick: &TradeTick) -> Result<(), String> {
+                self.prices.lock().unwrap().push(tick.price);
+                if context.is_warming_up() {
+                    assert!(context.buy_market(tick.instrument_id, 1.0).is_err());
+                    *self.rejected.lock().unwrap() += 1;
+                    return Ok(());
+                }
+                context.buy_market(tick.instrument_id, 1.0).map(|_| ())
+            }
+        }
+
+        let instrument_id = InstrumentId::new(1);
+        let bar_type = crate::data::BarType {
+            instrument_id,
+            bar_spec: crate::data::BarSpecification {
+                step: 1,
+                aggregation: crate::data::BarAggregation::Tick(1),
+            },
+        };
+        let trade = |price: f64, ts: u64| trade_tick(instrument_id, price, 1.0, ts);
+
+        let data_engine = new_data_engine();
+        {
+            let mut data_engine = data_engine.lock().unwrap();
+            let bars = (1..=5).map(|i| Bar {
+                bar_type: bar_type.clone(),
+                open: i as f64,
+                high: i as f64,
+                low: i as f64,
+                close: i as f64,
+                volume: 1.0,
+                ts_event: i * 10,
+                ts_init: i * 10,
+            }).collect();
+            data_engine.add_bars(bars).unwrap();
+            data_engine.cache().add_trade_tick(trade(25.0, 25)).unwrap();
+        }
+
+        let mut engine = StrategyEngine::new(data_engine);
+        let (sender, mut orders) = tokio::sync::mpsc::unbounded_channel();
+        engine.set_order_commands(sender).unwrap();
+        let config = StrategyConfig {
+            instruments: vec![instrument_id],
+            warmup: WarmupConfig {
+                bars: vec![(bar_type, 3)],
+                trade_ticks: 10,
+                quote_ticks: 0,
+            },
+            ..Default::default()
+        };
+        let strategy_id = config.strategy_id;
+        let prices = Arc::new(Mutex::new(Vec::new()));
+        let rejected = Arc::new(Mutex::new(0));
+        let strategy = Averager { prices: Arc::clone(&prices), rejected: Arc::clone(&rejected) };
+        engine.add_strategy(Box::new(strategy), config).unwrap();
+        // History from outside the cache, e.g. tick files
+        engine.add_warmup_data(strategy_id, vec![MarketData::Trade(trade(35.0, 35))]).unwrap();
+        engine.start().unwrap();
+
+        // Last three bars and the history, in time order, without orders
+        assert_eq!(*prices.lock().unwrap(), vec![25.0, 3.0, 35.0, 4.0, 5.0]);
+        assert_eq!(*rejected.lock().unwrap(), 2);
+        assert!(orders.try_recv().is_err());
+        assert_eq!(engine.strategy_state(&strategy_id), Some(StrategyState::Running));
+
+        engine.process_trade_tick(&trade(60.0, 60)).unwrap();
+        assert!(matches!(orders.try_recv(), Ok(OrderCommand::Submit(_))));
+    }
+
+    #[test]
+    fn test_shadow_strategy_publishes_orders_as_signals() {
+        struct Seller;
+
+        impl Strategy for Seller {
+            ignore_callbacks!(on_start, on_quote_tick, on_bar, on_timer, on_stop);
+            fn name(&self) -> &str { "Seller" }
+
+            fn on_trade_tick(&mut self, context: &mut StrategyContext, tick: &TradeTick) -> Result<(), String> {
+                context.sell_market(tick.instrument_id, 2.0)?;
+                Ok(())
+            }
+        }
+
+        let data_engine = new_data_engine();
+        data_engine.lock().unwrap().start().unwrap();
+        let mut engine = StrategyEngine::new(Arc::clone(&data_engine));
+        let log = Arc::new(DecisionLog::in_memory());
+        engine.set_decision_log(Arc::clone(&log)).unwrap();
+        let (sender, mut orders) = tokio::sync::mpsc::unbounded_channel();
+        engine.set_order_commands(sender).unwrap();
+
+        let strategy_id = StrategyId::new(4);
+        let instrument_id = InstrumentId::new(7);
+        let config = StrategyConfig {
+            strategy_id,
+            instruments: vec![instrument_id],
+            execution_mode: ExecutionMode::Shadow,
+            ..Default::default()
+        };
+        engine.add_strategy(Box::new(Seller), config).unwrap();
+        engine.start().unwrap();
+
+        let tick = trade_tick(instrument_id, 100.0, 1.0, 0);
+        engine.process_trade_tick(&tick).unwrap();
+
+        // Logged and published, but never sent
+        assert!(orders.try_recv().is_err());
+        let signals = data_engine.lock().unwrap().drain_signals();
+        assert_eq!(signals.len(), 1);
+        assert_eq!(signals[0].name, "shadow.4.7");
+        assert_eq!(signals[0].value, -2.0);
+        let records = log.records(strategy_id).unwrap();
+        assert!(matches!(records.last().unwrap().entry, DecisionEntry::Command(OrderCommand::Submit(_))));
+        assert_eq!(engine.strategy_state(&strategy_id), Some(StrategyState::Running));
+
+        // Promoted to live, orders reach the execution engine
+        engine.set_execution_mode(strategy_id, ExecutionMode::Live).unwrap();
+        engine.process_trade_tick(&tick).unwrap();
+        assert!(matches!(orders.try_recv(), Ok(OrderCommand::Submit(_))));
+        assert!(data_engine.lock().unwrap().drain_signals().is_empty());
+    }
+
+    #[test]
+    fn test_session_gates_market_data_and_flattens_at_close() {
+        use chrono::{NaiveTime, TimeZone};
+
+        struct Buyer {
+            ticks: Arc<Mutex<usize>>,
+        }
+
+        impl Strategy for Buyer {
+            ignore_callbacks!(on_start, on_quote_tick, on_bar, on_timer, on_stop);
+            fn name(&self) -> &str { "Buyer" }
+
+            fn on_trade_tick(&mut self, context: &mut StrategyContext, tick: &TradeTick) -> Result<(), String> {
+                *self.ticks.lock().unwrap() += 1;
+                context.record_trade(tick.instrument_id, 0.0, 1.0);
+                Ok(())
+            }
+        }
+
+        let data_engine = new_data_engine();
+        let mut engine = StrategyEngine::new(data_engine);
+        let (sender, mut orders) = tokio::sync::mpsc::unbounded_channel();
+        engine.set_order_commands(sender).unwrap();
+        let at = |h, m| chrono_tz::UTC.with_ymd_and_hms(2024, 7, 3, h, m, 0).unwrap().timestamp_nanos_opt().unwrap() as u64;
+        let clock = Arc::new(crate::clock::TestClock::new(at(9, 0)));
+        engine.set_clock(clock.clone()).unwrap();
+
+        let strategy_id = StrategyId::new(1);
+        let instrument_id = InstrumentId::new(5);
+        let hours = |h, m| NaiveTime::from_hms_opt(h, m, 0).unwrap();
+        let calendar = crate::calendar::TradingCalendar::new(
+            chrono_tz::UTC,
+            vec![crate::calendar::SessionWindow::weekdays(hours(9, 30), hours(16, 0))],
+        );
+        let config = StrategyConfig {
+            strategy_id,
+            instruments: vec![instrument_id],
+            session: Some(SessionConfig { calendar, flatten_at_close: true }),
+            ..Default::default()
+        };
+        let ticks = Arc::new(Mutex::new(0));
+        engine.add_strategy(Box::new(Buyer { ticks: Arc::clone(&ticks) }), config).unwrap();
+        engine.start().unwrap();
+        assert_eq!(engine.in_session(&strategy_id), Some(false));
+
+        let tick = trade_tick(instrument_id, 100.0, 1.0, 0);
+
+        // Before the open nothing is delivered
+        engine.process_trade_tick(&tick).unwrap();
+        assert_eq!(*ticks.lock().unwrap(), 0);
+
+        clock.set_time(at(10, 0));
+        engine.process_trade_tick(&tick).unwrap();
+        engine.process_trade_tick(&tick).unwrap();
+        assert_eq!(*ticks.lock().unwrap(), 2);
+        assert_eq!(engine.in_session(&strategy_id), Some(true));
+        assert!(orders.try_recv().is_err());
+
+        // The close flattens the two lots bought in session, without waiting
+        // for more market data
+        engine.advance_clock_to(&clock, at(16, 30)).unwrap();
+        assert_eq!(engine.in_session(&strategy_id), Some(false));
+        let Ok(OrderCommand::Submit(order)) = orders.try_recv() else {
+            panic!("expected a flattening order");
+        };
+        assert_eq!((order.side, order.quantity), (OrderSide::Sell, 2.0));
+        engine.process_trade_tick(&tick).unwrap();
+        assert_eq!(*ticks.lock().unwrap(), 2);
+        assert_eq!(engine.update_sessions().unwrap(), 0);
+        assert_eq!(engine.strategy_state(&strategy_id), Some(StrategyState::Running));
+    }
+
+    #[test]
+    fn test_strategy_namespace_carries_commands_logs_and_control() {
+        struct Talker;
+
+        impl Strategy for Talker {
+            ignore_callbacks!(on_start, on_quote_tick, on_bar, on_timer, on_stop);
+            fn name(&self) -> &str { "Talker" }
+
+            fn on_trade_tick(&mut self, context: &mut StrategyContext, tick: &TradeTick) -> Result<(), String> {
+                context.log(LogLevel::Info, "buying");
+                context.buy_market(tick.instrument_id, 1.0)?;
+                Ok(())
+            }
+        }
+
+        let message_bus = Arc::new(MessageBus::new());
+        let data_engine = new_data_engine();
+        let mut engine = StrategyEngine::new(data_engine);
+        let (sender, _orders) = tokio::sync::mpsc::unbounded_channel();
+        engine.set_order_commands(sender).unwrap();
+
+        let instrument_id = InstrumentId::new(3);
+        for id in [1, 2] {
+            let config = StrategyConfig {
+                strategy_id: StrategyId::new(id),
+                instruments: vec![instrument_id],
+                ..Default::default()
+            };
+            engine.add_strategy(Box::new(Talker), config).unwrap();
+        }
+        engine.set_message_bus(Arc::clone(&message_bus)).unwrap();
+        let mut first = message_bus.subscribe("strategy.1.*");
+        let mut second = message_bus.subscribe("strategy.2.*");
+        engine.start().unwrap();
+
+        let tick = trade_tick(instrument_id, 100.0, 1.0, 0);
+        engine.process_trade_tick(&tick).unwrap();
+
+        // Each namespace sees only its own strategy
+        let topics: Vec<String> = std::iter::from_fn(|| first.try_recv().ok()).map(|envelope| envelope.message_type).collect();
+        assert_eq!(topics, vec!["strategy.1.logs", "strategy.1.commands"]);
+        assert_eq!(std::iter::from_fn(|| second.try_recv().ok()).count(), 2);
+
+        // Controlled from outside through the namespace, ahead of the next
+        // event; the strategy paused skips the tick
+        message_bus.publish(&strategy_topic(StrategyId::new(1), CONTROL_CHANNEL), &StrategyControl::Pause { cancel_orders: false });
+        message_bus.publish(&strategy_topic(StrategyId::new(2), CONTROL_CHANNEL), &StrategyControl::Resume { force: false });
+        message_bus.publish(&strategy_topic(StrategyId::new(9), CONTROL_CHANNEL), &StrategyControl::Resume { force: false });
+        engine.process_trade_tick(&tick).unwrap();
+        assert_eq!(engine.strategy_state(&StrategyId::new(1)), Some(StrategyState::Paused));
+        assert_eq!(engine.strategy_state(&StrategyId::new(2)), Some(StrategyState::Running));
+        assert_eq!(first.try_recv().unwrap().message_type, "strategy.1.control");
+        assert!(first.try_recv().is_err());
+        let topics: Vec<String> = std::iter::from_fn(|| second.try_recv().ok()).map(|envelope| envelope.message_type).collect();
+        assert_eq!(topics, vec!["strategy.2.control", "strategy.2.logs", "strategy.2.commands"]);
+        assert_eq!(engine.process_control_messages().unwrap(), 0);
+
+        // Removal leaves other components' subscriptions to the namespace open
+        engine.stop().unwrap();
+        engine.remove_strategy(StrategyId::new(1)).unwrap();
+        message_bus.publish(&strategy_topic(StrategyId::new(1), LOGS_CHANNEL), &"still listening");
+        assert_eq!(first.try_recv().unwrap().message_type, "strategy.1.logs");
+        assert_eq!(message_bus.subscriber_count("strategy.1.*"), 1);
+        assert_eq!(message_bus.subscriber_count("strategy.2.*"), 1);
+        assert_eq!(engine.total_strategies(), 1);
+        assert!(engine.remove_strategy(StrategyId::new(1)).is_err());
+
+        engine.start().unwrap();
+        engine.process_trade_tick(&tick).unwrap();
+        assert_eq!(std::iter::from_fn(|| second.try_recv().ok()).count(), 2);
+        assert!(first.try_recv().is_err());
+    }
+
+    #[test]
+    fn test_metrics_against_benchmark() {
+        /// Reports a gain of its own on every tick, which the benchmark ignores
+        struct Misreporting;
+
+        impl Strategy for Misreporting {
+            ignore_callbacks!(on_start, on_quote_tick, on_bar, on_timer, on_stop);
+            fn name(&self) -> &str { "Misreporting" }
+
+            fn on_trade_tick(&mut self, context: &mut StrategyContext, tick: &TradeTick) -> Result<(), String> {
+                context.record_trade(tick.instrument_id, 1_000.0, 1.0);
+                Ok(())
+            }
+        }
+
+        let data_engine = new_data_engine();
+        let mut engine = StrategyEngine::new(data_engine);
+        let (traded, benchmark) = (InstrumentId::new(5), InstrumentId::new(9));
+        let strategy_id = StrategyId::new(1);
+        let config = StrategyConfig {
+            strategy_id,
+            instruments: vec![traded],
+            starting_equity: 1_000.0,
+            benchmark: Some(benchmark),
+            ..Default::default()
+        };
+        engine.add_strategy(Box::new(Misreporting), config).unwrap();
+        engine.start().unwrap();
+
+        let tick = |instrument_id, price| trade_tick(instrument_id, price, 1.0, 0);
+        // Buys one lot at each mark; the first fill only fixes the base
+        // values. Equity, with the open lots marked, goes 1000, 1050, 1029
+        // and 1059.87.
+        for (benchmark_price, price) in [(100.0, 100.0), (110.0, 150.0), (99.0, 139.5), (99.0, 149.79)] {
+            engine.process_trade_tick(&tick(benchmark, benchmark_price)).unwrap();
+            engine.process_trade_tick(&tick(traded, price)).unwrap();
+            let order = Order::market(strategy_id, traded, OrderSide::Buy, 1.0);
+            let order_id = order.order_id;
+            let fill = Fill {
+                order_id,
+                fill_id: "1".to_string(),
+                price,
+                quantity: 1.0,
+                timestamp: 0,
+                commission: 0.0,
+                commission_currency: "USD".to_string(),
+            };
+            engine.dispatch(StrategyEvent::Order(OrderEvent::OrderSubmitted { order, timestamp: 0 }), Route::Strategy(strategy_id)).unwrap();
+            engine.dispatch(StrategyEvent::Order(OrderEvent::OrderFilled { order_id, fill, timestamp: 0 }), Route::Strategy(strategy_id)).unwrap();
+        }
+
+        let pairs = [(0.05, 0.1), (-0.02, -0.1), (0.03, 0.0)];
+        let mean = |values: &[f64]| values.iter().sum::<f64>() / values.len() as f64;
+        let strategy: Vec<f64> = pairs.iter().map(|(s, _)| *s).collect();
+        let bench: Vec<f64> = pairs.iter().map(|(_, b)| *b).collect();
+        let active: Vec<f64> = pairs.iter().map(|(s, b)| s - b).collect();
+        let (ms, mb, ma) = (mean(&strategy), mean(&bench), mean(&active));
+        let beta = pairs.iter().map(|(s, b)| (s - ms) * (b - mb)).sum::<f64>() / bench.iter().map(|b| (b - mb).powi(2)).sum::<f64>();
+        let active_std = (active.iter().map(|a| (a - ma).powi(2)).sum::<f64>() / 2.0).sqrt();
+
+        let metrics = engine.get_strategy_metrics(&strategy_id).unwrap();
+        assert!((metrics.beta - beta).abs() < 1e-9);
+        assert!((metrics.alpha - (ms - beta * mb)).abs() < 1e-9);
+        assert!((metrics.information_ratio - ma / active_std).abs() < 1e-9);
+        // Worst point was 5% up against a 10% rise in the benchmark
+        assert!((metrics.max_relative_drawdown - (1.0 - 1.05 / 1.1)).abs() < 1e-9);
+    }
+
+    #[tokio::test]
+    async fn test_clock_shared_with_data_and_execution_engines() {
+        let data_engine = new_data_engine();
+        let execution_engine = Arc::new(ExecutionEngine::new(Arc::new(MessageBus::new())));
+        let mut engine = StrategyEngine::new(Arc::clone(&data_engine));
+        let clock = Arc::new(crate::clock::TestClock::new(1_000));
+        engine.set_clock(clock.clone()).unwrap();
+        let _handle = engine.connect_execution_engine(Arc::clone(&execution_engine)).unwrap();
+
+        clock.advance_to(5_000);
+        assert_eq!(data_engine.lock().unwrap().clock().timestamp_ns(), 5_000);
+        let strategy_id = StrategyId::new(1);
+        let order = Order::market(strategy_id, InstrumentId::new(7), OrderSide::Buy, 1.0);
+        let order_id = order.order_id;
+        // Unrouted, so rejected, but stamped with the replayed time
+        assert!(execution_engine.execute(OrderCommand::Submit(order)).await.is_err());
+        let order = execution_engine.get_order(order_id).unwrap();
+        assert_eq!(order.status, crate::execution_engine::OrderStatus::Rejected);
+        assert_eq!(order.updated_time, 5_000);
+        assert!(execution_engine.get_strategy_orders(strategy_id).is_empty());
+    }
+
+    #[test]
+    fn test_stop_waits_for_busy_actor_only_so_long() {
+        struct Stuck {
+            entered: mpsc::Sender<()>,
+            gate: Mutex<mpsc::Receiver<()>>,
+            handled: Arc<Mutex<u64>>,
+            stopped: mpsc::Sender<()>,
+        }
+
+        impl Strategy for Stuck {
+            ignore_callbacks!(on_start, on_quote_tick, on_bar, on_timer);
+            fn name(&self) -> &str { "Stuck" }
+
+            fn on_trade_tick(&mut self, _context: &mut StrategyContext, _tick: &TradeTick) -> Result<(), String> {
+                let _ = self.entered.send(());
+                let _ = self.gate.lock().unwrap().recv();
+                *self.handled.lock().unwrap() += 1;
+                Ok(())
+            }
+
+            fn on_stop(&mut self, _context: &mut StrategyContext) -> Result<(), String> {
+                let _ = self.stopped.send(());
+                Ok(())
+            }
+        }
+
+        let data_engine = new_data_engine();
+        let mut engine = StrategyEngine::new(data_engine);
+        engine.enable_actors(4).unwrap();
+        engine.set_worker_stop_timeout(std::time::Duration::from_millis(50));
+        let (entered, blocked) = mpsc::channel();
+        let (release, gate) = mpsc::channel();
+        let (stopped, stops) = mpsc::channel();
+        let handled = Arc::new(Mutex::new(0));
+        let instrument_id = InstrumentId::new(7);
+        let strategy = Stuck { entered, gate: Mutex::new(gate), handled: Arc::clone(&handled), stopped };
+        let config = StrategyConfig { instruments: vec![instrument_id], ..Default::default() };
+        let strategy_id = config.strategy_id;
+        engine.add_strategy(Box::new(strategy), config).unwrap();
+        engine.start().unwrap();
+
+        let tick = trade_tick(instrument_id, 100.0, 1.0, 0);
+        for _ in 0..3 {
+            engine.process_trade_tick(&tick).unwrap();
+        }
+        blocked.recv().unwrap();
+
+        // Stuck on the first tick: stop gives up rather than hanging
+        let started = std::time::Instant::now();
+        engine.stop().unwrap();
+        assert!(started.elapsed() < std::time::Duration::from_secs(2));
+        assert!(stops.try_recv().is_err());
+
+        // Once released, the worker skips the queued ticks and halts the strategy
+        release.send(()).unwrap();
+        stops.recv_timeout(std::time::Duration::from_secs(5)).unwrap();
+        assert_eq!(*handled.lock().unwrap(), 1);
+        assert_eq!(engine.strategy_state(&strategy_id), Some(StrategyState::Stopped));
+    }
 }
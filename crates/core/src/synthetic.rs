@@ -0,0 +1,250 @@
+//! AlphaForge Synthetic Instruments
+//!
+//! Prices spreads and weighted baskets from their constituent quote and
+//! trade streams, publishing the result as `QuoteTick`s under a synthetic
+//! instrument ID.
+
+use std::collections::HashMap;
+
+use crate::data::{QuoteTick, TradeTick};
+use crate::identifiers::InstrumentId;
+use crate::time::UnixNanos;
+
+/// Venue name used for synthetic instrument IDs
+pub const SYNTHETIC_VENUE: &str = "SYNTH";
+
+/// A synthetic instrument priced as a weighted sum of legs
+#[derive(Debug, Clone)]
+pub struct SyntheticInstrument {
+    pub id: InstrumentId,
+    pub name: String,
+    /// Constituent instruments and their weights; negative weights are short legs
+    pub legs: Vec<(InstrumentId, f64)>,
+}
+
+impl SyntheticInstrument {
+    /// Create a weighted basket
+    pub fn basket(name: &str, legs: Vec<(InstrumentId, f64)>) -> Self {
+        Self {
+            id: InstrumentId::from_symbol_venue(name, SYNTHETIC_VENUE),
+            name: name.to_string(),
+            legs,
+        }
+    }
+
+    /// Create an `a - b` spread (e.g. a calendar spread)
+    pub fn spread(name: &str, a: InstrumentId, b: InstrumentId) -> Self {
+        Self::basket(name, vec![(a, 1.0), (b, -1.0)])
+    }
+}
+
+/// Best prices known for a leg
+#[derive(Debug, Clone, Copy)]
+struct LegPrices {
+    bid_price: f64,
+    ask_price: f64,
+    bid_size: f64,
+    ask_size: f64,
+}
+
+/// Prices synthetic instruments from constituent market data
+#[derive(Debug, Default)]
+pub struct SyntheticEngine {
+    synthetics: HashMap<InstrumentId, SyntheticInstrument>,
+    // Constituent -> synthetics it feeds
+    dependents: HashMap<InstrumentId, Vec<InstrumentId>>,
+    legs: HashMap<InstrumentId, LegPrices>,
+}
+
+impl SyntheticEngine {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Register a synthetic instrument
+    pub fn add(&mut self, synthetic: SyntheticInstrument) -> Result<(), String> {
+        if synthetic.legs.is_empty() {
+            return Err(format!("Synthetic {} has no legs", synthetic.name));
+        }
+        if synthetic.legs.iter().any(|(_, w)| !w.is_finite() || *w == 0.0) {
+            return Err(format!("Synthetic {} has a zero or non-finite weight", synthetic.name));
+        }
+        if synthetic.legs.iter().enumerate().any(|(i, (leg, _))| synthetic.legs[..i].iter().any(|(other, _)| other == leg)) {
+            return Err(format!("Synthetic {} has duplicate legs", synthetic.name));
+        }
+        if self.synthetics.contains_key(&synthetic.id) {
+            return Err(format!("Synthetic {} already exists", synthetic.name));
+        }
+
+        for (leg, _) in &synthetic.legs {
+            self.dependents.entry(*leg).or_default().push(synthetic.id);
+        }
+        self.synthetics.insert(synthetic.id, synthetic);
+        Ok(())
+    }
+
+    /// Remove a synthetic instrument
+    pub fn remove(&mut self, synthetic_id: InstrumentId) -> bool {
+        let Some(synthetic) = self.synthetics.remove(&synthetic_id) else {
+            return false;
+        };
+        for (leg, _) in &synthetic.legs {
+            if let Some(dependents) = self.dependents.get_mut(leg) {
+                dependents.retain(|id| *id != synthetic_id);
+                if dependents.is_empty() {
+                    self.dependents.remove(leg);
+                    self.legs.remove(leg);
+                }
+            }
+        }
+        true
+    }
+
+    /// Get a registered synthetic instrument
+    pub fn get(&self, synthetic_id: InstrumentId) -> Option<&SyntheticInstrument> {
+        self.synthetics.get(&synthetic_id)
+    }
+
+    /// Update a leg from a quote and reprice dependent synthetics
+    pub fn update_quote(&mut self, tick: &QuoteTick) -> Vec<QuoteTick> {
+        if !self.dependents.contains_key(&tick.instrument_id) {
+            return Vec::new();
+        }
+        self.legs.insert(tick.instrument_id, LegPrices {
+            bid_price: tick.bid_price,
+            ask_price: tick.ask_price,
+            bid_size: tick.bid_size,
+            ask_size: tick.ask_size,
+        });
+        self.reprice(tick.instrument_id, tick.ts_event, tick.ts_init)
+    }
+
+    /// Update a leg from a trade and reprice dependent synthetics.
+    ///
+    /// The trade price stands in for both sides until a quote arrives.
+    pub fn update_trade(&mut self, tick: &TradeTick) -> Vec<QuoteTick> {
+        if !self.dependents.contains_key(&tick.instrument_id) {
+            return Vec::new();
+        }
+        self.legs.insert(tick.instrument_id, LegPrices {
+            bid_price: tick.price,
+            ask_price: tick.price,
+            bid_size: tick.size,
+            ask_size: tick.size,
+        });
+        self.reprice(tick.instrument_id, tick.ts_event, tick.ts_init)
+    }
+
+    fn reprice(&self, leg: InstrumentId, ts_event: UnixNanos, ts_init: UnixNanos) -> Vec<QuoteTick> {
+        let Some(dependents) = self.dependents.get(&leg) else {
+            return Vec::new();
+        };
+
+        dependents
+            .iter()
+            .filter_map(|id| self.synthetics.get(id))
+            .filter_map(|synthetic| self.price(synthetic, ts_event, ts_init))
+            .collect()
+    }
+
+    /// Buying the synthetic buys long legs at their ask and sells short legs
+    /// at their bid; selling does the reverse
+    fn price(&self, synthetic: &SyntheticInstrument, ts_event: UnixNanos, ts_init: UnixNanos) -> Option<QuoteTick> {
+        let mut bid_price = 0.0;
+        let mut ask_price = 0.0;
+        let mut bid_size = f64::INFINITY;
+        let mut ask_size = f64::INFINITY;
+
+        for (leg, weight) in &synthetic.legs {
+            let prices = self.legs.get(leg)?;
+            let units = weight.abs();
+            if *weight > 0.0 {
+                bid_price += weight * prices.bid_price;
+                ask_price += weight * prices.ask_price;
+                bid_size = bid_size.min(prices.bid_size / units);
+                ask_size = ask_size.min(prices.ask_size / units);
+            } else {
+                bid_price += weight * prices.ask_price;
+                ask_price += weight * prices.bid_price;
+                bid_size = bid_size.min(prices.ask_size / units);
+                ask_size = ask_size.min(prices.bid_size / units);
+            }
+        }
+
+        Some(QuoteTick {
+            instrument_id: synthetic.id,
+            bid_price,
+            ask_price,
+            bid_size,
+            ask_size,
+            ts_event,
+            ts_init,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn quote(instrument_id: InstrumentId, bid: f64, ask: f64, size: f64) -> QuoteTick {
+        QuoteTick {
+            instrument_id,
+            bid_price: bid,
+            ask_price: ask,
+            bid_size: size,
+            ask_size: size,
+            ts_event: 1,
+            ts_init: 1,
+        }
+    }
+
+    #[test]
+    fn test_calendar_spread() {
+        let front = InstrumentId::new(1);
+        let back = InstrumentId::new(2);
+        let spread = SyntheticInstrument::spread("BTC-MAR/JUN", back, front);
+        let spread_id = spread.id;
+
+        let mut engine = SyntheticEngine::new();
+        engine.add(spread).unwrap();
+        assert!(engine.add(SyntheticInstrument::spread("BTC-MAR/MAR", front, front)).is_err());
+
+        // Nothing until every leg is priced
+        assert!(engine.update_quote(&quote(front, 100.0, 101.0, 5.0)).is_empty());
+
+        let quotes = engine.update_quote(&quote(back, 104.0, 106.0, 2.0));
+        assert_eq!(quotes.len(), 1);
+        let synthetic = &quotes[0];
+        assert_eq!(synthetic.instrument_id, spread_id);
+        assert_eq!(synthetic.bid_price, 3.0); // 104 - 101
+        assert_eq!(synthetic.ask_price, 6.0); // 106 - 100
+        assert_eq!(synthetic.bid_size, 2.0);
+    }
+
+    #[test]
+    fn test_weighted_basket_from_trades() {
+        let a = InstrumentId::new(1);
+        let b = InstrumentId::new(2);
+        let mut engine = SyntheticEngine::new();
+        engine.add(SyntheticInstrument::basket("BASKET", vec![(a, 0.5), (b, 2.0)])).unwrap();
+        assert!(engine.add(SyntheticInstrument::basket("EMPTY", vec![])).is_err());
+
+        let trade = |instrument_id, price| TradeTick {
+            instrument_id,
+            price,
+            size: 4.0,
+            aggressor_side: crate::data::AggressorSide::Buyer,
+            trade_id: "1".to_string(),
+            ts_event: 1,
+            ts_init: 1,
+        };
+        engine.update_trade(&trade(a, 100.0));
+        let quotes = engine.update_trade(&trade(b, 10.0));
+        assert_eq!(quotes[0].bid_price, 70.0);
+        assert_eq!(quotes[0].ask_size, 2.0); // limited by leg b: 4 / 2
+
+        assert!(engine.remove(quotes[0].instrument_id));
+        assert!(engine.update_trade(&trade(a, 101.0)).is_empty());
+    }
+}
@@ -0,0 +1,184 @@
+//! AlphaForge Throttler
+//!
+//! Forwards at most N items per interval downstream, e.g. order book
+//! updates to a strategy that only needs a few a second. Time is passed in
+//! by the caller, so a throttler behaves the same live and in backtests.
+
+use std::collections::VecDeque;
+use std::fmt;
+use std::time::Duration;
+
+use crate::error::{AlphaForgeError, Result};
+use crate::time::UnixNanos;
+
+/// What a `Throttler` does with items over the limit
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ThrottlePolicy {
+    /// Discard them
+    Drop,
+    /// Hold up to `capacity` and forward them, in order, as the limit
+    /// allows; when full the oldest held item is discarded
+    Buffer { capacity: usize },
+}
+
+/// Limits the rate of items passed to a handler
+pub struct Throttler<T> {
+    limit: usize,
+    interval_ns: u64,
+    policy: ThrottlePolicy,
+    /// Forward times within the last interval, oldest first
+    sent: VecDeque<UnixNanos>,
+    buffer: VecDeque<T>,
+    output: Box<dyn FnMut(T) + Send>,
+    forwarded: u64,
+    dropped: u64,
+}
+
+impl<T> Throttler<T> {
+    /// Forward at most `limit` items in any `interval` to `output`
+    pub fn new(
+        limit: usize,
+        interval: Duration,
+        policy: ThrottlePolicy,
+        output: impl FnMut(T) + Send + 'static,
+    ) -> Result<Self> {
+        if limit == 0 || interval.is_zero() || policy == (ThrottlePolicy::Buffer { capacity: 0 }) {
+            return Err(AlphaForgeError::config(format!(
+                "Invalid throttle: {} per {:?}, {:?}",
+                limit, interval, policy
+            )));
+        }
+        Ok(Self {
+            limit,
+            interval_ns: interval.as_nanos() as u64,
+            policy,
+            sent: VecDeque::with_capacity(limit),
+            buffer: VecDeque::new(),
+            output: Box::new(output),
+            forwarded: 0,
+            dropped: 0,
+        })
+    }
+
+    /// Pass `item`, arriving at `ts`, downstream if the limit allows
+    pub fn send(&mut self, item: T, ts: UnixNanos) {
+        self.flush(ts);
+        if self.buffer.is_empty() && self.has_room(ts) {
+            self.forward(item, ts);
+            return;
+        }
+        match self.policy {
+            ThrottlePolicy::Drop => self.dropped += 1,
+            ThrottlePolicy::Buffer { capacity } => {
+                if self.buffer.len() == capacity {
+                    self.buffer.pop_front();
+                    self.dropped += 1;
+                }
+                self.buffer.push_back(item);
+            }
+        }
+    }
+
+    /// Forward buffered items the limit allows at `ts`, returning how many
+    pub fn flush(&mut self, ts: UnixNanos) -> usize {
+        let mut count = 0;
+        while !self.buffer.is_empty() && self.has_room(ts) {
+            let item = self.buffer.pop_front().unwrap();
+            self.forward(item, ts);
+            count += 1;
+        }
+        count
+    }
+
+    /// When the next buffered item can be forwarded, to set a timer for
+    pub fn next_release_ns(&self) -> Option<UnixNanos> {
+        if self.buffer.is_empty() {
+            return None;
+        }
+        // Full windows release when their oldest forward expires
+        Some(match self.sent.len() < self.limit {
+            true => self.sent.back().copied().unwrap_or(0),
+            false => self.sent[0] + self.interval_ns,
+        })
+    }
+
+    pub fn buffered(&self) -> usize {
+        self.buffer.len()
+    }
+
+    pub fn forwarded(&self) -> u64 {
+        self.forwarded
+    }
+
+    pub fn dropped(&self) -> u64 {
+        self.dropped
+    }
+
+    fn has_room(&mut self, ts: UnixNanos) -> bool {
+        while self.sent.front().is_some_and(|at| ts >= at + self.interval_ns) {
+            self.sent.pop_front();
+        }
+        self.sent.len() < self.limit
+    }
+
+    fn forward(&mut self, item: T, ts: UnixNanos) {
+        self.sent.push_back(ts);
+        self.forwarded += 1;
+        (self.output)(item);
+    }
+}
+
+impl<T> fmt::Debug for Throttler<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Throttler")
+            .field("limit", &self.limit)
+            .field("interval_ns", &self.interval_ns)
+            .field("policy", &self.policy)
+            .field("buffered", &self.buffer.len())
+            .field("forwarded", &self.forwarded)
+            .field("dropped", &self.dropped)
+            .finish()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::{Arc, Mutex};
+
+    #[test]
+    fn test_throttler_drop_and_buffer() {
+        const MS: u64 = 1_000_000;
+        let output = Arc::new(Mutex::new(Vec::new()));
+
+        // 2 updates per 100ms; the rest of a burst is dropped
+        let out = Arc::clone(&output);
+        let mut drop = Throttler::new(2, Duration::from_millis(100), ThrottlePolicy::Drop, move |i| out.lock().unwrap().push(i)).unwrap();
+        for (i, ts) in [0, 10, 20, 30, 100, 110, 120].into_iter().enumerate() {
+            drop.send(i, ts * MS);
+        }
+        assert_eq!(*output.lock().unwrap(), vec![0, 1, 4, 5]);
+        assert_eq!((drop.forwarded(), drop.dropped()), (4, 3));
+        assert_eq!(drop.next_release_ns(), None);
+
+        // Buffering keeps the latest 2 and releases them in order
+        output.lock().unwrap().clear();
+        let out = Arc::clone(&output);
+        let mut buffer = Throttler::new(2, Duration::from_millis(100), ThrottlePolicy::Buffer { capacity: 2 }, move |i| out.lock().unwrap().push(i)).unwrap();
+        for (i, ts) in [0, 10, 20, 30, 40].into_iter().enumerate() {
+            buffer.send(i, ts * MS);
+        }
+        assert_eq!(*output.lock().unwrap(), vec![0, 1]);
+        assert_eq!((buffer.buffered(), buffer.dropped()), (2, 1));
+        assert_eq!(buffer.next_release_ns(), Some(100 * MS));
+        assert_eq!(buffer.flush(99 * MS), 0);
+        assert_eq!(buffer.flush(100 * MS), 1);
+        // Later items wait behind those already held
+        buffer.send(5, 105 * MS);
+        assert_eq!(buffer.flush(110 * MS), 1);
+        assert_eq!(buffer.flush(200 * MS), 1);
+        assert_eq!(*output.lock().unwrap(), vec![0, 1, 3, 4, 5]);
+
+        assert!(Throttler::new(0, Duration::from_secs(1), ThrottlePolicy::Drop, |_: u8| {}).is_err());
+    }
+}
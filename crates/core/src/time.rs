@@ -3,15 +3,24 @@
 //! Provides unified time abstractions for backtesting and live trading modes.
 
 use std::sync::atomic::{AtomicU64, Ordering};
-use chrono::{DateTime, Utc, NaiveDateTime};
+use std::sync::OnceLock;
+use std::time::{Duration, Instant};
+use chrono::{DateTime, NaiveDateTime, SecondsFormat, Utc};
+use parking_lot::RwLock;
 
 /// Nanoseconds since UNIX epoch (1970-01-01 00:00:00 UTC)
 pub type UnixNanos = u64;
 
+pub const NANOS_PER_SECOND: u64 = 1_000_000_000;
+pub const NANOS_PER_MILLI: u64 = 1_000_000;
+pub const NANOS_PER_MICRO: u64 = 1_000;
+
 /// Atomic timestamp for lock-free time operations
 #[derive(Debug, Default)]
 pub struct AtomicTime {
     nanos: AtomicU64,
+    /// Every value handed out is later than the one before
+    strict: bool,
 }
 
 impl AtomicTime {
@@ -19,16 +28,44 @@ impl AtomicTime {
     pub fn new() -> Self {
         Self {
             nanos: AtomicU64::new(unix_nanos_now()),
+            strict: false,
+        }
+    }
+    
+    /// Create atomic time that never repeats or goes back: updates landing
+    /// on or before the last value move 1ns past it, and `get_next` hands
+    /// out distinct, ordered timestamps even in a tight loop
+    pub fn strictly_increasing() -> Self {
+        Self {
+            strict: true,
+            ..Self::new()
         }
     }
     
-    /// Get current timestamp
+    pub fn is_strict(&self) -> bool {
+        self.strict
+    }
+    
+    /// Get current timestamp without advancing it
     pub fn get(&self) -> UnixNanos {
-        self.nanos.load(Ordering::Relaxed)
+        self.nanos.load(Ordering::Acquire)
+    }
+    
+    /// Take a timestamp: in strict mode 1ns past the last one, otherwise
+    /// the current timestamp
+    pub fn get_next(&self) -> UnixNanos {
+        if self.strict {
+            return self.nanos.fetch_add(1, Ordering::AcqRel) + 1;
+        }
+        self.get()
     }
     
     /// Update timestamp
     pub fn set(&self, timestamp: UnixNanos) {
+        if self.strict {
+            self.advance_to(timestamp);
+            return;
+        }
         self.nanos.store(timestamp, Ordering::Relaxed);
     }
     
@@ -36,6 +73,11 @@ impl AtomicTime {
     pub fn update_now(&self) {
         self.set(unix_nanos_now());
     }
+    
+    // Store `timestamp`, or 1ns past the last value if that is later
+    fn advance_to(&self, timestamp: UnixNanos) {
+        let _ = self.nanos.fetch_update(Ordering::AcqRel, Ordering::Acquire, |last| Some(timestamp.max(last + 1)));
+    }
 }
 
 /// Get current Unix timestamp in nanoseconds
@@ -62,6 +104,66 @@ pub fn datetime_to_unix_nanos(dt: DateTime<Utc>) -> UnixNanos {
     (dt.timestamp() as u64) * 1_000_000_000 + (dt.timestamp_subsec_nanos() as u64)
 }
 
+/// `ts` as ISO 8601 in UTC with nanoseconds, e.g.
+/// `2024-07-03T13:30:00.000000000Z`, which sorts as the times do
+pub fn format_iso8601(ts: UnixNanos) -> String {
+    DateTime::from_timestamp_nanos(ts as i64).to_rfc3339_opts(SecondsFormat::Nanos, true)
+}
+
+pub fn secs_to_nanos(secs: f64) -> UnixNanos {
+    (secs * NANOS_PER_SECOND as f64).round() as UnixNanos
+}
+
+pub fn millis_to_nanos(millis: u64) -> UnixNanos {
+    millis * NANOS_PER_MILLI
+}
+
+pub fn micros_to_nanos(micros: u64) -> UnixNanos {
+    micros * NANOS_PER_MICRO
+}
+
+pub fn nanos_to_secs(ts: UnixNanos) -> f64 {
+    ts as f64 / NANOS_PER_SECOND as f64
+}
+
+/// Whole milliseconds in `ts`, truncated
+pub fn nanos_to_millis(ts: UnixNanos) -> u64 {
+    ts / NANOS_PER_MILLI
+}
+
+/// Whole microseconds in `ts`, truncated
+pub fn nanos_to_micros(ts: UnixNanos) -> u64 {
+    ts / NANOS_PER_MICRO
+}
+
+/// Parse a numeric epoch timestamp in seconds, milliseconds, microseconds
+/// or nanoseconds, told apart by magnitude as venues send any of them.
+/// Fractions are kept to the nanosecond, e.g. `1700000000.25` seconds.
+/// Seconds are assumed up to 11 digits, which covers dates until 5138.
+pub fn parse_epoch(s: &str) -> Result<UnixNanos, String> {
+    let s = s.trim();
+    let (whole, fraction) = s.split_once('.').unwrap_or((s, ""));
+    let is_digits = |part: &str| part.bytes().all(|b| b.is_ascii_digit());
+    if whole.is_empty() || !is_digits(whole) || !is_digits(fraction) {
+        return Err(format!("Invalid epoch timestamp: {:?}", s));
+    }
+    let value: u64 = whole.parse().map_err(|_| format!("Epoch timestamp out of range: {:?}", s))?;
+    let unit = match whole.trim_start_matches('0').len() {
+        0..=11 => NANOS_PER_SECOND,
+        12..=14 => NANOS_PER_MILLI,
+        15..=17 => NANOS_PER_MICRO,
+        _ => 1,
+    };
+    // Digits of the fraction below a nanosecond are dropped
+    let digits = unit.ilog10() as usize;
+    let fraction: String = fraction.chars().chain(std::iter::repeat('0')).take(digits).collect();
+    let fraction: u64 = if digits == 0 { 0 } else { fraction.parse().map_err(|_| format!("Invalid epoch timestamp: {:?}", s))? };
+    value
+        .checked_mul(unit)
+        .and_then(|nanos| nanos.checked_add(fraction))
+        .ok_or_else(|| format!("Epoch timestamp out of range: {:?}", s))
+}
+
 /// Precision time parsing for various formats
 pub fn parse_datetime_string(s: &str) -> Result<UnixNanos, String> {
     // Try multiple common formats
@@ -90,6 +192,138 @@ pub fn parse_datetime_string(s: &str) -> Result<UnixNanos, String> {
     Err("Unable to parse datetime string".to_string())
 }
 
+/// How often a `MonotonicClock` checks itself against system time unless configured
+pub const DEFAULT_CALIBRATION_INTERVAL: Duration = Duration::from_secs(1);
+
+/// Most a `MonotonicClock` runs fast or slow to slew towards system time,
+/// in parts per million, as NTP does
+const MAX_SLEW_PPM: i128 = 500;
+
+/// Offsets ahead of system time beyond which a `MonotonicClock` steps to
+/// it rather than slewing, as NTP does
+const STEP_THRESHOLD_NS: i128 = 128_000_000;
+
+#[derive(Debug, Clone, Copy)]
+struct Calibration {
+    instant: Instant,
+    unix: UnixNanos,
+    /// Rate this clock runs ahead of the monotonic clock, in parts per million
+    rate_ppm: i128,
+    /// System time minus this clock's time when last calibrated
+    offset_ns: i64,
+}
+
+impl Calibration {
+    fn at(&self, instant: Instant) -> UnixNanos {
+        let elapsed = instant.saturating_duration_since(self.instant).as_nanos() as i128;
+        self.unix + (elapsed * (1_000_000 + self.rate_ppm) / 1_000_000) as UnixNanos
+    }
+}
+
+/// Wall-clock time that never goes backwards.
+///
+/// Time is read from the monotonic clock, anchored to system time. Each
+/// calibration interval the clock's rate is adjusted by at most 500ppm to
+/// slew towards system time; only a large offset ahead is stepped, so a
+/// step of system time back is slewed out rather than freezing the clock.
+/// Readings are strictly increasing across threads, so they order events
+/// and measure latencies reliably.
+#[derive(Debug)]
+pub struct MonotonicClock {
+    calibration: RwLock<Calibration>,
+    interval: Duration,
+    /// Latest reading handed out, by any thread
+    last: AtomicU64,
+}
+
+impl MonotonicClock {
+    pub fn new() -> Self {
+        Self::with_calibration_interval(DEFAULT_CALIBRATION_INTERVAL)
+    }
+
+    pub fn with_calibration_interval(interval: Duration) -> Self {
+        Self {
+            calibration: RwLock::new(Calibration {
+                instant: Instant::now(),
+                unix: unix_nanos_now(),
+                rate_ppm: 0,
+                offset_ns: 0,
+            }),
+            interval,
+            last: AtomicU64::new(0),
+        }
+    }
+
+    /// Current time, later than every earlier reading
+    pub fn now(&self) -> UnixNanos {
+        let instant = Instant::now();
+        let calibration = *self.calibration.read();
+        let ts = if instant.saturating_duration_since(calibration.instant) >= self.interval {
+            self.calibrate(instant, unix_nanos_now())
+        } else {
+            calibration.at(instant)
+        };
+        self.after_last(ts)
+    }
+
+    /// System time minus this clock's time at the last calibration
+    pub fn offset_ns(&self) -> i64 {
+        self.calibration.read().offset_ns
+    }
+
+    // Re-anchor to `system`, the system time at `instant`, returning the
+    // time at `instant`: stepped forward when far behind, otherwise
+    // unchanged with the rate set to slew out the offset over the next
+    // interval
+    fn calibrate(&self, instant: Instant, system: UnixNanos) -> UnixNanos {
+        let mut calibration = self.calibration.write();
+        // Another thread calibrated since this one looked
+        if instant.saturating_duration_since(calibration.instant) < self.interval {
+            return calibration.at(instant);
+        }
+        let current = calibration.at(instant);
+        let offset = system as i128 - current as i128;
+        let (unix, rate_ppm) = if offset > STEP_THRESHOLD_NS {
+            (system, 0)
+        } else {
+            let interval = self.interval.as_nanos().max(1) as i128;
+            (current, (offset * 1_000_000 / interval).clamp(-MAX_SLEW_PPM, MAX_SLEW_PPM))
+        };
+        *calibration = Calibration {
+            instant,
+            unix,
+            rate_ppm,
+            offset_ns: offset as i64,
+        };
+        unix
+    }
+
+    // `ts`, or just after the last reading if that is not earlier
+    fn after_last(&self, ts: UnixNanos) -> UnixNanos {
+        let mut last = self.last.load(Ordering::Relaxed);
+        loop {
+            let next = ts.max(last + 1);
+            match self.last.compare_exchange_weak(last, next, Ordering::AcqRel, Ordering::Relaxed) {
+                Ok(_) => return next,
+                Err(actual) => last = actual,
+            }
+        }
+    }
+}
+
+impl Default for MonotonicClock {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Current time from a process-wide `MonotonicClock`: strictly increasing
+/// and corrected against system time
+pub fn monotonic_nanos_now() -> UnixNanos {
+    static CLOCK: OnceLock<MonotonicClock> = OnceLock::new();
+    CLOCK.get_or_init(MonotonicClock::new).now()
+}
+
 /// High-resolution timer for performance measurements
 #[derive(Debug, Clone)]
 pub struct PrecisionTimer {
@@ -123,6 +357,7 @@ impl PrecisionTimer {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use std::sync::Arc;
     
     #[test]
     fn test_unix_nanos_conversion() {
@@ -134,6 +369,27 @@ mod tests {
         assert!((now as i64 - converted_back as i64).abs() < 1000);
     }
     
+    #[test]
+    fn test_iso8601_and_epoch_parsing() {
+        let ts = 1_700_000_000_123_456_789;
+        assert_eq!(format_iso8601(ts), "2023-11-14T22:13:20.123456789Z");
+        assert_eq!(format_iso8601(0), "1970-01-01T00:00:00.000000000Z");
+        assert_eq!(parse_datetime_string(&format_iso8601(ts)), Ok(ts));
+        assert_eq!(nanos_to_millis(ts), 1_700_000_000_123);
+        assert_eq!(millis_to_nanos(nanos_to_millis(ts)), 1_700_000_000_123_000_000);
+        assert_eq!(secs_to_nanos(1.5), 1_500_000_000);
+
+        for epoch in ["1700000000.123456789", "1700000000123.456789", "1700000000123456.789", "1700000000123456789"] {
+            assert_eq!(parse_epoch(epoch), Ok(ts));
+        }
+        assert_eq!(parse_epoch("1700000000"), Ok(1_700_000_000_000_000_000));
+        assert_eq!(parse_epoch(" 1700000000.5 "), Ok(1_700_000_000_500_000_000));
+        assert!(parse_epoch("").is_err());
+        assert!(parse_epoch("-1").is_err());
+        assert!(parse_epoch("1.2.3").is_err());
+        assert!(parse_epoch("99999999999999999999").is_err());
+    }
+
     #[test]
     fn test_atomic_time() {
         let atomic_time = AtomicTime::new();
@@ -146,6 +402,97 @@ mod tests {
         assert!(updated > initial);
     }
     
+    #[test]
+    fn test_strictly_increasing_atomic_time() {
+        let time = Arc::new(AtomicTime::strictly_increasing());
+        let start = time.get();
+        // Reading does not advance the time
+        assert_eq!(time.get(), start);
+        assert_eq!(time.get_next(), start + 1);
+        assert_eq!(time.get(), start + 1);
+        // Going back or standing still moves forward instead
+        time.set(start - 1_000);
+        assert_eq!(time.get(), start + 2);
+        time.set(start + 1_000);
+        assert_eq!(time.get(), start + 1_000);
+        
+        let handles: Vec<_> = (0..4)
+            .map(|_| {
+                let time = Arc::clone(&time);
+                std::thread::spawn(move || {
+                    (0..1_000)
+                        .map(|_| {
+                            time.update_now();
+                            time.get_next()
+                        })
+                        .collect::<Vec<_>>()
+                })
+            })
+            .collect();
+        let mut seen = std::collections::HashSet::new();
+        for handle in handles {
+            let values = handle.join().unwrap();
+            assert!(values.windows(2).all(|pair| pair[0] < pair[1]));
+            assert!(values.into_iter().all(|value| seen.insert(value)));
+        }
+        let relaxed = AtomicTime::new();
+        assert!(!relaxed.is_strict());
+        assert_eq!(relaxed.get_next(), relaxed.get());
+    }
+    
+    #[test]
+    fn test_monotonic_clock_corrects_towards_system_time() {
+        let clock = MonotonicClock::with_calibration_interval(Duration::from_millis(10));
+        let start = clock.calibration.read().instant;
+        let base = clock.calibration.read().unix;
+        let at = |ms: u64| start + Duration::from_millis(ms);
+
+        // System time 1ms ahead: the clock runs 500ppm fast from here
+        assert_eq!(clock.calibrate(at(1_000), base + 1_001_000_000), base + 1_000_000_000);
+        assert_eq!(clock.offset_ns(), 1_000_000);
+        assert_eq!(clock.calibration.read().at(at(2_000)), base + 2_000_500_000);
+
+        // Stepped back by an hour: slewed 500ppm slow, never frozen
+        let stepped = base + 2_000_000_000 - 3_600_000_000_000;
+        assert_eq!(clock.calibrate(at(2_000), stepped), base + 2_000_500_000);
+        assert_eq!(clock.calibration.read().at(at(3_000)), base + 3_000_000_000);
+        assert!(clock.calibration.read().at(at(2_001)) > base + 2_000_500_000);
+
+        // An hour ahead is stepped to
+        let ahead = base + 4_000_000_000 + 3_600_000_000_000;
+        assert_eq!(clock.calibrate(at(4_000), ahead), ahead);
+        assert_eq!(clock.calibration.read().at(at(4_001)), ahead + 1_000_000);
+
+        // Always after the last reading
+        assert_eq!(clock.after_last(ahead + 5), ahead + 5);
+        assert_eq!(clock.after_last(ahead), ahead + 6);
+
+        let readings: Vec<UnixNanos> = (0..1000).map(|_| monotonic_nanos_now()).collect();
+        assert!(readings.windows(2).all(|pair| pair[1] > pair[0]));
+        assert!((monotonic_nanos_now() as i64 - unix_nanos_now() as i64).abs() < 1_000_000_000);
+    }
+
+    #[test]
+    fn test_monotonic_clock_increases_across_threads() {
+        let clock = Arc::new(MonotonicClock::new());
+        let handles: Vec<_> = (0..4)
+            .map(|_| {
+                let clock = Arc::clone(&clock);
+                std::thread::spawn(move || (0..1_000).map(|_| clock.now()).collect::<Vec<_>>())
+            })
+            .collect();
+        let mut readings = Vec::new();
+        for handle in handles {
+            let values = handle.join().unwrap();
+            assert!(values.windows(2).all(|pair| pair[0] < pair[1]));
+            readings.extend(values);
+        }
+        // No two threads were handed the same reading
+        let distinct: std::collections::HashSet<_> = readings.iter().collect();
+        assert_eq!(distinct.len(), 4_000);
+        assert!(clock.now() > *readings.iter().max().unwrap());
+    }
+
     #[test]
     fn test_precision_timer() {
         let timer = PrecisionTimer::start();
@@ -0,0 +1,288 @@
+//! AlphaForge Bus Transports
+//!
+//! Bridges selected message bus topics between processes or machines, so a
+//! deployment can be split into e.g. a data node, an execution node and
+//! strategy nodes. A `BusTransport` moves opaque frames between nodes; a
+//! `TransportBridge` exports matching local envelopes through it and
+//! publishes matching envelopes from other nodes on the local bus. Each
+//! frame names the node it came from, so a node ignores its own frames and
+//! never exports an envelope it imported.
+
+use std::collections::{HashSet, VecDeque};
+use std::sync::{Arc, Mutex};
+
+use async_trait::async_trait;
+use serde::{Deserialize, Serialize};
+use tokio::sync::{broadcast, mpsc};
+use tokio::task::JoinHandle;
+
+use crate::error::{AlphaForgeError, Result};
+use crate::message::{MessageEnvelope, TopicPattern};
+use crate::message_bus::{MessageBus, Subscription};
+use crate::uuid::UUID7;
+
+/// Imported envelope IDs remembered to keep them from being exported again
+const IMPORTED_ID_CAPACITY: usize = 10_000;
+
+/// Moves frames between the nodes of a deployment
+#[async_trait]
+pub trait BusTransport: Send + Sync {
+    /// Send a frame for `topic` to every node
+    async fn send(&self, topic: &str, frame: Vec<u8>) -> Result<()>;
+
+    /// Frames sent by any node (possibly including this one) on topics
+    /// matching any of `topics`, as (topic, frame). Frames on other topics
+    /// may be received too; the bridge drops them.
+    async fn receive(&self, topics: &[String]) -> Result<mpsc::UnboundedReceiver<(String, Vec<u8>)>>;
+}
+
+/// An envelope on the wire
+#[derive(Debug, Clone, Serialize, Deserialize)]
+struct TransportFrame {
+    origin: String,
+    envelope: MessageEnvelope,
+}
+
+/// Topics a node shares with the others
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct BridgeConfig {
+    /// Name of this node, unique within the deployment
+    pub node_id: String,
+    /// Topics, or topic patterns, sent to other nodes when published here
+    #[serde(default)]
+    pub export: Vec<String>,
+    /// Topics, or topic patterns, published here when another node sends them
+    #[serde(default)]
+    pub import: Vec<String>,
+}
+
+impl BridgeConfig {
+    pub fn new(node_id: impl Into<String>) -> Self {
+        Self {
+            node_id: node_id.into(),
+            export: Vec::new(),
+            import: Vec::new(),
+        }
+    }
+}
+
+/// Envelope IDs imported recently, oldest evicted first
+#[derive(Default)]
+struct ImportedIds {
+    ids: HashSet<UUID7>,
+    order: VecDeque<UUID7>,
+}
+
+impl ImportedIds {
+    fn insert(&mut self, id: UUID7) {
+        if self.ids.insert(id) {
+            self.order.push_back(id);
+            if self.order.len() > IMPORTED_ID_CAPACITY {
+                if let Some(oldest) = self.order.pop_front() {
+                    self.ids.remove(&oldest);
+                }
+            }
+        }
+    }
+
+    fn contains(&self, id: &UUID7) -> bool {
+        self.ids.contains(id)
+    }
+}
+
+/// Running exchange of topics between a message bus and other nodes
+pub struct TransportBridge {
+    tasks: Vec<JoinHandle<()>>,
+}
+
+impl TransportBridge {
+    /// Start exporting and importing topics through `transport`
+    pub async fn start(
+        config: BridgeConfig,
+        transport: Arc<dyn BusTransport>,
+        message_bus: Arc<MessageBus>,
+    ) -> Result<Self> {
+        if config.node_id.is_empty() {
+            return Err(AlphaForgeError::config("Bridge node ID cannot be empty"));
+        }
+        let export: Vec<TopicPattern> = config.export.iter().map(TopicPattern::new).collect();
+        let import: Vec<TopicPattern> = config.import.iter().map(TopicPattern::new).collect();
+        let imported = Arc::new(Mutex::new(ImportedIds::default()));
+
+        let mut tasks = Vec::new();
+        if !export.is_empty() {
+            // One subscription to everything, so overlapping patterns export once
+            let receiver = message_bus.subscribe_tracked("*");
+            tasks.push(tokio::spawn(Self::export(
+                config.node_id.clone(),
+                export,
+                Arc::clone(&transport),
+                receiver,
+                Arc::clone(&imported),
+            )));
+        }
+        if !import.is_empty() {
+            let frames = transport.receive(&config.import).await?;
+            tasks.push(tokio::spawn(Self::import(config.node_id.clone(), import, frames, message_bus, imported)));
+        }
+        tracing::info!(
+            "Bridge for node {} exporting {:?} and importing {:?}",
+            config.node_id,
+            config.export,
+            config.import
+        );
+        Ok(Self { tasks })
+    }
+
+    /// Stop exporting and importing
+    pub fn stop(self) {
+        for task in self.tasks {
+            task.abort();
+        }
+    }
+
+    async fn export(
+        node_id: String,
+        patterns: Vec<TopicPattern>,
+        transport: Arc<dyn BusTransport>,
+        mut receiver: Subscription,
+        imported: Arc<Mutex<ImportedIds>>,
+    ) {
+        while let Some(envelope) = receiver.recv().await {
+            if !patterns.iter().any(|pattern| pattern.matches(&envelope.message_type)) {
+                continue;
+            }
+            if imported.lock().map(|ids| ids.contains(&envelope.id)).unwrap_or(false) {
+                continue;
+            }
+            let topic = envelope.message_type.clone();
+            let frame = TransportFrame { origin: node_id.clone(), envelope };
+            let result = match bincode::serialize(&frame) {
+                Ok(bytes) => transport.send(&topic, bytes).await,
+                Err(e) => Err(AlphaForgeError::validation(format!("Failed to encode frame: {}", e))),
+            };
+            if let Err(e) = result {
+                tracing::warn!("Failed to export {}: {}", topic, e);
+            }
+        }
+    }
+
+    async fn import(
+        node_id: String,
+        patterns: Vec<TopicPattern>,
+        mut frames: mpsc::UnboundedReceiver<(String, Vec<u8>)>,
+        message_bus: Arc<MessageBus>,
+        imported: Arc<Mutex<ImportedIds>>,
+    ) {
+        while let Some((topic, bytes)) = frames.recv().await {
+            if !patterns.iter().any(|pattern| pattern.matches(&topic)) {
+                continue;
+            }
+            let frame: TransportFrame = match bincode::deserialize(&bytes) {
+                Ok(frame) => frame,
+                Err(e) => {
+                    tracing::warn!("Dropping malformed frame on {}: {}", topic, e);
+                    continue;
+                }
+            };
+            if frame.origin == node_id {
+                continue;
+            }
+            if let Ok(mut ids) = imported.lock() {
+                ids.insert(frame.envelope.id);
+            }
+            message_bus.publish_envelope(&frame.envelope);
+        }
+    }
+}
+
+/// Transport between bridges in one process, for tests and for running a
+/// multi-node deployment in a single binary
+#[derive(Clone)]
+pub struct LocalTransport {
+    sender: broadcast::Sender<(String, Vec<u8>)>,
+}
+
+impl LocalTransport {
+    /// A new network; clone it to connect further nodes
+    pub fn new(capacity: usize) -> Self {
+        let (sender, _) = broadcast::channel(capacity);
+        Self { sender }
+    }
+}
+
+#[async_trait]
+impl BusTransport for LocalTransport {
+    async fn send(&self, topic: &str, frame: Vec<u8>) -> Result<()> {
+        // No receivers yet is not an error: nobody is listening
+        let _ = self.sender.send((topic.to_string(), frame));
+        Ok(())
+    }
+
+    async fn receive(&self, _topics: &[String]) -> Result<mpsc::UnboundedReceiver<(String, Vec<u8>)>> {
+        let mut frames = self.sender.subscribe();
+        let (sender, receiver) = mpsc::unbounded_channel();
+        tokio::spawn(async move {
+            loop {
+                match frames.recv().await {
+                    Ok(frame) => {
+                        if sender.send(frame).is_err() {
+                            break;
+                        }
+                    }
+                    Err(broadcast::error::RecvError::Lagged(skipped)) => {
+                        tracing::warn!("Local transport dropped {} frames", skipped);
+                    }
+                    Err(broadcast::error::RecvError::Closed) => break,
+                }
+            }
+        });
+        Ok(receiver)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::time::Duration;
+
+    #[tokio::test]
+    async fn test_bridges_share_topics_between_nodes() {
+        let network = LocalTransport::new(1_024);
+        let (data_bus, strategy_bus) = (Arc::new(MessageBus::new()), Arc::new(MessageBus::new()));
+
+        let mut data_node = BridgeConfig::new("data");
+        data_node.export = vec!["data.*".to_string()];
+        data_node.import = vec!["orders.*".to_string()];
+        let mut strategy_node = BridgeConfig::new("strategies");
+        strategy_node.export = vec!["orders.*".to_string(), "orders.commands.*".to_string()];
+        strategy_node.import = vec!["data.*".to_string()];
+        let data_bridge = TransportBridge::start(data_node, Arc::new(network.clone()), Arc::clone(&data_bus)).await.unwrap();
+        let strategy_bridge = TransportBridge::start(strategy_node, Arc::new(network), Arc::clone(&strategy_bus)).await.unwrap();
+
+        let mut quotes = strategy_bus.subscribe("data.quotes.*");
+        let mut commands = data_bus.subscribe("orders.*");
+        let mut echoed = data_bus.subscribe("data.*");
+
+        data_bus.publish_from("data_engine", "data.quotes.BTCUSDT", &100.5f64);
+        data_bus.publish_from("data_engine", "risk.limits", &1u8);
+        strategy_bus.publish_from("strategy_engine", "orders.commands.1", &7u64);
+
+        let quote = tokio::time::timeout(Duration::from_secs(5), quotes.recv()).await.unwrap().unwrap();
+        assert_eq!(quote.sender, "data_engine");
+        assert_eq!(bincode::deserialize::<f64>(&quote.payload).unwrap(), 100.5);
+        // Exported once despite matching both patterns
+        let command = tokio::time::timeout(Duration::from_secs(5), commands.recv()).await.unwrap().unwrap();
+        assert_eq!(command.message_type, "orders.commands.1");
+
+        tokio::time::sleep(Duration::from_millis(50)).await;
+        assert!(commands.try_recv().is_err());
+        assert!(quotes.try_recv().is_err());
+        // The data node sees its own quote once: never imported back
+        assert_eq!(echoed.try_recv().unwrap().message_type, "data.quotes.BTCUSDT");
+        assert!(echoed.try_recv().is_err());
+
+        data_bridge.stop();
+        strategy_bridge.stop();
+    }
+}
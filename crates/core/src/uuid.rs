@@ -1,6 +1,9 @@
 //! UUID utilities for AlphaForge
 
+use std::cell::RefCell;
 use std::fmt;
+use std::sync::atomic::{AtomicU32, Ordering};
+use std::sync::Once;
 use serde::{Serialize, Deserialize};
 
 /// UUID v4 implementation optimized for performance
@@ -12,41 +15,8 @@ pub struct UUID4 {
 impl UUID4 {
     /// Generate a new UUID v4
     pub fn new() -> Self {
-        let mut bytes = [0u8; 16];
-        
-        // Use system randomness
-        #[cfg(target_os = "linux")]
-        {
-            use std::fs::File;
-            use std::io::Read;
-            if let Ok(mut f) = File::open("/dev/urandom") {
-                let _ = f.read_exact(&mut bytes);
-            }
-        }
-        
-        #[cfg(not(target_os = "linux"))]
-        {
-            // Fallback to std random
-            use std::collections::hash_map::DefaultHasher;
-            use std::hash::{Hash, Hasher};
-            use std::time::{SystemTime, UNIX_EPOCH};
-            
-            let now = SystemTime::now().duration_since(UNIX_EPOCH).unwrap();
-            let mut hasher = DefaultHasher::new();
-            now.hash(&mut hasher);
-            std::thread::current().id().hash(&mut hasher);
-            
-            let hash = hasher.finish();
-            bytes[0..8].copy_from_slice(&hash.to_le_bytes());
-            
-            // Second hash for remaining bytes
-            let mut hasher2 = DefaultHasher::new();
-            hash.hash(&mut hasher2);
-            std::ptr::addr_of!(bytes).hash(&mut hasher2);
-            let hash2 = hasher2.finish();
-            bytes[8..16].copy_from_slice(&hash2.to_le_bytes());
-        }
-        
+        let mut bytes = random_bytes();
+
         // Set version (4) and variant bits
         bytes[6] = (bytes[6] & 0x0f) | 0x40; // Version 4
         bytes[8] = (bytes[8] & 0x3f) | 0x80; // Variant bits
@@ -64,79 +34,268 @@ impl UUID4 {
         &self.bytes
     }
     
-    /// Convert to hyphenated string
-    pub fn to_string(&self) -> String {
-        format!(
-            "{:02x}{:02x}{:02x}{:02x}-{:02x}{:02x}-{:02x}{:02x}-{:02x}{:02x}-{:02x}{:02x}{:02x}{:02x}{:02x}{:02x}",
-            self.bytes[0], self.bytes[1], self.bytes[2], self.bytes[3],
-            self.bytes[4], self.bytes[5],
-            self.bytes[6], self.bytes[7],
-            self.bytes[8], self.bytes[9],
-            self.bytes[10], self.bytes[11], self.bytes[12], self.bytes[13], self.bytes[14], self.bytes[15]
-        )
+    /// Parse from hyphenated string
+    pub fn parse(s: &str) -> Result<Self, Error> {
+        parse_hyphenated(s).map(|bytes| Self { bytes })
     }
-    
+}
+
+impl fmt::Display for UUID4 {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write_hyphenated(&self.bytes, f)
+    }
+}
+
+impl Default for UUID4 {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// UUID v7: a millisecond Unix timestamp followed by random bits, so
+/// identifiers sort in the order they were generated. IDs generated in the
+/// same millisecond by this process stay ordered through a 12 bit counter.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize)]
+pub struct UUID7 {
+    bytes: [u8; 16],
+}
+
+impl UUID7 {
+    /// Generate a new UUID v7
+    pub fn new() -> Self {
+        use std::sync::atomic::AtomicU64;
+        // Last timestamp in milliseconds, shifted 12 bits, plus counter;
+        // a counter overflow carries into the timestamp
+        static LAST: AtomicU64 = AtomicU64::new(0);
+
+        let now = (crate::time::unix_nanos_now() / 1_000_000) << 12;
+        let mut last = LAST.load(Ordering::Relaxed);
+        let stamp = loop {
+            let next = now.max(last + 1);
+            match LAST.compare_exchange_weak(last, next, Ordering::Relaxed, Ordering::Relaxed) {
+                Ok(_) => break next,
+                Err(actual) => last = actual,
+            }
+        };
+
+        let mut bytes = random_bytes();
+        bytes[..6].copy_from_slice(&(stamp >> 12).to_be_bytes()[2..]);
+        bytes[6] = 0x70 | ((stamp >> 8) & 0x0f) as u8; // Version 7
+        bytes[7] = stamp as u8;
+        bytes[8] = (bytes[8] & 0x3f) | 0x80; // Variant bits
+
+        Self { bytes }
+    }
+
+    /// Create from byte array
+    pub fn from_bytes(bytes: [u8; 16]) -> Self {
+        Self { bytes }
+    }
+
+    /// Get raw bytes
+    pub fn as_bytes(&self) -> &[u8; 16] {
+        &self.bytes
+    }
+
+    /// Unix time in milliseconds the UUID was generated at
+    pub fn timestamp_ms(&self) -> u64 {
+        let mut ms = [0u8; 8];
+        ms[2..].copy_from_slice(&self.bytes[..6]);
+        u64::from_be_bytes(ms)
+    }
+
     /// Parse from hyphenated string
-    pub fn parse(s: &str) -> Result<Self, uuid::Error> {
-        if s.len() != 36 {
-            return Err(uuid::Error::InvalidLength);
+    pub fn parse(s: &str) -> Result<Self, Error> {
+        parse_hyphenated(s).map(|bytes| Self { bytes })
+    }
+}
+
+impl fmt::Display for UUID7 {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write_hyphenated(&self.bytes, f)
+    }
+}
+
+impl Default for UUID7 {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// UUIDs generated from one seed before a thread's generator is reseeded
+const RESEED_INTERVAL: u32 = 1 << 16;
+
+thread_local! {
+    static RNG: RefCell<Rng> = RefCell::new(Rng::from_entropy());
+}
+
+/// xoshiro256++ seeded from OS entropy
+struct Rng {
+    state: [u64; 4],
+    /// Outputs left before the next reseed
+    remaining: u32,
+    /// Process the generator was seeded in; a forked child reseeds rather
+    /// than repeat its parent's UUIDs
+    pid: u32,
+}
+
+impl Rng {
+    fn from_entropy() -> Self {
+        let mut rng = Self {
+            state: [0; 4],
+            remaining: 0,
+            pid: 0,
+        };
+        rng.reseed();
+        rng
+    }
+
+    fn reseed(&mut self) {
+        self.state = os_entropy();
+        // The all-zero state never leaves zero
+        if self.state == [0; 4] {
+            self.state[0] = 1;
         }
-        
+        self.remaining = RESEED_INTERVAL;
+        self.pid = process_id();
+    }
+
+    fn next_u64(&mut self) -> u64 {
+        let s = &mut self.state;
+        let result = s[0].wrapping_add(s[3]).rotate_left(23).wrapping_add(s[0]);
+        let t = s[1] << 17;
+        s[2] ^= s[0];
+        s[3] ^= s[1];
+        s[1] ^= s[2];
+        s[0] ^= s[3];
+        s[2] ^= t;
+        s[3] = s[3].rotate_left(45);
+        result
+    }
+}
+
+/// ID of this process, kept current in forked children
+static PROCESS_ID: AtomicU32 = AtomicU32::new(0);
+
+// The process ID without a system call per UUID
+fn process_id() -> u32 {
+    static WATCH_FORKS: Once = Once::new();
+    WATCH_FORKS.call_once(|| {
+        PROCESS_ID.store(std::process::id(), Ordering::Relaxed);
+        #[cfg(unix)]
+        // SAFETY: the handler only calls getpid, which is async-signal-safe
+        unsafe {
+            libc::pthread_atfork(None, None, Some(update_process_id));
+        }
+    });
+    PROCESS_ID.load(Ordering::Relaxed)
+}
+
+#[cfg(unix)]
+extern "C" fn update_process_id() {
+    PROCESS_ID.store(std::process::id(), Ordering::Relaxed);
+}
+
+// 32 bytes of OS entropy: /dev/urandom where available, otherwise the
+// randomly keyed std hasher
+fn os_entropy() -> [u64; 4] {
+    let mut seed = [0u64; 4];
+    #[cfg(unix)]
+    {
+        use std::fs::File;
+        use std::io::Read;
+        let mut bytes = [0u8; 32];
+        if File::open("/dev/urandom").and_then(|mut f| f.read_exact(&mut bytes)).is_ok() {
+            for (word, chunk) in seed.iter_mut().zip(bytes.chunks_exact(8)) {
+                *word = u64::from_le_bytes(chunk.try_into().unwrap());
+            }
+            return seed;
+        }
+    }
+    use std::collections::hash_map::RandomState;
+    use std::hash::{BuildHasher, Hasher};
+    for (i, word) in seed.iter_mut().enumerate() {
+        let mut hasher = RandomState::new().build_hasher();
+        hasher.write_usize(i);
+        *word = hasher.finish();
+    }
+    seed
+}
+
+// 16 random bytes from the thread's generator
+fn random_bytes() -> [u8; 16] {
+    RNG.with(|rng| {
+        let mut rng = rng.borrow_mut();
+        if rng.remaining == 0 || rng.pid != process_id() {
+            rng.reseed();
+        }
+        rng.remaining -= 1;
         let mut bytes = [0u8; 16];
-        let mut byte_idx = 0;
-        
-        for (i, chunk) in s.split('-').enumerate() {
-            match i {
-                0 => { // 8 chars
-                    if chunk.len() != 8 { return Err(uuid::Error::InvalidFormat); }
-                    for j in (0..8).step_by(2) {
-                        bytes[byte_idx] = u8::from_str_radix(&chunk[j..j+2], 16)
-                            .map_err(|_| uuid::Error::InvalidCharacter)?;
-                        byte_idx += 1;
-                    }
+        bytes[..8].copy_from_slice(&rng.next_u64().to_le_bytes());
+        bytes[8..].copy_from_slice(&rng.next_u64().to_le_bytes());
+        bytes
+    })
+}
+
+fn parse_hyphenated(s: &str) -> Result<[u8; 16], Error> {
+    if s.len() != 36 {
+        return Err(Error::InvalidLength);
+    }
+    
+    let mut bytes = [0u8; 16];
+    let mut byte_idx = 0;
+    
+    for (i, chunk) in s.split('-').enumerate() {
+        match i {
+            0 => { // 8 chars
+                if chunk.len() != 8 { return Err(Error::InvalidFormat); }
+                for j in (0..8).step_by(2) {
+                    bytes[byte_idx] = u8::from_str_radix(&chunk[j..j+2], 16)
+                        .map_err(|_| Error::InvalidCharacter)?;
+                    byte_idx += 1;
                 }
-                1 | 2 => { // 4 chars each
-                    if chunk.len() != 4 { return Err(uuid::Error::InvalidFormat); }
-                    for j in (0..4).step_by(2) {
-                        bytes[byte_idx] = u8::from_str_radix(&chunk[j..j+2], 16)
-                            .map_err(|_| uuid::Error::InvalidCharacter)?;
-                        byte_idx += 1;
-                    }
+            }
+            1 | 2 => { // 4 chars each
+                if chunk.len() != 4 { return Err(Error::InvalidFormat); }
+                for j in (0..4).step_by(2) {
+                    bytes[byte_idx] = u8::from_str_radix(&chunk[j..j+2], 16)
+                        .map_err(|_| Error::InvalidCharacter)?;
+                    byte_idx += 1;
                 }
-                3 => { // 4 chars
-                    if chunk.len() != 4 { return Err(uuid::Error::InvalidFormat); }
-                    for j in (0..4).step_by(2) {
-                        bytes[byte_idx] = u8::from_str_radix(&chunk[j..j+2], 16)
-                            .map_err(|_| uuid::Error::InvalidCharacter)?;
-                        byte_idx += 1;
-                    }
+            }
+            3 => { // 4 chars
+                if chunk.len() != 4 { return Err(Error::InvalidFormat); }
+                for j in (0..4).step_by(2) {
+                    bytes[byte_idx] = u8::from_str_radix(&chunk[j..j+2], 16)
+                        .map_err(|_| Error::InvalidCharacter)?;
+                    byte_idx += 1;
                 }
-                4 => { // 12 chars
-                    if chunk.len() != 12 { return Err(uuid::Error::InvalidFormat); }
-                    for j in (0..12).step_by(2) {
-                        bytes[byte_idx] = u8::from_str_radix(&chunk[j..j+2], 16)
-                            .map_err(|_| uuid::Error::InvalidCharacter)?;
-                        byte_idx += 1;
-                    }
+            }
+            4 => { // 12 chars
+                if chunk.len() != 12 { return Err(Error::InvalidFormat); }
+                for j in (0..12).step_by(2) {
+                    bytes[byte_idx] = u8::from_str_radix(&chunk[j..j+2], 16)
+                        .map_err(|_| Error::InvalidCharacter)?;
+                    byte_idx += 1;
                 }
-                _ => return Err(uuid::Error::InvalidFormat),
             }
+            _ => return Err(Error::InvalidFormat),
         }
-        
-        Ok(Self { bytes })
     }
+    
+    Ok(bytes)
 }
 
-impl Default for UUID4 {
-    fn default() -> Self {
-        Self::new()
-    }
-}
-
-impl fmt::Display for UUID4 {
-    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
-        write!(f, "{}", self.to_string())
-    }
+fn write_hyphenated(bytes: &[u8; 16], f: &mut fmt::Formatter<'_>) -> fmt::Result {
+    write!(
+        f,
+        "{:02x}{:02x}{:02x}{:02x}-{:02x}{:02x}-{:02x}{:02x}-{:02x}{:02x}-{:02x}{:02x}{:02x}{:02x}{:02x}{:02x}",
+        bytes[0], bytes[1], bytes[2], bytes[3],
+        bytes[4], bytes[5],
+        bytes[6], bytes[7],
+        bytes[8], bytes[9],
+        bytes[10], bytes[11], bytes[12], bytes[13], bytes[14], bytes[15]
+    )
 }
 
 /// UUID error types
@@ -151,6 +310,8 @@ pub enum Error {
 }
 
 // Create a module alias for compatibility
+#[deprecated(note = "use `uuid::Error` directly")]
+#[allow(clippy::module_inception)]
 pub mod uuid {
     pub use super::Error;
 }
@@ -199,4 +360,46 @@ mod tests {
         println!("UUID generation: {:.0} ops/sec", ops_per_sec);
         assert!(ops_per_sec > 100_000.0); // Should be >100k ops/sec
     }
+    
+    #[test]
+    fn test_uuid7_ordering() {
+        let before = crate::time::unix_nanos_now() / 1_000_000;
+        let ids: Vec<UUID7> = (0..10_000).map(|_| UUID7::new()).collect();
+
+        // Strictly increasing, even within a millisecond
+        assert!(ids.windows(2).all(|pair| pair[0] < pair[1]));
+        assert!(ids.windows(2).all(|pair| pair[0].to_string() < pair[1].to_string()));
+        assert_eq!(ids[0].bytes[6] & 0xf0, 0x70); // Version 7
+        assert_eq!(ids[0].bytes[8] & 0xc0, 0x80); // Variant bits
+        assert!(ids[0].timestamp_ms() >= before);
+        assert_eq!(UUID7::parse(&ids[0].to_string()).unwrap(), ids[0]);
+    }
+    
+    #[test]
+    fn test_thread_rng_reseeds() {
+        RNG.with(|rng| rng.borrow_mut().remaining = 1);
+        let ids: std::collections::HashSet<UUID4> = (0..1_000).map(|_| UUID4::new()).collect();
+        assert_eq!(ids.len(), 1_000);
+        RNG.with(|rng| assert_eq!(rng.borrow().remaining, RESEED_INTERVAL - 999));
+
+        // Threads draw from their own generators
+        let other = std::thread::spawn(UUID4::new).join().unwrap();
+        assert!(!ids.contains(&other));
+
+        // A forked child sees another process ID and reseeds
+        RNG.with(|rng| rng.borrow_mut().pid ^= 1);
+        UUID4::new();
+        RNG.with(|rng| {
+            let rng = rng.borrow();
+            assert_eq!((rng.pid, rng.remaining), (std::process::id(), RESEED_INTERVAL - 1));
+        });
+
+        // Children learn their process ID from the fork handler
+        #[cfg(unix)]
+        {
+            PROCESS_ID.store(0, Ordering::Relaxed);
+            update_process_id();
+            assert_eq!(process_id(), std::process::id());
+        }
+    }
 }
@@ -0,0 +1,281 @@
+//! AlphaForge Market Data Validation
+//!
+//! Sanity checks applied by the Data Engine before ticks reach caches and
+//! aggregators, with per-instrument violation counters.
+
+use std::collections::HashMap;
+use std::fmt::{self, Display};
+
+use serde::{Deserialize, Serialize};
+
+use crate::data::{QuoteTick, TradeTick};
+use crate::identifiers::InstrumentId;
+
+/// Validation settings
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct ValidationConfig {
+    /// Maximum relative move versus the previous accepted price
+    /// (e.g. `0.2` = 20%); `None` disables the jump check
+    pub max_price_jump: Option<f64>,
+    /// Consecutive jump violations after which the new level is accepted
+    /// as genuine and becomes the reference price
+    pub jump_reanchor_after: u32,
+    /// Treat locked quotes (bid == ask) as violations
+    pub reject_locked_quotes: bool,
+    /// Count violations but let the data through
+    pub flag_only: bool,
+}
+
+impl Default for ValidationConfig {
+    fn default() -> Self {
+        Self {
+            max_price_jump: Some(0.5),
+            jump_reanchor_after: 3,
+            reject_locked_quotes: false,
+            flag_only: false,
+        }
+    }
+}
+
+/// Reason a tick failed validation
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ValidationViolation {
+    /// NaN or infinite price
+    NonFinitePrice,
+    /// Zero, negative or non-finite size
+    InvalidSize,
+    /// Bid above ask
+    CrossedQuote,
+    /// Bid equal to ask
+    LockedQuote,
+    /// Price moved more than `max_price_jump` from the previous tick
+    PriceJump,
+}
+
+impl Display for ValidationViolation {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let reason = match self {
+            ValidationViolation::NonFinitePrice => "non-finite price",
+            ValidationViolation::InvalidSize => "invalid size",
+            ValidationViolation::CrossedQuote => "crossed quote",
+            ValidationViolation::LockedQuote => "locked quote",
+            ValidationViolation::PriceJump => "price jump",
+        };
+        write!(f, "{}", reason)
+    }
+}
+
+/// Violation counters for a single instrument
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct ViolationCounts {
+    pub non_finite_price: u64,
+    pub invalid_size: u64,
+    pub crossed_quote: u64,
+    pub locked_quote: u64,
+    pub price_jump: u64,
+}
+
+impl ViolationCounts {
+    /// Total violations across all kinds
+    pub fn total(&self) -> u64 {
+        self.non_finite_price
+            + self.invalid_size
+            + self.crossed_quote
+            + self.locked_quote
+            + self.price_jump
+    }
+
+    fn record(&mut self, violation: ValidationViolation) {
+        match violation {
+            ValidationViolation::NonFinitePrice => self.non_finite_price += 1,
+            ValidationViolation::InvalidSize => self.invalid_size += 1,
+            ValidationViolation::CrossedQuote => self.crossed_quote += 1,
+            ValidationViolation::LockedQuote => self.locked_quote += 1,
+            ValidationViolation::PriceJump => self.price_jump += 1,
+        }
+    }
+}
+
+/// Reference price used by the jump check
+#[derive(Debug, Clone, Copy, Default)]
+struct ReferencePrice {
+    price: Option<f64>,
+    pending_jumps: u32,
+}
+
+/// Validates trade and quote ticks, tracking violations per instrument
+#[derive(Debug)]
+pub struct DataValidator {
+    config: ValidationConfig,
+    reference: HashMap<InstrumentId, ReferencePrice>,
+    violations: HashMap<InstrumentId, ViolationCounts>,
+}
+
+impl DataValidator {
+    pub fn new(config: ValidationConfig) -> Self {
+        Self {
+            config,
+            reference: HashMap::new(),
+            violations: HashMap::new(),
+        }
+    }
+
+    /// Validate a trade tick.
+    ///
+    /// Returns the violation if the tick should be rejected; with
+    /// `flag_only` violations are counted and `Ok` is returned.
+    pub fn validate_trade(&mut self, tick: &TradeTick) -> Result<(), ValidationViolation> {
+        let result = if !tick.price.is_finite() {
+            Err(ValidationViolation::NonFinitePrice)
+        } else if !tick.size.is_finite() || tick.size <= 0.0 {
+            Err(ValidationViolation::InvalidSize)
+        } else {
+            self.check_jump(tick.instrument_id, tick.price)
+        };
+        self.finish(tick.instrument_id, result)
+    }
+
+    /// Validate a quote tick; the jump check uses the mid price
+    pub fn validate_quote(&mut self, tick: &QuoteTick) -> Result<(), ValidationViolation> {
+        let result = if !tick.bid_price.is_finite() || !tick.ask_price.is_finite() {
+            Err(ValidationViolation::NonFinitePrice)
+        } else if !tick.bid_size.is_finite()
+            || !tick.ask_size.is_finite()
+            || tick.bid_size < 0.0
+            || tick.ask_size < 0.0
+        {
+            Err(ValidationViolation::InvalidSize)
+        } else if tick.bid_price > tick.ask_price {
+            Err(ValidationViolation::CrossedQuote)
+        } else if self.config.reject_locked_quotes && tick.bid_price == tick.ask_price {
+            Err(ValidationViolation::LockedQuote)
+        } else {
+            self.check_jump(tick.instrument_id, (tick.bid_price + tick.ask_price) / 2.0)
+        };
+        self.finish(tick.instrument_id, result)
+    }
+
+    /// Violation counters for an instrument
+    pub fn violations(&self, instrument_id: InstrumentId) -> Option<&ViolationCounts> {
+        self.violations.get(&instrument_id)
+    }
+
+    /// Forget reference prices and counters
+    pub fn clear(&mut self) {
+        self.reference.clear();
+        self.violations.clear();
+    }
+
+    fn check_jump(&mut self, instrument_id: InstrumentId, price: f64) -> Result<(), ValidationViolation> {
+        let reference = self.reference.entry(instrument_id).or_default();
+
+        if let (Some(max_jump), Some(last)) = (self.config.max_price_jump, reference.price) {
+            let is_jump = last != 0.0 && ((price - last) / last).abs() > max_jump;
+            if is_jump {
+                reference.pending_jumps += 1;
+                if reference.pending_jumps < self.config.jump_reanchor_after {
+                    return Err(ValidationViolation::PriceJump);
+                }
+            }
+        }
+
+        reference.price = Some(price);
+        reference.pending_jumps = 0;
+        Ok(())
+    }
+
+    fn finish(
+        &mut self,
+        instrument_id: InstrumentId,
+        result: Result<(), ValidationViolation>,
+    ) -> Result<(), ValidationViolation> {
+        let Err(violation) = result else {
+            return Ok(());
+        };
+
+        self.violations.entry(instrument_id).or_default().record(violation);
+        if self.config.flag_only {
+            Ok(())
+        } else {
+            Err(violation)
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::data::AggressorSide;
+
+    fn trade(price: f64, size: f64) -> TradeTick {
+        TradeTick {
+            instrument_id: InstrumentId::new(1),
+            price,
+            size,
+            aggressor_side: AggressorSide::Buyer,
+            trade_id: "1".to_string(),
+            ts_event: 0,
+            ts_init: 0,
+        }
+    }
+
+    fn quote(bid: f64, ask: f64) -> QuoteTick {
+        QuoteTick {
+            instrument_id: InstrumentId::new(1),
+            bid_price: bid,
+            ask_price: ask,
+            bid_size: 1.0,
+            ask_size: 1.0,
+            ts_event: 0,
+            ts_init: 0,
+        }
+    }
+
+    #[test]
+    fn test_rejects_bad_ticks() {
+        let mut validator = DataValidator::new(ValidationConfig {
+            reject_locked_quotes: true,
+            ..Default::default()
+        });
+
+        assert_eq!(validator.validate_trade(&trade(f64::NAN, 1.0)), Err(ValidationViolation::NonFinitePrice));
+        assert_eq!(validator.validate_trade(&trade(100.0, 0.0)), Err(ValidationViolation::InvalidSize));
+        assert_eq!(validator.validate_quote(&quote(101.0, 100.0)), Err(ValidationViolation::CrossedQuote));
+        assert_eq!(validator.validate_quote(&quote(100.0, 100.0)), Err(ValidationViolation::LockedQuote));
+        assert!(validator.validate_quote(&quote(99.0, 101.0)).is_ok());
+
+        let counts = validator.violations(InstrumentId::new(1)).unwrap();
+        assert_eq!(counts.total(), 4);
+        assert_eq!(counts.crossed_quote, 1);
+    }
+
+    #[test]
+    fn test_price_jump_reanchors() {
+        let mut validator = DataValidator::new(ValidationConfig {
+            max_price_jump: Some(0.1),
+            jump_reanchor_after: 2,
+            ..Default::default()
+        });
+
+        assert!(validator.validate_trade(&trade(100.0, 1.0)).is_ok());
+        assert_eq!(validator.validate_trade(&trade(150.0, 1.0)), Err(ValidationViolation::PriceJump));
+        assert!(validator.validate_trade(&trade(101.0, 1.0)).is_ok());
+
+        // A sustained move is accepted on the second consecutive jump
+        assert!(validator.validate_trade(&trade(150.0, 1.0)).is_err());
+        assert!(validator.validate_trade(&trade(151.0, 1.0)).is_ok());
+        assert!(validator.validate_trade(&trade(152.0, 1.0)).is_ok());
+    }
+
+    #[test]
+    fn test_flag_only_counts_without_rejecting() {
+        let mut validator = DataValidator::new(ValidationConfig {
+            flag_only: true,
+            ..Default::default()
+        });
+
+        assert!(validator.validate_trade(&trade(100.0, -1.0)).is_ok());
+        assert_eq!(validator.violations(InstrumentId::new(1)).unwrap().invalid_size, 1);
+    }
+}
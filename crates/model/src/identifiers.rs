@@ -2,7 +2,7 @@
 
 use std::fmt;
 use serde::{Serialize, Deserialize};
-use alphaforge_core::uuid::UUID4;
+use alphaforge_core::uuid::UUID7;
 
 /// Instrument identifier
 #[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
@@ -131,10 +131,10 @@ impl ClientOrderId {
         })
     }
     
-    /// Generate a new UUID-based client order ID
+    /// Generate a new time-ordered UUID v7 client order ID
     pub fn generate() -> Self {
         Self {
-            value: UUID4::new().to_string(),
+            value: UUID7::new().to_string(),
         }
     }
     
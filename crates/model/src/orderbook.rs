@@ -68,7 +68,7 @@ impl Price {
     
     pub fn checked_mul_f64(self, factor: f64) -> Option<Self> {
         let result = (self.0 as f64 * factor).round() as i64;
-        if result > 0 && result <= i64::MAX {
+        if result > 0 {
             Some(Self(result))
         } else {
             None
@@ -188,8 +188,8 @@ impl OrderBook {
         self.ts_last = ts_event;
         
         let price_level = match order_side {
-            OrderSide::Buy => self.bids.entry(order_price).or_insert_with(VecDeque::new),
-            OrderSide::Sell => self.asks.entry(order_price).or_insert_with(VecDeque::new),
+            OrderSide::Buy => self.bids.entry(order_price).or_default(),
+            OrderSide::Sell => self.asks.entry(order_price).or_default(),
         };
         
         price_level.push_back(order);
@@ -414,6 +414,7 @@ mod tests {
     }
     
     #[test]
+    #[allow(clippy::inconsistent_digit_grouping)]
     fn test_order_book_basic_operations() {
         let instrument_id = InstrumentId::new("BTCUSD.BINANCE").unwrap();
         let mut book = OrderBook::new(instrument_id);
@@ -0,0 +1,354 @@
+//! Binance market data feed handler
+//!
+//! Parses Binance `aggTrade`, `bookTicker` and diff-depth streams into core
+//! data types and pushes them into a Data Engine ingestion channel.
+
+use std::sync::Arc;
+use std::sync::atomic::{AtomicU64, Ordering};
+use serde::Deserialize;
+use serde_json::Value;
+use tracing::{debug, warn};
+
+use alphaforge_core::data::*;
+use alphaforge_core::data_channel::DataSender;
+use alphaforge_core::error::{AlphaForgeError, Result};
+use alphaforge_core::identifiers::InstrumentId;
+use alphaforge_core::time::{millis_to_nanos, unix_nanos_now};
+
+use crate::websocket::{MessageHandler, WebSocketClient, WebSocketConfig, WsMessage};
+
+/// Venue name used when building instrument IDs
+pub const BINANCE_VENUE: &str = "BINANCE";
+
+/// Binance feed handler configuration
+#[derive(Debug, Clone)]
+pub struct BinanceFeedConfig {
+    /// Combined stream endpoint
+    pub url: String,
+    /// Symbols to subscribe, e.g. `BTCUSDT`
+    pub symbols: Vec<String>,
+    /// Subscribe to `aggTrade` streams
+    pub trades: bool,
+    /// Subscribe to `bookTicker` streams
+    pub quotes: bool,
+    /// Subscribe to `depth@100ms` diff streams
+    pub depth: bool,
+    /// Underlying connection settings (`url` and `subscriptions` are filled in)
+    pub websocket: WebSocketConfig,
+}
+
+impl Default for BinanceFeedConfig {
+    fn default() -> Self {
+        Self {
+            url: "wss://stream.binance.com:9443/stream".to_string(),
+            symbols: Vec::new(),
+            trades: true,
+            quotes: true,
+            depth: false,
+            websocket: WebSocketConfig::default(),
+        }
+    }
+}
+
+impl BinanceFeedConfig {
+    /// Stream names for the configured symbols
+    pub fn stream_names(&self) -> Vec<String> {
+        let mut streams = Vec::new();
+        for symbol in &self.symbols {
+            let symbol = symbol.to_lowercase();
+            if self.trades {
+                streams.push(format!("{}@aggTrade", symbol));
+            }
+            if self.quotes {
+                streams.push(format!("{}@bookTicker", symbol));
+            }
+            if self.depth {
+                streams.push(format!("{}@depth@100ms", symbol));
+            }
+        }
+        streams
+    }
+
+    /// `SUBSCRIBE` request for the configured streams
+    pub fn subscribe_message(&self) -> String {
+        serde_json::json!({
+            "method": "SUBSCRIBE",
+            "params": self.stream_names(),
+            "id": 1,
+        })
+        .to_string()
+    }
+}
+
+/// Instrument ID for a Binance symbol
+pub fn instrument_id(symbol: &str) -> InstrumentId {
+    InstrumentId::from_symbol_venue(&symbol.to_uppercase(), BINANCE_VENUE)
+}
+
+#[derive(Debug, Deserialize)]
+struct AggTrade {
+    #[serde(rename = "s")]
+    symbol: String,
+    #[serde(rename = "a")]
+    agg_trade_id: u64,
+    #[serde(rename = "p")]
+    price: String,
+    #[serde(rename = "q")]
+    quantity: String,
+    #[serde(rename = "T")]
+    trade_time: u64,
+    #[serde(rename = "m")]
+    buyer_is_maker: bool,
+}
+
+#[derive(Debug, Deserialize)]
+struct BookTicker {
+    #[serde(rename = "s")]
+    symbol: String,
+    #[serde(rename = "b")]
+    bid_price: String,
+    #[serde(rename = "B")]
+    bid_qty: String,
+    #[serde(rename = "a")]
+    ask_price: String,
+    #[serde(rename = "A")]
+    ask_qty: String,
+    /// Only present on futures streams
+    #[serde(rename = "T")]
+    transaction_time: Option<u64>,
+}
+
+#[derive(Debug, Deserialize)]
+struct DepthUpdate {
+    #[serde(rename = "E")]
+    event_time: u64,
+    #[serde(rename = "s")]
+    symbol: String,
+    #[serde(rename = "u")]
+    final_update_id: u64,
+    #[serde(rename = "b")]
+    bids: Vec<[String; 2]>,
+    #[serde(rename = "a")]
+    asks: Vec<[String; 2]>,
+}
+
+fn parse_number(value: &str) -> Result<f64> {
+    value
+        .parse()
+        .map_err(|_| AlphaForgeError::validation(format!("Invalid number: {}", value)))
+}
+
+/// Parse one Binance stream message into core data types.
+///
+/// Accepts both raw and combined (`{"stream": ..., "data": ...}`) payloads.
+/// Control responses such as subscription acknowledgements yield no data.
+pub fn parse_message(text: &str) -> Result<Option<MarketData>> {
+    let mut value: Value = serde_json::from_str(text)?;
+    if let Some(data) = value.get_mut("data") {
+        value = data.take();
+    }
+
+    let ts_init = unix_nanos_now();
+
+    match value.get("e").and_then(Value::as_str) {
+        Some("aggTrade") => {
+            let trade: AggTrade = serde_json::from_value(value)?;
+            Ok(Some(MarketData::Trade(TradeTick {
+                instrument_id: instrument_id(&trade.symbol),
+                price: parse_number(&trade.price)?,
+                size: parse_number(&trade.quantity)?,
+                // The maker was the buyer, so the seller crossed the spread
+                aggressor_side: if trade.buyer_is_maker {
+                    AggressorSide::Seller
+                } else {
+                    AggressorSide::Buyer
+                },
+                trade_id: trade.agg_trade_id.to_string(),
+                ts_event: millis_to_nanos(trade.trade_time),
+                ts_init,
+            })))
+        }
+        Some("depthUpdate") => {
+            let depth: DepthUpdate = serde_json::from_value(value)?;
+            let ts = millis_to_nanos(depth.event_time);
+
+            let mut deltas = Vec::with_capacity(depth.bids.len() + depth.asks.len());
+            for (side, levels) in [(BookSide::Bid, &depth.bids), (BookSide::Ask, &depth.asks)] {
+                for [price, size] in levels {
+                    let size = parse_number(size)?;
+                    deltas.push(OrderBookDelta {
+                        side,
+                        // Binance sends absolute level sizes; zero removes the level
+                        action: if size == 0.0 { DeltaAction::Delete } else { DeltaAction::Update },
+                        price: parse_number(price)?,
+                        size,
+                        order_id: None,
+                        ts,
+                    });
+                }
+            }
+
+            Ok(Some(MarketData::BookDeltas(OrderBookDeltas {
+                instrument_id: instrument_id(&depth.symbol),
+                deltas,
+                sequence_number: depth.final_update_id,
+                ts_last_update: ts,
+            })))
+        }
+        Some("bookTicker") | None if value.get("b").is_some() && value.get("A").is_some() => {
+            // Spot bookTicker payloads carry no event type or timestamp
+            let ticker: BookTicker = serde_json::from_value(value)?;
+            Ok(Some(MarketData::Quote(QuoteTick {
+                instrument_id: instrument_id(&ticker.symbol),
+                bid_price: parse_number(&ticker.bid_price)?,
+                ask_price: parse_number(&ticker.ask_price)?,
+                bid_size: parse_number(&ticker.bid_qty)?,
+                ask_size: parse_number(&ticker.ask_qty)?,
+                ts_event: ticker.transaction_time.map(millis_to_nanos).unwrap_or(ts_init),
+                ts_init,
+            })))
+        }
+        _ => Ok(None),
+    }
+}
+
+/// Live Binance market data feed
+pub struct BinanceFeedHandler {
+    client: WebSocketClient,
+    parse_errors: Arc<AtomicU64>,
+    rejected: Arc<AtomicU64>,
+}
+
+impl BinanceFeedHandler {
+    /// Connect and start pushing parsed data into the ingestion channel.
+    ///
+    /// Data is pushed with `try_send` so a slow engine never stalls the
+    /// socket reader; pair with a non-blocking `BackpressurePolicy` or size
+    /// the buffer generously, and watch `rejected_count`.
+    pub fn connect(config: BinanceFeedConfig, sender: DataSender) -> Self {
+        let parse_errors = Arc::new(AtomicU64::new(0));
+        let rejected = Arc::new(AtomicU64::new(0));
+
+        let handler: MessageHandler = {
+            let parse_errors = Arc::clone(&parse_errors);
+            let rejected = Arc::clone(&rejected);
+            Arc::new(move |message| {
+                let WsMessage::Text(text) = message else {
+                    return;
+                };
+                match parse_message(&text) {
+                    Ok(Some(data)) => {
+                        if let Err(e) = sender.try_send(data) {
+                            rejected.fetch_add(1, Ordering::Relaxed);
+                            debug!("Binance data rejected by channel: {}", e);
+                        }
+                    }
+                    Ok(None) => {}
+                    Err(e) => {
+                        parse_errors.fetch_add(1, Ordering::Relaxed);
+                        warn!("Failed to parse Binance message: {}", e);
+                    }
+                }
+            })
+        };
+
+        let websocket = WebSocketConfig {
+            url: config.url.clone(),
+            subscriptions: vec![config.subscribe_message()],
+            ..config.websocket
+        };
+
+        Self {
+            client: WebSocketClient::connect(websocket, handler),
+            parse_errors,
+            rejected,
+        }
+    }
+
+    /// Check if the feed currently holds an open connection
+    pub fn is_connected(&self) -> bool {
+        self.client.is_connected()
+    }
+
+    /// Number of messages that failed to parse
+    pub fn parse_error_count(&self) -> u64 {
+        self.parse_errors.load(Ordering::Relaxed)
+    }
+
+    /// Number of parsed items the ingestion channel refused
+    pub fn rejected_count(&self) -> u64 {
+        self.rejected.load(Ordering::Relaxed)
+    }
+
+    /// Close the feed
+    pub async fn close(self) {
+        self.client.close().await;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_agg_trade() {
+        let msg = r#"{"stream":"btcusdt@aggTrade","data":{"e":"aggTrade","E":1672515782136,"s":"BTCUSDT","a":12345,"p":"16500.10","q":"0.25","f":100,"l":105,"T":1672515782134,"m":true,"M":true}}"#;
+
+        let Some(MarketData::Trade(tick)) = parse_message(msg).unwrap() else {
+            panic!("expected trade");
+        };
+        assert_eq!(tick.instrument_id, instrument_id("BTCUSDT"));
+        assert_eq!(tick.price, 16500.10);
+        assert_eq!(tick.size, 0.25);
+        assert!(matches!(tick.aggressor_side, AggressorSide::Seller));
+        assert_eq!(tick.trade_id, "12345");
+        assert_eq!(tick.ts_event, 1_672_515_782_134_000_000);
+    }
+
+    #[test]
+    fn test_parse_book_ticker() {
+        let msg = r#"{"u":400900217,"s":"BNBUSDT","b":"25.35190000","B":"31.21000000","a":"25.36520000","A":"40.66000000"}"#;
+
+        let Some(MarketData::Quote(quote)) = parse_message(msg).unwrap() else {
+            panic!("expected quote");
+        };
+        assert_eq!(quote.instrument_id, instrument_id("bnbusdt"));
+        assert_eq!(quote.bid_price, 25.3519);
+        assert_eq!(quote.ask_size, 40.66);
+    }
+
+    #[test]
+    fn test_parse_depth_update() {
+        let msg = r#"{"e":"depthUpdate","E":1672515782136,"s":"BNBBTC","U":157,"u":160,"b":[["0.0024","10"]],"a":[["0.0026","100"],["0.0027","0"]]}"#;
+
+        let Some(MarketData::BookDeltas(book)) = parse_message(msg).unwrap() else {
+            panic!("expected deltas");
+        };
+        assert_eq!(book.sequence_number, 160);
+        assert_eq!(book.deltas.len(), 3);
+        assert_eq!(book.deltas[0].side, BookSide::Bid);
+        assert_eq!(book.deltas[0].action, DeltaAction::Update);
+        assert_eq!(book.deltas[2].side, BookSide::Ask);
+        assert_eq!(book.deltas[2].action, DeltaAction::Delete);
+    }
+
+    #[test]
+    fn test_subscription_ack_is_ignored() {
+        assert!(parse_message(r#"{"result":null,"id":1}"#).unwrap().is_none());
+        assert!(parse_message("not json").is_err());
+    }
+
+    #[test]
+    fn test_subscribe_message() {
+        let config = BinanceFeedConfig {
+            symbols: vec!["BTCUSDT".to_string()],
+            depth: true,
+            ..Default::default()
+        };
+        assert_eq!(
+            config.stream_names(),
+            vec!["btcusdt@aggTrade", "btcusdt@bookTicker", "btcusdt@depth@100ms"]
+        );
+        assert!(config.subscribe_message().contains("\"SUBSCRIBE\""));
+    }
+}
@@ -0,0 +1,13 @@
+//! AlphaForge Network
+//!
+//! Reusable network clients for venue connectivity.
+//!
+//! Venue feed handlers build on these clients instead of reimplementing
+//! connection management, reconnection and heartbeating.
+
+pub mod websocket;
+pub mod binance;
+
+// Re-export commonly used types
+pub use websocket::{MessageHandler, WebSocketClient, WebSocketConfig, WsMessage};
+pub use binance::{BinanceFeedConfig, BinanceFeedHandler};
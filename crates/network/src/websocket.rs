@@ -0,0 +1,404 @@
+//! Generic WebSocket client
+//!
+//! Maintains a single WebSocket connection on a background task: connects,
+//! replays subscription messages, heartbeats with pings, and reconnects with
+//! exponential backoff whenever the connection drops. Every inbound data
+//! frame is routed to a parser callback.
+
+use std::sync::Arc;
+use std::sync::atomic::{AtomicBool, AtomicU64, Ordering};
+use std::time::Duration;
+use futures::{SinkExt, StreamExt};
+use tokio::sync::mpsc;
+use tokio::task::JoinHandle;
+use tokio::time::Instant;
+use tokio_tungstenite::connect_async;
+use tokio_tungstenite::tungstenite::Message;
+use tracing::{debug, info, warn};
+
+use alphaforge_core::error::{AlphaForgeError, Result};
+
+/// WebSocket client configuration
+#[derive(Debug, Clone)]
+pub struct WebSocketConfig {
+    /// Endpoint URL (`ws://` or `wss://`)
+    pub url: String,
+    /// Messages sent after every (re)connect, e.g. stream subscriptions
+    pub subscriptions: Vec<String>,
+    /// Interval between client pings
+    pub heartbeat_interval: Duration,
+    /// Connection is considered stale after this long without inbound frames
+    pub idle_timeout: Duration,
+    /// Delay before the first reconnect attempt
+    pub initial_backoff: Duration,
+    /// Upper bound for the reconnect delay
+    pub max_backoff: Duration,
+    /// Give up after this many consecutive failed attempts (`None` retries forever)
+    pub max_reconnect_attempts: Option<u32>,
+}
+
+impl Default for WebSocketConfig {
+    fn default() -> Self {
+        Self {
+            url: String::new(),
+            subscriptions: Vec::new(),
+            heartbeat_interval: Duration::from_secs(30),
+            idle_timeout: Duration::from_secs(90),
+            initial_backoff: Duration::from_millis(500),
+            max_backoff: Duration::from_secs(30),
+            max_reconnect_attempts: None,
+        }
+    }
+}
+
+/// Inbound data frame routed to the parser callback
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum WsMessage {
+    Text(String),
+    Binary(Vec<u8>),
+}
+
+/// Parser callback invoked for every inbound data frame
+pub type MessageHandler = Arc<dyn Fn(WsMessage) + Send + Sync>;
+
+/// Commands from the client handle to the connection task
+#[derive(Debug)]
+enum Command {
+    Send(Message),
+    Close,
+}
+
+/// Connection state shared with the client handle
+#[derive(Debug, Default)]
+struct ConnectionState {
+    connected: AtomicBool,
+    reconnects: AtomicU64,
+    messages_received: AtomicU64,
+}
+
+/// Why a connected session ended
+enum SessionEnd {
+    /// Closed on request; do not reconnect
+    Closed,
+    /// Dropped by the peer or the network; reconnect
+    Dropped,
+}
+
+/// Auto-reconnecting WebSocket client
+pub struct WebSocketClient {
+    cmd_tx: mpsc::UnboundedSender<Command>,
+    state: Arc<ConnectionState>,
+    task: JoinHandle<()>,
+}
+
+impl WebSocketClient {
+    /// Start the connection task; the first connection attempt happens in the background
+    pub fn connect(config: WebSocketConfig, handler: MessageHandler) -> Self {
+        let (cmd_tx, cmd_rx) = mpsc::unbounded_channel();
+        let state = Arc::new(ConnectionState::default());
+        let task = tokio::spawn(run(config, handler, Arc::clone(&state), cmd_rx));
+
+        Self { cmd_tx, state, task }
+    }
+
+    /// Queue a text frame for sending on the current connection.
+    ///
+    /// Frames queued while disconnected may be discarded; anything that must
+    /// survive a reconnect belongs in `WebSocketConfig::subscriptions`.
+    pub fn send_text(&self, text: impl Into<String>) -> Result<()> {
+        self.send(Message::Text(text.into()))
+    }
+
+    /// Queue a binary frame for sending on the current connection
+    pub fn send_binary(&self, data: Vec<u8>) -> Result<()> {
+        self.send(Message::Binary(data))
+    }
+
+    /// Check if the client currently holds an open connection
+    pub fn is_connected(&self) -> bool {
+        self.state.connected.load(Ordering::Acquire)
+    }
+
+    /// Check if the connection task is still alive (it stops after `close`
+    /// or once `max_reconnect_attempts` is exhausted)
+    pub fn is_running(&self) -> bool {
+        !self.task.is_finished()
+    }
+
+    /// Number of successful reconnects after the initial connection
+    pub fn reconnect_count(&self) -> u64 {
+        self.state.reconnects.load(Ordering::Relaxed)
+    }
+
+    /// Number of data frames routed to the handler
+    pub fn messages_received(&self) -> u64 {
+        self.state.messages_received.load(Ordering::Relaxed)
+    }
+
+    /// Close the connection and wait for the connection task to finish
+    pub async fn close(self) {
+        let _ = self.cmd_tx.send(Command::Close);
+        let _ = self.task.await;
+    }
+
+    fn send(&self, message: Message) -> Result<()> {
+        self.cmd_tx
+            .send(Command::Send(message))
+            .map_err(|_| AlphaForgeError::network("WebSocket client is closed"))
+    }
+}
+
+/// Connection loop: connect, run the session, back off, repeat
+async fn run(
+    config: WebSocketConfig,
+    handler: MessageHandler,
+    state: Arc<ConnectionState>,
+    mut cmd_rx: mpsc::UnboundedReceiver<Command>,
+) {
+    let mut backoff = config.initial_backoff;
+    let mut failed_attempts = 0u32;
+    let mut has_connected = false;
+
+    loop {
+        match connect_async(config.url.as_str()).await {
+            Ok((stream, _)) => {
+                info!("WebSocket connected: {}", config.url);
+                if has_connected {
+                    state.reconnects.fetch_add(1, Ordering::Relaxed);
+                }
+                has_connected = true;
+                failed_attempts = 0;
+                backoff = config.initial_backoff;
+
+                state.connected.store(true, Ordering::Release);
+                let end = run_session(&config, &handler, &state, &mut cmd_rx, stream).await;
+                state.connected.store(false, Ordering::Release);
+
+                if let SessionEnd::Closed = end {
+                    info!("WebSocket closed: {}", config.url);
+                    return;
+                }
+                warn!("WebSocket connection lost: {}", config.url);
+            }
+            Err(e) => {
+                failed_attempts += 1;
+                warn!("WebSocket connect to {} failed (attempt {}): {}", config.url, failed_attempts, e);
+                if config.max_reconnect_attempts.is_some_and(|max| failed_attempts >= max) {
+                    warn!("Giving up on {} after {} attempts", config.url, failed_attempts);
+                    return;
+                }
+            }
+        }
+
+        // Wait out the backoff, still honouring a close request
+        tokio::select! {
+            _ = tokio::time::sleep(backoff) => {}
+            cmd = cmd_rx.recv() => {
+                if matches!(cmd, Some(Command::Close) | None) {
+                    return;
+                }
+            }
+        }
+        backoff = (backoff * 2).min(config.max_backoff);
+    }
+}
+
+/// Drive a single connected session until it closes or drops
+async fn run_session<S>(
+    config: &WebSocketConfig,
+    handler: &MessageHandler,
+    state: &ConnectionState,
+    cmd_rx: &mut mpsc::UnboundedReceiver<Command>,
+    stream: tokio_tungstenite::WebSocketStream<S>,
+) -> SessionEnd
+where
+    S: tokio::io::AsyncRead + tokio::io::AsyncWrite + Unpin,
+{
+    let (mut sink, mut source) = stream.split();
+
+    for subscription in &config.subscriptions {
+        if let Err(e) = sink.send(Message::Text(subscription.clone())).await {
+            warn!("Failed to send subscription: {}", e);
+            return SessionEnd::Dropped;
+        }
+    }
+
+    let mut heartbeat = tokio::time::interval_at(
+        Instant::now() + config.heartbeat_interval,
+        config.heartbeat_interval,
+    );
+    let mut last_inbound = Instant::now();
+
+    loop {
+        tokio::select! {
+            frame = source.next() => {
+                let message = match frame {
+                    Some(Ok(message)) => message,
+                    Some(Err(e)) => {
+                        warn!("WebSocket read error: {}", e);
+                        return SessionEnd::Dropped;
+                    }
+                    None => return SessionEnd::Dropped,
+                };
+                last_inbound = Instant::now();
+
+                match message {
+                    Message::Text(text) => {
+                        state.messages_received.fetch_add(1, Ordering::Relaxed);
+                        handler(WsMessage::Text(text));
+                    }
+                    Message::Binary(data) => {
+                        state.messages_received.fetch_add(1, Ordering::Relaxed);
+                        handler(WsMessage::Binary(data));
+                    }
+                    // tungstenite queues the pong reply itself
+                    Message::Ping(_) => debug!("WebSocket ping received"),
+                    Message::Pong(_) => debug!("WebSocket pong received"),
+                    Message::Close(frame) => {
+                        debug!("WebSocket close frame received: {:?}", frame);
+                        return SessionEnd::Dropped;
+                    }
+                    Message::Frame(_) => {}
+                }
+            }
+            cmd = cmd_rx.recv() => {
+                match cmd {
+                    Some(Command::Send(message)) => {
+                        if let Err(e) = sink.send(message).await {
+                            warn!("WebSocket send error: {}", e);
+                            return SessionEnd::Dropped;
+                        }
+                    }
+                    Some(Command::Close) | None => {
+                        let _ = sink.send(Message::Close(None)).await;
+                        return SessionEnd::Closed;
+                    }
+                }
+            }
+            _ = heartbeat.tick() => {
+                if last_inbound.elapsed() >= config.idle_timeout {
+                    warn!("WebSocket idle for {:?}, reconnecting", last_inbound.elapsed());
+                    return SessionEnd::Dropped;
+                }
+                if let Err(e) = sink.send(Message::Ping(Vec::new())).await {
+                    warn!("WebSocket ping failed: {}", e);
+                    return SessionEnd::Dropped;
+                }
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::Mutex;
+    use tokio::net::TcpListener;
+
+    /// Echo server that drops each connection after `frames_per_connection` frames
+    async fn spawn_server(frames_per_connection: usize) -> String {
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let addr = listener.local_addr().unwrap();
+
+        tokio::spawn(async move {
+            while let Ok((tcp, _)) = listener.accept().await {
+                tokio::spawn(async move {
+                    let mut ws = tokio_tungstenite::accept_async(tcp).await.unwrap();
+                    let mut echoed = 0;
+                    while let Some(Ok(message)) = ws.next().await {
+                        if message.is_text() {
+                            ws.send(message).await.unwrap();
+                            echoed += 1;
+                            if echoed == frames_per_connection {
+                                break; // drop the connection
+                            }
+                        }
+                    }
+                });
+            }
+        });
+
+        format!("ws://{}", addr)
+    }
+
+    fn collecting_handler() -> (MessageHandler, Arc<Mutex<Vec<WsMessage>>>) {
+        let received = Arc::new(Mutex::new(Vec::new()));
+        let sink = Arc::clone(&received);
+        let handler: MessageHandler = Arc::new(move |message| sink.lock().unwrap().push(message));
+        (handler, received)
+    }
+
+    async fn wait_for(condition: impl Fn() -> bool) {
+        for _ in 0..200 {
+            if condition() {
+                return;
+            }
+            tokio::time::sleep(Duration::from_millis(10)).await;
+        }
+        panic!("condition not met in time");
+    }
+
+    #[tokio::test]
+    async fn test_subscriptions_are_routed_to_handler() {
+        let url = spawn_server(usize::MAX).await;
+        let (handler, received) = collecting_handler();
+
+        let config = WebSocketConfig {
+            url,
+            subscriptions: vec!["subscribe:trades".to_string()],
+            ..Default::default()
+        };
+        let client = WebSocketClient::connect(config, handler);
+
+        wait_for(|| received.lock().unwrap().len() == 1).await;
+        client.send_text("hello").unwrap();
+        wait_for(|| received.lock().unwrap().len() == 2).await;
+
+        assert_eq!(
+            *received.lock().unwrap(),
+            vec![
+                WsMessage::Text("subscribe:trades".to_string()),
+                WsMessage::Text("hello".to_string()),
+            ]
+        );
+        assert!(client.is_connected());
+        client.close().await;
+    }
+
+    #[tokio::test]
+    async fn test_reconnects_and_resubscribes_after_drop() {
+        let url = spawn_server(1).await;
+        let (handler, received) = collecting_handler();
+
+        let config = WebSocketConfig {
+            url,
+            subscriptions: vec!["subscribe".to_string()],
+            initial_backoff: Duration::from_millis(10),
+            ..Default::default()
+        };
+        let client = WebSocketClient::connect(config, handler);
+
+        wait_for(|| client.reconnect_count() >= 2).await;
+        assert!(received.lock().unwrap().len() >= 2);
+        client.close().await;
+    }
+
+    #[tokio::test]
+    async fn test_gives_up_after_max_attempts() {
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let url = format!("ws://{}", listener.local_addr().unwrap());
+        drop(listener);
+
+        let (handler, _) = collecting_handler();
+        let config = WebSocketConfig {
+            url,
+            initial_backoff: Duration::from_millis(1),
+            max_reconnect_attempts: Some(2),
+            ..Default::default()
+        };
+        let client = WebSocketClient::connect(config, handler);
+
+        wait_for(|| !client.is_running()).await;
+        assert!(!client.is_connected());
+    }
+}
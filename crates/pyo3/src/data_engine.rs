@@ -1,11 +1,19 @@
 use pyo3::prelude::*;
-use pyo3::exceptions::{PyRuntimeError, PyValueError};
+use pyo3::exceptions::PyValueError;
 use std::str::FromStr;
+use alphaforge_core::AlphaForgeError;
+use crate::errors::to_py_err;
 
 // ============================================================================
 // DATA ENGINE PYTHON WRAPPERS
 // ============================================================================
 
+/// Parse an instrument ID passed from Python
+fn parse_instrument_id(instrument_id: &str) -> PyResult<alphaforge_core::identifiers::InstrumentId> {
+    alphaforge_core::identifiers::InstrumentId::from_str(instrument_id)
+        .map_err(|e| PyValueError::new_err(format!("Invalid instrument_id: {}", e)))
+}
+
 /// Python wrapper for DataEngineConfig
 #[pyclass(name = "DataEngineConfig")]
 #[derive(Clone, Debug)]
@@ -16,13 +24,17 @@ pub struct PyDataEngineConfig {
 #[pymethods]
 impl PyDataEngineConfig {
     #[new]
-    #[pyo3(signature = (max_bars_per_instrument = 10000, max_tick_buffer_size = 1000, enable_bar_aggregation = true, enable_order_book_deltas = true, enable_statistics = true))]
+    #[allow(clippy::too_many_arguments)]
+    #[pyo3(signature = (max_bars_per_instrument = 10000, max_tick_buffer_size = 1000, enable_bar_aggregation = true, enable_order_book_deltas = true, enable_statistics = true, trade_dedup_window = None, persistence_dir = None, enable_validation = false))]
     fn new(
         max_bars_per_instrument: usize,
         max_tick_buffer_size: usize,
         enable_bar_aggregation: bool,
         enable_order_book_deltas: bool,
         enable_statistics: bool,
+        trade_dedup_window: Option<usize>,
+        persistence_dir: Option<String>,
+        enable_validation: bool,
     ) -> Self {
         Self {
             inner: alphaforge_core::data_engine::DataEngineConfig {
@@ -31,10 +43,23 @@ impl PyDataEngineConfig {
                 enable_bar_aggregation,
                 enable_order_book_deltas,
                 enable_statistics,
+                trade_dedup_window,
+                persistence: persistence_dir.map(alphaforge_core::persistence::PersistenceConfig::new),
+                validation: enable_validation.then(alphaforge_core::validation::ValidationConfig::default),
+                ..Default::default()
             },
         }
     }
 
+    /// The `data_engine` section of a TOML or YAML node config file, with
+    /// `ALPHAFORGE__` environment overrides applied
+    #[staticmethod]
+    fn from_file(path: &str) -> PyResult<Self> {
+        let config = alphaforge_core::config::NodeConfig::from_file(path)
+            .map_err(to_py_err)?;
+        Ok(Self { inner: config.data_engine })
+    }
+
     #[getter]
     fn max_bars_per_instrument(&self) -> usize {
         self.inner.max_bars_per_instrument
@@ -59,6 +84,21 @@ impl PyDataEngineConfig {
     fn enable_statistics(&self) -> bool {
         self.inner.enable_statistics
     }
+
+    #[getter]
+    fn trade_dedup_window(&self) -> Option<usize> {
+        self.inner.trade_dedup_window
+    }
+
+    #[getter]
+    fn persistence_dir(&self) -> Option<String> {
+        self.inner.persistence.as_ref().map(|p| p.directory.display().to_string())
+    }
+
+    #[getter]
+    fn enable_validation(&self) -> bool {
+        self.inner.validation.is_some()
+    }
 }
 
 /// Python wrapper for DataEngineStatistics
@@ -85,6 +125,26 @@ impl PyDataEngineStatistics {
         self.inner.order_book_updates
     }
 
+    #[getter]
+    fn duplicate_trades_filtered(&self) -> u64 {
+        self.inner.duplicate_trades_filtered
+    }
+
+    #[getter]
+    fn bars_evicted(&self) -> u64 {
+        self.inner.bars_evicted
+    }
+
+    #[getter]
+    fn invalid_data_rejected(&self) -> u64 {
+        self.inner.invalid_data_rejected
+    }
+
+    #[getter]
+    fn latency_alerts(&self) -> u64 {
+        self.inner.latency_alerts
+    }
+
     #[getter]
     fn processing_rate(&self) -> f64 {
         self.inner.processing_rate
@@ -105,7 +165,7 @@ impl PyDataEngineStatistics {
 #[pyclass(name = "TradeTick")]
 #[derive(Clone, Debug)]
 pub struct PyTradeTick {
-    inner: alphaforge_core::data::TradeTick,
+    pub inner: alphaforge_core::data::TradeTick,
 }
 
 #[pymethods]
@@ -179,7 +239,7 @@ impl PyTradeTick {
 #[pyclass(name = "QuoteTick")]
 #[derive(Clone, Debug)]
 pub struct PyQuoteTick {
-    inner: alphaforge_core::data::QuoteTick,
+    pub inner: alphaforge_core::data::QuoteTick,
 }
 
 #[pymethods]
@@ -250,7 +310,7 @@ impl PyQuoteTick {
 #[pyclass(name = "Bar")]
 #[derive(Clone, Debug)]
 pub struct PyBar {
-    inner: alphaforge_core::data::Bar,
+    pub inner: alphaforge_core::data::Bar,
 }
 
 #[pymethods]
@@ -354,7 +414,7 @@ impl PyDataEngine {
     /// Start the Data Engine
     fn start(&mut self) -> PyResult<()> {
         self.inner.start()
-            .map_err(|e| PyRuntimeError::new_err(e))
+            .map_err(|e| to_py_err(AlphaForgeError::runtime(e)))
     }
 
     /// Stop the Data Engine
@@ -367,14 +427,29 @@ impl PyDataEngine {
         match self.inner.process_trade_tick(tick.inner) {
             Ok(Some(bar)) => Ok(Some(PyBar { inner: bar })),
             Ok(None) => Ok(None),
-            Err(e) => Err(PyRuntimeError::new_err(e)),
+            Err(e) => Err(to_py_err(AlphaForgeError::runtime(e))),
         }
     }
 
     /// Process a quote tick
     fn process_quote_tick(&mut self, tick: PyQuoteTick) -> PyResult<()> {
         self.inner.process_quote_tick(tick.inner)
-            .map_err(|e| PyRuntimeError::new_err(e))
+            .map_err(|e| to_py_err(AlphaForgeError::runtime(e)))
+    }
+
+    /// Process a batch of trade ticks, returning all completed bars
+    fn process_trade_ticks(&mut self, ticks: Vec<PyTradeTick>) -> PyResult<Vec<PyBar>> {
+        let ticks: Vec<_> = ticks.into_iter().map(|tick| tick.inner).collect();
+        let bars = self.inner.process_trade_ticks(&ticks)
+            .map_err(|e| to_py_err(AlphaForgeError::runtime(e)))?;
+        Ok(bars.into_iter().map(|bar| PyBar { inner: bar }).collect())
+    }
+
+    /// Process a batch of quote ticks
+    fn process_quote_ticks(&mut self, ticks: Vec<PyQuoteTick>) -> PyResult<()> {
+        let ticks: Vec<_> = ticks.into_iter().map(|tick| tick.inner).collect();
+        self.inner.process_quote_ticks(&ticks)
+            .map_err(|e| to_py_err(AlphaForgeError::runtime(e)))
     }
 
     /// Add bar aggregator
@@ -390,6 +465,119 @@ impl PyDataEngine {
             .collect()
     }
 
+    /// Query stored bars in ascending time order
+    #[pyo3(signature = (bar_type, from_ts = None, to_ts = None, limit = None))]
+    fn get_bars(
+        &self,
+        bar_type: PyBarType,
+        from_ts: Option<u64>,
+        to_ts: Option<u64>,
+        limit: Option<usize>,
+    ) -> Vec<PyBar> {
+        self.inner.get_bars(&bar_type.inner, from_ts, to_ts, limit)
+            .into_iter()
+            .map(|bar| PyBar { inner: bar })
+            .collect()
+    }
+
+    /// Get session VWAP for an instrument
+    fn get_vwap(&self, instrument_id: &str) -> PyResult<Option<f64>> {
+        Ok(self.inner.get_vwap(parse_instrument_id(instrument_id)?))
+    }
+
+    /// Get session TWAP for an instrument
+    fn get_twap(&self, instrument_id: &str) -> PyResult<Option<f64>> {
+        Ok(self.inner.get_twap(parse_instrument_id(instrument_id)?))
+    }
+
+    /// Start a new VWAP/TWAP session
+    #[pyo3(signature = (instrument_id = None))]
+    fn reset_session(&mut self, instrument_id: Option<&str>) -> PyResult<()> {
+        let instrument_id = instrument_id.map(parse_instrument_id).transpose()?;
+        self.inner.reset_session(instrument_id);
+        Ok(())
+    }
+
+    /// Get validation violation counts as a dict keyed by violation kind
+    fn get_validation_violations(&self, instrument_id: &str) -> PyResult<Option<std::collections::HashMap<&'static str, u64>>> {
+        Ok(self.inner
+            .get_validation_violations(parse_instrument_id(instrument_id)?)
+            .map(|counts| {
+                std::collections::HashMap::from([
+                    ("non_finite_price", counts.non_finite_price),
+                    ("invalid_size", counts.invalid_size),
+                    ("crossed_quote", counts.crossed_quote),
+                    ("locked_quote", counts.locked_quote),
+                    ("price_jump", counts.price_jump),
+                ])
+            }))
+    }
+
+    /// Get the latest funding rate for an instrument
+    fn get_funding_rate(&self, instrument_id: &str) -> PyResult<Option<f64>> {
+        Ok(self.inner
+            .get_funding_rate(parse_instrument_id(instrument_id)?)
+            .map(|update| update.rate))
+    }
+
+    /// Get the latest open interest for an instrument
+    fn get_open_interest(&self, instrument_id: &str) -> PyResult<Option<f64>> {
+        Ok(self.inner
+            .get_open_interest(parse_instrument_id(instrument_id)?)
+            .map(|update| update.open_interest))
+    }
+
+    /// Get the latest mark price for an instrument
+    fn get_mark_price(&self, instrument_id: &str) -> PyResult<Option<f64>> {
+        Ok(self.inner
+            .get_mark_price(parse_instrument_id(instrument_id)?)
+            .map(|update| update.mark_price))
+    }
+
+    /// Get the latest index price for an instrument
+    fn get_index_price(&self, instrument_id: &str) -> PyResult<Option<f64>> {
+        Ok(self.inner
+            .get_index_price(parse_instrument_id(instrument_id)?)
+            .map(|update| update.index_price))
+    }
+
+    /// Get (mean, p50, p99, max) feed latency in nanoseconds for an instrument
+    fn get_feed_latency(&self, instrument_id: &str) -> PyResult<Option<(f64, i64, i64, i64)>> {
+        Ok(self.inner
+            .get_feed_latency(parse_instrument_id(instrument_id)?)
+            .map(|l| (l.mean_ns, l.p50_ns, l.p99_ns, l.max_ns)))
+    }
+
+    /// Publish a named signal
+    fn publish_signal(&mut self, name: &str, value: f64, ts: u64) -> PyResult<()> {
+        self.inner
+            .publish_signal(alphaforge_core::data::SignalData::new(name, value, ts))
+            .map_err(|e| to_py_err(AlphaForgeError::runtime(e)))
+    }
+
+    /// Get the latest value of a signal
+    fn get_signal(&self, name: &str) -> Option<f64> {
+        self.inner.get_signal(name).map(|signal| signal.value)
+    }
+
+    /// Get average true range for a bar type
+    fn get_atr(&self, bar_type: PyBarType) -> Option<f64> {
+        self.inner.get_atr(&bar_type.inner)
+    }
+
+    /// Get realized volatility for a bar type
+    fn get_realized_volatility(&self, bar_type: PyBarType) -> Option<f64> {
+        self.inner.get_realized_volatility(&bar_type.inner)
+    }
+
+    /// Get (buy_volume, sell_volume, delta) for a configured order flow window
+    /// ending now
+    fn get_order_flow(&self, instrument_id: &str, window_ns: u64) -> PyResult<Option<(f64, f64, f64)>> {
+        Ok(self.inner
+            .get_order_flow(parse_instrument_id(instrument_id)?, window_ns)
+            .map(|flow| (flow.buy_volume, flow.sell_volume, flow.delta)))
+    }
+
     /// Check if engine is running
     fn is_running(&self) -> bool {
         self.inner.is_running()
@@ -411,6 +599,17 @@ impl PyDataEngine {
     fn reset_statistics(&mut self) {
         self.inner.reset_statistics();
     }
+
+    /// Serialize engine state to bytes
+    fn snapshot<'py>(&self, py: Python<'py>) -> PyResult<Bound<'py, pyo3::types::PyBytes>> {
+        let data = self.inner.snapshot().map_err(|e| to_py_err(AlphaForgeError::runtime(e)))?;
+        Ok(pyo3::types::PyBytes::new_bound(py, &data))
+    }
+
+    /// Restore engine state from `snapshot()` bytes
+    fn restore(&mut self, data: &[u8]) -> PyResult<()> {
+        self.inner.restore(data).map_err(|e| to_py_err(AlphaForgeError::validation(e)))
+    }
 }
 
 /// Register data engine module
@@ -0,0 +1,66 @@
+//! Python exceptions for AlphaForge errors
+//!
+//! Each `AlphaForgeError` category raises its own exception class, all
+//! derived from `AlphaForgeError` (itself a `RuntimeError`), carrying the
+//! error's stable numeric code as `code`.
+
+// pyo3 0.22's `create_exception!` checks its own `gil-refs` feature here
+#![allow(unexpected_cfgs)]
+
+use alphaforge_core::AlphaForgeError as CoreError;
+use pyo3::create_exception;
+use pyo3::exceptions::PyRuntimeError;
+use pyo3::prelude::*;
+use pyo3::types::PyModule;
+
+create_exception!(alphaforge_pyo3, AlphaForgeError, PyRuntimeError);
+create_exception!(alphaforge_pyo3, ConfigurationError, AlphaForgeError);
+create_exception!(alphaforge_pyo3, NetworkError, AlphaForgeError);
+create_exception!(alphaforge_pyo3, SerializationError, AlphaForgeError);
+create_exception!(alphaforge_pyo3, TimeError, AlphaForgeError);
+create_exception!(alphaforge_pyo3, ValidationError, AlphaForgeError);
+create_exception!(alphaforge_pyo3, ComponentError, AlphaForgeError);
+create_exception!(alphaforge_pyo3, MessageBusError, AlphaForgeError);
+create_exception!(alphaforge_pyo3, EngineRuntimeError, AlphaForgeError);
+create_exception!(alphaforge_pyo3, ExecutionError, AlphaForgeError);
+create_exception!(alphaforge_pyo3, CacheError, AlphaForgeError);
+
+/// Raise `error` as the exception of its category
+pub(crate) fn to_py_err(error: CoreError) -> PyErr {
+    let message = error.report();
+    let err = match error.root() {
+        CoreError::InvalidConfiguration { .. } => ConfigurationError::new_err(message),
+        CoreError::Network { .. } => NetworkError::new_err(message),
+        CoreError::Serialization { .. } => SerializationError::new_err(message),
+        CoreError::Time { .. } => TimeError::new_err(message),
+        CoreError::Validation { .. } => ValidationError::new_err(message),
+        CoreError::Component { .. } => ComponentError::new_err(message),
+        CoreError::MessageBus { .. } => MessageBusError::new_err(message),
+        CoreError::Runtime { .. } => EngineRuntimeError::new_err(message),
+        CoreError::Execution { .. } => ExecutionError::new_err(message),
+        CoreError::Cache { .. } => CacheError::new_err(message),
+        // `root` is never a context; categories added later raise the base class
+        _ => AlphaForgeError::new_err(message),
+    };
+    Python::with_gil(|py| {
+        // Setting an attribute on a fresh exception instance cannot fail
+        let _ = err.value_bound(py).setattr("code", error.code());
+    });
+    err
+}
+
+/// Add the exception classes to `module`
+pub(crate) fn register_exceptions(py: Python, module: &Bound<'_, PyModule>) -> PyResult<()> {
+    module.add("AlphaForgeError", py.get_type_bound::<AlphaForgeError>())?;
+    module.add("ConfigurationError", py.get_type_bound::<ConfigurationError>())?;
+    module.add("NetworkError", py.get_type_bound::<NetworkError>())?;
+    module.add("SerializationError", py.get_type_bound::<SerializationError>())?;
+    module.add("TimeError", py.get_type_bound::<TimeError>())?;
+    module.add("ValidationError", py.get_type_bound::<ValidationError>())?;
+    module.add("ComponentError", py.get_type_bound::<ComponentError>())?;
+    module.add("MessageBusError", py.get_type_bound::<MessageBusError>())?;
+    module.add("EngineRuntimeError", py.get_type_bound::<EngineRuntimeError>())?;
+    module.add("ExecutionError", py.get_type_bound::<ExecutionError>())?;
+    module.add("CacheError", py.get_type_bound::<CacheError>())?;
+    Ok(())
+}
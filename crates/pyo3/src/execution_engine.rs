@@ -1,12 +1,16 @@
 use pyo3::prelude::*;
 use pyo3::exceptions::{PyRuntimeError, PyValueError};
-use std::sync::Arc;
+use std::collections::HashMap;
+use std::sync::{Arc, Mutex};
 use alphaforge_core::execution_engine::{
     ExecutionEngine, Order, OrderSide, OrderType, OrderStatus, 
     TimeInForce, Fill, ExecutionStats
 };
 use alphaforge_core::identifiers::{StrategyId, InstrumentId, OrderId};
 use alphaforge_core::message_bus::MessageBus;
+use alphaforge_core::portfolio::{Portfolio, PortfolioSnapshot};
+use alphaforge_core::AlphaForgeError;
+use crate::errors::to_py_err;
 use std::str::FromStr;
 
 // ============================================================================
@@ -457,7 +461,7 @@ impl PyExecutionEngine {
             let result = inner.submit_order(order).await;
             match result {
                 Ok(order_id) => Ok(order_id.id),
-                Err(e) => Err(PyRuntimeError::new_err(format!("Execution error: {}", e))),
+                Err(e) => Err(to_py_err(e.into())),
             }
         })
     }
@@ -475,7 +479,7 @@ impl PyExecutionEngine {
             let result = inner.cancel_order(order_id).await;
             match result {
                 Ok(()) => Ok(()),
-                Err(e) => Err(PyRuntimeError::new_err(format!("Execution error: {}", e))),
+                Err(e) => Err(to_py_err(e.into())),
             }
         })
     }
@@ -483,7 +487,7 @@ impl PyExecutionEngine {
     /// Handle order fill
     fn handle_fill(&self, fill: PyFill) -> PyResult<()> {
         self.inner.handle_fill(fill.inner)
-            .map_err(|e| PyRuntimeError::new_err(format!("Fill error: {}", e)))
+            .map_err(|e| to_py_err(AlphaForgeError::from(e).with_context("Handling fill")))
     }
     
     /// Get execution statistics
@@ -493,7 +497,7 @@ impl PyExecutionEngine {
         }
     }
     
-    /// Get orders for a strategy
+    /// Working orders of a strategy
     fn get_strategy_orders(&self, strategy_id: u64) -> Vec<PyOrder> {
         let strategy_id = StrategyId::new(strategy_id);
         self.inner.get_strategy_orders(strategy_id)
@@ -515,6 +519,11 @@ impl PyExecutionEngine {
         Ok(())
     }
     
+    /// Book every subsequent fill into a portfolio
+    fn attach_portfolio(&self, portfolio: &PyPortfolio) {
+        self.inner.attach_portfolio(portfolio.inner.clone());
+    }
+    
     fn __str__(&self) -> String {
         let stats = self.inner.get_statistics();
         format!("ExecutionEngine(active_orders={}, total_submitted={})",
@@ -524,6 +533,174 @@ impl PyExecutionEngine {
     }
 }
 
+// ============================================================================
+// PYTHON WRAPPERS FOR PORTFOLIO
+// ============================================================================
+
+/// Python wrapper for PortfolioSnapshot
+#[pyclass(name = "PortfolioSnapshot")]
+pub struct PyPortfolioSnapshot {
+    pub inner: PortfolioSnapshot,
+}
+
+#[pymethods]
+impl PyPortfolioSnapshot {
+    #[getter]
+    fn ts(&self) -> u64 {
+        self.inner.ts
+    }
+    
+    #[getter]
+    fn realized_pnl(&self) -> f64 {
+        self.inner.realized_pnl
+    }
+    
+    #[getter]
+    fn unrealized_pnl(&self) -> f64 {
+        self.inner.unrealized_pnl
+    }
+    
+    #[getter]
+    fn total_pnl(&self) -> f64 {
+        self.inner.total_pnl()
+    }
+    
+    #[getter]
+    fn commission(&self) -> f64 {
+        self.inner.commission
+    }
+    
+    /// Strategy ID -> (realized, unrealized, commission)
+    #[getter]
+    fn strategies(&self) -> HashMap<u64, (f64, f64, f64)> {
+        self.inner.strategies
+            .iter()
+            .map(|(id, pnl)| (id.id, (pnl.realized_pnl, pnl.unrealized_pnl, pnl.commission)))
+            .collect()
+    }
+    
+    /// Instrument ID -> (net quantity, notional)
+    #[getter]
+    fn exposure_by_instrument(&self) -> HashMap<String, (f64, f64)> {
+        self.inner.exposure_by_instrument
+            .iter()
+            .map(|(id, exposure)| (id.to_string(), (exposure.quantity, exposure.notional)))
+            .collect()
+    }
+    
+    #[getter]
+    fn exposure_by_currency(&self) -> HashMap<String, f64> {
+        self.inner.exposure_by_currency.clone()
+    }
+    
+    fn __str__(&self) -> String {
+        format!("PortfolioSnapshot(realized={:.2}, unrealized={:.2}, strategies={})",
+            self.inner.realized_pnl,
+            self.inner.unrealized_pnl,
+            self.inner.strategies.len()
+        )
+    }
+}
+
+/// Python wrapper for Portfolio
+#[pyclass(name = "Portfolio")]
+pub struct PyPortfolio {
+    inner: Arc<Mutex<Portfolio>>,
+}
+
+impl PyPortfolio {
+    fn lock(&self) -> PyResult<std::sync::MutexGuard<'_, Portfolio>> {
+        self.inner.lock().map_err(|_| PyRuntimeError::new_err("Portfolio lock poisoned"))
+    }
+}
+
+#[pymethods]
+impl PyPortfolio {
+    #[new]
+    #[pyo3(signature = (default_currency = "USD"))]
+    fn new(default_currency: &str) -> Self {
+        Self { inner: Arc::new(Mutex::new(Portfolio::new(default_currency))) }
+    }
+    
+    /// Set the settlement currency and multiplier of an instrument
+    fn register_instrument(&self, instrument_id: String, currency: &str, multiplier: f64) -> PyResult<()> {
+        let instrument_id = InstrumentId::from_str(&instrument_id)
+            .map_err(|e| PyValueError::new_err(format!("Invalid instrument ID: {}", e)))?;
+        self.lock()?.register_instrument(instrument_id, currency, multiplier);
+        Ok(())
+    }
+    
+    /// Apply a fill, returning the PnL it realized
+    fn apply_fill(&self, order: &PyOrder, fill: &PyFill) -> PyResult<f64> {
+        Ok(self.lock()?.apply_fill(&order.inner, &fill.inner))
+    }
+    
+    /// Revalue open positions in an instrument at `price`
+    fn update_price(&self, instrument_id: String, price: f64) -> PyResult<()> {
+        let instrument_id = InstrumentId::from_str(&instrument_id)
+            .map_err(|e| PyValueError::new_err(format!("Invalid instrument ID: {}", e)))?;
+        self.lock()?.update_price(instrument_id, price);
+        Ok(())
+    }
+    
+    /// Open positions of a strategy as instrument ID -> signed quantity
+    fn open_positions(&self, strategy_id: u64) -> PyResult<HashMap<String, f64>> {
+        Ok(self.lock()?
+            .open_positions(StrategyId::new(strategy_id))
+            .into_iter()
+            .map(|(id, quantity)| (id.to_string(), quantity))
+            .collect())
+    }
+    
+    fn snapshot(&self) -> PyResult<PyPortfolioSnapshot> {
+        Ok(PyPortfolioSnapshot { inner: self.lock()?.snapshot() })
+    }
+    
+    /// Set the capital shared out by strategy weights
+    fn set_total_capital(&self, total_capital: f64) -> PyResult<()> {
+        self.lock()?.allocation_mut().set_total_capital(total_capital).map_err(PyValueError::new_err)
+    }
+    
+    /// Assign a strategy a weight of total capital, optionally capped
+    #[pyo3(signature = (strategy_id, weight, max_notional = None))]
+    fn allocate(&self, strategy_id: u64, weight: f64, max_notional: Option<f64>) -> PyResult<()> {
+        self.lock()?
+            .allocation_mut()
+            .allocate(StrategyId::new(strategy_id), weight, max_notional)
+            .map_err(PyValueError::new_err)
+    }
+    
+    /// Set new weights as strategy ID -> weight, returning each strategy's capital
+    fn rebalance(&self, weights: HashMap<u64, f64>) -> PyResult<HashMap<u64, f64>> {
+        let weights: Vec<_> = weights.into_iter().map(|(id, weight)| (StrategyId::new(id), weight)).collect();
+        let capital = self.lock()?.allocation_mut().rebalance(&weights).map_err(PyValueError::new_err)?;
+        Ok(capital.into_iter().map(|(id, capital)| (id.id, capital)).collect())
+    }
+    
+    /// Capital a strategy may deploy, or None when it has no allocation
+    fn strategy_capital(&self, strategy_id: u64) -> PyResult<Option<f64>> {
+        Ok(self.lock()?.allocation().capital(StrategyId::new(strategy_id)))
+    }
+    
+    fn gross_exposure(&self, strategy_id: u64) -> PyResult<f64> {
+        Ok(self.lock()?.gross_exposure(StrategyId::new(strategy_id)))
+    }
+    
+    /// Cap the combined exposure of all strategies to a group of instruments
+    fn set_exposure_limit(&self, group: &str, instrument_ids: Vec<String>, max_notional: f64) -> PyResult<()> {
+        let instruments = instrument_ids
+            .iter()
+            .map(|id| InstrumentId::from_str(id).map_err(|e| PyValueError::new_err(format!("Invalid instrument ID: {}", e))))
+            .collect::<PyResult<_>>()?;
+        self.lock()?.set_exposure_limit(group, instruments, max_notional).map_err(PyValueError::new_err)
+    }
+    
+    /// Combined exposure of a group, or None for an unknown group
+    fn group_exposure(&self, group: &str) -> PyResult<Option<f64>> {
+        Ok(self.lock()?.group_exposure(group))
+    }
+}
+
 // ============================================================================
 // MODULE REGISTRATION
 // ============================================================================
@@ -543,6 +720,8 @@ pub fn register_execution_types(py: Python, parent_module: &Bound<'_, PyModule>)
     execution_module.add_class::<PyFill>()?;
     execution_module.add_class::<PyExecutionStats>()?;
     execution_module.add_class::<PyExecutionEngine>()?;
+    execution_module.add_class::<PyPortfolio>()?;
+    execution_module.add_class::<PyPortfolioSnapshot>()?;
     
     parent_module.add_submodule(&execution_module)?;
     Ok(())
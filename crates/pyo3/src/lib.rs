@@ -2,12 +2,18 @@
 //! 
 //! High-performance Python bindings for AlphaForge trading system.
 
+// `#[pymethods]` returning `PyResult` trips this lint on pyo3 0.22's generated glue
+#![allow(clippy::useless_conversion)]
+
+use pyo3::exceptions::PyRuntimeError;
 use pyo3::prelude::*;
-use pyo3::types::PyModule;
-use tracing_subscriber::{EnvFilter, fmt};
+use pyo3::types::{PyBytes, PyModule};
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
 use alphaforge_core::generic_cache;
+use alphaforge_core::logging::{LogFileConfig, LogFormat, LogLevel, LoggingConfig};
 
 mod data_engine;
+mod errors;
 mod strategy_engine;
 mod execution_engine;
 
@@ -23,15 +29,50 @@ impl Clone for PyObjectWrapper {
     }
 }
 
+// Persisted as the bytes `pickle` makes of the object
+impl Serialize for PyObjectWrapper {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        let bytes = Python::with_gil(|py| -> PyResult<Vec<u8>> {
+            let pickled = py.import_bound("pickle")?.call_method1("dumps", (self.0.bind(py),))?;
+            Ok(pickled.downcast::<PyBytes>()?.as_bytes().to_vec())
+        })
+        .map_err(serde::ser::Error::custom)?;
+        bytes.serialize(serializer)
+    }
+}
+
+impl<'de> Deserialize<'de> for PyObjectWrapper {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let bytes = Vec::<u8>::deserialize(deserializer)?;
+        Python::with_gil(|py| -> PyResult<Self> {
+            let object = py.import_bound("pickle")?.call_method1("loads", (PyBytes::new_bound(py, &bytes),))?;
+            Ok(PyObjectWrapper(object.unbind()))
+        })
+        .map_err(serde::de::Error::custom)
+    }
+}
+
+// Measured as `sys.getsizeof`, which excludes objects the value refers to
+impl generic_cache::MemSize for PyObjectWrapper {
+    fn mem_size(&self) -> usize {
+        Python::with_gil(|py| {
+            py.import_bound("sys")
+                .and_then(|sys| sys.call_method1("getsizeof", (self.0.bind(py),)))
+                .and_then(|size| size.extract::<usize>())
+                .unwrap_or(std::mem::size_of::<Self>())
+        })
+    }
+}
+
 impl From<PyObject> for PyObjectWrapper {
     fn from(obj: PyObject) -> Self {
         PyObjectWrapper(obj)
     }
 }
 
-impl Into<PyObject> for PyObjectWrapper {
-    fn into(self) -> PyObject {
-        self.0
+impl From<PyObjectWrapper> for PyObject {
+    fn from(wrapper: PyObjectWrapper) -> Self {
+        wrapper.0
     }
 }
 
@@ -45,6 +86,7 @@ fn alphaforge_pyo3(m: &Bound<'_, PyModule>) -> PyResult<()> {
     
     // Initialize logging subsystem
     init_logging()?;
+    errors::register_exceptions(m.py(), m)?;
     
     // Register core submodules
     let py = m.py();
@@ -66,23 +108,89 @@ fn init_logging() -> PyResult<()> {
     static INIT: std::sync::Once = std::sync::Once::new();
     
     INIT.call_once(|| {
-        // Set up tracing subscriber for structured logging
-        let filter = EnvFilter::try_from_default_env()
-            .or_else(|_| EnvFilter::try_new("info"))
-            .unwrap();
-            
-        fmt()
-            .with_env_filter(filter)
-            .with_target(false)
-            .with_thread_ids(true)
-            .with_thread_names(true)
-            .compact()
-            .init();
+        let config = LoggingConfig { threads: true, ..Default::default() };
+        // `RUST_LOG` directives decide what is logged until
+        // `configure_logging` narrows it
+        let installed = std::env::var("RUST_LOG").ok().map(|directives| {
+            let config = LoggingConfig { level: LogLevel::Trace, ..config.clone() };
+            alphaforge_core::logger::init_with_directives(config, &directives)
+        });
+        if !matches!(installed, Some(Ok(_))) {
+            // An embedding application may have installed its own subscriber
+            let _ = alphaforge_core::logger::init(config);
+        }
     });
     
     Ok(())
 }
 
+fn parse_log_level(level: &str) -> PyResult<LogLevel> {
+    level.parse().map_err(pyo3::exceptions::PyValueError::new_err)
+}
+
+fn log_handle() -> PyResult<&'static alphaforge_core::logger::LogHandle> {
+    alphaforge_core::logger::handle().ok_or_else(|| PyRuntimeError::new_err("Logging is not managed by AlphaForge"))
+}
+
+/// Replace the logging configuration; `components` maps module path
+/// components to levels, and `directory` enables rotating log files
+#[pyfunction]
+#[pyo3(signature = (
+    level = "info",
+    json = false,
+    components = None,
+    directory = None,
+    max_file_bytes = 64 * 1024 * 1024,
+    max_files = 10,
+    stdout = true,
+    stdout_max_per_sec = None
+))]
+#[allow(clippy::too_many_arguments)]
+fn configure_logging_py(
+    level: &str,
+    json: bool,
+    components: Option<std::collections::HashMap<String, String>>,
+    directory: Option<std::path::PathBuf>,
+    max_file_bytes: u64,
+    max_files: usize,
+    stdout: bool,
+    stdout_max_per_sec: Option<u32>,
+) -> PyResult<()> {
+    let components = components
+        .unwrap_or_default()
+        .into_iter()
+        .map(|(component, level)| Ok((component, parse_log_level(&level)?)))
+        .collect::<PyResult<_>>()?;
+    let handle = log_handle()?;
+    let config = LoggingConfig {
+        level: parse_log_level(level)?,
+        components,
+        format: if json { LogFormat::Json } else { LogFormat::Text },
+        stdout,
+        stdout_max_per_sec,
+        file: directory.map(|directory| LogFileConfig {
+            max_file_bytes,
+            max_files,
+            ..LogFileConfig::new(directory)
+        }),
+        threads: handle.config().threads,
+    };
+    handle.reconfigure(config).map_err(errors::to_py_err)
+}
+
+/// Set the level of all logging, or of one component
+#[pyfunction]
+#[pyo3(signature = (level, component = None))]
+fn set_log_level_py(level: &str, component: Option<&str>) -> PyResult<()> {
+    let level = parse_log_level(level)?;
+    let handle = log_handle()?;
+    match component {
+        Some(component) => handle.set_component_level(component, Some(level)),
+        None => handle.set_level(level),
+    }
+    Ok(())
+}
+
 /// Register core module functions
 fn register_core_module(py: Python, parent: &Bound<'_, PyModule>) -> PyResult<()> {
     let core_module = PyModule::new_bound(py, "core")?;
@@ -90,6 +198,9 @@ fn register_core_module(py: Python, parent: &Bound<'_, PyModule>) -> PyResult<()
     // Add core functions
     core_module.add_function(wrap_pyfunction!(unix_nanos_now_py, &core_module)?)?;
     core_module.add_function(wrap_pyfunction!(uuid4_new_py, &core_module)?)?;
+    core_module.add_function(wrap_pyfunction!(uuid7_new_py, &core_module)?)?;
+    core_module.add_function(wrap_pyfunction!(configure_logging_py, &core_module)?)?;
+    core_module.add_function(wrap_pyfunction!(set_log_level_py, &core_module)?)?;
     
     parent.add_submodule(&core_module)?;
     
@@ -126,7 +237,15 @@ fn register_time_module(py: Python, parent: &Bound<'_, PyModule>) -> PyResult<()
     let time_module = PyModule::new_bound(py, "time")?;
     
     time_module.add_class::<PyAtomicTime>()?;
-    // Note: PyLiveClock temporarily removed due to clock module absence
+    time_module.add_class::<PyLiveClock>()?;
+    time_module.add_class::<PyTestClock>()?;
+    time_module.add_class::<PyTimeEvent>()?;
+    time_module.add_function(wrap_pyfunction!(format_iso8601_py, &time_module)?)?;
+    time_module.add_function(wrap_pyfunction!(parse_epoch_py, &time_module)?)?;
+    time_module.add_function(wrap_pyfunction!(secs_to_nanos_py, &time_module)?)?;
+    time_module.add_function(wrap_pyfunction!(millis_to_nanos_py, &time_module)?)?;
+    time_module.add_function(wrap_pyfunction!(nanos_to_secs_py, &time_module)?)?;
+    time_module.add_function(wrap_pyfunction!(nanos_to_millis_py, &time_module)?)?;
     
     parent.add_submodule(&time_module)?;
     
@@ -199,6 +318,48 @@ fn uuid4_new_py() -> String {
     alphaforge_core::uuid::UUID4::new().to_string()
 }
 
+#[pyfunction]
+fn uuid7_new_py() -> String {
+    alphaforge_core::uuid::UUID7::new().to_string()
+}
+
+// Time formatting and conversion bindings
+#[pyfunction]
+#[pyo3(name = "format_iso8601")]
+fn format_iso8601_py(ts: u64) -> String {
+    alphaforge_core::time::format_iso8601(ts)
+}
+
+#[pyfunction]
+#[pyo3(name = "parse_epoch")]
+fn parse_epoch_py(value: &str) -> PyResult<u64> {
+    alphaforge_core::time::parse_epoch(value).map_err(pyo3::exceptions::PyValueError::new_err)
+}
+
+#[pyfunction]
+#[pyo3(name = "secs_to_nanos")]
+fn secs_to_nanos_py(secs: f64) -> u64 {
+    alphaforge_core::time::secs_to_nanos(secs)
+}
+
+#[pyfunction]
+#[pyo3(name = "millis_to_nanos")]
+fn millis_to_nanos_py(millis: u64) -> u64 {
+    alphaforge_core::time::millis_to_nanos(millis)
+}
+
+#[pyfunction]
+#[pyo3(name = "nanos_to_secs")]
+fn nanos_to_secs_py(ts: u64) -> f64 {
+    alphaforge_core::time::nanos_to_secs(ts)
+}
+
+#[pyfunction]
+#[pyo3(name = "nanos_to_millis")]
+fn nanos_to_millis_py(ts: u64) -> u64 {
+    alphaforge_core::time::nanos_to_millis(ts)
+}
+
 // Python wrapper for Price
 #[pyclass(name = "Price")]
 #[derive(Clone, Debug)]
@@ -408,16 +569,28 @@ pub struct PyAtomicTime {
 #[pymethods]
 impl PyAtomicTime {
     #[new]
-    fn new() -> Self {
-        Self {
-            inner: alphaforge_core::time::AtomicTime::new(),
-        }
+    #[pyo3(signature = (strict=false))]
+    fn new(strict: bool) -> Self {
+        let inner = match strict {
+            true => alphaforge_core::time::AtomicTime::strictly_increasing(),
+            false => alphaforge_core::time::AtomicTime::new(),
+        };
+        Self { inner }
+    }
+    
+    #[getter]
+    fn strict(&self) -> bool {
+        self.inner.is_strict()
     }
     
     fn get(&self) -> u64 {
         self.inner.get()
     }
     
+    fn get_next(&self) -> u64 {
+        self.inner.get_next()
+    }
+    
     fn set(&self, timestamp: u64) {
         self.inner.set(timestamp);
     }
@@ -427,27 +600,204 @@ impl PyAtomicTime {
     }
 }
 
-// Python wrapper for LiveClock - Temporarily commented out due to clock module absence
-// #[pyclass(name = "LiveClock")]
-// pub struct PyLiveClock {
-//     inner: std::sync::Mutex<alphaforge_core::clock::LiveClock>,
-// }
-
-// #[pymethods]
-// impl PyLiveClock {
-//     #[new]
-//     fn new() -> Self {
-//         Self {
-//             inner: std::sync::Mutex::new(alphaforge_core::clock::LiveClock::new()),
-//         }
-//     }
-//     
-//     fn timestamp_ns(&self) -> u64 {
-//         let clock = self.inner.lock().unwrap();
-//         use alphaforge_core::clock::Clock;
-//         clock.timestamp_ns()
-//     }
-// }
+// Python wrapper for TimeEvent
+#[pyclass(name = "TimeEvent")]
+#[derive(Clone)]
+pub struct PyTimeEvent {
+    #[pyo3(get)]
+    name: String,
+    #[pyo3(get)]
+    ts_event: u64,
+    #[pyo3(get)]
+    ts_init: u64,
+}
+
+#[pymethods]
+impl PyTimeEvent {
+    fn __repr__(&self) -> String {
+        format!("TimeEvent(name={:?}, ts_event={}, ts_init={})", self.name, self.ts_event, self.ts_init)
+    }
+}
+
+/// Time events of a clock's timers awaiting collection by Python
+struct PyTimeEvents {
+    sender: tokio::sync::mpsc::UnboundedSender<alphaforge_core::clock::TimeEvent>,
+    receiver: std::sync::Mutex<tokio::sync::mpsc::UnboundedReceiver<alphaforge_core::clock::TimeEvent>>,
+    // First exception raised by a callback since the last `raise_callback_error`,
+    // kept only when callbacks run on the caller's thread
+    callback_error: Option<std::sync::Arc<std::sync::Mutex<Option<PyErr>>>>,
+}
+
+impl PyTimeEvents {
+    fn new(raise_callback_errors: bool) -> Self {
+        let (sender, receiver) = tokio::sync::mpsc::unbounded_channel();
+        Self {
+            sender,
+            receiver: std::sync::Mutex::new(receiver),
+            callback_error: raise_callback_errors.then(Default::default),
+        }
+    }
+
+    // Where a timer's events go: to `callback` as the timer fires if given,
+    // otherwise queued for `drain_events`. Exceptions the callback raises
+    // are logged, and kept for `raise_callback_error` if requested.
+    fn sender(&self, callback: Option<PyObject>) -> alphaforge_core::clock::TimeEventSender {
+        use alphaforge_core::clock::TimeEventSender;
+        let Some(callback) = callback else {
+            return TimeEventSender::Channel(self.sender.clone());
+        };
+        let callback_error = self.callback_error.clone();
+        TimeEventSender::Handler(std::sync::Arc::new(move |event| {
+            Python::with_gil(|py| {
+                let Err(e) = callback.call0(py) else {
+                    return;
+                };
+                tracing::error!("Timer {} callback raised: {}", event.name, e);
+                if let Some(callback_error) = &callback_error {
+                    callback_error.lock().unwrap().get_or_insert(e);
+                }
+            })
+        }))
+    }
+
+    // Raise the first exception callbacks raised since the last call
+    fn raise_callback_error(&self) -> PyResult<()> {
+        match self.callback_error.as_ref().and_then(|error| error.lock().unwrap().take()) {
+            Some(e) => Err(e),
+            None => Ok(()),
+        }
+    }
+
+    fn drain(&self) -> Vec<PyTimeEvent> {
+        let mut receiver = self.receiver.lock().unwrap();
+        std::iter::from_fn(|| receiver.try_recv().ok())
+            .map(|event| PyTimeEvent { name: event.name, ts_event: event.ts_event, ts_init: event.ts_init })
+            .collect()
+    }
+}
+
+// Python wrapper for LiveClock
+#[pyclass(name = "LiveClock")]
+pub struct PyLiveClock {
+    inner: alphaforge_core::clock::LiveClock,
+    events: PyTimeEvents,
+    // Runs the clock's timers, as Python has no Tokio runtime of its own
+    runtime: tokio::runtime::Runtime,
+}
+
+#[pymethods]
+impl PyLiveClock {
+    #[new]
+    fn new() -> PyResult<Self> {
+        let runtime = tokio::runtime::Builder::new_multi_thread()
+            .worker_threads(1)
+            .enable_all()
+            .build()
+            .map_err(|e| PyRuntimeError::new_err(format!("Failed to create runtime: {}", e)))?;
+        let inner = alphaforge_core::clock::LiveClock::new();
+        Ok(Self { inner, events: PyTimeEvents::new(false), runtime })
+    }
+    
+    fn timestamp_ns(&self) -> u64 {
+        use alphaforge_core::clock::Clock;
+        self.inner.timestamp_ns()
+    }
+    
+    /// Fire every `interval_ns` from `start_time_ns`, by default one
+    /// interval from now, until `stop_time_ns`, calling `callback` on the
+    /// clock's thread, where exceptions it raises are logged; without one,
+    /// events are collected with `drain_events`
+    #[pyo3(signature = (name, interval_ns, callback=None, start_time_ns=None, stop_time_ns=None))]
+    fn set_timer(&self, name: String, interval_ns: u64, callback: Option<PyObject>, start_time_ns: Option<u64>, stop_time_ns: Option<u64>) -> PyResult<()> {
+        use alphaforge_core::clock::Clock;
+        let start_time_ns = start_time_ns.unwrap_or_else(|| self.inner.timestamp_ns() + interval_ns);
+        // The first timer starts the clock's timer task on the runtime
+        let _guard = self.runtime.enter();
+        self.inner
+            .set_timer(name, interval_ns, start_time_ns, stop_time_ns, self.events.sender(callback))
+            .map_err(errors::to_py_err)
+    }
+    
+    fn cancel_timer(&self, name: String) -> PyResult<()> {
+        use alphaforge_core::clock::Clock;
+        self.inner.cancel_timer(name).map_err(errors::to_py_err)
+    }
+    
+    /// Time events fired since the last call, oldest first
+    fn drain_events(&self) -> Vec<PyTimeEvent> {
+        self.events.drain()
+    }
+}
+
+// Python wrapper for TestClock
+#[pyclass(name = "TestClock")]
+pub struct PyTestClock {
+    inner: alphaforge_core::clock::TestClock,
+    events: PyTimeEvents,
+}
+
+#[pymethods]
+impl PyTestClock {
+    #[new]
+    #[pyo3(signature = (start_time_ns=0))]
+    fn new(start_time_ns: u64) -> Self {
+        Self {
+            inner: alphaforge_core::clock::TestClock::new(start_time_ns),
+            events: PyTimeEvents::new(true),
+        }
+    }
+    
+    fn timestamp_ns(&self) -> u64 {
+        use alphaforge_core::clock::Clock;
+        self.inner.timestamp_ns()
+    }
+    
+    /// Fire every `interval_ns` from `start_time_ns`, by default one
+    /// interval from now, until `stop_time_ns`, calling `callback` as time
+    /// advances past each firing, which then raises the first exception it
+    /// raised; without one, events are collected with `drain_events`
+    #[pyo3(signature = (name, interval_ns, callback=None, start_time_ns=None, stop_time_ns=None))]
+    fn set_timer(&self, name: String, interval_ns: u64, callback: Option<PyObject>, start_time_ns: Option<u64>, stop_time_ns: Option<u64>) -> PyResult<()> {
+        use alphaforge_core::clock::Clock;
+        let start_time_ns = start_time_ns.unwrap_or_else(|| self.inner.timestamp_ns() + interval_ns);
+        self.inner
+            .set_timer(name, interval_ns, start_time_ns, stop_time_ns, self.events.sender(callback))
+            .map_err(errors::to_py_err)
+    }
+    
+    fn cancel_timer(&self, name: String) -> PyResult<()> {
+        use alphaforge_core::clock::Clock;
+        self.inner.cancel_timer(name).map_err(errors::to_py_err)
+    }
+    
+    fn next_timer_ns(&self) -> Option<u64> {
+        use alphaforge_core::clock::Clock;
+        self.inner.next_timer_ns()
+    }
+    
+    /// Advance time by `duration_ns`, firing due timers; returns how many fired
+    fn advance_time(&self, duration_ns: u64) -> PyResult<usize> {
+        let fired = self.inner.advance_time(duration_ns);
+        self.events.raise_callback_error()?;
+        Ok(fired)
+    }
+    
+    /// Advance time to `target_ns`, firing due timers; returns how many fired
+    fn advance_to(&self, target_ns: u64) -> PyResult<usize> {
+        let fired = self.inner.advance_to(target_ns);
+        self.events.raise_callback_error()?;
+        Ok(fired)
+    }
+    
+    fn set_time(&self, timestamp_ns: u64) {
+        self.inner.set_time(timestamp_ns);
+    }
+    
+    /// Time events fired since the last call, in firing order
+    fn drain_events(&self) -> Vec<PyTimeEvent> {
+        self.events.drain()
+    }
+}
 
 // Python wrapper for MessageBus
 #[pyclass(name = "MessageBus")]
@@ -515,6 +865,16 @@ impl PyMessageEnvelope {
     fn payload(&self) -> Vec<u8> {
         self.inner.payload.clone()
     }
+
+    #[getter]
+    fn codec(&self) -> &'static str {
+        self.inner.codec.as_str()
+    }
+
+    #[getter]
+    fn schema_version(&self) -> Option<u32> {
+        self.inner.schema_version
+    }
 }
 
 // Cache Statistics wrapper for Python
@@ -572,27 +932,79 @@ pub struct PyCacheConfig {
     pub enable_persistence: bool,
     #[pyo3(get, set)]
     pub persistence_path: Option<String>,
+    #[pyo3(get, set)]
+    pub flush_interval_seconds: Option<u64>,
+    #[pyo3(get, set)]
+    pub sweep_interval_seconds: Option<u64>,
+    /// Keep entries in one concurrent map, for read-heavy caches
+    #[pyo3(get, set)]
+    pub concurrent: bool,
+    /// Compress values whose pickled size reaches this many bytes
+    #[pyo3(get, set)]
+    pub compression_threshold: Option<usize>,
+    pub eviction_policy: alphaforge_core::cache::EvictionPolicy,
+}
+
+fn parse_eviction_policy(policy: &str) -> PyResult<alphaforge_core::cache::EvictionPolicy> {
+    use alphaforge_core::cache::EvictionPolicy;
+    match policy.to_ascii_lowercase().as_str() {
+        "lru" => Ok(EvictionPolicy::LRU),
+        "fifo" => Ok(EvictionPolicy::FIFO),
+        "lfu" => Ok(EvictionPolicy::LFU),
+        _ => Err(pyo3::exceptions::PyValueError::new_err(format!(
+            "Invalid eviction policy: {} (expected lru, fifo or lfu)",
+            policy
+        ))),
+    }
 }
 
 #[pymethods]
 impl PyCacheConfig {
     #[new]
-    #[pyo3(signature = (max_size=10000, ttl_seconds=None, enable_statistics=true, enable_persistence=false, persistence_path=None))]
+    #[pyo3(signature = (max_size=10000, ttl_seconds=None, enable_statistics=true, enable_persistence=false, persistence_path=None, flush_interval_seconds=None, sweep_interval_seconds=None, concurrent=false, compression_threshold=None, eviction_policy="lru"))]
+    #[allow(clippy::too_many_arguments)]
     fn new(
         max_size: usize,
         ttl_seconds: Option<u64>,
         enable_statistics: bool,
         enable_persistence: bool,
         persistence_path: Option<String>,
-    ) -> Self {
-        PyCacheConfig {
+        flush_interval_seconds: Option<u64>,
+        sweep_interval_seconds: Option<u64>,
+        concurrent: bool,
+        compression_threshold: Option<usize>,
+        eviction_policy: &str,
+    ) -> PyResult<Self> {
+        Ok(PyCacheConfig {
             max_size,
             ttl_seconds,
             enable_statistics,
             enable_persistence,
             persistence_path,
+            flush_interval_seconds,
+            sweep_interval_seconds,
+            concurrent,
+            compression_threshold,
+            eviction_policy: parse_eviction_policy(eviction_policy)?,
+        })
+    }
+
+    /// Order entries are evicted in: "lru", "fifo" or "lfu"
+    #[getter]
+    fn eviction_policy(&self) -> &'static str {
+        use alphaforge_core::cache::EvictionPolicy;
+        match self.eviction_policy {
+            EvictionPolicy::LRU => "lru",
+            EvictionPolicy::FIFO => "fifo",
+            EvictionPolicy::LFU => "lfu",
         }
     }
+
+    #[setter]
+    fn set_eviction_policy(&mut self, policy: &str) -> PyResult<()> {
+        self.eviction_policy = parse_eviction_policy(policy)?;
+        Ok(())
+    }
 }
 
 impl From<PyCacheConfig> for generic_cache::GenericCacheConfig {
@@ -601,86 +1013,136 @@ impl From<PyCacheConfig> for generic_cache::GenericCacheConfig {
             max_size: config.max_size,
             ttl_seconds: config.ttl_seconds,
             enable_statistics: config.enable_statistics,
+            persistence_path: config.persistence_path.filter(|_| config.enable_persistence).map(Into::into),
+            flush_interval_seconds: config.flush_interval_seconds,
+            sweep_interval_seconds: config.sweep_interval_seconds,
+            concurrency: if config.concurrent {
+                generic_cache::CacheConcurrency::Concurrent
+            } else {
+                generic_cache::CacheConcurrency::Locked
+            },
+            compression_threshold: config.compression_threshold,
+            eviction_policy: config.eviction_policy,
+            ..Default::default()
         }
     }
 }
 
-// High-performance Cache wrapper for Python using real Rust implementation
+// High-performance Cache wrapper for Python using real Rust implementation.
+//
+// The cache's flusher takes the GIL to clone and pickle values while it
+// holds the cache's locks, so every call into the cache releases the GIL
+// first rather than waiting for a lock while holding it.
 #[pyclass(name = "Cache")]
 pub struct PyCache {
-    cache: generic_cache::GenericCache<PyObjectWrapper>,
+    /// Taken only when dropped
+    cache: Option<generic_cache::GenericCache<PyObjectWrapper>>,
+}
+
+impl PyCache {
+    fn cache(&self) -> &generic_cache::GenericCache<PyObjectWrapper> {
+        self.cache.as_ref().expect("cache is present until dropped")
+    }
 }
 
 #[pymethods]
 impl PyCache {
+    /// Create a cache, loading the entries saved at `persistence_path` when
+    /// persistence is enabled
     #[new]
-    fn new(config: PyCacheConfig) -> Self {
+    fn new(py: Python, config: PyCacheConfig) -> PyResult<Self> {
         let rust_config = generic_cache::GenericCacheConfig::from(config);
-        PyCache {
-            cache: generic_cache::GenericCache::new(rust_config),
-        }
+        let cache = py
+            .allow_threads(|| generic_cache::GenericCache::open(rust_config))
+            .map_err(errors::to_py_err)?
+            .with_mem_size();
+        Ok(PyCache { cache: Some(cache) })
     }
 
     /// Get value from cache
-    fn get(&self, py: Python, key: &str) -> PyResult<Option<PyObject>> {
-        match self.cache.get(key) {
-            Some(wrapper) => Ok(Some(wrapper.0.clone_ref(py))),
-            None => Ok(None),
-        }
+    fn get(&self, py: Python, key: &str) -> Option<PyObject> {
+        py.allow_threads(|| self.cache().get(key)).map(PyObject::from)
     }
 
     /// Put value into cache
-    fn put(&self, key: &str, value: PyObject) -> bool {
-        self.cache.put(key.to_string(), PyObjectWrapper::from(value))
+    fn put(&self, py: Python, key: &str, value: PyObject) -> bool {
+        py.allow_threads(|| self.cache().put(key.to_string(), PyObjectWrapper::from(value)))
+    }
+
+    /// Put value into cache, expiring after ttl_seconds rather than the
+    /// configured TTL, or never when None
+    #[pyo3(signature = (key, value, ttl_seconds=None))]
+    fn put_with_ttl(&self, py: Python, key: &str, value: PyObject, ttl_seconds: Option<u64>) -> bool {
+        py.allow_threads(|| self.cache().put_with_ttl(key.to_string(), PyObjectWrapper::from(value), ttl_seconds))
     }
 
     /// Check if key exists in cache
-    fn contains(&self, key: &str) -> bool {
-        self.cache.contains(key)
+    fn contains(&self, py: Python, key: &str) -> bool {
+        py.allow_threads(|| self.cache().contains(key))
     }
 
     /// Remove key from cache
-    fn remove(&self, key: &str) -> bool {
-        self.cache.remove(key)
+    fn remove(&self, py: Python, key: &str) -> bool {
+        py.allow_threads(|| self.cache().remove(key))
     }
 
     /// Clear all entries from cache
-    fn clear(&self) {
-        self.cache.clear()
+    fn clear(&self, py: Python) {
+        py.allow_threads(|| self.cache().clear())
     }
 
     /// Get current cache size
-    fn size(&self) -> usize {
-        self.cache.size()
+    fn size(&self, py: Python) -> usize {
+        py.allow_threads(|| self.cache().size())
     }
 
     /// Get all keys in cache
-    fn keys(&self) -> Vec<String> {
-        self.cache.keys()
+    fn keys(&self, py: Python) -> Vec<String> {
+        py.allow_threads(|| self.cache().keys())
+    }
+
+    /// Get (key, value) pairs whose keys start with prefix, sorted by key
+    fn scan(&self, py: Python, prefix: &str) -> Vec<(String, PyObject)> {
+        py.allow_threads(|| self.cache().scan(prefix))
+            .into_iter()
+            .map(|(key, wrapper)| (key, PyObject::from(wrapper)))
+            .collect()
     }
 
     /// Get cache statistics
-    fn statistics(&self) -> Option<PyCacheStatistics> {
-        self.cache.statistics().map(PyCacheStatistics::from)
+    fn statistics(&self, py: Python) -> Option<PyCacheStatistics> {
+        py.allow_threads(|| self.cache().statistics()).map(PyCacheStatistics::from)
     }
 
     /// Reset cache statistics
-    fn reset_statistics(&self) {
-        self.cache.reset_statistics()
+    fn reset_statistics(&self, py: Python) {
+        py.allow_threads(|| self.cache().reset_statistics())
     }
 
-    /// Save cache to disk if persistence is enabled
-    fn save_to_disk(&self) -> bool {
-        // For now, return true as if saved (persistence can be implemented later)
-        true
+    /// Save cache to disk if persistence is enabled, returning whether it was
+    fn save_to_disk(&self, py: Python) -> PyResult<bool> {
+        match py.allow_threads(|| self.cache().save_to_disk()) {
+            Ok(_) => Ok(true),
+            Err(alphaforge_core::AlphaForgeError::InvalidConfiguration { .. }) => Ok(false),
+            Err(e) => Err(errors::to_py_err(e)),
+        }
     }
 
     // Python dict-like interface
-    fn __len__(&self) -> usize {
-        self.size()
+    fn __len__(&self, py: Python) -> usize {
+        self.size(py)
     }
 
-    fn __contains__(&self, key: &str) -> bool {
-        self.contains(key)
+    fn __contains__(&self, py: Python, key: &str) -> bool {
+        self.contains(py, key)
+    }
+}
+
+impl Drop for PyCache {
+    // Dropping saves unsaved changes, which waits for the flusher
+    fn drop(&mut self) {
+        if let Some(cache) = self.cache.take() {
+            Python::with_gil(|py| py.allow_threads(move || drop(cache)));
+        }
     }
 }
@@ -1,7 +1,23 @@
 use pyo3::prelude::*;
-use pyo3::exceptions::{PyRuntimeError, PyValueError};
+use pyo3::exceptions::PyValueError;
+use pyo3::types::PyTuple;
+use std::cell::Cell;
 use std::collections::HashMap;
+use std::ptr::NonNull;
+use std::rc::Rc;
 use std::str::FromStr;
+use std::sync::{Arc, Mutex};
+
+use alphaforge_core::data::{Bar, QuoteTick, TradeTick};
+use alphaforge_core::data_engine::{DataEngine, DataEngineConfig};
+use alphaforge_core::execution_engine::Fill;
+use alphaforge_core::identifiers::{InstrumentId, OrderId, StrategyId};
+use alphaforge_core::strategy_engine::{Strategy, StrategyContext, StrategyEngine};
+use alphaforge_core::AlphaForgeError;
+
+use crate::data_engine::{PyBar, PyQuoteTick, PyTradeTick};
+use crate::errors::to_py_err;
+use crate::execution_engine::PyFill;
 
 // ============================================================================
 // STRATEGY ENGINE PYTHON WRAPPERS
@@ -54,6 +70,7 @@ impl PyStrategyState {
             alphaforge_core::strategy_engine::StrategyState::Paused => "Paused".to_string(),
             alphaforge_core::strategy_engine::StrategyState::Stopped => "Stopped".to_string(),
             alphaforge_core::strategy_engine::StrategyState::Error => "Error".to_string(),
+            alphaforge_core::strategy_engine::StrategyState::RiskBreached => "RiskBreached".to_string(),
         }
     }
 
@@ -81,19 +98,20 @@ impl PyStrategyConfig {
         name,
         instruments = vec![],
         max_position_size = 1000.0,
-        max_daily_loss = 10000.0,
-        max_drawdown = 0.05,
+        max_daily_loss = None,
+        max_drawdown = None,
         enable_logging = true,
         enable_metrics = true,
         enable_backtesting = false
     ))]
+    #[allow(clippy::too_many_arguments)]
     fn new(
         strategy_id: PyStrategyId,
         name: String,
         instruments: Vec<String>,
         max_position_size: f64,
-        max_daily_loss: f64,
-        max_drawdown: f64,
+        max_daily_loss: Option<f64>,
+        max_drawdown: Option<f64>,
         enable_logging: bool,
         enable_metrics: bool,
         enable_backtesting: bool,
@@ -119,10 +137,20 @@ impl PyStrategyConfig {
                 enable_logging,
                 enable_metrics,
                 enable_backtesting,
+                ..Default::default()
             },
         })
     }
 
+    /// The strategies of a TOML or YAML node config file, with
+    /// `ALPHAFORGE__` environment overrides applied
+    #[staticmethod]
+    fn from_file(path: &str) -> PyResult<Vec<Self>> {
+        let config = alphaforge_core::config::NodeConfig::from_file(path)
+            .map_err(to_py_err)?;
+        Ok(config.strategies.into_iter().map(|inner| Self { inner }).collect())
+    }
+
     #[getter]
     fn strategy_id(&self) -> PyStrategyId {
         PyStrategyId { inner: self.inner.strategy_id }
@@ -144,12 +172,12 @@ impl PyStrategyConfig {
     }
 
     #[getter]
-    fn max_daily_loss(&self) -> f64 {
+    fn max_daily_loss(&self) -> Option<f64> {
         self.inner.max_daily_loss
     }
 
     #[getter]
-    fn max_drawdown(&self) -> f64 {
+    fn max_drawdown(&self) -> Option<f64> {
         self.inner.max_drawdown
     }
 
@@ -167,6 +195,82 @@ impl PyStrategyConfig {
     fn enable_backtesting(&self) -> bool {
         self.inner.enable_backtesting
     }
+
+    /// Instrument the strategy's returns are compared against, or None
+    #[getter]
+    fn benchmark(&self) -> Option<String> {
+        self.inner.benchmark.map(|id| id.to_string())
+    }
+
+    #[setter]
+    fn set_benchmark(&mut self, benchmark: Option<&str>) -> PyResult<()> {
+        self.inner.benchmark = benchmark
+            .map(alphaforge_core::identifiers::InstrumentId::from_str)
+            .transpose()
+            .map_err(|e| PyValueError::new_err(format!("Invalid instrument ID: {}", e)))?;
+        Ok(())
+    }
+
+    /// Define a strategy parameter (int, float, bool, str or timedelta),
+    /// optionally bounded; timedelta bounds are in seconds
+    #[pyo3(signature = (name, value, min = None, max = None))]
+    fn define_parameter(&mut self, name: &str, value: &Bound<'_, PyAny>, min: Option<f64>, max: Option<f64>) -> PyResult<()> {
+        let value = parameter_from_py(value)?;
+        let parameters = self.inner.parameters.clone();
+        self.inner.parameters = if min.is_some() || max.is_some() {
+            parameters.with_range(name, value, min, max)
+        } else {
+            parameters.with(name, value)
+        }
+        .map_err(PyValueError::new_err)?;
+        Ok(())
+    }
+
+    /// Change a defined parameter
+    fn set_parameter(&mut self, name: &str, value: &Bound<'_, PyAny>) -> PyResult<()> {
+        let value = parameter_from_py(value)?;
+        self.inner.parameters.set(name, value).map_err(PyValueError::new_err)?;
+        Ok(())
+    }
+
+    fn get_parameter(&self, py: Python<'_>, name: &str) -> PyResult<PyObject> {
+        use alphaforge_core::parameters::ParameterValue;
+
+        let value = self.inner.parameters
+            .get(name)
+            .ok_or_else(|| PyValueError::new_err(format!("Unknown parameter: {}", name)))?;
+        Ok(match value {
+            ParameterValue::Int(value) => value.into_py(py),
+            ParameterValue::Float(value) => value.into_py(py),
+            ParameterValue::Bool(value) => value.into_py(py),
+            ParameterValue::String(value) => value.into_py(py),
+            ParameterValue::Duration(value) => value.into_py(py),
+        })
+    }
+
+    #[getter]
+    fn parameter_names(&self) -> Vec<String> {
+        self.inner.parameters.names().map(str::to_string).collect()
+    }
+}
+
+/// Convert a Python value to a parameter value (bool is checked before int)
+fn parameter_from_py(value: &Bound<'_, PyAny>) -> PyResult<alphaforge_core::parameters::ParameterValue> {
+    use alphaforge_core::parameters::ParameterValue;
+
+    if let Ok(value) = value.downcast::<pyo3::types::PyBool>() {
+        Ok(ParameterValue::Bool(value.is_true()))
+    } else if let Ok(value) = value.extract::<i64>() {
+        Ok(ParameterValue::Int(value))
+    } else if let Ok(value) = value.extract::<f64>() {
+        Ok(ParameterValue::Float(value))
+    } else if let Ok(value) = value.extract::<String>() {
+        Ok(ParameterValue::String(value))
+    } else if let Ok(value) = value.extract::<std::time::Duration>() {
+        Ok(ParameterValue::Duration(value))
+    } else {
+        Err(PyValueError::new_err(format!("Unsupported parameter type: {}", value.get_type())))
+    }
 }
 
 /// Python wrapper for StrategyMetrics
@@ -228,6 +332,31 @@ impl PyStrategyMetrics {
         self.inner.sharpe_ratio
     }
 
+    #[getter]
+    fn sortino_ratio(&self) -> f64 {
+        self.inner.sortino_ratio
+    }
+
+    #[getter]
+    fn alpha(&self) -> f64 {
+        self.inner.alpha
+    }
+
+    #[getter]
+    fn beta(&self) -> f64 {
+        self.inner.beta
+    }
+
+    #[getter]
+    fn information_ratio(&self) -> f64 {
+        self.inner.information_ratio
+    }
+
+    #[getter]
+    fn max_relative_drawdown(&self) -> f64 {
+        self.inner.max_relative_drawdown
+    }
+
     #[getter]
     fn open_positions(&self) -> HashMap<String, f64> {
         self.inner
@@ -304,140 +433,372 @@ impl PyStrategy {
     }
 
     /// Override this method in your strategy
-    fn on_start(&mut self, _py: Python) -> PyResult<()> {
+    fn on_start(&mut self, _py: Python, _context: &Bound<'_, PyStrategyContext>) -> PyResult<()> {
         // Default implementation - override in Python
         Ok(())
     }
 
     /// Override this method in your strategy
-    fn on_trade_tick(&mut self, _py: Python, _tick: &crate::data_engine::PyTradeTick) -> PyResult<()> {
+    fn on_trade_tick(&mut self, _py: Python, _context: &Bound<'_, PyStrategyContext>, _tick: &crate::data_engine::PyTradeTick) -> PyResult<()> {
         // Default implementation - override in Python
         Ok(())
     }
 
     /// Override this method in your strategy
-    fn on_quote_tick(&mut self, _py: Python, _tick: &crate::data_engine::PyQuoteTick) -> PyResult<()> {
+    fn on_quote_tick(&mut self, _py: Python, _context: &Bound<'_, PyStrategyContext>, _tick: &crate::data_engine::PyQuoteTick) -> PyResult<()> {
         // Default implementation - override in Python
         Ok(())
     }
 
     /// Override this method in your strategy
-    fn on_bar(&mut self, _py: Python, _bar: &crate::data_engine::PyBar) -> PyResult<()> {
+    fn on_bar(&mut self, _py: Python, _context: &Bound<'_, PyStrategyContext>, _bar: &crate::data_engine::PyBar) -> PyResult<()> {
         // Default implementation - override in Python
         Ok(())
     }
 
     /// Override this method in your strategy
-    fn on_timer(&mut self, _py: Python) -> PyResult<()> {
+    fn on_timer(&mut self, _py: Python, _context: &Bound<'_, PyStrategyContext>, _name: String) -> PyResult<()> {
         // Default implementation - override in Python
         Ok(())
     }
 
     /// Override this method in your strategy
-    fn on_stop(&mut self, _py: Python) -> PyResult<()> {
+    fn on_fill(&mut self, _py: Python, _context: &Bound<'_, PyStrategyContext>, _fill: &crate::execution_engine::PyFill) -> PyResult<()> {
+        // Default implementation - override in Python
+        Ok(())
+    }
+
+    /// Override this method in your strategy
+    fn on_stop(&mut self, _py: Python, _context: &Bound<'_, PyStrategyContext>) -> PyResult<()> {
         // Default implementation - override in Python
         Ok(())
     }
 }
 
-/// Python wrapper for StrategyContext
-#[pyclass(name = "StrategyContext")]
+/// The context of the strategy a callback runs for, passed as the first
+/// argument of every `Strategy` callback. It is only valid until the
+/// callback returns.
+#[pyclass(name = "StrategyContext", unsendable)]
 pub struct PyStrategyContext {
-    // We'll store minimal data here and access the real context through the engine
-    strategy_id: PyStrategyId,
-    state: PyStrategyState,
+    context: Rc<Cell<Option<NonNull<StrategyContext>>>>,
+}
+
+impl PyStrategyContext {
+    fn with<T>(&self, f: impl FnOnce(&mut StrategyContext) -> T) -> PyResult<T> {
+        let mut context = self.context
+            .get()
+            .ok_or_else(|| to_py_err(AlphaForgeError::runtime("Strategy context used after its callback returned")))?;
+        // SAFETY: the pointer is only set while `PythonStrategy::call` holds
+        // the context's exclusive borrow and waits for Python on this thread
+        // (the class is unsendable), so nothing else can reach the context
+        Ok(f(unsafe { context.as_mut() }))
+    }
+
+    fn try_with<T>(&self, f: impl FnOnce(&mut StrategyContext) -> Result<T, String>) -> PyResult<T> {
+        self.with(f)?.map_err(|e| to_py_err(AlphaForgeError::runtime(e)))
+    }
+}
+
+fn parse_instrument_id(instrument_id: &str) -> PyResult<InstrumentId> {
+    InstrumentId::from_str(instrument_id).map_err(|e| PyValueError::new_err(format!("Invalid instrument ID: {}", e)))
 }
 
 #[pymethods]
 impl PyStrategyContext {
     #[getter]
-    fn strategy_id(&self) -> PyStrategyId {
-        self.strategy_id.clone()
+    fn strategy_id(&self) -> PyResult<PyStrategyId> {
+        self.with(|context| PyStrategyId { inner: context.config.strategy_id })
     }
 
     #[getter]
-    fn state(&self) -> PyStrategyState {
-        self.state.clone()
+    fn state(&self) -> PyResult<PyStrategyState> {
+        self.with(|context| PyStrategyState { inner: context.state })
+    }
+
+    #[getter]
+    fn metrics(&self) -> PyResult<PyStrategyMetrics> {
+        self.with(|context| PyStrategyMetrics { inner: context.metrics.clone() })
     }
 
     /// Check if strategy is active
-    fn is_active(&self) -> bool {
-        matches!(
-            self.state.inner,
-            alphaforge_core::strategy_engine::StrategyState::Running
-        )
+    fn is_active(&self) -> PyResult<bool> {
+        self.with(|context| context.is_active())
+    }
+
+    /// Current timestamp in nanoseconds from the strategy's clock
+    fn current_time_ns(&self) -> PyResult<u64> {
+        self.with(|context| context.current_time_ns())
+    }
+
+    /// Open position in an instrument, negative when short
+    fn position(&self, instrument_id: &str) -> PyResult<f64> {
+        let instrument_id = parse_instrument_id(instrument_id)?;
+        self.with(|context| context.metrics.open_positions.get(&instrument_id).copied().unwrap_or(0.0))
+    }
+
+    /// Buy at market, returning the order ID
+    fn buy_market(&self, instrument_id: &str, quantity: f64) -> PyResult<u64> {
+        let instrument_id = parse_instrument_id(instrument_id)?;
+        self.try_with(|context| context.buy_market(instrument_id, quantity)).map(|order_id| order_id.id)
+    }
+
+    /// Sell at market, returning the order ID
+    fn sell_market(&self, instrument_id: &str, quantity: f64) -> PyResult<u64> {
+        let instrument_id = parse_instrument_id(instrument_id)?;
+        self.try_with(|context| context.sell_market(instrument_id, quantity)).map(|order_id| order_id.id)
     }
 
-    /// Get current timestamp in nanoseconds
-    fn current_time_ns(&self) -> u64 {
-        use std::time::{SystemTime, UNIX_EPOCH};
-        SystemTime::now()
-            .duration_since(UNIX_EPOCH)
-            .expect("Time went backwards")
-            .as_nanos() as u64
+    /// Buy with a limit price, returning the order ID
+    fn buy_limit(&self, instrument_id: &str, quantity: f64, price: f64) -> PyResult<u64> {
+        let instrument_id = parse_instrument_id(instrument_id)?;
+        self.try_with(|context| context.buy_limit(instrument_id, quantity, price)).map(|order_id| order_id.id)
+    }
+
+    /// Sell with a limit price, returning the order ID
+    fn sell_limit(&self, instrument_id: &str, quantity: f64, price: f64) -> PyResult<u64> {
+        let instrument_id = parse_instrument_id(instrument_id)?;
+        self.try_with(|context| context.sell_limit(instrument_id, quantity, price)).map(|order_id| order_id.id)
+    }
+
+    /// Cancel an active order
+    fn cancel_order(&self, order_id: u64) -> PyResult<()> {
+        self.try_with(|context| context.cancel_order(OrderId::from_u64(order_id)))
+    }
+
+    /// Flatten the position in an instrument with a market order, returning
+    /// its ID, or None when there is no position
+    fn close_position(&self, instrument_id: &str) -> PyResult<Option<u64>> {
+        let instrument_id = parse_instrument_id(instrument_id)?;
+        self.try_with(|context| context.close_position(instrument_id))
+            .map(|order_id| order_id.map(|order_id| order_id.id))
+    }
+
+    /// Call `on_timer` with `name` every `interval_ns`
+    fn set_timer(&self, name: &str, interval_ns: u64) -> PyResult<()> {
+        self.try_with(|context| context.set_timer(name, interval_ns))
+    }
+
+    fn cancel_timer(&self, name: &str) -> PyResult<()> {
+        self.try_with(|context| context.cancel_timer(name))
+    }
+
+    /// Publish a named signal for other strategies and components
+    fn publish_signal(&self, name: &str, value: f64) -> PyResult<()> {
+        self.try_with(|context| context.publish_signal(name, value))
+    }
+
+    /// Log a message at `level` ("trace" to "error"), subject to the
+    /// strategy's log level
+    fn log(&self, level: &str, message: &str) -> PyResult<()> {
+        let level = level.parse().map_err(PyValueError::new_err)?;
+        self.with(|context| context.log(level, message))
+    }
+}
+
+/// Clears the `PyStrategyContext` of a callback when the callback returns
+struct ContextScope(Rc<Cell<Option<NonNull<StrategyContext>>>>);
+
+impl Drop for ContextScope {
+    fn drop(&mut self) {
+        self.0.set(None);
+    }
+}
+
+/// Runs a Python `Strategy` subclass as a Rust strategy, calling its
+/// callbacks through the GIL with the strategy's context. An exception raised by a callback moves the
+/// strategy to the `Error` state like any other strategy failure.
+struct PythonStrategy {
+    object: Py<PyAny>,
+    name: String,
+    version: String,
+}
+
+impl PythonStrategy {
+    fn new(py: Python<'_>, object: Py<PyAny>) -> PyResult<Self> {
+        let name = object.getattr(py, "name")?.extract(py)?;
+        let version = object.getattr(py, "version")?.extract(py)?;
+        Ok(Self { object, name, version })
+    }
+
+    fn call<A: IntoPy<Py<PyTuple>>>(
+        &self,
+        context: &mut StrategyContext,
+        method: &str,
+        args: impl FnOnce(Py<PyStrategyContext>) -> A,
+    ) -> Result<(), String> {
+        Python::with_gil(|py| {
+            let scope = ContextScope(Rc::new(Cell::new(Some(NonNull::from(context)))));
+            let result = Py::new(py, PyStrategyContext { context: Rc::clone(&scope.0) })
+                .and_then(|context| self.object.call_method1(py, method, args(context)));
+            result
+                .map(|_| ())
+                .map_err(|e| format!("{}.{} raised {}", self.name, method, e))
+        })
+    }
+}
+
+impl Strategy for PythonStrategy {
+    fn on_start(&mut self, context: &mut StrategyContext) -> Result<(), String> {
+        self.call(context, "on_start", |context| (context,))
+    }
+
+    fn on_trade_tick(&mut self, context: &mut StrategyContext, tick: &TradeTick) -> Result<(), String> {
+        self.call(context, "on_trade_tick", |context| (context, PyTradeTick { inner: tick.clone() }))
+    }
+
+    fn on_quote_tick(&mut self, context: &mut StrategyContext, tick: &QuoteTick) -> Result<(), String> {
+        self.call(context, "on_quote_tick", |context| (context, PyQuoteTick { inner: tick.clone() }))
+    }
+
+    fn on_bar(&mut self, context: &mut StrategyContext, bar: &Bar) -> Result<(), String> {
+        self.call(context, "on_bar", |context| (context, PyBar { inner: bar.clone() }))
+    }
+
+    fn on_timer(&mut self, context: &mut StrategyContext, name: &str) -> Result<(), String> {
+        self.call(context, "on_timer", |context| (context, name))
+    }
+
+    fn on_fill(&mut self, context: &mut StrategyContext, fill: &Fill) -> Result<(), String> {
+        self.call(context, "on_fill", |context| (context, PyFill { inner: fill.clone() }))
+    }
+
+    fn on_stop(&mut self, context: &mut StrategyContext) -> Result<(), String> {
+        self.call(context, "on_stop", |context| (context,))
+    }
+
+    fn name(&self) -> &str {
+        &self.name
+    }
+
+    fn version(&self) -> &str {
+        &self.version
     }
 }
 
-/// Simple strategy engine wrapper for Python
+/// Strategy engine running Python strategies on the Rust event loop
 #[pyclass(name = "StrategyEngine")]
 pub struct PyStrategyEngine {
-    // We'll keep a simplified version for now - just track strategy metadata
+    inner: StrategyEngine,
     strategy_configs: HashMap<u64, PyStrategyConfig>,
-    is_running: bool,
+}
+
+impl PyStrategyEngine {
+    fn check(result: Result<(), String>) -> PyResult<()> {
+        result.map_err(|e| to_py_err(AlphaForgeError::runtime(e)))
+    }
 }
 
 #[pymethods]
 impl PyStrategyEngine {
     #[new]
     fn new() -> Self {
+        let data_engine = DataEngine::new(DataEngineConfig::default());
         Self {
+            inner: StrategyEngine::new(Arc::new(Mutex::new(data_engine))),
             strategy_configs: HashMap::new(),
-            is_running: false,
         }
     }
 
-    /// Add a strategy to the engine
-    fn add_strategy(&mut self, strategy_id: u64, config: PyStrategyConfig) -> PyResult<()> {
-        if self.strategy_configs.contains_key(&strategy_id) {
-            return Err(PyRuntimeError::new_err(format!(
-                "Strategy with ID {} already exists", 
-                strategy_id
+    /// Add a strategy to the engine.
+    ///
+    /// `strategy` is an instance of a `Strategy` subclass whose callbacks
+    /// receive the engine's events; without one the strategy only holds
+    /// its configuration.
+    #[pyo3(signature = (strategy_id, config, strategy = None))]
+    fn add_strategy(
+        &mut self,
+        py: Python<'_>,
+        strategy_id: u64,
+        config: PyStrategyConfig,
+        strategy: Option<Py<PyAny>>,
+    ) -> PyResult<()> {
+        if config.inner.strategy_id.id != strategy_id {
+            return Err(PyValueError::new_err(format!(
+                "Config is for strategy {}, not {}",
+                config.inner.strategy_id.id, strategy_id
             )));
         }
 
+        let strategy = match strategy {
+            Some(strategy) => strategy,
+            None => Py::new(py, PyStrategy::new(config.inner.name.clone(), None))?.into_any(),
+        };
+        let strategy = PythonStrategy::new(py, strategy)?;
+        Self::check(self.inner.add_strategy(Box::new(strategy), config.inner.clone()))?;
         self.strategy_configs.insert(strategy_id, config);
         Ok(())
     }
 
-    /// Start the strategy engine
-    fn start(&mut self) -> PyResult<()> {
-        if self.is_running {
-            return Err(PyRuntimeError::new_err("Strategy engine is already running"));
-        }
-
-        self.is_running = true;
+    /// Remove a strategy from a stopped engine, closing the subscriptions
+    /// in its namespace
+    fn remove_strategy(&mut self, strategy_id: u64) -> PyResult<()> {
+        Self::check(self.inner.remove_strategy(StrategyId::new(strategy_id)))?;
+        self.strategy_configs.remove(&strategy_id);
         Ok(())
     }
 
-    /// Stop the strategy engine
-    fn stop(&mut self) {
-        self.is_running = false;
+    /// Start the strategy engine, calling each strategy's `on_start`
+    fn start(&mut self) -> PyResult<()> {
+        Self::check(self.inner.start())
+    }
+
+    /// Stop the strategy engine, calling each strategy's `on_stop`
+    fn stop(&mut self) -> PyResult<()> {
+        Self::check(self.inner.stop())
     }
 
     /// Check if engine is running
     fn is_running(&self) -> bool {
-        self.is_running
+        self.inner.is_running()
     }
 
     /// Get total number of strategies
     fn total_strategies(&self) -> usize {
-        self.strategy_configs.len()
+        self.inner.total_strategies()
     }
 
     /// Get strategy config by ID
     fn get_strategy_config(&self, strategy_id: u64) -> Option<PyStrategyConfig> {
         self.strategy_configs.get(&strategy_id).cloned()
     }
+
+    /// Deliver a trade tick to the strategies trading its instrument
+    fn process_trade_tick(&mut self, tick: &PyTradeTick) -> PyResult<()> {
+        Self::check(self.inner.process_trade_tick(&tick.inner))
+    }
+
+    /// Deliver a quote tick to the strategies trading its instrument
+    fn process_quote_tick(&mut self, tick: &PyQuoteTick) -> PyResult<()> {
+        Self::check(self.inner.process_quote_tick(&tick.inner))
+    }
+
+    /// Deliver a bar to every strategy
+    fn process_bar(&mut self, bar: &PyBar) -> PyResult<()> {
+        Self::check(self.inner.process_bar(&bar.inner))
+    }
+
+    /// Current state of a strategy
+    fn strategy_state(&self, strategy_id: u64) -> Option<PyStrategyState> {
+        self.inner
+            .strategy_state(&StrategyId::new(strategy_id))
+            .map(|inner| PyStrategyState { inner })
+    }
+
+    fn get_strategy_metrics(&self, strategy_id: u64) -> Option<PyStrategyMetrics> {
+        self.inner
+            .get_strategy_metrics(&StrategyId::new(strategy_id))
+            .map(|inner| PyStrategyMetrics { inner })
+    }
+
+    /// Override a strategy's log level ("trace" to "error"), or clear the
+    /// override with None
+    #[pyo3(signature = (strategy_id, level = None))]
+    fn set_log_level(&mut self, strategy_id: u64, level: Option<&str>) -> PyResult<()> {
+        let level = level.map(str::parse).transpose().map_err(PyValueError::new_err)?;
+        self.inner
+            .set_log_level(StrategyId::new(strategy_id), level)
+            .map_err(PyValueError::new_err)
+    }
 }
 
 /// Register strategy engine module
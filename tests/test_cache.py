@@ -0,0 +1,51 @@
+# Test AlphaForge Cache
+"""
+Tests for the Rust-backed cache used from several Python threads.
+"""
+
+import os
+import tempfile
+import threading
+
+import pytest
+
+rust = pytest.importorskip("alphaforge_pyo3.alphaforge_pyo3")
+Cache = rust.cache.Cache
+CacheConfig = rust.cache.CacheConfig
+
+
+class TestCachePersistence:
+    """Test the cache while its flusher saves snapshots."""
+
+    def test_writes_while_flusher_runs(self):
+        """Test Python threads writing while the flusher pickles values do not deadlock."""
+        with tempfile.TemporaryDirectory() as directory:
+            path = os.path.join(directory, "cache.bin")
+            config = CacheConfig(
+                max_size=1_000,
+                enable_persistence=True,
+                persistence_path=path,
+                flush_interval_seconds=0,
+            )
+            cache = Cache(config)
+
+            def write(worker):
+                for i in range(2_000):
+                    key = f"{worker}_{i % 100}"
+                    cache.put(key, {"worker": worker, "values": list(range(50))})
+                    cache.get(key)
+                    if i % 10 == 0:
+                        cache.remove(key)
+                        cache.scan(f"{worker}_")
+
+            threads = [threading.Thread(target=write, args=(worker,), daemon=True) for worker in range(4)]
+            for thread in threads:
+                thread.start()
+            for thread in threads:
+                thread.join(timeout=30)
+            assert not any(thread.is_alive() for thread in threads)
+
+            assert cache.save_to_disk()
+            saved = len(cache)
+            del cache
+            assert len(Cache(config)) == saved
@@ -0,0 +1,105 @@
+# Test AlphaForge Clocks
+"""
+Tests for the live and test clocks and their timers.
+"""
+
+import time
+
+import pytest
+
+rust = pytest.importorskip("alphaforge_pyo3.alphaforge_pyo3")
+LiveClock = rust.time.LiveClock
+TestClock = rust.time.TestClock
+
+MS = 1_000_000
+
+
+def wait_for(condition, timeout=2.0):
+    deadline = time.monotonic() + timeout
+    while not condition():
+        if time.monotonic() > deadline:
+            return False
+        time.sleep(0.005)
+    return True
+
+
+class TestTestClock:
+    """Test timers of the test clock."""
+
+    def test_timer_events_drained_in_order(self):
+        """Test events fire as time advances and are drained in firing order."""
+        clock = TestClock(1_000)
+        clock.set_timer("fast", 100)
+        clock.set_timer("slow", 250, stop_time_ns=1_500)
+        assert clock.next_timer_ns() == 1_100
+
+        assert clock.advance_to(1_300) == 4
+        events = [(event.name, event.ts_event) for event in clock.drain_events()]
+        assert events == [("fast", 1_100), ("fast", 1_200), ("slow", 1_250), ("fast", 1_300)]
+        assert clock.drain_events() == []
+
+        clock.cancel_timer("fast")
+        assert clock.advance_time(500) == 1
+        assert [event.name for event in clock.drain_events()] == ["slow"]
+        assert clock.timestamp_ns() == 1_800
+
+    def test_callback_called_as_time_advances(self):
+        """Test a callback runs for each firing instead of queueing events."""
+        clock = TestClock()
+        calls = []
+        clock.set_timer("tick", 10, callback=lambda: calls.append(clock.timestamp_ns()))
+
+        clock.advance_time(30)
+        assert calls == [10, 20, 30]
+        assert clock.drain_events() == []
+
+    def test_callback_exception_raised_from_advance(self):
+        """Test an exception in a callback is raised by the advancing call."""
+        clock = TestClock()
+
+        def fail():
+            raise ValueError("bad timer")
+
+        clock.set_timer("failing", 10, callback=fail)
+        with pytest.raises(ValueError, match="bad timer"):
+            clock.advance_time(20)
+        assert clock.timestamp_ns() == 20
+
+        clock.cancel_timer("failing")
+        assert clock.advance_time(20) == 0
+
+
+class TestLiveClock:
+    """Test timers of the live clock."""
+
+    def test_timestamps_follow_wall_time(self):
+        """Test the clock reads the current time."""
+        clock = LiveClock()
+        first = clock.timestamp_ns()
+        assert abs(first - time.time_ns()) < 1_000 * MS
+        assert clock.timestamp_ns() >= first
+
+    def test_timer_events_drained(self):
+        """Test a timer without a callback queues its events."""
+        clock = LiveClock()
+        clock.set_timer("heartbeat", 5 * MS)
+
+        events = []
+        assert wait_for(lambda: events.extend(clock.drain_events()) or len(events) >= 2)
+        clock.cancel_timer("heartbeat")
+        assert all(event.name == "heartbeat" for event in events)
+        assert events[1].ts_event - events[0].ts_event == 5 * MS
+
+    def test_callback_exception_does_not_stop_timer(self):
+        """Test a timer keeps firing after its callback raised."""
+        clock = LiveClock()
+        calls = []
+
+        def flaky():
+            calls.append(None)
+            if len(calls) == 1:
+                raise ValueError("first call fails")
+
+        clock.set_timer("flaky", 5 * MS, callback=flaky)
+        assert wait_for(lambda: len(calls) >= 2)
+        clock.cancel_timer("flaky")
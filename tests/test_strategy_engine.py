@@ -0,0 +1,87 @@
+# Test AlphaForge Python Strategies
+"""
+Tests for Python strategies run by the Rust strategy engine.
+"""
+
+import pytest
+
+rust = pytest.importorskip("alphaforge_pyo3.alphaforge_pyo3")
+Strategy = rust.strategy.Strategy
+StrategyConfig = rust.strategy.StrategyConfig
+StrategyEngine = rust.strategy.StrategyEngine
+StrategyId = rust.strategy.StrategyId
+TradeTick = rust.data.TradeTick
+
+INSTRUMENT = "BTCUSD.BINANCE"
+
+
+class RecordingStrategy(Strategy):
+    """Records what its callbacks see through the context."""
+
+    def on_start(self, context):
+        self.events = [("start", context.strategy_id.id, context.state.name)]
+
+    def on_trade_tick(self, context, tick):
+        self.events.append(("tick", tick.price, context.position(INSTRUMENT), context.is_active()))
+        self.context = context
+        context.log("info", "tick received")
+        assert context.current_time_ns() > 0
+        with pytest.raises(RuntimeError, match="No execution engine connected"):
+            context.buy_market(INSTRUMENT, 1.0)
+
+    def on_stop(self, context):
+        self.events.append(("stop", context.metrics.total_trades))
+
+
+def make_engine(strategy):
+    engine = StrategyEngine()
+    config = StrategyConfig(StrategyId(7), "recorder", instruments=[INSTRUMENT])
+    engine.add_strategy(7, config, strategy)
+    return engine
+
+
+class TestPythonStrategy:
+    """Test callbacks of Python strategies."""
+
+    def test_callbacks_receive_context(self):
+        """Test callbacks read state and submit orders through the context."""
+        strategy = RecordingStrategy("recorder")
+        engine = make_engine(strategy)
+
+        engine.start()
+        engine.process_trade_tick(TradeTick(INSTRUMENT, 100.5, 2.0, 0, "T1", 1, 1))
+        engine.stop()
+
+        assert strategy.events == [
+            ("start", 7, "Running"),
+            ("tick", 100.5, 0.0, True),
+            ("stop", 0),
+        ]
+        assert engine.strategy_state(7).name == "Stopped"
+
+    def test_context_expires_with_callback(self):
+        """Test a context kept past its callback cannot reach the strategy."""
+        strategy = RecordingStrategy("recorder")
+        engine = make_engine(strategy)
+
+        engine.start()
+        engine.process_trade_tick(TradeTick(INSTRUMENT, 100.5, 2.0, 0, "T1", 1, 1))
+
+        with pytest.raises(RuntimeError, match="after its callback returned"):
+            strategy.context.buy_market(INSTRUMENT, 1.0)
+        engine.stop()
+
+    def test_callback_exception_stops_strategy(self):
+        """Test an exception in a callback moves the strategy to Error."""
+
+        class FailingStrategy(Strategy):
+            def on_trade_tick(self, context, tick):
+                raise ValueError("bad tick")
+
+        engine = make_engine(FailingStrategy("failing"))
+
+        engine.start()
+        engine.process_trade_tick(TradeTick(INSTRUMENT, 100.5, 2.0, 0, "T1", 1, 1))
+
+        assert engine.strategy_state(7).name == "Error"
+        engine.stop()